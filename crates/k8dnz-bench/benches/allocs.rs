@@ -0,0 +1,74 @@
+// crates/k8dnz-bench/benches/allocs.rs
+//
+// Heap allocation counts (a counting global allocator), for code paths whose point is
+// allocating less. Counts are exact and machine-independent; wall-clock for the same
+// paths is in the `wall` bench.
+//
+//   cargo bench -p k8dnz-bench --bench allocs
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use k8dnz_bench::MAX_TICKS;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+const EMISSIONS: u64 = 100_000;
+
+struct Counting;
+
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// (allocations, bytes requested) made by `f` alone; engine setup happens before the reset.
+fn count(f: impl FnOnce(&mut Engine) -> u8) -> (u64, u64, u8) {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    ALLOCS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    let acc = f(&mut e);
+    (
+        ALLOCS.load(Ordering::Relaxed),
+        BYTES.load(Ordering::Relaxed),
+        acc,
+    )
+}
+
+fn main() {
+    let collected = count(|e| {
+        e.run_emissions(EMISSIONS, MAX_TICKS)
+            .iter()
+            .fold(0u8, |acc, t| acc ^ t.pack_byte())
+    });
+    let callback = count(|e| {
+        let mut acc = 0u8;
+        e.run_with_emission_callback(EMISSIONS, MAX_TICKS, &mut |_, t| acc ^= t.pack_byte());
+        acc
+    });
+    assert_eq!(collected.2, callback.2, "both paths see the same tokens");
+
+    println!("engine_emission_sink n={EMISSIONS}");
+    for (name, (allocs, bytes, _)) in [("run_emissions", collected), ("callback", callback)] {
+        println!("  {name:<14} allocs={allocs:<6} bytes={bytes}");
+    }
+}
@@ -0,0 +1,72 @@
+// crates/k8dnz-bench/benches/instructions.rs
+//
+// Instruction-count benches (iai, runs under valgrind/cachegrind). Counts are stable
+// across runs, so these are the ones to compare between commits; inputs are smaller
+// than the wall-clock benches because cachegrind is ~50x slower than native.
+
+use iai::black_box;
+
+use k8dnz_bench::{binary_corpus, default_recipe_bytes, text_corpus, FitFixture, MAX_TICKS};
+use k8dnz_cli::cmd::timemap::args::MapMode;
+use k8dnz_cli::cmd::timemap::mapping::{map_byte, FEISTEL_DEFAULT_ROUNDS};
+use k8dnz_core::lane::{decode_k8l1, encode_k8l1};
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+fn engine_step_1k() -> u64 {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let mut n = 0u64;
+    while n < 1024 && e.stats.ticks < MAX_TICKS {
+        if let Some(tok) = e.step() {
+            black_box(tok);
+            n += 1;
+        }
+    }
+    n
+}
+
+fn encode_k8l1_1k() -> Vec<u8> {
+    let text = text_corpus(1024);
+    encode_k8l1(black_box(&text), &default_recipe_bytes(), MAX_TICKS, None)
+        .unwrap()
+        .0
+}
+
+fn decode_k8l1_1k() -> Vec<u8> {
+    let text = text_corpus(1024);
+    let (enc, _) = encode_k8l1(&text, &default_recipe_bytes(), MAX_TICKS, None).unwrap();
+    decode_k8l1(black_box(&enc)).unwrap()
+}
+
+fn map_bytes(mode: MapMode) -> u8 {
+    let raw = binary_corpus(64 * 1024, 0xB1);
+    raw.iter().enumerate().fold(0u8, |acc, (i, &x)| {
+        acc ^ map_byte(mode, 0x1234_5678, i as u64, x, FEISTEL_DEFAULT_ROUNDS)
+    })
+}
+
+fn map_byte_splitmix64() -> u8 {
+    map_bytes(MapMode::Splitmix64)
+}
+
+fn map_byte_text40_weighted() -> u8 {
+    map_bytes(MapMode::Text40Weighted)
+}
+
+fn map_byte_text64() -> u8 {
+    map_bytes(MapMode::Text64)
+}
+
+fn fit_xor_chunked_512() {
+    FitFixture::new(512).run(4096, 256);
+}
+
+iai::main!(
+    engine_step_1k,
+    encode_k8l1_1k,
+    decode_k8l1_1k,
+    map_byte_splitmix64,
+    map_byte_text40_weighted,
+    map_byte_text64,
+    fit_xor_chunked_512
+);
@@ -0,0 +1,259 @@
+// crates/k8dnz-bench/benches/wall.rs
+//
+// Wall-clock benches. Every group sets Throughput::Bytes so criterion reports MB/s
+// (bytes = emitted / encoded / mapped / fitted bytes, respectively).
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use k8dnz_bench::{
+    binary_corpus, default_recipe_bytes, text_corpus, FitFixture, BINARY_CORPUS_BYTES,
+    FIT_CORPUS_BYTES, MAX_TICKS, SCAN_CORPUS_BYTES, TEXT_CORPUS_BYTES,
+};
+use k8dnz_cli::cmd::timemap::args::MapMode;
+use k8dnz_cli::cmd::timemap::mapping::{map_byte, FEISTEL_DEFAULT_ROUNDS};
+use k8dnz_core::lane::{decode_k8l1, encode_k8l1};
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::signal::bitpack::{hamming_distance_u8, symbol_match_count};
+use k8dnz_core::Engine;
+
+const ENGINE_EMISSIONS: u64 = 16 * 1024;
+/// Collected vs callback emission: run_emissions holds all of these tokens in one
+/// Vec (CALLBACK_EMISSIONS * size_of::<PairToken>() bytes), the callback holds none.
+/// The allocation counts themselves are in the `allocs` bench.
+const CALLBACK_EMISSIONS: u64 = 100_000;
+const BITPACK_SIZES: [usize; 4] = [64, 256, 4096, 65536];
+
+/// fit-xor-chunked over the 1 MB fit corpus; the search budget leaves one target's
+/// worth of slack past the last chunk.
+const FIT_SEARCH_EMISSIONS: u64 = 2 * FIT_CORPUS_BYTES as u64;
+const FIT_CHUNK_SIZE: usize = 1024;
+
+/// fit-xor scan cost: exhaustive step-1 scan vs a coarse scan with and without
+/// progressive refinement (quality per variant is in the logged matches= line),
+/// over the 256 KB scan corpus.
+const PROGRESSIVE_SEARCH_EMISSIONS: u64 = 2 * SCAN_CORPUS_BYTES as u64;
+const PROGRESSIVE_COARSE_STEP: usize = 64;
+
+/// Bitfield mapping comparison: the same rgbpair fit under each --bit-mapping. The fitted
+/// residual size per mapping is in the logged scoreboard.
+const BITFIELD_TARGET_BYTES: usize = 512;
+const BITFIELD_SEARCH_EMISSIONS: u64 = 16 * 1024;
+const BITFIELD_CHUNK_SIZE: usize = 256;
+const BITFIELD_BITS_PER_EMISSION: u8 = 8;
+
+/// Bitfield window scan, sequential vs --parallel-scan (rayon, one job per window).
+/// A 10 MB target in chunks of 512 symbols with --scan-step 1 keeps the run dominated
+/// by window scoring; at 8 bits/emission each target byte takes one emission.
+const SCAN_TARGET_BYTES: usize = 10 * 1024 * 1024;
+const SCAN_SEARCH_EMISSIONS: u64 = 2 * SCAN_TARGET_BYTES as u64;
+const SCAN_CHUNK_SIZE: usize = 512;
+
+fn bench_engine(c: &mut Criterion) {
+    let mut g = c.benchmark_group("engine");
+    g.throughput(Throughput::Bytes(ENGINE_EMISSIONS));
+    g.sample_size(10);
+    g.bench_function("step_16k_emissions", |b| {
+        b.iter(|| {
+            let mut e = Engine::new(default_recipe()).unwrap();
+            let mut n = 0u64;
+            while n < ENGINE_EMISSIONS && e.stats.ticks < MAX_TICKS {
+                if let Some(tok) = e.step() {
+                    black_box(tok);
+                    n += 1;
+                }
+            }
+            n
+        })
+    });
+    g.finish();
+}
+
+fn bench_emission_callback(c: &mut Criterion) {
+    let mut g = c.benchmark_group("engine_emission_sink");
+    g.throughput(Throughput::Bytes(CALLBACK_EMISSIONS));
+    g.sample_size(10);
+    g.bench_function("run_emissions_100k", |b| {
+        b.iter(|| {
+            let mut e = Engine::new(default_recipe()).unwrap();
+            let toks = e.run_emissions(CALLBACK_EMISSIONS, MAX_TICKS);
+            toks.iter().fold(0u8, |acc, t| acc ^ t.pack_byte())
+        })
+    });
+    g.bench_function("callback_100k", |b| {
+        b.iter(|| {
+            let mut e = Engine::new(default_recipe()).unwrap();
+            let mut acc = 0u8;
+            e.run_with_emission_callback(CALLBACK_EMISSIONS, MAX_TICKS, &mut |_, t| {
+                acc ^= t.pack_byte()
+            });
+            acc
+        })
+    });
+    g.finish();
+}
+
+fn bench_lane_codec(c: &mut Criterion) {
+    let text = text_corpus(TEXT_CORPUS_BYTES);
+    let recipe = default_recipe_bytes();
+    let (encoded, _) = encode_k8l1(&text, &recipe, MAX_TICKS, None).unwrap();
+
+    let mut g = c.benchmark_group("lane_codec");
+    g.throughput(Throughput::Bytes(text.len() as u64));
+    g.sample_size(10);
+    g.bench_function("encode_k8l1_64k", |b| {
+        b.iter(|| encode_k8l1(black_box(&text), &recipe, MAX_TICKS, None).unwrap())
+    });
+    g.bench_function("decode_k8l1_64k", |b| {
+        b.iter(|| decode_k8l1(black_box(&encoded)).unwrap())
+    });
+    g.finish();
+}
+
+fn bench_map_byte(c: &mut Criterion) {
+    let raw = binary_corpus(BINARY_CORPUS_BYTES, 0xB1);
+    let modes = [
+        MapMode::None,
+        MapMode::Splitmix64,
+        MapMode::Ascii7,
+        MapMode::Ascii7Splitmix,
+        MapMode::Text40,
+        MapMode::Text40Weighted,
+        MapMode::Text40Lane,
+        MapMode::Text40Field,
+        MapMode::Text64,
+        MapMode::Feistel,
+    ];
+
+    let mut g = c.benchmark_group("map_byte");
+    g.throughput(Throughput::Bytes(raw.len() as u64));
+    for mode in modes {
+        g.bench_with_input(
+            BenchmarkId::from_parameter(format!("{mode:?}")),
+            &raw,
+            |b, raw| {
+                b.iter(|| {
+                    let mut acc = 0u8;
+                    for (i, &x) in raw.iter().enumerate() {
+                        acc ^= map_byte(mode, 0x1234_5678, i as u64, x, FEISTEL_DEFAULT_ROUNDS);
+                    }
+                    acc
+                })
+            },
+        );
+    }
+    g.finish();
+}
+
+/// Batch symbol compares vs the scalar per-byte loops they replace in the bitfield scan.
+fn bench_bitpack(c: &mut Criterion) {
+    let mut g = c.benchmark_group("bitpack");
+    for n in BITPACK_SIZES {
+        let a = binary_corpus(n, 0xA5);
+        let b = binary_corpus(n, 0x5A);
+        g.throughput(Throughput::Bytes(n as u64));
+        g.bench_with_input(BenchmarkId::new("hamming_distance_u8", n), &n, |bn, _| {
+            bn.iter(|| hamming_distance_u8(black_box(&a), black_box(&b)))
+        });
+        g.bench_with_input(BenchmarkId::new("hamming_scalar", n), &n, |bn, _| {
+            bn.iter(|| {
+                black_box(&a)
+                    .iter()
+                    .zip(black_box(&b))
+                    .map(|(x, y)| (x ^ y).count_ones() as u64)
+                    .sum::<u64>()
+            })
+        });
+        g.bench_with_input(BenchmarkId::new("symbol_match_count", n), &n, |bn, _| {
+            bn.iter(|| symbol_match_count(black_box(&a), black_box(&b)))
+        });
+        g.bench_with_input(BenchmarkId::new("match_scalar", n), &n, |bn, _| {
+            bn.iter(|| {
+                black_box(&a)
+                    .iter()
+                    .zip(black_box(&b))
+                    .filter(|(x, y)| x == y)
+                    .count() as u64
+            })
+        });
+    }
+    g.finish();
+}
+
+fn bench_fit_xor_chunked(c: &mut Criterion) {
+    let fx = FitFixture::new(FIT_CORPUS_BYTES);
+
+    let mut g = c.benchmark_group("fit_xor_chunked");
+    g.throughput(Throughput::Bytes(FIT_CORPUS_BYTES as u64));
+    g.sample_size(10);
+    g.bench_function("target_1m_scan_2m", |b| {
+        b.iter(|| fx.run(FIT_SEARCH_EMISSIONS, FIT_CHUNK_SIZE))
+    });
+    g.finish();
+}
+
+fn bench_fit_xor_progressive(c: &mut Criterion) {
+    let fx = FitFixture::new(SCAN_CORPUS_BYTES);
+
+    let mut g = c.benchmark_group("fit_xor_progressive");
+    g.throughput(Throughput::Bytes(SCAN_CORPUS_BYTES as u64));
+    g.sample_size(10);
+    g.bench_function("step_1", |b| {
+        b.iter(|| fx.run_fit_xor(PROGRESSIVE_SEARCH_EMISSIONS, 1, false))
+    });
+    g.bench_function("step_64", |b| {
+        b.iter(|| fx.run_fit_xor(PROGRESSIVE_SEARCH_EMISSIONS, PROGRESSIVE_COARSE_STEP, false))
+    });
+    g.bench_function("step_64_progressive", |b| {
+        b.iter(|| fx.run_fit_xor(PROGRESSIVE_SEARCH_EMISSIONS, PROGRESSIVE_COARSE_STEP, true))
+    });
+    g.finish();
+}
+
+fn bench_bitfield_mapping(c: &mut Criterion) {
+    let fx = FitFixture::new(BITFIELD_TARGET_BYTES);
+
+    let mut g = c.benchmark_group("fit_bitfield_mapping");
+    g.throughput(Throughput::Bytes(BITFIELD_TARGET_BYTES as u64));
+    g.sample_size(10);
+    for mapping in ["geom", "rgb-moment"] {
+        g.bench_function(mapping, |b| {
+            b.iter(|| {
+                fx.run_bitfield(
+                    BITFIELD_SEARCH_EMISSIONS,
+                    BITFIELD_CHUNK_SIZE,
+                    mapping,
+                    BITFIELD_BITS_PER_EMISSION,
+                )
+            })
+        });
+    }
+    g.finish();
+}
+
+fn bench_bitfield_parallel_scan(c: &mut Criterion) {
+    let fx = FitFixture::new(SCAN_TARGET_BYTES);
+
+    let mut g = c.benchmark_group("fit_bitfield_scan");
+    g.throughput(Throughput::Bytes(SCAN_TARGET_BYTES as u64));
+    g.sample_size(10);
+    for (name, parallel) in [("sequential", false), ("parallel", true)] {
+        g.bench_function(name, |b| {
+            b.iter(|| fx.run_bitfield_scan(SCAN_SEARCH_EMISSIONS, SCAN_CHUNK_SIZE, parallel))
+        });
+    }
+    g.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_engine,
+    bench_emission_callback,
+    bench_lane_codec,
+    bench_map_byte,
+    bench_bitpack,
+    bench_fit_xor_chunked,
+    bench_fit_xor_progressive,
+    bench_bitfield_mapping,
+    bench_bitfield_parallel_scan
+);
+criterion_main!(benches);
@@ -0,0 +1,181 @@
+// crates/k8dnz-bench/src/lib.rs
+//
+// Shared, deterministic inputs for the bench targets in benches/. Nothing here is
+// random at run time: every corpus is a pure function of its length (and seed), so
+// two runs on the same machine measure the same work.
+//
+//   cargo bench -p k8dnz-bench --bench wall           wall-clock, reports MB/s
+//   cargo bench -p k8dnz-bench --bench instructions   instruction counts (needs valgrind)
+//   cargo bench -p k8dnz-bench --bench allocs         heap allocation counts
+
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use k8dnz_cli::cmd::timemap::args::TimemapArgs;
+use k8dnz_core::recipe::{defaults::default_recipe, format as recipe_format};
+
+/// Lane codec input (text).
+pub const TEXT_CORPUS_BYTES: usize = 64 * 1024;
+/// map_byte input (binary).
+pub const BINARY_CORPUS_BYTES: usize = 1024 * 1024;
+/// fit-xor-chunked target (binary).
+pub const FIT_CORPUS_BYTES: usize = 1024 * 1024;
+/// fit-xor scan target (binary).
+pub const SCAN_CORPUS_BYTES: usize = 256 * 1024;
+
+/// Generous tick guard for every engine-driven bench (each emission costs thousands
+/// of ticks, and the fit benches drive millions of emissions).
+pub const MAX_TICKS: u64 = 1_000_000_000_000;
+
+const GENESIS: &[u8] = include_bytes!("../../../text/Genesis1.txt");
+
+/// Genesis 1 repeated and truncated to `len` bytes.
+pub fn text_corpus(len: usize) -> Vec<u8> {
+    GENESIS.iter().copied().cycle().take(len).collect()
+}
+
+/// splitmix64 byte stream.
+pub fn binary_corpus(len: usize, seed: u64) -> Vec<u8> {
+    let mut x = seed;
+    (0..len)
+        .map(|_| {
+            x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            (z ^ (z >> 31)) as u8
+        })
+        .collect()
+}
+
+pub fn default_recipe_bytes() -> Vec<u8> {
+    recipe_format::encode(&default_recipe())
+}
+
+/// Files for one `timemap fit-xor-chunked` run, kept in a temp dir for the bench's lifetime.
+pub struct FitFixture {
+    _dir: tempfile::TempDir,
+    pub recipe: PathBuf,
+    pub target: PathBuf,
+    pub out_timemap: PathBuf,
+    pub out_residual: PathBuf,
+}
+
+impl FitFixture {
+    /// Target = `target_len` bytes of the fit corpus stream (seed 0x5CA7), so a
+    /// shorter target is always a prefix of a longer one.
+    pub fn new(target_len: usize) -> Self {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let p = |name: &str| dir.path().join(name);
+        let fx = FitFixture {
+            recipe: p("bench.k8r"),
+            target: p("target.bin"),
+            out_timemap: p("out.tm"),
+            out_residual: p("out.bin"),
+            _dir: dir,
+        };
+        std::fs::write(&fx.recipe, default_recipe_bytes()).expect("write recipe");
+        std::fs::write(&fx.target, binary_corpus(target_len, 0x5CA7)).expect("write target");
+        fx
+    }
+
+    /// Runs the real CLI code path in-process (it logs to stderr like the binary does).
+    pub fn run(&self, search_emissions: u64, chunk_size: usize) {
+        self.run_timemap(
+            "fit-xor-chunked",
+            search_emissions,
+            &["--chunk-size".into(), chunk_size.to_string()],
+        );
+    }
+
+    /// `timemap fit-xor` with the match-count objective; `progressive` adds
+    /// `--progressive-refinement` on top of the `scan_step` scan.
+    pub fn run_fit_xor(&self, search_emissions: u64, scan_step: usize, progressive: bool) {
+        let mut extra = vec![
+            "--objective".to_string(),
+            "matches".into(),
+            "--scan-step".into(),
+            scan_step.to_string(),
+        ];
+        if progressive {
+            extra.push("--progressive-refinement".into());
+        }
+        self.run_timemap("fit-xor", search_emissions, &extra);
+    }
+
+    /// `timemap fit-xor-chunked --map bitfield --mode rgbpair` with the given
+    /// `--bit-mapping` (clap name, e.g. "geom") at `bits_per_emission`.
+    pub fn run_bitfield(
+        &self,
+        search_emissions: u64,
+        chunk_size: usize,
+        bit_mapping: &str,
+        bits_per_emission: u8,
+    ) {
+        let extra = [
+            "--map",
+            "bitfield",
+            "--mode",
+            "rgbpair",
+            "--bit-mapping",
+            bit_mapping,
+            "--bits-per-emission",
+            &bits_per_emission.to_string(),
+            "--chunk-size",
+            &chunk_size.to_string(),
+        ]
+        .map(String::from);
+        self.run_timemap("fit-xor-chunked", search_emissions, &extra);
+    }
+
+    /// Scan-bound bitfield fit (geom, 8 bits/emission, --scan-step 1), with or
+    /// without `--parallel-scan`.
+    pub fn run_bitfield_scan(&self, search_emissions: u64, chunk_size: usize, parallel: bool) {
+        let mut extra = [
+            "--map",
+            "bitfield",
+            "--mode",
+            "rgbpair",
+            "--bits-per-emission",
+            "8",
+            "--scan-step",
+            "1",
+            "--chunk-size",
+            &chunk_size.to_string(),
+        ]
+        .map(String::from)
+        .to_vec();
+        if parallel {
+            extra.push("--parallel-scan".into());
+        }
+        self.run_timemap("fit-xor-chunked", search_emissions, &extra);
+    }
+
+    fn run_timemap(&self, sub: &str, search_emissions: u64, extra: &[String]) {
+        #[derive(Parser)]
+        struct Wrap {
+            #[command(flatten)]
+            tm: TimemapArgs,
+        }
+        let s = |p: &Path| p.to_string_lossy().into_owned();
+        let mut argv = vec![
+            "timemap".to_string(),
+            sub.into(),
+            "--recipe".into(),
+            s(&self.recipe),
+            "--target".into(),
+            s(&self.target),
+            "--out-timemap".into(),
+            s(&self.out_timemap),
+            "--out-residual".into(),
+            s(&self.out_residual),
+            "--search-emissions".into(),
+            search_emissions.to_string(),
+            "--max-ticks".into(),
+            MAX_TICKS.to_string(),
+        ];
+        argv.extend_from_slice(extra);
+        let w = Wrap::parse_from(argv);
+        k8dnz_cli::cmd::timemap::run(w.tm).unwrap_or_else(|e| panic!("{sub}: {e}"));
+    }
+}
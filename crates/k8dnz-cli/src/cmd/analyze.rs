@@ -1,11 +1,34 @@
-use clap::Args;
+use clap::{ArgGroup, Args};
 use std::io::Cursor;
 
+use k8dnz_core::stats::{
+    bigram_entropy, chi_squared_uniform, cross_entropy, kl_divergence, ks_uniform,
+    lz78_complexity, lz_complexity_normalized, trigram_entropy, UniformityTest,
+};
+
 #[derive(Args, Debug)]
+#[command(group(
+    ArgGroup::new("csv_source")
+        .args(["sliding_window_entropy", "compression_profile"])
+        .multiple(true)
+))]
 pub struct AnalyzeArgs {
     /// Input file path to analyze as raw bytes
-    #[arg(long)]
-    pub r#in: String,
+    #[arg(long, required_unless_present = "diff_two_files")]
+    pub r#in: Option<String>,
+
+    /// Compare --file-a and --file-b side by side (summary metrics with deltas,
+    /// cross-entropy and KL divergence of B against A, zstd of A XOR B vs B)
+    #[arg(long, requires_all = ["file_a", "file_b"], conflicts_with = "in")]
+    pub diff_two_files: bool,
+
+    /// Reference file for --diff-two-files (e.g. the plaintext)
+    #[arg(long, requires = "diff_two_files")]
+    pub file_a: Option<String>,
+
+    /// Compared file for --diff-two-files (e.g. the residual)
+    #[arg(long, requires = "diff_two_files")]
+    pub file_b: Option<String>,
 
     /// Show the top N most frequent bytes
     #[arg(long, default_value_t = 16)]
@@ -18,16 +41,120 @@ pub struct AnalyzeArgs {
     /// Zstd compression level (1..=22 typical). Higher is slower.
     #[arg(long, default_value_t = 3)]
     pub zstd_level: i32,
+
+    /// Chi-squared goodness-of-fit of the byte histogram against uniform 0..=255
+    #[arg(long)]
+    pub chi_squared_test: bool,
+
+    /// Kolmogorov-Smirnov test of the byte distribution against uniform 0..=255
+    #[arg(long)]
+    pub kolmogorov_test: bool,
+
+    /// Significance level for --chi-squared-test / --kolmogorov-test
+    #[arg(long, default_value_t = 0.05)]
+    pub alpha: f64,
+    /// Byte entropy over sliding windows of this many bytes (step = width/4),
+    /// drawn as a bar chart; bare flag uses 256
+    #[arg(long, num_args = 0..=1, default_missing_value = "256")]
+    pub sliding_window_entropy: Option<usize>,
+
+    /// Write the sliding-window rows (window_start,window_end,entropy_bits) or the
+    /// compression-profile rows (block_index,offset,raw_bytes,zstd_bytes,ratio) as CSV
+    #[arg(long, requires = "csv_source")]
+    pub out_csv: Option<String>,
+
+    /// Zstd-compress non-overlapping --block-size blocks independently (at --zstd-level)
+    /// and chart compressed size per block; '!' marks ratio > 0.95 (nearly incompressible)
+    #[arg(long)]
+    pub compression_profile: bool,
+
+    /// Block size in bytes for --compression-profile
+    #[arg(long, default_value_t = 512, requires = "compression_profile")]
+    pub block_size: usize,
+
+    /// Second-order entropy H(X[i+1] | X[i]) from the 256x256 bigram table,
+    /// plus compression_ratio_estimate = H2 / 8
+    #[arg(long)]
+    pub bigram_entropy: bool,
+
+    /// Third-order entropy H(X[i+2] | X[i], X[i+1]) (sparse trigram counts)
+    #[arg(long)]
+    pub trigram_entropy: bool,
+
+    /// Lempel-Ziv complexity C_LZ: phrase count of the LZ78 parsing
+    #[arg(long)]
+    pub lz_complexity: bool,
+
+    /// With --lz-complexity, also print C_LZ / (n / log_256(n)) (about 1.0 for random
+    /// bytes; well below 1.0 means repetitive structure)
+    #[arg(long, requires = "lz_complexity")]
+    pub lz_normalize: bool,
+}
+
+/// Histogram and compressibility metrics of one file.
+#[derive(Clone, Debug)]
+pub struct ByteSummary {
+    pub bytes: u64,
+    pub distinct: usize,
+    pub min_count: u64,
+    pub max_count: u64,
+    pub entropy_bits: f64,
+    pub bigram_entropy_bits: f64,
+    pub zstd_bytes: usize,
+}
+
+impl ByteSummary {
+    pub fn of(bytes: &[u8], zstd_level: i32) -> anyhow::Result<Self> {
+        let h = histogram(bytes);
+        let (min_count, max_count) = min_max_256(&h);
+        Ok(ByteSummary {
+            bytes: bytes.len() as u64,
+            distinct: h.iter().filter(|&&c| c > 0).count(),
+            min_count,
+            max_count,
+            entropy_bits: entropy_bits_256(&h, bytes.len() as u64),
+            bigram_entropy_bits: bigram_entropy(bytes),
+            zstd_bytes: zstd_size(bytes, zstd_level)?,
+        })
+    }
+
+    /// raw/zstd, 0 when the compressed size is 0.
+    pub fn zstd_ratio(&self) -> f64 {
+        if self.zstd_bytes == 0 {
+            0.0
+        } else {
+            self.bytes as f64 / self.zstd_bytes as f64
+        }
+    }
+
+    /// (name, value, decimals) rows for the --diff-two-files table.
+    fn metrics(&self) -> [(&'static str, f64, usize); 7] {
+        [
+            ("bytes", self.bytes as f64, 0),
+            ("distinct_bytes", self.distinct as f64, 0),
+            ("min_count", self.min_count as f64, 0),
+            ("max_count", self.max_count as f64, 0),
+            ("entropy_bits", self.entropy_bits, 6),
+            ("h2_bits", self.bigram_entropy_bits, 6),
+            ("zstd_bytes", self.zstd_bytes as f64, 0),
+        ]
+    }
 }
 
 pub fn run(args: AnalyzeArgs) -> anyhow::Result<()> {
-    let bytes = std::fs::read(&args.r#in)?;
+    if args.diff_two_files {
+        return run_diff_two_files(&args);
+    }
+    if args.out_csv.is_some() && args.sliding_window_entropy.is_some() && args.compression_profile {
+        anyhow::bail!(
+            "--out-csv takes one table: pass either --sliding-window-entropy or --compression-profile"
+        );
+    }
+    let path = args.r#in.as_deref().expect("clap requires --in");
+    let bytes = std::fs::read(path)?;
     let n = bytes.len() as u64;
 
-    let mut h = [0u64; 256];
-    for &b in &bytes {
-        h[b as usize] += 1;
-    }
+    let h = histogram(&bytes);
 
     let distinct = h.iter().filter(|&&c| c > 0).count();
     let (minc, maxc) = min_max_256(&h);
@@ -42,7 +169,7 @@ pub fn run(args: AnalyzeArgs) -> anyhow::Result<()> {
     rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
 
     eprintln!("--- analyze ---");
-    eprintln!("file            = {}", args.r#in);
+    eprintln!("file            = {}", path);
     eprintln!("bytes           = {}", n);
     eprintln!("distinct_bytes  = {}/256", distinct);
     eprintln!("min_count       = {}", minc);
@@ -58,6 +185,70 @@ pub fn run(args: AnalyzeArgs) -> anyhow::Result<()> {
         eprintln!("ratio_raw/zstd  = {:.4}x", ratio);
     }
 
+    if args.chi_squared_test || args.kolmogorov_test {
+        eprintln!("--- uniformity (alpha={}) ---", args.alpha);
+        if args.chi_squared_test {
+            let t = chi_squared_uniform(&bytes);
+            eprintln!(
+                "chi2            = {:.3} (df=255) {}",
+                t.statistic,
+                verdict(&t, args.alpha)
+            );
+        }
+        if args.kolmogorov_test {
+            let t = ks_uniform(&bytes);
+            eprintln!(
+                "ks_d            = {:.6} {}",
+                t.statistic,
+                verdict(&t, args.alpha)
+            );
+        }
+    }
+
+    if args.bigram_entropy || args.trigram_entropy {
+        eprintln!("--- higher-order entropy ---");
+        eprintln!("h1_bits         = {:.6}", entropy);
+        if args.bigram_entropy {
+            let h2 = bigram_entropy(&bytes);
+            eprintln!("h2_bits         = {:.6} (H(X[i+1] | X[i]))", h2);
+            eprintln!("compression_ratio_estimate = {:.4} (h2/8)", h2 / 8.0);
+        }
+        if args.trigram_entropy {
+            let (h3, distinct3) = trigram_entropy(&bytes);
+            eprintln!("h3_bits         = {:.6} (H(X[i+2] | X[i], X[i+1]))", h3);
+            eprintln!("distinct_trigrams = {}", distinct3);
+        }
+    }
+
+    if args.lz_complexity {
+        let c = lz78_complexity(&bytes);
+        eprintln!("--- lz complexity ---");
+        eprintln!("lz78_phrases    = {}", c);
+        if args.lz_normalize {
+            let norm = lz_complexity_normalized(&bytes);
+            eprintln!("lz_normalized   = {:.6} (C_LZ / (n / log_256(n)); ~1.0 random)", norm);
+            if norm > 1.0 {
+                eprintln!(
+                    "WARN: lz_normalized > 1.0; the normalization only holds asymptotically ({} bytes is too short)",
+                    n
+                );
+            }
+        }
+    }
+
+    if let Some(width) = args.sliding_window_entropy {
+        report_sliding_window_entropy(&bytes, width, args.out_csv.as_deref())?;
+    }
+
+    if args.compression_profile {
+        report_compression_profile(
+            &bytes,
+            args.block_size,
+            args.zstd_level,
+            args.out_csv.as_deref(),
+        )?;
+    }
+
     let topn = args.top.min(rows.len());
     eprintln!("--- top {} bytes ---", topn);
     for (i, (b, c)) in rows.iter().take(topn).enumerate() {
@@ -79,12 +270,235 @@ pub fn run(args: AnalyzeArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn run_diff_two_files(args: &AnalyzeArgs) -> anyhow::Result<()> {
+    let path_a = args.file_a.as_deref().expect("clap requires --file-a");
+    let path_b = args.file_b.as_deref().expect("clap requires --file-b");
+    let a = std::fs::read(path_a)?;
+    let b = std::fs::read(path_b)?;
+    let sa = ByteSummary::of(&a, args.zstd_level)?;
+    let sb = ByteSummary::of(&b, args.zstd_level)?;
+
+    eprintln!("--- analyze diff ---");
+    eprintln!("file_a          = {}", path_a);
+    eprintln!("file_b          = {}", path_b);
+    eprintln!("zstd_level      = {}", args.zstd_level);
+    eprintln!(
+        "{:<16} {:>16} {:>16} {:>16}",
+        "metric", "A", "B", "delta(B-A)"
+    );
+    let rows = sa.metrics().into_iter().zip(sb.metrics());
+    for ((name, va, prec), (_, vb, _)) in rows {
+        eprintln!(
+            "{:<16} {:>16.prec$} {:>16.prec$} {:>+16.prec$}",
+            name,
+            va,
+            vb,
+            vb - va
+        );
+    }
+    let (ra, rb) = (sa.zstd_ratio(), sb.zstd_ratio());
+    eprintln!(
+        "{:<16} {:>16.4} {:>16.4} {:>+16.4}",
+        "ratio_raw/zstd",
+        ra,
+        rb,
+        rb - ra
+    );
+
+    // B with A xored out over the common prefix; B's tail (if longer) kept as-is.
+    let mut xored = b.clone();
+    for (x, &y) in xored.iter_mut().zip(&a) {
+        *x ^= y;
+    }
+    let xor_z = zstd_size(&xored, args.zstd_level)?;
+
+    eprintln!("--- B against A ---");
+    eprintln!(
+        "cross_entropy_bits = {:.6} (H(B; A): bits/byte coding B with A's byte model)",
+        cross_entropy(&b, &a)
+    );
+    eprintln!(
+        "kl_divergence_bits = {:.6} (D_KL(B || A))",
+        kl_divergence(&b, &a)
+    );
+    eprintln!(
+        "xor_common_bytes   = {}{}",
+        a.len().min(b.len()),
+        if a.len() != b.len() {
+            " (lengths differ: only the common prefix is xored)"
+        } else {
+            ""
+        }
+    );
+    eprintln!("zstd(A xor B)      = {}", xor_z);
+    eprintln!("zstd(B)            = {}", sb.zstd_bytes);
+    eprintln!(
+        "xor_vs_b_ratio     = {:.4} (zstd(A xor B) / zstd(B); < 1 means A explains B)",
+        xor_z as f64 / sb.zstd_bytes.max(1) as f64
+    );
+    Ok(())
+}
+
+const ENTROPY_BAR_WIDTH: usize = 64;
+
+/// `(start, end, entropy_bits)` per window of `width` bytes, stepping `width/4`.
+/// Only full windows are reported, except that input shorter than `width` yields
+/// one partial window.
+fn sliding_window_entropy(bytes: &[u8], width: usize) -> Vec<(usize, usize, f64)> {
+    let step = (width / 4).max(1);
+    let mut out = Vec::new();
+    let mut start = 0usize;
+    loop {
+        let end = usize::min(start + width, bytes.len());
+        let mut h = [0u64; 256];
+        for &b in &bytes[start..end] {
+            h[b as usize] += 1;
+        }
+        out.push((start, end, entropy_bits_256(&h, (end - start) as u64)));
+        start += step;
+        if start + width > bytes.len() {
+            break;
+        }
+    }
+    out
+}
+
+fn report_sliding_window_entropy(
+    bytes: &[u8],
+    width: usize,
+    out_csv: Option<&str>,
+) -> anyhow::Result<()> {
+    if width == 0 {
+        anyhow::bail!("--sliding-window-entropy width must be >= 1");
+    }
+    let rows = sliding_window_entropy(bytes, width);
+
+    if let Some(path) = out_csv {
+        let mut csv = String::from("window_start,window_end,entropy_bits\n");
+        for (s, e, h) in &rows {
+            csv.push_str(&format!("{s},{e},{h:.6}\n"));
+        }
+        std::fs::write(path, csv)?;
+    }
+
+    let (lo, hi) = rows.iter().fold((f64::INFINITY, 0.0f64), |(lo, hi), r| {
+        (lo.min(r.2), hi.max(r.2))
+    });
+    eprintln!(
+        "--- sliding window entropy (width={} step={} windows={} min={:.3} max={:.3}) ---",
+        width,
+        (width / 4).max(1),
+        rows.len(),
+        lo,
+        hi
+    );
+    for (s, e, h) in &rows {
+        let bar = ((h / 8.0) * ENTROPY_BAR_WIDTH as f64).round() as usize;
+        eprintln!("{:>10}..{:<10} {:>6.3} |{}", s, e, h, "#".repeat(bar));
+    }
+    if let Some(path) = out_csv {
+        eprintln!("csv             = {} ({} rows)", path, rows.len());
+    }
+    Ok(())
+}
+
+/// Blocks above this compressed/raw ratio are flagged as nearly incompressible.
+const INCOMPRESSIBLE_RATIO: f64 = 0.95;
+
+/// `(offset, raw_len, zstd_len)` per non-overlapping block; the last block may be short.
+/// One compressor context is reused across blocks.
+fn compression_profile(
+    bytes: &[u8],
+    block_size: usize,
+    level: i32,
+) -> anyhow::Result<Vec<(usize, usize, usize)>> {
+    let mut z = zstd::bulk::Compressor::new(level)?;
+    let mut out = Vec::with_capacity(bytes.len().div_ceil(block_size));
+    for (i, block) in bytes.chunks(block_size).enumerate() {
+        let c = z.compress(block)?;
+        out.push((i * block_size, block.len(), c.len()));
+    }
+    Ok(out)
+}
+
+fn report_compression_profile(
+    bytes: &[u8],
+    block_size: usize,
+    level: i32,
+    out_csv: Option<&str>,
+) -> anyhow::Result<()> {
+    if block_size == 0 {
+        anyhow::bail!("--block-size must be >= 1");
+    }
+    let rows = compression_profile(bytes, block_size, level)?;
+    let ratio = |raw: usize, z: usize| z as f64 / raw.max(1) as f64;
+
+    if let Some(path) = out_csv {
+        let mut csv = String::from("block_index,offset,raw_bytes,zstd_bytes,ratio\n");
+        for (i, (off, raw, z)) in rows.iter().enumerate() {
+            csv.push_str(&format!("{i},{off},{raw},{z},{:.6}\n", ratio(*raw, *z)));
+        }
+        std::fs::write(path, csv)?;
+    }
+
+    let total_z: usize = rows.iter().map(|r| r.2).sum();
+    let flagged = rows
+        .iter()
+        .filter(|r| ratio(r.1, r.2) > INCOMPRESSIBLE_RATIO)
+        .count();
+    eprintln!(
+        "--- compression profile (block_size={} zstd_level={} blocks={} zstd_bytes={} overall_ratio={:.4} incompressible={}) ---",
+        block_size,
+        level,
+        rows.len(),
+        total_z,
+        ratio(bytes.len(), total_z),
+        flagged
+    );
+    for (i, (_off, raw, z)) in rows.iter().enumerate() {
+        let r = ratio(*raw, *z);
+        let bar = (r.min(1.0) * ENTROPY_BAR_WIDTH as f64).round() as usize;
+        let mark = if r > INCOMPRESSIBLE_RATIO { '!' } else { ' ' };
+        eprintln!("{:>8} {:>6} {:>7.4} {}|{}", i, z, r, mark, "#".repeat(bar));
+    }
+    if let Some(path) = out_csv {
+        eprintln!("csv             = {} ({} rows)", path, rows.len());
+    }
+    Ok(())
+}
+
+fn verdict(t: &UniformityTest, alpha: f64) -> String {
+    if t.passes(alpha) {
+        format!("PASS (p={})", fmt_p(t.p_value))
+    } else {
+        format!("FAIL (p={}, reject at alpha={})", fmt_p(t.p_value), alpha)
+    }
+}
+
+fn fmt_p(p: f64) -> String {
+    if p == 0.0 {
+        "0".to_string()
+    } else if p >= 0.01 {
+        format!("{:.2}", p)
+    } else {
+        format!("{:.2e}", p)
+    }
+}
+
 fn zstd_size(bytes: &[u8], level: i32) -> anyhow::Result<usize> {
     // Deterministic given bytes+level; good enough for a “scoreboard”.
     let out = zstd::stream::encode_all(Cursor::new(bytes), level)?;
     Ok(out.len())
 }
 
+fn histogram(bytes: &[u8]) -> [u64; 256] {
+    let mut h = [0u64; 256];
+    for &b in bytes {
+        h[b as usize] += 1;
+    }
+    h
+}
+
 fn min_max_256(h: &[u64; 256]) -> (u64, u64) {
     let mut min = u64::MAX;
     let mut max = 0u64;
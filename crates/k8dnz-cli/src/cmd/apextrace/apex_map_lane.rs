@@ -4,6 +4,7 @@ use std::{env, thread};
 use k8dnz_apextrace::{
     generate_bytes, ApexKey, ApexMap, ApexMapCfg, OverrideTrace, RefineCfg, RefineStats, SearchCfg,
 };
+use k8dnz_core::error::K8Error;
 use k8dnz_core::lane;
 use k8dnz_core::repr::{text_norm, ws_lanes::WsLanes};
 use k8dnz_core::symbol::patch::PatchList;
@@ -402,7 +403,7 @@ pub fn run_apex_map_lane(args: ApexMapLaneArgs) -> Result<()> {
     let (artifact, baseline_stats, baseline_ticks_used) =
         run_baseline_k8l1(&input, &recipe_bytes, args.max_ticks)?;
     let view = decode_k8l1_view_any(&artifact)?;
-    let baseline_class_patch_entries = patch_count(&view.class_patch)?;
+    let baseline_class_patch_entries = patch_count(&view, &view.class_patch)?;
     let baseline_class_patch_bytes = view.class_patch.len();
 
     let cfg = SearchCfg {
@@ -1085,11 +1086,10 @@ fn run_baseline_k8l1(
         .min(1_280_000_000);
 
     let out = loop {
-        match lane::encode_k8l1(input, recipe_bytes, baseline_ticks_used) {
+        match lane::encode_k8l1(input, recipe_bytes, baseline_ticks_used, None) {
             Ok(ok) => break ok,
             Err(e) => {
-                let msg = e.to_string();
-                if msg.contains("insufficient emissions") && baseline_ticks_used < baseline_ticks_cap
+                if matches!(e, K8Error::InsufficientEmissions { .. }) && baseline_ticks_used < baseline_ticks_cap
                 {
                     let next = baseline_ticks_used
                         .saturating_mul(2)
@@ -1116,6 +1116,10 @@ fn run_baseline_k8l1(
         );
     }
 
+    eprintln!(
+        "apex-map-lane baseline quality: {}",
+        out.1.quality_summary()
+    );
     Ok((out.0, out.1, baseline_ticks_used))
 }
 
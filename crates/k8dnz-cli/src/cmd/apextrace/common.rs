@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Context, Result};
 use k8dnz_apextrace::{branch_name, render_lattice, render_paths, render_subtree_stats, ApexKey, SubtreeStats};
-use k8dnz_core::symbol::{patch::PatchList, varint};
+use k8dnz_core::lane;
+use k8dnz_core::symbol::varint;
 
 pub fn render_lattice_csv(key: &ApexKey, max_quats: Option<u64>, active_only: bool) -> Result<String> {
     let points = render_lattice(key, max_quats)?;
@@ -237,10 +238,12 @@ pub fn match_pct_f64(matches: f64, total: u64) -> f64 {
 
 const MAGIC_K8L1_ANY: &[u8; 4] = b"K8L1";
 const K8L1_VERSION_MIN_ANY: u8 = 1;
-const K8L1_VERSION_MAX_ANY: u8 = 3;
+const K8L1_VERSION_MAX_ANY: u8 = lane::K8L1_VERSION_V8;
 
 #[derive(Clone, Debug)]
 pub struct K8L1ViewAny {
+    pub ver: u8,
+    pub total_len: usize,
     pub class_patch: Vec<u8>,
     #[allow(dead_code)]
     pub other_patch: Vec<u8>,
@@ -264,7 +267,7 @@ pub fn decode_k8l1_view_any(bytes: &[u8]) -> Result<K8L1ViewAny> {
     if !(K8L1_VERSION_MIN_ANY..=K8L1_VERSION_MAX_ANY).contains(&ver) {
         return Err(anyhow!("k8l1: unsupported version {}", ver));
     }
-    let _total_len = varint::get_u64(bytes, &mut i)? as usize;
+    let total_len = varint::get_u64(bytes, &mut i)? as usize;
     let _other_len = varint::get_u64(bytes, &mut i)? as usize;
     let _max_ticks = varint::get_u64(bytes, &mut i)?;
     let recipe_len = varint::get_u64(bytes, &mut i)? as usize;
@@ -280,6 +283,19 @@ pub fn decode_k8l1_view_any(bytes: &[u8]) -> Result<K8L1ViewAny> {
         }
         i += omega_len;
     }
+    if ver >= 4 {
+        let punct_len = varint::get_u64(bytes, &mut i)? as usize;
+        if i + punct_len > bytes.len() {
+            return Err(anyhow!("k8l1: punct alphabet oob"));
+        }
+        i += punct_len;
+    }
+    if ver >= 5 {
+        if i >= bytes.len() {
+            return Err(anyhow!("k8l1: text flags oob"));
+        }
+        i += 1;
+    }
     let class_patch_len = varint::get_u64(bytes, &mut i)? as usize;
     if i + class_patch_len > bytes.len() {
         return Err(anyhow!("k8l1: class_patch oob"));
@@ -293,6 +309,8 @@ pub fn decode_k8l1_view_any(bytes: &[u8]) -> Result<K8L1ViewAny> {
     let other_patch = bytes[i..i + other_patch_len].to_vec();
     i += other_patch_len;
     Ok(K8L1ViewAny {
+        ver,
+        total_len,
         class_patch,
         other_patch,
         omega_len,
@@ -300,8 +318,8 @@ pub fn decode_k8l1_view_any(bytes: &[u8]) -> Result<K8L1ViewAny> {
     })
 }
 
-pub fn patch_count(patch_bytes: &[u8]) -> Result<usize> {
-    let p = PatchList::decode(patch_bytes).map_err(|e| anyhow!("{e}"))?;
+pub fn patch_count(view: &K8L1ViewAny, patch_bytes: &[u8]) -> Result<usize> {
+    let p = lane::decode_patch_blob(view.ver, patch_bytes, view.total_len).map_err(|e| anyhow!("{e}"))?;
     Ok(p.entries.len())
 }
 
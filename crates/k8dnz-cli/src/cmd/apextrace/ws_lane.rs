@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use k8dnz_apextrace::{generate_bytes, ApexKey, SearchCfg};
+use k8dnz_core::error::K8Error;
 use k8dnz_core::lane;
 use k8dnz_core::repr::{text_norm, ws_lanes::WsLanes};
 use k8dnz_core::symbol::patch::PatchList;
@@ -22,7 +23,7 @@ pub fn run_ws_lane(args: WsLaneArgs) -> Result<()> {
     let ws = WsLanes::split(&norm);
     let (artifact, baseline_stats, baseline_ticks_used) = run_baseline_k8l1(&input, &recipe_bytes, args.max_ticks)?;
     let view = decode_k8l1_view_any(&artifact)?;
-    let baseline_class_patch_entries = patch_count(&view.class_patch)?;
+    let baseline_class_patch_entries = patch_count(&view, &view.class_patch)?;
     let baseline_class_patch_bytes = view.class_patch.len();
     let cfg = SearchCfg {
         seed_from: args.seed_from,
@@ -83,11 +84,10 @@ fn run_baseline_k8l1(input: &[u8], recipe_bytes: &[u8], max_ticks: u64) -> Resul
     let mut baseline_ticks_used = max_ticks.max(1);
     let baseline_ticks_cap = baseline_ticks_used.saturating_mul(8).max(160_000_000).min(1_280_000_000);
     let out = loop {
-        match lane::encode_k8l1(input, recipe_bytes, baseline_ticks_used) {
+        match lane::encode_k8l1(input, recipe_bytes, baseline_ticks_used, None) {
             Ok(ok) => break ok,
             Err(e) => {
-                let msg = e.to_string();
-                if msg.contains("insufficient emissions") && baseline_ticks_used < baseline_ticks_cap {
+                if matches!(e, K8Error::InsufficientEmissions { .. }) && baseline_ticks_used < baseline_ticks_cap {
                     let next = baseline_ticks_used.saturating_mul(2).min(baseline_ticks_cap);
                     if next == baseline_ticks_used {
                         return Err(anyhow!("baseline k8l1 encode failed: {e}"));
@@ -106,6 +106,10 @@ fn run_baseline_k8l1(input: &[u8], recipe_bytes: &[u8], max_ticks: u64) -> Resul
     if baseline_ticks_used != max_ticks {
         eprintln!("apextrace ws-lane baseline auto-ticks resolved: used max_ticks={}", baseline_ticks_used);
     }
+    eprintln!(
+        "apextrace ws-lane baseline quality: {}",
+        out.1.quality_summary()
+    );
     Ok((out.0, out.1, baseline_ticks_used))
 }
 fn save_ws_lane_outputs(out_key: Option<&str>, out_pred: Option<&str>, best: &WsLaneBest, chunked: Option<&WsLaneChunkedBest>) -> Result<()> {
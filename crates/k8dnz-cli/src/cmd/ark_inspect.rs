@@ -1,9 +1,12 @@
 // crates/k8dnz-cli/src/cmd/ark_inspect.rs
 
+use anyhow::Context;
 use clap::Args;
+use k8dnz_core::recipe::format as recipe_format;
+use k8dnz_core::recipe::recipe::PayloadKind;
 use std::io::Cursor;
 
-use crate::io::ark;
+use crate::io::{ark, recipe_file};
 
 #[derive(Args, Debug)]
 pub struct ArkInspectArgs {
@@ -12,9 +15,22 @@ pub struct ArkInspectArgs {
     pub r#in: String,
 
     /// Also recompute recipe_id from decoded recipe and report match/mismatch
-    #[arg(long, default_value_t = true)]
+    #[arg(long, visible_alias = "verify", default_value_t = true)]
     pub verify_recipe_id: bool,
 
+    /// With verification on, also require the embedded recipe to match this .k8r
+    /// (same recipe_id); fails on mismatch.
+    #[arg(long)]
+    pub recipe: Option<String>,
+
+    /// Write the embedded recipe to --out as a standalone .k8r and exit
+    #[arg(long, requires = "out")]
+    pub extract_recipe: bool,
+
+    /// Output path for --extract-recipe
+    #[arg(long)]
+    pub out: Option<String>,
+
     /// If set, dump the ciphertext/residual bytes to this file path
     #[arg(long)]
     pub dump_ciphertext: Option<String>,
@@ -29,6 +45,10 @@ pub struct ArkInspectArgs {
 }
 
 pub fn run(args: ArkInspectArgs) -> anyhow::Result<()> {
+    if args.extract_recipe {
+        return extract_recipe(&args);
+    }
+
     let meta_len = std::fs::metadata(&args.r#in).ok().map(|m| m.len());
 
     let (embedded_rid, recipe, data) = ark::read_ark_with_id(&args.r#in)?;
@@ -50,6 +70,9 @@ pub fn run(args: ArkInspectArgs) -> anyhow::Result<()> {
         if !ok {
             eprintln!("WARNING: embedded recipe_id != recomputed recipe_id (should never happen)");
         }
+        if let Some(path) = args.recipe.as_deref() {
+            check_against_recipe_file(&embedded_rid, path)?;
+        }
     }
 
     eprintln!("--- recipe ---");
@@ -110,6 +133,61 @@ pub fn run(args: ArkInspectArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// `--extract-recipe`: dump the embedded K8R1 blob byte-for-byte once it decodes.
+fn extract_recipe(args: &ArkInspectArgs) -> anyhow::Result<()> {
+    let out = args
+        .out
+        .as_deref()
+        .context("--extract-recipe requires --out")?;
+
+    let blob = ark::read_ark_recipe_bytes(&args.r#in)?;
+    let recipe = recipe_format::decode(&blob).map_err(|e| {
+        anyhow::anyhow!(
+            "embedded recipe in {} is malformed ({} bytes): {e}",
+            args.r#in,
+            blob.len()
+        )
+    })?;
+    let rid = recipe_format::recipe_id_hex(&recipe);
+
+    std::fs::write(out, &blob).with_context(|| format!("write {out}"))?;
+
+    eprintln!("--- ark-inspect: extract-recipe ---");
+    eprintln!("file              = {}", args.r#in);
+    eprintln!("out               = {} ({} bytes)", out, blob.len());
+    eprintln!("recipe_id         = {}", rid);
+    eprintln!("version           = {}", recipe.version);
+    eprintln!("payload_kind      = {:?}", recipe.payload_kind);
+    if recipe.payload_kind == PayloadKind::ResidualXor {
+        eprintln!("note              = payload is a residual; this recipe is required to reconstruct the plaintext");
+    }
+
+    if args.verify_recipe_id {
+        if let Some(path) = args.recipe.as_deref() {
+            check_against_recipe_file(&rid, path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check_against_recipe_file(embedded_rid: &str, path: &str) -> anyhow::Result<()> {
+    let expected = recipe_format::recipe_id_hex(&recipe_file::load_k8r(path)?);
+    let ok = expected == embedded_rid;
+    eprintln!("recipe_file          = {}", path);
+    eprintln!("recipe_file_id       = {}", expected);
+    eprintln!("recipe_file_match    = {}", ok);
+    if !ok {
+        anyhow::bail!(
+            "embedded recipe_id {} != {} recipe_id {}",
+            embedded_rid,
+            path,
+            expected
+        );
+    }
+    Ok(())
+}
+
 fn zstd_size(bytes: &[u8], level: i32) -> anyhow::Result<usize> {
     let out = zstd::stream::encode_all(Cursor::new(bytes), level)?;
     Ok(out.len())
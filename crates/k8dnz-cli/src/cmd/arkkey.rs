@@ -1,7 +1,12 @@
+use anyhow::Context;
 use clap::{Args, Subcommand};
 use k8dnz_core::recipe::ark_key::{decode_ark1s, encode_ark1s};
+use k8dnz_core::recipe::format::{self as recipe_format, recipe_id_hex};
+use k8dnz_core::recipe::keygen::{recipe_from_key, DEFAULT_SALT, PBKDF2_ROUNDS};
+use k8dnz_core::recipe::shamir::{combine_shares, decode_share, encode_share, split_secret};
+use k8dnz_core::Engine;
 
-use crate::io::recipe_file;
+use crate::io::{ark, recipe_file};
 
 #[derive(Args)]
 pub struct ArkKeyArgs {
@@ -13,6 +18,14 @@ pub struct ArkKeyArgs {
 pub enum ArkKeyCmd {
     FromRecipe(FromRecipeArgs),
     ToRecipe(ToRecipeArgs),
+    /// Derive a recipe from a passphrase (PBKDF2-HMAC-SHA256)
+    Generate(GenerateArgs),
+    /// Re-encrypt an .ark under a new recipe without writing the plaintext anywhere
+    Rotate(RotateArgs),
+    /// Split a recipe into Shamir shares (share_N.k8rs); any --threshold of them rebuild it
+    Split(SplitArgs),
+    /// Rebuild a recipe from at least threshold .k8rs shares
+    Combine(CombineArgs),
 }
 
 #[derive(Args)]
@@ -30,6 +43,79 @@ pub struct ToRecipeArgs {
     pub out: String,
 }
 
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// Passphrase to derive the recipe from
+    #[arg(long)]
+    pub key: String,
+
+    /// Salt string (default: built-in fixed salt)
+    #[arg(long)]
+    pub salt: Option<String>,
+
+    #[arg(long)]
+    pub out: String,
+}
+
+#[derive(Args)]
+pub struct RotateArgs {
+    /// Input .ark (encoded under --old-recipe)
+    #[arg(long)]
+    pub r#in: String,
+
+    /// Recipe the input was encoded with (.k8r)
+    #[arg(long)]
+    pub old_recipe: String,
+
+    /// Recipe to re-encode under (.k8r)
+    #[arg(long)]
+    pub new_recipe: String,
+
+    /// Output .ark path
+    #[arg(long)]
+    pub out: String,
+
+    /// Process the payload in --block-size pieces instead of loading it whole
+    #[arg(long)]
+    pub stream: bool,
+
+    /// Block size in bytes for --stream
+    #[arg(long, default_value_t = 64 * 1024)]
+    pub block_size: usize,
+
+    /// Max ticks guard for each keystream (old and new)
+    #[arg(long, default_value_t = 50_000_000)]
+    pub max_ticks: u64,
+}
+
+#[derive(Args)]
+pub struct SplitArgs {
+    #[arg(long)]
+    pub recipe: String,
+
+    /// Number of shares to write (2..=255)
+    #[arg(long)]
+    pub shares: u8,
+
+    /// Shares needed to rebuild the recipe (2..=shares)
+    #[arg(long)]
+    pub threshold: u8,
+
+    /// Directory for share_1.k8rs .. share_N.k8rs (created if missing)
+    #[arg(long)]
+    pub out_dir: String,
+}
+
+#[derive(Args)]
+pub struct CombineArgs {
+    /// Share files (.k8rs); any threshold of them from the same split
+    #[arg(long, num_args = 1.., required = true)]
+    pub shares: Vec<String>,
+
+    #[arg(long)]
+    pub out: String,
+}
+
 pub fn run(args: ArkKeyArgs) -> anyhow::Result<()> {
     match args.cmd {
         ArkKeyCmd::FromRecipe(a) => {
@@ -44,5 +130,203 @@ pub fn run(args: ArkKeyArgs) -> anyhow::Result<()> {
             eprintln!("arkkey ok: out={}", a.out);
             Ok(())
         }
+        ArkKeyCmd::Generate(a) => {
+            let salt = a.salt.as_deref().map(str::as_bytes).unwrap_or(DEFAULT_SALT);
+            let r = recipe_from_key(a.key.as_bytes(), salt);
+            for w in r.validate_deep() {
+                eprintln!("WARN: recipe: {w}");
+            }
+            recipe_file::save_k8r(&a.out, &r)?;
+            eprintln!(
+                "arkkey generate ok: out={} recipe_id={} rounds={} salt={}",
+                a.out,
+                recipe_id_hex(&r),
+                PBKDF2_ROUNDS,
+                if a.salt.is_some() {
+                    "custom"
+                } else {
+                    "default"
+                }
+            );
+            Ok(())
+        }
+        ArkKeyCmd::Rotate(a) => cmd_rotate(a),
+        ArkKeyCmd::Split(a) => cmd_split(a),
+        ArkKeyCmd::Combine(a) => cmd_combine(a),
+    }
+}
+
+fn cmd_split(a: SplitArgs) -> anyhow::Result<()> {
+    let r = recipe_file::load_k8r(&a.recipe)?;
+    let secret = recipe_format::encode(&r);
+
+    let mut rng_err = None;
+    let shares = split_secret(&secret, a.shares, a.threshold, |buf| {
+        if let Err(e) = getrandom::fill(buf) {
+            rng_err.get_or_insert(e);
+        }
+    })
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+    if let Some(e) = rng_err {
+        anyhow::bail!("OS random source failed: {e}");
+    }
+
+    std::fs::create_dir_all(&a.out_dir)
+        .with_context(|| format!("create out dir {}", a.out_dir))?;
+    for s in &shares {
+        let path = std::path::Path::new(&a.out_dir).join(format!("share_{}.k8rs", s.index));
+        std::fs::write(&path, encode_share(s))
+            .with_context(|| format!("write share {}", path.display()))?;
+    }
+
+    eprintln!(
+        "arkkey split ok: recipe_id={} shares={} threshold={} out_dir={} bytes_per_share={}",
+        recipe_id_hex(&r),
+        a.shares,
+        a.threshold,
+        a.out_dir,
+        secret.len()
+    );
+    Ok(())
+}
+
+fn cmd_combine(a: CombineArgs) -> anyhow::Result<()> {
+    let mut shares = Vec::with_capacity(a.shares.len());
+    for p in &a.shares {
+        let bytes = std::fs::read(p).with_context(|| format!("read share {p}"))?;
+        let s = decode_share(&bytes).with_context(|| format!("decode share {p}"))?;
+        shares.push(s);
+    }
+
+    let secret = combine_shares(&shares).map_err(|e| anyhow::anyhow!("{e}"))?;
+    // The recipe's own crc32/blake3 catch a wrong reconstruction.
+    let r = recipe_format::decode(&secret).context("combined shares do not form a valid recipe")?;
+    recipe_file::save_k8r(&a.out, &r)?;
+
+    eprintln!(
+        "arkkey combine ok: out={} recipe_id={} shares_used={}",
+        a.out,
+        recipe_id_hex(&r),
+        shares[0].threshold
+    );
+    Ok(())
+}
+
+fn cmd_rotate(a: RotateArgs) -> anyhow::Result<()> {
+    let old = recipe_file::load_k8r(&a.old_recipe)?;
+    let new = recipe_file::load_k8r(&a.new_recipe)?;
+
+    let (n, old_eng, new_eng) = if a.stream {
+        rotate_stream(&a, &old, &new)?
+    } else {
+        rotate_in_memory(&a, &old, &new)?
+    };
+
+    eprintln!(
+        "arkkey rotate ok: in={} out={} bytes={} old_recipe_id={} new_recipe_id={} old_ticks={} new_ticks={} stream={}",
+        a.r#in,
+        a.out,
+        n,
+        recipe_id_hex(&old),
+        recipe_id_hex(&new),
+        old_eng.stats.ticks,
+        new_eng.stats.ticks,
+        a.stream
+    );
+    Ok(())
+}
+
+/// The .ark embeds its recipe; refuse to "decode" with a different one.
+fn check_embedded(rid: &str, old: &k8dnz_core::Recipe) -> anyhow::Result<()> {
+    let want = recipe_id_hex(old);
+    if rid != want {
+        anyhow::bail!(
+            "ark recipe_id={} does not match --old-recipe recipe_id={}",
+            rid,
+            want
+        );
+    }
+    Ok(())
+}
+
+/// Everything (decode + re-encode) happens in memory; --out is only created once
+/// both keystreams have been produced.
+fn rotate_in_memory(
+    a: &RotateArgs,
+    old: &k8dnz_core::Recipe,
+    new: &k8dnz_core::Recipe,
+) -> anyhow::Result<(u64, Engine, Engine)> {
+    let (rid, _embedded, mut data) = ark::read_ark_with_id(&a.r#in)?;
+    check_embedded(&rid, old)?;
+
+    let mut old_eng = Engine::new(old.clone())?;
+    let ks_old = ark::keystream_bytes(&mut old_eng, data.len(), a.max_ticks)?;
+    let mut new_eng = Engine::new(new.clone())?;
+    let ks_new = ark::keystream_bytes(&mut new_eng, data.len(), a.max_ticks)?;
+
+    // cipher ^ old = plain; plain ^ new = cipher'. Plaintext only exists transiently here.
+    for ((b, ko), kn) in data.iter_mut().zip(ks_old.iter()).zip(ks_new.iter()) {
+        *b ^= ko ^ kn;
+    }
+
+    ark::write_ark(&a.out, new, &data)?;
+    Ok((data.len() as u64, old_eng, new_eng))
+}
+
+/// Block-wise rotation. Output goes to `<out>.tmp` and is renamed on success, so a
+/// failure part-way (e.g. the old keystream running out of ticks) leaves --out untouched.
+fn rotate_stream(
+    a: &RotateArgs,
+    old: &k8dnz_core::Recipe,
+    new: &k8dnz_core::Recipe,
+) -> anyhow::Result<(u64, Engine, Engine)> {
+    if a.block_size == 0 {
+        anyhow::bail!("--block-size must be > 0");
+    }
+
+    let (rid, _embedded, mut rd) = ark::open_ark_stream(&a.r#in)?;
+    check_embedded(&rid, old)?;
+    let total = rd.remaining();
+
+    let tmp = format!("{}.tmp", a.out);
+    let res = (|| -> anyhow::Result<(Engine, Engine)> {
+        let f = std::io::BufWriter::new(std::fs::File::create(&tmp)?);
+        let mut aw = ark::ArkWriter::begin(f, new, total)?;
+
+        let mut old_eng = Engine::new(old.clone())?;
+        let mut new_eng = Engine::new(new.clone())?;
+        let mut old_gen = ark::KeystreamGen::new(&old_eng);
+        let mut new_gen = ark::KeystreamGen::new(&new_eng);
+
+        let mut buf = vec![0u8; a.block_size];
+        let mut ks_old: Vec<u8> = Vec::with_capacity(a.block_size);
+        let mut ks_new: Vec<u8> = Vec::with_capacity(a.block_size);
+        loop {
+            let n = rd.read_data(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            ks_old.clear();
+            old_gen.fill(&mut old_eng, n, a.max_ticks, &mut ks_old, None)?;
+            ks_new.clear();
+            new_gen.fill(&mut new_eng, n, a.max_ticks, &mut ks_new, None)?;
+            for ((b, ko), kn) in buf[..n].iter_mut().zip(ks_old.iter()).zip(ks_new.iter()) {
+                *b ^= ko ^ kn;
+            }
+            aw.write_data(&buf[..n])?;
+        }
+        aw.finish()?;
+        Ok((old_eng, new_eng))
+    })();
+
+    match res {
+        Ok((old_eng, new_eng)) => {
+            std::fs::rename(&tmp, &a.out)?;
+            Ok((total, old_eng, new_eng))
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp);
+            Err(e)
+        }
     }
 }
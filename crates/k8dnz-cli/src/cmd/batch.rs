@@ -0,0 +1,251 @@
+// crates/k8dnz-cli/src/cmd/batch.rs
+//
+// Run a TOML manifest of CLI operations in order:
+//
+//   [[op]]
+//   cmd = "encode"          # any subcommand; nested ones as "timemap fit"
+//   recipe = "r.k8r"        # every other key becomes --key value (`_` -> `-`)
+//   in = "plain.bin"        # true = bare flag, false = omitted, arrays repeat the flag
+//   out = "plain.ark"
+//
+// Each op runs as a child process of this executable, so it sees exactly the same
+// argument parsing as a shell invocation (paths resolve against the current dir).
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Args;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// TOML manifest with one [[op]] table per operation
+    #[arg(long)]
+    pub manifest: String,
+
+    /// Stop at the first failing op (false = run the rest and fail at the end)
+    #[arg(long, action = clap::ArgAction::Set, default_value_t = true)]
+    pub stop_on_error: bool,
+
+    /// Run up to N ops at once. Ops that share any string value (e.g. one writes the
+    /// path another reads) keep manifest order.
+    #[arg(long, default_value_t = 1)]
+    pub parallel_ops: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct BatchOp {
+    /// Subcommand words, e.g. ["timemap", "fit"].
+    pub cmd: Vec<String>,
+    /// Flags after the subcommand.
+    pub argv: Vec<String>,
+    /// Every string value in the op table; used to order dependent ops.
+    pub values: Vec<String>,
+}
+
+impl BatchOp {
+    pub fn label(&self) -> String {
+        self.cmd.join(" ")
+    }
+
+    fn command_line(&self) -> String {
+        let mut parts = self.cmd.clone();
+        parts.extend(self.argv.iter().cloned());
+        parts.join(" ")
+    }
+
+    fn conflicts_with(&self, other: &BatchOp) -> bool {
+        self.values.iter().any(|v| other.values.contains(v))
+    }
+}
+
+pub fn run(args: BatchArgs) -> Result<()> {
+    let text = std::fs::read_to_string(&args.manifest)
+        .with_context(|| format!("read manifest {}", args.manifest))?;
+    let ops = parse_manifest(&text, &args.manifest)?;
+    if ops.is_empty() {
+        bail!("{}: no [[op]] tables", args.manifest);
+    }
+
+    eprintln!(
+        "batch: manifest={} ops={} parallel_ops={} stop_on_error={}",
+        args.manifest,
+        ops.len(),
+        args.parallel_ops.max(1),
+        args.stop_on_error
+    );
+
+    let exe = std::env::current_exe().context("resolve current executable for batch")?;
+    let started = Instant::now();
+    let failed = if args.parallel_ops <= 1 {
+        run_sequential(&exe, &ops, args.stop_on_error)
+    } else {
+        run_parallel(&exe, &ops, args.parallel_ops, args.stop_on_error)
+    };
+
+    if failed.is_empty() {
+        eprintln!(
+            "batch ok: ops={} elapsed_ms={}",
+            ops.len(),
+            started.elapsed().as_millis()
+        );
+        return Ok(());
+    }
+
+    let list: Vec<String> = failed
+        .iter()
+        .map(|&i| format!("{} ({})", i + 1, ops[i].label()))
+        .collect();
+    bail!(
+        "batch failed: {} of {} ops failed: {}",
+        failed.len(),
+        ops.len(),
+        list.join(", ")
+    )
+}
+
+/// Parse `[[op]]` tables into argv form. Only the table shape (a `cmd` string and
+/// supported value types) is checked up front; the child rejects unknown subcommands
+/// and flags when the op runs.
+pub fn parse_manifest(text: &str, path: &str) -> Result<Vec<BatchOp>> {
+    let doc: toml::Table = text
+        .parse()
+        .map_err(|e| anyhow!("{path}: invalid TOML: {e}"))?;
+    let entries = match doc.get("op") {
+        Some(toml::Value::Array(a)) => a.as_slice(),
+        Some(_) => bail!("{path}: `op` must be an array of tables ([[op]])"),
+        None => &[],
+    };
+
+    let mut ops = Vec::with_capacity(entries.len());
+    for (i, e) in entries.iter().enumerate() {
+        let table = e
+            .as_table()
+            .ok_or_else(|| anyhow!("{path}: op[{i}] is not a table"))?;
+        let cmd: Vec<String> = table
+            .get("cmd")
+            .and_then(toml::Value::as_str)
+            .ok_or_else(|| anyhow!("{path}: op[{i}] missing string `cmd`"))?
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        match cmd.first().map(String::as_str) {
+            None => bail!("{path}: op[{i}] has an empty `cmd`"),
+            Some("batch") => bail!("{path}: op[{i}]: nested batch is not supported"),
+            Some(_) => {}
+        }
+
+        let mut argv = Vec::new();
+        let mut values = Vec::new();
+        for (key, value) in table.iter().filter(|(k, _)| k.as_str() != "cmd") {
+            let flag = format!("--{}", key.replace('_', "-"));
+            let items = match value {
+                toml::Value::Array(a) => a.as_slice(),
+                v => std::slice::from_ref(v),
+            };
+            for item in items {
+                match item {
+                    toml::Value::Boolean(true) => argv.push(flag.clone()),
+                    toml::Value::Boolean(false) => {}
+                    toml::Value::String(s) => {
+                        argv.push(flag.clone());
+                        argv.push(s.clone());
+                        values.push(s.clone());
+                    }
+                    toml::Value::Integer(n) => {
+                        argv.push(format!("{flag}={n}"));
+                    }
+                    toml::Value::Float(x) => {
+                        argv.push(format!("{flag}={x}"));
+                    }
+                    _ => bail!("{path}: op[{i}].{key}: unsupported value type"),
+                }
+            }
+        }
+        ops.push(BatchOp { cmd, argv, values });
+    }
+    Ok(ops)
+}
+
+fn op_command(exe: &Path, op: &BatchOp) -> Command {
+    let mut c = Command::new(exe);
+    c.args(&op.cmd).args(&op.argv);
+    c
+}
+
+/// Returns the indices of failed ops.
+fn run_sequential(exe: &Path, ops: &[BatchOp], stop_on_error: bool) -> Vec<usize> {
+    let mut failed = Vec::new();
+    for (i, op) in ops.iter().enumerate() {
+        eprintln!("--- op {}/{}: {} ---", i + 1, ops.len(), op.label());
+        let status = op_command(exe, op).status();
+        if let Err(e) = check_status(op, status) {
+            eprintln!("batch: op {} ({}) failed: {:#}", i + 1, op.label(), e);
+            failed.push(i);
+            if stop_on_error {
+                break;
+            }
+        }
+    }
+    failed
+}
+
+/// Starts ops in manifest order, at most `jobs` at a time; an op waits until no
+/// earlier conflicting op is still running. Output is buffered per op.
+fn run_parallel(exe: &Path, ops: &[BatchOp], jobs: usize, stop_on_error: bool) -> Vec<usize> {
+    let (tx, rx) = mpsc::channel::<(usize, std::io::Result<Output>)>();
+    let mut running: Vec<usize> = Vec::new();
+    let mut failed = Vec::new();
+    let mut next = 0usize;
+
+    thread::scope(|scope| loop {
+        let stopping = stop_on_error && !failed.is_empty();
+        while !stopping
+            && next < ops.len()
+            && running.len() < jobs
+            && !running.iter().any(|&r| ops[r].conflicts_with(&ops[next]))
+        {
+            let (i, tx) = (next, tx.clone());
+            let mut c = op_command(exe, &ops[i]);
+            c.stdin(Stdio::null());
+            scope.spawn(move || {
+                let _ = tx.send((i, c.output()));
+            });
+            running.push(i);
+            next += 1;
+        }
+        if running.is_empty() {
+            break;
+        }
+
+        let (i, res) = rx.recv().expect("batch worker channel");
+        running.retain(|&r| r != i);
+
+        let op = &ops[i];
+        eprintln!("--- op {}/{}: {} ---", i + 1, ops.len(), op.label());
+        let res = res.map(|out| {
+            // Forward the child's bytes untouched; ops may write binary to stdout.
+            let _ = std::io::stdout().write_all(&out.stdout);
+            let _ = std::io::stderr().write_all(&out.stderr);
+            out.status
+        });
+        if let Err(e) = check_status(op, res) {
+            eprintln!("batch: op {} ({}) failed: {:#}", i + 1, op.label(), e);
+            failed.push(i);
+        }
+    });
+
+    failed.sort_unstable();
+    failed
+}
+
+fn check_status(op: &BatchOp, res: std::io::Result<ExitStatus>) -> Result<()> {
+    let status = res.with_context(|| format!("spawn `{}`", op.command_line()))?;
+    if !status.success() {
+        bail!("`{}` exited with {}", op.command_line(), status);
+    }
+    Ok(())
+}
@@ -1,7 +1,12 @@
+use std::io::{BufWriter, Write};
+
 use clap::Args;
 use k8dnz_core::Engine;
 
-use crate::io::ark;
+use crate::cmd::encode::STREAM_BLOCK;
+use crate::io::dry_run::DryRunSink;
+use crate::io::{ark, recipe_file};
+use crate::io::progress::{self, NoProgress, Progress, ProgressReporter};
 
 #[derive(Args)]
 pub struct DecodeFileArgs {
@@ -9,32 +14,111 @@ pub struct DecodeFileArgs {
     #[arg(long)]
     pub r#in: String,
 
-    /// Output decoded file path
-    #[arg(long)]
-    pub out: String,
+    /// Output decoded file path (optional with --dry-run)
+    #[arg(long, required_unless_present = "dry_run")]
+    pub out: Option<String>,
 
     /// Max ticks guard for keystream generation
     #[arg(long, default_value_t = 50_000_000)]
     pub max_ticks: u64,
+
+    /// Print a progress line (bytes done/remaining, MB/s, ETA) to stderr every second.
+    #[arg(long)]
+    pub progress: bool,
+
+    /// Fail unless the recipe embedded in the .ark has this id (hex).
+    #[arg(long)]
+    pub validate_recipe_id: Option<String>,
+
+    /// Run the full decode but discard the output; report success and tick usage.
+    /// --out is not written.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// With --dry-run, also print the SHA-256 of the plaintext that would be written.
+    #[arg(long, requires = "dry_run")]
+    pub dry_run_hash: bool,
 }
 
 pub fn run(args: DecodeFileArgs) -> anyhow::Result<()> {
     // Read the embedded recipe_id directly from the ark payload (no recompute).
     let (rid, recipe, cipher) = ark::read_ark_with_id(&args.r#in)?;
+    recipe_file::validate_recipe_id(&recipe, args.validate_recipe_id.as_deref())?;
 
     let mut engine = Engine::new(recipe.clone())?;
 
-    let key = ark::keystream_bytes(&mut engine, cipher.len(), args.max_ticks)?;
+    // Total is known from the ark header, so the ETA is meaningful from the first report.
+    let mut dry = args.dry_run.then(|| DryRunSink::new(args.dry_run_hash));
+    // Stream into <out>.tmp and rename on success, so a failed run leaves any
+    // existing --out file untouched.
+    let tmp = args.out.as_deref().filter(|_| !args.dry_run).map(|p| format!("{p}.tmp"));
+    let out: Box<dyn Write + '_> = match (dry.as_mut(), tmp.as_deref()) {
+        (Some(sink), _) => Box::new(sink),
+        (None, Some(path)) => Box::new(BufWriter::new(std::fs::File::create(path)?)),
+        (None, None) => anyhow::bail!("--out is required without --dry-run"),
+    };
+    let res = if args.progress {
+        let p = progress::stderr_progress("decode", cipher.len() as u64);
+        decode_stream(&mut engine, &cipher, args.max_ticks, ProgressReporter::new(out, p))
+    } else {
+        decode_stream(&mut engine, &cipher, args.max_ticks, ProgressReporter::new(out, NoProgress))
+    };
+    if let Err(e) = res {
+        if args.dry_run {
+            eprintln!(
+                "decode dry-run: would fail (max_ticks={} ticks={} emissions={}): {e}",
+                args.max_ticks, engine.stats.ticks, engine.stats.emissions
+            );
+        } else if let Some(path) = tmp.as_deref() {
+            let _ = std::fs::remove_file(path);
+        }
+        return Err(e);
+    }
+    if let (Some(path), Some(out)) = (tmp.as_deref(), args.out.as_deref()) {
+        std::fs::rename(path, out)?;
+    }
 
-    let mut plain = cipher;
-    for (p, k) in plain.iter_mut().zip(key.iter()) {
-        *p ^= *k;
+    if let Some(sink) = dry.as_ref() {
+        eprintln!(
+            "decode dry-run ok: would_write_bytes={} ticks={} max_ticks={} within_max_ticks=true emissions={} recipe_id={}{}",
+            sink.written(),
+            engine.stats.ticks,
+            args.max_ticks,
+            engine.stats.emissions,
+            rid,
+            sink.sha256_hex().map(|h| format!(" sha256={h}")).unwrap_or_default()
+        );
+        return Ok(());
     }
 
-    std::fs::write(&args.out, plain)?;
     eprintln!(
         "decode ok: out={} ticks={} emissions={} recipe_id={}",
-        args.out, engine.stats.ticks, engine.stats.emissions, rid
+        args.out.as_deref().unwrap_or_default(),
+        engine.stats.ticks,
+        engine.stats.emissions,
+        rid
     );
     Ok(())
 }
+
+fn decode_stream<W: Write, P: Progress>(
+    engine: &mut Engine,
+    cipher: &[u8],
+    max_ticks: u64,
+    mut sink: ProgressReporter<W, P>,
+) -> anyhow::Result<()> {
+    let mut gen = ark::KeystreamGen::new(engine);
+    let mut key: Vec<u8> = Vec::with_capacity(STREAM_BLOCK);
+    let mut plain: Vec<u8> = Vec::with_capacity(STREAM_BLOCK);
+
+    for block in cipher.chunks(STREAM_BLOCK) {
+        key.clear();
+        gen.fill(engine, block.len(), max_ticks, &mut key, None)?;
+        plain.clear();
+        plain.extend(block.iter().zip(key.iter()).map(|(c, k)| c ^ k));
+        sink.write_all(&plain)?;
+    }
+
+    sink.finish()?;
+    Ok(())
+}
@@ -1,11 +1,19 @@
 // crates/k8dnz-cli/src/cmd/encode.rs
 
+use std::io::{BufWriter, Write};
+
 use clap::{Args, ValueEnum};
+use k8dnz_core::recipe::defaults::{self, DefaultRecipes};
 use k8dnz_core::recipe::recipe::{KeystreamMix, PayloadKind};
 use k8dnz_core::{Engine, Recipe};
 
+use crate::io::progress::{self, NoProgress, Progress, ProgressReporter};
+use crate::io::dry_run::DryRunSink;
 use crate::io::{ark, recipe_file};
 
+/// Keystream/XOR block for streaming encode/decode.
+pub(crate) const STREAM_BLOCK: usize = 4 * 1024;
+
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum Profile {
     /// Uses the tuned default cadence labeling shift (current winner).
@@ -15,10 +23,11 @@ pub enum Profile {
 }
 
 fn profile_shift(p: Profile) -> i64 {
-    match p {
-        Profile::Tuned => 7_141_012,
-        Profile::Baseline => 0,
-    }
+    let preset = match p {
+        Profile::Tuned => DefaultRecipes::Default,
+        Profile::Baseline => DefaultRecipes::Flat,
+    };
+    defaults::get(preset).quant.shift
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -57,14 +66,18 @@ pub struct EncodeArgs {
     #[arg(long)]
     pub r#in: String,
 
-    /// Output .ark path
-    #[arg(long)]
-    pub out: String,
+    /// Output .ark path (optional with --dry-run)
+    #[arg(long, required_unless_present = "dry_run")]
+    pub out: Option<String>,
 
     /// Recipe path (.k8r). If omitted, uses built-in default recipe.
     #[arg(long)]
     pub recipe: Option<String>,
 
+    /// Built-in recipe preset: default, flat, high-entropy, text-aligned, rgb-optimized.
+    #[arg(long, value_parser = recipe_file::parse_preset, conflicts_with = "recipe")]
+    pub recipe_preset: Option<DefaultRecipes>,
+
     /// Convenience profile for qshift selection (only used when --recipe is NOT provided
     /// and --qshift is NOT provided).
     ///
@@ -88,6 +101,11 @@ pub struct EncodeArgs {
     #[arg(long, default_value_t = 50_000_000)]
     pub max_ticks: u64,
 
+    /// Start even when the pre-flight emission estimate is below the input length
+    /// (the estimate is pessimistic; the keystream fill still fails if ticks run out).
+    #[arg(long)]
+    pub skip_budget_check: bool,
+
     /// Optional: dump the USED keystream bytes (mixed if mixing enabled).
     #[arg(long)]
     pub dump_keystream: Option<String>,
@@ -95,21 +113,36 @@ pub struct EncodeArgs {
     /// Optional: dump the RAW cadence keystream bytes (pre-mix).
     #[arg(long)]
     pub dump_raw_keystream: Option<String>,
+
+    /// Print a progress line (bytes done/remaining, MB/s, ETA) to stderr every second.
+    #[arg(long)]
+    pub progress: bool,
+
+    /// Fail unless the recipe id (after --qshift/--keystream-mix/--payload overrides, as
+    /// embedded in the .ark) matches this hex value.
+    #[arg(long)]
+    pub validate_recipe_id: Option<String>,
+
+    /// Run the full encode but discard the output; report the .ark size and tick usage.
+    /// Nothing is written, not even --out.
+    #[arg(long, conflicts_with_all = ["dump_keystream", "dump_raw_keystream"])]
+    pub dry_run: bool,
+
+    /// With --dry-run, also print the SHA-256 of the .ark bytes that would be written.
+    #[arg(long, requires = "dry_run")]
+    pub dry_run_hash: bool,
 }
 
 pub fn run(args: EncodeArgs) -> anyhow::Result<()> {
     let plain = std::fs::read(&args.r#in)?;
 
-    let recipe_from_file = args.recipe.is_some();
-    let mut recipe: Recipe = if let Some(p) = args.recipe.as_deref() {
-        recipe_file::load_k8r(p)?
-    } else {
-        k8dnz_core::recipe::defaults::default_recipe()
-    };
+    let recipe_from_file = args.recipe.is_some() || args.recipe_preset.is_some();
+    let mut recipe: Recipe =
+        recipe_file::load_or_preset(args.recipe.as_deref(), args.recipe_preset)?;
 
     // Precedence:
     // 1) --qshift (explicit override)
-    // 2) if --recipe was provided, keep shift embedded in recipe file
+    // 2) if --recipe / --recipe-preset was provided, keep shift embedded in the recipe
     // 3) otherwise apply --profile convenience shift
     let effective_shift: i64 = if let Some(s) = args.qshift {
         recipe.quant.shift = s;
@@ -130,40 +163,82 @@ pub fn run(args: EncodeArgs) -> anyhow::Result<()> {
         recipe.payload_kind = p.to_core();
     }
 
-    let rid = k8dnz_core::recipe::format::recipe_id_hex(&recipe);
+    let rid = recipe_file::validate_recipe_id(&recipe, args.validate_recipe_id.as_deref())?;
+
+    for w in recipe.validate_deep() {
+        eprintln!("WARN: recipe: {w}");
+    }
 
     let mut engine = Engine::new(recipe.clone())?;
 
-    let (key_used, key_raw_opt) = if args.dump_raw_keystream.is_some() {
-        let (used, raw) = ark::keystream_bytes_with_raw(&mut engine, plain.len(), args.max_ticks)?;
-        (used, Some(raw))
+    let estimated = engine.max_emissions_estimate(args.max_ticks);
+    eprintln!("estimated_emissions={} (need {})", estimated, plain.len());
+    if estimated < plain.len() as u64 {
+        if !args.skip_budget_check {
+            anyhow::bail!(
+                "max_ticks={} looks too low: estimated_emissions={} < need {}; raise --max-ticks (or pass --skip-budget-check)",
+                args.max_ticks,
+                estimated,
+                plain.len()
+            );
+        }
+        eprintln!(
+            "WARN: max_ticks={} may be too low: estimated_emissions={} < need {}",
+            args.max_ticks,
+            estimated,
+            plain.len()
+        );
+    }
+
+    let mut key_used = args.dump_keystream.is_some().then(Vec::new);
+    let mut key_raw = args.dump_raw_keystream.is_some().then(Vec::new);
+
+    let mut dry = args.dry_run.then(|| DryRunSink::new(args.dry_run_hash));
+    // Stream into <out>.tmp and rename on success, so a failed run leaves any
+    // existing --out file untouched.
+    let tmp = args.out.as_deref().filter(|_| !args.dry_run).map(|p| format!("{p}.tmp"));
+    let out: Box<dyn Write + '_> = match (dry.as_mut(), tmp.as_deref()) {
+        (Some(sink), _) => Box::new(sink),
+        (None, Some(path)) => Box::new(BufWriter::new(std::fs::File::create(path)?)),
+        (None, None) => anyhow::bail!("--out is required without --dry-run"),
+    };
+    let res = if args.progress {
+        let total = ark::ark_len(&recipe, plain.len() as u64);
+        let sink = ProgressReporter::new(out, progress::stderr_progress("encode", total));
+        encode_stream(&mut engine, &recipe, &plain, args.max_ticks, sink, &mut key_used, &mut key_raw)
     } else {
-        (
-            ark::keystream_bytes(&mut engine, plain.len(), args.max_ticks)?,
-            None,
-        )
+        let sink = ProgressReporter::new(out, NoProgress);
+        encode_stream(&mut engine, &recipe, &plain, args.max_ticks, sink, &mut key_used, &mut key_raw)
     };
+    if let Err(e) = res {
+        if args.dry_run {
+            eprintln!(
+                "encode dry-run: would fail (max_ticks={} ticks={} emissions={}): {e}",
+                args.max_ticks, engine.stats.ticks, engine.stats.emissions
+            );
+        } else if let Some(path) = tmp.as_deref() {
+            let _ = std::fs::remove_file(path);
+        }
+        return Err(e);
+    }
+    if let (Some(path), Some(out)) = (tmp.as_deref(), args.out.as_deref()) {
+        std::fs::rename(path, out)?;
+    }
 
-    if let Some(path) = args.dump_keystream.as_deref() {
-        std::fs::write(path, &key_used)?;
-        eprintln!("dumped keystream: {} ({} bytes)", path, key_used.len());
+    if let (Some(path), Some(key)) = (args.dump_keystream.as_deref(), key_used.as_deref()) {
+        std::fs::write(path, key)?;
+        eprintln!("dumped keystream: {} ({} bytes)", path, key.len());
     }
 
-    if let (Some(path), Some(raw)) = (args.dump_raw_keystream.as_deref(), key_raw_opt.as_deref()) {
+    if let (Some(path), Some(raw)) = (args.dump_raw_keystream.as_deref(), key_raw.as_deref()) {
         std::fs::write(path, raw)?;
         eprintln!("dumped raw keystream: {} ({} bytes)", path, raw.len());
     }
 
-    // XOR
-    let mut data = plain.clone();
-    for (c, k) in data.iter_mut().zip(key_used.iter()) {
-        *c ^= *k;
-    }
-
-    ark::write_ark(&args.out, &recipe, &data)?;
-
     let profile_label = if args.qshift.is_some() {
         "custom"
+    } else if let Some(p) = args.recipe_preset.filter(|_| args.recipe.is_none()) {
+        p.name()
     } else if recipe_from_file {
         "recipe"
     } else {
@@ -173,10 +248,26 @@ pub fn run(args: EncodeArgs) -> anyhow::Result<()> {
         }
     };
 
+    if let Some(sink) = dry.as_ref() {
+        eprintln!(
+            "encode dry-run ok: in_bytes={} would_write_bytes={} ticks={} max_ticks={} within_max_ticks=true emissions={} profile={} qshift={} recipe_id={}{}",
+            plain.len(),
+            sink.written(),
+            engine.stats.ticks,
+            args.max_ticks,
+            engine.stats.emissions,
+            profile_label,
+            effective_shift,
+            rid,
+            sink.sha256_hex().map(|h| format!(" sha256={h}")).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
     eprintln!(
         "encode ok: in_bytes={} out={} ticks={} emissions={} profile={} qshift={} recipe_id={} mix={:?} payload={:?}",
         plain.len(),
-        args.out,
+        args.out.as_deref().unwrap_or_default(),
         engine.stats.ticks,
         engine.stats.emissions,
         profile_label,
@@ -188,3 +279,35 @@ pub fn run(args: EncodeArgs) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Plaintext is XORed with the keystream block by block and streamed into the .ark,
+/// so only one block of keystream is live at a time (unless a dump was requested).
+fn encode_stream<W: Write, P: Progress>(
+    engine: &mut Engine,
+    recipe: &Recipe,
+    plain: &[u8],
+    max_ticks: u64,
+    sink: ProgressReporter<W, P>,
+    key_used: &mut Option<Vec<u8>>,
+    key_raw: &mut Option<Vec<u8>>,
+) -> anyhow::Result<()> {
+    let mut aw = ark::ArkWriter::begin(sink, recipe, plain.len() as u64)?;
+    let mut gen = ark::KeystreamGen::new(engine);
+    let mut key: Vec<u8> = Vec::with_capacity(STREAM_BLOCK);
+    let mut data: Vec<u8> = Vec::with_capacity(STREAM_BLOCK);
+
+    for block in plain.chunks(STREAM_BLOCK) {
+        key.clear();
+        gen.fill(engine, block.len(), max_ticks, &mut key, key_raw.as_mut())?;
+        if let Some(u) = key_used.as_mut() {
+            u.extend_from_slice(&key);
+        }
+
+        data.clear();
+        data.extend(block.iter().zip(key.iter()).map(|(p, k)| p ^ k));
+        aw.write_data(&data)?;
+    }
+
+    aw.finish()?.finish()?;
+    Ok(())
+}
@@ -2,6 +2,7 @@
 
 use anyhow::{anyhow, bail, Context, Result};
 use clap::Args;
+use std::io::Write;
 
 use crate::cmd::omega::{omega_to_spec, parse_omega_spec};
 use crate::io::recipe_file;
@@ -53,6 +54,21 @@ pub struct Encode2kbArgs {
     #[arg(long)]
     pub omega: Option<String>,
 
+    /// Punctuation alphabet for the punct lane (e.g. "<>/=\"" for XML).
+    /// Default: the built-in prose alphabet. A custom one is stored in the artifact (K8L1 v4).
+    #[arg(long)]
+    pub punct_alphabet: Option<String>,
+
+    /// Code accented Latin letters (U+00C0..U+017E) in the letter lane instead of as raw
+    /// bytes. Only applies to valid UTF-8 input; the artifact is then K8L1 v5.
+    #[arg(long, default_value_t = false)]
+    pub utf8_aware: bool,
+
+    /// Also try DECIMAL_RUN digits: runs of 3+ digits as (length, value mod 1000), kept
+    /// only when smaller than the per-digit and numeric lanes. The artifact is then K8L1 v6.
+    #[arg(long, default_value_t = false)]
+    pub decimal_runs: bool,
+
     /// Optional ApexTrace comparator on the whitespace/class lane.
     ///
     /// This does NOT change the encoded artifact. It only reports whether a
@@ -75,7 +91,7 @@ pub struct Encode2kbArgs {
 
 const MAGIC_K8L1: &[u8; 4] = b"K8L1";
 const K8L1_VERSION_MIN: u8 = 1;
-const K8L1_VERSION_MAX: u8 = 3;
+const K8L1_VERSION_MAX: u8 = lane::K8L1_VERSION_V8;
 
 const PATCH_KIND: u64 = 1;
 const PATCH_CASE: u64 = 2;
@@ -111,6 +127,7 @@ struct K8L1View {
     max_ticks: u64,
     recipe_bytes: Vec<u8>,
     omega_len: usize,
+    punct_len: usize,
     class_patch: Vec<u8>,
     other_patch: Vec<u8>,
     trailing_len: usize,
@@ -161,7 +178,11 @@ pub fn run(args: Encode2kbArgs) -> Result<()> {
     };
     let omega_spec = omega_to_spec(&omega);
 
-    let (artifact, stats, max_ticks_used) = encode_with_retries(
+    // The report below needs the header offsets, so the artifact is kept in memory and
+    // the same bytes go to disk.
+    let mut artifact = Vec::new();
+    let (stats, max_ticks_used) = encode_with_retries(
+        &mut artifact,
         &input,
         &recipe_bytes,
         args.max_ticks,
@@ -170,14 +191,24 @@ pub fn run(args: Encode2kbArgs) -> Result<()> {
         args.auto_mul,
         args.auto_max_ticks,
         omega,
+        args.punct_alphabet.as_deref().map(str::as_bytes),
+        lane::TextLanesV2Config {
+            utf8_aware: args.utf8_aware,
+            decimal_runs: args.decimal_runs,
+        },
     )?;
 
-    std::fs::write(&args.out, &artifact).with_context(|| format!("write {}", args.out))?;
+    let tmp = format!("{}.tmp", args.out);
+    if let Err(e) = std::fs::write(&tmp, &artifact) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e).with_context(|| format!("write {tmp}"));
+    }
+    std::fs::rename(&tmp, &args.out).with_context(|| format!("rename {tmp} -> {}", args.out))?;
 
     let view = decode_k8l1_view(&artifact)?;
-    let bd = decode_patch_breakdown(&view.other_patch).unwrap_or_default();
+    let bd = decode_patch_breakdown(view.ver, view.total_len, &view.other_patch).unwrap_or_default();
 
-    let total_bytes = artifact.len();
+    let total_bytes = stats.artifact_bytes;
     let plain_zstd_bytes = zstd_bytes(&input, args.zstd_level)?;
     let delta_vs_plain_zstd = total_bytes as i64 - plain_zstd_bytes as i64;
 
@@ -187,6 +218,7 @@ pub fn run(args: Encode2kbArgs) -> Result<()> {
     let other_payload = view.other_patch_len;
     let payload_sum = recipe_payload
         .saturating_add(omega_payload)
+        .saturating_add(view.punct_len)
         .saturating_add(class_payload)
         .saturating_add(other_payload);
     let header_overhead = view.consumed_len.saturating_sub(payload_sum);
@@ -202,6 +234,8 @@ pub fn run(args: Encode2kbArgs) -> Result<()> {
     let apex = if args.apex_class_report {
         Some(run_apex_class_report(
             &input,
+            view.ver,
+            view.total_len,
             &view.class_patch,
             args.apex_seed_from,
             args.apex_seed_count,
@@ -247,6 +281,11 @@ pub fn run(args: Encode2kbArgs) -> Result<()> {
         bd.raw_bytes
     );
 
+    println!(
+        "PUNCT alphabet_bytes={} n_punct={} n_raw={} coverage={:.4}",
+        view.punct_len, stats.n_punct, stats.n_raw, stats.punct_coverage
+    );
+
     println!(
         "MISMATCHES class={} other={} | kind={} case={} letter={} digit={} punct={} raw={}",
         stats.class_mismatches,
@@ -259,6 +298,15 @@ pub fn run(args: Encode2kbArgs) -> Result<()> {
         bd.raw
     );
 
+    println!("QUALITY {}", stats.quality_summary());
+
+    let zstd_lanes = if stats.compressed_patches.is_empty() {
+        "<none>".to_string()
+    } else {
+        stats.compressed_patches.join(",")
+    };
+    println!("PATCH_ZSTD lanes={}", zstd_lanes);
+
     if let Some(apex) = &apex {
         println!(
             "APEX_CLASS key_bytes_exact={} patch_entries={} patch_bytes={} total_payload_exact={} matches={} total={} match_pct={:.6} root_quadrant={} root_seed=0x{:016X} recipe_seed=0x{:016X} delta_patch_vs_class_patch={} delta_total_vs_class_patch={}",
@@ -346,7 +394,9 @@ pub fn run(args: Encode2kbArgs) -> Result<()> {
     Ok(())
 }
 
-fn encode_with_retries(
+#[allow(clippy::too_many_arguments)]
+fn encode_with_retries<W: Write>(
+    w: &mut W,
     input: &[u8],
     recipe_bytes: &[u8],
     base_max_ticks: u64,
@@ -355,13 +405,15 @@ fn encode_with_retries(
     mul: u64,
     cap: u64,
     omega: k8dnz_core::lane::OmegaProgram,
-) -> Result<(Vec<u8>, lane::LaneEncodeStats, u64)> {
+    punct_alphabet: Option<&[u8]>,
+    cfg: lane::TextLanesV2Config,
+) -> Result<(lane::LaneEncodeStats, u64)> {
     let mut max_ticks = base_max_ticks.max(1);
     let mut tries = 0u32;
 
     loop {
-        match lane::encode_k8l1_with_omega_prog(input, recipe_bytes, max_ticks, omega.clone()) {
-            Ok((artifact, stats)) => return Ok((artifact, stats, max_ticks)),
+        match lane::write_k8l1_with_config(w, input, recipe_bytes, max_ticks, omega.clone(), punct_alphabet, cfg) {
+            Ok(stats) => return Ok((stats, max_ticks)),
             Err(e) => {
                 let s = e.to_string();
                 let is_insufficient = s.contains("insufficient emissions")
@@ -426,6 +478,23 @@ fn decode_k8l1_view(bytes: &[u8]) -> Result<K8L1View> {
         i += omega_len;
     }
 
+    let mut punct_len = 0usize;
+    if ver >= 4 {
+        punct_len = varint::get_u64(bytes, &mut i)? as usize;
+        if i + punct_len > bytes.len() {
+            bail!("k8l1: punct alphabet oob");
+        }
+        i += punct_len;
+    }
+
+    // v5 text flags
+    if ver >= 5 {
+        if i >= bytes.len() {
+            bail!("k8l1: text flags oob");
+        }
+        i += 1;
+    }
+
     let class_patch_len = varint::get_u64(bytes, &mut i)? as usize;
     if i + class_patch_len > bytes.len() {
         bail!("k8l1: class_patch oob");
@@ -447,6 +516,7 @@ fn decode_k8l1_view(bytes: &[u8]) -> Result<K8L1View> {
         max_ticks,
         recipe_bytes,
         omega_len,
+        punct_len,
         class_patch,
         other_patch,
         trailing_len: bytes.len().saturating_sub(i),
@@ -457,7 +527,7 @@ fn decode_k8l1_view(bytes: &[u8]) -> Result<K8L1View> {
     })
 }
 
-fn decode_patch_breakdown(other_patch: &[u8]) -> Result<PatchBreakdown> {
+fn decode_patch_breakdown(ver: u8, total_len: usize, other_patch: &[u8]) -> Result<PatchBreakdown> {
     let mut out = PatchBreakdown::default();
     let mut i = 0usize;
 
@@ -479,7 +549,7 @@ fn decode_patch_breakdown(other_patch: &[u8]) -> Result<PatchBreakdown> {
         let child = &other_patch[i..i + patch_len];
         i += patch_len;
 
-        let decoded = PatchList::decode(child).map_err(|e| anyhow!("{e}"))?;
+        let decoded = lane::decode_patch_blob(ver, child, total_len).map_err(|e| anyhow!("{e}"))?;
         let entries = decoded.entries.len();
 
         match patch_id {
@@ -533,6 +603,8 @@ fn zstd_bytes(input: &[u8], level: i32) -> Result<usize> {
 
 fn run_apex_class_report(
     input: &[u8],
+    ver: u8,
+    total_len: usize,
     class_patch_bytes: &[u8],
     seed_from: u64,
     seed_count: u64,
@@ -542,7 +614,7 @@ fn run_apex_class_report(
     let norm = text_norm::normalize_newlines(input);
     let ws = WsLanes::split(&norm);
 
-    let _class_patch = PatchList::decode(class_patch_bytes).map_err(|e| anyhow!("{e}"))?;
+    let _class_patch = lane::decode_patch_blob(ver, class_patch_bytes, total_len).map_err(|e| anyhow!("{e}"))?;
     let class_patch_len = class_patch_bytes.len();
 
     if seed_step == 0 {
@@ -6,12 +6,11 @@ use clap::Args;
 use crate::cmd::omega::{omega_to_spec, parse_omega_spec};
 use crate::io::recipe_file;
 use k8dnz_core::lane;
-use k8dnz_core::symbol::patch::PatchList;
 use k8dnz_core::symbol::varint;
 
 const MAGIC_K8L1: &[u8; 4] = b"K8L1";
 const K8L1_VERSION_MIN: u8 = 1;
-const K8L1_VERSION_MAX: u8 = 3;
+const K8L1_VERSION_MAX: u8 = lane::K8L1_VERSION_V8;
 
 const PATCH_KIND: u64 = 1;
 const PATCH_CASE: u64 = 2;
@@ -55,6 +54,8 @@ pub struct LaneSweepArgs {
 
 #[derive(Clone, Debug)]
 struct K8L1View {
+    ver: u8,
+    total_len: usize,
     recipe_len: usize,
     omega_len: usize,
     class_patch_len: usize,
@@ -107,6 +108,8 @@ struct SweepRow {
     punct_mismatches: usize,
     raw_mismatches: usize,
     emissions_needed: u64,
+    /// `LaneEncodeStats::quality_summary`.
+    quality: String,
 }
 
 pub fn run(args: LaneSweepArgs) -> Result<()> {
@@ -137,7 +140,7 @@ pub fn run(args: LaneSweepArgs) -> Result<()> {
         )?;
 
         let view = decode_k8l1_view(&artifact)?;
-        let bd = decode_patch_breakdown(&view.other_patch).unwrap_or_default();
+        let bd = decode_patch_breakdown(view.ver, view.total_len, &view.other_patch).unwrap_or_default();
 
         let plain_zstd_bytes = zstd_bytes(slice, args.zstd_level)?;
         let artifact_bytes = artifact.len();
@@ -172,6 +175,7 @@ pub fn run(args: LaneSweepArgs) -> Result<()> {
             punct_mismatches: bd.punct,
             raw_mismatches: bd.raw,
             emissions_needed: stats.emissions_needed as u64,
+            quality: stats.quality_summary(),
         });
     }
 
@@ -253,7 +257,7 @@ fn decode_k8l1_view(bytes: &[u8]) -> Result<K8L1View> {
         anyhow::bail!("k8l1: unsupported version {}", ver);
     }
 
-    let _total_len = varint::get_u64(bytes, &mut i)? as usize;
+    let total_len = varint::get_u64(bytes, &mut i)? as usize;
     let _other_len = varint::get_u64(bytes, &mut i)? as usize;
     let _max_ticks = varint::get_u64(bytes, &mut i)?;
 
@@ -272,6 +276,22 @@ fn decode_k8l1_view(bytes: &[u8]) -> Result<K8L1View> {
         i += omega_len;
     }
 
+    if ver >= 4 {
+        let punct_len = varint::get_u64(bytes, &mut i)? as usize;
+        if i + punct_len > bytes.len() {
+            anyhow::bail!("k8l1: punct alphabet oob");
+        }
+        i += punct_len;
+    }
+
+    // v5 text flags
+    if ver >= 5 {
+        if i >= bytes.len() {
+            anyhow::bail!("k8l1: text flags oob");
+        }
+        i += 1;
+    }
+
     let class_patch_len = varint::get_u64(bytes, &mut i)? as usize;
     if i + class_patch_len > bytes.len() {
         anyhow::bail!("k8l1: class_patch oob");
@@ -287,6 +307,8 @@ fn decode_k8l1_view(bytes: &[u8]) -> Result<K8L1View> {
     i += other_patch_len;
 
     Ok(K8L1View {
+        ver,
+        total_len,
         recipe_len,
         omega_len,
         class_patch_len,
@@ -297,7 +319,7 @@ fn decode_k8l1_view(bytes: &[u8]) -> Result<K8L1View> {
     })
 }
 
-fn decode_patch_breakdown(other_patch: &[u8]) -> Result<PatchBreakdown> {
+fn decode_patch_breakdown(ver: u8, total_len: usize, other_patch: &[u8]) -> Result<PatchBreakdown> {
     let mut out = PatchBreakdown::default();
     let mut i = 0usize;
 
@@ -319,7 +341,7 @@ fn decode_patch_breakdown(other_patch: &[u8]) -> Result<PatchBreakdown> {
         let child = &other_patch[i..i + patch_len];
         i += patch_len;
 
-        let decoded = PatchList::decode(child).map_err(|e| anyhow!("{e}"))?;
+        let decoded = lane::decode_patch_blob(ver, child, total_len).map_err(|e| anyhow!("{e}"))?;
         let entries = decoded.entries.len();
 
         match patch_id {
@@ -413,6 +435,7 @@ fn print_rows(rows: &[SweepRow]) {
             best_total.max_ticks_used,
             best_total.omega_spec,
         );
+        eprintln!("lane_sweep best_artifact quality: {}", best_total.quality);
     }
 
     if let Some(best_delta) = rows.iter().min_by_key(|row| row.delta_vs_plain_zstd) {
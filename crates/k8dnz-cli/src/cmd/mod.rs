@@ -4,13 +4,20 @@ pub mod analyze;
 pub mod apextrace;
 pub mod ark_inspect;
 pub mod arkkey;
+pub mod batch;
 pub mod decode_file;
 pub mod encode;
 pub mod recipe;
 pub mod regen;
+pub mod score;
 pub mod sim;
 pub mod timemap;
 pub mod tune;
+pub mod tune_genetic;
+pub mod tune_recipe;
+pub mod tune_seed;
+pub mod tune_tournament;
+pub mod util;
 
 pub mod orbexp;
 
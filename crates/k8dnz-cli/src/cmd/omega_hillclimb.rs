@@ -4,6 +4,7 @@ use clap::{Args, ValueEnum};
 
 use crate::cmd::omega::{omega_with_lane_single, parse_omega_spec, LaneName};
 use crate::io::recipe_file;
+use k8dnz_core::error::K8Error;
 use k8dnz_core::lane;
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -102,10 +103,7 @@ fn encode_with_retries(
         match lane::encode_k8l1_with_omega_prog(input, recipe_bytes, max_ticks, omega.clone()) {
             Ok((_artifact, st)) => return Ok((st, max_ticks)),
             Err(e) => {
-                let s = e.to_string();
-                let is_insufficient = s.contains("insufficient emissions")
-                    || s.contains("need 1, got 0")
-                    || s.contains("within max_ticks");
+                let is_insufficient = matches!(e, K8Error::InsufficientEmissions { .. });
 
                 if auto_ticks && is_insufficient && max_ticks < cap {
                     let next = max_ticks.saturating_mul(mul).min(cap);
@@ -233,6 +231,7 @@ pub fn run(args: OmegaHillclimbArgs) -> anyhow::Result<()> {
             let mut best_skip: Option<u64> = None;
             let mut best_bytes: Option<usize> = None;
             let mut best_ticks: u64 = trial_max_ticks;
+            let mut best_quality = String::new();
 
             let mut ok_count: u64 = 0;
             let mut fail_count: u64 = 0;
@@ -278,6 +277,7 @@ pub fn run(args: OmegaHillclimbArgs) -> anyhow::Result<()> {
                             best_bytes = Some(cur);
                             best_skip = Some(skip);
                             best_ticks = ticks_used;
+                            best_quality = st.quality_summary();
                         }
                     }
                     Err(_) => {
@@ -303,6 +303,7 @@ pub fn run(args: OmegaHillclimbArgs) -> anyhow::Result<()> {
                     fail_count,
                     omega_to_spec(&omega)
                 );
+                eprintln!("lane_quality={:?} {}", lane_v, best_quality);
             } else {
                 eprintln!(
                     "lane_done={:?} no_valid_candidate ok={} fail={} omega_unchanged={}",
@@ -318,8 +319,9 @@ pub fn run(args: OmegaHillclimbArgs) -> anyhow::Result<()> {
     eprintln!("final_omega={}", omega_to_spec(&omega));
 
     if args.verify {
-        let (artifact, _st) = lane::encode_k8l1_with_omega_prog(&input, &recipe_bytes, args.max_ticks, omega.clone())
+        let (artifact, st) = lane::encode_k8l1_with_omega_prog(&input, &recipe_bytes, args.max_ticks, omega.clone())
             .map_err(|e| anyhow::anyhow!("{e}"))?;
+        eprintln!("final_quality {}", st.quality_summary());
         let decoded = lane::decode_k8l1(&artifact).map_err(|e| anyhow::anyhow!("{e}"))?;
         let norm = k8dnz_core::repr::text_norm::normalize_newlines(&input);
         if decoded != norm {
@@ -7,6 +7,7 @@ use clap::{Args, ValueEnum};
 
 use crate::cmd::omega::{omega_to_spec, omega_with_lane, parse_omega_spec, LaneName};
 use crate::io::recipe_file;
+use k8dnz_core::error::K8Error;
 use k8dnz_core::lane;
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -122,6 +123,7 @@ struct RowStats {
     total_len: usize,
     other_len: usize,
     emissions_needed: usize,
+    quality: String,
 }
 
 #[derive(Clone, Debug)]
@@ -146,6 +148,8 @@ struct SweepRow {
     max_ticks_used: u64,
     status: String,
     omega_spec: String,
+    /// `LaneEncodeStats::quality_summary` (empty for failed encodes).
+    quality: String,
 }
 
 pub fn run(args: OmegaSweepArgs) -> Result<()> {
@@ -239,6 +243,7 @@ pub fn run(args: OmegaSweepArgs) -> Result<()> {
                                 max_ticks_used: ticks_used,
                                 status: "VERIFY_FAIL".to_string(),
                                 omega_spec,
+                                quality: st.quality,
                             };
                             emit_row(&row, out_fh.as_mut())?;
                             if !args.fail_soft {
@@ -275,6 +280,7 @@ pub fn run(args: OmegaSweepArgs) -> Result<()> {
                         max_ticks_used: ticks_used,
                         status,
                         omega_spec,
+                        quality: st.quality,
                     };
                     emit_row(&row, out_fh.as_mut())?;
 
@@ -322,6 +328,7 @@ pub fn run(args: OmegaSweepArgs) -> Result<()> {
                         max_ticks_used: 0,
                         status: format!("ERROR:{}", e.to_string().replace(',', ";")),
                         omega_spec,
+                        quality: String::new(),
                     };
                     emit_row(&row, out_fh.as_mut())?;
                     if !args.fail_soft {
@@ -362,6 +369,7 @@ pub fn run(args: OmegaSweepArgs) -> Result<()> {
             best.status,
             best.omega_spec,
         );
+        eprintln!("best_quality {}", best.quality);
 
         if let Some(path) = args.best_omega_out.as_deref() {
             std::fs::write(path, best.omega_spec.as_bytes())
@@ -490,14 +498,12 @@ fn encode_with_retries(
                     total_len: st.total_len,
                     other_len: st.other_len,
                     emissions_needed: st.emissions_needed,
+                    quality: st.quality_summary(),
                 };
                 return Ok((artifact, row, max_ticks));
             }
             Err(e) => {
-                let s = e.to_string();
-                let is_insufficient = s.contains("insufficient emissions")
-                    || s.contains("need 1, got 0")
-                    || s.contains("within max_ticks");
+                let is_insufficient = matches!(e, K8Error::InsufficientEmissions { .. });
 
                 if auto_ticks && is_insufficient && max_ticks < cap {
                     let next = max_ticks.saturating_mul(mul).min(cap);
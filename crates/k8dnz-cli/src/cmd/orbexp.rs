@@ -1,7 +1,11 @@
 // crates/k8dnz-cli/src/cmd/orbexp.rs
 
 use clap::{Args, Subcommand, ValueEnum};
-use k8dnz_core::orbexp::{bitlen_u64, compute_first_meet, derive_steps, DeriveMode, OrbParams};
+use k8dnz_core::orbexp::{
+    bitlen_u64, compute_first_meet, compute_multi_meet, derive_steps, simulate_multi_meet,
+    DeriveMode, OrbParams, OrbSimulator,
+};
+use std::io::Write;
 
 #[derive(Args)]
 pub struct OrbExpArgs {
@@ -20,6 +24,16 @@ pub enum OrbExpCmd {
 
     /// Compute a single block (hex) meet-time (debug-friendly).
     One(OneArgs),
+
+    /// N-body (2..=8) meet time: closed form vs simulation, cross-checked.
+    MultiOrbit(MultiOrbitArgs),
+
+    /// Step a two-body orbit tick by tick; optionally write the phase trajectory as CSV.
+    Sim(SimArgs),
+
+    /// Draw a two-body orbit as ASCII: one row per tick over the circle 0..MOD
+    /// (A, C, @ = same cell), with ---MEET--- after every tick where the phases meet.
+    Plot(PlotArgs),
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -58,7 +72,7 @@ pub struct BlockScanArgs {
     #[arg(long, default_value = "0x243f6a8885a308d3")]
     pub p: String,
 
-    /// Derivation mode: int | crc32 | decpairs
+    /// Derivation mode: int | crc32 | decpairs | blake3 | sha256
     #[arg(long, default_value = "int")]
     pub derive: String,
 
@@ -94,7 +108,7 @@ pub struct BandSplitArgs {
     #[arg(long, default_value = "0x243f6a8885a308d3")]
     pub p: String,
 
-    /// Derivation mode: int | crc32 | decpairs
+    /// Derivation mode: int | crc32 | decpairs | blake3 | sha256
     #[arg(long, default_value = "int")]
     pub derive: String,
 
@@ -163,17 +177,318 @@ pub struct OneArgs {
     #[arg(long, default_value = "0x243f6a8885a308d3")]
     pub p: String,
 
-    /// Derivation mode: int | crc32 | decpairs
+    /// Derivation mode: int | crc32 | decpairs | blake3 | sha256
     #[arg(long, default_value = "int")]
     pub derive: String,
 }
 
+#[derive(Args)]
+pub struct MultiOrbitArgs {
+    /// MOD
+    #[arg(long)]
+    pub modn: u64,
+
+    /// Comma-separated per-body steps, e.g. "3,7,13,19" (2..=8 bodies)
+    #[arg(long)]
+    pub steps: String,
+
+    /// Simulation tick limit
+    #[arg(long, default_value_t = 1_000_000)]
+    pub max_ticks: u64,
+}
+
+#[derive(Args)]
+pub struct SimArgs {
+    /// MOD
+    #[arg(long, default_value_t = 4_294_967_291u64)]
+    pub modn: u64,
+
+    /// Step of body A (decimal or 0x... hex)
+    #[arg(long)]
+    pub step_a: String,
+
+    /// Step of body C (decimal or 0x... hex)
+    #[arg(long)]
+    pub step_c: String,
+
+    /// Ticks to simulate
+    #[arg(long, default_value_t = 1000)]
+    pub steps: u64,
+
+    /// Write `t,phase_a,phase_c,meet` rows (t = 0..=steps) to this CSV
+    #[arg(long)]
+    pub output_phases: Option<String>,
+}
+
+#[derive(Args)]
+pub struct PlotArgs {
+    /// MOD (positions on the circle)
+    #[arg(long, default_value_t = 100)]
+    pub modn: u64,
+
+    /// Step of body A (decimal or 0x... hex)
+    #[arg(long)]
+    pub step_a: String,
+
+    /// Step of body C (decimal or 0x... hex)
+    #[arg(long)]
+    pub step_c: String,
+
+    /// Ticks to draw (rows t = 0..=ticks)
+    #[arg(long, default_value_t = 200)]
+    pub ticks: u64,
+
+    /// Characters per row; MOD positions are scaled onto this many cells
+    #[arg(long, default_value_t = 80)]
+    pub width: usize,
+
+    /// Tick rows printed to stdout (the first ones); --output always gets every row
+    #[arg(long, default_value_t = 20)]
+    pub height: usize,
+
+    /// Write the full trajectory (header, every row, summary) to this text file
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+const MULTI_ORBIT_MAX_BODIES: usize = 8;
+
 pub fn run(args: OrbExpArgs) -> anyhow::Result<()> {
     match args.cmd {
         OrbExpCmd::Blockscan(a) => cmd_blockscan(a),
         OrbExpCmd::Bandsplit(a) => cmd_bandsplit(a),
         OrbExpCmd::One(a) => cmd_one(a),
+        OrbExpCmd::MultiOrbit(a) => cmd_multi_orbit(a),
+        OrbExpCmd::Sim(a) => cmd_sim(a),
+        OrbExpCmd::Plot(a) => cmd_plot(a),
+    }
+}
+
+fn cmd_sim(a: SimArgs) -> anyhow::Result<()> {
+    let params = OrbParams {
+        modn: a.modn,
+        step_a: parse_u64_any(&a.step_a)?,
+        step_c: parse_u64_any(&a.step_c)?,
+    };
+    let closed = compute_first_meet(params).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let mut sim = OrbSimulator::new(params).map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let mut csv = match a.output_phases.as_deref() {
+        Some(p) => {
+            let mut w = std::io::BufWriter::new(std::fs::File::create(p)?);
+            writeln!(w, "t,phase_a,phase_c,meet")?;
+            writeln!(w, "0,0,0,1")?;
+            Some(w)
+        }
+        None => None,
+    };
+
+    let mut meets = 0u64;
+    let mut first_meet = None;
+    for _ in 0..a.steps {
+        let meet = sim.tick();
+        if let Some(t) = meet {
+            meets += 1;
+            first_meet.get_or_insert(t);
+        }
+        if let Some(w) = csv.as_mut() {
+            let (pa, pc) = sim.phases();
+            writeln!(w, "{},{},{},{}", sim.ticks(), pa, pc, meet.is_some() as u8)?;
+        }
+    }
+    if let Some(mut w) = csv {
+        w.flush()?;
+    }
+
+    let (pa, pc) = sim.phases();
+    println!("mod         = {}", params.modn);
+    println!("step_a      = {}", params.step_a);
+    println!("step_c      = {}", params.step_c);
+    println!("steps       = {}", a.steps);
+    println!("phases      = ({}, {})", pa, pc);
+    println!("meets       = {}", meets);
+    match first_meet {
+        Some(t) => println!("t_sim       = {}", t),
+        None => println!("t_sim       = none within steps={}", a.steps),
+    }
+    println!("t_closed    = {}", closed.t_first_meet);
+    if let Some(p) = a.output_phases.as_deref() {
+        eprintln!("wrote phases: {} ({} rows)", p, a.steps + 1);
+    }
+
+    match first_meet {
+        Some(t) if closed.t_first_meet != 0 && t != closed.t_first_meet => {
+            anyhow::bail!("closed form {} != simulation {}", closed.t_first_meet, t)
+        }
+        None if closed.t_first_meet != 0 && closed.t_first_meet <= a.steps => {
+            anyhow::bail!("closed form {} but simulation found no meet", closed.t_first_meet)
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Separator emitted after each row whose tick is a meet.
+const PLOT_MEET: &str = "---MEET---";
+
+/// One plot row: `width` cells with A, C, or @ where both land in the same cell.
+fn plot_row(modn: u64, width: usize, pa: u64, pc: u64) -> String {
+    let cell = |p: u64| (p as u128 * width as u128 / modn as u128) as usize;
+    let mut row = vec![b'.'; width];
+    let (ca, cc) = (cell(pa), cell(pc));
+    row[ca] = b'A';
+    row[cc] = if ca == cc { b'@' } else { b'C' };
+    String::from_utf8(row).expect("ascii row")
+}
+
+fn cmd_plot(a: PlotArgs) -> anyhow::Result<()> {
+    if a.width == 0 {
+        anyhow::bail!("--width must be >= 1");
+    }
+    let params = OrbParams {
+        modn: a.modn,
+        step_a: parse_u64_any(&a.step_a)?,
+        step_c: parse_u64_any(&a.step_c)?,
+    };
+    let closed = compute_first_meet(params).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let mut sim =
+        OrbSimulator::with_history_capacity(params, 0).map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let header = vec![
+        format!(
+            "mod={} step_a={} step_c={} d=(step_a-step_c) mod {}={} gcd(mod,d)={}",
+            params.modn, params.step_a, params.step_c, params.modn, closed.d, closed.gcd
+        ),
+        if closed.d == 0 {
+            "period: d == 0, the bodies move in lockstep (they meet every tick)".to_string()
+        } else {
+            format!(
+                "period = mod / gcd(mod, d) = {} / {} = {}",
+                params.modn, closed.gcd, closed.t_first_meet
+            )
+        },
+        format!(
+            "{:>8} positions 0..{} over {} cells ({:.3} per cell)",
+            "t",
+            params.modn - 1,
+            a.width,
+            params.modn as f64 / a.width as f64
+        ),
+    ];
+
+    // Every row, with meet separators; `row_ends[k]` = lines up to and including tick k.
+    let mut lines = vec![format!("{:>8} {}", 0, plot_row(params.modn, a.width, 0, 0))];
+    let mut row_ends = vec![lines.len()];
+    let mut meets: Vec<u64> = Vec::new();
+    for _ in 0..a.ticks {
+        let meet = sim.tick();
+        let (pa, pc) = sim.phases();
+        lines.push(format!(
+            "{:>8} {}",
+            sim.ticks(),
+            plot_row(params.modn, a.width, pa, pc)
+        ));
+        if let Some(t) = meet {
+            meets.push(t);
+            lines.push(format!("{PLOT_MEET} t={t}"));
+        }
+        row_ends.push(lines.len());
+    }
+
+    let summary = format!(
+        "meets={} at t={:?} (closed-form period {})",
+        meets.len(),
+        meets,
+        closed.t_first_meet
+    );
+
+    for l in &header {
+        println!("{l}");
+    }
+    let shown_rows = a.height.min(row_ends.len());
+    let shown = match shown_rows {
+        0 => 0,
+        n => row_ends[n - 1],
+    };
+    for l in &lines[..shown] {
+        println!("{l}");
+    }
+    if shown_rows < row_ends.len() {
+        println!(
+            "... {} more ticks (raise --height or see --output)",
+            row_ends.len() - shown_rows
+        );
     }
+    println!("{summary}");
+
+    if let Some(path) = a.output.as_deref() {
+        let mut w = std::io::BufWriter::new(std::fs::File::create(path)?);
+        for l in header.iter().chain(&lines) {
+            writeln!(w, "{l}")?;
+        }
+        writeln!(w, "{summary}")?;
+        w.flush()?;
+        eprintln!("wrote trajectory: {} ({} ticks)", path, row_ends.len());
+    }
+    Ok(())
+}
+
+fn cmd_multi_orbit(a: MultiOrbitArgs) -> anyhow::Result<()> {
+    let steps = a
+        .steps
+        .split(',')
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .map(parse_u64_any)
+        .collect::<anyhow::Result<Vec<u64>>>()?;
+    if steps.len() < 2 || steps.len() > MULTI_ORBIT_MAX_BODIES {
+        anyhow::bail!(
+            "--steps needs 2..={} bodies, got {}",
+            MULTI_ORBIT_MAX_BODIES,
+            steps.len()
+        );
+    }
+
+    let closed = match compute_multi_meet(a.modn, &steps) {
+        Ok(r) => Some(r),
+        Err(e) => {
+            eprintln!("WARN: closed form unavailable: {e}");
+            None
+        }
+    };
+    let sim = simulate_multi_meet(a.modn, &steps, a.max_ticks).map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    println!("mod         = {}", a.modn);
+    println!("bodies      = {}", steps.len());
+    println!("steps       = {:?}", steps);
+    if let Some(r) = closed {
+        println!("d           = {}", r.d);
+        println!("gcd         = {}", r.gcd);
+        println!(
+            "t_closed    = {} (bitlen={})",
+            r.t_first_meet,
+            bitlen_u64(r.t_first_meet)
+        );
+    }
+    match sim {
+        Some(t) => println!("t_sim       = {}", t),
+        None => println!("t_sim       = none within max_ticks={}", a.max_ticks),
+    }
+
+    match (closed, sim) {
+        (Some(r), Some(t)) if r.t_first_meet == t => println!("check       = ok"),
+        (Some(r), Some(t)) => {
+            anyhow::bail!("closed form {} != simulation {}", r.t_first_meet, t)
+        }
+        (Some(r), None) if r.t_first_meet > a.max_ticks => {
+            println!("check       = skipped (t_closed > max_ticks)")
+        }
+        (Some(r), None) => {
+            anyhow::bail!("closed form {} but simulation found no meet", r.t_first_meet)
+        }
+        (None, _) => println!("check       = skipped (no closed form)"),
+    }
+    Ok(())
 }
 
 fn cmd_one(a: OneArgs) -> anyhow::Result<()> {
@@ -1,7 +1,9 @@
 // crates/k8dnz-cli/src/cmd/recipe.rs
 
+use anyhow::Context;
 use clap::{Args, Subcommand};
 use k8dnz_core::recipe::format as recipe_format;
+use k8dnz_core::recipe::random::random_recipe;
 use k8dnz_core::Recipe;
 
 use crate::io::recipe_file;
@@ -16,6 +18,14 @@ pub struct RecipeArgs {
 pub enum RecipeCmd {
     /// Print all recipe fields (human readable) and warn on degenerate ranges
     Inspect(InspectArgs),
+    /// Blend two recipes: numeric fields lerp by --t, the rest comes from --a
+    Interpolate(InterpolateArgs),
+    /// Print a recipe as URL-safe base64 (same bytes as the .k8r file)
+    ToBase64(ToBase64Args),
+    /// Decode a base64 recipe string; prints its recipe_id and optionally writes a .k8r
+    FromBase64(FromBase64Args),
+    /// Reproducible random recipe(s) from --seed (random quant/clamp/seed, default orbits)
+    GenRandom(GenRandomArgs),
 }
 
 #[derive(Args)]
@@ -25,10 +35,175 @@ pub struct InspectArgs {
     pub recipe: String,
 }
 
+#[derive(Args)]
+pub struct InterpolateArgs {
+    /// Recipe at t=0.0 (.k8r); non-numeric fields are copied from here
+    #[arg(long)]
+    pub a: String,
+
+    /// Recipe at t=1.0 (.k8r)
+    #[arg(long)]
+    pub b: String,
+
+    /// Blend position in [0.0, 1.0]
+    #[arg(long, default_value_t = 0.5)]
+    pub t: f64,
+
+    /// Output recipe path (.k8r)
+    #[arg(long)]
+    pub out: String,
+}
+
+#[derive(Args)]
+pub struct ToBase64Args {
+    /// Recipe path (.k8r)
+    #[arg(long)]
+    pub r#in: String,
+}
+
+#[derive(Args)]
+pub struct FromBase64Args {
+    /// Base64 recipe string (as printed by `recipe to-base64`)
+    #[arg(long)]
+    pub str: String,
+
+    /// Output recipe path (.k8r); omit to only validate and print the recipe_id
+    #[arg(long)]
+    pub out: Option<String>,
+}
+
+#[derive(Args)]
+pub struct GenRandomArgs {
+    /// PRNG seed; the same seed gives the same recipe_id on every platform
+    #[arg(long)]
+    pub seed: u64,
+
+    /// Output recipe path (.k8r). With --count > 1, recipe i goes to <stem>_<i>.<ext>
+    /// and uses seed + i.
+    #[arg(long)]
+    pub out: String,
+
+    /// Number of recipes to generate
+    #[arg(long, default_value_t = 1)]
+    pub count: usize,
+}
+
 pub fn run(args: RecipeArgs) -> anyhow::Result<()> {
     match args.cmd {
         RecipeCmd::Inspect(a) => cmd_inspect(a),
+        RecipeCmd::Interpolate(a) => cmd_interpolate(a),
+        RecipeCmd::ToBase64(a) => cmd_to_base64(a),
+        RecipeCmd::FromBase64(a) => cmd_from_base64(a),
+        RecipeCmd::GenRandom(a) => cmd_gen_random(a),
+    }
+}
+
+fn cmd_gen_random(a: GenRandomArgs) -> anyhow::Result<()> {
+    if a.count == 0 {
+        anyhow::bail!("--count must be >= 1");
     }
+    for i in 0..a.count {
+        let seed = a.seed.wrapping_add(i as u64);
+        let (r, attempts) = random_recipe(seed);
+        // The chosen candidate must validate cleanly and survive an encode/decode
+        // round trip with the same recipe_id before anything is written.
+        let warnings = r.validate_deep();
+        if !warnings.is_empty() {
+            let list: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+            anyhow::bail!(
+                "gen-random seed={}: no clean recipe after {} attempts: {}",
+                seed,
+                attempts,
+                list.join("; ")
+            );
+        }
+        let bytes = recipe_format::encode(&r);
+        let rid = recipe_format::recipe_id_hex(&r);
+        let back = recipe_format::decode(&bytes)?;
+        if recipe_format::recipe_id_hex(&back) != rid {
+            anyhow::bail!("gen-random seed={}: recipe_id changed across encode/decode", seed);
+        }
+
+        let out = if a.count == 1 {
+            a.out.clone()
+        } else {
+            indexed_path(&a.out, i)
+        };
+        std::fs::write(&out, &bytes).with_context(|| format!("write recipe {out}"))?;
+        eprintln!(
+            "gen-random ok: seed={} out={} recipe_id={} attempts={} quant=[{},{}] shift={} clamp=[{},{}]",
+            seed,
+            out,
+            rid,
+            attempts,
+            r.quant.min,
+            r.quant.max,
+            r.quant.shift,
+            r.field_clamp.min,
+            r.field_clamp.max
+        );
+    }
+    Ok(())
+}
+
+/// `dir/base.k8r` -> `dir/base_<i>.k8r` (no extension -> `base_<i>`).
+fn indexed_path(out: &str, i: usize) -> String {
+    let p = std::path::Path::new(out);
+    let stem = p.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    let name = match p.extension() {
+        Some(ext) => format!("{}_{}.{}", stem, i, ext.to_string_lossy()),
+        None => format!("{}_{}", stem, i),
+    };
+    p.with_file_name(name).to_string_lossy().into_owned()
+}
+
+fn cmd_to_base64(a: ToBase64Args) -> anyhow::Result<()> {
+    let r = recipe_file::load_k8r(&a.r#in)?;
+    let b64 = r.to_base64();
+    eprintln!(
+        "to-base64 ok: in={} recipe_id={} chars={}",
+        a.r#in,
+        recipe_format::recipe_id_hex(&r),
+        b64.len()
+    );
+    println!("{b64}");
+    Ok(())
+}
+
+fn cmd_from_base64(a: FromBase64Args) -> anyhow::Result<()> {
+    let rid = recipe_format::recipe_id_hex_from_base64(&a.str)
+        .map_err(|e| anyhow::anyhow!("from-base64: {e}"))?;
+    if let Some(out) = &a.out {
+        let r = Recipe::from_base64(&a.str).map_err(|e| anyhow::anyhow!("from-base64: {e}"))?;
+        recipe_file::save_k8r(out, &r)?;
+    }
+    eprintln!(
+        "from-base64 ok: out={} recipe_id={}",
+        a.out.as_deref().unwrap_or("<none>"),
+        rid
+    );
+    Ok(())
+}
+
+fn cmd_interpolate(a: InterpolateArgs) -> anyhow::Result<()> {
+    let ra = recipe_file::load_k8r(&a.a)?;
+    let rb = recipe_file::load_k8r(&a.b)?;
+    let r = Recipe::interpolate(&ra, &rb, a.t).map_err(|e| anyhow::anyhow!("{e}"))?;
+    recipe_file::save_k8r(&a.out, &r)?;
+
+    eprintln!(
+        "interpolate ok: t={} out={} recipe_id={} seed={} quant=[{},{}] shift={} clamp=[{},{}]",
+        a.t,
+        a.out,
+        recipe_format::recipe_id_hex(&r),
+        r.seed,
+        r.quant.min,
+        r.quant.max,
+        r.quant.shift,
+        r.field_clamp.min,
+        r.field_clamp.max
+    );
+    Ok(())
 }
 
 fn cmd_inspect(a: InspectArgs) -> anyhow::Result<()> {
@@ -71,10 +246,12 @@ fn cmd_inspect(a: InspectArgs) -> anyhow::Result<()> {
     // Clamp/quant (these are the knobs most likely to cause “all zero” streams)
     println!("field_clamp.min = {:?}", r.field_clamp.min);
     println!("field_clamp.max = {:?}", r.field_clamp.max);
+    println!("field_clamp.soft_knee = {:?}", r.field_clamp.soft_knee);
 
     println!("quant.min    = {:?}", r.quant.min);
     println!("quant.max    = {:?}", r.quant.max);
     println!("quant.shift  = {:?}", r.quant.shift);
+    println!("quant_gamma  = {:?}", r.quant_gamma);
 
     // RGB config (print Debug; avoids Display constraints)
     println!("rgb.backend  = {:?}", r.rgb.backend);
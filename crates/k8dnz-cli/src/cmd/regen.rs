@@ -1,4 +1,5 @@
 use clap::Args;
+use k8dnz_core::signal::token::PairToken;
 use k8dnz_core::Engine;
 
 use crate::io::{bin, jsonl, recipe_file};
@@ -24,13 +25,32 @@ pub struct RegenArgs {
     /// Output file path; if omitted, prints to stdout (jsonl only).
     #[arg(long)]
     pub output: Option<String>,
+
+    /// Compare the regenerated stream (packed bytes, as `--out bin` writes them)
+    /// against this reference file instead of writing output. Fails on any mismatch.
+    #[arg(long)]
+    pub verify: Option<String>,
+
+    /// With --verify: report up to N mismatching positions before giving up.
+    #[arg(long, default_value_t = 1)]
+    pub max_mismatches: usize,
+
+    /// Fail unless the loaded recipe's id (hex, as printed by every run) matches.
+    #[arg(long)]
+    pub validate_recipe_id: Option<String>,
 }
 
 pub fn run(args: RegenArgs) -> anyhow::Result<()> {
     let recipe = recipe_file::load_k8r(&args.recipe)?;
+    let rid = recipe_file::validate_recipe_id(&recipe, args.validate_recipe_id.as_deref())?;
+    eprintln!("regen: recipe={} recipe_id={}", args.recipe, rid);
     let mut engine = Engine::new(recipe)?;
     let toks = engine.run_emissions(args.emissions, args.max_ticks);
 
+    if let Some(reference) = args.verify.as_deref() {
+        return verify(reference, &toks, args.max_mismatches);
+    }
+
     match args.out.as_str() {
         "jsonl" => {
             if let Some(p) = args.output.as_deref() {
@@ -51,3 +71,43 @@ pub fn run(args: RegenArgs) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn verify(reference: &str, toks: &[PairToken], max_mismatches: usize) -> anyhow::Result<()> {
+    let want = std::fs::read(reference)?;
+    let got: Vec<u8> = toks.iter().map(|t| t.pack_n16()).collect();
+
+    let mut reported = 0usize;
+    let mut total = 0usize;
+    for (pos, (&w, &g)) in want.iter().zip(got.iter()).enumerate() {
+        if w == g {
+            continue;
+        }
+        total += 1;
+        if reported < max_mismatches.max(1) {
+            eprintln!("mismatch pos={} want=0x{:02x} got=0x{:02x}", pos, w, g);
+            reported += 1;
+        }
+    }
+
+    if want.len() != got.len() {
+        eprintln!(
+            "len mismatch: reference={} regenerated={}",
+            want.len(),
+            got.len()
+        );
+    }
+
+    if total == 0 && want.len() == got.len() {
+        eprintln!("verify ok: reference={} bytes={}", reference, got.len());
+        return Ok(());
+    }
+
+    anyhow::bail!(
+        "verify failed: reference={} mismatches={} reported={} reference_len={} regenerated_len={}",
+        reference,
+        total,
+        reported,
+        want.len(),
+        got.len()
+    )
+}
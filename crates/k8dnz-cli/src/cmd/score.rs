@@ -0,0 +1,164 @@
+// crates/k8dnz-cli/src/cmd/score.rs
+//
+// Recompute the fit-xor-chunked scoreboard for an existing
+// (timemap, residual, recipe) triple without re-running the fit.
+
+use clap::{Args, ValueEnum};
+use std::io::Cursor;
+
+use crate::io::timemap;
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum ScoreFmt {
+    /// key = value lines (same labels as the fit scoreboard)
+    Text,
+    /// One JSON object
+    Json,
+}
+
+#[derive(Args, Debug)]
+pub struct ScoreArgs {
+    /// Recipe path (.k8r); only its on-disk size is scored.
+    #[arg(long)]
+    pub recipe: String,
+
+    /// Timing map (TM0/TM1/TM2); re-encoded with the same auto format the fitters write.
+    #[arg(long)]
+    pub timemap: String,
+
+    /// Residual bytes written by the fit.
+    #[arg(long)]
+    pub residual: String,
+
+    /// Optional original file for the plain_zstd baseline.
+    #[arg(long)]
+    pub plain: Option<String>,
+
+    #[arg(long, default_value_t = 3)]
+    pub zstd_level: i32,
+
+    #[arg(long, value_enum, default_value_t = ScoreFmt::Text)]
+    pub fmt: ScoreFmt,
+}
+
+#[derive(Clone, Debug)]
+struct Scoreboard {
+    recipe_raw: usize,
+    plain_raw: Option<usize>,
+    plain_zstd: Option<usize>,
+    tm_raw: usize,
+    tm_zstd: usize,
+    resid_raw: usize,
+    resid_zstd: usize,
+    effective_no_recipe: usize,
+    effective_with_recipe: usize,
+}
+
+pub fn run(args: ScoreArgs) -> anyhow::Result<()> {
+    let recipe_raw = std::fs::read(&args.recipe)?.len();
+
+    let tm = timemap::read_timemap(&args.timemap)?;
+    let tm_bytes = tm.encode_auto();
+    let residual = std::fs::read(&args.residual)?;
+
+    if tm.indices.len() != residual.len() {
+        eprintln!(
+            "note: timemap/residual len mismatch: tm={} resid={}",
+            tm.indices.len(),
+            residual.len()
+        );
+    }
+
+    // Match fit-xor-chunked: the baseline covers only the produced prefix.
+    let (plain_raw, plain_zstd) = match args.plain.as_deref() {
+        Some(path) => {
+            let plain = std::fs::read(path)?;
+            let produced = plain.len().min(residual.len());
+            if produced != plain.len() {
+                eprintln!(
+                    "note: partial output produced_bytes={} target_bytes={}",
+                    produced,
+                    plain.len()
+                );
+            }
+            (Some(produced), Some(zstd_size(&plain[..produced], args.zstd_level)?))
+        }
+        None => (None, None),
+    };
+
+    let tm_zstd = zstd_size(&tm_bytes, args.zstd_level)?;
+    let resid_zstd = zstd_size(&residual, args.zstd_level)?;
+    let effective_no_recipe = tm_zstd.saturating_add(resid_zstd);
+
+    let sb = Scoreboard {
+        recipe_raw,
+        plain_raw,
+        plain_zstd,
+        tm_raw: tm_bytes.len(),
+        tm_zstd,
+        resid_raw: residual.len(),
+        resid_zstd,
+        effective_no_recipe,
+        effective_with_recipe: recipe_raw.saturating_add(effective_no_recipe),
+    };
+
+    match args.fmt {
+        ScoreFmt::Text => print!("{}", render_text(&sb)),
+        ScoreFmt::Json => println!("{}", render_json(&sb)),
+    }
+
+    Ok(())
+}
+
+fn delta(effective: usize, plain_zstd: Option<usize>) -> Option<i64> {
+    plain_zstd.map(|p| (effective as i64) - (p as i64))
+}
+
+fn render_text(sb: &Scoreboard) -> String {
+    let mut s = String::new();
+    s.push_str("--- scoreboard ---\n");
+    s.push_str(&format!("recipe_raw_bytes           = {}\n", sb.recipe_raw));
+    if let (Some(raw), Some(z)) = (sb.plain_raw, sb.plain_zstd) {
+        s.push_str(&format!("plain_raw_bytes            = {}\n", raw));
+        s.push_str(&format!("plain_zstd_bytes           = {}\n", z));
+    }
+    s.push_str(&format!("tm_raw_bytes               = {}\n", sb.tm_raw));
+    s.push_str(&format!("tm_zstd_bytes              = {}\n", sb.tm_zstd));
+    s.push_str(&format!("resid_raw_bytes            = {}\n", sb.resid_raw));
+    s.push_str(&format!("resid_zstd_bytes           = {}\n", sb.resid_zstd));
+    s.push_str(&format!("effective_bytes_no_recipe  = {}\n", sb.effective_no_recipe));
+    s.push_str(&format!("effective_bytes_with_recipe= {}\n", sb.effective_with_recipe));
+    if let Some(d) = delta(sb.effective_no_recipe, sb.plain_zstd) {
+        s.push_str(&format!("delta_vs_plain_zstd_no_recipe  = {}\n", d));
+    }
+    if let Some(d) = delta(sb.effective_with_recipe, sb.plain_zstd) {
+        s.push_str(&format!("delta_vs_plain_zstd_with_recipe= {}\n", d));
+    }
+    s
+}
+
+fn render_json(sb: &Scoreboard) -> String {
+    fn opt<T: std::fmt::Display>(v: Option<T>) -> String {
+        v.map(|x| x.to_string()).unwrap_or_else(|| "null".to_string())
+    }
+
+    format!(
+        "{{\"recipe_raw\":{},\"plain_raw\":{},\"plain_zstd\":{},\"tm_raw\":{},\"tm_zstd\":{},\"resid_raw\":{},\"resid_zstd\":{},\"effective_no_recipe\":{},\"effective_with_recipe\":{},\"delta_vs_plain_zstd_no_recipe\":{},\"delta_vs_plain_zstd_with_recipe\":{}}}",
+        sb.recipe_raw,
+        opt(sb.plain_raw),
+        opt(sb.plain_zstd),
+        sb.tm_raw,
+        sb.tm_zstd,
+        sb.resid_raw,
+        sb.resid_zstd,
+        sb.effective_no_recipe,
+        sb.effective_with_recipe,
+        opt(delta(sb.effective_no_recipe, sb.plain_zstd)),
+        opt(delta(sb.effective_with_recipe, sb.plain_zstd)),
+    )
+}
+
+fn zstd_size(bytes: &[u8], level: i32) -> anyhow::Result<usize> {
+    let out = zstd::stream::encode_all(Cursor::new(bytes), level)?;
+    Ok(out.len())
+}
@@ -1,12 +1,15 @@
 // crates/k8dnz-cli/src/cmd/sim.rs
 
+use anyhow::Context;
 use clap::{Args, ValueEnum};
 use k8dnz_core::dynamics::engine::{EmissionField, FieldRangeStats};
+use k8dnz_core::recipe::defaults::{self, DefaultRecipes};
 use k8dnz_core::recipe::recipe::RgbRecipe;
-use k8dnz_core::signal::rgb_emit::emit_rgbpair_from_fields;
+use k8dnz_core::signal::rgb_emit::{emit_hsv_pair_from_params, emit_rgbpair_from_fields};
 use k8dnz_core::signal::token::{PairToken, Rgb, RgbPairToken};
 use k8dnz_core::{Engine, Recipe};
 
+use super::util::parse_u64_range;
 use crate::io::{bin, jsonl, recipe_file};
 
 use std::time::Instant;
@@ -18,6 +21,17 @@ pub enum SimOutFmt {
     /// Packed bytes (pair): byte = (a<<4) | b
     /// Packed bytes (rgbpair): 6 bytes per emission: A.rgb then C.rgb
     Bin,
+    /// Fixed-width pair table (pair mode only):
+    /// `idx: A=0xN B=0xN byte=0xNN char=c`, `*` marks --highlight rows
+    Table,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PlotMode {
+    /// 16x16 grid: row = A nibble, column = B nibble
+    Grid,
+    /// 256 horizontal bars, one per packed byte
+    Bar,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
@@ -37,10 +51,11 @@ pub enum Profile {
 }
 
 fn profile_shift(p: Profile) -> i64 {
-    match p {
-        Profile::Tuned => 7_141_012,
-        Profile::Baseline => 0,
-    }
+    let preset = match p {
+        Profile::Tuned => DefaultRecipes::Default,
+        Profile::Baseline => DefaultRecipes::Flat,
+    };
+    defaults::get(preset).quant.shift
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -51,6 +66,14 @@ pub enum RgbBackend {
     Dna,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RgbModel {
+    /// Field-driven linear RGB law (see --rgb-backend)
+    Field,
+    /// Hue-angle phase per dot, converted to RGB at emission time
+    Hsv,
+}
+
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum RgbAlt {
     None,
@@ -63,6 +86,10 @@ pub struct SimArgs {
     #[arg(long)]
     pub recipe: Option<String>,
 
+    /// Built-in recipe preset: default, flat, high-entropy, text-aligned, rgb-optimized.
+    #[arg(long, value_parser = recipe_file::parse_preset, conflicts_with = "recipe")]
+    pub recipe_preset: Option<DefaultRecipes>,
+
     /// Save the effective recipe (after SIM-only overrides; or best candidate in --qsearch) to this .k8r path.
     #[arg(long)]
     pub save_recipe: Option<String>,
@@ -83,6 +110,16 @@ pub struct SimArgs {
     #[arg(long, default_value_t = 5_000_000)]
     pub max_ticks: u64,
 
+    /// Resume from an engine state file (written by --export-state) instead of tick 0.
+    /// The state must embed the same recipe id as this run. --max-ticks stays cumulative.
+    /// Rejected together with the analysis modes (--qsearch, --period-detect, ...).
+    #[arg(long)]
+    pub import_state: Option<String>,
+
+    /// After the final emission, write the engine state here so a later run can resume it.
+    #[arg(long)]
+    pub export_state: Option<String>,
+
     /// Output format
     #[arg(long, value_enum, default_value_t = SimOutFmt::Jsonl)]
     pub fmt: SimOutFmt,
@@ -91,14 +128,32 @@ pub struct SimArgs {
     #[arg(long, value_enum, default_value_t = SimMode::Pair)]
     pub mode: SimMode,
 
+    /// Print a structured engine description (Engine::describe) before running.
+    #[arg(long)]
+    pub verbose: bool,
+
     /// Output path (required for --fmt bin). For qsearch, writes ONE run with the best shift.
     #[arg(long)]
     pub out: Option<String>,
 
+    /// Write the field values behind each emission (raw + clamped, both dots)
+    /// instead of tokens. jsonl: one object per emission; bin: 4 x i64 LE per emission.
+    #[arg(long)]
+    pub output_raw_fields: bool,
+
     /// Print distribution stats / field clamp stats
     #[arg(long)]
     pub stats: bool,
 
+    /// Decimated sampling: keep every N-th emission (skipping N-1 between kept tokens)
+    /// until --emissions tokens are kept. Skipped emissions are never sampled.
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with_all = ["output_raw_fields", "rgb_from_field"]
+    )]
+    pub sample_every_k: Option<u64>,
+
     // --- SIM-only overrides (do NOT mutate recipe on disk) ---
     /// Override quant min (i64)
     #[arg(long)]
@@ -120,12 +175,37 @@ pub struct SimArgs {
     #[arg(long)]
     pub clamp_max: Option<i64>,
 
+    /// Soft-saturate field samples within this many units of clamp min/max instead of
+    /// hard clipping (0 = hard clamp). Bumps the recipe to format v6.
+    #[arg(long)]
+    pub clamp_soft_knee: Option<u32>,
+
     // --- RGBPAIR: true field-based emission (cone/DNA law) ---
     /// If set, --mode rgbpair uses emission-time FIELD samples (clamped) to drive RGB law,
     /// instead of the palette16 mapping in PairToken::to_rgb_pair().
     #[arg(long)]
     pub rgb_from_field: bool,
 
+    /// RGB emission model used with --rgb-from-field: field (linear RGB) or hsv (hue phase)
+    #[arg(long, value_enum, default_value_t = RgbModel::Field)]
+    pub rgb_model: RgbModel,
+
+    /// HSV model: hue step (degrees) per emission for dot A
+    #[arg(long, default_value_t = 7)]
+    pub rgb_hsv_step_a: u16,
+
+    /// HSV model: hue step (degrees) per emission for dot C
+    #[arg(long, default_value_t = 3)]
+    pub rgb_hsv_step_c: u16,
+
+    /// HSV model: saturation (0..=255)
+    #[arg(long, default_value_t = 200)]
+    pub rgb_saturation: u8,
+
+    /// HSV model: value/brightness (0..=255)
+    #[arg(long, default_value_t = 220)]
+    pub rgb_value: u8,
+
     /// RGB backend: cone or dna
     #[arg(long, value_enum, default_value_t = RgbBackend::Dna)]
     pub rgb_backend: RgbBackend,
@@ -170,23 +250,133 @@ pub struct SimArgs {
     /// Per-candidate max ticks (defaults to 20,000,000 if omitted).
     #[arg(long)]
     pub qsearch_max_ticks: Option<u64>,
+
+    /// Also search the quant range width: min:max:step (inclusive). Each width keeps the
+    /// range centered and scales quant.shift proportionally, then runs the shift search
+    /// around it (default step = width/32).
+    #[arg(long, requires = "qsearch")]
+    pub qsearch_width_range: Option<String>,
+
+    // --- PERIOD DETECT (autocorrelation of the packed byte stream) ---
+    /// Look for sub-cycle periodicity: report lags whose autocorrelation exceeds 0.9.
+    #[arg(long)]
+    pub period_detect: bool,
+
+    /// Largest lag to test.
+    #[arg(long, default_value_t = 65_536)]
+    pub max_period: usize,
+
+    /// Emissions to sample for period detection (uses --max-ticks as the guard).
+    #[arg(long, default_value_t = 100_000)]
+    pub detect_emissions: u64,
+    // --- STAT TEST (built-in randomness battery on the packed byte stream) ---
+    /// Run chi2 / runs / serial-correlation / ApEn tests and print a pass/fail report.
+    #[arg(long)]
+    pub stat_test: bool,
+
+    /// Emissions to sample for --stat-test (uses --max-ticks as the guard).
+    #[arg(long, default_value_t = 100_000)]
+    pub stat_test_emissions: u64,
+    // --- TPE STATS (ticks between emissions) ---
+    /// Print mean/stddev/min/max ticks per emission (high stddev = bursty emissions).
+    #[arg(long)]
+    pub tpe_stats: bool,
+
+    /// Emissions to sample for --tpe-stats (tick budget scales with the recipe's period).
+    #[arg(long, default_value_t = 10_000)]
+    pub tpe_emissions: u64,
+    // --- CORRELATION TEST (engine bytes vs a target file) ---
+    /// Print Pearson r and Spearman rho (with p-values) between the packed byte stream
+    /// and --other. Generates exactly one emission per byte of --other (uses --max-ticks).
+    #[arg(long)]
+    pub correlation_test: bool,
+
+    /// Target file for --correlation-test.
+    #[arg(long)]
+    pub other: Option<String>,
+    // --- DIFF RECIPE (byte-level stream comparison) ---
+    /// Compare this run's recipe against another .k8r over --emissions emissions:
+    /// prints changed fields, diff_rate and the first differing positions.
+    #[arg(long)]
+    pub diff_recipe: Option<String>,
+
+    // --- PAIR TABLE (human-readable debug view) ---
+    /// Shorthand for --fmt table.
+    #[arg(long)]
+    pub emit_pairs: bool,
+
+    /// Table output path for --fmt table; falls back to --out, then stdout.
+    #[arg(long)]
+    pub out_table: Option<String>,
+
+    /// Mark table rows whose packed byte equals this value with `*`.
+    #[arg(long)]
+    pub highlight: Option<u8>,
+
+    // --- DISTRIBUTION PLOT (pair mode) ---
+    /// Write an ASCII plot of the packed-byte histogram instead of the token stream
+    /// (to --out-plot, else --out, else stdout).
+    #[arg(long)]
+    pub plot_distribution: bool,
+
+    /// Plot layout for --plot-distribution.
+    #[arg(long, value_enum, default_value_t = PlotMode::Grid)]
+    pub plot_mode: PlotMode,
+
+    /// Plot characters, lowest to highest frequency. A leading space is the zero level;
+    /// zero counts always plot as a space.
+    #[arg(long, default_value = PLOT_PALETTE_DEFAULT)]
+    pub plot_palette: String,
+
+    /// Plot output path; falls back to --out, then stdout.
+    #[arg(long)]
+    pub out_plot: Option<String>,
 }
 
-pub fn run(args: SimArgs) -> anyhow::Result<()> {
-    // Load recipe (from file if provided, else default).
-    let mut recipe: Recipe = if let Some(path) = args.recipe.as_deref() {
-        recipe_file::load_k8r(path)?
-    } else {
-        k8dnz_core::recipe::defaults::default_recipe()
-    };
+const PLOT_PALETTE_DEFAULT: &str = " .,:;!|%#@";
+const PLOT_BAR_WIDTH: usize = 64;
+
+pub fn run(mut args: SimArgs) -> anyhow::Result<()> {
+    if args.emit_pairs {
+        args.fmt = SimOutFmt::Table;
+    }
+    if !matches!(args.fmt, SimOutFmt::Table) && args.out_table.is_some() {
+        anyhow::bail!("--out-table requires --fmt table (or --emit-pairs)");
+    }
+    if args.plot_distribution && args.mode != SimMode::Pair {
+        anyhow::bail!("--plot-distribution requires --mode pair");
+    }
+    if args.out_plot.is_some() && !args.plot_distribution {
+        anyhow::bail!("--out-plot requires --plot-distribution");
+    }
+    if args.plot_distribution && plot_levels(&args.plot_palette).is_empty() {
+        anyhow::bail!("--plot-palette needs at least one non-space character");
+    }
+    if args.import_state.is_some() || args.export_state.is_some() {
+        let analysis = [
+            (args.qsearch, "--qsearch"),
+            (args.period_detect, "--period-detect"),
+            (args.stat_test, "--stat-test"),
+            (args.tpe_stats, "--tpe-stats"),
+            (args.correlation_test, "--correlation-test"),
+            (args.diff_recipe.is_some(), "--diff-recipe"),
+        ];
+        if let Some((_, flag)) = analysis.iter().find(|(on, _)| *on) {
+            anyhow::bail!("--import-state/--export-state only apply to the normal sim path, not {flag}");
+        }
+    }
+
+    // Load recipe (from file or preset if provided, else default).
+    let mut recipe: Recipe =
+        recipe_file::load_or_preset(args.recipe.as_deref(), args.recipe_preset)?;
 
     // Precedence (matches encode):
     // 1) explicit --qshift wins
-    // 2) else if --recipe provided => recipe wins
+    // 2) else if --recipe / --recipe-preset provided => recipe wins
     // 3) else profile shift
     if let Some(v) = args.qshift {
         recipe.quant.shift = v;
-    } else if args.recipe.is_none() {
+    } else if args.recipe.is_none() && args.recipe_preset.is_none() {
         recipe.quant.shift = profile_shift(args.profile);
     }
 
@@ -203,6 +393,12 @@ pub fn run(args: SimArgs) -> anyhow::Result<()> {
     if let Some(v) = args.clamp_max {
         recipe.field_clamp.max = v;
     }
+    if let Some(k) = args.clamp_soft_knee {
+        recipe.field_clamp.soft_knee = (k != 0).then_some(k);
+        recipe.version = recipe
+            .version
+            .max(k8dnz_core::recipe::format::FORMAT_VERSION_SOFT_CLAMP);
+    }
 
     // Guard: quant range must be sane.
     if recipe.quant.min >= recipe.quant.max {
@@ -229,6 +425,8 @@ pub fn run(args: SimArgs) -> anyhow::Result<()> {
         "custom"
     } else if args.recipe.is_some() {
         "recipe"
+    } else if let Some(p) = args.recipe_preset {
+        p.name()
     } else {
         match args.profile {
             Profile::Tuned => "tuned",
@@ -257,20 +455,76 @@ pub fn run(args: SimArgs) -> anyhow::Result<()> {
         return run_qsearch(args, recipe);
     }
 
+    if args.period_detect {
+        return run_period_detect(&args, recipe);
+    }
+
+    if args.stat_test {
+        return run_stat_test(&args, recipe);
+    }
+
+    if args.tpe_stats {
+        return run_tpe_stats(&args, recipe);
+    }
+
+    if args.correlation_test {
+        return run_correlation_test(&args, recipe);
+    }
+
+    if let Some(path) = args.diff_recipe.as_deref() {
+        return run_diff_recipe(&args, &recipe, path);
+    }
+
     if let Some(path) = args.save_recipe.as_deref() {
         recipe_file::save_k8r(path, &recipe)?;
         eprintln!("saved recipe: {} (recipe_id={})", path, rid);
     }
 
     // Normal sim path.
-    let mut engine = Engine::new(recipe.clone())?;
+    let mut engine = match args.import_state.as_deref() {
+        Some(path) => import_state(path, &rid)?,
+        None => Engine::new(recipe.clone())?,
+    };
+
+    if args.verbose {
+        eprintln!("--- describe ---");
+        eprint!("{}", engine.describe());
+        eprintln!("quant_step_size           = {}", engine.recipe.quant_step_size());
+        eprintln!("quant_level(field=0)      = {}", engine.recipe.quant_level_for_field_value(0));
+        let (lo, hi) = engine.expected_emission_period_range();
+        eprintln!("emission_period_range     = {}..{} ticks", lo, hi);
+        eprintln!(
+            "search_emissions_99       = {} (reachable_bins={}/16)",
+            engine.recommended_search_emissions(),
+            engine.reachable_quant_bins()
+        );
+        if args.max_ticks < args.emissions.saturating_mul(lo) {
+            eprintln!(
+                "WARN: --max-ticks {} is below emissions * min period ({}); expect fewer emissions",
+                args.max_ticks,
+                args.emissions.saturating_mul(lo)
+            );
+        }
+    }
 
     // Pair stream (and optionally fields)
     let fr_opt: Option<FieldRangeStats>;
     let toks: Vec<PairToken>;
     let fields: Option<Vec<(PairToken, EmissionField)>>;
 
-    if args.mode == SimMode::Rgbpair && args.rgb_from_field {
+    if let Some(k) = args.sample_every_k {
+        let kept = engine.run_every_k_with_positions(k, args.emissions, args.max_ticks);
+        eprintln!(
+            "sample every k: k={} kept={} first_index={:?} last_index={:?}",
+            k,
+            kept.len(),
+            kept.first().map(|&(i, _)| i),
+            kept.last().map(|&(i, _)| i)
+        );
+        toks = kept.into_iter().map(|(_, t)| t).collect();
+        fields = None;
+        fr_opt = None;
+    } else if args.output_raw_fields || (args.mode == SimMode::Rgbpair && args.rgb_from_field) {
         // We need token + emission field samples.
         let pairs = engine.run_emissions_with_fields(args.emissions, args.max_ticks);
         toks = pairs.iter().map(|(t, _)| *t).collect();
@@ -288,13 +542,26 @@ pub fn run(args: SimArgs) -> anyhow::Result<()> {
         fr_opt = fr;
     }
 
-    // Output (Pair or Rgbpair)
-    write_output(&args, &toks, fields.as_deref(), &recipe)?;
+    // Output (raw fields, Pair or Rgbpair)
+    if args.output_raw_fields {
+        let raw: Vec<EmissionField> = fields.iter().flatten().map(|(_, f)| *f).collect();
+        write_raw_fields(&args, &raw)?;
+    } else if args.plot_distribution {
+        write_distribution_plot(&args, &toks)?;
+    } else {
+        write_output(&args, &toks, fields.as_deref(), &recipe)?;
+    }
 
     if args.stats {
         print_stats(&toks, fr_opt.as_ref(), &recipe);
     }
 
+    if let Some(path) = args.export_state.as_deref() {
+        std::fs::write(path, engine.serialize_state())
+            .with_context(|| format!("write engine state {path}"))?;
+        eprintln!("exported state: {} (ticks={})", path, engine.stats.ticks);
+    }
+
     eprintln!(
         "sim ok: ticks={} alignments={} emissions={}",
         engine.stats.ticks, engine.stats.alignments, engine.stats.emissions
@@ -303,6 +570,25 @@ pub fn run(args: SimArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn import_state(path: &str, rid: &str) -> anyhow::Result<Engine> {
+    let bytes = std::fs::read(path).with_context(|| format!("read engine state {path}"))?;
+    let engine = Engine::deserialize_state(&bytes).map_err(|e| anyhow::anyhow!("{path}: {e}"))?;
+    let state_rid = k8dnz_core::recipe::format::recipe_id_hex(&engine.recipe);
+    if state_rid != rid {
+        anyhow::bail!(
+            "engine state {} was saved with recipe_id={} but this run uses recipe_id={}",
+            path,
+            state_rid,
+            rid
+        );
+    }
+    eprintln!(
+        "imported state: {} (ticks={} emissions={})",
+        path, engine.stats.ticks, engine.stats.emissions
+    );
+    Ok(engine)
+}
+
 fn parse_rgb_triplet(s: &str) -> anyhow::Result<Rgb> {
     let parts: Vec<&str> = s.split(',').map(|x| x.trim()).collect();
     if parts.len() != 3 {
@@ -337,6 +623,26 @@ fn make_rgb_recipe(args: &SimArgs) -> anyhow::Result<RgbRecipe> {
     })
 }
 
+fn write_raw_fields(args: &SimArgs, fields: &[EmissionField]) -> anyhow::Result<()> {
+    match args.fmt {
+        SimOutFmt::Jsonl => {
+            if let Some(path) = args.out.as_deref() {
+                jsonl::write_fields_file(path, fields)?;
+            } else {
+                jsonl::write_fields_stdout(fields)?;
+            }
+        }
+        SimOutFmt::Bin => {
+            let Some(path) = args.out.as_deref() else {
+                anyhow::bail!("--fmt bin requires --out <path>");
+            };
+            bin::write_fields_file(path, fields)?;
+        }
+        SimOutFmt::Table => anyhow::bail!("--fmt table does not support --output-raw-fields"),
+    }
+    Ok(())
+}
+
 fn write_output(
     args: &SimArgs,
     toks: &[PairToken],
@@ -358,6 +664,7 @@ fn write_output(
                 };
                 bin::write_bytes_file(path, toks)?;
             }
+            SimOutFmt::Table => write_pair_table(args, toks)?,
         },
 
         SimMode::Rgbpair => {
@@ -366,17 +673,31 @@ fn write_output(
                     anyhow::bail!("internal error: rgb_from_field requires fields");
                 };
 
-                let cfg = make_rgb_recipe(args)?;
-                // Use a deterministic spread scale; matches prior intent.
-                let spread = (recipe.quant.max - recipe.quant.min).abs().max(1);
-
-                pairs
-                    .iter()
-                    .enumerate()
-                    .map(|(i, (_t, ef))| {
-                        emit_rgbpair_from_fields(&cfg, i as u64, ef.clamped_a, ef.clamped_c, spread)
-                    })
-                    .collect()
+                if args.rgb_model == RgbModel::Hsv {
+                    (0..pairs.len() as u64)
+                        .map(|i| {
+                            emit_hsv_pair_from_params(
+                                i,
+                                args.rgb_hsv_step_a,
+                                args.rgb_hsv_step_c,
+                                args.rgb_saturation,
+                                args.rgb_value,
+                            )
+                        })
+                        .collect()
+                } else {
+                    let cfg = make_rgb_recipe(args)?;
+                    // Use a deterministic spread scale; matches prior intent.
+                    let spread = (recipe.quant.max - recipe.quant.min).abs().max(1);
+
+                    pairs
+                        .iter()
+                        .enumerate()
+                        .map(|(i, (_t, ef))| {
+                            emit_rgbpair_from_fields(&cfg, i as u64, ef.clamped_a, ef.clamped_c, spread)
+                        })
+                        .collect()
+                }
             } else {
                 // Back-compat / MVP: palette16 mapping
                 toks.iter().copied().map(|p| p.to_rgb_pair()).collect()
@@ -396,12 +717,127 @@ fn write_output(
                     };
                     bin::write_rgbpairs_file(path, &rgb)?;
                 }
+                SimOutFmt::Table => anyhow::bail!("--fmt table requires --mode pair"),
             }
         }
     }
     Ok(())
 }
 
+/// Fixed-width pair table; the index column is padded to the widest index.
+fn pair_table(toks: &[PairToken], highlight: Option<u8>) -> String {
+    let width = toks.len().saturating_sub(1).to_string().len();
+    let mut s = String::with_capacity(toks.len() * 48);
+    for (i, t) in toks.iter().enumerate() {
+        let byte = t.pack_byte();
+        let ch = if byte.is_ascii_graphic() || byte == b' ' {
+            byte as char
+        } else {
+            '.'
+        };
+        s.push_str(&format!(
+            "{:>width$}: A=0x{:x} B=0x{:x} byte=0x{:02x} char={}",
+            i, t.a, t.b, byte, ch
+        ));
+        if highlight == Some(byte) {
+            s.push_str(" *");
+        }
+        s.push('\n');
+    }
+    s
+}
+
+fn write_pair_table(args: &SimArgs, toks: &[PairToken]) -> anyhow::Result<()> {
+    let table = pair_table(toks, args.highlight);
+    match args.out_table.as_deref().or(args.out.as_deref()) {
+        Some(path) => {
+            std::fs::write(path, table)?;
+            eprintln!("wrote pair table: {} ({} rows)", path, toks.len());
+        }
+        None => print!("{table}"),
+    }
+    Ok(())
+}
+
+/// Palette characters for non-zero counts (a leading space is the zero level).
+fn plot_levels(palette: &str) -> Vec<char> {
+    let chars: Vec<char> = palette.chars().collect();
+    match chars.split_first() {
+        Some((' ', rest)) => rest.to_vec(),
+        _ => chars,
+    }
+}
+
+/// Zero plots as a space; otherwise count/max is split evenly across `levels`.
+fn plot_char(count: u64, max: u64, levels: &[char]) -> char {
+    if count == 0 || max == 0 {
+        return ' ';
+    }
+    let n = levels.len() as u64;
+    let idx = (count * n).div_ceil(max).clamp(1, n) - 1;
+    levels[idx as usize]
+}
+
+fn distribution_plot(hist: &[u64; 256], mode: PlotMode, palette: &str) -> String {
+    let levels = plot_levels(palette);
+    let max = hist.iter().copied().max().unwrap_or(0);
+    let total: u64 = hist.iter().sum();
+    let mut s = String::new();
+    match mode {
+        PlotMode::Grid => {
+            s.push_str("A\\B  0123456789abcdef\n");
+            for a in 0..16usize {
+                s.push_str(&format!("  {:x} |", a));
+                for b in 0..16usize {
+                    s.push(plot_char(hist[(a << 4) | b], max, &levels));
+                }
+                s.push_str("|\n");
+            }
+        }
+        PlotMode::Bar => {
+            let full = *levels.last().unwrap_or(&'#');
+            for (byte, &c) in hist.iter().enumerate() {
+                let len = if max == 0 {
+                    0
+                } else {
+                    (c as usize * PLOT_BAR_WIDTH).div_ceil(max as usize)
+                };
+                s.push_str(&format!(
+                    "0x{:02x} |{}{}| {}\n",
+                    byte,
+                    full.to_string().repeat(len),
+                    " ".repeat(PLOT_BAR_WIDTH - len),
+                    c
+                ));
+            }
+        }
+    }
+    let distinct = hist.iter().filter(|&&c| c > 0).count();
+    s.push_str(&format!("total={} distinct={}/256 max={}\n", total, distinct, max));
+    s
+}
+
+fn write_distribution_plot(args: &SimArgs, toks: &[PairToken]) -> anyhow::Result<()> {
+    let mut hist = [0u64; 256];
+    for t in toks {
+        hist[t.pack_byte() as usize] += 1;
+    }
+    let plot = distribution_plot(&hist, args.plot_mode, &args.plot_palette);
+    match args.out_plot.as_deref().or(args.out.as_deref()) {
+        Some(path) => {
+            std::fs::write(path, plot)?;
+            eprintln!(
+                "wrote distribution plot: {} ({:?}, {} tokens)",
+                path,
+                args.plot_mode,
+                toks.len()
+            );
+        }
+        None => print!("{plot}"),
+    }
+    Ok(())
+}
+
 // ---- everything below this line is unchanged from your current sim.rs ----
 
 #[derive(Clone, Debug)]
@@ -429,6 +865,22 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
 
     let base_shift: i64 = base_recipe.quant.shift;
 
+    // One center recipe per width; without --qsearch-width-range just the base recipe.
+    let centers: Vec<Recipe> = match args.qsearch_width_range.as_deref() {
+        None => vec![base_recipe.clone()],
+        Some(spec) => {
+            let widths = parse_u64_range(spec, "--qsearch-width-range")?;
+            if widths.first() == Some(&0) || widths.last() > Some(&(i64::MAX as u64 / 2)) {
+                anyhow::bail!("--qsearch-width-range widths must be in 1..=2^62");
+            }
+            widths
+                .into_iter()
+                .map(|w| with_quant_width(&base_recipe, w as i64))
+                .collect::<anyhow::Result<_>>()?
+        }
+    };
+    let width_search = args.qsearch_width_range.is_some();
+
     let per_emissions = args.qsearch_emissions;
 
     // IMPORTANT: do NOT inherit the user's main --max-ticks by default.
@@ -440,6 +892,14 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
         "base shift={} width={} step={} candidates={} (per-candidate emissions={} max_ticks={})",
         base_shift, width, step, n, per_emissions, per_max_ticks
     );
+    if let Some(spec) = args.qsearch_width_range.as_deref() {
+        eprintln!(
+            "width search: range={} widths={} total_candidates={}",
+            spec,
+            centers.len(),
+            centers.len() * n
+        );
+    }
 
     if per_emissions >= 10_000 || per_max_ticks >= 80_000_000 {
         eprintln!(
@@ -450,36 +910,52 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
 
     let t0 = Instant::now();
 
-    let mut rows: Vec<(i64, Metrics, String)> = Vec::with_capacity(n);
+    let total = centers.len() * n;
+    let mut rows: Vec<(Recipe, Metrics, String)> = Vec::with_capacity(total);
 
-    for idx in 0..n {
-        let offset = (idx as i64) - half;
-        let shift = base_shift.saturating_add(offset.saturating_mul(step));
+    for (wi, center) in centers.iter().enumerate() {
+        let cw = center.quant.max - center.quant.min;
+        let cstep = if width_search {
+            args.qsearch_step.unwrap_or((cw / 32).max(1))
+        } else {
+            step
+        };
 
-        let mut r = base_recipe.clone();
-        r.quant.shift = shift;
+        for idx in 0..n {
+            let offset = (idx as i64) - half;
+            let shift = center.quant.shift.saturating_add(offset.saturating_mul(cstep));
 
-        let rid = k8dnz_core::recipe::format::recipe_id_hex(&r);
+            let mut r = center.clone();
+            r.quant.shift = shift;
 
-        let start = Instant::now();
-        let mut e = Engine::new(r.clone())?;
-        let toks = e.run_emissions(per_emissions, per_max_ticks);
-        let m = compute_metrics(&toks, e.stats.ticks);
+            let rid = k8dnz_core::recipe::format::recipe_id_hex(&r);
 
-        eprintln!(
-            "cand {}/{} shift={} recipe_id={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={} elapsed_ms={}",
-            idx + 1,
-            n,
-            shift,
-            rid,
-            m.distinct_bytes,
-            m.entropy_byte,
-            m.peak_nibble,
-            m.ticks,
-            start.elapsed().as_millis()
-        );
+            let start = Instant::now();
+            let mut e = Engine::new(r.clone())?;
+            let toks = e.run_emissions(per_emissions, per_max_ticks);
+            let m = compute_metrics(&toks, e.stats.ticks);
+
+            let width_label = if width_search {
+                format!("width={} ", cw)
+            } else {
+                String::new()
+            };
+            eprintln!(
+                "cand {}/{} {}shift={} recipe_id={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={} elapsed_ms={}",
+                wi * n + idx + 1,
+                total,
+                width_label,
+                shift,
+                rid,
+                m.distinct_bytes,
+                m.entropy_byte,
+                m.peak_nibble,
+                m.ticks,
+                start.elapsed().as_millis()
+            );
 
-        rows.push((shift, m, rid));
+            rows.push((r, m, rid));
+        }
     }
 
     // Rank: primary entropy_byte (desc), then distinct_bytes (desc), then lower peak_nibble (asc)
@@ -492,11 +968,11 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
     });
 
     eprintln!("--- qsearch ranking (top 9) ---");
-    for (rank, (shift, m, rid)) in rows.iter().enumerate() {
+    for (rank, (r, m, rid)) in rows.iter().enumerate() {
         eprintln!(
             "#{:>2} shift={} recipe_id={} entropy_byte={:.4} distinct={}/256 peak_nibble={} ticks={}",
             rank + 1,
-            shift,
+            r.quant.shift,
             rid,
             m.entropy_byte,
             m.distinct_bytes,
@@ -508,23 +984,42 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
         }
     }
 
-    let (best_shift, _best_m, best_rid) = rows[0].clone();
+    if width_search {
+        eprintln!("--- qsearch 2d scoreboard (top 3 width x shift) ---");
+        for (rank, (r, m, rid)) in rows.iter().take(3).enumerate() {
+            eprintln!(
+                "#{} width={} qmin={} qmax={} shift={} recipe_id={} entropy_byte={:.4} distinct={}/256 peak_nibble={}",
+                rank + 1,
+                r.quant.max - r.quant.min,
+                r.quant.min,
+                r.quant.max,
+                r.quant.shift,
+                rid,
+                m.entropy_byte,
+                m.distinct_bytes,
+                m.peak_nibble
+            );
+        }
+    }
+
+    let (best_recipe, _best_m, best_rid) = rows.swap_remove(0);
+    let best_shift = best_recipe.quant.shift;
+    let best_width = best_recipe.quant.max - best_recipe.quant.min;
     eprintln!(
-        "best shift={} (base {}) recipe_id={} total_elapsed_ms={}",
+        "best shift={} (base {}) width={} (base {}) recipe_id={} total_elapsed_ms={}",
         best_shift,
         base_shift,
+        best_width,
+        width,
         best_rid,
         t0.elapsed().as_millis()
     );
 
-    let mut best_recipe = base_recipe.clone();
-    best_recipe.quant.shift = best_shift;
-
     if let Some(path) = args.save_recipe.as_deref() {
         recipe_file::save_k8r(path, &best_recipe)?;
         eprintln!(
-            "qsearch saved best recipe: recipe={} shift={} recipe_id={}",
-            path, best_shift, best_rid
+            "qsearch saved best recipe: recipe={} shift={} width={} qmin={} qmax={} recipe_id={}",
+            path, best_shift, best_width, best_recipe.quant.min, best_recipe.quant.max, best_rid
         );
     }
 
@@ -537,6 +1032,7 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
             SimMode::Pair => match args.fmt {
                 SimOutFmt::Jsonl => jsonl::write_tokens_file(path, &toks)?,
                 SimOutFmt::Bin => bin::write_bytes_file(path, &toks)?,
+                SimOutFmt::Table => std::fs::write(path, pair_table(&toks, args.highlight))?,
             },
             SimMode::Rgbpair => {
                 let rgb: Vec<RgbPairToken> =
@@ -544,6 +1040,7 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
                 match args.fmt {
                     SimOutFmt::Jsonl => jsonl::write_rgbpairs_file(path, &rgb)?,
                     SimOutFmt::Bin => bin::write_rgbpairs_file(path, &rgb)?,
+                    SimOutFmt::Table => anyhow::bail!("--fmt table requires --mode pair"),
                 }
             }
         }
@@ -559,17 +1056,279 @@ fn run_qsearch(args: SimArgs, base_recipe: Recipe) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// `base` with its quant range resized to `width` around the same center; the shift
+/// keeps its position relative to the width. Fails on an empty or inverted base range.
+fn with_quant_width(base: &Recipe, width: i64) -> anyhow::Result<Recipe> {
+    let base_width = (base.quant.max as i128) - (base.quant.min as i128);
+    if base_width <= 0 {
+        anyhow::bail!(
+            "quant width search needs quant.max > quant.min (got min={} max={})",
+            base.quant.min,
+            base.quant.max
+        );
+    }
+    let center = (base.quant.min as i128 + base.quant.max as i128) / 2;
+    let min = center - (width as i128) / 2;
+
+    let mut r = base.clone();
+    r.quant.min = min as i64;
+    r.quant.max = (min + width as i128) as i64;
+    r.quant.shift = (base.quant.shift as i128 * width as i128 / base_width) as i64;
+    Ok(r)
+}
+
+const PERIOD_CORR_THRESHOLD: f64 = 0.9;
+
+fn run_period_detect(args: &SimArgs, recipe: Recipe) -> anyhow::Result<()> {
+    let t0 = Instant::now();
+
+    let mut e = Engine::new(recipe)?;
+    let toks = e.run_emissions(args.detect_emissions, args.max_ticks);
+    let bytes: Vec<u8> = toks.iter().map(|t| t.pack_byte()).collect();
+
+    if (bytes.len() as u64) < args.detect_emissions {
+        eprintln!(
+            "note: only {} of {} emissions within max_ticks={}",
+            bytes.len(),
+            args.detect_emissions,
+            args.max_ticks
+        );
+    }
+
+    let cands = detect_periods(&bytes, args.max_period);
+
+    eprintln!("--- sim --period-detect ---");
+    eprintln!(
+        "emissions={} max_period={} threshold={:.2} candidates={} ticks={} elapsed_ms={}",
+        bytes.len(),
+        args.max_period,
+        PERIOD_CORR_THRESHOLD,
+        cands.len(),
+        e.stats.ticks,
+        t0.elapsed().as_millis()
+    );
+
+    if cands.is_empty() {
+        eprintln!("no candidate periods (no lag above threshold)");
+    }
+    for (rank, (lag, r)) in cands.iter().take(5).enumerate() {
+        eprintln!("#{} period={} corr={:.6}", rank + 1, lag, r);
+    }
+
+    Ok(())
+}
+
+const DIFF_SHOW_FIRST: usize = 10;
+
+fn run_diff_recipe(args: &SimArgs, recipe: &Recipe, other_path: &str) -> anyhow::Result<()> {
+    let other = recipe_file::load_k8r(other_path)?;
+    let t0 = Instant::now();
+
+    let fields = recipe.field_diff(&other);
+    let diffs = Engine::diff_outputs(recipe, &other, args.emissions, args.max_ticks)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let rate = if args.emissions == 0 {
+        0.0
+    } else {
+        diffs.len() as f64 / args.emissions as f64
+    };
+
+    eprintln!("--- sim --diff-recipe ---");
+    eprintln!(
+        "other={} other_recipe_id={} emissions={} differing={} diff_rate={:.6} elapsed_ms={}",
+        other_path,
+        k8dnz_core::recipe::format::recipe_id_hex(&other),
+        args.emissions,
+        diffs.len(),
+        rate,
+        t0.elapsed().as_millis()
+    );
+    if fields.is_empty() {
+        eprintln!("fields: identical");
+    }
+    for f in &fields {
+        eprintln!("field {}: {} -> {}", f.field, f.a, f.b);
+    }
+    for (i, a, b) in diffs.iter().take(DIFF_SHOW_FIRST) {
+        eprintln!("emission={} a=0x{:02X} b=0x{:02X}", i, a, b);
+    }
+    if diffs.len() > DIFF_SHOW_FIRST {
+        eprintln!("... {} more", diffs.len() - DIFF_SHOW_FIRST);
+    }
+
+    Ok(())
+}
+
+fn run_tpe_stats(args: &SimArgs, recipe: Recipe) -> anyhow::Result<()> {
+    let t0 = Instant::now();
+
+    let mut e = Engine::new(recipe)?;
+    let (mean, stddev, min, max) = e.ticks_per_emission_stats(args.tpe_emissions);
+
+    if e.stats.emissions < args.tpe_emissions {
+        eprintln!(
+            "note: only {} of {} emissions within the tick budget (ticks={})",
+            e.stats.emissions, args.tpe_emissions, e.stats.ticks
+        );
+    }
+
+    eprintln!("--- sim --tpe-stats ---");
+    eprintln!(
+        "emissions={} ticks={} elapsed_ms={}",
+        e.stats.emissions,
+        e.stats.ticks,
+        t0.elapsed().as_millis()
+    );
+    eprintln!("tpe_mean   = {:.3}", mean);
+    eprintln!("tpe_stddev = {:.3}", stddev);
+    eprintln!("tpe_min    = {}", min);
+    eprintln!("tpe_max    = {}", max);
+    eprintln!(
+        "tpe_cv     = {:.4}",
+        if mean > 0.0 { stddev / mean } else { 0.0 }
+    );
+    Ok(())
+}
+
+fn run_correlation_test(args: &SimArgs, recipe: Recipe) -> anyhow::Result<()> {
+    let Some(path) = args.other.as_deref() else {
+        anyhow::bail!("--correlation-test needs --other <file>");
+    };
+    let other = std::fs::read(path).with_context(|| format!("read {path}"))?;
+    if other.len() < 3 {
+        anyhow::bail!("--other {} has {} bytes; need at least 3", path, other.len());
+    }
+    let t0 = Instant::now();
+
+    let mut e = Engine::new(recipe)?;
+    let bytes: Vec<u8> = e
+        .run_emissions(other.len() as u64, args.max_ticks)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    if bytes.len() < other.len() {
+        anyhow::bail!(
+            "only {} of {} emissions within max_ticks={}; raise --max-ticks",
+            bytes.len(),
+            other.len(),
+            args.max_ticks
+        );
+    }
+
+    let c = k8dnz_core::stats::correlation_test(&bytes, &other);
+    eprintln!("--- sim --correlation-test ---");
+    eprintln!(
+        "other={} n={} ticks={} elapsed_ms={}",
+        path,
+        c.n,
+        e.stats.ticks,
+        t0.elapsed().as_millis()
+    );
+    eprintln!("pearson_r    = {:.6} p-value={:.6}", c.pearson, c.pearson_p);
+    eprintln!("spearman_rho = {:.6} p-value={:.6}", c.spearman, c.spearman_p);
+    if c.pearson > 0.05 {
+        eprintln!("note: pearson_r > 0.05; the recipe may track the target (try fit-xor)");
+    }
+    Ok(())
+}
+
+fn run_stat_test(args: &SimArgs, recipe: Recipe) -> anyhow::Result<()> {
+    let t0 = Instant::now();
+
+    let mut e = Engine::new(recipe)?;
+    let report = e.statistical_test_run(args.stat_test_emissions, args.max_ticks);
+
+    if report.bytes < args.stat_test_emissions {
+        eprintln!(
+            "note: only {} of {} emissions within max_ticks={}",
+            report.bytes, args.stat_test_emissions, args.max_ticks
+        );
+    }
+
+    eprintln!("--- sim --stat-test ---");
+    eprintln!(
+        "emissions={} alpha={} ticks={} elapsed_ms={}",
+        report.bytes,
+        report.alpha,
+        e.stats.ticks,
+        t0.elapsed().as_millis()
+    );
+    for t in &report.tests {
+        eprintln!(
+            "{:<12} stat={:<14.6} p={:.6} {}",
+            t.name,
+            t.statistic,
+            t.p_value,
+            if t.pass { "PASS" } else { "FAIL" }
+        );
+    }
+    if !report.all_pass() {
+        let failed: Vec<&str> = report.failed().map(|t| t.name).collect();
+        eprintln!("WARN: failed tests: {}", failed.join(","));
+    }
+
+    Ok(())
+}
+
+/// FFT autocorrelation of the (mean-removed) byte stream at lags 1..=max_lag.
+/// Returns lags with correlation > 0.9, strongest first.
+/// Each lag is normalized by its overlap (n - lag) so long periods are not penalized;
+/// max_lag is capped at n/2 so every reported lag has at least half the stream behind it.
+fn detect_periods(bytes: &[u8], max_lag: usize) -> Vec<(usize, f64)> {
+    use rustfft::{num_complex::Complex, FftPlanner};
+
+    let n = bytes.len();
+    if n < 2 {
+        return Vec::new();
+    }
+    let max_lag = max_lag.min(n / 2);
+
+    let mean = bytes.iter().map(|&b| b as f64).sum::<f64>() / n as f64;
+
+    // zero-pad to >= 2n so the circular correlation equals the linear one
+    let m = (2 * n).next_power_of_two();
+    let mut buf: Vec<Complex<f64>> = bytes
+        .iter()
+        .map(|&b| Complex::new(b as f64 - mean, 0.0))
+        .chain(std::iter::repeat(Complex::new(0.0, 0.0)))
+        .take(m)
+        .collect();
+
+    let mut planner = FftPlanner::<f64>::new();
+    planner.plan_fft_forward(m).process(&mut buf);
+    for c in buf.iter_mut() {
+        *c = Complex::new(c.norm_sqr(), 0.0);
+    }
+    planner.plan_fft_inverse(m).process(&mut buf);
+
+    let r0 = buf[0].re / n as f64;
+    if r0 <= f64::EPSILON * m as f64 {
+        // constant stream: every lag trivially repeats, nothing to rank
+        return Vec::new();
+    }
+
+    let mut out: Vec<(usize, f64)> = (1..=max_lag)
+        .map(|lag| (lag, buf[lag].re / (n - lag) as f64 / r0))
+        .filter(|&(_, r)| r > PERIOD_CORR_THRESHOLD)
+        .collect();
+
+    out.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    out
+}
+
 fn compute_metrics(toks: &[PairToken], ticks: u64) -> Metrics {
     let mut ha = [0u64; 16];
     let mut hb = [0u64; 16];
     let mut hbyte = [0u64; 256];
 
     for t in toks {
-        let a = (t.a & 0x0F) as usize;
-        let b = (t.b & 0x0F) as usize;
-        ha[a] += 1;
-        hb[b] += 1;
-        let byte = (((a as u8) & 0x0F) << 4) | ((b as u8) & 0x0F);
+        let byte = t.pack_byte();
+        ha[(t.a & 0x0F) as usize] += 1;
+        hb[(t.b & 0x0F) as usize] += 1;
         hbyte[byte as usize] += 1;
     }
 
@@ -598,11 +1357,9 @@ fn print_stats(toks: &[PairToken], fr: Option<&FieldRangeStats>, recipe: &Recipe
     let mut hbyte = [0u64; 256];
 
     for t in toks {
-        let a = (t.a & 0x0F) as usize;
-        let b = (t.b & 0x0F) as usize;
-        ha[a] += 1;
-        hb[b] += 1;
-        let byte = (((a as u8) & 0x0F) << 4) | ((b as u8) & 0x0F);
+        let byte = t.pack_byte();
+        ha[(t.a & 0x0F) as usize] += 1;
+        hb[(t.b & 0x0F) as usize] += 1;
         hbyte[byte as usize] += 1;
     }
 
@@ -715,3 +1472,52 @@ fn entropy_bits_256(h: &[u64; 256], total: u64) -> f64 {
     }
     ent
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_periods_finds_planted_period() {
+        let pattern: Vec<u8> = (0..37u32).map(|i| (i.wrapping_mul(97) % 251) as u8).collect();
+        let bytes: Vec<u8> = pattern.iter().copied().cycle().take(5_000).collect();
+
+        let cands = detect_periods(&bytes, 200);
+        assert!(!cands.is_empty());
+        assert_eq!(cands[0].0 % 37, 0);
+        assert!(cands.iter().all(|&(lag, _)| lag % 37 == 0));
+    }
+
+    #[test]
+    fn detect_periods_ignores_constant_and_noise() {
+        assert!(detect_periods(&[7u8; 1000], 100).is_empty());
+
+        let mut x: u64 = 0x1234_5678_9ABC_DEF0;
+        let noise: Vec<u8> = (0..4096)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                (x >> 56) as u8
+            })
+            .collect();
+        assert!(detect_periods(&noise, 1024).is_empty());
+    }
+
+    #[test]
+    fn with_quant_width_rejects_an_empty_base_range() {
+        let mut r = k8dnz_core::recipe::defaults::default_recipe();
+        r.quant.max = r.quant.min;
+        assert!(with_quant_width(&r, 1_000).is_err());
+    }
+
+    #[test]
+    fn detect_periods_caps_lag_at_half_the_stream() {
+        // Matching outliers at both ends correlate perfectly over a one-byte overlap.
+        let mut bytes: Vec<u8> = (0..100u32).map(|i| (i % 7) as u8).collect();
+        bytes[0] = 255;
+        bytes[99] = 255;
+        let cands = detect_periods(&bytes, 1000);
+        assert!(cands.iter().all(|&(lag, _)| lag <= 50), "{cands:?}");
+    }
+}
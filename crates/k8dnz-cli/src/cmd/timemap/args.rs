@@ -23,6 +23,10 @@ pub enum MapMode {
     Bitfield,
 
     Text64,
+
+    /// Position-keyed nibble Feistel permutation of the raw byte (see --feistel-rounds);
+    /// residuals are taken between the permuted model and permuted plaintext
+    Feistel,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
@@ -32,6 +36,14 @@ pub enum BitMapping {
 
     /// NEW: low-pass intensity -> IIR smooth -> threshold (bits_per_emission must be 1)
     LowpassThresh,
+
+    /// Geom in CIE L*a*b* of the mean A/C color: bit0 L*>=50, bit1 a*>0, bit2 b*>0,
+    /// bit3 chroma>30, then further lightness bits
+    LabGeom,
+
+    /// A-vs-C comparisons of RGB moments: bits 0-2 r/g/b, bit3 r+g, bit4 max channel,
+    /// bit5 min channel, bit6 r-g, bit7 b-g (each set when A's value is larger)
+    RgbMoment,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
@@ -44,6 +56,9 @@ pub enum FitObjective {
 pub enum ResidualMode {
     Xor,
     Sub,
+    /// Bitfield only: (target - pred) clamped to -1/0/+1 on the circular symbol
+    /// alphabet, packed at 2 bits per symbol. Lossy when a prediction is off by more.
+    Ternary,
 }
 
 /// Bitfield residual container encoding (only used when --map bitfield)
@@ -53,8 +68,14 @@ pub enum BitfieldResidualEncoding {
     Packed,
     /// BF2: per-lane bitsets zstd-compressed separately (“time-split / lanes”)
     Lanes,
+    /// BF4: packed symbols in independently zstd-compressed chunks behind an offset
+    /// table, so reconstruct --start-symbol/--end-symbol only decodes what it needs
+    Chunked,
 }
 
+/// Residual symbols per BF4 chunk unless --bf4-chunk-symbols says otherwise.
+pub const BF4_DEFAULT_CHUNK_SYMBOLS: usize = 65_536;
+
 /// Optional per-chunk transform applied to predicted symbols before residual.
 /// Data-agnostic knob: rotate symbol alphabet by k (mod 2^b).
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
@@ -71,6 +92,35 @@ pub enum LawType {
     JumpWalk,
     /// Closed-form per-chunk start_pos(k) (structured revisits / gear formula).
     ClosedForm,
+    /// Chunk k starts at offset + scale*F(k) with F = 1, 2, 3, 5, 8, ... (log-growing gaps).
+    Fibonacci,
+    /// Chunk n starts at offset + scale*p(n), p = lower twin primes 3, 5, 11, 17, 29, ...
+    TwinPrime,
+    /// Chunk n starts at offset + scale*p(n), p = primes 2, 3, 5, 7, 11, ...
+    Prime,
+    /// Chunk starts are Poisson arrivals: exponential gaps with mean --poisson-rate.
+    Poisson,
+}
+
+/// On-disk format for `timemap apply --output-positions`.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum PositionsFmt {
+    /// One decimal position per line
+    Text,
+    /// Raw little-endian u64 array
+    Binary,
+}
+
+/// Fill byte for positions missing from the timemap in a sparse reconstruct.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum GapFillMode {
+    /// 0x00
+    Zero,
+    /// 0xff
+    #[value(name = "0xff")]
+    Ff,
+    /// splitmix64(position) low byte; deterministic per position
+    Random,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
@@ -85,6 +135,10 @@ pub enum TagFormat {
     Byte,
     /// Packed k-bit tags with TG1 header
     Packed,
+    /// `block,tag` rows (auto-selected for .csv)
+    Csv,
+    /// `[[tags]]` tables with `block` and `tag` keys (auto-selected for .toml)
+    Toml,
 }
 
 #[derive(Args)]
@@ -103,6 +157,19 @@ pub enum TimemapCmd {
 
     Apply(ApplyArgs),
     Fit(FitArgs),
+
+    /// Build a timemap from a text file of positions (one u64 per line, `#` comments)
+    ImportText(ImportTextArgs),
+
+    /// Write a timemap's indices as text (one u64 per line)
+    ExportText(ExportTextArgs),
+
+    /// Split a timemap at an index position into two timemaps
+    Split(SplitArgs),
+
+    /// Re-encode a timemap in its most compact format (TM0, TM1 or TM2)
+    Repack(RepackArgs),
+
     FitXor(FitXorArgs),
     FitXorChunked(FitXorChunkedArgs),
     Reconstruct(ReconstructArgs),
@@ -133,6 +200,57 @@ pub struct MakeArgs {
 pub struct InspectArgs {
     #[arg(long)]
     pub r#in: String,
+
+    /// Also print the encoded size in every format and the most compact one
+    #[arg(long, default_value_t = false)]
+    pub suggest_format: bool,
+}
+
+#[derive(Args)]
+pub struct RepackArgs {
+    #[arg(long)]
+    pub r#in: String,
+
+    #[arg(long)]
+    pub out: String,
+}
+
+#[derive(Args)]
+pub struct ImportTextArgs {
+    #[arg(long)]
+    pub r#in: String,
+
+    #[arg(long)]
+    pub out: String,
+
+    /// Fail unless positions are strictly increasing; otherwise they are sorted and de-duplicated.
+    #[arg(long, default_value_t = false)]
+    pub require_sorted: bool,
+}
+
+#[derive(Args)]
+pub struct SplitArgs {
+    #[arg(long)]
+    pub r#in: String,
+
+    /// Number of indices that go to --out-a (0..=len)
+    #[arg(long)]
+    pub at: usize,
+
+    #[arg(long)]
+    pub out_a: String,
+
+    #[arg(long)]
+    pub out_b: String,
+}
+
+#[derive(Args)]
+pub struct ExportTextArgs {
+    #[arg(long)]
+    pub r#in: String,
+
+    #[arg(long)]
+    pub out: String,
 }
 
 #[derive(Args)]
@@ -169,6 +287,14 @@ pub struct ApplyArgs {
 
     #[arg(long, default_value_t = 50_000_000)]
     pub max_ticks: u64,
+
+    /// Optional: write the stream position of every output byte
+    /// (pair: emission index; rgbpair: emission_index*6 + lane).
+    #[arg(long)]
+    pub output_positions: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = PositionsFmt::Text)]
+    pub output_positions_fmt: PositionsFmt,
 }
 
 #[derive(Args)]
@@ -176,11 +302,30 @@ pub struct FitArgs {
     #[arg(long)]
     pub recipe: String,
 
-    #[arg(long)]
-    pub target: String,
+    #[arg(long, required_unless_present = "multi_target", conflicts_with = "multi_target")]
+    pub target: Option<String>,
 
-    #[arg(long)]
-    pub out: String,
+    #[arg(long, required_unless_present = "multi_target")]
+    pub out: Option<String>,
+
+    /// Fit several targets against one engine run (repeatable); writes one timemap per target.
+    #[arg(long, requires = "out_multi_timemap")]
+    pub multi_target: Vec<String>,
+
+    /// Output path pattern for --multi-target; `{}` is replaced by the 0-based target index.
+    #[arg(long, requires = "multi_target")]
+    pub out_multi_timemap: Option<String>,
+
+    /// With --multi-target: fail unless every target's first emission comes after the
+    /// previous target's last one. Sequential matching (the default) always satisfies
+    /// this; with --concurrent it rejects fits whose targets overlap or swap places.
+    #[arg(long, default_value_t = false, requires = "multi_target")]
+    pub require_order: bool,
+
+    /// With --multi-target: match every target at once from --start-emission instead of
+    /// in the order given.
+    #[arg(long, default_value_t = false)]
+    pub concurrent: bool,
 
     #[arg(long, default_value_t = 2_000_000)]
     pub search_emissions: u64,
@@ -219,6 +364,10 @@ pub struct FitXorArgs {
     #[arg(long)]
     pub map_seed_hex: Option<String>,
 
+    /// Rounds for --map feistel.
+    #[arg(long, default_value_t = super::mapping::FEISTEL_DEFAULT_ROUNDS)]
+    pub feistel_rounds: u8,
+
     #[arg(long, value_enum, default_value_t = ResidualMode::Xor)]
     pub residual: ResidualMode,
 
@@ -231,6 +380,19 @@ pub struct FitXorArgs {
     #[arg(long, default_value_t = 0)]
     pub start_emission: u64,
 
+    /// Run the full scan independently from each listed start emission (comma separated);
+    /// the start with the fewest effective bytes is written out.
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["start_emission_step", "start_emission_count"])]
+    pub multi_start_emission: Vec<u64>,
+
+    /// Spacing of the start emission sequence --start-emission + k * step.
+    #[arg(long, requires = "start_emission_count")]
+    pub start_emission_step: Option<u64>,
+
+    /// Number of start emissions in the --start-emission-step sequence.
+    #[arg(long, requires = "start_emission_step")]
+    pub start_emission_count: Option<u64>,
+
     #[arg(long, default_value_t = 1)]
     pub scan_step: usize,
 
@@ -244,8 +406,9 @@ pub struct FitXorArgs {
     #[arg(long)]
     pub cond_tags: Option<String>,
 
-    #[arg(long, value_enum, default_value_t = TagFormat::Byte)]
-    pub cond_tag_format: TagFormat,
+    /// Tag file format. Omitted: `.csv`/`.toml` by extension, otherwise byte.
+    #[arg(long, value_enum)]
+    pub cond_tag_format: Option<TagFormat>,
 
     #[arg(long, default_value_t = 16)]
     pub cond_block_bytes: usize,
@@ -255,6 +418,23 @@ pub struct FitXorArgs {
 
     #[arg(long)]
     pub cond_seed_hex: Option<String>,
+
+    /// Write the tags applied to the target's blocks as TOML (requires --cond-tags).
+    #[arg(long)]
+    pub dump_cond_tags: Option<String>,
+
+    /// After the --scan-step scan, rescan +/- one step around the best window at a quarter
+    /// of the step, repeating for --progressive-levels levels.
+    #[arg(long, default_value_t = false)]
+    pub progressive_refinement: bool,
+
+    /// Refinement levels for --progressive-refinement.
+    #[arg(long, default_value_t = 3)]
+    pub progressive_levels: usize,
+
+    /// Stop refining once the next step would drop below this.
+    #[arg(long, default_value_t = 1)]
+    pub progressive_min_step: usize,
 }
 
 #[derive(Args, Clone)]
@@ -283,6 +463,10 @@ pub struct FitXorChunkedArgs {
     #[arg(long)]
     pub map_seed_hex: Option<String>,
 
+    /// Rounds for --map feistel.
+    #[arg(long, default_value_t = super::mapping::FEISTEL_DEFAULT_ROUNDS)]
+    pub feistel_rounds: u8,
+
     #[arg(long, value_enum, default_value_t = ResidualMode::Xor)]
     pub residual: ResidualMode,
 
@@ -316,9 +500,56 @@ pub struct FitXorChunkedArgs {
     #[arg(long, default_value_t = 200_000)]
     pub lookahead: usize,
 
-    /// Multiplier applied to tm jump-cost. (0 disables jump penalty; 1 = current behavior)
-    #[arg(long, default_value_t = 1)]
-    pub trans_penalty: u64,
+    /// Cap (MB) on the in-RAM model stream (byte maps only). The stream is then
+    /// generated on demand and bytes behind the current chunk are evicted to a
+    /// memory-mapped temp file; peak RAM is about this cap plus lookahead plus the
+    /// remaining target. 0 keeps only the active window. Omit to keep the whole
+    /// search stream in RAM.
+    #[arg(long)]
+    pub stream_cache_size: Option<usize>,
+
+    /// Keep only a ring of the last lookahead + chunk-size stream bytes (byte maps only)
+    /// instead of the whole search stream. The finish-the-target bound on each window is
+    /// then taken from --search-emissions rather than the generated stream, so a fit cut
+    /// short by --max-ticks may stop with partial output where the full stream would not.
+    #[arg(long, default_value_t = false, conflicts_with = "stream_cache_size")]
+    pub use_ring_buffer: bool,
+
+    /// Score each chunk's candidate windows on the rayon pool (--map bitfield only).
+    /// Ties still go to the lowest start, so the output matches a sequential scan.
+    #[arg(long, default_value_t = false)]
+    pub parallel_scan: bool,
+
+    /// Multiplier applied to tm jump-cost. (0 disables jump penalty; default 1)
+    /// An explicit value wins over --trans-penalty-calibrate.
+    #[arg(long)]
+    pub trans_penalty: Option<u64>,
+
+    /// Bitfield only: estimate trans_penalty from a dry run over the first
+    /// --calibration-chunks chunks (at trans_penalty=1) before the real fit.
+    #[arg(long, default_value_t = false)]
+    pub trans_penalty_calibrate: bool,
+
+    /// Chunks fitted by the --trans-penalty-calibrate dry run.
+    #[arg(long, default_value_t = 10)]
+    pub calibration_chunks: usize,
+
+    /// Extra score per candidate window: weight * fraction of the preceding
+    /// --diversity-window stream symbols already used by earlier chunks.
+    /// Pushes chunks apart instead of draining one dense streak. 0 disables.
+    #[arg(long, default_value_t = 0.0)]
+    pub diversity_penalty: f64,
+
+    /// Neighbourhood (stream symbols/bytes behind a candidate start) for --diversity-penalty.
+    #[arg(long, default_value_t = 4096)]
+    pub diversity_window: u64,
+
+    /// Absolute stream position where the first chunk must start (bytes for
+    /// pair/rgbpair, emissions for --map bitfield; same units as `start_pos` in the
+    /// chunk log). Nothing before it is scanned; later chunks follow as usual.
+    /// Must lie within [start_emission, search_emissions] in those units.
+    #[arg(long)]
+    pub anchor_first_chunk_at: Option<u64>,
 
     // -------- bitfield params (used only when --map bitfield) --------
     #[arg(long, default_value_t = 2)]
@@ -335,10 +566,14 @@ pub struct FitXorChunkedArgs {
     #[arg(long, default_value_t = 3)]
     pub bit_smooth_shift: u8,
 
-    /// Bitfield residual encoding (BF1 packed or BF2 lanes). Only used when --map bitfield.
+    /// Bitfield residual encoding (BF1 packed, BF2 lanes or BF4 chunked). Only used when --map bitfield.
     #[arg(long, value_enum, default_value_t = BitfieldResidualEncoding::Packed)]
     pub bitfield_residual: BitfieldResidualEncoding,
 
+    /// Residual symbols per independently compressed BF4 chunk (--bitfield-residual chunked).
+    #[arg(long, default_value_t = BF4_DEFAULT_CHUNK_SYMBOLS)]
+    pub bf4_chunk_symbols: usize,
+
     /// Convenience flag for the “time-split / lanes” experiment:
     /// same as `--bitfield-residual lanes`.
     #[arg(long, default_value_t = false)]
@@ -348,12 +583,19 @@ pub struct FitXorChunkedArgs {
     #[arg(long, value_enum, default_value_t = ChunkXform::None)]
     pub chunk_xform: ChunkXform,
 
+    /// Geom only: extra score per candidate window, weight * sum of (255 - contrast) / 255
+    /// over its emissions (contrast = |luma A - luma C|). Low-contrast emissions carry the
+    /// least reliable bits, so windows built from them lose ties. 0 disables.
+    #[arg(long, default_value_t = 0.0)]
+    pub bitfield_quality_weight: f64,
+
     // -------- conditioning via tags (byte pipeline only) --------
     #[arg(long)]
     pub cond_tags: Option<String>,
 
-    #[arg(long, value_enum, default_value_t = TagFormat::Byte)]
-    pub cond_tag_format: TagFormat,
+    /// Tag file format. Omitted: `.csv`/`.toml` by extension, otherwise byte.
+    #[arg(long, value_enum)]
+    pub cond_tag_format: Option<TagFormat>,
 
     #[arg(long, default_value_t = 16)]
     pub cond_block_bytes: usize,
@@ -428,10 +670,14 @@ pub struct GenLawArgs {
     #[arg(long, default_value_t = 3)]
     pub bit_smooth_shift: u8,
 
-    /// Bitfield residual encoding (BF1 packed or BF2 lanes).
+    /// Bitfield residual encoding (BF1 packed, BF2 lanes or BF4 chunked).
     #[arg(long, value_enum, default_value_t = BitfieldResidualEncoding::Packed)]
     pub bitfield_residual: BitfieldResidualEncoding,
 
+    /// Residual symbols per independently compressed BF4 chunk (--bitfield-residual chunked).
+    #[arg(long, default_value_t = BF4_DEFAULT_CHUNK_SYMBOLS)]
+    pub bf4_chunk_symbols: usize,
+
     /// Convenience flag for BF2 lanes.
     #[arg(long, default_value_t = false)]
     pub time_split: bool,
@@ -446,7 +692,7 @@ pub struct GenLawArgs {
 
     // ---- law selector ----
     /// Which law implementation to use to generate indices.
-    #[arg(long, value_enum, default_value_t = LawType::JumpWalk)]
+    #[arg(long, alias = "gen-mode", value_enum, default_value_t = LawType::JumpWalk)]
     pub law_type: LawType,
 
     // ---- “law” params (JumpWalk Θ) ----
@@ -535,6 +781,36 @@ pub struct GenLawArgs {
     /// Gear 2 phase φ2. Used only for --law-type closedform.
     #[arg(long, default_value_t = 0)]
     pub law_cf_phi2: u64,
+    // ---- Fibonacci params ----
+    /// Multiplier applied to every Fibonacci number (> 0). Used only for --law-type fibonacci.
+    #[arg(long, default_value_t = 1.0)]
+    pub fibonacci_scale: f64,
+
+    /// Added to every scaled Fibonacci offset. Used only for --law-type fibonacci.
+    #[arg(long, default_value_t = 0)]
+    pub fibonacci_offset: u64,
+
+    // ---- Prime params ----
+    /// Multiplier applied to every prime (>= 1). Used only for --law-type twin-prime / prime.
+    #[arg(long, default_value_t = 1)]
+    pub twin_prime_scale: u64,
+
+    /// Added to every scaled prime. Used only for --law-type twin-prime / prime.
+    #[arg(long, default_value_t = 0)]
+    pub twin_prime_offset: u64,
+
+    // ---- Poisson params ----
+    /// Mean gap between chunk starts in emissions (λ, > 0). Used only for --law-type poisson.
+    #[arg(long, alias = "rate", default_value_t = 4096.0)]
+    pub poisson_rate: f64,
+
+    /// Seed for the exponential gaps. Used only for --law-type poisson.
+    #[arg(long, alias = "seed", default_value_t = 0)]
+    pub poisson_seed: u64,
+
+    /// Multiplier applied to the generated arrival times (> 0). Used only for --law-type poisson.
+    #[arg(long, default_value_t = 1.0)]
+    pub poisson_scale: f64,
 }
 
 #[derive(Args)]
@@ -563,12 +839,31 @@ pub struct ReconstructArgs {
     #[arg(long)]
     pub map_seed_hex: Option<String>,
 
+    /// Rounds for --map feistel.
+    #[arg(long, default_value_t = super::mapping::FEISTEL_DEFAULT_ROUNDS)]
+    pub feistel_rounds: u8,
+
     #[arg(long, value_enum, default_value_t = ResidualMode::Xor)]
     pub residual_mode: ResidualMode,
 
     #[arg(long, default_value_t = 80_000_000)]
     pub max_ticks: u64,
 
+    /// Sparse output: write max_timemap_index+1 bytes with reconstructed bytes at their
+    /// timemap positions and this byte (decimal or 0x.., default 0x00) everywhere else.
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "0",
+        value_parser = super::util::parse_byte,
+        conflicts_with = "gap_fill_mode"
+    )]
+    pub write_gaps: Option<u8>,
+
+    /// Sparse output like --write-gaps, with the gap byte chosen by mode.
+    #[arg(long, value_enum)]
+    pub gap_fill_mode: Option<GapFillMode>,
+
     // -------- bitfield params (used only when --map bitfield) --------
     #[arg(long, default_value_t = 2)]
     pub bits_per_emission: u8,
@@ -584,12 +879,23 @@ pub struct ReconstructArgs {
     #[arg(long, default_value_t = 3)]
     pub bit_smooth_shift: u8,
 
+    /// Bitfield only: first residual symbol to rebuild (inclusive). Must land on a byte
+    /// boundary (start * bits_per_emission divisible by 8). BF4 decodes only the chunks
+    /// covering the range.
+    #[arg(long)]
+    pub start_symbol: Option<usize>,
+
+    /// Bitfield only: end of the symbol range (exclusive); defaults to all symbols.
+    #[arg(long)]
+    pub end_symbol: Option<usize>,
+
     // -------- conditioning via tags (byte pipeline only) --------
     #[arg(long)]
     pub cond_tags: Option<String>,
 
-    #[arg(long, value_enum, default_value_t = TagFormat::Byte)]
-    pub cond_tag_format: TagFormat,
+    /// Tag file format. Omitted: `.csv`/`.toml` by extension, otherwise byte.
+    #[arg(long, value_enum)]
+    pub cond_tag_format: Option<TagFormat>,
 
     #[arg(long, default_value_t = 16)]
     pub cond_block_bytes: usize,
@@ -602,10 +908,53 @@ pub struct ReconstructArgs {
 }
 
 #[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct BfLanesArgs {
+    #[command(subcommand)]
+    pub action: Option<BfLanesCmd>,
+
+    /// BF1/BF2 residual (required unless a subcommand is given)
+    #[arg(long)]
+    pub r#in: Option<String>,
+
+    #[arg(long, default_value_t = 3)]
+    pub zstd_level: i32,
+}
+
+#[derive(Subcommand)]
+pub enum BfLanesCmd {
+    /// Symbol occupancy, consecutive-symbol structure, and (BF2) per-lane zstd sizes
+    Analyze(BfLanesAnalyzeArgs),
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum BfAnalyzeFmt {
+    /// Report + bar chart on stderr
+    Text,
+    /// One JSON object on stdout
+    Json,
+}
+
+#[derive(Args)]
+pub struct BfLanesAnalyzeArgs {
     #[arg(long)]
     pub r#in: String,
 
+    /// Optional: recipe used for the fit (printed as recipe_id for traceability)
+    #[arg(long)]
+    pub recipe: Option<String>,
+
+    /// Optional: timemap used for the fit (checked against symbol_count)
+    #[arg(long)]
+    pub timemap: Option<String>,
+
     #[arg(long, default_value_t = 3)]
     pub zstd_level: i32,
+
+    /// Flag symbol values whose count is off from uniform by more than this factor
+    #[arg(long, default_value_t = 4.0)]
+    pub imbalance_ratio: f64,
+
+    #[arg(long, alias = "format", value_enum, default_value_t = BfAnalyzeFmt::Text)]
+    pub fmt: BfAnalyzeFmt,
 }
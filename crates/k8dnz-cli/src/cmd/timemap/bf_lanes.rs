@@ -5,14 +5,19 @@
 // - BF1: unpacks symbols -> builds lane bitsets -> reports raw + zstd sizes
 // - BF2: reads lane bitsets directly (already time-split) -> reports raw + zstd sizes
 // - Baseline: packed-symbol payload zstd (BF1) or packed-symbol reconstructed zstd (BF2)
+// - analyze: symbol occupancy + imbalance flags, consecutive-symbol joint stats,
+//   and (BF2) per-lane zstd sizes
 //
-// Used by `timemap bf-lanes`.
+// Used by `timemap bf-lanes` and `timemap bf-lanes analyze`.
 
 use anyhow::Context;
 use k8dnz_core::signal::bitpack;
+use k8dnz_core::stats;
 
-use super::args::{BfLanesArgs, BitMapping};
+use super::args::{BfAnalyzeFmt, BfLanesAnalyzeArgs, BfLanesArgs, BfLanesCmd, BitMapping};
+use super::bitfield::{read_bitfield_residual, BitfieldResidual};
 use super::util::{zstd_compress_len, zstd_decompress};
+use crate::io::{recipe_file, timemap};
 
 const BF1_MAGIC: &[u8; 4] = b"BF1\0";
 const BF2_MAGIC: &[u8; 4] = b"BF2\0";
@@ -40,7 +45,13 @@ fn popcount_bytes(bs: &[u8]) -> usize {
 }
 
 pub fn cmd_bf_lanes(a: BfLanesArgs) -> anyhow::Result<()> {
-    let in_path = a.r#in.as_str();
+    if let Some(BfLanesCmd::Analyze(aa)) = a.action {
+        return cmd_bf_lanes_analyze(aa);
+    }
+
+    let Some(in_path) = a.r#in.as_deref() else {
+        anyhow::bail!("bf-lanes: --in <path> is required");
+    };
     let zstd_level = a.zstd_level;
 
     let bytes = std::fs::read(in_path).with_context(|| format!("read bf: {}", in_path))?;
@@ -278,3 +289,292 @@ pub fn cmd_bf_lanes(a: BfLanesArgs) -> anyhow::Result<()> {
 
     anyhow::bail!("bf-lanes: unknown magic (expected BF1\\0 or BF2\\0)");
 }
+
+// ---------------- analyze ----------------
+
+const BAR_WIDTH: usize = 50;
+const JOINT_TOP: usize = 8;
+// Full joint matrix is only emitted in JSON up to this many symbol values.
+const JOINT_MATRIX_MAX_LANES: usize = 16;
+
+struct BfSymbols {
+    format: &'static str,
+    bits: u8,
+    mapping: BitMapping,
+    orig_len_bytes: usize,
+    syms: Vec<u8>,
+    // BF2 only: (lane, zstd bytes of its bitset)
+    lane_zstd: Option<Vec<(usize, usize)>>,
+}
+
+fn bf_symbols(res: BitfieldResidual, zstd_level: i32) -> anyhow::Result<BfSymbols> {
+    match res {
+        BitfieldResidual::Bf1 {
+            bits_per_emission,
+            mapping,
+            orig_len_bytes,
+            symbols,
+            ..
+        } => Ok(BfSymbols {
+            format: "BF1",
+            bits: bits_per_emission,
+            mapping,
+            orig_len_bytes,
+            syms: symbols,
+            lane_zstd: None,
+        }),
+        BitfieldResidual::Bf2 {
+            bits_per_emission,
+            mapping,
+            orig_len_bytes,
+            symbol_count,
+            lanes_raw_bitsets,
+            ..
+        } => {
+            let need = symbol_count.div_ceil(8);
+            if let Some((lane, bs)) = lanes_raw_bitsets.iter().enumerate().find(|(_, bs)| bs.len() < need) {
+                anyhow::bail!(
+                    "bf-lanes analyze: BF2 invalid: lane {} bitset has {} bytes, need {} for symbol_count={}",
+                    lane,
+                    bs.len(),
+                    need,
+                    symbol_count
+                );
+            }
+            let mask = sym_mask(bits_per_emission);
+            let mut syms = vec![0u8; symbol_count];
+            let mut seen = vec![false; symbol_count];
+            let mut lane_zstd = Vec::with_capacity(lanes_raw_bitsets.len());
+
+            for (lane, bs) in lanes_raw_bitsets.iter().enumerate() {
+                for (i, (s, hit)) in syms.iter_mut().zip(seen.iter_mut()).enumerate() {
+                    if (bs[i >> 3] >> (i & 7)) & 1 == 1 {
+                        *s = (lane as u8) & mask;
+                        *hit = true;
+                    }
+                }
+                lane_zstd.push((lane, zstd_compress_len(bs, zstd_level)));
+            }
+            if seen.iter().any(|&v| !v) {
+                anyhow::bail!("bf-lanes analyze: BF2 invalid: some symbol positions not assigned to any lane");
+            }
+
+            Ok(BfSymbols {
+                format: "BF2",
+                bits: bits_per_emission,
+                mapping,
+                orig_len_bytes,
+                syms,
+                lane_zstd: Some(lane_zstd),
+            })
+        }
+        BitfieldResidual::Bf4 {
+            bits_per_emission,
+            mapping,
+            orig_len_bytes,
+            symbols,
+            ..
+        } => Ok(BfSymbols {
+            format: "BF4",
+            bits: bits_per_emission,
+            mapping,
+            orig_len_bytes,
+            syms: symbols,
+            lane_zstd: None,
+        }),
+    }
+}
+
+fn cmd_bf_lanes_analyze(a: BfLanesAnalyzeArgs) -> anyhow::Result<()> {
+    let res = read_bitfield_residual(&a.r#in)?;
+    let bf = bf_symbols(res, a.zstd_level)?;
+
+    let bits = bf.bits;
+    if bits == 0 || bits > 8 {
+        anyhow::bail!("bf-lanes analyze: unsupported bits_per_emission={} (expected 1..=8)", bits);
+    }
+    let n_vals = 1usize << bits;
+    let n = bf.syms.len();
+
+    let recipe_id = match a.recipe.as_deref() {
+        Some(p) => Some(k8dnz_core::recipe::format::recipe_id_hex(&recipe_file::load_k8r(p)?)),
+        None => None,
+    };
+    let tm_len = match a.timemap.as_deref() {
+        Some(p) => Some(timemap::read_timemap(p)?.indices.len()),
+        None => None,
+    };
+
+    // occupancy
+    let mut counts = vec![0u64; n_vals];
+    for &s in &bf.syms {
+        counts[(s as usize) & (n_vals - 1)] += 1;
+    }
+    let expected = n as f64 / n_vals as f64;
+    let ratio = a.imbalance_ratio.max(1.0);
+    let flagged: Vec<usize> = (0..n_vals)
+        .filter(|&v| {
+            let c = counts[v] as f64;
+            n > 0 && (c > expected * ratio || c < expected / ratio)
+        })
+        .collect();
+
+    let h_sym = stats::entropy_bits(&counts, n as u64);
+
+    // consecutive-symbol structure
+    let (prev, next) = if n >= 2 {
+        (&bf.syms[..n - 1], &bf.syms[1..])
+    } else {
+        (&bf.syms[..0], &bf.syms[..0])
+    };
+    let mi_consec = stats::mutual_information(prev, next);
+    let h_next_given_prev = stats::conditional_entropy(prev, next);
+
+    let mut joint = vec![0u64; n_vals * n_vals];
+    for (&x, &y) in prev.iter().zip(next.iter()) {
+        joint[(x as usize) * n_vals + (y as usize)] += 1;
+    }
+    let mut joint_top: Vec<(usize, usize, u64)> = joint
+        .iter()
+        .enumerate()
+        .filter(|(_, &c)| c > 0)
+        .map(|(i, &c)| (i / n_vals, i % n_vals, c))
+        .collect();
+    joint_top.sort_by(|x, y| y.2.cmp(&x.2).then_with(|| (x.0, x.1).cmp(&(y.0, y.1))));
+    joint_top.truncate(JOINT_TOP);
+
+    let mut lane_zstd_sorted = bf.lane_zstd.clone();
+    if let Some(v) = lane_zstd_sorted.as_mut() {
+        v.sort_by(|x, y| y.1.cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+    }
+
+    match a.fmt {
+        BfAnalyzeFmt::Text => {
+            eprintln!("--- bf-lanes analyze ({}) ---", bf.format);
+            eprintln!("in                     = {}", a.r#in);
+            if let Some(rid) = recipe_id.as_deref() {
+                eprintln!("recipe_id               = {}", rid);
+            }
+            eprintln!("bits_per_emission       = {}", bits);
+            eprintln!("mapping                 = {:?}", bf.mapping);
+            eprintln!("orig_len_bytes          = {}", bf.orig_len_bytes);
+            eprintln!("symbol_count            = {}", n);
+            if let Some(t) = tm_len {
+                let note = if t == n { "ok" } else { "MISMATCH" };
+                eprintln!("timemap_len             = {} ({})", t, note);
+            }
+            eprintln!("symbol_entropy_bits     = {:.4} (max {})", h_sym, bits);
+            eprintln!();
+
+            let peak = counts.iter().copied().max().unwrap_or(0).max(1);
+            for (v, &c) in counts.iter().enumerate() {
+                let pct = if n == 0 { 0.0 } else { c as f64 * 100.0 / n as f64 };
+                let bar = "#".repeat(((c as f64 / peak as f64) * BAR_WIDTH as f64).round() as usize);
+                let flag = if flagged.contains(&v) { "  <-- imbalance" } else { "" };
+                eprintln!(
+                    "sym {:>3} ({:0width$b})  count={:>8}  pct={:>6.2}%  {}{}",
+                    v,
+                    v,
+                    c,
+                    pct,
+                    bar,
+                    flag,
+                    width = (bits as usize)
+                );
+            }
+
+            eprintln!();
+            eprintln!("--- imbalance (ratio={}) ---", ratio);
+            if flagged.is_empty() {
+                eprintln!("none: every symbol value within {}x of uniform", ratio);
+            } else {
+                eprintln!(
+                    "flagged {} of {} symbol values; bitfield mapping may be poorly aligned with the target",
+                    flagged.len(),
+                    n_vals
+                );
+            }
+
+            eprintln!();
+            eprintln!("--- consecutive symbols ---");
+            eprintln!("mi_prev_next_bits       = {:.6}", mi_consec);
+            eprintln!("h_next_given_prev_bits  = {:.6}", h_next_given_prev);
+            for (x, y, c) in &joint_top {
+                eprintln!("pair {:>3} -> {:>3}  count={}", x, y, c);
+            }
+
+            if let Some(v) = lane_zstd_sorted.as_ref() {
+                let total: usize = v.iter().map(|(_, z)| *z).sum();
+                eprintln!();
+                eprintln!("--- BF2 per-lane zstd (level {}, largest first) ---", a.zstd_level);
+                for (lane, z) in v {
+                    let share = if total == 0 { 0.0 } else { *z as f64 * 100.0 / total as f64 };
+                    eprintln!("lane {:>3}  zstd={:>8}  share={:>6.2}%", lane, z, share);
+                }
+                eprintln!("total_lane_zstd         = {}", total);
+            }
+        }
+        BfAnalyzeFmt::Json => {
+            let mut j = String::new();
+            j.push('{');
+            j.push_str(&format!("\"format\":\"{}\"", bf.format));
+            j.push_str(&format!(",\"in\":{}", json_str(&a.r#in)));
+            if let Some(rid) = recipe_id.as_deref() {
+                j.push_str(&format!(",\"recipe_id\":\"{}\"", rid));
+            }
+            j.push_str(&format!(",\"bits_per_emission\":{}", bits));
+            j.push_str(&format!(",\"mapping\":{}", json_str(&format!("{:?}", bf.mapping))));
+            j.push_str(&format!(",\"orig_len_bytes\":{}", bf.orig_len_bytes));
+            j.push_str(&format!(",\"symbol_count\":{}", n));
+            if let Some(t) = tm_len {
+                j.push_str(&format!(",\"timemap_len\":{}", t));
+            }
+            j.push_str(&format!(",\"symbol_entropy_bits\":{:.6}", h_sym));
+            j.push_str(&format!(",\"counts\":{}", json_list(counts.iter())));
+            j.push_str(&format!(",\"imbalance_ratio\":{}", ratio));
+            j.push_str(&format!(",\"imbalanced\":{}", json_list(flagged.iter())));
+            j.push_str(&format!(",\"mi_prev_next_bits\":{:.6}", mi_consec));
+            j.push_str(&format!(",\"h_next_given_prev_bits\":{:.6}", h_next_given_prev));
+            let top: Vec<String> = joint_top
+                .iter()
+                .map(|(x, y, c)| format!("{{\"prev\":{},\"next\":{},\"count\":{}}}", x, y, c))
+                .collect();
+            j.push_str(&format!(",\"joint_top\":[{}]", top.join(",")));
+            if n_vals <= JOINT_MATRIX_MAX_LANES {
+                let rows: Vec<String> = joint.chunks(n_vals).map(|r| json_list(r.iter())).collect();
+                j.push_str(&format!(",\"joint\":[{}]", rows.join(",")));
+            }
+            if let Some(v) = bf.lane_zstd.as_ref() {
+                j.push_str(&format!(",\"lane_zstd\":{}", json_list(v.iter().map(|(_, z)| z))));
+            }
+            j.push('}');
+            println!("{}", j);
+        }
+    }
+
+    Ok(())
+}
+
+// JSON string literal: quotes, backslashes and control characters escaped.
+fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_list<T: std::fmt::Display>(it: impl Iterator<Item = T>) -> String {
+    let parts: Vec<String> = it.map(|v| v.to_string()).collect();
+    format!("[{}]", parts.join(","))
+}
@@ -1,19 +1,38 @@
 use super::args::*;
-use super::residual::{apply_residual_symbol, make_residual_symbol, sym_mask};
-use super::util::{parse_seed_hex_opt, tm_jump_cost, zstd_compress_len};
+use super::residual::{
+    apply_residual_symbol, make_residual_symbol, residual_symbol_bits, sym_mask,
+};
+use super::util::{
+    first_chunk_min_pos, parse_seed_hex_opt, tm_jump_cost, zstd_compress_file_len,
+    zstd_compress_len, DiversityTracker,
+};
 
 use anyhow::Context;
+use rayon::prelude::*;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
 
 use k8dnz_core::signal::bitpack;
 use k8dnz_core::signal::timing_map::TimingMap;
+use k8dnz_core::signal::token::{Rgb, RgbPairToken};
 use k8dnz_core::Engine;
 
 use crate::io::{recipe_file, timemap};
 
 const BF1_MAGIC: &[u8; 4] = b"BF1\0";
 const BF2_MAGIC: &[u8; 4] = b"BF2\0";
+const BF4_MAGIC: &[u8; 4] = b"BF4\0";
 
 const BF1_FLAG_CHUNK_ADDK: u8 = 1u8 << 0;
+const BF4_FLAG_CHUNK_ADDK: u8 = 1u8 << 0;
+
+// BF4 layout (all integers LE):
+//   "BF4\0" | bits:u8 | mapping:u8 | flags:u8 | 0:u8
+//   orig_len_bytes:u64 | symbol_count:u64 | chunk_symbols:u32 | chunk_count:u32
+//   [flags & CHUNK_ADDK: addk_chunk_size:u32 | addk_count:u32 | addk bytes]
+//   chunk_offsets: chunk_count x u64 (absolute file offset of each chunk record)
+//   chunk record: chunk_index:u32 | start_symbol:u64 | compressed_length:u32 | zstd(packed symbols)
+const BF4_HEADER_LEN: usize = 32;
+const BF4_CHUNK_HEADER_LEN: usize = 16;
 
 fn zstd_compress(bytes: &[u8], level: i32) -> anyhow::Result<Vec<u8>> {
     zstd::encode_all(bytes, level).map_err(|e| anyhow::anyhow!("zstd compress: {e}"))
@@ -32,7 +51,8 @@ pub enum BitfieldResidual {
         symbol_count: usize,
         chunk_size: Option<usize>,
         chunk_addk: Option<Vec<u8>>,
-        packed_symbols: Vec<u8>,
+        /// Unpacked residual symbols.
+        symbols: Vec<u8>,
     },
     Bf2 {
         bits_per_emission: u8,
@@ -42,6 +62,15 @@ pub enum BitfieldResidual {
         lane_count: usize,
         lanes_raw_bitsets: Vec<Vec<u8>>,
     },
+    /// Fully decoded BF4 (reconstruct reads ranges through `Bf4Index` instead).
+    Bf4 {
+        bits_per_emission: u8,
+        mapping: BitMapping,
+        orig_len_bytes: usize,
+        chunk_size: Option<usize>,
+        chunk_addk: Option<Vec<u8>>,
+        symbols: Vec<u8>,
+    },
 }
 
 fn mapping_tag(m: BitMapping) -> u8 {
@@ -49,6 +78,8 @@ fn mapping_tag(m: BitMapping) -> u8 {
         BitMapping::Geom => 0,
         BitMapping::Hash => 1,
         BitMapping::LowpassThresh => 2,
+        BitMapping::LabGeom => 3,
+        BitMapping::RgbMoment => 4,
     }
 }
 
@@ -57,70 +88,30 @@ fn mapping_from_tag(v: u8) -> anyhow::Result<BitMapping> {
         0 => Ok(BitMapping::Geom),
         1 => Ok(BitMapping::Hash),
         2 => Ok(BitMapping::LowpassThresh),
+        3 => Ok(BitMapping::LabGeom),
+        4 => Ok(BitMapping::RgbMoment),
         _ => anyhow::bail!("bitfield residual unknown mapping tag: {}", v),
     }
 }
 
-fn read_bitfield_residual(path: &str) -> anyhow::Result<BitfieldResidual> {
-    let bytes = std::fs::read(path).with_context(|| format!("read bf: {}", path))?;
+pub(crate) fn read_bitfield_residual(path: &str) -> anyhow::Result<BitfieldResidual> {
+    let mut f = std::fs::File::open(path).with_context(|| format!("read bf: {}", path))?;
+    let file_len = f.metadata().with_context(|| format!("read bf: {}", path))?.len();
 
-    if bytes.len() < 24 {
-        anyhow::bail!("bitfield residual too small: {} bytes", bytes.len());
+    if file_len < 24 {
+        anyhow::bail!("bitfield residual too small: {} bytes", file_len);
     }
 
-    let magic = &bytes[0..4];
-
-    if magic == BF1_MAGIC {
-        let bits = bytes[4];
-        let mapping_u8 = bytes[5];
-        let mapping = mapping_from_tag(mapping_u8)?;
-
-        let flags = bytes[6];
-        let _reserved = bytes[7];
-
-        let orig_len_bytes = u64::from_le_bytes(bytes[8..16].try_into().unwrap()) as usize;
-        let symbol_count = u64::from_le_bytes(bytes[16..24].try_into().unwrap()) as usize;
-
-        let mut cursor = 24usize;
-
-        let mut chunk_size: Option<usize> = None;
-        let mut chunk_addk: Option<Vec<u8>> = None;
-
-        if (flags & BF1_FLAG_CHUNK_ADDK) != 0 {
-            if bytes.len() < cursor + 8 {
-                anyhow::bail!("BF1 truncated reading chunk header");
-            }
-            let cs = u32::from_le_bytes(bytes[cursor..cursor + 4].try_into().unwrap()) as usize;
-            let cc =
-                u32::from_le_bytes(bytes[cursor + 4..cursor + 8].try_into().unwrap()) as usize;
-            cursor += 8;
-
-            if cs == 0 {
-                anyhow::bail!("BF1 invalid: chunk_size=0");
-            }
-            if bytes.len() < cursor + cc {
-                anyhow::bail!("BF1 truncated reading chunk_addk");
-            }
-            let ks = bytes[cursor..cursor + cc].to_vec();
-            cursor += cc;
-
-            chunk_size = Some(cs);
-            chunk_addk = Some(ks);
-        }
-
-        let payload = bytes[cursor..].to_vec();
-
-        return Ok(BitfieldResidual::Bf1 {
-            bits_per_emission: bits,
-            mapping,
-            orig_len_bytes,
-            symbol_count,
-            chunk_size,
-            chunk_addk,
-            packed_symbols: payload,
-        });
+    let mut head = [0u8; 4];
+    f.read_exact(&mut head).with_context(|| format!("read bf: {}", path))?;
+    if &head == BF1_MAGIC {
+        return read_bf1(std::io::BufReader::new(f), file_len);
     }
 
+    let mut bytes = head.to_vec();
+    f.read_to_end(&mut bytes).with_context(|| format!("read bf: {}", path))?;
+    let magic = &bytes[0..4];
+
     if magic == BF2_MAGIC {
         if bytes.len() < 32 {
             anyhow::bail!("BF2 residual too small: {} bytes", bytes.len());
@@ -174,7 +165,245 @@ fn read_bitfield_residual(path: &str) -> anyhow::Result<BitfieldResidual> {
         });
     }
 
-    anyhow::bail!("bitfield residual bad magic (expected BF1\\0 or BF2\\0)");
+    if magic == BF4_MAGIC {
+        let mut cur = std::io::Cursor::new(&bytes);
+        let idx = Bf4Index::read(&mut cur)?;
+        let symbols = idx.read_symbols(&mut cur, 0, idx.symbol_count)?;
+        let (chunk_size, chunk_addk) = match idx.chunk_addk {
+            Some((cs, ks)) => (Some(cs), Some(ks)),
+            None => (None, None),
+        };
+        return Ok(BitfieldResidual::Bf4 {
+            bits_per_emission: idx.bits_per_emission,
+            mapping: idx.mapping,
+            orig_len_bytes: idx.orig_len_bytes,
+            chunk_size,
+            chunk_addk,
+            symbols,
+        });
+    }
+
+    anyhow::bail!("bitfield residual bad magic (expected BF1\\0, BF2\\0 or BF4\\0)");
+}
+
+/// BF1 straight off the file (past the magic): the packed payload is unpacked as it
+/// is read, so only the symbols are held in memory.
+fn read_bf1<R: Read>(mut r: R, file_len: u64) -> anyhow::Result<BitfieldResidual> {
+    let mut h = [0u8; 20];
+    r.read_exact(&mut h).context("BF1 truncated reading header")?;
+    let bits = h[0];
+    let mapping = mapping_from_tag(h[1])?;
+    let flags = h[2];
+    let orig_len_bytes = u64::from_le_bytes(h[4..12].try_into().unwrap()) as usize;
+    let symbol_count = u64::from_le_bytes(h[12..20].try_into().unwrap()) as usize;
+    let mut consumed = 24u64;
+
+    let mut chunk_size: Option<usize> = None;
+    let mut chunk_addk: Option<Vec<u8>> = None;
+
+    if (flags & BF1_FLAG_CHUNK_ADDK) != 0 {
+        let mut ah = [0u8; 8];
+        r.read_exact(&mut ah).context("BF1 truncated reading chunk header")?;
+        let cs = u32::from_le_bytes(ah[0..4].try_into().unwrap()) as usize;
+        let cc = u32::from_le_bytes(ah[4..8].try_into().unwrap()) as usize;
+        consumed += 8;
+
+        if cs == 0 {
+            anyhow::bail!("BF1 invalid: chunk_size=0");
+        }
+        if cc as u64 > file_len.saturating_sub(consumed) {
+            anyhow::bail!("BF1 truncated reading chunk_addk");
+        }
+        let mut ks = vec![0u8; cc];
+        r.read_exact(&mut ks).context("BF1 truncated reading chunk_addk")?;
+        consumed += cc as u64;
+
+        chunk_size = Some(cs);
+        chunk_addk = Some(ks);
+    }
+
+    // symbol_count comes from the file; check it against the payload before allocating.
+    let packed_len = (symbol_count as u128 * bits as u128).div_ceil(8);
+    let left = file_len.saturating_sub(consumed);
+    if packed_len > left as u128 {
+        anyhow::bail!(
+            "BF1 truncated: {} symbols at {} bits need {} packed bytes, {} left",
+            symbol_count,
+            bits,
+            packed_len,
+            left
+        );
+    }
+    let symbols = bitpack::unpack_symbols_from_reader(bits, &mut r, symbol_count)
+        .context("BF1: unpack residual symbols")?;
+
+    Ok(BitfieldResidual::Bf1 {
+        bits_per_emission: bits,
+        mapping,
+        orig_len_bytes,
+        symbol_count,
+        chunk_size,
+        chunk_addk,
+        symbols,
+    })
+}
+
+/// BF4 header and chunk offset table; chunks themselves are read on demand.
+pub(crate) struct Bf4Index {
+    pub bits_per_emission: u8,
+    pub mapping: BitMapping,
+    pub orig_len_bytes: usize,
+    pub symbol_count: usize,
+    pub chunk_symbols: usize,
+    /// (addk chunk size, per-chunk k) when the fit used chunk_xform=addk.
+    pub chunk_addk: Option<(usize, Vec<u8>)>,
+    offsets: Vec<u64>,
+}
+
+/// Bytes left between the current position and the end of `r`.
+fn remaining_bytes<R: Seek>(r: &mut R) -> anyhow::Result<u64> {
+    let pos = r.stream_position()?;
+    let end = r.seek(SeekFrom::End(0))?;
+    r.seek(SeekFrom::Start(pos))?;
+    Ok(end.saturating_sub(pos))
+}
+
+/// Reads `n` bytes, refusing up front when fewer remain so a corrupt length
+/// never drives the allocation.
+fn read_exact_vec<R: Read + Seek>(r: &mut R, n: usize, what: &str) -> anyhow::Result<Vec<u8>> {
+    let left = remaining_bytes(r)?;
+    if n as u64 > left {
+        anyhow::bail!("BF4 truncated reading {}: need {} bytes, {} left", what, n, left);
+    }
+    let mut buf = vec![0u8; n];
+    r.read_exact(&mut buf)
+        .with_context(|| format!("BF4 truncated reading {}", what))?;
+    Ok(buf)
+}
+
+impl Bf4Index {
+    pub(crate) fn read<R: Read + Seek>(r: &mut R) -> anyhow::Result<Self> {
+        r.seek(SeekFrom::Start(0))?;
+        let h = read_exact_vec(r, BF4_HEADER_LEN, "header")?;
+        if &h[0..4] != BF4_MAGIC {
+            anyhow::bail!("BF4 bad magic");
+        }
+        let bits_per_emission = h[4];
+        let mapping = mapping_from_tag(h[5])?;
+        let flags = h[6];
+        let orig_len_bytes = u64::from_le_bytes(h[8..16].try_into().unwrap()) as usize;
+        let symbol_count = u64::from_le_bytes(h[16..24].try_into().unwrap()) as usize;
+        let chunk_symbols = u32::from_le_bytes(h[24..28].try_into().unwrap()) as usize;
+        let chunk_count = u32::from_le_bytes(h[28..32].try_into().unwrap()) as usize;
+
+        if bits_per_emission == 0 || bits_per_emission > 8 {
+            anyhow::bail!("BF4 invalid: bits_per_emission={}", bits_per_emission);
+        }
+        if chunk_symbols == 0 {
+            anyhow::bail!("BF4 invalid: chunk_symbols=0");
+        }
+        if chunk_count != symbol_count.div_ceil(chunk_symbols) {
+            anyhow::bail!(
+                "BF4 invalid: chunk_count={} for symbols={} chunk_symbols={}",
+                chunk_count,
+                symbol_count,
+                chunk_symbols
+            );
+        }
+
+        let chunk_addk = if (flags & BF4_FLAG_CHUNK_ADDK) != 0 {
+            let ah = read_exact_vec(r, 8, "chunk_addk header")?;
+            let cs = u32::from_le_bytes(ah[0..4].try_into().unwrap()) as usize;
+            let cc = u32::from_le_bytes(ah[4..8].try_into().unwrap()) as usize;
+            if cs == 0 {
+                anyhow::bail!("BF4 invalid: addk chunk_size=0");
+            }
+            Some((cs, read_exact_vec(r, cc, "chunk_addk")?))
+        } else {
+            None
+        };
+
+        // chunk_count comes from the file; bound it by what the offset table can hold.
+        let left = remaining_bytes(r)?;
+        if chunk_count as u64 > left / 8 {
+            anyhow::bail!(
+                "BF4 invalid: chunk_count={} but only {} bytes remain for the offset table",
+                chunk_count,
+                left
+            );
+        }
+        let mut offsets = Vec::with_capacity(chunk_count);
+        let table = read_exact_vec(r, chunk_count * 8, "chunk offsets")?;
+        offsets.extend(
+            table
+                .chunks_exact(8)
+                .map(|c| u64::from_le_bytes(c.try_into().unwrap())),
+        );
+
+        Ok(Self {
+            bits_per_emission,
+            mapping,
+            orig_len_bytes,
+            symbol_count,
+            chunk_symbols,
+            chunk_addk,
+            offsets,
+        })
+    }
+
+    /// Symbols `[start, end)`, decompressing only the chunks that overlap the range.
+    pub(crate) fn read_symbols<R: Read + Seek>(
+        &self,
+        r: &mut R,
+        start: usize,
+        end: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        if start > end || end > self.symbol_count {
+            anyhow::bail!(
+                "BF4 symbol range {}..{} outside 0..{}",
+                start,
+                end,
+                self.symbol_count
+            );
+        }
+        let mut out = Vec::with_capacity(end - start);
+        if start == end {
+            return Ok(out);
+        }
+
+        let first = start / self.chunk_symbols;
+        let last = (end - 1) / self.chunk_symbols;
+        for ci in first..=last {
+            let chunk_start = ci * self.chunk_symbols;
+            let n = self.chunk_symbols.min(self.symbol_count - chunk_start);
+
+            r.seek(SeekFrom::Start(self.offsets[ci]))
+                .with_context(|| format!("BF4 seek chunk {}", ci))?;
+            let ch = read_exact_vec(r, BF4_CHUNK_HEADER_LEN, "chunk header")?;
+            let idx = u32::from_le_bytes(ch[0..4].try_into().unwrap()) as usize;
+            let sym0 = u64::from_le_bytes(ch[4..12].try_into().unwrap()) as usize;
+            let clen = u32::from_le_bytes(ch[12..16].try_into().unwrap()) as usize;
+            if idx != ci || sym0 != chunk_start {
+                anyhow::bail!(
+                    "BF4 chunk {} header mismatch: index={} start_symbol={} (want {})",
+                    ci,
+                    idx,
+                    sym0,
+                    chunk_start
+                );
+            }
+
+            let comp = read_exact_vec(r, clen, "chunk payload")?;
+            let packed = zstd_decompress_bytes(&comp)?;
+            let syms = bitpack::unpack_symbols(self.bits_per_emission, &packed, n)
+                .map_err(|e| anyhow::anyhow!("BF4 chunk {}: {e}", ci))?;
+
+            let lo = start.max(chunk_start) - chunk_start;
+            let hi = end.min(chunk_start + n) - chunk_start;
+            out.extend_from_slice(&syms[lo..hi]);
+        }
+        Ok(out)
+    }
 }
 
 fn write_bitfield_residual_bf1(
@@ -185,10 +414,7 @@ fn write_bitfield_residual_bf1(
     residual_symbols: &[u8],
     chunk_size: Option<usize>,
     chunk_addk: Option<&[u8]>,
-) -> anyhow::Result<Vec<u8>> {
-    let packed = bitpack::pack_symbols(bits_per_emission, residual_symbols)
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
-
+) -> anyhow::Result<usize> {
     let mut flags: u8 = 0;
     let mut extra: Vec<u8> = Vec::new();
 
@@ -204,19 +430,25 @@ fn write_bitfield_residual_bf1(
         extra.extend_from_slice(ks);
     }
 
-    let mut out: Vec<u8> = Vec::with_capacity(24 + extra.len() + packed.len());
-    out.extend_from_slice(BF1_MAGIC);
-    out.push(bits_per_emission);
-    out.push(mapping_tag(mapping));
-    out.push(flags);
-    out.push(0u8);
-    out.extend_from_slice(&(orig_len_bytes as u64).to_le_bytes());
-    out.extend_from_slice(&(residual_symbols.len() as u64).to_le_bytes());
-    out.extend_from_slice(&extra);
-    out.extend_from_slice(&packed);
-
-    std::fs::write(path, &out).with_context(|| format!("write BF1 residual: {}", path))?;
-    Ok(out)
+    let mut header: Vec<u8> = Vec::with_capacity(24 + extra.len());
+    header.extend_from_slice(BF1_MAGIC);
+    header.push(bits_per_emission);
+    header.push(mapping_tag(mapping));
+    header.push(flags);
+    header.push(0u8);
+    header.extend_from_slice(&(orig_len_bytes as u64).to_le_bytes());
+    header.extend_from_slice(&(residual_symbols.len() as u64).to_le_bytes());
+    header.extend_from_slice(&extra);
+
+    let f = std::fs::File::create(path).with_context(|| format!("create BF1 residual: {}", path))?;
+    let mut w = BufWriter::new(f);
+    w.write_all(&header).with_context(|| format!("write BF1 residual: {}", path))?;
+    bitpack::pack_symbols_to_writer(bits_per_emission, residual_symbols, &mut w)
+        .context("BF1: pack residual symbols")?;
+    w.flush().with_context(|| format!("write BF1 residual: {}", path))?;
+
+    let packed_len = (residual_symbols.len() * bits_per_emission as usize).div_ceil(8);
+    Ok(header.len() + packed_len)
 }
 
 fn write_bitfield_residual_bf2(
@@ -268,6 +500,67 @@ fn write_bitfield_residual_bf2(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+fn write_bitfield_residual_bf4(
+    path: &str,
+    bits_per_emission: u8,
+    mapping: BitMapping,
+    orig_len_bytes: usize,
+    residual_symbols: &[u8],
+    chunk_symbols: usize,
+    zstd_level: i32,
+    chunk_size: Option<usize>,
+    chunk_addk: Option<&[u8]>,
+) -> anyhow::Result<Vec<u8>> {
+    if chunk_symbols == 0 || chunk_symbols > u32::MAX as usize {
+        anyhow::bail!("BF4: chunk symbols must be in 1..=u32::MAX");
+    }
+    let chunk_count = residual_symbols.len().div_ceil(chunk_symbols);
+
+    let mut flags: u8 = 0;
+    let mut extra: Vec<u8> = Vec::new();
+    if let (Some(cs), Some(ks)) = (chunk_size, chunk_addk) {
+        if cs == 0 {
+            anyhow::bail!("BF4: chunk_size must be > 0");
+        }
+        flags |= BF4_FLAG_CHUNK_ADDK;
+        extra.extend_from_slice(&(cs as u32).to_le_bytes());
+        extra.extend_from_slice(&(ks.len() as u32).to_le_bytes());
+        extra.extend_from_slice(ks);
+    }
+
+    let mut out: Vec<u8> = Vec::new();
+    out.extend_from_slice(BF4_MAGIC);
+    out.push(bits_per_emission);
+    out.push(mapping_tag(mapping));
+    out.push(flags);
+    out.push(0u8);
+    out.extend_from_slice(&(orig_len_bytes as u64).to_le_bytes());
+    out.extend_from_slice(&(residual_symbols.len() as u64).to_le_bytes());
+    out.extend_from_slice(&(chunk_symbols as u32).to_le_bytes());
+    out.extend_from_slice(&(chunk_count as u32).to_le_bytes());
+    out.extend_from_slice(&extra);
+
+    let table_at = out.len();
+    out.resize(table_at + chunk_count * 8, 0u8);
+
+    for (ci, chunk) in residual_symbols.chunks(chunk_symbols).enumerate() {
+        let packed =
+            bitpack::pack_symbols(bits_per_emission, chunk).map_err(|e| anyhow::anyhow!("{e}"))?;
+        let comp = zstd_compress(&packed, zstd_level)?;
+
+        let off = out.len() as u64;
+        out[table_at + ci * 8..table_at + ci * 8 + 8].copy_from_slice(&off.to_le_bytes());
+        out.extend_from_slice(&(ci as u32).to_le_bytes());
+        out.extend_from_slice(&((ci * chunk_symbols) as u64).to_le_bytes());
+        out.extend_from_slice(&(comp.len() as u32).to_le_bytes());
+        out.extend_from_slice(&comp);
+    }
+
+    std::fs::write(path, &out).with_context(|| format!("write BF4 residual: {}", path))?;
+    Ok(out)
+}
+
 pub(crate) fn write_bitfield_residual(
     path: &str,
     bits_per_emission: u8,
@@ -276,33 +569,45 @@ pub(crate) fn write_bitfield_residual(
     residual_symbols: &[u8],
     zstd_level: i32,
     encoding: BitfieldResidualEncoding,
+    bf4_chunk_symbols: usize,
     chunk_size: Option<usize>,
     chunk_addk: Option<&[u8]>,
 ) -> anyhow::Result<usize> {
     match encoding {
-        BitfieldResidualEncoding::Packed => {
-            let bytes = write_bitfield_residual_bf1(
+        BitfieldResidualEncoding::Packed => write_bitfield_residual_bf1(
+            path,
+            bits_per_emission,
+            mapping,
+            orig_len_bytes,
+            residual_symbols,
+            chunk_size,
+            chunk_addk,
+        ),
+        BitfieldResidualEncoding::Lanes => {
+            write_bitfield_residual_bf2(
                 path,
                 bits_per_emission,
                 mapping,
                 orig_len_bytes,
                 residual_symbols,
-                chunk_size,
-                chunk_addk,
+                zstd_level,
             )?;
-            Ok(bytes.len())
+            let n = std::fs::read(path).map(|b| b.len()).unwrap_or(0usize);
+            Ok(n)
         }
-        BitfieldResidualEncoding::Lanes => {
-            write_bitfield_residual_bf2(
+        BitfieldResidualEncoding::Chunked => {
+            let bytes = write_bitfield_residual_bf4(
                 path,
                 bits_per_emission,
                 mapping,
                 orig_len_bytes,
                 residual_symbols,
+                bf4_chunk_symbols,
                 zstd_level,
+                chunk_size,
+                chunk_addk,
             )?;
-            let n = std::fs::read(path).map(|b| b.len()).unwrap_or(0usize);
-            Ok(n)
+            Ok(bytes.len())
         }
     }
 }
@@ -329,6 +634,45 @@ fn geom_symbol_from_rgb_msb_interleave(rgb6: &[u8; 6], bits_per_emission: u8) ->
     out & mask
 }
 
+/// LabGeom: chroma sqrt(a*^2 + b*^2) above this sets bit 3.
+const LAB_CHROMA_SPLIT: f64 = 30.0;
+
+/// Perceptual Geom: the first four bits split L*a*b* space at mid lightness, the a*
+/// and b* axes and a chroma ring; bits 4.. continue down L* scaled to 0..=255 (bit 4 is
+/// its second-highest bit, bit 0 being roughly the highest).
+fn lab_geom_symbol_from_rgb6(rgb6: &[u8; 6], bits_per_emission: u8) -> u8 {
+    let tok = RgbPairToken {
+        a: Rgb::new(rgb6[0], rgb6[1], rgb6[2]),
+        c: Rgb::new(rgb6[3], rgb6[4], rgb6[5]),
+    };
+    let (l, a, b) = tok.to_lab();
+    let lq = (l * 2.55).round().clamp(0.0, 255.0) as u8;
+
+    let mut out = (l >= 50.0) as u8
+        | ((a > 0.0) as u8) << 1
+        | ((b > 0.0) as u8) << 2
+        | ((a * a + b * b > LAB_CHROMA_SPLIT * LAB_CHROMA_SPLIT) as u8) << 3;
+    for i in 4..bits_per_emission.min(8) {
+        out |= ((lq >> (10 - i)) & 1) << i;
+    }
+    out & sym_mask(bits_per_emission)
+}
+
+/// Moment comparisons between the A and C colors; bit i of the symbol is the i-th
+/// comparison, so fewer bits per emission keep the leading (per-channel) ones.
+fn rgb_moment_symbol_from_rgb6(rgb6: &[u8; 6], bits_per_emission: u8) -> u8 {
+    let [ra, ga, ba, rc, gc, bc] = rgb6.map(i16::from);
+    let out = (ra > rc) as u8
+        | ((ga > gc) as u8) << 1
+        | ((ba > bc) as u8) << 2
+        | ((ra + ga > rc + gc) as u8) << 3
+        | ((ra.max(ga).max(ba) > rc.max(gc).max(bc)) as u8) << 4
+        | ((ra.min(ga).min(ba) > rc.min(gc).min(bc)) as u8) << 5
+        | ((ra - ga > rc - gc) as u8) << 6
+        | ((ba - ga > bc - gc) as u8) << 7;
+    out & sym_mask(bits_per_emission)
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct LowpassState {
     y: u16,
@@ -377,6 +721,8 @@ pub(crate) fn map_symbol_bitfield(
 
     match mapping {
         BitMapping::Geom => geom_symbol_from_rgb_msb_interleave(rgb6, bits_per_emission),
+        BitMapping::LabGeom => lab_geom_symbol_from_rgb6(rgb6, bits_per_emission),
+        BitMapping::RgbMoment => rgb_moment_symbol_from_rgb6(rgb6, bits_per_emission),
         BitMapping::Hash => {
             let mut x = map_seed ^ emission.rotate_left(17);
             for &b in rgb6.iter() {
@@ -399,6 +745,7 @@ pub(crate) fn map_symbol_bitfield(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn ensure_symbol_stream_len(
     engine: &mut Engine,
     stream_syms: &mut Vec<u8>,
@@ -411,6 +758,7 @@ fn ensure_symbol_stream_len(
     bit_tau: u16,
     bit_smooth_shift: u8,
     lp_state: &mut LowpassState,
+    mut stream_quality: Option<&mut Vec<u8>>,
 ) -> bool {
     if stream_syms.len() >= need_len {
         return true;
@@ -422,7 +770,8 @@ fn ensure_symbol_stream_len(
     {
         if let Some(tok) = engine.step() {
             let em = (engine.stats.emissions - 1) as u64;
-            let rgb6 = tok.to_rgb_pair().to_bytes();
+            let rgb = tok.to_rgb_pair();
+            let rgb6 = rgb.to_bytes();
             let sym = map_symbol_bitfield(
                 mapping,
                 map_seed,
@@ -434,6 +783,9 @@ fn ensure_symbol_stream_len(
                 lp_state,
             );
             stream_syms.push(sym);
+            if let Some(q) = stream_quality.as_deref_mut() {
+                q.push(rgb.contrast());
+            }
         }
     }
 
@@ -444,14 +796,6 @@ fn apply_chunk_addk(pred: u8, k: u8, mask: u8) -> u8 {
     pred.wrapping_add(k) & mask
 }
 
-fn proxy_cost_for_residual(resid_mode: ResidualMode, resid_sym: u8) -> usize {
-    if resid_mode == ResidualMode::Xor {
-        resid_sym.count_ones() as usize
-    } else {
-        if resid_sym == 0 { 0 } else { 1 }
-    }
-}
-
 fn pack_bits01_to_u64(bits01: &[u8]) -> Vec<u64> {
     let n = bits01.len();
     let words = (n + 63) / 64;
@@ -504,126 +848,193 @@ fn hamming01_aligned(target_words: &[u64], stream_words: &[u64], start_bit: usiz
     acc
 }
 
-pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()> {
-    if a.mode != ApplyMode::Rgbpair {
-        anyhow::bail!("--map bitfield requires --mode rgbpair");
-    }
-    if a.bits_per_emission == 0 || a.bits_per_emission > 8 {
-        anyhow::bail!("--bits-per-emission must be in 1..=8");
-    }
-    if a.bit_mapping == BitMapping::LowpassThresh && a.bits_per_emission != 1 {
-        anyhow::bail!("bit-mapping lowpass-thresh requires --bits-per-emission 1");
-    }
-    if a.chunk_size == 0 {
-        anyhow::bail!("--chunk-size must be >= 1");
-    }
-    if a.scan_step == 0 {
-        anyhow::bail!("--scan-step must be >= 1");
-    }
+/// Per-chunk outcome of `fit_chunks`, used by --trans-penalty-calibrate.
+#[derive(Clone, Copy, Debug)]
+struct ChunkFitStat {
+    len: usize,
+    matches: u64,
+    jump_cost: usize,
+}
 
-    let recipe = recipe_file::load_k8r(&a.recipe)?;
-    let recipe_raw_len = std::fs::read(&a.recipe).map(|b| b.len()).unwrap_or(0usize);
+struct ChunkFitOut {
+    tm_indices: Vec<u64>,
+    residual_syms: Vec<u8>,
+    chunk_addk: Vec<u8>,
+    chunks: Vec<ChunkFitStat>,
+    /// Ternary only: symbols whose residual was clamped and will not reconstruct.
+    clipped: usize,
+}
 
-    let target_bytes =
-        std::fs::read(&a.target).with_context(|| format!("read target: {}", a.target))?;
-    if target_bytes.is_empty() {
-        anyhow::bail!("target is empty");
-    }
+/// --bitfield-quality-weight applies only to Geom, whose bits come straight from the colors.
+fn quality_tracked(a: &FitXorChunkedArgs) -> bool {
+    a.bitfield_quality_weight > 0.0 && a.bit_mapping == BitMapping::Geom
+}
 
-    let seed = parse_seed_hex_opt(a.map_seed, &a.map_seed_hex)?;
+/// Window score add-on for --bitfield-quality-weight: weight * sum((255 - contrast) / 255).
+fn quality_penalty(contrast: &[u8], weight: f64) -> usize {
+    let low: u64 = contrast.iter().map(|&c| 255 - c as u64).sum();
+    (weight * low as f64 / 255.0).round() as usize
+}
 
-    let bit_len: usize = target_bytes.len() * 8;
-    let bits: usize = a.bits_per_emission as usize;
-    let sym_count: usize = (bit_len + bits - 1) / bits;
+/// Window score add-on: tm jump cost scaled by trans_penalty, plus --diversity-penalty.
+fn placement_cost(
+    prev_pos: Option<u64>,
+    base_pos: u64,
+    trans_penalty: u64,
+    diversity: &DiversityTracker,
+) -> usize {
+    let jump = (tm_jump_cost(prev_pos, base_pos) as u64).saturating_mul(trans_penalty) as usize;
+    jump.saturating_add(diversity.penalty(base_pos))
+}
 
-    let total_bits: usize = sym_count.saturating_mul(bits);
-    let need_bytes: usize = (total_bits + 7) / 8;
-    let mut padded_target: Vec<u8> = vec![0u8; need_bytes];
-    padded_target[..target_bytes.len()].copy_from_slice(&target_bytes);
+/// One scored window. Picks compare by (score, start), so the lowest start wins a tie
+/// whatever order the windows were scored in (--parallel-scan).
+#[derive(Clone, Copy, Debug)]
+struct WindowPick {
+    score: usize,
+    start: usize,
+    matches: u64,
+    resid_metric: usize,
+    k: u8,
+}
 
-    let target_syms = bitpack::unpack_symbols(a.bits_per_emission, &padded_target, sym_count)
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
+impl WindowPick {
+    fn key(&self) -> (usize, usize) {
+        (self.score, self.start)
+    }
+}
 
-    let mask = sym_mask(a.bits_per_emission);
+/// Candidate window starts of one chunk: `min_start..=max_start` every `step`.
+#[derive(Clone, Copy, Debug)]
+struct ScanRange {
+    min_start: usize,
+    max_start: usize,
+    step: usize,
+    parallel: bool,
+}
 
-    let mut engine = Engine::new(recipe)?;
+impl ScanRange {
+    fn len(&self) -> u64 {
+        ((self.max_start - self.min_start) / self.step + 1) as u64
+    }
 
-    while (engine.stats.emissions as u64) < a.start_emission && engine.stats.ticks < a.max_ticks {
-        let _ = engine.step();
-        if (engine.stats.emissions as u64) >= a.search_emissions {
-            break;
+    fn start(&self, i: usize) -> usize {
+        self.min_start + i * self.step
+    }
+
+    /// Lowest-key pick over every window; `score` must not touch shared mutable state.
+    fn best<F: Fn(usize) -> WindowPick + Sync>(&self, score: F) -> Option<WindowPick> {
+        let n = self.len() as usize;
+        if self.parallel {
+            (0..n)
+                .into_par_iter()
+                .map(|i| score(self.start(i)))
+                .min_by_key(WindowPick::key)
+        } else {
+            (0..n).map(|i| score(self.start(i))).min_by_key(WindowPick::key)
         }
     }
 
-    let start_ticks = engine.stats.ticks;
-    let start_em = engine.stats.emissions as u64;
+    /// `f(start)` for every window, in start order.
+    fn collect<T: Send, F: Fn(usize) -> T + Sync>(&self, f: F) -> Vec<T> {
+        let n = self.len() as usize;
+        if self.parallel {
+            (0..n).into_par_iter().map(|i| f(self.start(i))).collect()
+        } else {
+            (0..n).map(|i| f(self.start(i))).collect()
+        }
+    }
+}
 
-    let mut stream_syms: Vec<u8> = Vec::new();
-    stream_syms.reserve((a.search_emissions.saturating_sub(start_em)).min(500_000) as usize);
+/// Zstd-objective score of the window at `start`: zstd of the residual plus `jump_cost`.
+/// With addk, the four most common per-symbol offsets are tried and the first best kept.
+#[allow(clippy::too_many_arguments)]
+fn zstd_window_pick(
+    a: &FitXorChunkedArgs,
+    mask: u8,
+    stream_win: &[u8],
+    target_win: &[u8],
+    start: usize,
+    jump_cost: usize,
+    want_addk: bool,
+    scratch_resid: &mut [u8],
+) -> WindowPick {
+    let mut score_k = |k: u8| {
+        let mut matches: u64 = 0;
+        for (i, (&s, &t)) in stream_win.iter().zip(target_win).enumerate() {
+            let pred = apply_chunk_addk(s & mask, k, mask);
+            let resid = make_residual_symbol(a.residual, pred, t & mask, mask);
+            scratch_resid[i] = resid;
+            if resid == 0 {
+                matches += 1;
+            }
+        }
+        let zlen = zstd_compress_len(scratch_resid, a.zstd_level);
+        WindowPick {
+            score: zlen.saturating_add(jump_cost),
+            start,
+            matches,
+            resid_metric: zlen,
+            k,
+        }
+    };
 
-    let mut lp_state = LowpassState::new();
+    if !want_addk {
+        return score_k(0);
+    }
 
-    while (engine.stats.emissions as u64) < a.search_emissions && engine.stats.ticks < a.max_ticks {
-        if let Some(tok) = engine.step() {
-            let em = (engine.stats.emissions - 1) as u64;
-            let rgb6 = tok.to_rgb_pair().to_bytes();
-            let sym = map_symbol_bitfield(
-                a.bit_mapping,
-                seed,
-                em,
-                &rgb6,
-                a.bits_per_emission,
-                a.bit_tau,
-                a.bit_smooth_shift,
-                &mut lp_state,
-            );
-            stream_syms.push(sym & mask);
-        }
+    let alpha = 1usize << (a.bits_per_emission as usize);
+    let mut counts: Vec<u32> = vec![0u32; alpha];
+    for (&s, &t) in stream_win.iter().zip(target_win) {
+        let ksym = (t & mask).wrapping_sub(s & mask) & mask;
+        counts[ksym as usize] = counts[ksym as usize].saturating_add(1);
     }
 
-    let abs_stream_base_pos: u64 = a.start_emission;
-    let total_n = target_syms.len();
+    let mut ks: Vec<(u32, u8)> = (0..alpha).map(|k| (counts[k], k as u8)).collect();
+    ks.sort_by(|a1, b1| b1.0.cmp(&a1.0).then_with(|| a1.1.cmp(&b1.1)));
+    ks.truncate(4);
 
+    ks.iter()
+        .map(|&(_cnt, k)| score_k(k))
+        .min_by_key(WindowPick::key)
+        .expect("alphabet has at least 2 symbols")
+}
+
+/// Greedy chunk-by-chunk window search over `stream_syms` (extended on demand).
+/// `max_chunks == 0` means no limit.
+#[allow(clippy::too_many_arguments)]
+fn fit_chunks(
+    a: &FitXorChunkedArgs,
+    seed: u64,
+    mask: u8,
+    target_syms: &[u8],
+    abs_stream_base_pos: u64,
+    first_min_pos: u64,
+    trans_penalty: u64,
+    max_chunks: usize,
+    log_chunks: bool,
+    engine: &mut Engine,
+    stream_syms: &mut Vec<u8>,
+    stream_quality: &mut Vec<u8>,
+    lp_state: &mut LowpassState,
+) -> ChunkFitOut {
+    let total_n = target_syms.len();
     let mut tm_indices: Vec<u64> = Vec::with_capacity(total_n.min(stream_syms.len()));
     let mut residual_syms: Vec<u8> = Vec::with_capacity(total_n.min(stream_syms.len()));
-
+    let mut clipped: usize = 0;
     let mut chunk_addk: Vec<u8> = Vec::new();
-
-    eprintln!(
-        "--- fit-xor-chunked (bitfield) --- map=bitfield bits_per_emission={} bit_mapping={:?} map_seed={} (0x{:016x}) bit_tau={} bit_smooth_shift={} residual={:?} objective={:?} refine_topk={} lookahead={} trans_penalty={} chunk_size={} scan_step={} zstd_level={} chunk_xform={:?} target_bytes={} target_symbols={} stream_symbols={} base_pos={} start_emission={} end_emissions={} ticks={} delta_ticks={}",
-        a.bits_per_emission,
-        a.bit_mapping,
-        seed,
-        seed,
-        a.bit_tau,
-        a.bit_smooth_shift,
-        a.residual,
-        a.objective,
-        a.refine_topk,
-        a.lookahead,
-        a.trans_penalty,
-        a.chunk_size,
-        a.scan_step,
-        a.zstd_level,
-        a.chunk_xform,
-        target_bytes.len(),
-        total_n,
-        stream_syms.len(),
-        abs_stream_base_pos,
-        a.start_emission,
-        (start_em + (stream_syms.len() as u64)),
-        engine.stats.ticks,
-        engine.stats.ticks.saturating_sub(start_ticks),
-    );
+    let mut chunks: Vec<ChunkFitStat> = Vec::new();
 
     let want_addk = a.chunk_xform == ChunkXform::Addk;
+    let mut diversity = DiversityTracker::new(a.diversity_penalty, a.diversity_window);
+    let track_quality = quality_tracked(a);
 
     let mut prev_pos: Option<u64> = None;
     let mut chunk_idx: usize = 0;
     let mut off: usize = 0;
 
     while off < total_n {
-        if a.max_chunks != 0 && chunk_idx >= a.max_chunks {
+        if max_chunks != 0 && chunk_idx >= max_chunks {
             break;
         }
 
@@ -631,7 +1042,7 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
         let n = remaining_total.min(a.chunk_size);
 
         let min_pos: u64 = match prev_pos {
-            None => abs_stream_base_pos,
+            None => first_min_pos,
             Some(p) => p.saturating_add(1),
         };
 
@@ -641,8 +1052,8 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
         let need_min = min_start.saturating_add(n);
         if need_min > stream_syms.len()
             && !ensure_symbol_stream_len(
-                &mut engine,
-                &mut stream_syms,
+                engine,
+                stream_syms,
                 need_min,
                 a.bit_mapping,
                 seed,
@@ -651,7 +1062,8 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
                 a.max_ticks,
                 a.bit_tau,
                 a.bit_smooth_shift,
-                &mut lp_state,
+                lp_state,
+                track_quality.then_some(&mut *stream_quality),
             )
         {
             eprintln!(
@@ -675,204 +1087,132 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
             break;
         }
 
-        let mut best_start: usize = min_start;
-        let mut best_matches: u64 = 0;
-        let mut best_score: usize = usize::MAX;
-        let mut best_resid_metric: usize = usize::MAX;
-        let mut best_k: u8 = 0;
-        let mut scanned: u64 = 0;
+        let window_cost = |s0: usize| {
+            let base_pos = abs_stream_base_pos + (s0 as u64);
+            let cost = placement_cost(prev_pos, base_pos, trans_penalty, &diversity);
+            if track_quality {
+                let q = quality_penalty(&stream_quality[s0..s0 + n], a.bitfield_quality_weight);
+                cost.saturating_add(q)
+            } else {
+                cost
+            }
+        };
+
+        let target_win = &target_syms[off..off + n];
+        let scan = ScanRange {
+            min_start,
+            max_start,
+            step: a.scan_step,
+            parallel: a.parallel_scan,
+        };
 
         let fast01 = a.bits_per_emission == 1
             && a.residual == ResidualMode::Xor
             && a.objective == FitObjective::Zstd
             && a.bit_mapping != BitMapping::LowpassThresh;
 
-        if fast01 {
-            let target_slice = &target_syms[off..off + n];
-            let stream_slice = &stream_syms;
-
-            let target_words = pack_bits01_to_u64(target_slice);
-            let stream_words = pack_bits01_to_u64(stream_slice);
-
-            let mut s0: usize = min_start;
-            while s0 <= max_start {
-                scanned += 1;
-
-                let base_pos = abs_stream_base_pos + (s0 as u64);
-                let jump_cost_raw = tm_jump_cost(prev_pos, base_pos) as u64;
-                let jump_cost_u64 = jump_cost_raw.saturating_mul(a.trans_penalty);
-                let jump_cost = jump_cost_u64 as usize;
+        let pick = if fast01 {
+            let target_words = pack_bits01_to_u64(target_win);
+            let stream_words = pack_bits01_to_u64(stream_syms);
 
+            scan.best(|s0| {
+                let jump_cost = window_cost(s0);
                 let d0 = hamming01_aligned(&target_words, &stream_words, s0, n) as usize;
-
-                if want_addk {
-                    let d1 = n.saturating_sub(d0);
-                    let (d, k) = if d0 <= d1 { (d0, 0u8) } else { (d1, 1u8) };
-                    let score = d.saturating_add(jump_cost);
-                    if score < best_score || (score == best_score && s0 < best_start) {
-                        best_score = score;
-                        best_start = s0;
-                        best_k = k;
-                        best_matches = (n - d) as u64;
-                        best_resid_metric = d;
-                    }
+                let (d, k) = if want_addk && n - d0 < d0 {
+                    (n - d0, 1u8)
                 } else {
-                    let score = d0.saturating_add(jump_cost);
-                    if score < best_score || (score == best_score && s0 < best_start) {
-                        best_score = score;
-                        best_start = s0;
-                        best_k = 0;
-                        best_matches = (n - d0) as u64;
-                        best_resid_metric = d0;
-                    }
+                    (d0, 0u8)
+                };
+                WindowPick {
+                    score: d.saturating_add(jump_cost),
+                    start: s0,
+                    matches: (n - d) as u64,
+                    resid_metric: d,
+                    k,
                 }
-
-                s0 = s0.saturating_add(a.scan_step);
-            }
+            })
         } else {
-            let mut scratch_resid: Vec<u8> = vec![0u8; n];
-
-            let mut refine: Vec<(usize, usize, u64)> = Vec::new();
-
-            let mut s0: usize = min_start;
-            while s0 <= max_start {
-                scanned += 1;
-
-                let base_pos = abs_stream_base_pos + (s0 as u64);
-
-                let mut matches: u64 = 0;
-                let mut proxy_cost: usize = 0;
-
-                for i in 0..n {
-                    let pred0 = stream_syms[s0 + i] & mask;
-                    let resid_b =
-                        make_residual_symbol(a.residual, pred0, target_syms[off + i] & mask, mask);
-                    scratch_resid[i] = resid_b;
-                    if resid_b == 0 {
-                        matches += 1;
-                    }
-                    proxy_cost = proxy_cost
-                        .saturating_add(proxy_cost_for_residual(a.residual, resid_b));
-                }
-
-                let jump_cost_raw = tm_jump_cost(prev_pos, base_pos) as u64;
-                let jump_cost_u64 = jump_cost_raw.saturating_mul(a.trans_penalty);
-                let jump_cost = jump_cost_u64 as usize;
-
-                if a.objective == FitObjective::Zstd {
-                    refine.push((proxy_cost.saturating_add(jump_cost), s0, matches));
-                } else {
-                    let score = proxy_cost.saturating_add(jump_cost);
-                    if score < best_score || (score == best_score && s0 < best_start) {
-                        best_score = score;
-                        best_start = s0;
-                        best_matches = matches;
-                        best_resid_metric = proxy_cost;
-                    }
-                    if a.refine_topk != 0 {
-                        refine.push((score, s0, matches));
+            // Stream and target symbols are already <= mask, so a zero residual is
+            // exactly an equal symbol and the Xor proxy is the window's Hamming distance.
+            let proxy = |s0: usize| {
+                let stream_win = &stream_syms[s0..s0 + n];
+                let matches = bitpack::symbol_match_count(stream_win, target_win);
+                let proxy_cost: usize = match a.residual {
+                    ResidualMode::Xor => {
+                        bitpack::hamming_distance_u8(stream_win, target_win) as usize
                     }
+                    ResidualMode::Sub | ResidualMode::Ternary => n - matches as usize,
+                };
+                WindowPick {
+                    score: proxy_cost.saturating_add(window_cost(s0)),
+                    start: s0,
+                    matches,
+                    resid_metric: proxy_cost,
+                    k: 0,
                 }
-
-                s0 = s0.saturating_add(a.scan_step);
-            }
+            };
 
             if a.objective == FitObjective::Zstd {
-                refine.sort_by(|a1, b1| a1.0.cmp(&b1.0).then_with(|| a1.1.cmp(&b1.1)));
+                let mut refine = scan.collect(|s0| proxy(s0).key());
+                refine.sort_unstable();
 
                 let mut topk = a.refine_topk;
                 if topk == 0 {
                     topk = 256;
                 }
-                if refine.len() > topk {
-                    refine.truncate(topk);
-                }
-
-                for &(_proxy_score, cand_s, _cand_matches) in refine.iter() {
-                    let base_pos = abs_stream_base_pos + (cand_s as u64);
-
-                    let jump_cost_raw = tm_jump_cost(prev_pos, base_pos) as u64;
-                    let jump_cost_u64 = jump_cost_raw.saturating_mul(a.trans_penalty);
-                    let jump_cost = jump_cost_u64 as usize;
-
-                    if want_addk {
-                        let alpha = 1usize << (a.bits_per_emission as usize);
-
-                        let mut counts: Vec<u32> = vec![0u32; alpha];
-                        for i in 0..n {
-                            let pred0 = stream_syms[cand_s + i] & mask;
-                            let targ = target_syms[off + i] & mask;
-                            let ksym = targ.wrapping_sub(pred0) & mask;
-                            counts[ksym as usize] = counts[ksym as usize].saturating_add(1);
-                        }
-
-                        let mut ks: Vec<(u32, u8)> =
-                            (0..alpha).map(|k| (counts[k], k as u8)).collect();
-                        ks.sort_by(|a1, b1| b1.0.cmp(&a1.0).then_with(|| a1.1.cmp(&b1.1)));
-                        if ks.len() > 4 {
-                            ks.truncate(4);
-                        }
-
-                        for &(_cnt, kk) in ks.iter() {
-                            let mut matches: u64 = 0;
-                            for i in 0..n {
-                                let pred0 = stream_syms[cand_s + i] & mask;
-                                let pred = apply_chunk_addk(pred0, kk, mask);
-                                let resid = make_residual_symbol(
-                                    a.residual,
-                                    pred,
-                                    target_syms[off + i] & mask,
-                                    mask,
-                                );
-                                scratch_resid[i] = resid;
-                                if resid == 0 {
-                                    matches += 1;
-                                }
-                            }
-
-                            let zlen = zstd_compress_len(&scratch_resid, a.zstd_level);
-                            let score = zlen.saturating_add(jump_cost);
-
-                            if score < best_score || (score == best_score && cand_s < best_start) {
-                                best_score = score;
-                                best_start = cand_s;
-                                best_matches = matches;
-                                best_resid_metric = zlen;
-                                best_k = kk;
-                            }
-                        }
-                    } else {
-                        let mut matches: u64 = 0;
-                        for i in 0..n {
-                            let pred0 = stream_syms[cand_s + i] & mask;
-                            let resid = make_residual_symbol(
-                                a.residual,
-                                pred0,
-                                target_syms[off + i] & mask,
-                                mask,
-                            );
-                            scratch_resid[i] = resid;
-                            if resid == 0 {
-                                matches += 1;
-                            }
-                        }
-
-                        let zlen = zstd_compress_len(&scratch_resid, a.zstd_level);
-                        let score = zlen.saturating_add(jump_cost);
-
-                        if score < best_score || (score == best_score && cand_s < best_start) {
-                            best_score = score;
-                            best_start = cand_s;
-                            best_matches = matches;
-                            best_resid_metric = zlen;
-                            best_k = 0;
-                        }
-                    }
+                refine.truncate(topk);
+
+                let score_zstd = |scratch_resid: &mut Vec<u8>, cand_s: usize| {
+                    zstd_window_pick(
+                        a,
+                        mask,
+                        &stream_syms[cand_s..cand_s + n],
+                        target_win,
+                        cand_s,
+                        window_cost(cand_s),
+                        want_addk,
+                        scratch_resid,
+                    )
+                };
+                // scratch_resid is per rayon job (map_init) rather than shared
+                if a.parallel_scan {
+                    refine
+                        .par_iter()
+                        .map_init(|| vec![0u8; n], |scratch, &(_, s)| score_zstd(scratch, s))
+                        .min_by_key(WindowPick::key)
+                } else {
+                    let mut scratch_resid: Vec<u8> = vec![0u8; n];
+                    refine
+                        .iter()
+                        .map(|&(_, s)| score_zstd(&mut scratch_resid, s))
+                        .min_by_key(WindowPick::key)
                 }
+            } else {
+                scan.best(proxy)
             }
-        }
+        };
+        let scanned = scan.len();
+        let WindowPick {
+            score: best_score,
+            start: best_start,
+            matches: best_matches,
+            resid_metric: best_resid_metric,
+            k: best_k,
+        } = pick.unwrap_or(WindowPick {
+            score: usize::MAX,
+            start: min_start,
+            matches: 0,
+            resid_metric: usize::MAX,
+            k: 0,
+        });
 
         let base_pos = abs_stream_base_pos + (best_start as u64);
+        diversity.record(base_pos, n as u64);
+        chunks.push(ChunkFitStat {
+            len: n,
+            matches: best_matches,
+            jump_cost: tm_jump_cost(prev_pos, base_pos),
+        });
 
         for i in 0..n {
             let pos = base_pos + (i as u64);
@@ -885,12 +1225,12 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
                 pred0
             };
 
-            residual_syms.push(make_residual_symbol(
-                a.residual,
-                pred,
-                target_syms[off + i] & mask,
-                mask,
-            ));
+            let targ = target_syms[off + i] & mask;
+            let resid = make_residual_symbol(a.residual, pred, targ, mask);
+            if apply_residual_symbol(a.residual, pred, resid, mask) != targ {
+                clipped += 1;
+            }
+            residual_syms.push(resid);
         }
 
         if want_addk {
@@ -899,7 +1239,9 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
 
         prev_pos = Some(base_pos + (n as u64) - 1);
 
-        if want_addk {
+        if !log_chunks {
+            // calibration dry run: stay quiet
+        } else if want_addk {
             eprintln!(
                 "chunk {:04} off_sym={} len_sym={} start_emission={} scanned_windows={} matches={}/{} ({:.2}%) chunk_score={} chunk_resid_metric={} addk={}",
                 chunk_idx,
@@ -934,6 +1276,286 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
         chunk_idx += 1;
     }
 
+    ChunkFitOut {
+        tm_indices,
+        residual_syms,
+        chunk_addk,
+        chunks,
+        clipped,
+    }
+}
+
+/// Dry run over the first --calibration-chunks chunks with trans_penalty=1 on a
+/// cloned engine/stream. Returns mean bits saved per chunk by matching beyond chance
+/// (matches - len/alphabet) * bits_per_emission, divided by the mean jump varint cost.
+#[allow(clippy::too_many_arguments)]
+fn calibrate_trans_penalty(
+    a: &FitXorChunkedArgs,
+    seed: u64,
+    mask: u8,
+    target_syms: &[u8],
+    abs_stream_base_pos: u64,
+    first_min_pos: u64,
+    engine: &Engine,
+    stream_syms: &[u8],
+    stream_quality: &[u8],
+    lp_state: LowpassState,
+) -> u64 {
+    let mut engine = engine.clone();
+    let mut stream_syms = stream_syms.to_vec();
+    let mut stream_quality = stream_quality.to_vec();
+    let mut lp_state = lp_state;
+
+    let out = fit_chunks(
+        a,
+        seed,
+        mask,
+        target_syms,
+        abs_stream_base_pos,
+        first_min_pos,
+        1,
+        a.calibration_chunks.max(1),
+        false,
+        &mut engine,
+        &mut stream_syms,
+        &mut stream_quality,
+        &mut lp_state,
+    );
+    if out.chunks.is_empty() {
+        eprintln!("trans_penalty calibration: no chunks fitted; keeping 1");
+        return 1;
+    }
+
+    let alphabet = (1u64 << a.bits_per_emission) as f64;
+    let k = out.chunks.len() as f64;
+    let mut saved_bits = 0.0f64;
+    let mut jump_cost = 0.0f64;
+    let mut match_rate = 0.0f64;
+    for c in &out.chunks {
+        let chance = c.len as f64 / alphabet;
+        saved_bits += ((c.matches as f64) - chance).max(0.0) * (a.bits_per_emission as f64);
+        jump_cost += c.jump_cost as f64;
+        match_rate += (c.matches as f64) / (c.len.max(1) as f64);
+    }
+    let mean_saved_bits = saved_bits / k;
+    let mean_jump_cost = jump_cost / k;
+    let mean_match_rate = match_rate / k;
+
+    let tp = if mean_jump_cost > 0.0 {
+        (mean_saved_bits / mean_jump_cost).round() as u64
+    } else {
+        1
+    };
+
+    eprintln!(
+        "trans_penalty calibration: chunks={} mean_match_rate={:.4} mean_bits_saved={:.2} mean_jump_varint_bytes={:.3} => trans_penalty={}",
+        out.chunks.len(),
+        mean_match_rate,
+        mean_saved_bits,
+        mean_jump_cost,
+        tp
+    );
+    tp
+}
+
+pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()> {
+    if a.mode != ApplyMode::Rgbpair {
+        anyhow::bail!("--map bitfield requires --mode rgbpair");
+    }
+    if a.stream_cache_size.is_some() {
+        anyhow::bail!("--stream-cache-size is not supported with --map bitfield");
+    }
+    if a.use_ring_buffer {
+        anyhow::bail!("--use-ring-buffer is not supported with --map bitfield");
+    }
+    if a.bits_per_emission == 0 || a.bits_per_emission > 8 {
+        anyhow::bail!("--bits-per-emission must be in 1..=8");
+    }
+    if a.bit_mapping == BitMapping::LowpassThresh && a.bits_per_emission != 1 {
+        anyhow::bail!("bit-mapping lowpass-thresh requires --bits-per-emission 1");
+    }
+    if a.chunk_size == 0 {
+        anyhow::bail!("--chunk-size must be >= 1");
+    }
+    if a.scan_step == 0 {
+        anyhow::bail!("--scan-step must be >= 1");
+    }
+
+    let recipe = recipe_file::load_k8r(&a.recipe)?;
+    let recipe_raw_len = std::fs::read(&a.recipe).map(|b| b.len()).unwrap_or(0usize);
+
+    let target_bytes =
+        std::fs::read(&a.target).with_context(|| format!("read target: {}", a.target))?;
+    if target_bytes.is_empty() {
+        anyhow::bail!("target is empty");
+    }
+
+    let seed = parse_seed_hex_opt(a.map_seed, &a.map_seed_hex)?;
+
+    let bit_len: usize = target_bytes.len() * 8;
+    let bits: usize = a.bits_per_emission as usize;
+    let sym_count: usize = (bit_len + bits - 1) / bits;
+
+    let total_bits: usize = sym_count.saturating_mul(bits);
+    let need_bytes: usize = (total_bits + 7) / 8;
+    let mut padded_target: Vec<u8> = vec![0u8; need_bytes];
+    padded_target[..target_bytes.len()].copy_from_slice(&target_bytes);
+
+    let target_syms = bitpack::unpack_symbols(a.bits_per_emission, &padded_target, sym_count)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    let mask = sym_mask(a.bits_per_emission);
+
+    let mut engine = Engine::new(recipe)?;
+
+    while (engine.stats.emissions as u64) < a.start_emission && engine.stats.ticks < a.max_ticks {
+        let _ = engine.step();
+        if (engine.stats.emissions as u64) >= a.search_emissions {
+            break;
+        }
+    }
+
+    let start_ticks = engine.stats.ticks;
+    let start_em = engine.stats.emissions as u64;
+
+    let mut stream_syms: Vec<u8> = Vec::new();
+    stream_syms.reserve((a.search_emissions.saturating_sub(start_em)).min(500_000) as usize);
+
+    let mut lp_state = LowpassState::new();
+    let track_quality = quality_tracked(&a);
+    let mut stream_quality: Vec<u8> = Vec::new();
+
+    while (engine.stats.emissions as u64) < a.search_emissions && engine.stats.ticks < a.max_ticks {
+        if let Some(tok) = engine.step() {
+            let em = (engine.stats.emissions - 1) as u64;
+            let rgb = tok.to_rgb_pair();
+            let rgb6 = rgb.to_bytes();
+            let sym = map_symbol_bitfield(
+                a.bit_mapping,
+                seed,
+                em,
+                &rgb6,
+                a.bits_per_emission,
+                a.bit_tau,
+                a.bit_smooth_shift,
+                &mut lp_state,
+            );
+            stream_syms.push(sym & mask);
+            if track_quality {
+                stream_quality.push(rgb.contrast());
+            }
+        }
+    }
+
+    let abs_stream_base_pos: u64 = a.start_emission;
+    let total_n = target_syms.len();
+    let first_min_pos =
+        first_chunk_min_pos(a.anchor_first_chunk_at, abs_stream_base_pos, a.search_emissions)?;
+
+    eprintln!(
+        "--- fit-xor-chunked (bitfield) --- map=bitfield bits_per_emission={} bit_mapping={:?} map_seed={} (0x{:016x}) bit_tau={} bit_smooth_shift={} residual={:?} objective={:?} refine_topk={} lookahead={} trans_penalty={} chunk_size={} scan_step={} zstd_level={} chunk_xform={:?} target_bytes={} target_symbols={} stream_symbols={} base_pos={} start_emission={} end_emissions={} ticks={} delta_ticks={}",
+        a.bits_per_emission,
+        a.bit_mapping,
+        seed,
+        seed,
+        a.bit_tau,
+        a.bit_smooth_shift,
+        a.residual,
+        a.objective,
+        a.refine_topk,
+        a.lookahead,
+        a.trans_penalty.map_or("auto".to_string(), |tp| tp.to_string()),
+        a.chunk_size,
+        a.scan_step,
+        a.zstd_level,
+        a.chunk_xform,
+        target_bytes.len(),
+        total_n,
+        stream_syms.len(),
+        abs_stream_base_pos,
+        a.start_emission,
+        (start_em + (stream_syms.len() as u64)),
+        engine.stats.ticks,
+        engine.stats.ticks.saturating_sub(start_ticks),
+    );
+
+    let want_addk = a.chunk_xform == ChunkXform::Addk;
+
+    let trans_penalty = if a.trans_penalty_calibrate {
+        let calibrated = calibrate_trans_penalty(
+            &a,
+            seed,
+            mask,
+            &target_syms,
+            abs_stream_base_pos,
+            first_min_pos,
+            &engine,
+            &stream_syms,
+            &stream_quality,
+            lp_state,
+        );
+        match a.trans_penalty {
+            Some(tp) => {
+                eprintln!(
+                    "trans_penalty_calibrated = {} (ignored: --trans-penalty {} given)",
+                    calibrated, tp
+                );
+                tp
+            }
+            None => {
+                eprintln!("trans_penalty_calibrated = {} (using it)", calibrated);
+                calibrated
+            }
+        }
+    } else {
+        a.trans_penalty.unwrap_or(1)
+    };
+
+    if a.diversity_penalty > 0.0 {
+        eprintln!(
+            "diversity_penalty={} diversity_window={}",
+            a.diversity_penalty, a.diversity_window
+        );
+    }
+    if track_quality {
+        let sum: u64 = stream_quality.iter().map(|&c| c as u64).sum();
+        let mean = sum as f64 / stream_quality.len().max(1) as f64;
+        eprintln!(
+            "bitfield_quality_weight={} mean_contrast={:.1}",
+            a.bitfield_quality_weight, mean
+        );
+    } else if a.bitfield_quality_weight > 0.0 {
+        eprintln!(
+            "WARN: --bitfield-quality-weight only applies to --bit-mapping geom; ignored for {:?}",
+            a.bit_mapping
+        );
+    }
+    if a.anchor_first_chunk_at.is_some() {
+        eprintln!("anchor_first_chunk_at={}", first_min_pos);
+    }
+
+    let ChunkFitOut {
+        tm_indices,
+        residual_syms,
+        chunk_addk,
+        chunks: _,
+        clipped,
+    } = fit_chunks(
+        &a,
+        seed,
+        mask,
+        &target_syms,
+        abs_stream_base_pos,
+        first_min_pos,
+        trans_penalty,
+        a.max_chunks,
+        true,
+        &mut engine,
+        &mut stream_syms,
+        &mut stream_quality,
+        &mut lp_state,
+    );
+
     if tm_indices.len() != residual_syms.len() {
         anyhow::bail!(
             "internal: tm_indices/residual len mismatch: tm={} resid={}",
@@ -946,6 +1568,12 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
     }
 
     let produced_syms = residual_syms.len();
+    if clipped > 0 {
+        eprintln!(
+            "WARN: ternary residual clipped {}/{} symbols (prediction off by more than one step); reconstruct will not match the target",
+            clipped, produced_syms
+        );
+    }
     if produced_syms != target_syms.len() {
         eprintln!(
             "note: partial output produced_symbols={} target_symbols={}",
@@ -962,14 +1590,19 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
     let tm_is_tm0 = tm_bytes.len() >= 4 && &tm_bytes[0..4] == b"TM0\0";
 
     let want_lanes = a.time_split || a.bitfield_residual == BitfieldResidualEncoding::Lanes;
+    let resid_bits = residual_symbol_bits(a.residual, a.bits_per_emission);
 
-    let (resid_raw, resid_zstd) = if !want_lanes {
-        let file_bytes = write_bitfield_residual_bf1(
+    let (resid_raw, resid_zstd) = if !want_lanes
+        && a.bitfield_residual == BitfieldResidualEncoding::Chunked
+    {
+        let file_bytes = write_bitfield_residual_bf4(
             &a.out_residual,
-            a.bits_per_emission,
+            resid_bits,
             a.bit_mapping,
             target_bytes.len(),
             &residual_syms,
+            a.bf4_chunk_symbols,
+            a.zstd_level,
             if want_addk { Some(a.chunk_size) } else { None },
             if want_addk {
                 Some(chunk_addk.as_slice())
@@ -980,10 +1613,27 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
         let resid_raw = file_bytes.len();
         let resid_zstd = zstd_compress_len(&file_bytes, a.zstd_level);
         (resid_raw, resid_zstd)
+    } else if !want_lanes {
+        let resid_raw = write_bitfield_residual_bf1(
+            &a.out_residual,
+            resid_bits,
+            a.bit_mapping,
+            target_bytes.len(),
+            &residual_syms,
+            if want_addk { Some(a.chunk_size) } else { None },
+            if want_addk {
+                Some(chunk_addk.as_slice())
+            } else {
+                None
+            },
+        )?;
+        let resid_zstd = zstd_compress_file_len(&a.out_residual, a.zstd_level)
+            .with_context(|| format!("read back residual for sizing: {}", a.out_residual))?;
+        (resid_raw, resid_zstd)
     } else {
         write_bitfield_residual_bf2(
             &a.out_residual,
-            a.bits_per_emission,
+            resid_bits,
             a.bit_mapping,
             target_bytes.len(),
             &residual_syms,
@@ -1028,7 +1678,47 @@ pub fn cmd_fit_xor_chunked_bitfield(a: FitXorChunkedArgs) -> anyhow::Result<()>
     Ok(())
 }
 
+fn residual_is_bf4(path: &str) -> anyhow::Result<bool> {
+    let mut f = std::fs::File::open(path).with_context(|| format!("open bf: {}", path))?;
+    let mut magic = [0u8; 4];
+    Ok(f.read_exact(&mut magic).is_ok() && &magic == BF4_MAGIC)
+}
+
+/// --start-symbol/--end-symbol as a checked range over `symbol_count` residual symbols.
+/// An empty residual without explicit bounds is the empty range.
+fn symbol_range(
+    start: Option<usize>,
+    end: Option<usize>,
+    symbol_count: usize,
+    bits_per_emission: u8,
+) -> anyhow::Result<std::ops::Range<usize>> {
+    let s = start.unwrap_or(0);
+    let e = end.unwrap_or(symbol_count);
+    if symbol_count == 0 && start.is_none() && end.is_none() {
+        return Ok(0..0);
+    }
+    if s >= e || e > symbol_count {
+        anyhow::bail!(
+            "symbol range {}..{} is empty or outside 0..{}",
+            s,
+            e,
+            symbol_count
+        );
+    }
+    if !(s * bits_per_emission as usize).is_multiple_of(8) {
+        anyhow::bail!(
+            "--start-symbol {} is not byte-aligned at {} bits per symbol",
+            s,
+            bits_per_emission
+        );
+    }
+    Ok(s..e)
+}
+
 pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
+    if a.write_gaps.is_some() || a.gap_fill_mode.is_some() {
+        anyhow::bail!("--write-gaps/--gap-fill-mode are not supported with --map bitfield");
+    }
     if a.mode != ApplyMode::Rgbpair {
         anyhow::bail!("--map bitfield requires --mode rgbpair");
     }
@@ -1040,15 +1730,14 @@ pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
     }
 
     let recipe = recipe_file::load_k8r(&a.recipe)?;
+    // An empty timemap is only valid with an empty residual (checked against symbol_count below).
     let tm = timemap::read_timemap(&a.timemap)?;
-    if tm.indices.is_empty() {
-        anyhow::bail!("timemap empty");
-    }
 
     let seed = parse_seed_hex_opt(a.map_seed, &a.map_seed_hex)?;
 
-    let bf = read_bitfield_residual(&a.residual)?;
+    let bf4 = residual_is_bf4(&a.residual)?;
 
+    // BF4: resid_syms holds only the requested range; other formats load everything.
     let (bf_bits, bf_mapping, bf_orig_len_bytes, bf_symbol_count, bf_chunk_size, bf_chunk_addk, resid_syms): (
         u8,
         BitMapping,
@@ -1057,78 +1746,132 @@ pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
         Option<usize>,
         Option<Vec<u8>>,
         Vec<u8>,
-    ) = match bf {
-        BitfieldResidual::Bf1 {
-            bits_per_emission,
-            mapping,
-            orig_len_bytes,
-            symbol_count,
-            chunk_size,
-            chunk_addk,
-            packed_symbols,
-        } => {
-            let syms = bitpack::unpack_symbols(bits_per_emission, &packed_symbols, symbol_count)
-                .map_err(|e| anyhow::anyhow!("{e}"))?;
-            (
+    ) = if bf4 {
+        let mut f = std::fs::File::open(&a.residual)
+            .with_context(|| format!("open bf: {}", a.residual))?;
+        let idx = Bf4Index::read(&mut f)?;
+        let range =
+            symbol_range(a.start_symbol, a.end_symbol, idx.symbol_count, a.bits_per_emission)?;
+        let syms = idx.read_symbols(&mut f, range.start, range.end)?;
+        let decoded_chunks = if range.is_empty() {
+            0
+        } else {
+            (range.end - 1) / idx.chunk_symbols - range.start / idx.chunk_symbols + 1
+        };
+        eprintln!(
+            "BF4: chunks={} chunk_symbols={} decoded_chunks={} symbols={}..{}",
+            idx.symbol_count.div_ceil(idx.chunk_symbols),
+            idx.chunk_symbols,
+            decoded_chunks,
+            range.start,
+            range.end
+        );
+        let (cs, ks) = match idx.chunk_addk {
+            Some((cs, ks)) => (Some(cs), Some(ks)),
+            None => (None, None),
+        };
+        (
+            idx.bits_per_emission,
+            idx.mapping,
+            idx.orig_len_bytes,
+            idx.symbol_count,
+            cs,
+            ks,
+            syms,
+        )
+    } else {
+        match read_bitfield_residual(&a.residual)? {
+            BitfieldResidual::Bf1 {
                 bits_per_emission,
                 mapping,
                 orig_len_bytes,
                 symbol_count,
                 chunk_size,
                 chunk_addk,
-                syms,
-            )
-        }
-        BitfieldResidual::Bf2 {
-            bits_per_emission,
-            mapping,
-            orig_len_bytes,
-            symbol_count,
-            lane_count,
-            lanes_raw_bitsets,
-        } => {
-            let mut out = vec![0u8; symbol_count];
-            let mut seen = vec![false; symbol_count];
-
-            for lane in 0..lane_count {
-                let bs = &lanes_raw_bitsets[lane];
-                for i in 0..symbol_count {
-                    let byte = bs[i >> 3];
-                    let bit = (byte >> (i & 7)) & 1;
-                    if bit == 1 {
-                        if seen[i] {
-                            anyhow::bail!(
-                                "BF2 invalid: symbol position {} set in multiple lanes",
-                                i
-                            );
+                symbols,
+            } => (
+                bits_per_emission,
+                mapping,
+                orig_len_bytes,
+                symbol_count,
+                chunk_size,
+                chunk_addk,
+                symbols,
+            ),
+            BitfieldResidual::Bf2 {
+                bits_per_emission,
+                mapping,
+                orig_len_bytes,
+                symbol_count,
+                lane_count,
+                lanes_raw_bitsets,
+            } => {
+                let mut out = vec![0u8; symbol_count];
+                let mut seen = vec![false; symbol_count];
+
+                for lane in 0..lane_count {
+                    let bs = &lanes_raw_bitsets[lane];
+                    for i in 0..symbol_count {
+                        let byte = bs[i >> 3];
+                        let bit = (byte >> (i & 7)) & 1;
+                        if bit == 1 {
+                            if seen[i] {
+                                anyhow::bail!(
+                                    "BF2 invalid: symbol position {} set in multiple lanes",
+                                    i
+                                );
+                            }
+                            out[i] = lane as u8;
+                            seen[i] = true;
                         }
-                        out[i] = lane as u8;
-                        seen[i] = true;
                     }
                 }
-            }
 
-            if seen.iter().any(|&v| !v) {
-                anyhow::bail!("BF2 invalid: some symbol positions not assigned to any lane");
-            }
+                if seen.iter().any(|&v| !v) {
+                    anyhow::bail!("BF2 invalid: some symbol positions not assigned to any lane");
+                }
 
-            (
+                (
+                    bits_per_emission,
+                    mapping,
+                    orig_len_bytes,
+                    symbol_count,
+                    None,
+                    None,
+                    out,
+                )
+            }
+            BitfieldResidual::Bf4 {
                 bits_per_emission,
                 mapping,
                 orig_len_bytes,
-                symbol_count,
-                None,
-                None,
-                out,
-            )
+                chunk_size,
+                chunk_addk,
+                symbols,
+            } => (
+                bits_per_emission,
+                mapping,
+                orig_len_bytes,
+                symbols.len(),
+                chunk_size,
+                chunk_addk,
+                symbols,
+            ),
         }
     };
 
-    if bf_bits != a.bits_per_emission {
+    let sym_range =
+        symbol_range(a.start_symbol, a.end_symbol, bf_symbol_count, a.bits_per_emission)?;
+    let resid_window: &[u8] = if bf4 { &resid_syms } else { &resid_syms[sym_range.clone()] };
+
+    let resid_bits = residual_symbol_bits(a.residual_mode, a.bits_per_emission);
+    if bf_bits != resid_bits {
         anyhow::bail!(
-            "bitfield residual bits_per_emission mismatch: file={} cli={}",
+            "bitfield residual bits per symbol mismatch: file={} cli={} (bits_per_emission={} residual_mode={:?})",
             bf_bits,
-            a.bits_per_emission
+            resid_bits,
+            a.bits_per_emission,
+            a.residual_mode
         );
     }
     if bf_mapping != a.bit_mapping {
@@ -1162,16 +1905,17 @@ pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
     let mut engine = Engine::new(recipe)?;
 
     let mut max_idx: u64 = 0;
-    for &idx in tm.indices.iter() {
+    for &idx in tm.indices[sym_range.clone()].iter() {
         if idx > max_idx {
             max_idx = idx;
         }
     }
 
-    let mut out_syms: Vec<u8> = Vec::with_capacity(bf_symbol_count);
-    let mut i: usize = 0;
+    let mut out_syms: Vec<u8> = Vec::with_capacity(sym_range.len());
+    let mut i: usize = sym_range.start;
 
     let mask = sym_mask(a.bits_per_emission);
+    let resid_mask = sym_mask(resid_bits);
 
     let mut lp_state = LowpassState::new();
 
@@ -1195,7 +1939,7 @@ pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
                 &mut lp_state,
             ) & mask;
 
-            while i < tm.indices.len() && tm.indices[i] == em {
+            while i < sym_range.end && tm.indices[i] == em {
                 let pred = if let (Some(cs), Some(ref ks)) = (bf_chunk_size, bf_chunk_addk.as_ref()) {
                     let ci = i / cs;
                     apply_chunk_addk(pred0_all, ks[ci], mask)
@@ -1203,18 +1947,19 @@ pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
                     pred0_all
                 };
 
-                let sym = apply_residual_symbol(a.residual_mode, pred, resid_syms[i] & mask, mask);
+                let resid = resid_window[i - sym_range.start] & resid_mask;
+                let sym = apply_residual_symbol(a.residual_mode, pred, resid, mask);
                 out_syms.push(sym);
                 i += 1;
             }
         }
     }
 
-    if i != tm.indices.len() {
+    if i != sym_range.end {
         anyhow::bail!(
             "reconstruct short (bitfield): wrote {} of {} symbols (max_idx={} ticks={} emissions={})",
-            i,
-            tm.indices.len(),
+            i - sym_range.start,
+            sym_range.len(),
             max_idx,
             engine.stats.ticks,
             engine.stats.emissions
@@ -1223,7 +1968,9 @@ pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
 
     let mut out_bytes = bitpack::pack_symbols(a.bits_per_emission, &out_syms)
         .map_err(|e| anyhow::anyhow!("{e}"))?;
-    out_bytes.truncate(bf_orig_len_bytes);
+    // symbol_range keeps the start byte-aligned
+    let byte_off = sym_range.start * (a.bits_per_emission as usize) / 8;
+    out_bytes.truncate(bf_orig_len_bytes.saturating_sub(byte_off));
 
     std::fs::write(&a.out, &out_bytes)
         .with_context(|| format!("write reconstruct out: {}", a.out))?;
@@ -1244,3 +1991,83 @@ pub fn cmd_reconstruct_bitfield(a: ReconstructArgs) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgb_moment_bits_follow_their_rules() {
+        // [ra, ga, ba, rc, gc, bc] => expected symbol at 8 bits, bit by bit:
+        // 0 ra>rc, 1 ga>gc, 2 ba>bc, 3 ra+ga>rc+gc, 4 max>max, 5 min>min,
+        // 6 ra-ga>rc-gc, 7 ba-ga>bc-gc.
+        let cases: [([u8; 6], u8); 7] = [
+            ([10, 10, 10, 10, 10, 10], 0),
+            ([10, 10, 10, 20, 20, 20], 0),
+            // 0: 20>10, 3: 30>20, 4: 20>10, 6: 10>0
+            ([20, 10, 10, 10, 10, 10], 0b0101_1001),
+            // 1: 20>10, 3: 30>20, 4: 20>10
+            ([10, 20, 10, 10, 10, 10], 0b0001_1010),
+            // 2: 20>10, 4: 20>10, 7: 10>0 (3: 20>20 is false)
+            ([10, 10, 20, 10, 10, 10], 0b1001_0100),
+            // 0..=5 all strictly greater; 6 and 7 compare 0>0
+            ([20, 20, 20, 10, 10, 10], 0b0011_1111),
+            // 0: 255>0, 2: 255>0, 6: 255>-255, 7: 255>-255 (3, 4: 255>255 are false)
+            ([255, 0, 255, 0, 255, 0], 0b1100_0101),
+        ];
+        for (rgb6, want) in cases {
+            assert_eq!(rgb_moment_symbol_from_rgb6(&rgb6, 8), want, "{rgb6:?}");
+        }
+
+        // Narrower symbols keep the low bits.
+        let rgb6 = [20, 20, 20, 10, 10, 10];
+        assert_eq!(rgb_moment_symbol_from_rgb6(&rgb6, 4), 0b1111);
+        assert_eq!(rgb_moment_symbol_from_rgb6(&rgb6, 1), 0b1);
+    }
+
+    fn write_bf4(path: &str, syms: &[u8], chunk_symbols: usize) -> Bf4Index {
+        write_bitfield_residual(
+            path,
+            2,
+            BitMapping::Geom,
+            syms.len() / 4,
+            syms,
+            3,
+            BitfieldResidualEncoding::Chunked,
+            chunk_symbols,
+            None,
+            None,
+        )
+        .unwrap();
+        Bf4Index::read(&mut std::fs::File::open(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn bf4_uses_the_configured_chunk_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("r.bf4").to_string_lossy().into_owned();
+        let syms: Vec<u8> = (0..40u8).map(|i| i % 4).collect();
+
+        let idx = write_bf4(&path, &syms, 7);
+        assert_eq!(idx.chunk_symbols, 7);
+        assert_eq!(idx.offsets.len(), 6);
+        let mut f = std::fs::File::open(&path).unwrap();
+        assert_eq!(idx.read_symbols(&mut f, 5, 23).unwrap(), &syms[5..23]);
+    }
+
+    #[test]
+    fn empty_bf4_residual_reads_back_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("empty.bf4").to_string_lossy().into_owned();
+
+        let idx = write_bf4(&path, &[], 16);
+        assert_eq!(idx.symbol_count, 0);
+        let range = symbol_range(None, None, idx.symbol_count, 2).unwrap();
+        assert!(range.is_empty());
+        let mut f = std::fs::File::open(&path).unwrap();
+        assert!(idx.read_symbols(&mut f, range.start, range.end).unwrap().is_empty());
+
+        // Explicit bounds still have to lie inside the residual.
+        assert!(symbol_range(Some(0), Some(1), 0, 2).is_err());
+    }
+}
@@ -2,14 +2,20 @@
 
 use super::args::*;
 use super::mapping::map_byte;
-use super::residual::{apply_residual_byte, make_residual_byte};
-use super::tags::{apply_conditioning_if_enabled, read_cond_tags, CondTags};
+use super::residual::{apply_mapped_residual_byte, make_mapped_residual_byte};
+use super::stream_cache::StreamCache;
+use super::tags::{
+    apply_conditioning_if_enabled, read_cond_tags, write_cond_tags_toml, CondTags,
+};
 use super::util::{
-    parse_seed, parse_seed_hex_opt, tm_jump_cost, zstd_compress_len,
+    first_chunk_min_pos, parse_seed, parse_seed_hex_opt, splitmix64, tm_jump_cost,
+    zstd_compress_len, DiversityTracker,
 };
 
-use k8dnz_core::signal::timing_map::TimingMap;
-use k8dnz_core::Engine;
+use k8dnz_core::error::K8Error;
+use k8dnz_core::signal::fit::{self, FitOptions};
+use k8dnz_core::signal::timing_map::{TimemapFormat, TimingMap};
+use k8dnz_core::{Engine, Recipe};
 
 use crate::io::{recipe_file, timemap};
 
@@ -27,6 +33,68 @@ pub fn cmd_make(a: MakeArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn cmd_import_text(a: ImportTextArgs) -> anyhow::Result<()> {
+    let mut tm = TimingMap::from_positions_file(&a.r#in, a.require_sorted)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    // Binary timemaps are strictly increasing; without --require-sorted we fix the order up.
+    let read = tm.indices.len();
+    if !a.require_sorted {
+        tm.indices.sort_unstable();
+        tm.indices.dedup();
+    }
+
+    timemap::write_timemap_auto(&a.out, &tm)?;
+    eprintln!(
+        "timemap import ok: in={} out={} read={} len={} dropped_dups={} first={:?} last={:?}",
+        a.r#in,
+        a.out,
+        read,
+        tm.indices.len(),
+        read - tm.indices.len(),
+        tm.indices.first(),
+        tm.last_index()
+    );
+    Ok(())
+}
+
+pub fn cmd_export_text(a: ExportTextArgs) -> anyhow::Result<()> {
+    let tm = timemap::read_timemap(&a.r#in)?;
+    tm.to_positions_file(&a.out).map_err(|e| anyhow::anyhow!("{e}"))?;
+    eprintln!(
+        "timemap export ok: in={} out={} len={}",
+        a.r#in,
+        a.out,
+        tm.indices.len()
+    );
+    Ok(())
+}
+
+pub fn cmd_split(a: SplitArgs) -> anyhow::Result<()> {
+    let tm = timemap::read_timemap(&a.r#in)?;
+    if a.at > tm.indices.len() {
+        anyhow::bail!(
+            "--at {} is past the end of the timemap (len={})",
+            a.at,
+            tm.indices.len()
+        );
+    }
+    let (first, second) = tm.split_at(a.at);
+    timemap::write_timemap_auto(&a.out_a, &first)?;
+    timemap::write_timemap_auto(&a.out_b, &second)?;
+    eprintln!(
+        "timemap split ok: in={} at={} out_a={} len_a={} out_b={} len_b={} first_b={:?}",
+        a.r#in,
+        a.at,
+        a.out_a,
+        first.indices.len(),
+        a.out_b,
+        second.indices.len(),
+        second.indices.first()
+    );
+    Ok(())
+}
+
 pub fn cmd_inspect(a: InspectArgs) -> anyhow::Result<()> {
     let tm = timemap::read_timemap(&a.r#in)?;
     eprintln!(
@@ -36,6 +104,50 @@ pub fn cmd_inspect(a: InspectArgs) -> anyhow::Result<()> {
         tm.indices.first(),
         tm.indices.last()
     );
+    match tm.validate() {
+        Ok(()) => eprintln!("timemap validate: ok"),
+        Err(e) => eprintln!("timemap validate: FAIL {e}"),
+    }
+    if a.suggest_format {
+        let bytes = std::fs::read(&a.r#in)?;
+        let current = TimemapFormat::detect(&bytes).map_or("?", TimemapFormat::name);
+        eprintln!("timemap format: current={} bytes={}", current, bytes.len());
+        for f in TimemapFormat::ALL {
+            match tm.encoded_len(f) {
+                Some(n) => eprintln!("timemap format: {} bytes={}", f.name(), n),
+                None => eprintln!(
+                    "timemap format: {} n/a (not an arithmetic progression)",
+                    f.name()
+                ),
+            }
+        }
+        let (best, n) = tm.smallest_format();
+        eprintln!(
+            "timemap suggest: format={} bytes={} saving={}",
+            best.name(),
+            n,
+            bytes.len() as i64 - n as i64
+        );
+    }
+    Ok(())
+}
+
+pub fn cmd_repack(a: RepackArgs) -> anyhow::Result<()> {
+    let bytes = std::fs::read(&a.r#in)?;
+    let tm = timemap::read_timemap(&a.r#in)?;
+    let before = TimemapFormat::detect(&bytes).map_or("?", TimemapFormat::name);
+    let (fmt, after) = tm.smallest_format();
+    timemap::write_timemap_as(&a.out, &tm, fmt)?;
+    eprintln!(
+        "timemap repack ok: in={} out={} len={} before={} ({}) after={} ({})",
+        a.r#in,
+        a.out,
+        tm.indices.len(),
+        bytes.len(),
+        before,
+        after,
+        fmt.name()
+    );
     Ok(())
 }
 
@@ -122,12 +234,31 @@ pub fn cmd_apply(a: ApplyArgs) -> anyhow::Result<()> {
         }
     }
 
+    // Output byte i was taken from stream position tm.indices[i] in both modes
+    // (pair: emission index, rgbpair: emission_index*6 + lane).
+    if let Some(path) = &a.output_positions {
+        write_positions(path, &tm, a.output_positions_fmt)?;
+        eprintln!(
+            "apply positions ok: out={} count={} fmt={:?}",
+            path,
+            tm.indices.len(),
+            a.output_positions_fmt
+        );
+    }
+
     Ok(())
 }
 
 pub fn cmd_fit(a: FitArgs) -> anyhow::Result<()> {
+    if !a.multi_target.is_empty() {
+        return cmd_fit_multi(a);
+    }
+
+    let target_path = a.target.as_deref().ok_or_else(|| anyhow::anyhow!("--target is required"))?;
+    let out_path = a.out.as_deref().ok_or_else(|| anyhow::anyhow!("--out is required"))?;
+
     let recipe = recipe_file::load_k8r(&a.recipe)?;
-    let target = std::fs::read(&a.target)?;
+    let target = std::fs::read(target_path)?;
     if target.is_empty() {
         anyhow::bail!("target is empty");
     }
@@ -135,7 +266,6 @@ pub fn cmd_fit(a: FitArgs) -> anyhow::Result<()> {
     let mut engine = Engine::new(recipe)?;
     let mut indices: Vec<u64> = Vec::with_capacity(target.len());
 
-    let mut want: usize = 0;
     let want_len = target.len();
     let first_byte = target[0];
 
@@ -147,28 +277,27 @@ pub fn cmd_fit(a: FitArgs) -> anyhow::Result<()> {
     }
 
     let start_ticks = engine.stats.ticks;
-    let mut first_byte_seen: u64 = 0;
-
-    while (engine.stats.emissions as u64) < a.search_emissions && engine.stats.ticks < a.max_ticks {
-        if let Some(tok) = engine.step() {
-            let idx = (engine.stats.emissions - 1) as u64;
-            let b = tok.pack_byte();
+    let start_engine = engine.clone();
 
-            if b == first_byte {
-                first_byte_seen += 1;
-            }
-
-            if b == target[want] {
-                indices.push(idx);
-                want += 1;
-                if want == want_len {
-                    break;
-                }
-            }
+    // Greedy in-order match within --search-emissions.
+    for &b in &target {
+        match engine.run_until_byte_within(b, a.search_emissions, a.max_ticks) {
+            Some((idx, _)) => indices.push(idx),
+            None => break,
         }
     }
+    let want = indices.len();
 
     if want != want_len {
+        // Replay the searched span to count how often the first target byte came up.
+        let mut replay = start_engine;
+        let mut first_byte_seen: u64 = 0;
+        while replay
+            .run_until_byte_within(first_byte, engine.stats.emissions, a.max_ticks)
+            .is_some()
+        {
+            first_byte_seen += 1;
+        }
         anyhow::bail!(
             "timemap fit failed: matched {}/{} bytes; first_target=0x{:02x} first_seen={} start_emission={} searched_emissions={} ticks={} (start_ticks={} delta_ticks={})",
             want,
@@ -184,11 +313,11 @@ pub fn cmd_fit(a: FitArgs) -> anyhow::Result<()> {
     }
 
     let tm = TimingMap { indices };
-    timemap::write_timemap_auto(&a.out, &tm)?;
+    timemap::write_timemap_auto(out_path, &tm)?;
 
     eprintln!(
         "timemap fit ok: out={} target_bytes={} first_idx={:?} last_idx={:?} start_emission={} start_ticks={} end_emissions={} end_ticks={} delta_ticks={}",
-        a.out,
+        out_path,
         want_len,
         tm.indices.first(),
         tm.indices.last(),
@@ -202,7 +331,141 @@ pub fn cmd_fit(a: FitArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+struct MultiFitState {
+    target: Vec<u8>,
+    indices: Vec<u64>,
+}
+
+impl MultiFitState {
+    fn done(&self) -> bool {
+        self.indices.len() == self.target.len()
+    }
+}
+
+/// One engine pass, N timemaps. Ordered by default: target k only starts after
+/// target k-1 is complete. With --concurrent, every target is matched greedily
+/// from --start-emission.
+fn cmd_fit_multi(a: FitArgs) -> anyhow::Result<()> {
+    let pattern = a
+        .out_multi_timemap
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("--multi-target requires --out-multi-timemap"))?;
+    if !pattern.contains("{}") {
+        anyhow::bail!("--out-multi-timemap must contain {{}} (target index placeholder)");
+    }
+
+    let mut fits: Vec<MultiFitState> = Vec::with_capacity(a.multi_target.len());
+    for path in &a.multi_target {
+        let target = std::fs::read(path)?;
+        if target.is_empty() {
+            anyhow::bail!("target is empty: {}", path);
+        }
+        fits.push(MultiFitState {
+            indices: Vec::with_capacity(target.len()),
+            target,
+        });
+    }
+
+    let recipe = recipe_file::load_k8r(&a.recipe)?;
+    let mut engine = Engine::new(recipe)?;
+
+    while engine.stats.emissions < a.start_emission && engine.stats.ticks < a.max_ticks {
+        let _ = engine.step();
+        if engine.stats.emissions >= a.search_emissions {
+            break;
+        }
+    }
+
+    let start_ticks = engine.stats.ticks;
+    let ordered = !a.concurrent;
+    let mut cursor: usize = 0; // first unfinished target
+
+    while cursor < fits.len() && engine.stats.emissions < a.search_emissions && engine.stats.ticks < a.max_ticks {
+        let Some(tok) = engine.step() else {
+            continue;
+        };
+        let idx = engine.stats.emissions - 1;
+        let b = tok.pack_byte();
+
+        let active = if ordered {
+            &mut fits[cursor..cursor + 1]
+        } else {
+            &mut fits[cursor..]
+        };
+        for f in active.iter_mut().filter(|f| !f.done()) {
+            if b == f.target[f.indices.len()] {
+                f.indices.push(idx);
+            }
+        }
+
+        while cursor < fits.len() && fits[cursor].done() {
+            cursor += 1;
+        }
+    }
+
+    if let Some((k, f)) = fits.iter().enumerate().find(|(_, f)| !f.done()) {
+        anyhow::bail!(
+            "timemap fit multi failed: target[{}]={} matched {}/{} bytes; ordered={} start_emission={} searched_emissions={} ticks={} (start_ticks={})",
+            k,
+            a.multi_target[k],
+            f.indices.len(),
+            f.target.len(),
+            ordered,
+            a.start_emission,
+            engine.stats.emissions,
+            engine.stats.ticks,
+            start_ticks,
+        );
+    }
+
+    if a.require_order {
+        for k in 1..fits.len() {
+            let (prev_last, first) = (fits[k - 1].indices.last(), fits[k].indices.first());
+            if first <= prev_last {
+                anyhow::bail!(
+                    "timemap fit multi: --require-order violated: target[{}]={} starts at {:?} before target[{}] ends at {:?}",
+                    k,
+                    a.multi_target[k],
+                    first,
+                    k - 1,
+                    prev_last,
+                );
+            }
+        }
+    }
+
+    for (k, f) in fits.into_iter().enumerate() {
+        let out = pattern.replace("{}", &k.to_string());
+        let tm = TimingMap { indices: f.indices };
+        timemap::write_timemap_auto(&out, &tm)?;
+        eprintln!(
+            "timemap fit ok: target[{}]={} out={} target_bytes={} first_idx={:?} last_idx={:?}",
+            k,
+            a.multi_target[k],
+            out,
+            f.target.len(),
+            tm.indices.first(),
+            tm.indices.last(),
+        );
+    }
+
+    eprintln!(
+        "timemap fit multi ok: targets={} ordered={} start_emission={} end_emissions={} end_ticks={} delta_ticks={}",
+        a.multi_target.len(),
+        ordered,
+        a.start_emission,
+        engine.stats.emissions,
+        engine.stats.ticks,
+        engine.stats.ticks.saturating_sub(start_ticks),
+    );
+
+    Ok(())
+}
+
 pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
+    if a.residual == ResidualMode::Ternary {
+        anyhow::bail!("--residual ternary requires --map bitfield");
+    }
     let recipe = recipe_file::load_k8r(&a.recipe)?;
     let recipe_raw_len = std::fs::read(&a.recipe).map(|b| b.len()).unwrap_or(0usize);
 
@@ -213,6 +476,12 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
     if a.scan_step == 0 {
         anyhow::bail!("--scan-step must be >= 1");
     }
+    if a.progressive_refinement && a.scan_step < 4 {
+        eprintln!(
+            "WARN: --progressive-refinement with --scan-step {} < 4 has nothing to refine",
+            a.scan_step
+        );
+    }
 
     let seed = parse_seed_hex_opt(a.map_seed, &a.map_seed_hex)?;
 
@@ -225,14 +494,279 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
 
     let n = target.len();
 
+    if let Some(dump) = &a.dump_cond_tags {
+        let ct = cond
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("--dump-cond-tags requires --cond-tags"))?;
+        let applied = usize::min(ct.tags.len(), n.div_ceil(ct.block_bytes));
+        write_cond_tags_toml(dump, &ct.tags[..applied], ct.block_bytes)?;
+        eprintln!("cond tags dumped: path={} blocks={}", dump, applied);
+    }
+
+    let starts = fit_xor_start_emissions(&a)?;
+    let multi = starts.len() > 1;
+    if multi {
+        eprintln!("--- multi-start scoreboard ---");
+    }
+
+    let mut best: Option<FitXorStart> = None;
+    for &start_emission in &starts {
+        let r = match fit_xor_scan_from(&a, &recipe, start_emission, &target, seed, &cond, cond_seed) {
+            Ok(r) => r,
+            Err(e) if multi => {
+                eprintln!("start_emission={} skipped: {}", start_emission, e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        if multi {
+            eprintln!(
+                "start_emission={} window_start_pos={} matches={}/{} scanned_windows={} tm_zstd_bytes={} resid_zstd_bytes={} effective_bytes_no_recipe={}",
+                r.start_emission,
+                r.abs_win_start_pos,
+                r.best_matches,
+                n,
+                r.scanned,
+                r.tm_zstd,
+                r.resid_zstd,
+                r.effective_no_recipe
+            );
+        }
+        if best.as_ref().is_none_or(|b| r.effective_no_recipe < b.effective_no_recipe) {
+            best = Some(r);
+        }
+    }
+    let best = best.ok_or_else(|| anyhow::anyhow!("timemap fit-xor: no start emission produced a full window"))?;
+    if multi {
+        eprintln!("best_start_emission = {}", best.start_emission);
+    }
+
+    let plain_zstd = zstd_compress_len(&target, a.zstd_level);
+
+    let effective_no_recipe = best.effective_no_recipe;
+    let effective_with_recipe = recipe_raw_len.saturating_add(effective_no_recipe);
+
+    timemap::write_timemap_auto(&a.out_timemap, &best.tm)?;
+    std::fs::write(&a.out_residual, &best.residual)?;
+
+    eprintln!(
+        "timemap fit-xor ok: mode={:?} map={:?} map_seed={} (0x{:016x}) residual={:?} objective={:?} scan_step={} scanned_windows={} zstd_level={} tm_out={} resid_out={} target_bytes={} matches={}/{} ({:.4}%) window_start_pos={} scanned_emissions={} stream_bytes={} ticks={} cond_tags={} cond_seed={} (0x{:016x}) cond_block_bytes={} cond_tag_format={:?}",
+        a.mode,
+        a.map,
+        seed,
+        seed,
+        a.residual,
+        a.objective,
+        a.scan_step,
+        best.scanned,
+        a.zstd_level,
+        a.out_timemap,
+        a.out_residual,
+        n,
+        best.best_matches,
+        n,
+        (best.best_matches as f64) * 100.0 / (n as f64),
+        best.abs_win_start_pos,
+        best.scanned_emissions,
+        best.stream_bytes,
+        best.ticks,
+        a.cond_tags.as_deref().unwrap_or("<none>"),
+        cond_seed,
+        cond_seed,
+        a.cond_block_bytes,
+        a.cond_tag_format
+    );
+
+    eprintln!("--- scoreboard ---");
+    eprintln!("recipe_raw_bytes           = {}", recipe_raw_len);
+    eprintln!("plain_raw_bytes            = {}", target.len());
+    eprintln!("plain_zstd_bytes           = {}", plain_zstd);
+    eprintln!("tm_raw_bytes               = {}", best.tm_raw);
+    eprintln!("tm_zstd_bytes              = {}", best.tm_zstd);
+    eprintln!("resid_raw_bytes            = {}", best.resid_raw);
+    eprintln!("resid_zstd_bytes           = {}", best.resid_zstd);
+    eprintln!("effective_bytes_no_recipe  = {}", effective_no_recipe);
+    eprintln!("effective_bytes_with_recipe= {}", effective_with_recipe);
+    eprintln!(
+        "delta_vs_plain_zstd_no_recipe  = {}",
+        (effective_no_recipe as i64) - (plain_zstd as i64)
+    );
+    eprintln!(
+        "delta_vs_plain_zstd_with_recipe= {}",
+        (effective_with_recipe as i64) - (plain_zstd as i64)
+    );
+    eprintln!("note_best_scan_score_proxy_or_zstd = {}", best.best_zstd_resid);
+    eprintln!(
+        "note_best_scan_effective_prog_plus_score = {}",
+        best.best_score_effective
+    );
+
+    Ok(())
+}
+
+/// Result of one `fit-xor` scan from a single start emission.
+struct FitXorStart {
+    start_emission: u64,
+    scanned_emissions: u64,
+    stream_bytes: usize,
+    ticks: u64,
+    scanned: u64,
+    best_matches: u64,
+    best_zstd_resid: usize,
+    best_score_effective: usize,
+    abs_win_start_pos: u64,
+    tm: TimingMap,
+    residual: Vec<u8>,
+    tm_raw: usize,
+    tm_zstd: usize,
+    resid_raw: usize,
+    resid_zstd: usize,
+    effective_no_recipe: usize,
+}
+
+/// Start emissions to scan: --multi-start-emission, else the --start-emission-step /
+/// --start-emission-count sequence from --start-emission, else just --start-emission.
+fn fit_xor_start_emissions(a: &FitXorArgs) -> anyhow::Result<Vec<u64>> {
+    if !a.multi_start_emission.is_empty() {
+        return Ok(a.multi_start_emission.clone());
+    }
+    match (a.start_emission_step, a.start_emission_count) {
+        (Some(step), Some(count)) => {
+            if count == 0 {
+                anyhow::bail!("--start-emission-count must be >= 1");
+            }
+            (0..count)
+                .map(|k| {
+                    k.checked_mul(step)
+                        .and_then(|d| a.start_emission.checked_add(d))
+                        .ok_or_else(|| anyhow::anyhow!("start emission sequence overflows u64"))
+                })
+                .collect()
+        }
+        _ => Ok(vec![a.start_emission]),
+    }
+}
+
+/// `fit_xor_scan_from` through `TimingMap::stride_from_fit`; fills in the scoreboard
+/// numbers the library call does not return.
+fn fit_xor_scan_core(
+    a: &FitXorArgs,
+    recipe: &Recipe,
+    start_emission: u64,
+    target: &[u8],
+    mut opts: FitOptions,
+) -> anyhow::Result<FitXorStart> {
+    let n = target.len();
+    let mut engine = Engine::new(recipe.clone())?;
+
+    while engine.stats.emissions < start_emission && engine.stats.ticks < a.max_ticks {
+        let _ = engine.step();
+        if engine.stats.emissions >= a.search_emissions {
+            break;
+        }
+    }
+
+    let start_ticks = engine.stats.ticks;
+    let start_em = engine.stats.emissions;
+    opts.search_emissions = a.search_emissions.saturating_sub(start_em);
+
+    let (tm, residual) = match TimingMap::stride_from_fit(target, &mut engine, a.max_ticks, &opts) {
+        Ok(r) => r,
+        Err(K8Error::InsufficientEmissions { produced, .. }) => anyhow::bail!(
+            "timemap fit-xor short: need at least {} stream bytes after start_emission={}, got {} (mode={:?}, ticks={} delta_ticks={})",
+            n,
+            start_emission,
+            produced,
+            a.mode,
+            engine.stats.ticks,
+            engine.stats.ticks.saturating_sub(start_ticks),
+        ),
+        Err(e) => return Err(e.into()),
+    };
+
+    let abs_win_start_pos = tm.indices[0];
+    let stream_bytes = (engine.stats.emissions - start_em) as usize;
+    let best_matches = residual.iter().filter(|&&r| r == 0).count() as u64;
+    let best_zstd_resid = match a.objective {
+        FitObjective::Matches => n - best_matches as usize,
+        FitObjective::Zstd => zstd_compress_len(&residual, a.zstd_level),
+    };
+    // The library stops at the first perfect window under the matches objective.
+    let last_window = if a.objective == FitObjective::Matches && best_zstd_resid == 0 {
+        (abs_win_start_pos - start_em) as usize
+    } else {
+        stream_bytes - n
+    };
+
+    let tm_bytes = tm.encode_auto();
+    let tm_raw = tm_bytes.len();
+    let tm_zstd = zstd_compress_len(&tm_bytes, a.zstd_level);
+    let resid_raw = residual.len();
+    let resid_zstd = zstd_compress_len(&residual, a.zstd_level);
+
+    Ok(FitXorStart {
+        start_emission,
+        scanned_emissions: engine.stats.emissions,
+        stream_bytes,
+        ticks: engine.stats.ticks,
+        scanned: (last_window / a.scan_step) as u64 + 1,
+        best_matches,
+        best_zstd_resid,
+        best_score_effective: best_zstd_resid.saturating_add(tm0_len_contig(n as u64)),
+        abs_win_start_pos,
+        tm,
+        residual,
+        tm_raw,
+        tm_zstd,
+        resid_raw,
+        resid_zstd,
+        effective_no_recipe: tm_zstd.saturating_add(resid_zstd),
+    })
+}
+
+fn fit_xor_scan_from(
+    a: &FitXorArgs,
+    recipe: &Recipe,
+    start_emission: u64,
+    target: &[u8],
+    seed: u64,
+    cond: &Option<CondTags>,
+    cond_seed: u64,
+) -> anyhow::Result<FitXorStart> {
+    let n = target.len();
+
+    // Plain pair-mode fits are exactly what the library scan covers.
+    if let (ApplyMode::Pair, None, false, Some(map_mode), Some(residual_mode)) = (
+        a.mode,
+        cond,
+        a.progressive_refinement,
+        a.map.to_core(),
+        a.residual.to_core(),
+    ) {
+        let opts = FitOptions {
+            map_mode,
+            map_seed: seed,
+            residual_mode,
+            scan_step: a.scan_step,
+            objective: match a.objective {
+                FitObjective::Matches => fit::FitObjective::Matches,
+                FitObjective::Zstd => fit::FitObjective::Zstd,
+            },
+            zstd_level: a.zstd_level,
+            feistel_rounds: a.feistel_rounds,
+            search_emissions: 0,
+        };
+        return fit_xor_scan_core(a, recipe, start_emission, target, opts);
+    }
+
     let bytes_per_emission: u64 = match a.mode {
         ApplyMode::Pair => 1,
         ApplyMode::Rgbpair => 6,
     };
 
-    let mut engine = Engine::new(recipe)?;
+    let mut engine = Engine::new(recipe.clone())?;
 
-    while (engine.stats.emissions as u64) < a.start_emission && engine.stats.ticks < a.max_ticks {
+    while (engine.stats.emissions as u64) < start_emission && engine.stats.ticks < a.max_ticks {
         let _ = engine.step();
         if (engine.stats.emissions as u64) >= a.search_emissions {
             break;
@@ -260,7 +794,7 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
         anyhow::bail!(
             "timemap fit-xor short: need at least {} stream bytes after start_emission={}, got {} (mode={:?}, ticks={} delta_ticks={})",
             n,
-            a.start_emission,
+            start_emission,
             stream.len(),
             a.mode,
             engine.stats.ticks,
@@ -269,30 +803,28 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
     }
 
     let max_start = stream.len() - n;
-    let abs_stream_base_pos: u64 = a.start_emission * bytes_per_emission;
+    let abs_stream_base_pos: u64 = start_emission * bytes_per_emission;
 
     let mut scratch_resid: Vec<u8> = vec![0u8; n];
 
-    let mut best_start: usize = 0;
-    let mut best_matches: u64 = 0;
-
-    let mut best_zstd_resid: usize = usize::MAX;
-    let mut best_score_effective: usize = usize::MAX;
-
-    let mut scanned: u64 = 0;
-
-    let mut s: usize = 0;
-    while s <= max_start {
-        scanned += 1;
-
+    // (score_effective, score_metric, matches) of the window starting at stream offset `s`.
+    let mut score_window = |s: usize| -> (usize, usize, u64) {
         let base_pos = abs_stream_base_pos + (s as u64);
         let mut m: u64 = 0;
 
         for i in 0..n {
             let pos = base_pos + (i as u64);
-            let mapped0 = map_byte(a.map, seed, pos, stream[s + i]);
-            let mapped = apply_conditioning_if_enabled(mapped0, &cond, cond_seed, i);
-            let resid = make_residual_byte(a.residual, mapped, target[i]);
+            let mapped0 = map_byte(a.map, seed, pos, stream[s + i], a.feistel_rounds);
+            let mapped = apply_conditioning_if_enabled(mapped0, cond, cond_seed, i);
+            let resid = make_mapped_residual_byte(
+                a.residual,
+                a.map,
+                seed,
+                pos,
+                a.feistel_rounds,
+                mapped,
+                target[i],
+            );
             scratch_resid[i] = resid;
             if resid == 0 {
                 m += 1;
@@ -308,7 +840,22 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
         // Previously we added tm1_len_contig(...) which overestimates program cost now that TM0 exists.
         // For contiguous indices, the on-disk timemap will be TM0 (tiny), so use tm0_len_contig(...) here.
         let tm_raw_len = tm0_len_contig(n as u64);
-        let score_effective = score_metric.saturating_add(tm_raw_len);
+        (score_metric.saturating_add(tm_raw_len), score_metric, m)
+    };
+
+    let mut best_start: usize = 0;
+    let mut best_matches: u64 = 0;
+
+    let mut best_zstd_resid: usize = usize::MAX;
+    let mut best_score_effective: usize = usize::MAX;
+
+    let mut scanned: u64 = 0;
+
+    let mut s: usize = 0;
+    while s <= max_start {
+        scanned += 1;
+
+        let (score_effective, score_metric, m) = score_window(s);
 
         if score_effective < best_score_effective {
             best_score_effective = score_effective;
@@ -323,6 +870,48 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
         s = s.saturating_add(a.scan_step);
     }
 
+    // Progressive refinement: rescan [best - step, best + step] at step / 4 per level.
+    if a.progressive_refinement {
+        eprintln!(
+            "progressive level=0 step={} windows={} best_start={} score={} matches={}/{}",
+            a.scan_step, scanned, best_start, best_score_effective, best_matches, n
+        );
+        let mut step = a.scan_step;
+        for level in 1..=a.progressive_levels {
+            if best_matches == n as u64 {
+                eprintln!("progressive: all {} bytes match; refinement skipped", n);
+                break;
+            }
+            let next = step / 4;
+            if next < a.progressive_min_step.max(1) {
+                break;
+            }
+            let lo = best_start.saturating_sub(step);
+            let hi = best_start.saturating_add(step).min(max_start);
+            let mut windows = 0u64;
+            let mut s = lo;
+            while s <= hi {
+                if s != best_start {
+                    windows += 1;
+                    let (score_effective, score_metric, m) = score_window(s);
+                    if score_effective < best_score_effective {
+                        best_score_effective = score_effective;
+                        best_zstd_resid = score_metric;
+                        best_start = s;
+                        best_matches = m;
+                    }
+                }
+                s = s.saturating_add(next);
+            }
+            scanned += windows;
+            step = next;
+            eprintln!(
+                "progressive level={} step={} range={}..={} windows={} best_start={} score={} matches={}/{}",
+                level, step, lo, hi, windows, best_start, best_score_effective, best_matches, n
+            );
+        }
+    }
+
     let abs_win_start_pos: u64 = abs_stream_base_pos + (best_start as u64);
 
     let tm = TimingMap::stride(n as u64, abs_win_start_pos, 1).map_err(|e| anyhow::anyhow!("{e}"))?;
@@ -330,9 +919,17 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
     let mut residual: Vec<u8> = Vec::with_capacity(n);
     for i in 0..n {
         let pos = abs_win_start_pos + (i as u64);
-        let mapped0 = map_byte(a.map, seed, pos, stream[best_start + i]);
-        let mapped = apply_conditioning_if_enabled(mapped0, &cond, cond_seed, i);
-        residual.push(make_residual_byte(a.residual, mapped, target[i]));
+        let mapped0 = map_byte(a.map, seed, pos, stream[best_start + i], a.feistel_rounds);
+        let mapped = apply_conditioning_if_enabled(mapped0, cond, cond_seed, i);
+        residual.push(make_mapped_residual_byte(
+            a.residual,
+            a.map,
+            seed,
+            pos,
+            a.feistel_rounds,
+            mapped,
+            target[i],
+        ));
     }
 
     let tm_bytes = tm.encode_auto();
@@ -342,70 +939,36 @@ pub fn cmd_fit_xor(a: FitXorArgs) -> anyhow::Result<()> {
     let resid_raw = residual.len();
     let resid_zstd = zstd_compress_len(&residual, a.zstd_level);
 
-    let plain_zstd = zstd_compress_len(&target, a.zstd_level);
-
-    let effective_no_recipe = tm_zstd.saturating_add(resid_zstd);
-    let effective_with_recipe = recipe_raw_len.saturating_add(effective_no_recipe);
-
-    timemap::write_timemap_auto(&a.out_timemap, &tm)?;
-    std::fs::write(&a.out_residual, &residual)?;
-
-    eprintln!(
-        "timemap fit-xor ok: mode={:?} map={:?} map_seed={} (0x{:016x}) residual={:?} objective={:?} scan_step={} scanned_windows={} zstd_level={} tm_out={} resid_out={} target_bytes={} matches={}/{} ({:.4}%) window_start_pos={} scanned_emissions={} stream_bytes={} ticks={} cond_tags={} cond_seed={} (0x{:016x}) cond_block_bytes={} cond_tag_format={:?}",
-        a.mode,
-        a.map,
-        seed,
-        seed,
-        a.residual,
-        a.objective,
-        a.scan_step,
+    Ok(FitXorStart {
+        start_emission,
+        scanned_emissions: start_em + (stream.len() as u64 / bytes_per_emission),
+        stream_bytes: stream.len(),
+        ticks: engine.stats.ticks,
         scanned,
-        a.zstd_level,
-        a.out_timemap,
-        a.out_residual,
-        n,
         best_matches,
-        n,
-        (best_matches as f64) * 100.0 / (n as f64),
+        best_zstd_resid,
+        best_score_effective,
         abs_win_start_pos,
-        (start_em + (stream.len() as u64 / bytes_per_emission)),
-        stream.len(),
-        engine.stats.ticks,
-        a.cond_tags.as_deref().unwrap_or("<none>"),
-        cond_seed,
-        cond_seed,
-        a.cond_block_bytes,
-        a.cond_tag_format
-    );
-
-    eprintln!("--- scoreboard ---");
-    eprintln!("recipe_raw_bytes           = {}", recipe_raw_len);
-    eprintln!("plain_raw_bytes            = {}", target.len());
-    eprintln!("plain_zstd_bytes           = {}", plain_zstd);
-    eprintln!("tm_raw_bytes               = {}", tm_raw);
-    eprintln!("tm_zstd_bytes              = {}", tm_zstd);
-    eprintln!("resid_raw_bytes            = {}", resid_raw);
-    eprintln!("resid_zstd_bytes           = {}", resid_zstd);
-    eprintln!("effective_bytes_no_recipe  = {}", effective_no_recipe);
-    eprintln!("effective_bytes_with_recipe= {}", effective_with_recipe);
-    eprintln!(
-        "delta_vs_plain_zstd_no_recipe  = {}",
-        (effective_no_recipe as i64) - (plain_zstd as i64)
-    );
-    eprintln!(
-        "delta_vs_plain_zstd_with_recipe= {}",
-        (effective_with_recipe as i64) - (plain_zstd as i64)
-    );
-    eprintln!("note_best_scan_score_proxy_or_zstd = {}", best_zstd_resid);
-    eprintln!(
-        "note_best_scan_effective_prog_plus_score = {}",
-        best_score_effective
-    );
-
-    Ok(())
+        tm,
+        residual,
+        tm_raw,
+        tm_zstd,
+        resid_raw,
+        resid_zstd,
+        effective_no_recipe: tm_zstd.saturating_add(resid_zstd),
+    })
 }
 
 pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
+    if a.residual == ResidualMode::Ternary {
+        anyhow::bail!("--residual ternary requires --map bitfield");
+    }
+    if a.parallel_scan {
+        anyhow::bail!("--parallel-scan requires --map bitfield");
+    }
+    if a.trans_penalty_calibrate {
+        anyhow::bail!("--trans-penalty-calibrate requires --map bitfield");
+    }
     let recipe = recipe_file::load_k8r(&a.recipe)?;
     let recipe_raw_len = std::fs::read(&a.recipe).map(|b| b.len()).unwrap_or(0usize);
 
@@ -446,21 +1009,41 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
     let start_ticks = engine.stats.ticks;
     let start_em = engine.stats.emissions as u64;
 
-    let mut stream: Vec<u8> = Vec::new();
-    stream.reserve(
-        ((a.search_emissions.saturating_sub(start_em)).min(500_000) * bytes_per_emission) as usize,
-    );
+    let abs_stream_base_pos: u64 = a.start_emission * bytes_per_emission;
 
-    while (engine.stats.emissions as u64) < a.search_emissions && engine.stats.ticks < a.max_ticks {
-        if let Some(tok) = engine.step() {
-            match a.mode {
-                ApplyMode::Pair => stream.push(tok.pack_byte()),
-                ApplyMode::Rgbpair => stream.extend_from_slice(&tok.to_rgb_pair().to_bytes()),
-            }
-        }
-    }
+    // Every byte the search may generate; the ring path bounds windows by this instead of
+    // generating it all.
+    let budget_len =
+        (a.search_emissions.saturating_sub(start_em) * bytes_per_emission) as usize;
+
+    let cache_cap = a.stream_cache_size.map(|mb| mb.saturating_mul(1 << 20));
+    let mut stream = if a.use_ring_buffer {
+        // A chunk reads [min_start, min_start + lookahead + n) and the frontier never gets
+        // further ahead than that (plus one partial token), so this size always suffices.
+        let size = a
+            .lookahead
+            .saturating_add(a.chunk_size)
+            .min(budget_len)
+            .saturating_add(bytes_per_emission as usize);
+        StreamCache::ring(a.mode, size, abs_stream_base_pos)
+    } else {
+        let mut s = StreamCache::new(a.mode, cache_cap);
+        s.reserve(
+            ((a.search_emissions.saturating_sub(start_em)).min(500_000) * bytes_per_emission)
+                as usize,
+        );
+        s
+    };
+
+    // Uncapped: generate the whole search stream up front. Capped: start with one
+    // cache's worth; chunks extend it on demand. Ring: chunks generate their own window.
+    let initial_len = if a.use_ring_buffer {
+        0
+    } else {
+        cache_cap.unwrap_or(usize::MAX)
+    };
+    stream.ensure_len(&mut engine, initial_len, a.search_emissions, a.max_ticks);
 
-    let abs_stream_base_pos: u64 = a.start_emission * bytes_per_emission;
     let total_n = target.len();
 
     let mut tm_indices: Vec<u64> = Vec::with_capacity(total_n);
@@ -493,6 +1076,24 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
         a.cond_tag_format
     );
 
+    let mut diversity = DiversityTracker::new(a.diversity_penalty, a.diversity_window);
+    if a.diversity_penalty > 0.0 {
+        eprintln!(
+            "diversity_penalty={} diversity_window={}",
+            a.diversity_penalty, a.diversity_window
+        );
+    }
+
+    // Positions are absolute: the searched stream starts at the start_emission offset.
+    let first_min_pos = first_chunk_min_pos(
+        a.anchor_first_chunk_at,
+        abs_stream_base_pos,
+        abs_stream_base_pos + budget_len as u64,
+    )?;
+    if a.anchor_first_chunk_at.is_some() {
+        eprintln!("anchor_first_chunk_at={}", first_min_pos);
+    }
+
     let mut prev_pos: Option<u64> = None;
     let mut chunk_idx: usize = 0;
     let mut off: usize = 0;
@@ -506,35 +1107,45 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
         let n = remaining_total.min(a.chunk_size);
 
         let min_pos: u64 = match prev_pos {
-            None => abs_stream_base_pos,
+            None => first_min_pos,
             Some(p) => p.saturating_add(1),
         };
 
         let min_start: usize = (min_pos - abs_stream_base_pos) as usize;
         let max_start_cap = min_start.saturating_add(a.lookahead);
 
+        stream.evict_before(min_start)?;
+        if cache_cap.is_some() {
+            // Everything the window bounds below can reach; the uncapped stream
+            // already holds it, so both paths pick the same windows.
+            let reach = max_start_cap.saturating_add(remaining_total);
+            stream.ensure_len(&mut engine, reach, a.search_emissions, a.max_ticks);
+        } else if a.use_ring_buffer {
+            let reach = max_start_cap.saturating_add(n);
+            stream.ensure_len(&mut engine, reach, a.search_emissions, a.max_ticks);
+        }
+
         let need_min = min_start.saturating_add(n);
         if need_min > stream.len()
-            && !ensure_stream_len(
-                &mut engine,
-                &mut stream,
-                need_min,
-                a.mode,
-                a.search_emissions,
-                a.max_ticks,
-            )
+            && !stream.ensure_len(&mut engine, need_min, a.search_emissions, a.max_ticks)
         {
             eprintln!("no room for chunk {} (writing partial)", chunk_idx);
             break;
         }
 
         let need_finish_from_min = min_start.saturating_add(remaining_total);
-        if need_finish_from_min > stream.len()
-            && !ensure_stream_len(
+        if a.use_ring_buffer && need_finish_from_min > budget_len {
+            eprintln!(
+                "no room to finish from min_start for chunk {} (writing partial)",
+                chunk_idx
+            );
+            break;
+        }
+        if !a.use_ring_buffer
+            && need_finish_from_min > stream.len()
+            && !stream.ensure_len(
                 &mut engine,
-                &mut stream,
                 need_finish_from_min,
-                a.mode,
                 a.search_emissions,
                 a.max_ticks,
             )
@@ -551,7 +1162,12 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
         } else {
             0
         };
-        let max_start_finish = stream.len().saturating_sub(remaining_total);
+        let finish_len = if a.use_ring_buffer {
+            budget_len
+        } else {
+            stream.len()
+        };
+        let max_start_finish = finish_len.saturating_sub(remaining_total);
         let max_start: usize = max_start_possible.min(max_start_cap).min(max_start_finish);
 
         if min_start > max_start {
@@ -576,16 +1192,26 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
 
             for i in 0..n {
                 let pos = base_pos + (i as u64);
-                let mapped0 = map_byte(a.map, seed, pos, stream[s + i]);
+                let raw = stream.byte(s + i)?;
+                let mapped0 = map_byte(a.map, seed, pos, raw, a.feistel_rounds);
                 let mapped = apply_conditioning_if_enabled(mapped0, &cond, cond_seed, off + i);
-                let resid_b = make_residual_byte(a.residual, mapped, target[off + i]);
+                let resid_b = make_mapped_residual_byte(
+                    a.residual,
+                    a.map,
+                    seed,
+                    pos,
+                    a.feistel_rounds,
+                    mapped,
+                    target[off + i],
+                );
                 scratch_resid[i] = resid_b;
                 if resid_b == 0 {
                     matches += 1;
                 }
             }
 
-            let jump_cost = tm_jump_cost(prev_pos, base_pos);
+            let jump_cost =
+                tm_jump_cost(prev_pos, base_pos).saturating_add(diversity.penalty(base_pos));
 
             if a.objective == FitObjective::Zstd {
                 let zlen = zstd_compress_len(&scratch_resid, a.zstd_level);
@@ -626,13 +1252,23 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
 
             for &(_proxy_score, cand_s, cand_matches) in refine.iter() {
                 let base_pos = abs_stream_base_pos + (cand_s as u64);
-                let jump_cost = tm_jump_cost(prev_pos, base_pos);
+                let jump_cost =
+                    tm_jump_cost(prev_pos, base_pos).saturating_add(diversity.penalty(base_pos));
 
                 for i in 0..n {
                     let pos = base_pos + (i as u64);
-                    let mapped0 = map_byte(a.map, seed, pos, stream[cand_s + i]);
+                    let raw = stream.byte(cand_s + i)?;
+                    let mapped0 = map_byte(a.map, seed, pos, raw, a.feistel_rounds);
                     let mapped = apply_conditioning_if_enabled(mapped0, &cond, cond_seed, off + i);
-                    scratch_resid[i] = make_residual_byte(a.residual, mapped, target[off + i]);
+                    scratch_resid[i] = make_mapped_residual_byte(
+                        a.residual,
+                        a.map,
+                        seed,
+                        pos,
+                        a.feistel_rounds,
+                        mapped,
+                        target[off + i],
+                    );
                 }
 
                 let zlen = zstd_compress_len(&scratch_resid, a.zstd_level);
@@ -649,13 +1285,23 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
 
         let base_pos = abs_stream_base_pos + (best_start as u64);
         let jump_cost = tm_jump_cost(prev_pos, base_pos);
+        diversity.record(base_pos, n as u64);
 
         for i in 0..n {
             let pos = base_pos + (i as u64);
-            let mapped0 = map_byte(a.map, seed, pos, stream[best_start + i]);
+            let raw = stream.byte(best_start + i)?;
+            let mapped0 = map_byte(a.map, seed, pos, raw, a.feistel_rounds);
             let mapped = apply_conditioning_if_enabled(mapped0, &cond, cond_seed, off + i);
             tm_indices.push(pos);
-            residual.push(make_residual_byte(a.residual, mapped, target[off + i]));
+            residual.push(make_mapped_residual_byte(
+                a.residual,
+                a.map,
+                seed,
+                pos,
+                a.feistel_rounds,
+                mapped,
+                target[off + i],
+            ));
         }
 
         prev_pos = Some(base_pos + (n as u64) - 1);
@@ -664,9 +1310,18 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
             let mut scratch: Vec<u8> = vec![0u8; n];
             for i in 0..n {
                 let pos = base_pos + (i as u64);
-                let mapped0 = map_byte(a.map, seed, pos, stream[best_start + i]);
+                let raw = stream.byte(best_start + i)?;
+                let mapped0 = map_byte(a.map, seed, pos, raw, a.feistel_rounds);
                 let mapped = apply_conditioning_if_enabled(mapped0, &cond, cond_seed, off + i);
-                scratch[i] = make_residual_byte(a.residual, mapped, target[off + i]);
+                scratch[i] = make_mapped_residual_byte(
+                    a.residual,
+                    a.map,
+                    seed,
+                    pos,
+                    a.feistel_rounds,
+                    mapped,
+                    target[off + i],
+                );
             }
             zstd_compress_len(&scratch, a.zstd_level)
         } else if best_resid_zstd != usize::MAX {
@@ -733,6 +1388,23 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
     timemap::write_timemap_auto(&a.out_timemap, &tm)?;
     std::fs::write(&a.out_residual, &residual)?;
 
+    if let Some(mb) = a.stream_cache_size {
+        eprintln!(
+            "stream_cache: cap_mb={} stream_bytes={} ram_bytes={} evicted_bytes={}",
+            mb,
+            stream.len(),
+            stream.ram_len(),
+            stream.evicted_len()
+        );
+    }
+    if let Some(size) = stream.ring_capacity() {
+        eprintln!(
+            "ring_buffer: size_bytes={} stream_bytes={}",
+            size,
+            stream.len()
+        );
+    }
+
     eprintln!("--- scoreboard ---");
     eprintln!("recipe_raw_bytes           = {}", recipe_raw_len);
     eprintln!("plain_raw_bytes            = {}", produced);
@@ -756,6 +1428,9 @@ pub fn cmd_fit_xor_chunked(a: FitXorChunkedArgs) -> anyhow::Result<()> {
 }
 
 pub fn cmd_reconstruct(a: ReconstructArgs) -> anyhow::Result<()> {
+    if a.residual_mode == ResidualMode::Ternary {
+        anyhow::bail!("--residual-mode ternary requires --map bitfield");
+    }
     let recipe = recipe_file::load_k8r(&a.recipe)?;
     let tm = timemap::read_timemap(&a.timemap)?;
     if tm.indices.is_empty() {
@@ -798,9 +1473,17 @@ pub fn cmd_reconstruct(a: ReconstructArgs) -> anyhow::Result<()> {
                     let idx = (engine.stats.emissions - 1) as u64;
 
                     while i < tm.indices.len() && tm.indices[i] == idx {
-                        let mapped0 = map_byte(a.map, seed, idx, tok.pack_byte());
+                        let mapped0 = map_byte(a.map, seed, idx, tok.pack_byte(), a.feistel_rounds);
                         let mapped = apply_conditioning_if_enabled(mapped0, &cond, cond_seed, i);
-                        out.push(apply_residual_byte(a.residual_mode, mapped, resid[i]));
+                        out.push(apply_mapped_residual_byte(
+                            a.residual_mode,
+                            a.map,
+                            seed,
+                            idx,
+                            a.feistel_rounds,
+                            mapped,
+                            resid[i],
+                        ));
                         i += 1;
                     }
                 }
@@ -821,10 +1504,18 @@ pub fn cmd_reconstruct(a: ReconstructArgs) -> anyhow::Result<()> {
                             break;
                         }
                         while i < tm.indices.len() && tm.indices[i] == pos {
-                            let mapped0 = map_byte(a.map, seed, pos, rgb6[lane as usize]);
+                            let mapped0 = map_byte(a.map, seed, pos, rgb6[lane as usize], a.feistel_rounds);
                             let mapped =
                                 apply_conditioning_if_enabled(mapped0, &cond, cond_seed, i);
-                            out.push(apply_residual_byte(a.residual_mode, mapped, resid[i]));
+                            out.push(apply_mapped_residual_byte(
+                                a.residual_mode,
+                                a.map,
+                                seed,
+                                pos,
+                                a.feistel_rounds,
+                                mapped,
+                                resid[i],
+                            ));
                             i += 1;
                         }
                     }
@@ -844,6 +1535,17 @@ pub fn cmd_reconstruct(a: ReconstructArgs) -> anyhow::Result<()> {
         );
     }
 
+    if let Some(fill) = gap_fill(&a) {
+        out = fill_gaps(&tm.indices, &out, max_idx, fill)?;
+        eprintln!(
+            "reconstruct sparse: len={} filled={} gaps={} gap_fill={}",
+            out.len(),
+            tm.indices.len(),
+            out.len() - tm.indices.len(),
+            fill.describe()
+        );
+    }
+
     std::fs::write(&a.out, &out)?;
     eprintln!(
         "reconstruct ok: out={} bytes={} ticks={} emissions={} map_seed={} (0x{:016x}) cond_tags={} cond_seed={} (0x{:016x})",
@@ -862,31 +1564,65 @@ pub fn cmd_reconstruct(a: ReconstructArgs) -> anyhow::Result<()> {
 
 // ---- helpers ----
 
-fn ensure_stream_len(
-    engine: &mut Engine,
-    stream: &mut Vec<u8>,
-    need_len: usize,
-    mode: ApplyMode,
-    search_emissions: u64,
-    max_ticks: u64,
-) -> bool {
-    if stream.len() >= need_len {
-        return true;
+#[derive(Copy, Clone)]
+enum GapFill {
+    Byte(u8),
+    Random,
+}
+
+impl GapFill {
+    fn at(self, pos: u64) -> u8 {
+        match self {
+            GapFill::Byte(b) => b,
+            GapFill::Random => splitmix64(pos) as u8,
+        }
     }
 
-    while stream.len() < need_len
-        && (engine.stats.emissions as u64) < search_emissions
-        && engine.stats.ticks < max_ticks
-    {
-        if let Some(tok) = engine.step() {
-            match mode {
-                ApplyMode::Pair => stream.push(tok.pack_byte()),
-                ApplyMode::Rgbpair => stream.extend_from_slice(&tok.to_rgb_pair().to_bytes()),
-            }
+    fn describe(self) -> String {
+        match self {
+            GapFill::Byte(b) => format!("0x{b:02x}"),
+            GapFill::Random => "random".to_string(),
         }
     }
+}
 
-    stream.len() >= need_len
+fn gap_fill(a: &ReconstructArgs) -> Option<GapFill> {
+    if let Some(b) = a.write_gaps {
+        return Some(GapFill::Byte(b));
+    }
+    a.gap_fill_mode.map(|m| match m {
+        GapFillMode::Zero => GapFill::Byte(0x00),
+        GapFillMode::Ff => GapFill::Byte(0xFF),
+        GapFillMode::Random => GapFill::Random,
+    })
+}
+
+/// Expands the dense reconstruct output (one byte per timemap entry) to
+/// max_idx+1 bytes, placing each byte at its timemap position.
+fn fill_gaps(indices: &[u64], dense: &[u8], max_idx: u64, fill: GapFill) -> anyhow::Result<Vec<u8>> {
+    let len = usize::try_from(max_idx)
+        .ok()
+        .and_then(|m| m.checked_add(1))
+        .ok_or_else(|| anyhow::anyhow!("sparse reconstruct: max index {max_idx} too large"))?;
+    let mut out: Vec<u8> = (0..len as u64).map(|p| fill.at(p)).collect();
+    for (&idx, &b) in indices.iter().zip(dense) {
+        out[idx as usize] = b;
+    }
+    Ok(out)
+}
+
+fn write_positions(path: &str, tm: &TimingMap, fmt: PositionsFmt) -> anyhow::Result<()> {
+    match fmt {
+        PositionsFmt::Text => tm.to_positions_file(path)?,
+        PositionsFmt::Binary => {
+            let mut out = Vec::with_capacity(tm.indices.len() * 8);
+            for &p in &tm.indices {
+                out.extend_from_slice(&p.to_le_bytes());
+            }
+            std::fs::write(path, out)?;
+        }
+    }
+    Ok(())
 }
 
 fn collect_pair_bytes(
@@ -898,13 +1634,11 @@ fn collect_pair_bytes(
     let mut i: usize = 0;
     let max_idx = *tm.indices.last().unwrap_or(&0);
 
-    while engine.stats.ticks < max_ticks && (engine.stats.emissions as u64) <= max_idx {
-        if let Some(tok) = engine.step() {
-            let idx = (engine.stats.emissions - 1) as u64;
-            while i < tm.indices.len() && tm.indices[i] == idx {
-                out.push(tok.pack_byte());
-                i += 1;
-            }
+    let need = (max_idx + 1).saturating_sub(engine.stats.emissions);
+    for (idx, tok) in engine.run_emissions_with_positions(need, max_ticks) {
+        while i < tm.indices.len() && tm.indices[i] == idx {
+            out.push(tok.pack_byte());
+            i += 1;
         }
     }
 
@@ -931,21 +1665,19 @@ fn collect_rgbpair_bytes(
     let mut i: usize = 0;
     let max_idx = *tm.indices.last().unwrap_or(&0);
 
-    while engine.stats.ticks < max_ticks && ((engine.stats.emissions as u64) * 6) <= max_idx {
-        if let Some(tok) = engine.step() {
-            let em = (engine.stats.emissions - 1) as u64;
-            let base = em * 6;
-            let rgb6 = tok.to_rgb_pair().to_bytes();
-
-            for lane in 0..6u64 {
-                let pos = base + lane;
-                if pos > max_idx {
-                    break;
-                }
-                while i < tm.indices.len() && tm.indices[i] == pos {
-                    out.push(rgb6[lane as usize]);
-                    i += 1;
-                }
+    let need = (max_idx / 6 + 1).saturating_sub(engine.stats.emissions);
+    for (em, tok) in engine.run_emissions_with_positions(need, max_ticks) {
+        let base = em * 6;
+        let rgb6 = tok.to_rgb_pair().to_bytes();
+
+        for lane in 0..6u64 {
+            let pos = base + lane;
+            if pos > max_idx {
+                break;
+            }
+            while i < tm.indices.len() && tm.indices[i] == pos {
+                out.push(rgb6[lane as usize]);
+                i += 1;
             }
         }
     }
@@ -141,6 +141,226 @@ fn closed_form_start_offset(
     (m as u64 % window_len) as usize
 }
 
+/// Per-chunk start offsets for --law-type fibonacci: chunk k starts at
+/// `offset + floor(scale * F(k))` with F = 1, 2, 3, 5, 8, ..., or right after the
+/// previous chunk when that point is still inside it (so indices stay strictly
+/// increasing and the timemap is valid for `reconstruct`).
+///
+/// The gaps grow geometrically (ratio ~1.618), so the map is cheap to describe and
+/// revisits the stream at self-similar spacings; the idea is that this may suit
+/// targets with fractal-like structure (compressed data, code). Compare against
+/// closed-form on the actual target before relying on it.
+///
+/// Fails if the last chunk would end past `stream_len` (the emission budget).
+/// Returns the offsets and how many chunks were pushed up to the previous end.
+fn fibonacci_start_offsets(
+    sym_count: usize,
+    chunk_size: usize,
+    stream_len: usize,
+    scale: f64,
+    offset: u64,
+) -> anyhow::Result<(Vec<usize>, usize)> {
+    if !(scale.is_finite() && scale > 0.0) {
+        anyhow::bail!("--fibonacci-scale must be finite and > 0 (got {scale})");
+    }
+
+    let fib = std::iter::successors(Some((1u128, 2u128)), |&(f0, f1)| {
+        Some((f1, f0.saturating_add(f1)))
+    })
+    .map(|(f0, _)| (f0 as f64 * scale).floor() as u128 + offset as u128);
+    sequence_start_offsets(
+        "fibonacci",
+        "lower --fibonacci-scale/--fibonacci-offset",
+        fib,
+        sym_count,
+        chunk_size,
+        stream_len,
+    )
+}
+
+/// Shared by the sequence laws: chunk k starts at `starts[k]`, or at the previous
+/// chunk's end when `starts[k]` falls inside it. Returns the offsets and the number
+/// of chunks pushed up that way.
+fn sequence_start_offsets(
+    law: &str,
+    hint: &str,
+    starts: impl Iterator<Item = u128>,
+    sym_count: usize,
+    chunk_size: usize,
+    stream_len: usize,
+) -> anyhow::Result<(Vec<usize>, usize)> {
+    let chunks = sym_count.div_ceil(chunk_size);
+    let mut out = Vec::with_capacity(chunks);
+    let mut clamped = 0usize;
+    let mut prev_end = 0u128;
+    for (k, s) in starts.take(chunks).enumerate() {
+        let start = if s < prev_end {
+            clamped += 1;
+            prev_end
+        } else {
+            s
+        };
+        let n = usize::min(chunk_size, sym_count - k * chunk_size);
+        let end = start + n as u128;
+        if end > stream_len as u128 {
+            anyhow::bail!(
+                "{law}: chunk {k}/{chunks} would end at offset {end} but only {stream_len} emissions were produced; raise --search-emissions/--max-ticks, raise --chunk-size or {hint}"
+            );
+        }
+        out.push(start as usize);
+        prev_end = end;
+    }
+    Ok((out, clamped))
+}
+
+/// Primality for the prime laws: an Eratosthenes sieve up to a fixed bound, then
+/// trial division past it so the sequence never runs out.
+struct PrimeSource {
+    sieve: Vec<bool>,
+}
+
+impl PrimeSource {
+    fn new(limit: u64) -> Self {
+        let n = limit as usize + 1;
+        let mut sieve = vec![true; n];
+        for v in sieve.iter_mut().take(2) {
+            *v = false;
+        }
+        let mut p = 2usize;
+        while p * p < n {
+            if sieve[p] {
+                for m in (p * p..n).step_by(p) {
+                    sieve[m] = false;
+                }
+            }
+            p += 1;
+        }
+        Self { sieve }
+    }
+
+    fn limit(&self) -> u64 {
+        self.sieve.len() as u64 - 1
+    }
+
+    fn is_prime(&self, n: u64) -> bool {
+        if let Some(&v) = self.sieve.get(n as usize) {
+            return v;
+        }
+        if n.is_multiple_of(2) || n.is_multiple_of(3) {
+            return false;
+        }
+        let mut d = 5u64;
+        while d * d <= n {
+            if n.is_multiple_of(d) || n.is_multiple_of(d + 2) {
+                return false;
+            }
+            d += 6;
+        }
+        true
+    }
+
+    /// Primes (or lower twin-prime members) in increasing order.
+    fn seq(&self, twin: bool) -> impl Iterator<Item = u64> + '_ {
+        (2u64..).filter(move |&n| self.is_prime(n) && (!twin || self.is_prime(n + 2)))
+    }
+}
+
+/// Upper bound on the sieve (one byte per number); past it trial division takes over.
+const PRIME_SIEVE_MAX: u64 = 1 << 26;
+
+fn prime_sieve_limit(search_emissions: u64) -> u64 {
+    search_emissions.saturating_mul(2).min(PRIME_SIEVE_MAX)
+}
+
+/// Per-chunk start offsets for --law-type twin-prime / prime: chunk n starts at
+/// `offset + scale * p(n)` (pushed up to the previous end like fibonacci), where p is
+/// the lower twin primes 3, 5, 11, 17, 29, ... or all primes 2, 3, 5, 7, ....
+///
+/// Which engines line up: every lower twin prime above 3 is 5 mod 6, so starts after
+/// the first two sit on one residue mod 6*scale (gaps are multiples of 6*scale); plain
+/// primes above 3 split over two residues (1 and 5 mod 6). An engine whose symbol
+/// period (see `sim --period-detect`) divides 6*scale therefore sees every chunk start
+/// at the same stream phase (the quant range moves that period, so retune --qshift
+/// with the law in mind). Periods coprime to 6 spread the starts over all phases,
+/// much like a random map with irregular but deterministic gaps.
+///
+/// The sieve covers `2 * search_emissions` (capped at PRIME_SIEVE_MAX); when chunks
+/// outrun it, the sequence continues past the bound via trial division. Returns the
+/// offsets, the number of chunks pushed up, and how many starts came from past the
+/// sieve bound.
+fn prime_start_offsets(
+    twin: bool,
+    sym_count: usize,
+    chunk_size: usize,
+    stream_len: usize,
+    search_emissions: u64,
+    scale: u64,
+    offset: u64,
+) -> anyhow::Result<(Vec<usize>, usize, usize)> {
+    if scale == 0 {
+        anyhow::bail!("--twin-prime-scale must be >= 1");
+    }
+    let law = if twin { "twin-prime" } else { "prime" };
+
+    let primes = PrimeSource::new(prime_sieve_limit(search_emissions));
+    let chunks = sym_count.div_ceil(chunk_size);
+    let seq: Vec<u64> = primes.seq(twin).take(chunks).collect();
+    let beyond = seq.iter().filter(|&&p| p > primes.limit()).count();
+
+    let starts = seq
+        .into_iter()
+        .map(|p| p as u128 * scale as u128 + offset as u128);
+    let (out, clamped) = sequence_start_offsets(
+        law,
+        "lower --twin-prime-scale/--twin-prime-offset",
+        starts,
+        sym_count,
+        chunk_size,
+        stream_len,
+    )?;
+    Ok((out, clamped, beyond))
+}
+
+/// Per-chunk start offsets for --law-type poisson: chunk 0 starts at 0 and chunk k
+/// at `round(scale * T(k))`, where T(k) sums k exponential gaps with mean `rate`
+/// (pushed up to the previous end like fibonacci). Gap k is `-rate * ln(1 - x)` with
+/// `x = splitmix64(seed ^ k) / u64::MAX`, so the map replays from (rate, seed, scale).
+///
+/// The gaps are irregular but carry no structure beyond their mean, which is roughly
+/// how the engine spaces its own emissions; the other laws all impose one.
+fn poisson_start_offsets(
+    sym_count: usize,
+    chunk_size: usize,
+    stream_len: usize,
+    rate: f64,
+    seed: u64,
+    scale: f64,
+) -> anyhow::Result<(Vec<usize>, usize)> {
+    if !(rate.is_finite() && rate > 0.0) {
+        anyhow::bail!("--poisson-rate must be finite and > 0 (got {rate})");
+    }
+    if !(scale.is_finite() && scale > 0.0) {
+        anyhow::bail!("--poisson-scale must be finite and > 0 (got {scale})");
+    }
+
+    let arrivals = (0u64..).scan(0.0f64, move |t, k| {
+        if k > 0 {
+            // u64::MAX rounds up to 1.0 as f64; keep x < 1 so the log stays finite.
+            let x = (splitmix64(seed ^ k) as f64 / u64::MAX as f64).min(1.0 - f64::EPSILON);
+            *t += -rate * (1.0 - x).ln();
+        }
+        Some((*t * scale).round() as u128)
+    });
+    sequence_start_offsets(
+        "poisson",
+        "lower --poisson-rate/--poisson-scale",
+        arrivals,
+        sym_count,
+        chunk_size,
+        stream_len,
+    )
+}
+
 /// Maintain last K candidates; keep most-recent at the end.
 fn push_candidate_ring(ring: &mut Vec<usize>, k: usize, val: usize) {
     if k <= 1 {
@@ -160,6 +380,9 @@ pub fn cmd_gen_law(a: GenLawArgs) -> anyhow::Result<()> {
     if a.mode != ApplyMode::Rgbpair {
         anyhow::bail!("timemap gen-law (bitfield) requires --mode rgbpair");
     }
+    if a.residual == ResidualMode::Ternary {
+        anyhow::bail!("timemap gen-law does not support --residual ternary (use fit-xor-chunked)");
+    }
     if a.bits_per_emission == 0 || a.bits_per_emission > 8 {
         anyhow::bail!("--bits-per-emission must be in 1..=8");
     }
@@ -390,6 +613,7 @@ pub fn cmd_gen_law(a: GenLawArgs) -> anyhow::Result<()> {
                 &residual_syms,
                 a.zstd_level,
                 enc,
+                a.bf4_chunk_symbols,
                 if use_addk { Some(a.chunk_size) } else { None },
                 if use_addk { Some(chunk_addk.as_slice()) } else { None },
             )?;
@@ -482,21 +706,63 @@ pub fn cmd_gen_law(a: GenLawArgs) -> anyhow::Result<()> {
             Ok(())
         }
 
-        LawType::ClosedForm => {
+        LawType::ClosedForm
+        | LawType::Fibonacci
+        | LawType::TwinPrime
+        | LawType::Prime
+        | LawType::Poisson => {
+            let (seq_offsets, seq_clamped, prime_beyond_sieve) = match a.law_type {
+                LawType::Fibonacci => {
+                    let (o, c) = fibonacci_start_offsets(
+                        sym_count,
+                        a.chunk_size,
+                        stream_syms.len(),
+                        a.fibonacci_scale,
+                        a.fibonacci_offset,
+                    )?;
+                    (o, c, 0)
+                }
+                LawType::TwinPrime | LawType::Prime => prime_start_offsets(
+                    a.law_type == LawType::TwinPrime,
+                    sym_count,
+                    a.chunk_size,
+                    stream_syms.len(),
+                    a.search_emissions,
+                    a.twin_prime_scale,
+                    a.twin_prime_offset,
+                )?,
+                LawType::Poisson => {
+                    let (o, c) = poisson_start_offsets(
+                        sym_count,
+                        a.chunk_size,
+                        stream_syms.len(),
+                        a.poisson_rate,
+                        a.poisson_seed,
+                        a.poisson_scale,
+                    )?;
+                    (o, c, 0)
+                }
+                _ => (Vec::new(), 0, 0),
+            };
+
             for k in 0..(chunks as u64) {
-                let start_offset = closed_form_start_offset(
-                    k,
-                    window_len,
-                    a.law_cf_b,
-                    a.law_cf_a,
-                    a.law_cf_c,
-                    a.law_cf_p1,
-                    a.law_cf_g1,
-                    a.law_cf_phi1,
-                    a.law_cf_p2,
-                    a.law_cf_g2,
-                    a.law_cf_phi2,
-                );
+                let start_offset = if a.law_type != LawType::ClosedForm {
+                    seq_offsets[k as usize]
+                } else {
+                    closed_form_start_offset(
+                        k,
+                        window_len,
+                        a.law_cf_b,
+                        a.law_cf_a,
+                        a.law_cf_c,
+                        a.law_cf_p1,
+                        a.law_cf_g1,
+                        a.law_cf_phi1,
+                        a.law_cf_p2,
+                        a.law_cf_g2,
+                        a.law_cf_phi2,
+                    )
+                };
 
                 let chunk_off = (k as usize) * a.chunk_size;
                 let remaining = sym_count.saturating_sub(chunk_off);
@@ -540,6 +806,7 @@ pub fn cmd_gen_law(a: GenLawArgs) -> anyhow::Result<()> {
                 &residual_syms,
                 a.zstd_level,
                 enc,
+                a.bf4_chunk_symbols,
                 if use_addk { Some(a.chunk_size) } else { None },
                 if use_addk { Some(chunk_addk.as_slice()) } else { None },
             )?;
@@ -583,17 +850,34 @@ pub fn cmd_gen_law(a: GenLawArgs) -> anyhow::Result<()> {
             eprintln!("max_ticks                  = {}", a.max_ticks);
             eprintln!("window_len(typical)        = {}", window_len);
 
-            eprintln!("cf_b                       = {}", a.law_cf_b);
-            eprintln!("cf_a                       = {}", a.law_cf_a);
-            eprintln!("cf_c                       = {}", a.law_cf_c);
-            eprintln!(
-                "cf_p1/cf_g1/cf_phi1         = {}/{}/{}",
-                a.law_cf_p1, a.law_cf_g1, a.law_cf_phi1
-            );
-            eprintln!(
-                "cf_p2/cf_g2/cf_phi2         = {}/{}/{}",
-                a.law_cf_p2, a.law_cf_g2, a.law_cf_phi2
-            );
+            if a.law_type == LawType::Fibonacci {
+                eprintln!("fibonacci_scale            = {}", a.fibonacci_scale);
+                eprintln!("fibonacci_offset           = {}", a.fibonacci_offset);
+                eprintln!("fibonacci_clamped_chunks   = {}", seq_clamped);
+            } else if matches!(a.law_type, LawType::TwinPrime | LawType::Prime) {
+                eprintln!("twin_prime_scale           = {}", a.twin_prime_scale);
+                eprintln!("twin_prime_offset          = {}", a.twin_prime_offset);
+                eprintln!("prime_sieve_limit          = {}", prime_sieve_limit(a.search_emissions));
+                eprintln!("prime_clamped_chunks       = {}", seq_clamped);
+                eprintln!("prime_beyond_sieve_chunks  = {}", prime_beyond_sieve);
+            } else if a.law_type == LawType::Poisson {
+                eprintln!("poisson_rate               = {}", a.poisson_rate);
+                eprintln!("poisson_seed               = {}", a.poisson_seed);
+                eprintln!("poisson_scale              = {}", a.poisson_scale);
+                eprintln!("poisson_clamped_chunks     = {}", seq_clamped);
+            } else {
+                eprintln!("cf_b                       = {}", a.law_cf_b);
+                eprintln!("cf_a                       = {}", a.law_cf_a);
+                eprintln!("cf_c                       = {}", a.law_cf_c);
+                eprintln!(
+                    "cf_p1/cf_g1/cf_phi1         = {}/{}/{}",
+                    a.law_cf_p1, a.law_cf_g1, a.law_cf_phi1
+                );
+                eprintln!(
+                    "cf_p2/cf_g2/cf_phi2         = {}/{}/{}",
+                    a.law_cf_p2, a.law_cf_g2, a.law_cf_phi2
+                );
+            }
 
             eprintln!(
                 "matches                    = {}/{} ({:.2}%)",
@@ -638,3 +922,30 @@ pub fn cmd_gen_law(a: GenLawArgs) -> anyhow::Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prime_sequences_continue_past_the_sieve() {
+        // Sieve only covers 0..=10; everything after comes from trial division.
+        let ps = PrimeSource::new(10);
+        let twins: Vec<u64> = ps.seq(true).take(8).collect();
+        assert_eq!(twins, [3, 5, 11, 17, 29, 41, 59, 71]);
+        let primes: Vec<u64> = ps.seq(false).take(10).collect();
+        assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+
+    #[test]
+    fn poisson_gaps_average_the_rate() {
+        let (o, _) = poisson_start_offsets(4000, 1, usize::MAX, 100.0, 7, 1.0).unwrap();
+        assert_eq!(o[0], 0);
+        let mean = o[3999] as f64 / 3999.0;
+        assert!((mean - 100.0).abs() < 5.0, "mean gap {mean}");
+
+        let (scaled, _) = poisson_start_offsets(4000, 1, usize::MAX, 100.0, 7, 0.5).unwrap();
+        assert!(scaled[3999].abs_diff(o[3999] / 2) <= 2);
+        assert!(poisson_start_offsets(10, 1, usize::MAX, 0.0, 7, 1.0).is_err());
+    }
+}
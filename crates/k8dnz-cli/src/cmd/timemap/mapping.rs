@@ -1,20 +1,38 @@
 // crates/k8dnz-cli/src/cmd/timemap/mapping.rs
 
+use k8dnz_core::signal::fit;
+
 use super::args::MapMode;
 use super::util::splitmix64;
 
-pub fn map_byte(mode: MapMode, seed: u64, pos: u64, raw: u8) -> u8 {
-    match mode {
-        MapMode::None => raw,
-        MapMode::Splitmix64 => {
-            let k = splitmix64(seed ^ pos) as u8;
-            raw ^ k
-        }
-        MapMode::Ascii7 => ascii7(raw),
-        MapMode::Ascii7Splitmix => {
-            let k = splitmix64(seed ^ pos) as u8;
-            ascii7(raw ^ k)
+pub use k8dnz_core::signal::fit::FEISTEL_DEFAULT_ROUNDS;
+
+impl MapMode {
+    /// The core `signal::fit` mode for the position-keyed byte maps it shares with the
+    /// CLI; `None` for the text alphabets and bitfield.
+    pub fn to_core(self) -> Option<fit::MapMode> {
+        match self {
+            MapMode::None => Some(fit::MapMode::None),
+            MapMode::Splitmix64 => Some(fit::MapMode::Splitmix64),
+            MapMode::Ascii7 => Some(fit::MapMode::Ascii7),
+            MapMode::Ascii7Splitmix => Some(fit::MapMode::Ascii7Splitmix),
+            MapMode::Feistel => Some(fit::MapMode::Feistel),
+            MapMode::Text40
+            | MapMode::Text40Weighted
+            | MapMode::Text40Lane
+            | MapMode::Text40Field
+            | MapMode::Bitfield
+            | MapMode::Text64 => None,
         }
+    }
+}
+
+/// `feistel_rounds` is only read by `MapMode::Feistel`.
+pub fn map_byte(mode: MapMode, seed: u64, pos: u64, raw: u8, feistel_rounds: u8) -> u8 {
+    if let Some(core) = mode.to_core() {
+        return fit::map_byte(core, seed, pos, raw, feistel_rounds);
+    }
+    match mode {
         MapMode::Text40 => text_from_alphabet(TEXT40_ALPHABET, raw),
         MapMode::Text40Weighted => {
             text_from_weighted_alphabet(TEXT40_ALPHABET, TEXT40_WEIGHTS, raw)
@@ -23,15 +41,11 @@ pub fn map_byte(mode: MapMode, seed: u64, pos: u64, raw: u8) -> u8 {
         MapMode::Text40Field => text40_field(seed, pos, raw),
         MapMode::Bitfield => raw, // not used in byte pipeline
         MapMode::Text64 => text_from_alphabet(TEXT64_ALPHABET, raw),
-    }
-}
-
-fn ascii7(b: u8) -> u8 {
-    let x = b & 0x7F;
-    if (0x20..=0x7E).contains(&x) {
-        x
-    } else {
-        0x20u8 + (x % 95)
+        MapMode::None
+        | MapMode::Splitmix64
+        | MapMode::Ascii7
+        | MapMode::Ascii7Splitmix
+        | MapMode::Feistel => unreachable!("handled by signal::fit::map_byte"),
     }
 }
 
@@ -5,8 +5,9 @@ mod bf_lanes;
 mod bitfield;
 mod byte_pipeline;
 mod gen_law; // NEW
-mod mapping;
-mod residual;
+pub mod mapping;
+pub mod residual;
+mod stream_cache;
 mod tags;
 mod util;
 
@@ -21,6 +22,10 @@ pub fn run(args: TimemapArgs) -> anyhow::Result<()> {
         MapSeed(a) => byte_pipeline::cmd_map_seed(a),
         Apply(a) => byte_pipeline::cmd_apply(a),
         Fit(a) => byte_pipeline::cmd_fit(a),
+        ImportText(a) => byte_pipeline::cmd_import_text(a),
+        ExportText(a) => byte_pipeline::cmd_export_text(a),
+        Split(a) => byte_pipeline::cmd_split(a),
+        Repack(a) => byte_pipeline::cmd_repack(a),
         FitXor(a) => byte_pipeline::cmd_fit_xor(a),
         FitXorChunked(a) => {
             if a.map == args::MapMode::Bitfield {
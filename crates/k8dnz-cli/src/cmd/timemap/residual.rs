@@ -1,18 +1,77 @@
 // crates/k8dnz-cli/src/cmd/timemap/residual.rs
 
-use super::args::ResidualMode;
+use k8dnz_core::signal::fit;
+
+use super::args::{MapMode, ResidualMode};
+
+/// Ternary residuals are stored as 2-bit two's complement codes whatever the
+/// symbol width: 0 = prediction correct, 1 = one step above, 3 = one step below
+/// (2 is unused).
+pub const TERNARY_BITS: u8 = 2;
+
+const TERNARY_ZERO: u8 = 0;
+const TERNARY_PLUS: u8 = 1;
+const TERNARY_MINUS: u8 = 3;
+
+impl ResidualMode {
+    /// The core `signal::fit` residual; `None` for Ternary (bitfield only).
+    pub fn to_core(self) -> Option<fit::ResidualMode> {
+        match self {
+            ResidualMode::Xor => Some(fit::ResidualMode::Xor),
+            ResidualMode::Sub => Some(fit::ResidualMode::Sub),
+            ResidualMode::Ternary => None,
+        }
+    }
+}
 
 pub fn make_residual_byte(mode: ResidualMode, model: u8, plain: u8) -> u8 {
-    match mode {
-        ResidualMode::Xor => model ^ plain,
-        ResidualMode::Sub => plain.wrapping_sub(model),
+    match mode.to_core() {
+        Some(core) => fit::make_residual_byte(core, model, plain),
+        None => make_ternary(model, plain, 0xFF),
     }
 }
 
 pub fn apply_residual_byte(mode: ResidualMode, model: u8, resid: u8) -> u8 {
-    match mode {
-        ResidualMode::Xor => model ^ resid,
-        ResidualMode::Sub => model.wrapping_add(resid),
+    match mode.to_core() {
+        Some(core) => fit::apply_residual_byte(core, model, resid),
+        None => apply_ternary(model, resid, 0xFF),
+    }
+}
+
+/// `make_residual_byte` for a byte-pipeline position; `--map feistel` takes the
+/// residual in the permuted domain (see `fit::make_mapped_residual_byte`).
+pub fn make_mapped_residual_byte(
+    mode: ResidualMode,
+    map: MapMode,
+    seed: u64,
+    pos: u64,
+    feistel_rounds: u8,
+    model: u8,
+    plain: u8,
+) -> u8 {
+    match (mode.to_core(), map.to_core()) {
+        (Some(r), Some(m)) => {
+            fit::make_mapped_residual_byte(r, m, seed, pos, feistel_rounds, model, plain)
+        }
+        _ => make_residual_byte(mode, model, plain),
+    }
+}
+
+/// Inverse of `make_mapped_residual_byte`.
+pub fn apply_mapped_residual_byte(
+    mode: ResidualMode,
+    map: MapMode,
+    seed: u64,
+    pos: u64,
+    feistel_rounds: u8,
+    model: u8,
+    resid: u8,
+) -> u8 {
+    match (mode.to_core(), map.to_core()) {
+        (Some(r), Some(m)) => {
+            fit::apply_mapped_residual_byte(r, m, seed, pos, feistel_rounds, model, resid)
+        }
+        _ => apply_residual_byte(mode, model, resid),
     }
 }
 
@@ -26,10 +85,19 @@ pub fn sym_mask(bits_per_emission: u8) -> u8 {
     }
 }
 
+/// Bits per packed residual symbol: `bits_per_emission`, except Ternary (always 2).
+pub fn residual_symbol_bits(mode: ResidualMode, bits_per_emission: u8) -> u8 {
+    match mode {
+        ResidualMode::Ternary => TERNARY_BITS,
+        ResidualMode::Xor | ResidualMode::Sub => bits_per_emission,
+    }
+}
+
 pub fn make_residual_symbol(mode: ResidualMode, model: u8, plain: u8, mask: u8) -> u8 {
     match mode {
         ResidualMode::Xor => (model ^ plain) & mask,
         ResidualMode::Sub => plain.wrapping_sub(model) & mask,
+        ResidualMode::Ternary => make_ternary(model, plain, mask),
     }
 }
 
@@ -37,5 +105,31 @@ pub fn apply_residual_symbol(mode: ResidualMode, model: u8, resid: u8, mask: u8)
     match mode {
         ResidualMode::Xor => (model ^ resid) & mask,
         ResidualMode::Sub => model.wrapping_add(resid) & mask,
+        ResidualMode::Ternary => apply_ternary(model, resid, mask),
     }
 }
+
+/// `(plain - model).clamp(-1, 1)` on the circular alphabet `0..=mask`, so wrapping
+/// past the top counts as +1. Lossy once the circular distance exceeds 1.
+fn make_ternary(model: u8, plain: u8, mask: u8) -> u8 {
+    let alphabet = mask as i16 + 1;
+    let d = (plain as i16 - model as i16).rem_euclid(alphabet);
+    if d == 0 {
+        TERNARY_ZERO
+    } else if d <= alphabet / 2 {
+        TERNARY_PLUS
+    } else {
+        TERNARY_MINUS
+    }
+}
+
+/// `(model + resid).rem_euclid(alphabet)`; the unused code 2 is treated as 0.
+fn apply_ternary(model: u8, resid: u8, mask: u8) -> u8 {
+    let alphabet = mask as i16 + 1;
+    let step: i16 = match resid & sym_mask(TERNARY_BITS) {
+        TERNARY_PLUS => 1,
+        TERNARY_MINUS => -1,
+        _ => 0,
+    };
+    (model as i16 + step).rem_euclid(alphabet) as u8
+}
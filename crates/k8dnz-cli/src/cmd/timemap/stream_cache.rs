@@ -0,0 +1,180 @@
+// crates/k8dnz-cli/src/cmd/timemap/stream_cache.rs
+//
+// Engine byte stream for fit-xor-chunked, indexed by position relative to the
+// search start. Without a cap it is a plain growing Vec (the legacy behavior).
+// With a cap, bytes below the current chunk's minimum position are evicted from
+// RAM once the window exceeds the cap; they are appended to an anonymous temp file
+// which is memory-mapped, so old positions stay readable without living on the heap.
+// As a ring (--use-ring-buffer) only the last `size` bytes are kept at all; reads go
+// through `byte`, which fails instead of panicking once a position has been overwritten.
+
+use std::io::Write;
+use std::ops::Index;
+
+use anyhow::Context;
+use k8dnz_core::dynamics::emission_buffer::EmissionBuffer;
+use k8dnz_core::Engine;
+use memmap2::Mmap;
+
+use super::args::ApplyMode;
+
+pub struct StreamCache {
+    /// Position of `ram[0]`; everything below it lives in the spill file.
+    base: usize,
+    ram: Vec<u8>,
+    cap: Option<usize>,
+    spill: Option<Spill>,
+    ring: Option<EmissionBuffer>,
+    mode: ApplyMode,
+}
+
+struct Spill {
+    file: std::fs::File,
+    map: Option<Mmap>,
+}
+
+impl StreamCache {
+    /// `cap` is the in-RAM budget in bytes; `None` never evicts.
+    pub fn new(mode: ApplyMode, cap: Option<usize>) -> Self {
+        StreamCache {
+            base: 0,
+            ram: Vec::new(),
+            cap,
+            spill: None,
+            ring: None,
+            mode,
+        }
+    }
+
+    /// Ring of the last `size` bytes, keyed by absolute stream position (`origin` is the
+    /// absolute position of relative position 0).
+    pub fn ring(mode: ApplyMode, size: usize, origin: u64) -> Self {
+        StreamCache {
+            ring: Some(EmissionBuffer::with_origin(size, origin)),
+            ..StreamCache::new(mode, None)
+        }
+    }
+
+    /// Total bytes generated so far (evicted + in RAM).
+    pub fn len(&self) -> usize {
+        match &self.ring {
+            Some(r) => r.len() as usize,
+            None => self.base + self.ram.len(),
+        }
+    }
+
+    /// Byte at `pos`; errors if a ring has already overwritten it (or it was never generated).
+    pub fn byte(&self, pos: usize) -> anyhow::Result<u8> {
+        match &self.ring {
+            Some(r) => r.get(r.origin() + pos as u64).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "stream position {} is outside the ring buffer (held {}..{}, size {})",
+                    r.origin() + pos as u64,
+                    r.first_held(),
+                    r.origin() + r.len(),
+                    r.capacity()
+                )
+            }),
+            None => Ok(self[pos]),
+        }
+    }
+
+    pub fn ring_capacity(&self) -> Option<usize> {
+        self.ring.as_ref().map(EmissionBuffer::capacity)
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        let additional = self.cap.map_or(additional, |c| additional.min(c));
+        self.ram.reserve(additional);
+    }
+
+    /// Bytes currently held on the heap.
+    pub fn ram_len(&self) -> usize {
+        self.ram.len()
+    }
+
+    pub fn evicted_len(&self) -> usize {
+        self.base
+    }
+
+    pub fn push_token(&mut self, tok: k8dnz_core::PairToken) {
+        if let Some(r) = self.ring.as_mut() {
+            match self.mode {
+                ApplyMode::Pair => r.push(tok.pack_byte()),
+                ApplyMode::Rgbpair => tok.to_rgb_pair().to_bytes().iter().for_each(|&b| r.push(b)),
+            }
+            return;
+        }
+        match self.mode {
+            ApplyMode::Pair => self.ram.push(tok.pack_byte()),
+            ApplyMode::Rgbpair => self.ram.extend_from_slice(&tok.to_rgb_pair().to_bytes()),
+        }
+    }
+
+    /// Steps the engine until `need_len` bytes exist or a budget runs out.
+    pub fn ensure_len(
+        &mut self,
+        engine: &mut Engine,
+        need_len: usize,
+        search_emissions: u64,
+        max_ticks: u64,
+    ) -> bool {
+        if self.len() < need_len {
+            let per_token = match self.mode {
+                ApplyMode::Pair => 1,
+                ApplyMode::Rgbpair => 6,
+            };
+            let tokens = (need_len - self.len()).div_ceil(per_token) as u64;
+            let budget = search_emissions.saturating_sub(engine.stats.emissions);
+            engine.run_with_emission_callback(tokens.min(budget), max_ticks, &mut |_, tok| {
+                self.push_token(tok)
+            });
+        }
+        self.len() >= need_len
+    }
+
+    /// Sliding window: once RAM holds more than the cap, everything below `min_pos`
+    /// (which the caller will not scan again) moves to the spill file.
+    pub fn evict_before(&mut self, min_pos: usize) -> anyhow::Result<()> {
+        let Some(cap) = self.cap else {
+            return Ok(());
+        };
+        if self.ram.len() <= cap || min_pos <= self.base {
+            return Ok(());
+        }
+        let cut = (min_pos - self.base).min(self.ram.len());
+
+        if self.spill.is_none() {
+            let file = tempfile::tempfile().context("create stream cache temp file")?;
+            self.spill = Some(Spill { file, map: None });
+        }
+        let spill = self.spill.as_mut().expect("spill just created");
+        spill
+            .file
+            .write_all(&self.ram[..cut])
+            .context("write stream cache temp file")?;
+        // Safety: the file is private to this process (unlinked temp file) and only
+        // ever appended to; the map is rebuilt after every append.
+        spill.map = Some(unsafe { Mmap::map(&spill.file) }.context("mmap stream cache")?);
+
+        self.ram.drain(..cut);
+        self.base += cut;
+        Ok(())
+    }
+}
+
+impl Index<usize> for StreamCache {
+    type Output = u8;
+
+    fn index(&self, pos: usize) -> &u8 {
+        if pos >= self.base {
+            return &self.ram[pos - self.base];
+        }
+        let map = self
+            .spill
+            .as_ref()
+            .and_then(|s| s.map.as_ref())
+            .expect("evicted position without a spill map");
+        &map[pos]
+    }
+}
@@ -45,14 +45,129 @@ fn unpack_tags(bits_per_tag: u8, tag_count: usize, payload: &[u8]) -> anyhow::Re
     Ok(out)
 }
 
-pub fn read_cond_tags(path: &str, fmt: TagFormat, block_bytes: usize) -> anyhow::Result<CondTags> {
+/// An explicit `--cond-tag-format` always wins. Without one, `.csv` and `.toml`
+/// pick their text format and anything else (including `.bin`) reads as byte tags.
+fn detect_tag_format(path: &str, fmt: Option<TagFormat>) -> TagFormat {
+    if let Some(fmt) = fmt {
+        return fmt;
+    }
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("csv") => TagFormat::Csv,
+        Some("toml") => TagFormat::Toml,
+        _ => TagFormat::Byte,
+    }
+}
+
+/// Turns `(block, tag)` pairs into a dense tag-per-block vector. Blocks may be
+/// listed in any order but must cover 0..n exactly once.
+fn tags_from_pairs(pairs: &[(i64, i64)], src: &str) -> anyhow::Result<Vec<u8>> {
+    let mut tags: Vec<Option<u8>> = vec![None; pairs.len()];
+    for &(block, tag) in pairs {
+        if block < 0 {
+            anyhow::bail!("{src}: block index must be non-negative (got {block})");
+        }
+        if !(0..=255).contains(&tag) {
+            anyhow::bail!("{src}: tag for block {block} must be in 0..=255 (got {tag})");
+        }
+        let slot = tags.get_mut(block as usize).ok_or_else(|| {
+            anyhow::anyhow!(
+                "{src}: block {block} out of range; blocks must be dense 0..{}",
+                pairs.len()
+            )
+        })?;
+        if slot.is_some() {
+            anyhow::bail!("{src}: duplicate entry for block {block}");
+        }
+        *slot = Some(tag as u8);
+    }
+    // Every slot is filled: n pairs, n slots, no duplicates, none out of range.
+    Ok(tags.into_iter().flatten().collect())
+}
+
+fn parse_tag_int(s: &str) -> anyhow::Result<i64> {
+    let t = s.trim();
+    let v = if let Some(h) = t.strip_prefix("0x").or_else(|| t.strip_prefix("0X")) {
+        i64::from_str_radix(h, 16)
+    } else {
+        t.parse::<i64>()
+    };
+    v.map_err(|e| anyhow::anyhow!("bad integer {t:?}: {e}"))
+}
+
+fn tags_text<'a>(bytes: &'a [u8], path: &str) -> anyhow::Result<&'a str> {
+    std::str::from_utf8(bytes).map_err(|_| anyhow::anyhow!("{path}: cond tags file is not UTF-8"))
+}
+
+fn parse_csv_tags(text: &str, path: &str) -> anyhow::Result<Vec<u8>> {
+    let mut pairs = Vec::new();
+    for (lineno, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut cols = line.split(',');
+        let (b, t) = match (cols.next(), cols.next(), cols.next()) {
+            (Some(b), Some(t), None) => (b, t),
+            _ => anyhow::bail!("{path}:{}: expected `block,tag`", lineno + 1),
+        };
+        if pairs.is_empty() && b.trim().eq_ignore_ascii_case("block") {
+            continue;
+        }
+        let block = parse_tag_int(b).map_err(|e| anyhow::anyhow!("{path}:{}: {e}", lineno + 1))?;
+        let tag = parse_tag_int(t).map_err(|e| anyhow::anyhow!("{path}:{}: {e}", lineno + 1))?;
+        pairs.push((block, tag));
+    }
+    tags_from_pairs(&pairs, path)
+}
+
+fn parse_toml_tags(text: &str, path: &str) -> anyhow::Result<Vec<u8>> {
+    let doc: toml::Table = text
+        .parse()
+        .map_err(|e| anyhow::anyhow!("{path}: invalid TOML: {e}"))?;
+    let entries = match doc.get("tags") {
+        Some(toml::Value::Array(a)) => a.as_slice(),
+        Some(_) => anyhow::bail!("{path}: `tags` must be an array of tables ([[tags]])"),
+        None => &[],
+    };
+
+    let mut pairs = Vec::with_capacity(entries.len());
+    for (i, e) in entries.iter().enumerate() {
+        let get = |key: &str| {
+            e.get(key)
+                .and_then(toml::Value::as_integer)
+                .ok_or_else(|| anyhow::anyhow!("{path}: tags[{i}] missing integer `{key}`"))
+        };
+        pairs.push((get("block")?, get("tag")?));
+    }
+    tags_from_pairs(&pairs, path)
+}
+
+/// Writes `tags` as `[[tags]]` tables; `read_cond_tags` loads the file back unchanged.
+pub fn write_cond_tags_toml(path: &str, tags: &[u8], block_bytes: usize) -> anyhow::Result<()> {
+    let mut s = format!(
+        "# cond tags: {} blocks, block_bytes={}\n",
+        tags.len(),
+        block_bytes
+    );
+    for (block, tag) in tags.iter().enumerate() {
+        s.push_str(&format!("\n[[tags]]\nblock = {block}\ntag = 0x{tag:02X}\n"));
+    }
+    std::fs::write(path, s)?;
+    Ok(())
+}
+
+pub fn read_cond_tags(path: &str, fmt: Option<TagFormat>, block_bytes: usize) -> anyhow::Result<CondTags> {
     if block_bytes == 0 {
         anyhow::bail!("--cond-block-bytes must be >= 1");
     }
 
     let bytes = std::fs::read(path)?;
 
-    match fmt {
+    match detect_tag_format(path, fmt) {
         TagFormat::Byte => Ok(CondTags {
             tags: bytes,
             block_bytes,
@@ -72,6 +187,14 @@ pub fn read_cond_tags(path: &str, fmt: TagFormat, block_bytes: usize) -> anyhow:
             let tags = unpack_tags(bits, tag_count, payload)?;
             Ok(CondTags { tags, block_bytes })
         }
+        TagFormat::Csv => Ok(CondTags {
+            tags: parse_csv_tags(tags_text(&bytes, path)?, path)?,
+            block_bytes,
+        }),
+        TagFormat::Toml => Ok(CondTags {
+            tags: parse_toml_tags(tags_text(&bytes, path)?, path)?,
+            block_bytes,
+        }),
     }
 }
 
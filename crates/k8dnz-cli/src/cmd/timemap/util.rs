@@ -20,12 +20,16 @@ pub fn parse_seed_hex_opt(map_seed: u64, map_seed_hex: &Option<String>) -> anyho
     }
 }
 
-pub fn splitmix64(mut x: u64) -> u64 {
-    x = x.wrapping_add(0x9E3779B97F4A7C15);
-    let mut z = x;
-    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
-    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
-    z ^ (z >> 31)
+pub use k8dnz_core::signal::fit::splitmix64;
+
+/// clap value parser for a single byte given as decimal or 0x-prefixed hex.
+pub fn parse_byte(s: &str) -> Result<u8, String> {
+    let t = s.trim();
+    let r = match t.strip_prefix("0x").or_else(|| t.strip_prefix("0X")) {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => t.parse::<u8>(),
+    };
+    r.map_err(|_| format!("expected a byte 0..=255 or 0x00..=0xff, got {s:?}"))
 }
 
 /// Returns compressed length in bytes, or usize::MAX on error (keeps callers simple).
@@ -35,6 +39,12 @@ pub fn zstd_compress_len(bytes: &[u8], level: i32) -> usize {
         .unwrap_or(usize::MAX)
 }
 
+/// `zstd_compress_len` of a file's contents, streamed from disk.
+pub fn zstd_compress_file_len(path: &str, level: i32) -> anyhow::Result<usize> {
+    let f = std::fs::File::open(path)?;
+    Ok(zstd::encode_all(std::io::BufReader::new(f), level)?.len())
+}
+
 pub fn zstd_decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
     Ok(zstd::decode_all(bytes)?)
 }
@@ -56,6 +66,68 @@ pub fn tm1_len_contig(start_pos: u64, n: usize) -> usize {
     magic + count + delta0 + deltas_rest
 }
 
+/// --diversity-penalty bookkeeping for chunked fits: spans already taken by earlier
+/// chunks, so a candidate start can be charged for how densely the stream just
+/// behind it (`window` symbols) has been sampled.
+pub struct DiversityTracker {
+    weight: f64,
+    window: u64,
+    spans: std::collections::VecDeque<(u64, u64)>, // [start, end)
+}
+
+impl DiversityTracker {
+    pub fn new(weight: f64, window: u64) -> Self {
+        Self {
+            weight,
+            window,
+            spans: std::collections::VecDeque::new(),
+        }
+    }
+
+    /// `round(weight * used_fraction)` of `[start - window, start)`; 0 when disabled.
+    pub fn penalty(&self, start: u64) -> usize {
+        if self.weight <= 0.0 || self.window == 0 {
+            return 0;
+        }
+        let lo = start.saturating_sub(self.window);
+        let used: u64 = self
+            .spans
+            .iter()
+            .map(|&(s, e)| e.min(start).saturating_sub(s.max(lo)))
+            .sum();
+        (self.weight * used as f64 / self.window as f64).round() as usize
+    }
+
+    /// Chunks are placed monotonically, so spans that fall out of the window
+    /// behind `start` can never count again.
+    pub fn record(&mut self, start: u64, len: u64) {
+        self.spans.push_back((start, start.saturating_add(len)));
+        let lo = start.saturating_sub(self.window);
+        while self.spans.front().is_some_and(|&(_, e)| e <= lo) {
+            self.spans.pop_front();
+        }
+    }
+}
+
+/// Lower bound for the first chunk's start: `base_pos`, or --anchor-first-chunk-at
+/// once checked against the searched stream range `[base_pos, end_pos]`.
+pub fn first_chunk_min_pos(
+    anchor: Option<u64>,
+    base_pos: u64,
+    end_pos: u64,
+) -> anyhow::Result<u64> {
+    match anchor {
+        None => Ok(base_pos),
+        Some(p) if p >= base_pos && p <= end_pos => Ok(p),
+        Some(p) => anyhow::bail!(
+            "--anchor-first-chunk-at {} is outside the searched stream [{}, {}]",
+            p,
+            base_pos,
+            end_pos
+        ),
+    }
+}
+
 pub fn tm_jump_cost(prev_pos: Option<u64>, next_start_pos: u64) -> usize {
     match prev_pos {
         None => var_u64_len(next_start_pos),
@@ -26,12 +26,39 @@
 // FIXES (2026-02-13):
 // - Bound quant.shift during candidate generation (prevents degenerate all-zero keystream recipes).
 // - Health-check penalty on model keystream distribution (reject/penalize dead streams even if residual ranks well).
+//
+// RGB params (optional, --tune-rgb-params):
+// - after the shift search, sweeps (rgb.g_step, rgb.p_scale) ranked by effective_bytes of the
+//   rgbpair model stream (6 bytes/emission) against --fit-in; the best pair is saved in the
+//   tuned recipe, which is then written with the v5 (RGB-carrying) recipe layout.
+//
+// Genetic search (optional, --genetic): replaces the shift search with a population search
+// over all numeric recipe params; see tune_genetic.rs.
+//
+// Seed search (optional, --tune-seed): replaces the shift search with a parallel grid over
+// (recipe.seed, quant.shift); see tune_seed.rs.
+//
+// Tournament (optional, --multi-recipe-tournament r1.k8r r2.k8r ...): a full tune pass per
+// base recipe with the same parameters; the lowest effective_bytes winner is kept. See
+// tune_tournament.rs.
+//
+// Cross-validation (optional, --cross-validate --cv-splits k): the shift search runs once per
+// fold on the other k-1 folds of --fit-in; every fold winner is then scored on every held-out
+// fold and the shift with the lowest mean held-out effective_bytes is kept. The std dev of
+// that shift's held-out scores is reported as a stability measure.
+//
+// Tuning recipes (optional, --emit-tune-recipe / --from-tune-recipe): a TOML provenance record
+// of the shift search that can be replayed and checked; see tune_recipe.rs.
 
 use clap::{Args, ValueEnum};
-use k8dnz_core::dynamics::engine::FieldRangeStats;
+use k8dnz_core::dynamics::engine::{EngineStats, FieldRangeStats};
+use k8dnz_core::recipe::defaults::DefaultRecipes;
 use k8dnz_core::recipe::format as recipe_format;
-use k8dnz_core::recipe::recipe::{KeystreamMix, PayloadKind};
-use k8dnz_core::signal::token::PairToken;
+use k8dnz_core::recipe::recipe::{KeystreamMix, PayloadKind, RgbRecipe};
+use k8dnz_core::signal::quantize::QuantStats;
+use k8dnz_core::signal::rgb_emit::emit_rgbpair_from_fields;
+use k8dnz_core::signal::token::{PackedByte, PairToken};
+use k8dnz_core::stats::{self, TpeStats};
 use k8dnz_core::{Engine, Recipe};
 
 use crate::io::{ark, recipe_file};
@@ -52,12 +79,16 @@ impl KeystreamMixArg {
     }
 }
 
-#[derive(Args, Debug)]
+#[derive(Args, Clone, Debug)]
 pub struct TuneArgs {
     /// Base recipe path (.k8r). If omitted, uses built-in default recipe.
     #[arg(long)]
     pub recipe: Option<String>,
 
+    /// Built-in recipe preset: default, flat, high-entropy, text-aligned, rgb-optimized.
+    #[arg(long, value_parser = recipe_file::parse_preset, conflicts_with = "recipe")]
+    pub recipe_preset: Option<DefaultRecipes>,
+
     /// Output tuned recipe path (.k8r). REQUIRED.
     #[arg(long)]
     pub out_recipe: String,
@@ -211,6 +242,142 @@ pub struct TuneArgs {
     /// Requires --fit-in.
     #[arg(long)]
     pub dump_raw_model_pass: Option<String>,
+
+    /// Save every evaluated candidate of EACH pass as
+    /// <dir>/candidate_pass{P}_rank{R}_shift{S}.k8r (P and R 1-based, R within the pass).
+    #[arg(
+        long,
+        conflicts_with_all = ["genetic", "tune_seed", "multi_recipe_tournament"]
+    )]
+    pub export_all_candidates: Option<String>,
+
+    /// Only export the best K candidates of each pass (with --export-all-candidates).
+    #[arg(long, requires = "export_all_candidates")]
+    pub export_top_k: Option<usize>,
+
+    // --- Sensitivity around the best shift (optional) ---
+    /// Write a CSV of (delta, effective_bytes) for a fine sweep of +/-1% of quant width
+    /// around the best shift, and add a `sensitivity` section to the report.
+    /// Requires --fit-in.
+    #[arg(long)]
+    pub sensitivity_report: Option<String>,
+
+    /// Grid points for --sensitivity-report (forced odd; center is the best shift).
+    #[arg(long, default_value_t = 21)]
+    pub sensitivity_points: usize,
+
+    // --- Warm start (optional) ---
+    /// Continue from a previous --report: center on its best_shift and halve its final step.
+    /// The report's base_recipe_id must match this run's base recipe.
+    /// An explicit --step / --step-div / --passes grid still wins over the inferred step.
+    #[arg(long, conflicts_with = "qshift")]
+    pub warm_start: Option<String>,
+
+    // --- RGB params (optional) ---
+    /// After the shift search, sweep (rgb.g_step, rgb.p_scale) by effective_bytes of the
+    /// rgbpair model stream and save the best pair into the tuned recipe. Requires --fit-in.
+    #[arg(long, default_value_t = false)]
+    pub tune_rgb_params: bool,
+
+    /// g_step sweep for --tune-rgb-params as lo:hi:step (inclusive).
+    #[arg(long, default_value = "-8:8:1", allow_hyphen_values = true)]
+    pub rgb_step_range: String,
+
+    /// p_scale sweep for --tune-rgb-params as lo:hi:step (inclusive).
+    #[arg(long, default_value = "1:8:1", allow_hyphen_values = true)]
+    pub rgb_scale_range: String,
+
+    // --- Quantizer gamma (optional) ---
+    /// After the shift search, sweep quant_gamma over 0.25, 0.5, 1.0, 2.0, 4.0 by
+    /// effective_bytes of the keystream residual and save the best into the tuned recipe
+    /// (1.0 keeps the linear quantizer). Requires --fit-in.
+    #[arg(long, default_value_t = false)]
+    pub tune_gamma: bool,
+
+    // --- Genetic search (optional, see tune_genetic) ---
+    /// Evolve all numeric recipe params (orbit, lockstep, quant, clamp, waves) instead of
+    /// the shift search, ranked by effective_bytes on --fit-in. Requires --fit-in.
+    #[arg(long, default_value_t = false)]
+    pub genetic: bool,
+
+    /// --genetic population size
+    #[arg(long, default_value_t = 50)]
+    pub pop_size: usize,
+
+    /// --genetic generation count
+    #[arg(long, default_value_t = 100)]
+    pub generations: usize,
+
+    /// --genetic tournament size for parent selection
+    #[arg(long, default_value_t = 3)]
+    pub tournament_size: usize,
+
+    /// --genetic per-gene mutation probability (raised automatically when diversity collapses)
+    #[arg(long, default_value_t = 0.2)]
+    pub mutation_rate: f64,
+
+    // --- Cross-validation (optional) ---
+    /// k-fold cross-validation of the shift search: split --fit-in into --cv-splits folds,
+    /// tune on the rest, score on the held-out fold. best_shift minimizes the mean held-out
+    /// effective_bytes. Requires --fit-in; falls back to normal tuning below 5 KB.
+    #[arg(long, default_value_t = false, conflicts_with = "genetic")]
+    pub cross_validate: bool,
+
+    /// --cross-validate fold count
+    #[arg(long, default_value_t = 5)]
+    pub cv_splits: usize,
+
+    // --- Seed search (optional, see tune_seed) ---
+    /// Brute-force (recipe.seed, quant.shift) pairs instead of the shift search, ranked by
+    /// effective_bytes on --fit-in. Requires --fit-in and --keystream-mix splitmix64.
+    /// Run it after the shift search has converged.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["genetic", "cross_validate"])]
+    pub tune_seed: bool,
+
+    /// --tune-seed seed values as lo:hi:step (inclusive).
+    #[arg(long, default_value = "0:1000000:1000")]
+    pub seed_range: String,
+
+    /// --tune-seed: keep this many seeds from --seed-range, chosen by a shuffle keyed by
+    /// the base recipe's seed.
+    #[arg(long)]
+    pub seed_count: Option<usize>,
+
+    // --- Tournament (optional, see tune_tournament) ---
+    /// Tune each of these base recipes with the same parameters and save the one with the
+    /// lowest effective_bytes on --fit-in to --out-recipe. Requires --fit-in.
+    #[arg(
+        long,
+        num_args = 1..,
+        requires = "fit_in",
+        conflicts_with_all = [
+            "recipe", "recipe_preset", "warm_start", "out_ark", "dump_residual", "dump_model",
+            "dump_raw_model", "dump_residual_pass", "dump_model_pass", "dump_raw_model_pass",
+            "sensitivity_report",
+        ]
+    )]
+    pub multi_recipe_tournament: Vec<String>,
+
+    /// --multi-recipe-tournament: directory for each entrant's tuned recipe
+    /// (recipe_N.k8r) and report (recipe_N_report.txt), N 1-based. Defaults to a
+    /// temporary directory that is removed afterwards.
+    #[arg(long, requires = "multi_recipe_tournament")]
+    pub out_dir: Option<String>,
+
+    // --- Tuning recipes (optional, see tune_recipe) ---
+    /// Write a TOML tuning recipe for this run: the tune arguments, the base recipe bytes,
+    /// every pass's ranking and the best shift. Replay it with --from-tune-recipe.
+    #[arg(
+        long,
+        conflicts_with_all = ["genetic", "tune_seed", "multi_recipe_tournament", "from_tune_recipe"]
+    )]
+    pub emit_tune_recipe: Option<String>,
+
+    /// Re-run the tune recorded by --emit-tune-recipe and fail unless it reproduces the
+    /// recorded best shift. Only --out-recipe and --report are taken from this command
+    /// line; every search parameter comes from the file.
+    #[arg(long, conflicts_with_all = ["recipe", "recipe_preset", "multi_recipe_tournament"])]
+    pub from_tune_recipe: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -219,6 +386,7 @@ struct Metrics {
     entropy_byte: f64,
     peak_nibble: u64,
     ticks: u64,
+    tpe: TpeStats,
 }
 
 #[derive(Clone, Debug)]
@@ -240,6 +408,8 @@ struct ResidualMetrics {
     model_entropy_byte: f64,
 
     ticks: u64,
+    // Emission spacing; lower stddev breaks ties toward steadier recipes.
+    tpe: TpeStats,
 }
 
 #[derive(Clone, Debug)]
@@ -252,12 +422,21 @@ struct ByteSummary {
     top16_mass: f64,
 }
 
+// Second difference (one grid step) above this fraction of effective_bytes => steep minimum.
+const SENSITIVITY_STEEP_REL: f64 = 0.01;
+
+// --cross-validate falls back to normal tuning below this many --fit-in bytes.
+const CV_MIN_FIT_BYTES: usize = 5 * 1024;
+
+/// --tune-gamma candidates.
+const TUNE_GAMMAS: [f64; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+
 const KEYSTREAM_DEAD_DISTINCT_MAX: usize = 2;
 const KEYSTREAM_DEAD_ENTROPY_MAX: f64 = 0.50;
 
 /// Bound candidate shifts into a safe deterministic window so the tuner cannot generate dead recipes.
 /// We bind to +/- width where width = quant.max - quant.min.
-fn clamp_shift_to_width(shift: i64, width: i64) -> i64 {
+pub(crate) fn clamp_shift_to_width(shift: i64, width: i64) -> i64 {
     if width <= 0 {
         return 0;
     }
@@ -266,18 +445,87 @@ fn clamp_shift_to_width(shift: i64, width: i64) -> i64 {
     shift.clamp(lo, hi)
 }
 
+/// What a previous tune report tells us about where to continue.
+#[derive(Clone, Debug, PartialEq)]
+struct WarmStart {
+    base_recipe_id: String,
+    best_shift: i64,
+    final_step: i64,
+}
+
+/// Parse the `key = value` lines of a tune report. `final_step` is recorded by newer
+/// reports; older ones fall back to the last numeric `step_div` (or the single-pass
+/// default width/32).
+fn parse_warm_start(text: &str, width: i64) -> anyhow::Result<WarmStart> {
+    let mut base_recipe_id: Option<String> = None;
+    let mut best_shift: Option<i64> = None;
+    let mut final_step: Option<i64> = None;
+    let mut last_div: Option<i64> = None;
+
+    for line in text.lines() {
+        let Some((k, v)) = line.split_once('=') else {
+            continue;
+        };
+        let v = v.trim();
+        match k.trim() {
+            "base_recipe_id" => base_recipe_id = Some(v.to_string()),
+            "best_shift" => best_shift = v.parse().ok(),
+            "final_step" => final_step = v.parse().ok(),
+            "step_div" => {
+                if let Ok(d) = v.parse::<i64>() {
+                    last_div = Some(d);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let base_recipe_id =
+        base_recipe_id.ok_or_else(|| anyhow::anyhow!("warm-start report has no base_recipe_id"))?;
+    let best_shift =
+        best_shift.ok_or_else(|| anyhow::anyhow!("warm-start report has no best_shift"))?;
+    let final_step = final_step
+        .or_else(|| last_div.map(|d| width / d.max(1)))
+        .unwrap_or(width / 32)
+        .max(1);
+
+    Ok(WarmStart {
+        base_recipe_id,
+        best_shift,
+        final_step,
+    })
+}
+
+/// Step used by the last pass of this run's grid (mirrors tune_shift_multipass).
+fn final_grid_step(args: &TuneArgs, width: i64) -> anyhow::Result<i64> {
+    let div = if let Some(s) = args.step_div.as_deref() {
+        parse_step_div_list(s)?.last().copied()
+    } else if args.passes > 1 {
+        Some((1..args.passes).fold(32i64, |d, _| d.saturating_mul(8)))
+    } else {
+        None
+    };
+    Ok(match div {
+        Some(d) => (width / d).max(1),
+        None => args.step.unwrap_or((width / 32).max(1)),
+    })
+}
+
 /// Health check: returns true if the model keystream looks dead / near-dead.
 fn keystream_is_dead(model: &ByteSummary) -> bool {
     model.distinct_bytes <= KEYSTREAM_DEAD_DISTINCT_MAX
         || model.entropy_byte <= KEYSTREAM_DEAD_ENTROPY_MAX
 }
 
-pub fn run(args: TuneArgs) -> anyhow::Result<()> {
-    let mut recipe: Recipe = if let Some(path) = args.recipe.as_deref() {
-        recipe_file::load_k8r(path)?
-    } else {
-        k8dnz_core::recipe::defaults::default_recipe()
-    };
+pub fn run(mut args: TuneArgs) -> anyhow::Result<()> {
+    if args.from_tune_recipe.is_some() {
+        return super::tune_recipe::replay(&args);
+    }
+    if !args.multi_recipe_tournament.is_empty() {
+        return super::tune_tournament::run(&args);
+    }
+    let mut recipe: Recipe =
+        recipe_file::load_or_preset(args.recipe.as_deref(), args.recipe_preset)?;
 
     // Apply deterministic overrides (explicit inputs).
     if let Some(v) = args.qmin {
@@ -355,9 +603,67 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
     if wants_any_fit_dump && fit_bytes.is_none() {
         anyhow::bail!("--dump-* requires --fit-in <path>");
     }
+    if args.sensitivity_report.is_some() && fit_bytes.is_none() {
+        anyhow::bail!("--sensitivity-report requires --fit-in <path>");
+    }
+    if args.tune_rgb_params && fit_bytes.is_none() {
+        anyhow::bail!("--tune-rgb-params requires --fit-in <path>");
+    }
+    if args.tune_gamma && fit_bytes.is_none() {
+        anyhow::bail!("--tune-gamma requires --fit-in <path>");
+    }
+    if args.cross_validate && fit_bytes.is_none() {
+        anyhow::bail!("--cross-validate requires --fit-in <path>");
+    }
+    if args.cross_validate && args.cv_splits < 2 {
+        anyhow::bail!("--cv-splits must be >= 2 (got {})", args.cv_splits);
+    }
+    if args.genetic {
+        let Some(plain) = fit_bytes.as_deref() else {
+            anyhow::bail!("--genetic requires --fit-in <path>");
+        };
+        return super::tune_genetic::run(&args, recipe, plain);
+    }
+    if args.tune_seed {
+        let Some(plain) = fit_bytes.as_deref() else {
+            anyhow::bail!("--tune-seed requires --fit-in <path>");
+        };
+        return super::tune_seed::run(&args, recipe, plain);
+    }
 
     let base_rid = k8dnz_core::recipe::format::recipe_id_hex(&recipe);
 
+    let warm = match args.warm_start.as_deref() {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)?;
+            let ws = parse_warm_start(&text, width0)?;
+            if ws.base_recipe_id != base_rid {
+                anyhow::bail!(
+                    "--warm-start {}: report base_recipe_id={} does not match this run's base_recipe_id={} (different recipe or overrides)",
+                    path,
+                    ws.base_recipe_id,
+                    base_rid
+                );
+            }
+            recipe.quant.shift = clamp_shift_to_width(ws.best_shift, width0);
+            let step = (ws.final_step / 2).max(1);
+            let explicit_grid = args.step.is_some() || args.step_div.is_some() || args.passes > 1;
+            if !explicit_grid {
+                args.step = Some(step);
+            }
+            eprintln!(
+                "warm start: {} shift={} prev_step={} step={}{}",
+                path,
+                recipe.quant.shift,
+                ws.final_step,
+                step,
+                if explicit_grid { " (ignored: explicit grid given)" } else { "" }
+            );
+            Some((path.to_string(), ws, step))
+        }
+        None => None,
+    };
+
     let mut report_lines: Vec<String> = Vec::new();
     report_lines.push("--- k8dnz tune report ---".to_string());
     report_lines.push(format!("base_recipe_id = {}", base_rid));
@@ -369,6 +675,12 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
         "base_clamp = min={} max={}",
         recipe.field_clamp.min, recipe.field_clamp.max
     ));
+    if let Some((path, ws, step)) = warm.as_ref() {
+        report_lines.push(format!("warm_start = {}", path));
+        report_lines.push(format!("warm_start_shift = {}", ws.best_shift));
+        report_lines.push(format!("warm_start_prev_step = {}", ws.final_step));
+        report_lines.push(format!("warm_start_step = {}", step));
+    }
     report_lines.push(format!("keystream_mix = {:?}", recipe.keystream_mix));
     report_lines.push(format!("fit_in = {:?}", args.fit_in));
     report_lines.push(format!("fit_by_residual = {}", args.fit_by_residual));
@@ -386,6 +698,10 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
         "dump_raw_model_pass = {:?}",
         args.dump_raw_model_pass
     ));
+    report_lines.push(format!(
+        "export_all_candidates = {:?}",
+        args.export_all_candidates
+    ));
     report_lines.push("".to_string());
 
     eprintln!("--- tune ---");
@@ -447,15 +763,41 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
         report_lines.push("".to_string());
     }
 
-    // Multi-pass shift search / refinement.
+    let cv_plain = match fit_bytes.as_deref() {
+        Some(plain) if args.cross_validate && plain.len() < CV_MIN_FIT_BYTES => {
+            eprintln!(
+                "WARN: --cross-validate needs at least {} bytes of --fit-in (got {}); falling back to normal tuning",
+                CV_MIN_FIT_BYTES,
+                plain.len()
+            );
+            report_lines.push(format!(
+                "cross_validate = skipped (fit_in {} bytes < {})",
+                plain.len(),
+                CV_MIN_FIT_BYTES
+            ));
+            None
+        }
+        Some(plain) if args.cross_validate => Some(plain),
+        _ => None,
+    };
+
+    // Multi-pass shift search / refinement (once per fold under --cross-validate).
+    let mut cv_lines = None;
     let (
-        best_recipe,
+        mut best_recipe,
         best_shift,
         best_metrics_opt,
         best_rmetrics_opt,
         per_pass_rankings,
         elapsed_ms,
-    ) = tune_shift_multipass(&args, recipe, fit_bytes.as_deref())?;
+    ) = match cv_plain {
+        Some(plain) => {
+            let (out, lines) = tune_shift_cross_validated(&args, recipe, plain)?;
+            cv_lines = Some(lines);
+            out
+        }
+        None => tune_shift_multipass(&args, recipe, fit_bytes.as_deref())?,
+    };
 
     // Final safety rail: ensure the chosen recipe doesn't have a dead keystream.
     // We only need this check when fit/residual features are used, because residual ranking can
@@ -475,6 +817,32 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
         }
     }
 
+    // Optional RGB sweep on top of the fixed best shift (independent of quant.shift).
+    let rgb_lines = match (args.tune_rgb_params, fit_bytes.as_deref()) {
+        (true, Some(plain)) => {
+            let (rgb, lines) = tune_rgb_params(&args, &best_recipe, plain)?;
+            best_recipe.rgb = rgb;
+            best_recipe.version = best_recipe.version.max(recipe_format::FORMAT_VERSION_RGB);
+            Some(lines)
+        }
+        _ => None,
+    };
+
+    // Optional quantizer curve on top of the fixed best shift.
+    let gamma_lines = match (args.tune_gamma, fit_bytes.as_deref()) {
+        (true, Some(plain)) => {
+            let (gamma, lines) = tune_quant_gamma(&args, &best_recipe, plain)?;
+            best_recipe.quant_gamma = gamma;
+            if gamma.is_some() {
+                best_recipe.version = best_recipe
+                    .version
+                    .max(recipe_format::FORMAT_VERSION_QUANT_GAMMA);
+            }
+            Some(lines)
+        }
+        _ => None,
+    };
+
     let best_rid = k8dnz_core::recipe::format::recipe_id_hex(&best_recipe);
 
     // Save tuned recipe (required).
@@ -486,16 +854,24 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
 
     report_lines.push(format!("best_shift = {}", best_shift));
     report_lines.push(format!("best_recipe_id = {}", best_rid));
+    report_lines.push(format!("final_step = {}", final_grid_step(&args, width0)?));
+
+    let warnings = best_recipe.validate_deep();
+    report_lines.push(format!("validation_warnings = {}", warnings.len()));
+    for w in &warnings {
+        eprintln!("WARN: best recipe: {w}");
+        report_lines.push(format!("validation_warning = {w}"));
+    }
 
     if let Some(m) = best_metrics_opt.as_ref() {
         report_lines.push(format!(
-            "best_token_metrics distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={}",
-            m.distinct_bytes, m.entropy_byte, m.peak_nibble, m.ticks
+            "best_token_metrics distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
+            m.distinct_bytes, m.entropy_byte, m.peak_nibble, m.ticks, m.tpe.mean(), m.tpe.stddev()
         ));
     }
     if let Some(m) = best_rmetrics_opt.as_ref() {
         report_lines.push(format!(
-            "best_residual_metrics effective_bytes={} (recipe_bytes={} + zstd_bytes={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy_byte={:.4} distinct={}/256 peak_byte={} ticks={}",
+            "best_residual_metrics effective_bytes={} (recipe_bytes={} + zstd_bytes={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy_byte={:.4} distinct={}/256 peak_byte={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
             m.effective_bytes,
             m.recipe_bytes,
             m.zstd_bytes,
@@ -507,9 +883,24 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
             m.entropy_byte,
             m.distinct_bytes,
             m.peak_byte,
-            m.ticks
+            m.ticks, m.tpe.mean(), m.tpe.stddev()
         ));
     }
+
+    // How much the best model stream tells us about the target.
+    if let Some(plain) = fit_bytes.as_deref() {
+        let mut e = Engine::new(best_recipe.clone())?;
+        let model = ark::keystream_bytes(&mut e, plain.len(), args.per_max_ticks)?;
+        let mi = stats::mutual_information(&model, plain);
+        let h_cond = stats::conditional_entropy(&model, plain);
+        eprintln!(
+            "best MI(model, target) = {:.6} bits H(target|model) = {:.6} bits",
+            mi, h_cond
+        );
+        report_lines.push(format!("best_mi_model_target = {:.6} bits", mi));
+        report_lines.push(format!("best_h_target_given_model = {:.6} bits", h_cond));
+    }
+
     report_lines.push(format!("elapsed_ms = {}", elapsed_ms));
     report_lines.push("".to_string());
 
@@ -528,14 +919,14 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
             report_lines.push("ranking = token_metrics".to_string());
             for (rank, (shift, m, rid)) in rows.iter().take(9).enumerate() {
                 report_lines.push(format!(
-                    "#{:>2} shift={} recipe_id={} entropy_byte={:.4} distinct={}/256 peak_nibble={} ticks={}",
+                    "#{:>2} shift={} recipe_id={} entropy_byte={:.4} distinct={}/256 peak_nibble={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
                     rank + 1,
                     shift,
                     rid,
                     m.entropy_byte,
                     m.distinct_bytes,
                     m.peak_nibble,
-                    m.ticks
+                    m.ticks, m.tpe.mean(), m.tpe.stddev()
                 ));
             }
         }
@@ -551,7 +942,7 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
             }
             for (rank, (shift, m, rid)) in rows.iter().take(9).enumerate() {
                 report_lines.push(format!(
-                    "#{:>2} shift={} recipe_id={} effective_bytes={} (recipe_bytes={} + zstd_bytes={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={}",
+                    "#{:>2} shift={} recipe_id={} effective_bytes={} (recipe_bytes={} + zstd_bytes={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
                     rank + 1,
                     shift,
                     rid,
@@ -566,7 +957,7 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
                     m.entropy_byte,
                     m.distinct_bytes,
                     m.peak_byte,
-                    m.ticks
+                    m.ticks, m.tpe.mean(), m.tpe.stddev()
                 ));
             }
         }
@@ -574,30 +965,57 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
         report_lines.push("".to_string());
     }
 
+    if let Some(lines) = cv_lines {
+        report_lines.push("--- cross_validation ---".to_string());
+        report_lines.extend(lines);
+        report_lines.push("".to_string());
+    }
+
+    if let Some(lines) = rgb_lines {
+        report_lines.push("--- rgb_params ---".to_string());
+        report_lines.extend(lines);
+        report_lines.push("".to_string());
+    }
+
+    if let Some(lines) = gamma_lines {
+        report_lines.push("--- quant_gamma ---".to_string());
+        report_lines.extend(lines);
+        report_lines.push("".to_string());
+    }
+
     // Optional validation run (token stream)
     if args.validate_best {
         let mut e = Engine::new(best_recipe.clone())?;
-        let toks = e.run_emissions(args.validate_emissions, args.validate_max_ticks);
-        let m = compute_token_metrics(&toks, e.stats.ticks);
+        let (qs, tpe) = quant_stats_with_tpe(&mut e, args.validate_emissions, args.validate_max_ticks);
+        let m = compute_token_metrics(&qs, e.stats.ticks, tpe);
         eprintln!(
-            "validate_best: emissions={} max_ticks={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={}",
+            "validate_best: emissions={} max_ticks={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
             args.validate_emissions,
             args.validate_max_ticks,
             m.distinct_bytes,
             m.entropy_byte,
             m.peak_nibble,
-            m.ticks
+            m.ticks, m.tpe.mean(), m.tpe.stddev()
         );
         report_lines.push("--- validate_best ---".to_string());
         report_lines.push(format!(
-            "validate_best: emissions={} max_ticks={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={}",
+            "validate_best: emissions={} max_ticks={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
             args.validate_emissions,
             args.validate_max_ticks,
             m.distinct_bytes,
             m.entropy_byte,
             m.peak_nibble,
-            m.ticks
+            m.ticks, m.tpe.mean(), m.tpe.stddev()
         ));
+        report_lines.push(format!("quant_stats = {}", qs.to_json()));
+        report_lines.push("".to_string());
+    }
+
+    // Optional sensitivity sweep around the best shift (requires fit_in)
+    if let (Some(path), Some(plain)) = (args.sensitivity_report.as_deref(), fit_bytes.as_deref()) {
+        let lines = run_sensitivity(&args, &best_recipe, plain, path)?;
+        report_lines.push("--- sensitivity ---".to_string());
+        report_lines.extend(lines);
         report_lines.push("".to_string());
     }
 
@@ -750,6 +1168,12 @@ pub fn run(args: TuneArgs) -> anyhow::Result<()> {
         eprintln!("wrote report: {}", path);
     }
 
+    if let Some(path) = args.emit_tune_recipe.as_deref() {
+        let passes = pass_tables(&args, &per_pass_rankings);
+        super::tune_recipe::write(path, &args, &base_rid, best_shift, &best_rid, passes)?;
+        eprintln!("wrote tune recipe: {}", path);
+    }
+
     // Final summary.
     eprintln!(
         "tune ok: best_shift={} best_recipe_id={} elapsed_ms={}",
@@ -765,6 +1189,34 @@ fn zstd_compress_len(bytes: &[u8], level: i32) -> usize {
         .unwrap_or(usize::MAX)
 }
 
+/// effective_bytes (recipe_bytes + zstd(residual)) of `r` against `plain`, the same score
+/// the shift search ranks by. None when the recipe fails to run or its keystream is dead.
+pub(crate) fn effective_bytes_for(
+    r: &Recipe,
+    plain: &[u8],
+    max_ticks: u64,
+    zstd_level: i32,
+) -> Option<usize> {
+    let mut e = Engine::new(r.clone()).ok()?;
+    let model = ark::keystream_bytes(&mut e, plain.len(), max_ticks).ok()?;
+    effective_bytes_for_model(r, plain, &model, zstd_level)
+}
+
+/// `effective_bytes_for` with the keystream `r` produces already in hand.
+pub(crate) fn effective_bytes_for_model(
+    r: &Recipe,
+    plain: &[u8],
+    model: &[u8],
+    zstd_level: i32,
+) -> Option<usize> {
+    if keystream_is_dead(&byte_summary(model)) {
+        return None;
+    }
+    let residual: Vec<u8> = plain.iter().zip(model).map(|(p, k)| p ^ k).collect();
+    let z = zstd_compress_len(&residual, zstd_level);
+    Some(recipe_format::encode(r).len().saturating_add(z))
+}
+
 fn expand_pass_pattern(pat: &str, pass_1based: usize) -> String {
     if pat.contains("%d") {
         pat.replace("%d", &pass_1based.to_string())
@@ -773,6 +1225,44 @@ fn expand_pass_pattern(pat: &str, pass_1based: usize) -> String {
     }
 }
 
+/// Writes the pass's candidates (`pass_base` with each ranked shift) in ranking order;
+/// residual rows rank the pass when present, as in `tune_shift_once`.
+fn maybe_export_candidates(
+    args: &TuneArgs,
+    pass_1based: usize,
+    pass_base: &Recipe,
+    rows_token: Option<&TokenRows>,
+    rows_resid: Option<&ResidRows>,
+) -> anyhow::Result<()> {
+    let Some(dir) = args.export_all_candidates.as_deref() else {
+        return Ok(());
+    };
+    let shifts: Vec<i64> = match (rows_resid, rows_token) {
+        (Some(rows), _) => rows.iter().map(|(shift, _, _)| *shift).collect(),
+        (None, Some(rows)) => rows.iter().map(|(shift, _, _)| *shift).collect(),
+        (None, None) => Vec::new(),
+    };
+    let k = args.export_top_k.unwrap_or(shifts.len()).min(shifts.len());
+
+    std::fs::create_dir_all(dir)?;
+    for (rank, shift) in shifts.iter().take(k).enumerate() {
+        let mut r = pass_base.clone();
+        r.quant.shift = *shift;
+        let path = std::path::Path::new(dir).join(format!(
+            "candidate_pass{}_rank{}_shift{}.k8r",
+            pass_1based,
+            rank + 1,
+            shift
+        ));
+        recipe_file::save_k8r(&path.to_string_lossy(), &r)?;
+    }
+    eprintln!(
+        "exported {} candidate recipe(s) (pass {}): {}",
+        k, pass_1based, dir
+    );
+    Ok(())
+}
+
 fn maybe_dump_best_of_pass(
     args: &TuneArgs,
     pass_1based: usize,
@@ -866,9 +1356,284 @@ fn parse_step_div_list(s: &str) -> anyhow::Result<Vec<i64>> {
     Ok(out)
 }
 
+/// Parse an inclusive `lo:hi:step` range (step > 0).
+fn parse_i16_range(s: &str, flag: &str) -> anyhow::Result<Vec<i16>> {
+    let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+    let [lo, hi, step] = parts.as_slice() else {
+        anyhow::bail!("{flag} expects lo:hi:step (got {s:?})");
+    };
+    let parse = |v: &str| -> anyhow::Result<i16> {
+        v.parse()
+            .map_err(|_| anyhow::anyhow!("invalid {flag} value: {v}"))
+    };
+    let (lo, hi, step) = (parse(lo)?, parse(hi)?, parse(step)?);
+    if step <= 0 {
+        anyhow::bail!("{flag} step must be > 0 (got {step})");
+    }
+    if lo > hi {
+        anyhow::bail!("{flag} needs lo <= hi (got {lo}:{hi})");
+    }
+    Ok((lo as i32..=hi as i32)
+        .step_by(step as usize)
+        .map(|v| v as i16)
+        .collect())
+}
+
+/// Sweep (g_step, p_scale) for `best`, keeping its shift. Emission fields do not depend on
+/// the RGB params, so the engine runs once and each combination re-colors the same emissions.
+/// Returns the best RgbRecipe plus report lines (with the top-5 scoreboard).
+fn tune_rgb_params(
+    args: &TuneArgs,
+    best: &Recipe,
+    plain: &[u8],
+) -> anyhow::Result<(RgbRecipe, Vec<String>)> {
+    let steps = parse_i16_range(&args.rgb_step_range, "--rgb-step-range")?;
+    let scales = parse_i16_range(&args.rgb_scale_range, "--rgb-scale-range")?;
+
+    let emissions = plain.len().div_ceil(6) as u64;
+    let mut e = Engine::new(best.clone())?;
+    let fields = e.run_emissions_with_fields(emissions, args.per_max_ticks);
+    if (fields.len() as u64) < emissions {
+        anyhow::bail!(
+            "--tune-rgb-params: only {} of {} emissions within per_max_ticks={}",
+            fields.len(),
+            emissions,
+            args.per_max_ticks
+        );
+    }
+
+    // Same spread as `sim --emit rgbpair --rgb-from-field`.
+    let spread = (best.quant.max - best.quant.min).abs().max(1);
+
+    // The v5 layout has a fixed size, so recipe_bytes is the same for every combination.
+    let mut sized = best.clone();
+    sized.version = sized.version.max(recipe_format::FORMAT_VERSION_RGB);
+    let recipe_bytes = recipe_format::encode(&sized).len();
+
+    eprintln!(
+        "--- tune rgb --- g_step={} p_scale={} combinations={} emissions={}",
+        args.rgb_step_range,
+        args.rgb_scale_range,
+        steps.len() * scales.len(),
+        emissions
+    );
+
+    // (g_step, p_scale, zstd_bytes, effective_bytes)
+    let mut rows: Vec<(i16, i16, usize, usize)> = Vec::with_capacity(steps.len() * scales.len());
+    for &g_step in &steps {
+        for &p_scale in &scales {
+            let cfg = RgbRecipe {
+                g_step,
+                p_scale,
+                ..best.rgb.clone()
+            };
+            let residual: Vec<u8> = fields
+                .iter()
+                .enumerate()
+                .flat_map(|(i, (_t, ef))| {
+                    emit_rgbpair_from_fields(&cfg, i as u64, ef.clamped_a, ef.clamped_c, spread)
+                        .to_bytes()
+                })
+                .zip(plain.iter())
+                .map(|(k, &b)| b ^ k)
+                .collect();
+            let z = zstd_compress_len(&residual, args.zstd_level);
+            rows.push((g_step, p_scale, z, recipe_bytes.saturating_add(z)));
+        }
+    }
+    rows.sort_by_key(|&(g, p, _, eff)| (eff, g, p));
+
+    let (g_step, p_scale, _, eff) = rows[0];
+    eprintln!(
+        "best rgb: g_step={} p_scale={} effective_bytes={}",
+        g_step, p_scale, eff
+    );
+
+    let mut lines = vec![
+        format!("rgb_step_range = {}", args.rgb_step_range),
+        format!("rgb_scale_range = {}", args.rgb_scale_range),
+        format!("rgb_combinations = {}", rows.len()),
+        format!("best_rgb_g_step = {}", g_step),
+        format!("best_rgb_p_scale = {}", p_scale),
+    ];
+    for (rank, (g, p, z, eff)) in rows.iter().take(5).enumerate() {
+        lines.push(format!(
+            "#{:>2} g_step={} p_scale={} effective_bytes={} (recipe_bytes={} + zstd_bytes={})",
+            rank + 1,
+            g,
+            p,
+            eff,
+            recipe_bytes,
+            z
+        ));
+    }
+
+    Ok((
+        RgbRecipe {
+            g_step,
+            p_scale,
+            ..best.rgb.clone()
+        },
+        lines,
+    ))
+}
+
+/// Sweep `quant_gamma` over TUNE_GAMMAS for `best`, keeping its shift, by effective_bytes
+/// of the keystream residual. Gamma 1.0 is scored as the linear quantizer (no v7 block),
+/// so a curve has to pay for its extra recipe bytes; ties go to the gentler curve.
+/// Candidates with a dead keystream are skipped. Returns the best gamma (None = linear)
+/// plus report lines.
+fn tune_quant_gamma(
+    args: &TuneArgs,
+    best: &Recipe,
+    plain: &[u8],
+) -> anyhow::Result<(Option<f64>, Vec<String>)> {
+    eprintln!(
+        "--- tune gamma --- candidates={:?} bytes={}",
+        TUNE_GAMMAS,
+        plain.len()
+    );
+
+    // (gamma, recipe_bytes, zstd_bytes, effective_bytes)
+    let mut rows: Vec<(f64, usize, usize, usize)> = Vec::with_capacity(TUNE_GAMMAS.len());
+    let mut dead: Vec<f64> = Vec::new();
+    for &gamma in &TUNE_GAMMAS {
+        let mut cand = best.clone();
+        if gamma == 1.0 {
+            cand.quant_gamma = None;
+        } else {
+            cand.quant_gamma = Some(gamma);
+            cand.version = cand.version.max(recipe_format::FORMAT_VERSION_QUANT_GAMMA);
+        }
+
+        let mut e = Engine::new(cand.clone())?;
+        let model = ark::keystream_bytes(&mut e, plain.len(), args.per_max_ticks)?;
+        if keystream_is_dead(&byte_summary(&model)) {
+            dead.push(gamma);
+            continue;
+        }
+        let residual: Vec<u8> = model.iter().zip(plain).map(|(k, b)| b ^ k).collect();
+        let z = zstd_compress_len(&residual, args.zstd_level);
+        let recipe_bytes = recipe_format::encode(&cand).len();
+        rows.push((gamma, recipe_bytes, z, recipe_bytes.saturating_add(z)));
+    }
+    if rows.is_empty() {
+        anyhow::bail!("--tune-gamma: every candidate produced a dead keystream");
+    }
+    rows.sort_by(|a, b| {
+        a.3.cmp(&b.3)
+            .then((a.0 - 1.0).abs().total_cmp(&(b.0 - 1.0).abs()))
+    });
+
+    let (gamma, _, _, eff) = rows[0];
+    eprintln!("best gamma: {} effective_bytes={}", gamma, eff);
+
+    let mut lines = vec![
+        format!("gamma_candidates = {:?}", TUNE_GAMMAS),
+        format!("best_quant_gamma = {}", gamma),
+    ];
+    if !dead.is_empty() {
+        lines.push(format!("dead_keystream_gammas = {:?}", dead));
+    }
+    for (rank, (g, rb, z, eff)) in rows.iter().enumerate() {
+        lines.push(format!(
+            "#{:>2} gamma={} effective_bytes={} (recipe_bytes={} + zstd_bytes={})",
+            rank + 1,
+            g,
+            eff,
+            rb,
+            z
+        ));
+    }
+
+    Ok(((gamma != 1.0).then_some(gamma), lines))
+}
+
 type TokenRows = Vec<(i64, Metrics, String)>;
 type ResidRows = Vec<(i64, ResidualMetrics, String)>;
 
+/// Every pass's full ranking as `[[pass]]` tables for --emit-tune-recipe.
+fn pass_tables(
+    args: &TuneArgs,
+    passes: &[(Option<i64>, Option<TokenRows>, Option<ResidRows>)],
+) -> Vec<toml::Table> {
+    use toml::Value;
+
+    let mut out = Vec::with_capacity(passes.len());
+    for (pass_idx, (div_opt, rows_token_opt, rows_resid_opt)) in passes.iter().enumerate() {
+        let mut t = toml::Table::new();
+        t.insert("pass".into(), Value::Integer(pass_idx as i64 + 1));
+        if let Some(div) = div_opt {
+            t.insert("step_div".into(), Value::Integer(*div));
+        }
+
+        let mut candidates = Vec::new();
+        if let Some(rows) = rows_token_opt.as_ref() {
+            t.insert("ranking".into(), Value::String("token_metrics".into()));
+            for (rank, (shift, m, rid)) in rows.iter().enumerate() {
+                let mut c = toml::Table::new();
+                c.insert("rank".into(), Value::Integer(rank as i64 + 1));
+                c.insert("shift".into(), Value::Integer(*shift));
+                c.insert("recipe_id".into(), Value::String(rid.clone()));
+                c.insert("entropy_byte".into(), Value::Float(m.entropy_byte));
+                c.insert("distinct_bytes".into(), Value::Integer(m.distinct_bytes as i64));
+                c.insert("peak_nibble".into(), Value::Integer(m.peak_nibble as i64));
+                c.insert("ticks".into(), Value::Integer(m.ticks as i64));
+                c.insert("tpe_stddev".into(), Value::Float(m.tpe.stddev()));
+                candidates.push(Value::Table(c));
+            }
+        }
+        if let Some(rows) = rows_resid_opt.as_ref() {
+            let ranking = if args.rank_by_effective_zstd {
+                "effective_zstd"
+            } else {
+                "residual_metrics"
+            };
+            t.insert("ranking".into(), Value::String(ranking.into()));
+            for (rank, (shift, m, rid)) in rows.iter().enumerate() {
+                let mut c = toml::Table::new();
+                c.insert("rank".into(), Value::Integer(rank as i64 + 1));
+                c.insert("shift".into(), Value::Integer(*shift));
+                c.insert("recipe_id".into(), Value::String(rid.clone()));
+                c.insert("effective_bytes".into(), Value::Integer(m.effective_bytes as i64));
+                c.insert("recipe_bytes".into(), Value::Integer(m.recipe_bytes as i64));
+                c.insert("zstd_bytes".into(), Value::Integer(m.zstd_bytes as i64));
+                c.insert("entropy_byte".into(), Value::Float(m.entropy_byte));
+                c.insert("distinct_bytes".into(), Value::Integer(m.distinct_bytes as i64));
+                c.insert("zero_rate".into(), Value::Float(m.zero_rate));
+                c.insert("model_entropy_byte".into(), Value::Float(m.model_entropy_byte));
+                c.insert("ticks".into(), Value::Integer(m.ticks as i64));
+                c.insert("tpe_stddev".into(), Value::Float(m.tpe.stddev()));
+                candidates.push(Value::Table(c));
+            }
+        }
+        t.insert("candidate".into(), Value::Array(candidates));
+        out.push(t);
+    }
+    out
+}
+
+/// Why --per-max-ticks is what it is: the tick budget the candidate needs for
+/// --per-emissions at the recipe's expected emission period range.
+fn log_period_budget(args: &TuneArgs, recipe: &Recipe) {
+    let Ok(e) = Engine::new(recipe.clone()) else {
+        return;
+    };
+    let (lo, hi) = e.expected_emission_period_range();
+    eprintln!(
+        "period_range={}..{} ticks per_emissions={} needs {}..{} ticks; per_max_ticks={}",
+        lo,
+        hi,
+        args.per_emissions,
+        args.per_emissions.saturating_mul(lo),
+        args.per_emissions.saturating_mul(hi),
+        args.per_max_ticks
+    );
+    if args.per_max_ticks < args.per_emissions.saturating_mul(lo) {
+        eprintln!("WARN: per_max_ticks is below the lower bound; candidates will be scored on short streams");
+    }
+}
+
 fn tune_shift_multipass(
     args: &TuneArgs,
     base_recipe: Recipe,
@@ -900,15 +1665,20 @@ fn tune_shift_multipass(
 
     let mut current_recipe = base_recipe.clone();
     let mut per_pass_rows: Vec<(Option<i64>, Option<TokenRows>, Option<ResidRows>)> = Vec::new();
+    // Summed over every candidate engine; per-pass figures are deltas against a
+    // checkpoint taken when the pass starts.
+    let mut totals = EngineStats::default();
 
     if let Some(divs) = pass_divs {
         for (pass_idx, div) in divs.into_iter().enumerate() {
             let pass_1based = pass_idx + 1;
+            let pass_start = totals.checkpoint();
             let step = (width / div).max(1);
             eprintln!(
                 "pass {}/? : derived step = width/{} = {}",
                 pass_1based, div, step
             );
+            log_period_budget(args, &current_recipe);
 
             let (
                 best_recipe,
@@ -923,8 +1693,17 @@ fn tune_shift_multipass(
                 Some(div),
                 Some(step),
                 fit_plain,
+                &mut totals,
             )?;
+            log_pass_stats(pass_1based, &totals.since(&pass_start));
 
+            maybe_export_candidates(
+                args,
+                pass_1based,
+                &current_recipe,
+                rows_token_opt.as_ref(),
+                rows_resid_opt.as_ref(),
+            )?;
             per_pass_rows.push((Some(div), rows_token_opt, rows_resid_opt));
 
             if let Some(plain) = fit_plain {
@@ -940,10 +1719,26 @@ fn tune_shift_multipass(
     } else if use_explicit_step {
         let default_step: i64 = (width / 32).max(1);
         let step: i64 = args.step.unwrap_or(default_step);
+        log_period_budget(args, &current_recipe);
 
         let (best_recipe, _best_shift, best_token_m, best_resid_m, rows_token_opt, rows_resid_opt) =
-            tune_shift_once(args, current_recipe.clone(), None, Some(step), fit_plain)?;
-
+            tune_shift_once(
+                args,
+                current_recipe.clone(),
+                None,
+                Some(step),
+                fit_plain,
+                &mut totals,
+            )?;
+        log_pass_stats(1, &totals);
+
+        maybe_export_candidates(
+            args,
+            1,
+            &current_recipe,
+            rows_token_opt.as_ref(),
+            rows_resid_opt.as_ref(),
+        )?;
         per_pass_rows.push((None, rows_token_opt, rows_resid_opt));
 
         if let Some(plain) = fit_plain {
@@ -966,7 +1761,7 @@ fn tune_shift_multipass(
             anyhow::bail!("internal: residual mode but no fit_plain");
         };
         let mut e = Engine::new(current_recipe.clone())?;
-        let used = ark::keystream_bytes(&mut e, plain.len(), args.per_max_ticks)?;
+        let (used, tpe) = keystream_bytes_with_tpe(&mut e, plain.len(), args.per_max_ticks)?;
         let model_sum = byte_summary(&used);
 
         let mut residual = plain.to_vec();
@@ -993,6 +1788,7 @@ fn tune_shift_multipass(
             model_distinct_bytes: model_sum.distinct_bytes,
             model_entropy_byte: model_sum.entropy_byte,
             ticks: e.stats.ticks,
+            tpe,
         };
 
         let elapsed_ms = t0.elapsed().as_millis();
@@ -1006,8 +1802,8 @@ fn tune_shift_multipass(
         ))
     } else {
         let mut e = Engine::new(current_recipe.clone())?;
-        let toks = e.run_emissions(args.per_emissions, args.per_max_ticks);
-        let best_m = compute_token_metrics(&toks, e.stats.ticks);
+        let (qs, tpe) = quant_stats_with_tpe(&mut e, args.per_emissions, args.per_max_ticks);
+        let best_m = compute_token_metrics(&qs, e.stats.ticks, tpe);
         let elapsed_ms = t0.elapsed().as_millis();
         Ok((
             current_recipe.clone(),
@@ -1020,12 +1816,171 @@ fn tune_shift_multipass(
     }
 }
 
+/// --cross-validate: run the shift search once per fold on the other folds of `plain`, then keep
+/// the fold winner with the lowest mean held-out effective_bytes. Returns the same tuple as
+/// `tune_shift_multipass` (scored on the whole file) plus report lines.
+#[allow(clippy::type_complexity)]
+fn tune_shift_cross_validated(
+    args: &TuneArgs,
+    base_recipe: Recipe,
+    plain: &[u8],
+) -> anyhow::Result<(
+    (
+        Recipe,
+        i64,
+        Option<Metrics>,
+        Option<ResidualMetrics>,
+        Vec<(Option<i64>, Option<TokenRows>, Option<ResidRows>)>,
+        u128,
+    ),
+    Vec<String>,
+)> {
+    let t0 = Instant::now();
+    let k = args.cv_splits;
+    let fold_len = plain.len() / k;
+    let folds: Vec<std::ops::Range<usize>> = (0..k)
+        .map(|i| i * fold_len..if i + 1 == k { plain.len() } else { (i + 1) * fold_len })
+        .collect();
+
+    // Folds are always ranked by effective_bytes; per-pass dumps only make sense for one search.
+    let mut fold_args = args.clone();
+    fold_args.rank_by_effective_zstd = true;
+    fold_args.dump_residual_pass = None;
+    fold_args.dump_model_pass = None;
+    fold_args.dump_raw_model_pass = None;
+    fold_args.export_all_candidates = None;
+
+    let held_out = |shift: i64, fold: &std::ops::Range<usize>| -> Option<usize> {
+        let mut r = base_recipe.clone();
+        r.quant.shift = shift;
+        effective_bytes_for(&r, &plain[fold.clone()], args.per_max_ticks, args.zstd_level)
+    };
+
+    let mut lines = vec![
+        format!("cv_splits = {}", k),
+        format!("fold_bytes = {} (last fold {})", fold_len, plain.len() - (k - 1) * fold_len),
+    ];
+
+    // (training winner, its score on the fold held out from that training run)
+    let mut winners: Vec<(i64, Option<usize>)> = Vec::with_capacity(k);
+    for (i, fold) in folds.iter().enumerate() {
+        eprintln!("--- cv fold {}/{} held_out={}..{} ---", i + 1, k, fold.start, fold.end);
+        let train = [&plain[..fold.start], &plain[fold.end..]].concat();
+        let (_r, shift, _tm, _rm, _rows, _ms) =
+            tune_shift_multipass(&fold_args, base_recipe.clone(), Some(&train))?;
+        let score = held_out(shift, fold);
+        let score_txt = score.map_or("dead".to_string(), |b| b.to_string());
+        eprintln!(
+            "cv fold {}/{}: train_bytes={} held_out_bytes={} best_shift={} held_out_effective_bytes={}",
+            i + 1,
+            k,
+            train.len(),
+            fold.len(),
+            shift,
+            score_txt
+        );
+        lines.push(format!(
+            "fold {} held_out={}..{} best_shift={} held_out_effective_bytes={}",
+            i + 1,
+            fold.start,
+            fold.end,
+            shift,
+            score_txt
+        ));
+        winners.push((shift, score));
+    }
+
+    let mut candidates: Vec<i64> = winners.iter().map(|&(s, _)| s).collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    // A candidate is only scored on the folds held out from the run(s) that picked it;
+    // every other fold was part of its training data.
+    // (shift, mean, std) of the best candidate so far.
+    let mut best: Option<(i64, f64, f64)> = None;
+    for &shift in &candidates {
+        let own = winners.iter().filter(|&&(s, _)| s == shift).map(|&(_, score)| score);
+        let Some(scores) = own.collect::<Option<Vec<usize>>>() else {
+            eprintln!("cv candidate shift={} -> dead keystream on a held-out fold (skipped)", shift);
+            lines.push(format!("candidate shift={} dead", shift));
+            continue;
+        };
+        let (mean, std) = mean_std(&scores);
+        eprintln!(
+            "cv candidate shift={} mean_held_out_effective_bytes={:.1} std={:.1} per_fold={:?}",
+            shift, mean, std, scores
+        );
+        lines.push(format!(
+            "candidate shift={} mean_held_out_effective_bytes={:.1} std_held_out_effective_bytes={:.1} per_fold={:?}",
+            shift, mean, std, scores
+        ));
+        if best.is_none_or(|(_, m, _)| mean < m) {
+            best = Some((shift, mean, std));
+        }
+    }
+    let Some((best_shift, mean, std)) = best else {
+        anyhow::bail!("--cross-validate: every fold winner has a dead keystream on some held-out fold");
+    };
+
+    eprintln!(
+        "cv: best_shift={} mean_held_out_effective_bytes={:.1} std_held_out_effective_bytes={:.1}",
+        best_shift, mean, std
+    );
+    lines.push(format!("cv_best_shift = {}", best_shift));
+    lines.push(format!("cv_mean_held_out_effective_bytes = {:.1}", mean));
+    lines.push(format!("cv_std_held_out_effective_bytes = {:.1}", std));
+    // The plain k-fold estimate: each fold scored with its own training winner.
+    let fold_scores: Vec<usize> = winners.iter().filter_map(|&(_, score)| score).collect();
+    let (kfold_mean, kfold_std) = mean_std(&fold_scores);
+    lines.push(format!("cv_kfold_mean_held_out_effective_bytes = {:.1}", kfold_mean));
+    lines.push(format!("cv_kfold_std_held_out_effective_bytes = {:.1}", kfold_std));
+
+    // Score the winner on the whole file so the usual best_* report lines stay meaningful.
+    let mut r = base_recipe;
+    r.quant.shift = best_shift;
+    fold_args.candidates = 1;
+    let (best_recipe, _shift, _tm, best_m, _trows, rows) =
+        tune_shift_once(&fold_args, r, None, None, Some(plain), &mut EngineStats::default())?;
+
+    Ok((
+        (
+            best_recipe,
+            best_shift,
+            None,
+            best_m,
+            vec![(None, None, rows)],
+            t0.elapsed().as_millis(),
+        ),
+        lines,
+    ))
+}
+
+fn mean_std(xs: &[usize]) -> (f64, f64) {
+    let n = xs.len().max(1) as f64;
+    let mean = xs.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let var = xs.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / n;
+    (mean, var.sqrt())
+}
+
+fn log_pass_stats(pass_1based: usize, d: &EngineStats) {
+    eprintln!(
+        "pass {} engine_stats: ticks={} alignments={} emissions={} emissions_per_mticks={:.1}",
+        pass_1based,
+        d.ticks,
+        d.alignments,
+        d.emissions,
+        d.emission_density_per_million_ticks()
+    );
+}
+
+/// Every candidate engine's counters are added into `totals`.
 fn tune_shift_once(
     args: &TuneArgs,
     base_recipe: Recipe,
     pass_div: Option<i64>,
     step_override: Option<i64>,
     fit_plain: Option<&[u8]>,
+    totals: &mut EngineStats,
 ) -> anyhow::Result<(
     Recipe,
     i64,
@@ -1045,6 +2000,7 @@ fn tune_shift_once(
     let step: i64 = step_override.unwrap_or(default_step);
 
     let base_shift: i64 = base_recipe.quant.shift;
+    let mut ids = k8dnz_core::recipe::format::RecipeIdPatcher::new(&base_recipe);
 
     if let Some(div) = pass_div {
         eprintln!(
@@ -1086,12 +2042,21 @@ fn tune_shift_once(
             let mut r = base_recipe.clone();
             r.quant.shift = shift;
 
-            let rid = k8dnz_core::recipe::format::recipe_id_hex(&r);
+            let rid = ids.id_hex(
+                r.seed,
+                r.quant.min,
+                r.quant.max,
+                shift,
+                r.field_clamp.min,
+                r.field_clamp.max,
+            );
 
             let start = Instant::now();
             let mut e = Engine::new(r.clone())?;
 
-            let used = match ark::keystream_bytes(&mut e, plain.len(), args.per_max_ticks) {
+            let run = keystream_bytes_with_tpe(&mut e, plain.len(), args.per_max_ticks);
+            totals.accumulate(&e.stats);
+            let (used, tpe) = match run {
                 Ok(v) => v,
                 Err(err) => {
                     eprintln!(
@@ -1117,6 +2082,7 @@ fn tune_shift_once(
                             model_distinct_bytes: 0,
                             model_entropy_byte: 0.0,
                             ticks: e.stats.ticks,
+                            tpe: TpeStats::new(),
                         },
                         rid,
                     ));
@@ -1153,6 +2119,7 @@ fn tune_shift_once(
                         model_distinct_bytes: model_sum.distinct_bytes,
                         model_entropy_byte: model_sum.entropy_byte,
                         ticks: e.stats.ticks,
+                        tpe,
                     },
                     rid,
                 ));
@@ -1183,10 +2150,11 @@ fn tune_shift_once(
                 model_distinct_bytes: model_sum.distinct_bytes,
                 model_entropy_byte: model_sum.entropy_byte,
                 ticks: e.stats.ticks,
+                tpe,
             };
 
             eprintln!(
-                "cand {}/{} shift={} recipe_id={} -> residual: effective_bytes={} (recipe={} + zstd={} @lvl {}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={} elapsed_ms={}",
+                "cand {}/{} shift={} recipe_id={} -> residual: effective_bytes={} (recipe={} + zstd={} @lvl {}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={} mean_tpe={:.1} tpe_stddev={:.1} elapsed_ms={}",
                 idx + 1,
                 n,
                 shift,
@@ -1203,7 +2171,7 @@ fn tune_shift_once(
                 m.entropy_byte,
                 m.distinct_bytes,
                 m.peak_byte,
-                m.ticks,
+                m.ticks, m.tpe.mean(), m.tpe.stddev(),
                 start.elapsed().as_millis()
             );
 
@@ -1216,6 +2184,7 @@ fn tune_shift_once(
                     .cmp(&b.1.effective_bytes)
                     .then_with(|| a.1.zstd_bytes.cmp(&b.1.zstd_bytes))
                     .then_with(|| a.1.recipe_bytes.cmp(&b.1.recipe_bytes))
+                    .then_with(|| tpe_stddev_cmp(&a.1.tpe, &b.1.tpe))
                     .then_with(|| a.0.cmp(&b.0))
             });
 
@@ -1225,7 +2194,7 @@ fn tune_shift_once(
             );
             for (rank, (shift, m, rid)) in rows.iter().take(9).enumerate() {
                 eprintln!(
-                    "#{:>2} shift={} recipe_id={} effective_bytes={} (recipe={} + zstd={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={}",
+                    "#{:>2} shift={} recipe_id={} effective_bytes={} (recipe={} + zstd={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
                     rank + 1,
                     shift,
                     rid,
@@ -1239,7 +2208,7 @@ fn tune_shift_once(
                     m.entropy_byte,
                     m.distinct_bytes,
                     m.peak_byte,
-                    m.ticks
+                    m.ticks, m.tpe.mean(), m.tpe.stddev()
                 );
             }
         } else {
@@ -1259,13 +2228,14 @@ fn tune_shift_once(
                     })
                     .then_with(|| a.1.distinct_bytes.cmp(&b.1.distinct_bytes))
                     .then_with(|| b.1.peak_byte.cmp(&a.1.peak_byte))
+                    .then_with(|| tpe_stddev_cmp(&a.1.tpe, &b.1.tpe))
                     .then_with(|| a.0.cmp(&b.0))
             });
 
             eprintln!("--- tune ranking (residual proxy top 9) ---");
             for (rank, (shift, m, rid)) in rows.iter().take(9).enumerate() {
                 eprintln!(
-                    "#{:>2} shift={} recipe_id={} effective_bytes={} (recipe={} + zstd={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={}",
+                    "#{:>2} shift={} recipe_id={} effective_bytes={} (recipe={} + zstd={}) model_distinct={}/256 model_entropy={:.4} top16_mass={:.4} zero_rate={:.4} printable_rate={:.4} entropy={:.4} distinct={}/256 peak_byte={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
                     rank + 1,
                     shift,
                     rid,
@@ -1280,7 +2250,7 @@ fn tune_shift_once(
                     m.entropy_byte,
                     m.distinct_bytes,
                     m.peak_byte,
-                    m.ticks
+                    m.ticks, m.tpe.mean(), m.tpe.stddev()
                 );
             }
         }
@@ -1319,15 +2289,23 @@ fn tune_shift_once(
             let mut r = base_recipe.clone();
             r.quant.shift = shift;
 
-            let rid = k8dnz_core::recipe::format::recipe_id_hex(&r);
+            let rid = ids.id_hex(
+                r.seed,
+                r.quant.min,
+                r.quant.max,
+                shift,
+                r.field_clamp.min,
+                r.field_clamp.max,
+            );
 
             let start = Instant::now();
             let mut e = Engine::new(r.clone())?;
-            let toks = e.run_emissions(args.per_emissions, args.per_max_ticks);
-            let m = compute_token_metrics(&toks, e.stats.ticks);
+            let (qs, tpe) = quant_stats_with_tpe(&mut e, args.per_emissions, args.per_max_ticks);
+            totals.accumulate(&e.stats);
+            let m = compute_token_metrics(&qs, e.stats.ticks, tpe);
 
             eprintln!(
-                "cand {}/{} shift={} recipe_id={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={} elapsed_ms={}",
+                "cand {}/{} shift={} recipe_id={} -> distinct={}/256 entropy_byte={:.4} peak_nibble={} ticks={} mean_tpe={:.1} tpe_stddev={:.1} elapsed_ms={}",
                 idx + 1,
                 n,
                 shift,
@@ -1335,7 +2313,7 @@ fn tune_shift_once(
                 m.distinct_bytes,
                 m.entropy_byte,
                 m.peak_nibble,
-                m.ticks,
+                m.ticks, m.tpe.mean(), m.tpe.stddev(),
                 start.elapsed().as_millis()
             );
 
@@ -1348,19 +2326,20 @@ fn tune_shift_once(
                 .unwrap_or(std::cmp::Ordering::Equal)
                 .then_with(|| b.1.distinct_bytes.cmp(&a.1.distinct_bytes))
                 .then_with(|| a.1.peak_nibble.cmp(&b.1.peak_nibble))
+                .then_with(|| tpe_stddev_cmp(&a.1.tpe, &b.1.tpe))
         });
 
         eprintln!("--- tune ranking (token top 9) ---");
         for (rank, (shift, m, rid)) in rows.iter().take(9).enumerate() {
             eprintln!(
-                "#{:>2} shift={} recipe_id={} entropy_byte={:.4} distinct={}/256 peak_nibble={} ticks={}",
+                "#{:>2} shift={} recipe_id={} entropy_byte={:.4} distinct={}/256 peak_nibble={} ticks={} mean_tpe={:.1} tpe_stddev={:.1}",
                 rank + 1,
                 shift,
                 rid,
                 m.entropy_byte,
                 m.distinct_bytes,
                 m.peak_nibble,
-                m.ticks
+                m.ticks, m.tpe.mean(), m.tpe.stddev()
             );
         }
 
@@ -1379,23 +2358,143 @@ fn tune_shift_once(
     }
 }
 
-fn compute_token_metrics(toks: &[PairToken], ticks: u64) -> Metrics {
-    let mut ha = [0u64; 16];
-    let mut hb = [0u64; 16];
-    let mut hbyte = [0u64; 256];
+/// Fine effective-bytes sweep of +/-1% of quant width around `best`'s shift.
+/// Writes `delta,effective_bytes` CSV to `csv_path` and returns report lines.
+fn run_sensitivity(
+    args: &TuneArgs,
+    best: &Recipe,
+    plain: &[u8],
+    csv_path: &str,
+) -> anyhow::Result<Vec<String>> {
+    let width: i64 = best.quant.max - best.quant.min;
+    let mut n = args.sensitivity_points.max(3);
+    if n.is_multiple_of(2) {
+        n += 1;
+    }
+    let half = (n / 2) as i64;
+    let span = (width / 100).max(1);
+    let step = (span / half).max(1);
+
+    eprintln!(
+        "--- sensitivity --- center_shift={} span=+/-{} step={} points={}",
+        best.quant.shift, span, step, n
+    );
+
+    let mut sweep_args = args.clone();
+    sweep_args.candidates = n;
+    sweep_args.rank_by_effective_zstd = true;
+
+    let (_r, _shift, _tm, _rm, _trows, rows_opt) =
+        tune_shift_once(
+            &sweep_args,
+            best.clone(),
+            None,
+            Some(step),
+            Some(plain),
+            &mut EngineStats::default(),
+        )?;
+    let mut rows = rows_opt.unwrap_or_default();
+    rows.sort_by_key(|(shift, _, _)| *shift);
+
+    let center = best.quant.shift;
+    let eff_at = |delta: i64| -> Option<usize> {
+        rows.iter()
+            .find(|(shift, _, _)| *shift - center == delta)
+            .map(|(_, m, _)| m.effective_bytes)
+            .filter(|&e| e != usize::MAX)
+    };
 
-    for t in toks {
-        let a = (t.a & 0x0F) as usize;
-        let b = (t.b & 0x0F) as usize;
-        ha[a] += 1;
-        hb[b] += 1;
-        let byte = (((a as u8) & 0x0F) << 4) | ((b as u8) & 0x0F);
-        hbyte[byte as usize] += 1;
+    let mut csv = String::from("delta,effective_bytes\n");
+    for (shift, m, _rid) in &rows {
+        if m.effective_bytes == usize::MAX {
+            csv.push_str(&format!("{},dead\n", shift - center));
+        } else {
+            csv.push_str(&format!("{},{}\n", shift - center, m.effective_bytes));
+        }
     }
+    std::fs::write(csv_path, csv)?;
+    eprintln!("wrote sensitivity csv: {} ({} rows)", csv_path, rows.len());
+
+    let mut lines = vec![
+        format!("csv = {}", csv_path),
+        format!("center_shift = {} span = +/-{} step = {} points = {}", center, span, step, rows.len()),
+    ];
 
-    let total = toks.len() as u64;
-    let distinct_bytes = hbyte.iter().filter(|&&c| c > 0).count();
-    let entropy_byte = entropy_bits_256(&hbyte, total);
+    if let Some((lo, hi)) = rows
+        .iter()
+        .map(|(_, m, _)| m.effective_bytes)
+        .filter(|&e| e != usize::MAX)
+        .fold(None, |acc: Option<(usize, usize)>, e| match acc {
+            None => Some((e, e)),
+            Some((lo, hi)) => Some((lo.min(e), hi.max(e))),
+        })
+    {
+        lines.push(format!("effective_bytes_min = {} max = {}", lo, hi));
+    }
+
+    // Central second difference: f(+h) - 2 f(0) + f(-h), per grid step and per shift unit^2.
+    match (eff_at(-step), eff_at(0), eff_at(step)) {
+        (Some(fm), Some(f0), Some(fp)) => {
+            let d2 = fp as f64 - 2.0 * f0 as f64 + fm as f64;
+            let d2_per_unit = d2 / (step as f64 * step as f64);
+            let rel = d2 / (f0.max(1) as f64);
+            let class = if rel >= SENSITIVITY_STEEP_REL { "steep" } else { "shallow" };
+            eprintln!(
+                "sensitivity: f(-h)={} f(0)={} f(+h)={} d2_per_step={:.1} d2_per_unit={:.6e} rel={:.5} => {}",
+                fm, f0, fp, d2, d2_per_unit, rel, class
+            );
+            lines.push(format!("second_diff_per_step = {:.1} bytes", d2));
+            lines.push(format!("second_deriv_per_shift_unit2 = {:.6e}", d2_per_unit));
+            lines.push(format!("second_diff_rel = {:.5}", rel));
+            lines.push(format!("minimum = {}", class));
+        }
+        _ => {
+            // A dead/failed neighbor is a cliff: treat as steep.
+            eprintln!("sensitivity: neighbor of best shift failed or dead => steep");
+            lines.push("minimum = steep (dead or failed neighbor)".to_string());
+        }
+    }
+
+    Ok(lines)
+}
+
+/// `run_quant_stats` plus the emission-spacing distribution of the same run; both are
+/// folded per emission, so no token buffer is kept.
+fn quant_stats_with_tpe(e: &mut Engine, k: u64, max_ticks: u64) -> (QuantStats, TpeStats) {
+    let mut qs = QuantStats::new();
+    let mut tpe = TpeStats::new();
+    let mut last = e.stats.ticks;
+    while qs.count() < k && e.stats.ticks < max_ticks {
+        if let Some(tok) = e.step() {
+            tpe.update(e.stats.ticks - last);
+            last = e.stats.ticks;
+            qs.update(tok.pack_byte());
+        }
+    }
+    (qs, tpe)
+}
+
+/// `ark::keystream_bytes`, one byte at a time so each emission's gap is observed.
+fn keystream_bytes_with_tpe(
+    e: &mut Engine,
+    n: usize,
+    max_ticks: u64,
+) -> anyhow::Result<(Vec<u8>, TpeStats)> {
+    let mut gen = ark::KeystreamGen::new(e);
+    let mut out = Vec::with_capacity(n);
+    let mut tpe = TpeStats::new();
+    let mut last = e.stats.ticks;
+    for _ in 0..n {
+        gen.fill(e, 1, max_ticks, &mut out, None)?;
+        tpe.update(e.stats.ticks - last);
+        last = e.stats.ticks;
+    }
+    Ok((out, tpe))
+}
+
+fn compute_token_metrics(qs: &QuantStats, ticks: u64, tpe: TpeStats) -> Metrics {
+    let (ha, hb) = qs.nibble_histograms();
+    let entropy_byte = entropy_bits_256(qs.histogram(), qs.count());
 
     let peak_nibble = ha
         .iter()
@@ -1405,31 +2504,29 @@ fn compute_token_metrics(toks: &[PairToken], ticks: u64) -> Metrics {
         .max(hb.iter().copied().max().unwrap_or(0));
 
     Metrics {
-        distinct_bytes,
+        distinct_bytes: qs.distinct(),
         entropy_byte,
         peak_nibble,
         ticks,
+        tpe,
     }
 }
 
+/// Last-resort tie-break: steadier emission rate first.
+fn tpe_stddev_cmp(a: &TpeStats, b: &TpeStats) -> std::cmp::Ordering {
+    a.stddev()
+        .partial_cmp(&b.stddev())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
 fn residual_metrics(bytes: &[u8]) -> ByteSummary {
     byte_summary(bytes)
 }
 
 fn byte_summary(bytes: &[u8]) -> ByteSummary {
-    let mut h = [0u64; 256];
-    let mut zeros: u64 = 0;
-    let mut printable: u64 = 0;
-
-    for &b in bytes {
-        h[b as usize] += 1;
-        if b == 0 {
-            zeros += 1;
-        }
-        if (0x20..=0x7E).contains(&b) {
-            printable += 1;
-        }
-    }
+    let h = PackedByte::frequency_table(bytes.iter().map(|&b| PackedByte(b)));
+    let zeros = h[0];
+    let printable: u64 = h[0x20..=0x7E].iter().sum();
 
     let total_u64 = bytes.len() as u64;
     let total_f = bytes.len() as f64;
@@ -1515,4 +2612,29 @@ mod tests {
         };
         assert!(!keystream_is_dead(&ok));
     }
+
+    #[test]
+    fn warm_start_reads_report_fields() {
+        let text = "--- k8dnz tune report ---\nbase_recipe_id = abc123\nbase_quant = min=0 max=1024 shift=0\n\nbest_shift = -77\nbest_recipe_id = def456\nfinal_step = 40\n\n--- pass 1 ---\nstep_div = 32\n";
+        let ws = parse_warm_start(text, 1024).unwrap();
+        assert_eq!(
+            ws,
+            WarmStart {
+                base_recipe_id: "abc123".to_string(),
+                best_shift: -77,
+                final_step: 40,
+            }
+        );
+    }
+
+    #[test]
+    fn warm_start_falls_back_to_last_step_div() {
+        let text = "base_recipe_id = x\nbest_shift = 5\n--- pass 1 ---\nstep_div = 32\n--- pass 2 ---\nstep_div = 256\n";
+        assert_eq!(parse_warm_start(text, 1024).unwrap().final_step, 4);
+
+        let explicit = "base_recipe_id = x\nbest_shift = 5\nstep_div = (explicit step)\n";
+        assert_eq!(parse_warm_start(explicit, 1024).unwrap().final_step, 32);
+
+        assert!(parse_warm_start("best_shift = 5\n", 1024).is_err());
+    }
 }
@@ -0,0 +1,480 @@
+// crates/k8dnz-cli/src/cmd/tune_genetic.rs
+//
+// tune --genetic: evolutionary search over every numeric recipe parameter.
+//
+// - chromosome: orbit phases/speeds, epsilon, lockstep v_l/delta/t_step, quant min/max/shift,
+//   field clamp min/max, and each field wave's phase + amp (seed is left alone)
+// - bounds are relative to the base recipe (speeds x0.5..x2, quant/clamp +/- half a width,
+//   phases over the full turn), so the search stays near a recipe that is known to emit
+// - fitness: effective_bytes against --fit-in (lower is better); recipes that fail to
+//   validate, run out of ticks, or produce a dead keystream rank last
+// - selection: tournament; crossover: uniform per gene; mutation: per-gene Gaussian noise
+// - diversity: mean pairwise Hamming distance of the 16-bit normalized chromosomes; below
+//   DIVERSITY_FLOOR the mutation rate doubles until the population spreads out again
+//
+// The PRNG is keyed by the base recipe's seed, so a run is reproducible.
+
+use k8dnz_core::fixed::turn32::Turn32;
+use k8dnz_core::recipe::format::recipe_id_hex;
+use k8dnz_core::signal::bitpack::hamming_distance_u8;
+use k8dnz_core::Recipe;
+
+use super::tune::{effective_bytes_for, TuneArgs};
+use crate::io::recipe_file;
+
+use std::time::Instant;
+
+/// Mean normalized Hamming diversity below which the mutation rate is raised.
+const DIVERSITY_FLOOR: f64 = 0.05;
+/// Mutation noise std-dev as a fraction of each gene's range.
+const MUTATION_SIGMA: f64 = 0.05;
+/// Best individuals copied unchanged into the next generation.
+const ELITES: usize = 1;
+
+const U32_MAX: i64 = u32::MAX as i64;
+
+#[derive(Clone, Copy, Debug)]
+enum GeneKey {
+    PhiA0,
+    PhiC0,
+    VA,
+    VC,
+    Epsilon,
+    VL,
+    Delta,
+    TStep,
+    QuantMin,
+    QuantMax,
+    QuantShift,
+    ClampMin,
+    ClampMax,
+    WavePhase(usize),
+    WaveAmp(usize),
+}
+
+impl GeneKey {
+    fn get(self, r: &Recipe) -> i64 {
+        match self {
+            GeneKey::PhiA0 => r.free.phi_a0.0 as i64,
+            GeneKey::PhiC0 => r.free.phi_c0.0 as i64,
+            GeneKey::VA => r.free.v_a.0 as i64,
+            GeneKey::VC => r.free.v_c.0 as i64,
+            GeneKey::Epsilon => r.free.epsilon.0 as i64,
+            GeneKey::VL => r.lock.v_l.0 as i64,
+            GeneKey::Delta => r.lock.delta.0 as i64,
+            GeneKey::TStep => r.lock.t_step as i64,
+            GeneKey::QuantMin => r.quant.min,
+            GeneKey::QuantMax => r.quant.max,
+            GeneKey::QuantShift => r.quant.shift,
+            GeneKey::ClampMin => r.field_clamp.min,
+            GeneKey::ClampMax => r.field_clamp.max,
+            GeneKey::WavePhase(i) => r.field.waves[i].phase as i64,
+            GeneKey::WaveAmp(i) => r.field.waves[i].amp as i64,
+        }
+    }
+
+    /// `v` is already inside the gene's bounds, which keep each target type in range.
+    fn set(self, r: &mut Recipe, v: i64) {
+        match self {
+            GeneKey::PhiA0 => r.free.phi_a0 = Turn32(v as u32),
+            GeneKey::PhiC0 => r.free.phi_c0 = Turn32(v as u32),
+            GeneKey::VA => r.free.v_a = Turn32(v as u32),
+            GeneKey::VC => r.free.v_c = Turn32(v as u32),
+            GeneKey::Epsilon => r.free.epsilon = Turn32(v as u32),
+            GeneKey::VL => r.lock.v_l = Turn32(v as u32),
+            GeneKey::Delta => r.lock.delta = Turn32(v as u32),
+            GeneKey::TStep => r.lock.t_step = v as u32,
+            GeneKey::QuantMin => r.quant.min = v,
+            GeneKey::QuantMax => r.quant.max = v,
+            GeneKey::QuantShift => r.quant.shift = v,
+            GeneKey::ClampMin => r.field_clamp.min = v,
+            GeneKey::ClampMax => r.field_clamp.max = v,
+            GeneKey::WavePhase(i) => r.field.waves[i].phase = v as u32,
+            GeneKey::WaveAmp(i) => r.field.waves[i].amp = v as i32,
+        }
+    }
+
+    fn name(self) -> String {
+        match self {
+            GeneKey::PhiA0 => "free.phi_a0".into(),
+            GeneKey::PhiC0 => "free.phi_c0".into(),
+            GeneKey::VA => "free.v_a".into(),
+            GeneKey::VC => "free.v_c".into(),
+            GeneKey::Epsilon => "free.epsilon".into(),
+            GeneKey::VL => "lock.v_l".into(),
+            GeneKey::Delta => "lock.delta".into(),
+            GeneKey::TStep => "lock.t_step".into(),
+            GeneKey::QuantMin => "quant.min".into(),
+            GeneKey::QuantMax => "quant.max".into(),
+            GeneKey::QuantShift => "quant.shift".into(),
+            GeneKey::ClampMin => "field_clamp.min".into(),
+            GeneKey::ClampMax => "field_clamp.max".into(),
+            GeneKey::WavePhase(i) => format!("field.waves[{i}].phase"),
+            GeneKey::WaveAmp(i) => format!("field.waves[{i}].amp"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Gene {
+    key: GeneKey,
+    lo: i64,
+    hi: i64,
+}
+
+impl Gene {
+    fn new(key: GeneKey, lo: i64, hi: i64) -> Self {
+        Self {
+            key,
+            lo,
+            hi: hi.max(lo + 1),
+        }
+    }
+
+    fn span(&self) -> f64 {
+        (self.hi - self.lo) as f64
+    }
+
+    fn clamp(&self, v: i64) -> i64 {
+        v.clamp(self.lo, self.hi)
+    }
+}
+
+/// x0.5..x2 of `v`, kept inside [floor, ceil].
+fn scaled(v: i64, floor: i64, ceil: i64) -> (i64, i64) {
+    let (a, b) = (v / 2, v.saturating_mul(2));
+    (a.min(b).max(floor), a.max(b).min(ceil))
+}
+
+fn genes_for(base: &Recipe) -> Vec<Gene> {
+    let speed = |key: GeneKey, ceil: i64| {
+        let (lo, hi) = scaled(key.get(base), 1, ceil);
+        Gene::new(key, lo, hi)
+    };
+
+    let qw = base.quant.max - base.quant.min;
+    let cw = base.field_clamp.max - base.field_clamp.min;
+
+    let mut genes = vec![
+        Gene::new(GeneKey::PhiA0, 0, U32_MAX),
+        Gene::new(GeneKey::PhiC0, 0, U32_MAX),
+        speed(GeneKey::VA, U32_MAX),
+        speed(GeneKey::VC, U32_MAX),
+        speed(GeneKey::Epsilon, Turn32::HALF.0 as i64 - 1),
+        speed(GeneKey::VL, U32_MAX),
+        speed(GeneKey::Delta, U32_MAX),
+        speed(GeneKey::TStep, U32_MAX),
+        // min/max windows never overlap, so quant.min < quant.max always holds.
+        Gene::new(
+            GeneKey::QuantMin,
+            base.quant.min - qw / 2,
+            base.quant.min + qw / 4,
+        ),
+        Gene::new(
+            GeneKey::QuantMax,
+            base.quant.max - qw / 4,
+            base.quant.max + qw / 2,
+        ),
+        Gene::new(GeneKey::QuantShift, -qw, qw),
+        Gene::new(
+            GeneKey::ClampMin,
+            base.field_clamp.min - cw / 2,
+            base.field_clamp.min + cw / 4,
+        ),
+        Gene::new(
+            GeneKey::ClampMax,
+            base.field_clamp.max - cw / 4,
+            base.field_clamp.max + cw / 2,
+        ),
+    ];
+    for i in 0..base.field.waves.len() {
+        genes.push(Gene::new(GeneKey::WavePhase(i), 0, U32_MAX));
+        let (lo, hi) = scaled(
+            GeneKey::WaveAmp(i).get(base),
+            i32::MIN as i64,
+            i32::MAX as i64,
+        );
+        genes.push(Gene::new(GeneKey::WaveAmp(i), lo, hi));
+    }
+    genes
+}
+
+#[derive(Clone, Debug)]
+struct Individual {
+    chrom: Vec<i64>,
+    /// effective_bytes; None = invalid / dead recipe.
+    fitness: Option<usize>,
+}
+
+impl Individual {
+    fn rank(&self) -> usize {
+        self.fitness.unwrap_or(usize::MAX)
+    }
+}
+
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1).
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Uniform in [lo, hi].
+    fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        let span = (hi as i128 - lo as i128 + 1) as u128;
+        (lo as i128 + (self.next_u64() as u128 % span) as i128) as i64
+    }
+
+    /// Standard normal (Box-Muller).
+    fn gauss(&mut self) -> f64 {
+        let u1 = self.unit().max(f64::MIN_POSITIVE);
+        let u2 = self.unit();
+        (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+    }
+}
+
+struct Ga<'a> {
+    args: &'a TuneArgs,
+    base: &'a Recipe,
+    plain: &'a [u8],
+    genes: Vec<Gene>,
+}
+
+impl Ga<'_> {
+    fn recipe(&self, chrom: &[i64]) -> Recipe {
+        let mut r = self.base.clone();
+        for (g, &v) in self.genes.iter().zip(chrom) {
+            g.key.set(&mut r, v);
+        }
+        r
+    }
+
+    fn evaluate(&self, chrom: Vec<i64>) -> Individual {
+        let fitness = effective_bytes_for(
+            &self.recipe(&chrom),
+            self.plain,
+            self.args.per_max_ticks,
+            self.args.zstd_level,
+        );
+        Individual { chrom, fitness }
+    }
+
+    fn tournament<'p>(&self, pop: &'p [Individual], rng: &mut Rng) -> &'p Individual {
+        let k = self.args.tournament_size.max(1);
+        (0..k)
+            .map(|_| &pop[rng.below(pop.len())])
+            .min_by_key(|ind| ind.rank())
+            .expect("tournament_size >= 1")
+    }
+
+    fn crossover(&self, a: &[i64], b: &[i64], rng: &mut Rng) -> Vec<i64> {
+        a.iter()
+            .zip(b)
+            .map(|(&x, &y)| if rng.next_u64() & 1 == 0 { x } else { y })
+            .collect()
+    }
+
+    fn mutate(&self, chrom: &mut [i64], rate: f64, rng: &mut Rng) {
+        for (v, g) in chrom.iter_mut().zip(&self.genes) {
+            if rng.unit() < rate {
+                let noise = rng.gauss() * MUTATION_SIGMA * g.span();
+                *v = g.clamp(v.saturating_add(noise.round() as i64));
+            }
+        }
+    }
+
+    /// Mean pairwise Hamming distance over 16-bit normalized genes, in [0, 1].
+    fn diversity(&self, pop: &[Individual]) -> f64 {
+        let packed: Vec<Vec<u8>> = pop
+            .iter()
+            .map(|ind| {
+                ind.chrom
+                    .iter()
+                    .zip(&self.genes)
+                    .flat_map(|(&v, g)| {
+                        let q = ((v - g.lo) as f64 / g.span() * 65535.0).round() as u16;
+                        q.to_le_bytes()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut sum = 0u64;
+        let mut pairs = 0u64;
+        for i in 0..packed.len() {
+            for j in (i + 1)..packed.len() {
+                sum += hamming_distance_u8(&packed[i], &packed[j]);
+                pairs += 1;
+            }
+        }
+        if pairs == 0 {
+            return 0.0;
+        }
+        sum as f64 / (pairs as f64 * (self.genes.len() * 16) as f64)
+    }
+}
+
+pub fn run(args: &TuneArgs, base: Recipe, plain: &[u8]) -> anyhow::Result<()> {
+    if args.pop_size < 2 {
+        anyhow::bail!("--pop-size must be >= 2");
+    }
+    if args.generations == 0 {
+        anyhow::bail!("--generations must be >= 1");
+    }
+    if !(0.0..=1.0).contains(&args.mutation_rate) {
+        anyhow::bail!("--mutation-rate must be in [0,1]");
+    }
+
+    let t0 = Instant::now();
+    let base_rid = recipe_id_hex(&base);
+    let ga = Ga {
+        args,
+        base: &base,
+        plain,
+        genes: genes_for(&base),
+    };
+    let mut rng = Rng(base.seed);
+
+    eprintln!("--- tune genetic ---");
+    eprintln!(
+        "base_recipe_id={} genes={} pop_size={} generations={} tournament_size={} mutation_rate={} fit_bytes={}",
+        base_rid,
+        ga.genes.len(),
+        args.pop_size,
+        args.generations,
+        args.tournament_size,
+        args.mutation_rate,
+        plain.len()
+    );
+
+    let mut report_lines: Vec<String> = vec![
+        "--- k8dnz tune genetic report ---".to_string(),
+        format!("base_recipe_id = {}", base_rid),
+        format!("fit_in = {:?}", args.fit_in),
+        format!("zstd_level = {}", args.zstd_level),
+        format!("pop_size = {}", args.pop_size),
+        format!("generations = {}", args.generations),
+        format!("tournament_size = {}", args.tournament_size),
+        format!("mutation_rate = {}", args.mutation_rate),
+        String::new(),
+    ];
+
+    // Generation 1: the base recipe plus uniform samples inside the gene bounds.
+    let base_chrom: Vec<i64> = ga.genes.iter().map(|g| g.clamp(g.key.get(&base))).collect();
+    let mut pop: Vec<Individual> = vec![ga.evaluate(base_chrom)];
+    while pop.len() < args.pop_size {
+        let chrom = ga.genes.iter().map(|g| rng.range(g.lo, g.hi)).collect();
+        pop.push(ga.evaluate(chrom));
+    }
+
+    let mut rate = args.mutation_rate;
+    for gen in 1..=args.generations {
+        pop.sort_by_key(Individual::rank);
+
+        let valid: Vec<usize> = pop.iter().filter_map(|ind| ind.fitness).collect();
+        let mean = if valid.is_empty() {
+            f64::NAN
+        } else {
+            valid.iter().sum::<usize>() as f64 / valid.len() as f64
+        };
+        let diversity = ga.diversity(&pop);
+        let best = pop[0]
+            .fitness
+            .map_or_else(|| "none".to_string(), |b| b.to_string());
+
+        let line = format!(
+            "gen {}/{} best_effective_bytes={} mean_effective_bytes={:.1} valid={}/{} diversity={:.4} mutation_rate={:.3}",
+            gen,
+            args.generations,
+            best,
+            mean,
+            valid.len(),
+            pop.len(),
+            diversity,
+            rate
+        );
+        eprintln!("{line}");
+        report_lines.push(line);
+
+        if gen == args.generations {
+            break;
+        }
+
+        if diversity < DIVERSITY_FLOOR {
+            // At least one mutated gene per child on average, even from --mutation-rate 0.
+            let raised = (rate * 2.0).max(1.0 / ga.genes.len() as f64).min(1.0);
+            if raised > rate {
+                eprintln!(
+                    "WARN: diversity={:.4} < {} (premature convergence); mutation_rate {:.3} -> {:.3}",
+                    diversity, DIVERSITY_FLOOR, rate, raised
+                );
+            }
+            rate = raised;
+        } else {
+            rate = args.mutation_rate;
+        }
+
+        let mut next: Vec<Individual> = pop.iter().take(ELITES).cloned().collect();
+        while next.len() < args.pop_size {
+            let a = ga.tournament(&pop, &mut rng);
+            let b = ga.tournament(&pop, &mut rng);
+            let mut child = ga.crossover(&a.chrom, &b.chrom, &mut rng);
+            ga.mutate(&mut child, rate, &mut rng);
+            next.push(ga.evaluate(child));
+        }
+        pop = next;
+    }
+
+    let best = &pop[0];
+    let Some(best_eff) = best.fitness else {
+        anyhow::bail!("--genetic: no individual produced a usable keystream");
+    };
+    let best_recipe = ga.recipe(&best.chrom);
+    let best_rid = recipe_id_hex(&best_recipe);
+
+    recipe_file::save_k8r(&args.out_recipe, &best_recipe)?;
+    eprintln!(
+        "saved tuned recipe: {} (effective_bytes={} recipe_id={})",
+        args.out_recipe, best_eff, best_rid
+    );
+
+    report_lines.push(String::new());
+    report_lines.push(format!("best_effective_bytes = {}", best_eff));
+    report_lines.push(format!("best_recipe_id = {}", best_rid));
+    for (g, &v) in ga.genes.iter().zip(&best.chrom) {
+        report_lines.push(format!("best {} = {}", g.key.name(), v));
+    }
+
+    let warnings = best_recipe.validate_deep();
+    report_lines.push(format!("validation_warnings = {}", warnings.len()));
+    for w in &warnings {
+        eprintln!("WARN: best recipe: {w}");
+        report_lines.push(format!("validation_warning = {w}"));
+    }
+
+    if let Some(path) = args.report.as_deref() {
+        std::fs::write(path, report_lines.join("\n") + "\n")?;
+        eprintln!("wrote report: {}", path);
+    }
+
+    eprintln!(
+        "tune ok: best_effective_bytes={} best_recipe_id={} elapsed_ms={}",
+        best_eff,
+        best_rid,
+        t0.elapsed().as_millis()
+    );
+    Ok(())
+}
@@ -0,0 +1,285 @@
+// crates/k8dnz-cli/src/cmd/tune_recipe.rs
+//
+// Tuning recipes: a TOML provenance record of one shift search (--emit-tune-recipe) that
+// --from-tune-recipe can replay.
+//
+//   "$schema_version" = 1
+//   crate_version = "0.1.0"
+//   created_unix = 1760000000
+//   args = ["--recipe=base.k8r", "--fit-in=plain.bin", ...]   # search args, see recorded_argv
+//   base_recipe = "base.k8r"        # --recipe path, if any ...
+//   base_recipe_k8r = "4b38..."     # ... and its bytes (hex), so a replay never needs the file
+//   base_recipe_id = "..."          # after --qmin/--qshift/... overrides
+//   fit_in = "plain.bin"            # plus fit_in_len / fit_in_crc32, checked on replay
+//   best_shift = -77
+//   best_recipe_id = "..."
+//
+//   [[pass]]                        # one per pass: pass, step_div (absent for --step), ranking
+//   [[pass.candidate]]              # every candidate, best first
+//
+// The shift search has no PRNG: the same arguments, base recipe bytes and --fit-in bytes
+// give the same result, so a replay just re-runs tune::run and compares the outcome.
+
+use anyhow::{anyhow, bail};
+use clap::{Parser, ValueEnum};
+use k8dnz_core::Recipe;
+use toml::Value;
+
+use super::tune::TuneArgs;
+use crate::io::recipe_file;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SCHEMA_KEY: &str = "$schema_version";
+const SCHEMA_VERSION: i64 = 1;
+
+/// Parser for the recorded argv.
+#[derive(Parser)]
+#[command(name = "tune")]
+struct Recorded {
+    #[command(flatten)]
+    args: TuneArgs,
+}
+
+pub fn write(
+    path: &str,
+    args: &TuneArgs,
+    base_recipe_id: &str,
+    best_shift: i64,
+    best_recipe_id: &str,
+    passes: Vec<toml::Table>,
+) -> anyhow::Result<()> {
+    let argv: Vec<Value> = recorded_argv(args).into_iter().map(Value::String).collect();
+    let created_unix = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| anyhow!("system time before unix epoch: {e}"))?
+        .as_secs();
+
+    let mut doc = toml::Table::new();
+    doc.insert(SCHEMA_KEY.into(), Value::Integer(SCHEMA_VERSION));
+    doc.insert(
+        "crate_version".into(),
+        Value::String(env!("CARGO_PKG_VERSION").into()),
+    );
+    doc.insert("created_unix".into(), Value::Integer(created_unix as i64));
+    doc.insert("args".into(), Value::Array(argv));
+    if let Some(p) = args.recipe.as_deref() {
+        doc.insert("base_recipe".into(), Value::String(p.into()));
+        doc.insert(
+            "base_recipe_k8r".into(),
+            Value::String(recipe_file::load_k8r(p)?.to_hex()),
+        );
+    }
+    doc.insert(
+        "base_recipe_id".into(),
+        Value::String(base_recipe_id.into()),
+    );
+    if let Some(p) = args.fit_in.as_deref() {
+        let bytes = std::fs::read(p)?;
+        doc.insert("fit_in".into(), Value::String(p.into()));
+        doc.insert("fit_in_len".into(), Value::Integer(bytes.len() as i64));
+        doc.insert(
+            "fit_in_crc32".into(),
+            Value::Integer(crc32fast::hash(&bytes) as i64),
+        );
+    }
+    doc.insert("best_shift".into(), Value::Integer(best_shift));
+    doc.insert(
+        "best_recipe_id".into(),
+        Value::String(best_recipe_id.into()),
+    );
+    doc.insert(
+        "pass".into(),
+        Value::Array(passes.into_iter().map(Value::Table).collect()),
+    );
+
+    let text = toml::to_string(&doc).map_err(|e| anyhow!("--emit-tune-recipe: {e}"))?;
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+/// The search-relevant part of `args` as `--name=value` strings (the `=` keeps negative
+/// values from parsing as flags). Outputs, dumps and the modes --emit-tune-recipe
+/// conflicts with are left out; --out-recipe is kept only because the parser requires it.
+fn recorded_argv(args: &TuneArgs) -> Vec<String> {
+    let mut v: Vec<String> = Vec::new();
+    let mut opt = |name: &str, value: Option<String>| {
+        if let Some(x) = value {
+            v.push(format!("--{name}={x}"));
+        }
+    };
+    opt("recipe", args.recipe.clone());
+    opt(
+        "recipe-preset",
+        args.recipe_preset.map(|p| p.name().to_string()),
+    );
+    opt("out-recipe", Some(args.out_recipe.clone()));
+    opt("qmin", args.qmin.map(|x| x.to_string()));
+    opt("qmax", args.qmax.map(|x| x.to_string()));
+    opt("qshift", args.qshift.map(|x| x.to_string()));
+    opt("clamp-min", args.clamp_min.map(|x| x.to_string()));
+    opt("clamp-max", args.clamp_max.map(|x| x.to_string()));
+    opt(
+        "measure-emissions",
+        Some(args.measure_emissions.to_string()),
+    );
+    opt(
+        "measure-max-ticks",
+        Some(args.measure_max_ticks.to_string()),
+    );
+    opt("candidates", Some(args.candidates.to_string()));
+    opt("step", args.step.map(|x| x.to_string()));
+    opt("per-emissions", Some(args.per_emissions.to_string()));
+    opt("per-max-ticks", Some(args.per_max_ticks.to_string()));
+    opt("passes", Some(args.passes.to_string()));
+    opt("step-div", args.step_div.clone());
+    opt(
+        "validate-emissions",
+        Some(args.validate_emissions.to_string()),
+    );
+    opt(
+        "validate-max-ticks",
+        Some(args.validate_max_ticks.to_string()),
+    );
+    opt("fit-in", args.fit_in.clone());
+    opt("zstd-level", Some(args.zstd_level.to_string()));
+    opt(
+        "keystream-mix",
+        args.keystream_mix
+            .to_possible_value()
+            .map(|p| p.get_name().to_string()),
+    );
+    opt("warm-start", args.warm_start.clone());
+    opt("rgb-step-range", Some(args.rgb_step_range.clone()));
+    opt("rgb-scale-range", Some(args.rgb_scale_range.clone()));
+    opt("cv-splits", Some(args.cv_splits.to_string()));
+
+    let flags = [
+        ("measure-field", args.measure_field),
+        ("set-clamp-from-field", args.set_clamp_from_field),
+        ("validate-best", args.validate_best),
+        ("fit-by-residual", args.fit_by_residual),
+        ("rank-by-effective-zstd", args.rank_by_effective_zstd),
+        ("tune-rgb-params", args.tune_rgb_params),
+        ("tune-gamma", args.tune_gamma),
+        ("cross-validate", args.cross_validate),
+    ];
+    v.extend(
+        flags
+            .iter()
+            .filter(|(_, on)| *on)
+            .map(|(name, _)| format!("--{name}")),
+    );
+    v
+}
+
+pub fn replay(args: &TuneArgs) -> anyhow::Result<()> {
+    let path = args
+        .from_tune_recipe
+        .as_deref()
+        .expect("replay needs --from-tune-recipe");
+    let text = std::fs::read_to_string(path)?;
+    let doc: toml::Table = text
+        .parse()
+        .map_err(|e| anyhow!("{path}: invalid TOML: {e}"))?;
+
+    let schema = int_field(&doc, SCHEMA_KEY, path)?;
+    if schema != SCHEMA_VERSION {
+        bail!("{path}: unsupported {SCHEMA_KEY} {schema} (this build reads {SCHEMA_VERSION})");
+    }
+    let crate_version = str_field(&doc, "crate_version", path)?;
+    if crate_version != env!("CARGO_PKG_VERSION") {
+        eprintln!(
+            "WARN: {path} was written by k8dnz-cli {crate_version}, replaying with {}",
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+    let want_shift = int_field(&doc, "best_shift", path)?;
+    let want_rid = str_field(&doc, "best_recipe_id", path)?;
+
+    let argv = doc
+        .get("args")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("{path}: missing array `args`"))?
+        .iter()
+        .map(|v| {
+            v.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("{path}: `args` must be strings"))
+        })
+        .collect::<anyhow::Result<Vec<String>>>()?;
+    let mut rec = Recorded::try_parse_from(std::iter::once("tune".to_string()).chain(argv))
+        .map_err(|e| anyhow!("{path}: recorded args: {e}"))?
+        .args;
+
+    let tmp = tempfile::tempdir()?;
+    if let Some(hex) = doc.get("base_recipe_k8r").and_then(Value::as_str) {
+        let p = tmp.path().join("base.k8r");
+        let base = Recipe::from_hex(hex).map_err(|e| anyhow!("{path}: base_recipe_k8r: {e}"))?;
+        let p = p.to_string_lossy().into_owned();
+        recipe_file::save_k8r(&p, &base)?;
+        rec.recipe = Some(p);
+    }
+    if let Some(fit_in) = rec.fit_in.as_deref() {
+        let bytes = std::fs::read(fit_in)?;
+        let want_len = int_field(&doc, "fit_in_len", path)?;
+        let want_crc = int_field(&doc, "fit_in_crc32", path)?;
+        if bytes.len() as i64 != want_len || crc32fast::hash(&bytes) as i64 != want_crc {
+            bail!(
+                "{path}: --fit-in {fit_in} changed since the tune was recorded (len={} crc32={:08x}, recorded len={} crc32={:08x})",
+                bytes.len(),
+                crc32fast::hash(&bytes),
+                want_len,
+                want_crc
+            );
+        }
+    }
+
+    // Outputs come from this command line; dumps and side artifacts are not replayed.
+    rec.out_recipe = args.out_recipe.clone();
+    rec.report = args.report.clone();
+    rec.emit_tune_recipe = None;
+    rec.from_tune_recipe = None;
+    rec.out_ark = None;
+    rec.dump_residual = None;
+    rec.dump_model = None;
+    rec.dump_raw_model = None;
+    rec.dump_residual_pass = None;
+    rec.dump_model_pass = None;
+    rec.dump_raw_model_pass = None;
+    rec.sensitivity_report = None;
+    rec.export_all_candidates = None;
+
+    eprintln!("--- tune replay: {} ---", path);
+    super::tune::run(rec)?;
+
+    let tuned = recipe_file::load_k8r(&args.out_recipe)?;
+    let got_shift = tuned.quant.shift;
+    let got_rid = k8dnz_core::recipe::format::recipe_id_hex(&tuned);
+    if got_shift != want_shift || got_rid != want_rid {
+        bail!(
+            "tune replay mismatch: best_shift={} best_recipe_id={} (recorded best_shift={} best_recipe_id={})",
+            got_shift,
+            got_rid,
+            want_shift,
+            want_rid
+        );
+    }
+    eprintln!(
+        "tune replay ok: best_shift={} best_recipe_id={} matches {}",
+        got_shift, got_rid, path
+    );
+    Ok(())
+}
+
+fn int_field(doc: &toml::Table, key: &str, path: &str) -> anyhow::Result<i64> {
+    doc.get(key)
+        .and_then(Value::as_integer)
+        .ok_or_else(|| anyhow!("{path}: missing integer `{key}`"))
+}
+
+fn str_field<'a>(doc: &'a toml::Table, key: &str, path: &str) -> anyhow::Result<&'a str> {
+    doc.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("{path}: missing string `{key}`"))
+}
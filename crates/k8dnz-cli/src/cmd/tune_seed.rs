@@ -0,0 +1,199 @@
+// crates/k8dnz-cli/src/cmd/tune_seed.rs
+//
+// tune --tune-seed: joint brute-force search over (recipe.seed, quant.shift).
+//
+// - seeds: --seed-range lo:hi:step (inclusive); --seed-count N keeps N of them, picked by a
+//   Fisher-Yates shuffle keyed by the base recipe's seed (reproducible)
+// - shifts: the same single-pass grid as the shift search (--candidates around the base
+//   shift, --step or width/32)
+// - every (seed, shift) pair is scored in parallel by effective_bytes against --fit-in;
+//   ties go to the earlier pair in (seed, shift) order
+//
+// recipe.seed never reaches the engine dynamics: it only keys the splitmix64 keystream
+// mixer, so the search requires --keystream-mix splitmix64. The engine runs once per
+// shift; each seed only re-mixes that raw stream.
+
+use k8dnz_core::recipe::format::recipe_id_hex;
+use k8dnz_core::recipe::recipe::KeystreamMix;
+use k8dnz_core::{Engine, Recipe};
+use rayon::prelude::*;
+
+use super::tune::{clamp_shift_to_width, effective_bytes_for_model, TuneArgs};
+use super::util::parse_u64_range;
+use crate::io::ark::{self, KeystreamGen};
+use crate::io::recipe_file;
+
+use std::time::Instant;
+
+pub fn run(args: &TuneArgs, base: Recipe, plain: &[u8]) -> anyhow::Result<()> {
+    let t0 = Instant::now();
+    if base.keystream_mix != KeystreamMix::SplitMix64 {
+        anyhow::bail!(
+            "--tune-seed requires --keystream-mix splitmix64 (recipe.seed only keys the keystream mixer; with mix=none every seed scores the same)"
+        );
+    }
+
+    let mut seeds = parse_u64_range(&args.seed_range, "--seed-range")?;
+    if let Some(count) = args.seed_count {
+        if count == 0 {
+            anyhow::bail!("--seed-count must be >= 1");
+        }
+        seeds = pick_seeds(seeds, count, base.seed);
+    }
+    let shifts = shift_grid(args, &base);
+
+    let pairs: Vec<(u64, i64)> = seeds
+        .iter()
+        .flat_map(|&seed| shifts.iter().map(move |&shift| (seed, shift)))
+        .collect();
+
+    let base_rid = recipe_id_hex(&base);
+    eprintln!("--- tune seed ---");
+    eprintln!(
+        "base_recipe_id={} seed_range={} seeds={} shifts={} pairs={} fit_bytes={}",
+        base_rid,
+        args.seed_range,
+        seeds.len(),
+        shifts.len(),
+        pairs.len(),
+        plain.len()
+    );
+
+    // Raw (pre-mix) keystream per shift; None when the engine falls short.
+    let raws: Vec<Option<Vec<u8>>> = shifts
+        .par_iter()
+        .map(|&shift| {
+            let mut e = Engine::new(with_seed_shift(&base, base.seed, shift)).ok()?;
+            ark::keystream_bytes_with_raw(&mut e, plain.len(), args.per_max_ticks)
+                .ok()
+                .map(|(_, raw)| raw)
+        })
+        .collect();
+
+    let scored: Vec<Option<usize>> = pairs
+        .par_iter()
+        .enumerate()
+        .map(|(i, &(seed, shift))| {
+            let raw = raws[i % shifts.len()].as_deref()?;
+            let r = with_seed_shift(&base, seed, shift);
+            effective_bytes_for_model(&r, plain, &KeystreamGen::remix(seed, raw), args.zstd_level)
+        })
+        .collect();
+
+    let mut report_lines: Vec<String> = vec![
+        "--- k8dnz tune seed report ---".to_string(),
+        format!("base_recipe_id = {}", base_rid),
+        format!("fit_in = {:?}", args.fit_in),
+        format!("zstd_level = {}", args.zstd_level),
+        format!("seed_range = {}", args.seed_range),
+        format!("seed_count = {:?}", args.seed_count),
+        format!("pairs = {}", pairs.len()),
+        String::new(),
+    ];
+
+    let mut best: Option<(usize, usize)> = None;
+    for (i, (&(seed, shift), eff)) in pairs.iter().zip(&scored).enumerate() {
+        let score = match eff {
+            Some(v) => v.to_string(),
+            None => "DEAD".to_string(),
+        };
+        let line = format!(
+            "cand {}/{} seed={} shift={} -> effective_bytes={}",
+            i + 1,
+            pairs.len(),
+            seed,
+            shift,
+            score
+        );
+        eprintln!("{line}");
+        report_lines.push(line);
+        if let Some(v) = *eff {
+            if best.is_none_or(|(b, _)| v < b) {
+                best = Some((v, i));
+            }
+        }
+    }
+
+    let Some((best_eff, best_idx)) = best else {
+        anyhow::bail!("--tune-seed: no (seed, shift) pair produced a usable keystream");
+    };
+    let (best_seed, best_shift) = pairs[best_idx];
+    let best_recipe = with_seed_shift(&base, best_seed, best_shift);
+    let best_rid = recipe_id_hex(&best_recipe);
+
+    recipe_file::save_k8r(&args.out_recipe, &best_recipe)?;
+    eprintln!(
+        "saved tuned recipe: {} (seed={} shift={} effective_bytes={} recipe_id={})",
+        args.out_recipe, best_seed, best_shift, best_eff, best_rid
+    );
+
+    report_lines.push(String::new());
+    report_lines.push(format!("best_seed = {}", best_seed));
+    report_lines.push(format!("best_shift = {}", best_shift));
+    report_lines.push(format!("best_effective_bytes = {}", best_eff));
+    report_lines.push(format!("best_recipe_id = {}", best_rid));
+
+    if let Some(path) = args.report.as_deref() {
+        std::fs::write(path, report_lines.join("\n") + "\n")?;
+        eprintln!("wrote report: {}", path);
+    }
+
+    eprintln!(
+        "tune ok: best_seed={} best_shift={} best_effective_bytes={} best_recipe_id={} elapsed_ms={}",
+        best_seed,
+        best_shift,
+        best_eff,
+        best_rid,
+        t0.elapsed().as_millis()
+    );
+    Ok(())
+}
+
+fn with_seed_shift(base: &Recipe, seed: u64, shift: i64) -> Recipe {
+    let mut r = base.clone();
+    r.seed = seed;
+    r.quant.shift = shift;
+    r
+}
+
+/// Same candidate grid as a single-pass shift search.
+fn shift_grid(args: &TuneArgs, base: &Recipe) -> Vec<i64> {
+    let mut n = args.candidates.max(1);
+    if n.is_multiple_of(2) {
+        n += 1;
+    }
+    let half = (n / 2) as i64;
+    let width = base.quant.max - base.quant.min;
+    let step = args.step.unwrap_or((width / 32).max(1));
+
+    let mut shifts: Vec<i64> = (0..n as i64)
+        .map(|i| {
+            let raw = base
+                .quant
+                .shift
+                .saturating_add((i - half).saturating_mul(step));
+            clamp_shift_to_width(raw, width)
+        })
+        .collect();
+    shifts.dedup();
+    shifts
+}
+
+/// First `count` values of a splitmix64-keyed Fisher-Yates shuffle of `seeds`.
+fn pick_seeds(mut seeds: Vec<u64>, count: usize, key: u64) -> Vec<u64> {
+    let mut state = key;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    let take = count.min(seeds.len());
+    for i in 0..take {
+        let j = i + (next() % (seeds.len() - i) as u64) as usize;
+        seeds.swap(i, j);
+    }
+    seeds.truncate(take);
+    seeds
+}
@@ -0,0 +1,143 @@
+// crates/k8dnz-cli/src/cmd/tune_tournament.rs
+//
+// tune --multi-recipe-tournament r1.k8r r2.k8r ...: one full tune pass per base recipe.
+//
+// - every entrant is tuned by tune::run with the same arguments; only --recipe, --out-recipe
+//   and --report change (recipe_N.k8r / recipe_N_report.txt in --out-dir, N 1-based)
+// - each tuned recipe is then scored by effective_bytes against --fit-in, the same score
+//   the shift search ranks by; the lowest wins, ties go to the earlier entrant
+// - the winner is copied to --out-recipe; the comparison table goes to stderr and --report
+
+use k8dnz_core::recipe::format::recipe_id_hex;
+
+use super::tune::{effective_bytes_for, TuneArgs};
+use crate::io::recipe_file;
+
+use std::path::Path;
+use std::time::Instant;
+
+struct Entrant {
+    base: String,
+    best_shift: i64,
+    recipe_id: String,
+    effective_bytes: Option<usize>,
+}
+
+pub fn run(args: &TuneArgs) -> anyhow::Result<()> {
+    let t0 = Instant::now();
+    let Some(fit_in) = args.fit_in.as_deref() else {
+        anyhow::bail!("--multi-recipe-tournament requires --fit-in <path>");
+    };
+    let plain = std::fs::read(fit_in)?;
+
+    let tmp;
+    let out_dir: &Path = match args.out_dir.as_deref() {
+        Some(d) => {
+            std::fs::create_dir_all(d)?;
+            Path::new(d)
+        }
+        None => {
+            tmp = tempfile::tempdir()?;
+            tmp.path()
+        }
+    };
+
+    let total = args.multi_recipe_tournament.len();
+    let mut entrants: Vec<Entrant> = Vec::with_capacity(total);
+    for (i, base) in args.multi_recipe_tournament.iter().enumerate() {
+        let n = i + 1;
+        let out_recipe = out_dir.join(format!("recipe_{n}.k8r"));
+        let report = out_dir.join(format!("recipe_{n}_report.txt"));
+        eprintln!("--- tournament entrant {}/{}: {} ---", n, total, base);
+
+        let mut entrant_args = args.clone();
+        entrant_args.multi_recipe_tournament = Vec::new();
+        entrant_args.out_dir = None;
+        entrant_args.recipe = Some(base.clone());
+        entrant_args.out_recipe = out_recipe.to_string_lossy().into_owned();
+        entrant_args.report = Some(report.to_string_lossy().into_owned());
+        super::tune::run(entrant_args)
+            .map_err(|e| anyhow::anyhow!("tournament entrant {n} ({base}): {e}"))?;
+
+        let tuned = recipe_file::load_k8r(&out_recipe.to_string_lossy())?;
+        entrants.push(Entrant {
+            base: base.clone(),
+            best_shift: tuned.quant.shift,
+            recipe_id: recipe_id_hex(&tuned),
+            effective_bytes: effective_bytes_for(
+                &tuned,
+                &plain,
+                args.per_max_ticks,
+                args.zstd_level,
+            ),
+        });
+    }
+
+    let mut winner: Option<(usize, usize)> = None;
+    for (i, e) in entrants.iter().enumerate() {
+        if let Some(v) = e.effective_bytes {
+            if winner.is_none_or(|(b, _)| v < b) {
+                winner = Some((v, i));
+            }
+        }
+    }
+    let Some((best_eff, best_idx)) = winner else {
+        anyhow::bail!("--multi-recipe-tournament: no entrant produced a usable keystream");
+    };
+
+    let best_src = out_dir.join(format!("recipe_{}.k8r", best_idx + 1));
+    std::fs::copy(&best_src, &args.out_recipe)?;
+
+    let mut lines: Vec<String> = vec![
+        "--- k8dnz tune tournament report ---".to_string(),
+        format!("fit_in = {}", fit_in),
+        format!("zstd_level = {}", args.zstd_level),
+        format!("entrants = {}", total),
+        String::new(),
+        format!(
+            "{:>4}  {:<32}  {:>12}  {:<32}  {:>15}",
+            "n", "base_recipe", "best_shift", "recipe_id", "effective_bytes"
+        ),
+    ];
+    for (i, e) in entrants.iter().enumerate() {
+        let eff = match e.effective_bytes {
+            Some(v) => v.to_string(),
+            None => "DEAD".to_string(),
+        };
+        lines.push(format!(
+            "{:>4}  {:<32}  {:>12}  {:<32}  {:>15}{}",
+            i + 1,
+            e.base,
+            e.best_shift,
+            e.recipe_id,
+            eff,
+            if i == best_idx { "  *" } else { "" }
+        ));
+    }
+    let w = &entrants[best_idx];
+    lines.push(String::new());
+    lines.push(format!("winner = {}", best_idx + 1));
+    lines.push(format!("winner_base_recipe = {}", w.base));
+    lines.push(format!("best_shift = {}", w.best_shift));
+    lines.push(format!("best_recipe_id = {}", w.recipe_id));
+    lines.push(format!("best_effective_bytes = {}", best_eff));
+
+    for l in &lines {
+        eprintln!("{l}");
+    }
+    if let Some(path) = args.report.as_deref() {
+        std::fs::write(path, lines.join("\n") + "\n")?;
+        eprintln!("wrote report: {}", path);
+    }
+
+    eprintln!(
+        "tune tournament ok: winner={} ({}) best_shift={} best_effective_bytes={} best_recipe_id={} elapsed_ms={}",
+        best_idx + 1,
+        w.base,
+        w.best_shift,
+        best_eff,
+        w.recipe_id,
+        t0.elapsed().as_millis()
+    );
+    Ok(())
+}
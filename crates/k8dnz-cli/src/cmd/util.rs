@@ -0,0 +1,23 @@
+// crates/k8dnz-cli/src/cmd/util.rs
+//
+// Argument helpers shared by more than one subcommand.
+
+/// Inclusive `lo:hi:step` list of u64 values; `flag` names the option in errors.
+pub fn parse_u64_range(s: &str, flag: &str) -> anyhow::Result<Vec<u64>> {
+    let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+    let [lo, hi, step] = parts.as_slice() else {
+        anyhow::bail!("{flag} expects lo:hi:step (got {s:?})");
+    };
+    let parse = |v: &str| -> anyhow::Result<u64> {
+        v.parse()
+            .map_err(|_| anyhow::anyhow!("invalid {flag} value: {v}"))
+    };
+    let (lo, hi, step) = (parse(lo)?, parse(hi)?, parse(step)?);
+    if step == 0 {
+        anyhow::bail!("{flag} step must be > 0");
+    }
+    if lo > hi {
+        anyhow::bail!("{flag} needs lo <= hi (got {lo}:{hi})");
+    }
+    Ok((lo..=hi).step_by(step as usize).collect())
+}
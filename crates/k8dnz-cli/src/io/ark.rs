@@ -1,5 +1,7 @@
 // crates/k8dnz-cli/src/io/ark.rs
 
+use std::io::{Read, Write};
+
 use anyhow::Context;
 use k8dnz_core::recipe::format as recipe_format;
 use k8dnz_core::recipe::recipe::KeystreamMix;
@@ -15,20 +17,165 @@ const MAGIC: &[u8; 4] = b"ARK1";
 /// data_bytes[data_len]       (ciphertext OR residual; interpretation lives in recipe.payload_kind)
 /// crc32:u32                  (over everything before crc32)
 pub fn write_ark(path: &str, recipe: &Recipe, data: &[u8]) -> anyhow::Result<()> {
-    let recipe_bytes = recipe_format::encode(recipe);
+    let f = std::io::BufWriter::new(
+        std::fs::File::create(path).with_context(|| format!("create {path}"))?,
+    );
+    let mut w = ArkWriter::begin(f, recipe, data.len() as u64)?;
+    w.write_data(data)?;
+    w.finish()?;
+    Ok(())
+}
 
-    let mut out = Vec::with_capacity(4 + 4 + recipe_bytes.len() + 8 + data.len() + 4);
-    out.extend_from_slice(MAGIC);
-    out.extend_from_slice(&(recipe_bytes.len() as u32).to_le_bytes());
-    out.extend_from_slice(&recipe_bytes);
-    out.extend_from_slice(&(data.len() as u64).to_le_bytes());
-    out.extend_from_slice(data);
+/// Total .ark size for a given recipe and payload length (header + data + crc).
+pub fn ark_len(recipe: &Recipe, data_len: u64) -> u64 {
+    (4 + 4 + recipe_format::encode(recipe).len() + 8 + 4) as u64 + data_len
+}
 
-    let crc = crc32(&out);
-    out.extend_from_slice(&crc.to_le_bytes());
+/// Streaming .ark writer: header up front, data in any number of pieces, crc at the end.
+/// Same bytes as `write_ark`; lets callers emit the payload block by block.
+pub struct ArkWriter<W: Write> {
+    w: W,
+    crc: crc32fast::Hasher,
+    remaining: u64,
+}
 
-    std::fs::write(path, out)?;
-    Ok(())
+impl<W: Write> ArkWriter<W> {
+    pub fn begin(mut w: W, recipe: &Recipe, data_len: u64) -> anyhow::Result<Self> {
+        let recipe_bytes = recipe_format::encode(recipe);
+        let mut crc = crc32fast::Hasher::new();
+
+        let mut head = Vec::with_capacity(4 + 4 + recipe_bytes.len() + 8);
+        head.extend_from_slice(MAGIC);
+        head.extend_from_slice(&(recipe_bytes.len() as u32).to_le_bytes());
+        head.extend_from_slice(&recipe_bytes);
+        head.extend_from_slice(&data_len.to_le_bytes());
+
+        crc.update(&head);
+        w.write_all(&head)?;
+        Ok(Self {
+            w,
+            crc,
+            remaining: data_len,
+        })
+    }
+
+    pub fn write_data(&mut self, chunk: &[u8]) -> anyhow::Result<()> {
+        if chunk.len() as u64 > self.remaining {
+            anyhow::bail!("ark writer: more data than declared data_len");
+        }
+        self.crc.update(chunk);
+        self.w.write_all(chunk)?;
+        self.remaining -= chunk.len() as u64;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> anyhow::Result<W> {
+        if self.remaining != 0 {
+            anyhow::bail!("ark writer: {} data bytes missing", self.remaining);
+        }
+        let crc = self.crc.finalize();
+        self.w.write_all(&crc.to_le_bytes())?;
+        self.w.flush()?;
+        Ok(self.w)
+    }
+}
+
+/// Streaming .ark reader: the whole-file crc is checked in a first pass, then the
+/// header is parsed and data is handed out in caller-sized pieces. Memory stays at
+/// one block no matter how large the payload is.
+pub struct ArkReader<R: Read> {
+    r: R,
+    remaining: u64,
+}
+
+impl<R: Read> ArkReader<R> {
+    /// Data bytes not yet read.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Fills `buf` with up to `buf.len()` data bytes; returns how many (0 at end).
+    pub fn read_data(&mut self, buf: &mut [u8]) -> anyhow::Result<usize> {
+        let n = (buf.len() as u64).min(self.remaining) as usize;
+        self.r
+            .read_exact(&mut buf[..n])
+            .context("ark data truncated")?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// Streaming counterpart of `read_ark_with_id`: same checks (magic, crc, lengths,
+/// embedded recipe), but returns a reader positioned at the data bytes.
+pub fn open_ark_stream(
+    path: &str,
+) -> anyhow::Result<(String, Recipe, ArkReader<std::io::BufReader<std::fs::File>>)> {
+    let open = || -> anyhow::Result<std::io::BufReader<std::fs::File>> {
+        Ok(std::io::BufReader::new(
+            std::fs::File::open(path).with_context(|| format!("read {path}"))?,
+        ))
+    };
+
+    let file_len = std::fs::metadata(path)
+        .with_context(|| format!("read {path}"))?
+        .len();
+    if file_len < 4 + 4 + 8 + 4 {
+        anyhow::bail!("ark too small");
+    }
+
+    // Pass 1: crc over everything before the trailing crc32.
+    let mut f = open()?;
+    let mut crc = crc32fast::Hasher::new();
+    let mut left = file_len - 4;
+    let mut buf = vec![0u8; 64 * 1024];
+    while left > 0 {
+        let n = (buf.len() as u64).min(left) as usize;
+        f.read_exact(&mut buf[..n])?;
+        crc.update(&buf[..n]);
+        left -= n as u64;
+    }
+    let mut tail = [0u8; 4];
+    f.read_exact(&mut tail)?;
+    if u32::from_le_bytes(tail) != crc.finalize() {
+        anyhow::bail!("ark crc32 mismatch");
+    }
+
+    // Pass 2: header, then hand the reader over at the data start.
+    let mut f = open()?;
+    let mut magic = [0u8; 4];
+    f.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        anyhow::bail!("bad ark magic");
+    }
+
+    let mut u32b = [0u8; 4];
+    f.read_exact(&mut u32b)?;
+    let recipe_len = u32::from_le_bytes(u32b) as u64;
+    if 4 + 4 + recipe_len + 8 + 4 > file_len {
+        anyhow::bail!("ark recipe_len out of range");
+    }
+    let mut recipe_bytes = vec![0u8; recipe_len as usize];
+    f.read_exact(&mut recipe_bytes)?;
+
+    let id16 = recipe_format::recipe_id_16_from_encoded(&recipe_bytes)?;
+    let rid = hex16(&id16);
+    let recipe = recipe_format::decode(&recipe_bytes)?;
+
+    let mut u64b = [0u8; 8];
+    f.read_exact(&mut u64b)?;
+    let data_len = u64::from_le_bytes(u64b);
+    if data_len != file_len - (4 + 4 + recipe_len + 8 + 4) {
+        anyhow::bail!("ark data_len mismatch");
+    }
+
+    Ok((
+        rid,
+        recipe,
+        ArkReader {
+            r: f,
+            remaining: data_len,
+        },
+    ))
 }
 
 #[allow(dead_code)]
@@ -87,6 +234,15 @@ pub fn read_ark_with_id(path: &str) -> anyhow::Result<(String, Recipe, Vec<u8>)>
 
 #[allow(dead_code)]
 pub fn ark_recipe_id_hex(path: &str) -> anyhow::Result<String> {
+    let recipe_bytes = read_ark_recipe_bytes(path)?;
+    let id16 = recipe_format::recipe_id_16_from_encoded(&recipe_bytes)?;
+    Ok(hex16(&id16))
+}
+
+/// The embedded K8R1 recipe blob, exactly as stored. Only the ark framing (magic,
+/// crc32, recipe_len) is checked; the blob itself is NOT decoded, so callers can
+/// report a malformed recipe separately from a damaged artifact.
+pub fn read_ark_recipe_bytes(path: &str) -> anyhow::Result<Vec<u8>> {
     let bytes = std::fs::read(path).with_context(|| format!("read {path}"))?;
     if bytes.len() < 4 + 4 + 8 + 4 {
         anyhow::bail!("ark too small");
@@ -110,8 +266,7 @@ pub fn ark_recipe_id_hex(path: &str) -> anyhow::Result<String> {
         anyhow::bail!("ark recipe_len out of range");
     }
 
-    let id16 = recipe_format::recipe_id_16_from_encoded(&bytes[recipe_start..recipe_end])?;
-    Ok(hex16(&id16))
+    Ok(bytes[recipe_start..recipe_end].to_vec())
 }
 
 /// Generate N keystream bytes from the engine.
@@ -152,46 +307,90 @@ fn keystream_impl(
         None
     };
 
-    // SplitMix64 state (only used if enabled)
-    let mut sm64_state: u64 = engine.recipe.seed ^ 0x6A09_E667_F3BC_C909;
-
-    while mixed.len() < n && engine.stats.ticks < max_ticks {
-        if let Some(tok) = engine.step() {
-            let r = ((tok.a & 0x0F) << 4) | (tok.b & 0x0F);
+    KeystreamGen::new(engine).fill(engine, n, max_ticks, &mut mixed, raw.as_mut())?;
+    Ok((mixed, raw))
+}
 
-            if let Some(rr) = raw.as_mut() {
-                rr.push(r);
-            }
+/// Resumable keystream: carries the SplitMix64 mask state between calls so a
+/// long stream can be produced in blocks with the same bytes as one big call.
+pub struct KeystreamGen {
+    sm64_state: u64,
+    produced: u64,
+}
 
-            let m = match engine.recipe.keystream_mix {
-                KeystreamMix::None => r,
-                KeystreamMix::SplitMix64 => {
-                    // splitmix64 step -> take low byte as mask
-                    sm64_state = sm64_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
-                    let mut z = sm64_state;
-                    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
-                    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
-                    z ^= z >> 31;
-                    let mask = (z & 0xFF) as u8;
-                    r ^ mask
-                }
-            };
+impl KeystreamGen {
+    pub fn new(engine: &k8dnz_core::Engine) -> Self {
+        Self::with_seed(engine.recipe.seed)
+    }
 
-            mixed.push(m);
+    fn with_seed(seed: u64) -> Self {
+        Self {
+            sm64_state: seed ^ 0x6A09_E667_F3BC_C909,
+            produced: 0,
         }
     }
 
-    if mixed.len() != n {
-        anyhow::bail!(
-            "keystream short: need {} bytes, got {} (ticks={}, emissions={})",
-            n,
-            mixed.len(),
-            engine.stats.ticks,
-            engine.stats.emissions
-        );
+    /// The SplitMix64-mixed keystream for `seed` over already generated raw bytes.
+    /// The mask depends only on the seed and byte index, so one raw stream can be
+    /// re-mixed for many seeds without rerunning the engine.
+    pub fn remix(seed: u64, raw: &[u8]) -> Vec<u8> {
+        let mut gen = Self::with_seed(seed);
+        raw.iter().map(|&r| r ^ gen.next_mask()).collect()
     }
 
-    Ok((mixed, raw))
+    fn next_mask(&mut self) -> u8 {
+        // splitmix64 step -> take low byte as mask
+        self.sm64_state = self.sm64_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.sm64_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z & 0xFF) as u8
+    }
+
+    /// Appends exactly `n` bytes to `mixed` (and the pre-mix bytes to `raw`), or fails.
+    pub fn fill(
+        &mut self,
+        engine: &mut k8dnz_core::Engine,
+        n: usize,
+        max_ticks: u64,
+        mixed: &mut Vec<u8>,
+        mut raw: Option<&mut Vec<u8>>,
+    ) -> anyhow::Result<()> {
+        let start = mixed.len();
+        let target = start + n;
+
+        while mixed.len() < target && engine.stats.ticks < max_ticks {
+            if let Some(tok) = engine.step() {
+                let r = tok.pack_byte();
+
+                if let Some(rr) = raw.as_deref_mut() {
+                    rr.push(r);
+                }
+
+                let m = match engine.recipe.keystream_mix {
+                    KeystreamMix::None => r,
+                    KeystreamMix::SplitMix64 => r ^ self.next_mask(),
+                };
+
+                mixed.push(m);
+            }
+        }
+
+        let got = (mixed.len() - start) as u64;
+        if mixed.len() != target {
+            anyhow::bail!(
+                "keystream short: need {} bytes, got {} (ticks={}, emissions={})",
+                self.produced + n as u64,
+                self.produced + got,
+                engine.stats.ticks,
+                engine.stats.emissions
+            );
+        }
+
+        self.produced += got;
+        Ok(())
+    }
 }
 
 fn crc32(bytes: &[u8]) -> u32 {
@@ -227,3 +426,23 @@ fn hex16(id: &[u8; 16]) -> String {
     }
     s
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remix_matches_the_mixed_stream_for_each_seed() {
+        let mut r = k8dnz_core::recipe::defaults::default_recipe();
+        r.keystream_mix = KeystreamMix::SplitMix64;
+        let mut e = k8dnz_core::Engine::new(r.clone()).unwrap();
+        let (_, raw) = keystream_bytes_with_raw(&mut e, 64, 50_000_000).unwrap();
+
+        for seed in [0u64, 7, u64::MAX] {
+            r.seed = seed;
+            let mut e = k8dnz_core::Engine::new(r.clone()).unwrap();
+            let mixed = keystream_bytes(&mut e, 64, 50_000_000).unwrap();
+            assert_eq!(KeystreamGen::remix(seed, &raw), mixed, "seed={seed}");
+        }
+    }
+}
@@ -1,6 +1,7 @@
 // crates/k8dnz-cli/src/io/bin.rs
 
 use anyhow::Context;
+use k8dnz_core::dynamics::engine::EmissionField;
 use k8dnz_core::signal::token::{PairToken, RgbPairToken};
 
 /// Legacy/compat: write PairToken stream as packed bytes to a file.
@@ -24,3 +25,16 @@ pub fn write_rgbpairs_file(path: &str, toks: &[RgbPairToken]) -> anyhow::Result<
     std::fs::write(path, out).with_context(|| format!("write rgbpairs bin: {path}"))?;
     Ok(())
 }
+
+/// Pre-quantization field values: 32 bytes per emission,
+/// i64 LE raw_a, clamped_a, raw_c, clamped_c.
+pub fn write_fields_file(path: &str, fields: &[EmissionField]) -> anyhow::Result<()> {
+    let mut out = Vec::with_capacity(fields.len() * 32);
+    for f in fields {
+        for v in [f.raw_a, f.clamped_a, f.raw_c, f.clamped_c] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    std::fs::write(path, out).with_context(|| format!("write fields bin: {path}"))?;
+    Ok(())
+}
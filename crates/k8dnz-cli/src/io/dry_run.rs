@@ -0,0 +1,46 @@
+// crates/k8dnz-cli/src/io/dry_run.rs
+//
+// Output sink for --dry-run: counts (and optionally SHA-256 hashes) the bytes a command
+// would have written, then drops them, so the run does the same work as a real one.
+
+use std::io::{self, Write};
+
+use sha2::{Digest, Sha256};
+
+pub struct DryRunSink {
+    written: u64,
+    hasher: Option<Sha256>,
+}
+
+impl DryRunSink {
+    pub fn new(hash: bool) -> Self {
+        Self {
+            written: 0,
+            hasher: hash.then(Sha256::new),
+        }
+    }
+
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Lowercase hex SHA-256 of everything written so far; None unless hashing was requested.
+    pub fn sha256_hex(&self) -> Option<String> {
+        let digest = self.hasher.clone()?.finalize();
+        Some(digest.iter().map(|b| format!("{b:02x}")).collect())
+    }
+}
+
+impl Write for DryRunSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(h) = self.hasher.as_mut() {
+            h.update(buf);
+        }
+        self.written += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
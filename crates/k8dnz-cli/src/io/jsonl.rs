@@ -1,6 +1,7 @@
 // crates/k8dnz-cli/src/io/jsonl.rs
 
 use anyhow::Context;
+use k8dnz_core::dynamics::engine::EmissionField;
 use k8dnz_core::signal::token::{PairToken, RgbPairToken};
 
 /// Legacy/compat: write PairToken stream as JSONL to a file.
@@ -48,3 +49,29 @@ pub fn write_rgbpairs_stdout(toks: &[RgbPairToken]) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+/// Pre-quantization field values, one line per emission.
+/// Format: {"emission":N,"field_a":{"raw":V,"clamped":V},"field_c":{"raw":V,"clamped":V}}
+fn field_line(emission: usize, f: &EmissionField) -> String {
+    format!(
+        "{{\"emission\":{},\"field_a\":{{\"raw\":{},\"clamped\":{}}},\"field_c\":{{\"raw\":{},\"clamped\":{}}}}}",
+        emission, f.raw_a, f.clamped_a, f.raw_c, f.clamped_c
+    )
+}
+
+pub fn write_fields_file(path: &str, fields: &[EmissionField]) -> anyhow::Result<()> {
+    let mut s = String::new();
+    for (i, f) in fields.iter().enumerate() {
+        s.push_str(&field_line(i, f));
+        s.push('\n');
+    }
+    std::fs::write(path, s).with_context(|| format!("write fields jsonl: {path}"))?;
+    Ok(())
+}
+
+pub fn write_fields_stdout(fields: &[EmissionField]) -> anyhow::Result<()> {
+    for (i, f) in fields.iter().enumerate() {
+        println!("{}", field_line(i, f));
+    }
+    Ok(())
+}
@@ -1,5 +1,7 @@
 pub mod ark;
 pub mod bin;
+pub mod dry_run;
 pub mod jsonl;
+pub mod progress;
 pub mod recipe_file;
 pub mod timemap;
@@ -0,0 +1,191 @@
+// crates/k8dnz-cli/src/io/progress.rs
+//
+// Byte-count progress for long encode/decode runs.
+// ProgressReporter<W, P> wraps a writer; with P = NoProgress every hook is an empty
+// inline fn, so the plain path monomorphizes to a bare passthrough (no runtime flag).
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// Speed EMA time constant: roughly "average over the last 5 seconds".
+const EMA_TAU_SECS: f64 = 5.0;
+
+pub trait Progress {
+    fn advance(&mut self, n: u64);
+    fn finish(&mut self) {}
+}
+
+/// Zero-cost stand-in when --progress is off.
+pub struct NoProgress;
+
+impl Progress for NoProgress {
+    #[inline(always)]
+    fn advance(&mut self, _n: u64) {}
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressStatus {
+    pub done: u64,
+    pub total: u64,
+    pub elapsed_secs: f64,
+    /// Exponential moving average of throughput (bytes/sec).
+    pub speed_bps: f64,
+    /// None until a speed sample exists (or when speed is 0).
+    pub eta_secs: Option<f64>,
+}
+
+impl ProgressStatus {
+    pub fn remaining(&self) -> u64 {
+        self.total.saturating_sub(self.done)
+    }
+}
+
+/// Calls `callback` at most once per `interval` (plus once from `finish`).
+pub struct EtaProgress<F: FnMut(&ProgressStatus)> {
+    total: u64,
+    done: u64,
+    start: Instant,
+    last: Instant,
+    last_done: u64,
+    ema_bps: Option<f64>,
+    interval: Duration,
+    callback: F,
+}
+
+impl<F: FnMut(&ProgressStatus)> EtaProgress<F> {
+    pub fn new(total: u64, interval: Duration, callback: F) -> Self {
+        let now = Instant::now();
+        Self {
+            total,
+            done: 0,
+            start: now,
+            last: now,
+            last_done: 0,
+            ema_bps: None,
+            interval,
+            callback,
+        }
+    }
+
+    fn sample(&mut self, now: Instant) {
+        let dt = now.duration_since(self.last).as_secs_f64();
+        if dt > 0.0 {
+            let inst = (self.done - self.last_done) as f64 / dt;
+            let alpha = 1.0 - (-dt / EMA_TAU_SECS).exp();
+            self.ema_bps = Some(match self.ema_bps {
+                None => inst,
+                Some(e) => e + alpha * (inst - e),
+            });
+        }
+        self.last = now;
+        self.last_done = self.done;
+    }
+
+    fn status(&self, now: Instant) -> ProgressStatus {
+        let speed_bps = self.ema_bps.unwrap_or(0.0);
+        let remaining = self.total.saturating_sub(self.done);
+        ProgressStatus {
+            done: self.done,
+            total: self.total,
+            elapsed_secs: now.duration_since(self.start).as_secs_f64(),
+            speed_bps,
+            eta_secs: (speed_bps > 0.0).then(|| remaining as f64 / speed_bps),
+        }
+    }
+}
+
+impl<F: FnMut(&ProgressStatus)> Progress for EtaProgress<F> {
+    fn advance(&mut self, n: u64) {
+        self.done += n;
+        let now = Instant::now();
+        if now.duration_since(self.last) >= self.interval {
+            self.sample(now);
+            let st = self.status(now);
+            (self.callback)(&st);
+        }
+    }
+
+    fn finish(&mut self) {
+        let now = Instant::now();
+        self.sample(now);
+        let st = self.status(now);
+        (self.callback)(&st);
+    }
+}
+
+/// Once-a-second stderr line: done/remaining bytes, MB/s, ETA.
+pub fn stderr_progress(label: &'static str, total: u64) -> EtaProgress<impl FnMut(&ProgressStatus)> {
+    EtaProgress::new(total, Duration::from_secs(1), move |st: &ProgressStatus| {
+        let eta = st
+            .eta_secs
+            .map_or("?".to_string(), |s| format!("{:.1}s", s));
+        eprintln!(
+            "progress {}: done={} remaining={} speed={:.3} MB/s eta={} elapsed={:.1}s",
+            label,
+            st.done,
+            st.remaining(),
+            st.speed_bps / 1_000_000.0,
+            eta,
+            st.elapsed_secs
+        );
+    })
+}
+
+pub struct ProgressReporter<W: Write, P: Progress> {
+    inner: W,
+    progress: P,
+}
+
+impl<W: Write, P: Progress> ProgressReporter<W, P> {
+    pub fn new(inner: W, progress: P) -> Self {
+        Self { inner, progress }
+    }
+
+    /// Flushes, emits the final progress report, and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.flush()?;
+        self.progress.finish();
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write, P: Progress> Write for ProgressReporter<W, P> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.progress.advance(n as u64);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reporter_passes_bytes_through_and_counts() {
+        let mut seen: Vec<(u64, u64)> = Vec::new();
+        let p = EtaProgress::new(10, Duration::ZERO, |st: &ProgressStatus| {
+            seen.push((st.done, st.remaining()))
+        });
+        let mut w = ProgressReporter::new(Vec::new(), p);
+        w.write_all(b"abcd").unwrap();
+        w.write_all(b"efghij").unwrap();
+        let out = w.finish().unwrap();
+
+        assert_eq!(out, b"abcdefghij");
+        assert_eq!(seen.last(), Some(&(10, 0)));
+        assert!(seen.iter().any(|&(d, _)| d == 4));
+    }
+
+    #[test]
+    fn no_progress_is_plain_passthrough() {
+        let mut w = ProgressReporter::new(Vec::new(), NoProgress);
+        w.write_all(b"xyz").unwrap();
+        assert_eq!(w.finish().unwrap(), b"xyz");
+    }
+}
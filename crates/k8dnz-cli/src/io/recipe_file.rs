@@ -1,6 +1,7 @@
 // crates/k8dnz-cli/src/io/recipe_file.rs
 
 use anyhow::{Context, Result};
+use k8dnz_core::recipe::defaults::{self, DefaultRecipes};
 use k8dnz_core::recipe::format as recipe_format;
 use k8dnz_core::Recipe;
 
@@ -23,3 +24,39 @@ pub fn save_k8r(path: &str, recipe: &Recipe) -> Result<()> {
     std::fs::write(path, bytes).with_context(|| format!("write recipe {path}"))?;
     Ok(())
 }
+
+/// `recipe_id_hex` of `recipe`, checked against `--validate-recipe-id` when one is given
+/// (case-insensitive), so a stale or substituted recipe file fails instead of producing
+/// a silently different stream.
+pub fn validate_recipe_id(recipe: &Recipe, expected: Option<&str>) -> Result<String> {
+    let rid = recipe_format::recipe_id_hex(recipe);
+    if let Some(want) = expected.map(str::trim) {
+        if !rid.eq_ignore_ascii_case(want) {
+            anyhow::bail!(
+                "recipe id mismatch: expected {want}, recipe has {rid} (wrong or stale recipe file?)"
+            );
+        }
+        eprintln!("recipe_id ok: {rid}");
+    }
+    Ok(rid)
+}
+
+/// clap value parser for `--recipe-preset <name>`.
+pub fn parse_preset(name: &str) -> std::result::Result<DefaultRecipes, String> {
+    DefaultRecipes::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = DefaultRecipes::ALL.iter().map(|p| p.name()).collect();
+        format!(
+            "unknown recipe preset {name:?} (expected one of: {})",
+            names.join(", ")
+        )
+    })
+}
+
+/// `--recipe <path>` if given, else `--recipe-preset`, else the built-in default.
+pub fn load_or_preset(path: Option<&str>, preset: Option<DefaultRecipes>) -> Result<Recipe> {
+    match (path, preset) {
+        (Some(p), _) => load_k8r(p),
+        (None, Some(v)) => Ok(defaults::get(v)),
+        (None, None) => Ok(defaults::default_recipe()),
+    }
+}
@@ -1,7 +1,7 @@
 // crates/k8dnz-cli/src/io/timemap.rs
 
 use anyhow::{Context, Result};
-use k8dnz_core::signal::timing_map::TimingMap;
+use k8dnz_core::signal::timing_map::{TimemapFormat, TimingMap};
 use std::path::Path;
 
 fn atomic_write(path: &str, bytes: &[u8], default_name: &str) -> Result<()> {
@@ -38,14 +38,30 @@ fn atomic_write(path: &str, bytes: &[u8], default_name: &str) -> Result<()> {
 }
 #[allow(dead_code)]
 pub fn write_tm1(path: &str, tm: &TimingMap) -> Result<()> {
+    tm.validate()
+        .map_err(|e| anyhow::anyhow!("{e}"))
+        .with_context(|| format!("refusing to write invalid timemap {path}"))?;
     let bytes = tm.encode_tm1();
     atomic_write(path, &bytes, "timemap.tm1")
 }
 
 pub fn write_timemap_auto(path: &str, tm: &TimingMap) -> Result<()> {
+    tm.validate()
+        .map_err(|e| anyhow::anyhow!("{e}"))
+        .with_context(|| format!("refusing to write invalid timemap {path}"))?;
     let bytes = tm.encode_auto();
     atomic_write(path, &bytes, "timemap.tm")
 }
+/// Writes `tm` in `fmt`; fails for TM0 when `tm` is not an arithmetic progression.
+pub fn write_timemap_as(path: &str, tm: &TimingMap, fmt: TimemapFormat) -> Result<()> {
+    tm.validate()
+        .map_err(|e| anyhow::anyhow!("{e}"))
+        .with_context(|| format!("refusing to write invalid timemap {path}"))?;
+    let bytes = tm.encode_as(fmt).ok_or_else(|| {
+        anyhow::anyhow!("timemap {path} is not representable as {}", fmt.name())
+    })?;
+    atomic_write(path, &bytes, "timemap.tm")
+}
 #[allow(dead_code)]
 pub fn read_tm1(path: &str) -> Result<TimingMap> {
     let bytes = std::fs::read(path).with_context(|| format!("read timemap {path}"))?;
@@ -34,11 +34,14 @@ pub enum Commands {
     Analyze(cmd::analyze::AnalyzeArgs),
 
     /// Tune/search recipes (fit + qsearch + stats)
-    Tune(cmd::tune::TuneArgs),
+    Tune(Box<cmd::tune::TuneArgs>),
 
     /// Timing map tools (TM1)
     Timemap(cmd::timemap::TimemapArgs),
 
+    /// Recompute the fit scoreboard for a (timemap, residual, recipe) triple
+    Score(cmd::score::ScoreArgs),
+
     /// Recipe tools (.k8r)
     Recipe(cmd::recipe::RecipeArgs),
 
@@ -63,6 +66,9 @@ pub enum Commands {
     /// Ω hillclimb (multi-lane skip/stride tuning across rounds)
     OmegaHillclimb(cmd::omega_hillclimb::OmegaHillclimbArgs),
 
+    /// Run a TOML manifest of CLI operations in sequence
+    Batch(cmd::batch::BatchArgs),
+
     /// ApexTrace generator / fitter
     #[command(name = "apextrace")]
     ApexTrace(cmd::apextrace::ApexTraceArgs),
@@ -78,8 +84,9 @@ fn main() -> anyhow::Result<()> {
         Commands::Decode(args) => cmd::decode_file::run(args),
         Commands::ArkInspect(args) => cmd::ark_inspect::run(args),
         Commands::Analyze(args) => cmd::analyze::run(args),
-        Commands::Tune(args) => cmd::tune::run(args),
+        Commands::Tune(args) => cmd::tune::run(*args),
         Commands::Timemap(args) => cmd::timemap::run(args),
+        Commands::Score(args) => cmd::score::run(args),
         Commands::Recipe(args) => cmd::recipe::run(args),
         Commands::ArkKey(args) => cmd::arkkey::run(args),
         Commands::Orbexp(args) => cmd::orbexp::run(args),
@@ -88,6 +95,7 @@ fn main() -> anyhow::Result<()> {
         Commands::LaneSweep(args) => cmd::lane_sweep::run(args),
         Commands::OmegaSweep(args) => cmd::omega_sweep::run(args),
         Commands::OmegaHillclimb(args) => cmd::omega_hillclimb::run(args),
+        Commands::Batch(args) => cmd::batch::run(args),
         Commands::ApexTrace(args) => cmd::apextrace::run(args),
     }
 }
\ No newline at end of file
@@ -5,8 +5,9 @@ use tempfile::TempDir;
 
 use crate::cmd::timemap::args::{
     ApplyMode, BitMapping, BitfieldResidualEncoding, ChunkXform, FitObjective, FitXorChunkedArgs, MapMode,
-    ReconstructArgs, ResidualMode, TagFormat, TimemapArgs, TimemapCmd,
+    ReconstructArgs, ResidualMode, TimemapArgs, TimemapCmd, BF4_DEFAULT_CHUNK_SYMBOLS,
 };
+use crate::cmd::timemap::mapping::FEISTEL_DEFAULT_ROUNDS;
 use crate::cmd::timemap::run as timemap_run;
 
 use super::format::{K8b1Blob, ReconParams};
@@ -152,6 +153,7 @@ pub fn compress_payload_to_blob(
 
             map_seed,
             map_seed_hex: None,
+            feistel_rounds: FEISTEL_DEFAULT_ROUNDS,
 
             residual: profile.residual_mode,
 
@@ -169,8 +171,16 @@ pub fn compress_payload_to_blob(
 
             refine_topk: profile.refine_topk,
             lookahead: lookahead as usize,
+            stream_cache_size: None,
+            use_ring_buffer: false,
+            parallel_scan: false,
 
-            trans_penalty: profile.trans_penalty,
+            trans_penalty: Some(profile.trans_penalty),
+            trans_penalty_calibrate: false,
+            calibration_chunks: 10,
+            diversity_penalty: 0.0,
+            diversity_window: 4096,
+            anchor_first_chunk_at: None,
 
             bits_per_emission: profile.bits_per_emission,
             bit_mapping: profile.bit_mapping,
@@ -178,11 +188,13 @@ pub fn compress_payload_to_blob(
             bit_smooth_shift: profile.bit_smooth_shift,
 
             bitfield_residual: profile.bitfield_residual,
+            bf4_chunk_symbols: BF4_DEFAULT_CHUNK_SYMBOLS,
             time_split: profile.time_split,
             chunk_xform: profile.chunk_xform,
+            bitfield_quality_weight: 0.0,
 
             cond_tags: None,
-            cond_tag_format: TagFormat::Byte,
+            cond_tag_format: None,
             cond_block_bytes: 16,
             cond_seed: 0,
             cond_seed_hex: None,
@@ -374,6 +386,8 @@ fn bit_mapping_to_u8(m: BitMapping) -> u8 {
         BitMapping::LowpassThresh => 1,
         BitMapping::Geom => 2,
         BitMapping::Hash => 3,
+        BitMapping::LabGeom => 4,
+        BitMapping::RgbMoment => 5,
     }
 }
 
@@ -381,6 +395,7 @@ fn residual_mode_to_u8(m: ResidualMode) -> u8 {
     match m {
         ResidualMode::Xor => 1,
         ResidualMode::Sub => 2,
+        ResidualMode::Ternary => 3,
     }
 }
 
@@ -410,16 +425,21 @@ pub(super) fn reconstruct_blob(blob: &K8b1Blob) -> Result<Vec<u8>> {
         max_ticks: blob.recon.max_ticks,
         map_seed: blob.recon.map_seed,
         map_seed_hex: None,
+        feistel_rounds: FEISTEL_DEFAULT_ROUNDS,
+        write_gaps: None,
+        gap_fill_mode: None,
 
         bits_per_emission: blob.recon.bits_per_emission,
         bit_mapping: u8_to_bit_mapping(blob.recon.bit_mapping),
         bit_tau: blob.recon.bit_tau as u16,
         bit_smooth_shift: blob.recon.bit_smooth_shift,
+        start_symbol: None,
+        end_symbol: None,
 
         residual_mode: u8_to_residual_mode(blob.recon.residual_mode),
 
         cond_tags: None,
-        cond_tag_format: TagFormat::Byte,
+        cond_tag_format: None,
         cond_block_bytes: 16,
         cond_seed: 0,
         cond_seed_hex: None,
@@ -438,6 +458,8 @@ fn u8_to_bit_mapping(v: u8) -> BitMapping {
     match v {
         2 => BitMapping::Geom,
         3 => BitMapping::Hash,
+        4 => BitMapping::LabGeom,
+        5 => BitMapping::RgbMoment,
         _ => BitMapping::LowpassThresh,
     }
 }
@@ -445,6 +467,7 @@ fn u8_to_bit_mapping(v: u8) -> BitMapping {
 fn u8_to_residual_mode(v: u8) -> ResidualMode {
     match v {
         2 => ResidualMode::Sub,
+        3 => ResidualMode::Ternary,
         _ => ResidualMode::Xor,
     }
 }
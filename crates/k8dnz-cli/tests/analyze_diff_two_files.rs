@@ -0,0 +1,63 @@
+use std::process::Command;
+
+#[test]
+fn diff_two_files_reports_side_by_side_and_xor() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (a, b) = (dir.path().join("a.bin"), dir.path().join("b.bin"));
+
+    // B = A xor a short repeating key: A explains B almost entirely.
+    let plain: Vec<u8> = b"In the beginning God created the heaven and the earth. "
+        .iter()
+        .cycle()
+        .take(4096)
+        .copied()
+        .collect();
+    let noise: Vec<u8> = (0..4096u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+        .collect();
+    let resid: Vec<u8> = plain.iter().zip(&noise).map(|(p, n)| p ^ n).collect();
+    std::fs::write(&a, &noise).unwrap();
+    std::fs::write(&b, &resid).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(["analyze", "--diff-two-files", "--file-a"])
+        .arg(&a)
+        .arg("--file-b")
+        .arg(&b)
+        .output()
+        .expect("run k8dnz-cli");
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{err}");
+
+    let row = |name: &str| -> Vec<String> {
+        err.lines()
+            .find(|l| l.split_whitespace().next() == Some(name))
+            .unwrap_or_else(|| panic!("no {name} row in:\n{err}"))
+            .split_whitespace()
+            .skip(1)
+            .map(str::to_string)
+            .collect()
+    };
+    assert_eq!(row("bytes"), ["4096", "4096", "+0"]);
+    assert_eq!(row("entropy_bits").len(), 3);
+
+    assert!(err.contains("cross_entropy_bits = "));
+    assert!(err.contains("kl_divergence_bits = "));
+    let ratio: f64 = err
+        .lines()
+        .find_map(|l| l.strip_prefix("xor_vs_b_ratio     = "))
+        .and_then(|v| v.split_whitespace().next())
+        .and_then(|v| v.parse().ok())
+        .expect("xor_vs_b_ratio line");
+    assert!(
+        ratio < 0.2,
+        "A xor B should compress far better than B: {ratio}"
+    );
+
+    let missing = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(["analyze", "--diff-two-files", "--file-a"])
+        .arg(&a)
+        .output()
+        .expect("run k8dnz-cli");
+    assert!(!missing.status.success());
+}
@@ -0,0 +1,53 @@
+use std::process::Command;
+
+fn analyze(bytes: &[u8], extra: &[&str]) -> (bool, String) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let input = dir.path().join("in.bin");
+    std::fs::write(&input, bytes).unwrap();
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(["analyze", "--in"])
+        .arg(&input)
+        .args(extra)
+        .output()
+        .expect("run k8dnz-cli");
+    (
+        out.status.success(),
+        String::from_utf8_lossy(&out.stderr).into_owned(),
+    )
+}
+
+fn value(stderr: &str, key: &str) -> f64 {
+    stderr
+        .lines()
+        .find_map(|l| l.strip_prefix(key)?.trim_start().strip_prefix("= "))
+        .and_then(|v| v.split_whitespace().next())
+        .unwrap_or_else(|| panic!("missing {key}:\n{stderr}"))
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn lz_complexity_reports_phrases_and_normalized_value() {
+    let (ok, stderr) = analyze(b"aaabababaab", &["--lz-complexity"]);
+    assert!(ok, "{stderr}");
+    assert_eq!(value(&stderr, "lz78_phrases"), 6.0);
+    assert!(!stderr.contains("lz_normalized"));
+
+    let text = b"In the beginning God created the heaven and the earth. ".repeat(500);
+    let (ok, stderr) = analyze(&text, &["--lz-complexity", "--lz-normalize"]);
+    assert!(ok, "{stderr}");
+    let norm = value(&stderr, "lz_normalized");
+    assert!(norm > 0.0 && norm < 0.2, "{stderr}");
+    assert!(!stderr.contains("WARN"));
+
+    // 0..=255 then a repeat: the normalization overshoots on tiny inputs.
+    let mut short: Vec<u8> = (0..=255u8).collect();
+    short.push(0);
+    let (ok, stderr) = analyze(&short, &["--lz-complexity", "--lz-normalize"]);
+    assert!(ok, "{stderr}");
+    assert!(value(&stderr, "lz_normalized") > 1.0);
+    assert!(stderr.contains("WARN: lz_normalized > 1.0"), "{stderr}");
+
+    let (ok, _) = analyze(b"abc", &["--lz-normalize"]);
+    assert!(!ok, "--lz-normalize requires --lz-complexity");
+}
@@ -0,0 +1,93 @@
+use std::process::Command;
+
+#[test]
+fn sliding_window_entropy_separates_constant_and_uniform_halves() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let input = dir.path().join("in.bin");
+    let csv = dir.path().join("w.csv");
+
+    // 1 KiB of zeros followed by 1 KiB cycling through every byte value.
+    let mut bytes = vec![0u8; 1024];
+    bytes.extend((0..1024u32).map(|i| (i.wrapping_mul(167) % 256) as u8));
+    std::fs::write(&input, &bytes).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(["analyze", "--in"])
+        .arg(&input)
+        .args(["--sliding-window-entropy", "256", "--out-csv"])
+        .arg(&csv)
+        .output()
+        .expect("run k8dnz-cli");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--- sliding window entropy"));
+
+    let text = std::fs::read_to_string(&csv).unwrap();
+    let mut lines = text.lines();
+    assert_eq!(lines.next(), Some("window_start,window_end,entropy_bits"));
+    let rows: Vec<(usize, usize, f64)> = lines
+        .map(|l| {
+            let f: Vec<&str> = l.split(',').collect();
+            (
+                f[0].parse().unwrap(),
+                f[1].parse().unwrap(),
+                f[2].parse().unwrap(),
+            )
+        })
+        .collect();
+
+    // (2048 - 256) / 64 + 1 full windows.
+    assert_eq!(rows.len(), 29);
+    assert!(rows.iter().all(|r| r.1 - r.0 == 256));
+    assert_eq!(rows[0].2, 0.0);
+    assert!((rows.last().unwrap().2 - 8.0).abs() < 1e-9);
+}
+
+#[test]
+fn compression_profile_flags_incompressible_blocks() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let input = dir.path().join("in.bin");
+    let csv = dir.path().join("p.csv");
+
+    // Two blocks of zeros, then two blocks of xorshift noise, then a short tail.
+    let mut bytes = vec![0u8; 1024];
+    let mut x: u32 = 0x2545_F491;
+    bytes.extend((0..1024).map(|_| {
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        x as u8
+    }));
+    bytes.extend_from_slice(&[7u8; 100]);
+    std::fs::write(&input, &bytes).unwrap();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(["analyze", "--in"])
+        .arg(&input)
+        .args(["--compression-profile", "--block-size", "512", "--out-csv"])
+        .arg(&csv)
+        .output()
+        .expect("run k8dnz-cli");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{stderr}");
+    assert!(stderr.contains("blocks=5"), "{stderr}");
+    assert!(stderr.contains("incompressible=2"), "{stderr}");
+
+    let text = std::fs::read_to_string(&csv).unwrap();
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next(),
+        Some("block_index,offset,raw_bytes,zstd_bytes,ratio")
+    );
+    let rows: Vec<Vec<f64>> = lines
+        .map(|l| l.split(',').map(|f| f.parse().unwrap()).collect())
+        .collect();
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[4][1], 2048.0);
+    assert_eq!(rows[4][2], 100.0);
+    assert!(rows[0][4] < 0.1 && rows[1][4] < 0.1);
+    assert!(rows[2][4] > 0.95 && rows[3][4] > 0.95);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::{run, GENESIS_LINE};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut h = crc32fast::Hasher::new();
+    h.update(bytes);
+    h.finalize()
+}
+
+#[test]
+fn extract_recipe_roundtrips_and_verifies() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    std::fs::write(p("plain.txt"), GENESIS_LINE).unwrap();
+    assert!(
+        run(&["sim", "--emissions", "1", "--save-recipe", &p("r.k8r")])
+            .status
+            .success()
+    );
+    assert!(run(&[
+        "sim",
+        "--emissions",
+        "1",
+        "--qshift",
+        "1",
+        "--save-recipe",
+        &p("other.k8r")
+    ])
+    .status
+    .success());
+    let o = run(&[
+        "encode",
+        "--recipe",
+        &p("r.k8r"),
+        "--in",
+        &p("plain.txt"),
+        "--out",
+        &p("a.ark"),
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    let o = run(&[
+        "ark-inspect",
+        "--in",
+        &p("a.ark"),
+        "--extract-recipe",
+        "--out",
+        &p("x.k8r"),
+        "--recipe",
+        &p("r.k8r"),
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+    assert_eq!(
+        std::fs::read(p("x.k8r")).unwrap(),
+        std::fs::read(p("r.k8r")).unwrap()
+    );
+    assert!(String::from_utf8_lossy(&o.stderr).contains("recipe_file_match    = true"));
+
+    let o = run(&[
+        "ark-inspect",
+        "--in",
+        &p("a.ark"),
+        "--verify",
+        "--recipe",
+        &p("other.k8r"),
+    ]);
+    assert!(!o.status.success());
+
+    // Flip a byte inside the embedded recipe and re-seal the ark crc.
+    let mut ark = std::fs::read(p("a.ark")).unwrap();
+    ark[20] ^= 1;
+    let n = ark.len() - 4;
+    let crc = crc32(&ark[..n]);
+    ark[n..].copy_from_slice(&crc.to_le_bytes());
+    std::fs::write(p("bad.ark"), &ark).unwrap();
+
+    let o = run(&[
+        "ark-inspect",
+        "--in",
+        &p("bad.ark"),
+        "--extract-recipe",
+        "--out",
+        &p("y.k8r"),
+    ]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("malformed"));
+    assert!(!std::path::Path::new(&p("y.k8r")).exists());
+}
@@ -0,0 +1,22 @@
+use std::process::Command;
+
+use k8dnz_core::recipe::format::{self, FORMAT_VERSION_RGB};
+use k8dnz_core::recipe::keygen::{recipe_from_key, DEFAULT_SALT};
+
+#[test]
+fn generated_recipe_keeps_rgb_params_on_disk() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let out = dir.path().join("k.k8r").to_string_lossy().into_owned();
+
+    let run = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(["ark-key", "generate", "--key", "rgb-roundtrip", "--out", &out])
+        .output()
+        .expect("run k8dnz-cli");
+    assert!(run.status.success(), "{}", String::from_utf8_lossy(&run.stderr));
+
+    let loaded = format::decode(&std::fs::read(&out).unwrap()).unwrap();
+    let derived = recipe_from_key(b"rgb-roundtrip", DEFAULT_SALT);
+    assert_eq!(loaded.version, FORMAT_VERSION_RGB);
+    assert_eq!(format!("{:?}", loaded.rgb), format!("{:?}", derived.rgb));
+    assert_eq!(format::recipe_id_hex(&loaded), format::recipe_id_hex(&derived));
+}
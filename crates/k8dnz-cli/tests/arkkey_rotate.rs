@@ -0,0 +1,124 @@
+mod common;
+
+use common::{genesis, ok, run};
+
+fn rotate_args(input: &str, old: &str, new: &str, out: &str, extra: &[&str]) -> Vec<String> {
+    let mut v: Vec<String> = [
+        "ark-key",
+        "rotate",
+        "--in",
+        input,
+        "--old-recipe",
+        old,
+        "--new-recipe",
+        new,
+        "--out",
+        out,
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    v.extend(extra.iter().map(|s| s.to_string()));
+    v
+}
+
+fn strs(v: &[String]) -> Vec<&str> {
+    v.iter().map(String::as_str).collect()
+}
+
+#[test]
+fn rotate_reencodes_under_new_recipe() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let plain = genesis(3);
+    std::fs::write(p("plain.txt"), &plain).expect("write plain");
+
+    ok(&["sim", "--emissions", "1", "--save-recipe", &p("old.k8r")]);
+    ok(&[
+        "ark-key",
+        "generate",
+        "--key",
+        "rotate-test",
+        "--out",
+        &p("new.k8r"),
+    ]);
+    ok(&[
+        "encode",
+        "--recipe",
+        &p("old.k8r"),
+        "--in",
+        &p("plain.txt"),
+        "--out",
+        &p("old.ark"),
+    ]);
+
+    let rotate = |out: &str, extra: &[&str]| {
+        let args = rotate_args(&p("old.ark"), &p("old.k8r"), &p("new.k8r"), out, extra);
+        ok(&strs(&args));
+    };
+
+    let mem = p("mem.ark");
+    let streamed = p("streamed.ark");
+    rotate(&mem, &[]);
+    rotate(&streamed, &["--stream", "--block-size", "7"]);
+    assert_eq!(
+        std::fs::read(&mem).unwrap(),
+        std::fs::read(&streamed).unwrap()
+    );
+
+    ok(&["decode", "--in", &streamed, "--out", &p("back.txt")]);
+    assert_eq!(std::fs::read(p("back.txt")).unwrap(), plain);
+}
+
+#[test]
+fn rotate_aborts_without_touching_out() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    std::fs::write(p("plain.txt"), b"key rotation").expect("write plain");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &p("old.k8r")]);
+    ok(&[
+        "ark-key",
+        "generate",
+        "--key",
+        "other",
+        "--out",
+        &p("new.k8r"),
+    ]);
+    ok(&[
+        "encode",
+        "--recipe",
+        &p("old.k8r"),
+        "--in",
+        &p("plain.txt"),
+        "--out",
+        &p("old.ark"),
+    ]);
+
+    // Corrupt one data byte: the crc check must fail before anything is written.
+    let mut bytes = std::fs::read(p("old.ark")).unwrap();
+    let at = bytes.len() - 6;
+    bytes[at] ^= 0xFF;
+    std::fs::write(p("bad.ark"), &bytes).unwrap();
+
+    let out = p("out.ark");
+    for (input, extra) in [
+        (p("bad.ark"), vec!["--stream"]),
+        (p("bad.ark"), vec![]),
+        // Not enough ticks for the old keystream.
+        (p("old.ark"), vec!["--stream", "--max-ticks", "10"]),
+    ] {
+        let args = rotate_args(&input, &p("old.k8r"), &p("new.k8r"), &out, &extra);
+        let args = strs(&args);
+
+        let res = run(&args);
+        assert!(!res.status.success(), "{:?} should fail", args);
+        assert!(
+            !std::path::Path::new(&out).exists(),
+            "{:?} left --out behind",
+            args
+        );
+        assert!(!std::path::Path::new(&format!("{out}.tmp")).exists());
+    }
+}
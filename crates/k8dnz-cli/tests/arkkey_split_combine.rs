@@ -0,0 +1,80 @@
+mod common;
+
+use k8dnz_core::recipe::checksum::crc32;
+
+use common::{printed, run, stderr};
+
+#[test]
+fn split_then_combine_rebuilds_the_recipe() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, shares, out) = (p("r.k8r"), p("shares"), p("back.k8r"));
+    assert!(run(&["sim", "--emissions", "1", "--save-recipe", &recipe])
+        .status
+        .success());
+
+    let o = run(&[
+        "ark-key",
+        "split",
+        "--recipe",
+        &recipe,
+        "--shares",
+        "5",
+        "--threshold",
+        "3",
+        "--out-dir",
+        &shares,
+    ]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    let recipe_id = printed(&o, "recipe_id");
+    let share = |i: u8| format!("{shares}/share_{i}.k8rs");
+    let (s1, s2, s4) = (share(1), share(2), share(4));
+
+    let o = run(&[
+        "ark-key", "combine", "--shares", &s1, &s2, &s4, "--out", &out,
+    ]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert_eq!(printed(&o, "recipe_id"), recipe_id);
+    assert_eq!(
+        std::fs::read(&out).unwrap(),
+        std::fs::read(&recipe).unwrap()
+    );
+
+    // threshold - 1 shares are refused.
+    let o = run(&[
+        "ark-key",
+        "combine",
+        "--shares",
+        &s1,
+        &s4,
+        "--out",
+        &p("short.k8r"),
+    ]);
+    assert!(!o.status.success());
+    assert!(!std::path::Path::new(&p("short.k8r")).exists());
+
+    // A share claiming index 0 (with a valid crc32) is rejected on decode.
+    let mut bytes = std::fs::read(&s2).unwrap();
+    bytes[5] = 0;
+    let body = bytes.len() - 4;
+    let crc = crc32(&bytes[..body]);
+    bytes[body..].copy_from_slice(&crc.to_le_bytes());
+    let bad = p("share_0.k8rs");
+    std::fs::write(&bad, &bytes).unwrap();
+    let o = run(&[
+        "ark-key",
+        "combine",
+        "--shares",
+        &s1,
+        &bad,
+        &s4,
+        "--out",
+        &p("bad.k8r"),
+    ]);
+    assert!(!o.status.success());
+    assert!(
+        stderr(&o).contains("invalid header (index 0"),
+        "{}",
+        stderr(&o)
+    );
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn batch(dir: &Path, manifest: &str, extra: &[&str]) -> Output {
+    std::fs::write(dir.join("ops.toml"), manifest).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .current_dir(dir)
+        .args(["batch", "--manifest", "ops.toml"])
+        .args(extra)
+        .output()
+        .expect("run k8dnz-cli")
+}
+
+const ROUNDTRIP: &str = r#"
+[[op]]
+cmd = "sim"
+emissions = 1
+save_recipe = "r.k8r"
+
+[[op]]
+cmd = "encode"
+recipe = "r.k8r"
+in = "plain.txt"
+out = "plain.ark"
+
+[[op]]
+cmd = "decode"
+in = "plain.ark"
+out = "back.txt"
+"#;
+
+#[test]
+fn manifest_runs_encode_decode_in_order() {
+    for jobs in ["1", "3"] {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let plain = b"batch manifests replace shell scripts\n";
+        std::fs::write(dir.path().join("plain.txt"), plain).unwrap();
+
+        let o = batch(dir.path(), ROUNDTRIP, &["--parallel-ops", jobs]);
+        assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+        assert_eq!(std::fs::read(dir.path().join("back.txt")).unwrap(), plain);
+    }
+}
+
+#[test]
+fn failing_op_is_reported_and_stops_the_batch() {
+    let manifest = r#"
+[[op]]
+cmd = "decode"
+in = "missing.ark"
+out = "x.bin"
+
+[[op]]
+cmd = "sim"
+emissions = 1
+save_recipe = "after.k8r"
+"#;
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let o = batch(dir.path(), manifest, &[]);
+    assert!(!o.status.success());
+    let err = String::from_utf8_lossy(&o.stderr);
+    assert!(err.contains("op 1 (decode) failed"), "{err}");
+    assert!(!dir.path().join("after.k8r").exists());
+
+    let o = batch(dir.path(), manifest, &["--stop-on-error", "false"]);
+    assert!(!o.status.success());
+    assert!(dir.path().join("after.k8r").exists());
+}
+
+#[test]
+fn bad_manifest_fails_before_running() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manifest = "[[op]]\ncmd = \"sim\"\nsave_recipe = \"r.k8r\"\n\n[[op]]\nrecipe = \"r.k8r\"\n";
+    let o = batch(dir.path(), manifest, &[]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("missing string `cmd`"));
+    assert!(!dir.path().join("r.k8r").exists());
+}
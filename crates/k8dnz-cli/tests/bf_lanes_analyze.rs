@@ -0,0 +1,143 @@
+mod common;
+
+use common::{genesis, ok, run};
+
+/// Fits a short target with a 2-bit bitfield and returns (recipe, timemap, residual).
+fn fit(dir: &std::path::Path, encoding: &str) -> (String, String, String) {
+    let p = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    let (recipe, target) = (p("r.k8r"), p("target.txt"));
+    std::fs::write(&target, genesis(2)).unwrap();
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let (tm, res) = (p(&format!("{encoding}.tm")), p(&format!("{encoding}.bf")));
+    ok(&[
+        "timemap",
+        "fit-xor-chunked",
+        "--recipe",
+        &recipe,
+        "--target",
+        &target,
+        "--search-emissions",
+        "6000",
+        "--max-ticks",
+        "200000000",
+        "--chunk-size",
+        "32",
+        "--lookahead",
+        "500",
+        "--map",
+        "bitfield",
+        "--mode",
+        "rgbpair",
+        "--bits-per-emission",
+        "2",
+        "--bitfield-residual",
+        encoding,
+        "--out-timemap",
+        &tm,
+        "--out-residual",
+        &res,
+    ]);
+    (recipe, tm, res)
+}
+
+fn analyze_json(res: &str) -> serde_json::Value {
+    let out = ok(&[
+        "timemap", "bf-lanes", "analyze", "--in", res, "--format", "json",
+    ]);
+    serde_json::from_slice(&out.stdout).expect("analyze --format json is valid JSON")
+}
+
+#[test]
+fn analyze_bf1_text_and_json() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (recipe, tm, res) = fit(dir.path(), "packed");
+    assert_eq!(&std::fs::read(&res).unwrap()[0..4], b"BF1\0");
+
+    let out = ok(&[
+        "timemap",
+        "bf-lanes",
+        "analyze",
+        "--in",
+        &res,
+        "--recipe",
+        &recipe,
+        "--timemap",
+        &tm,
+    ]);
+    let text = String::from_utf8_lossy(&out.stderr);
+    assert!(text.contains("--- bf-lanes analyze (BF1) ---"), "{text}");
+    assert_eq!(
+        text.lines().filter(|l| l.starts_with("sym ")).count(),
+        4,
+        "{text}"
+    );
+    assert!(
+        text.contains("timemap_len             = ") && text.contains("(ok)"),
+        "{text}"
+    );
+    assert!(!text.contains("per-lane zstd"), "{text}");
+
+    let j = analyze_json(&res);
+    assert_eq!(j["format"], "BF1");
+    assert_eq!(j["in"], res.as_str());
+    assert_eq!(j["bits_per_emission"], 2);
+    let counts: Vec<u64> = j["counts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_u64().unwrap())
+        .collect();
+    assert_eq!(counts.len(), 4);
+    assert_eq!(
+        counts.iter().sum::<u64>(),
+        j["symbol_count"].as_u64().unwrap()
+    );
+    assert!(j.get("lane_zstd").is_none());
+
+    // symbol_count past the packed payload is refused before the symbols are read.
+    let mut edited = std::fs::read(&res).unwrap();
+    edited[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+    let bad = dir.path().join("bad.bf").to_string_lossy().into_owned();
+    std::fs::write(&bad, &edited).unwrap();
+    let out = run(&["timemap", "bf-lanes", "analyze", "--in", &bad]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("BF1 truncated"));
+}
+
+#[test]
+fn analyze_bf2_reports_lane_sizes_and_rejects_edited_files() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (_, _, res) = fit(dir.path(), "lanes");
+    let bytes = std::fs::read(&res).unwrap();
+    assert_eq!(&bytes[0..4], b"BF2\0");
+
+    let out = ok(&["timemap", "bf-lanes", "analyze", "--in", &res]);
+    let text = String::from_utf8_lossy(&out.stderr);
+    assert!(text.contains("--- bf-lanes analyze (BF2) ---"), "{text}");
+    assert!(text.contains("--- BF2 per-lane zstd"), "{text}");
+
+    let j = analyze_json(&res);
+    assert_eq!(j["format"], "BF2");
+    let lanes = j["lane_zstd"].as_array().expect("lane_zstd");
+    assert_eq!(lanes.len(), 4);
+    assert!(lanes.iter().all(|z| z.as_u64().unwrap() > 0));
+    let counts: u64 = j["counts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_u64().unwrap())
+        .sum();
+    assert_eq!(counts, j["symbol_count"].as_u64().unwrap());
+
+    // symbol_count claims more symbols than the lane bitsets hold: error, not panic.
+    let mut edited = bytes.clone();
+    let n = u64::from_le_bytes(edited[16..24].try_into().unwrap());
+    edited[16..24].copy_from_slice(&(n + 64).to_le_bytes());
+    let bad = dir.path().join("bad.bf").to_string_lossy().into_owned();
+    std::fs::write(&bad, &edited).unwrap();
+    let out = run(&["timemap", "bf-lanes", "analyze", "--in", &bad]);
+    assert!(!out.status.success());
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.contains("BF2") && !err.contains("panicked"), "{err}");
+}
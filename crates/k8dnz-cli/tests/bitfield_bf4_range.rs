@@ -0,0 +1,94 @@
+mod common;
+
+use common::{genesis, ok, run};
+
+#[test]
+fn bf4_reconstructs_full_and_partial_ranges() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    let plain = genesis(3);
+    std::fs::write(&target, &plain).expect("write target");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let bf = [
+        "--map",
+        "bitfield",
+        "--mode",
+        "rgbpair",
+        "--bits-per-emission",
+        "2",
+    ];
+    let mut fit = vec![
+        "timemap",
+        "fit-xor-chunked",
+        "--recipe",
+        &recipe,
+        "--target",
+        &target,
+        "--search-emissions",
+        "6000",
+        "--max-ticks",
+        "200000000",
+        "--chunk-size",
+        "32",
+        "--lookahead",
+        "500",
+        "--bitfield-residual",
+        "chunked",
+        "--bf4-chunk-symbols",
+        "100",
+    ];
+    let (tm, res) = (p("out.tm"), p("out.bf4"));
+    fit.extend(["--out-timemap", &tm, "--out-residual", &res]);
+    fit.extend(bf);
+    ok(&fit);
+
+    let bytes = std::fs::read(&res).unwrap();
+    assert_eq!(&bytes[0..4], b"BF4\0");
+
+    let recon = |out: &str, range: &[&str]| {
+        let mut args = vec![
+            "timemap",
+            "reconstruct",
+            "--recipe",
+            &recipe,
+            "--timemap",
+            &tm,
+            "--residual",
+            &res,
+            "--out",
+            out,
+        ];
+        args.extend(bf);
+        args.extend(range);
+        run(&args)
+    };
+
+    let full = p("full.bin");
+    assert!(recon(&full, &[]).status.success());
+    assert_eq!(std::fs::read(&full).unwrap(), plain);
+
+    // symbols 200..400 at 2 bits = bytes 50..100, inside chunks 2 and 3 only
+    let part = p("part.bin");
+    let out = recon(&part, &["--start-symbol", "200", "--end-symbol", "400"]);
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("decoded_chunks=2"));
+    assert_eq!(std::fs::read(&part).unwrap(), &plain[50..100]);
+
+    let bad = recon(&part, &["--start-symbol", "3"]);
+    assert!(!bad.status.success());
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("byte-aligned"));
+
+    // A corrupt header claiming ~4G chunks must be rejected before the table is allocated.
+    let mut corrupt = bytes.clone();
+    corrupt[16..24].copy_from_slice(&u64::from(u32::MAX).to_le_bytes());
+    corrupt[24..28].copy_from_slice(&1u32.to_le_bytes());
+    corrupt[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+    std::fs::write(&res, &corrupt).unwrap();
+    let bad = recon(&full, &[]);
+    assert!(!bad.status.success());
+    assert!(String::from_utf8_lossy(&bad.stderr).contains("remain for the offset table"));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{genesis, ok, run};
+
+#[test]
+fn lab_geom_bitfield_roundtrips_and_is_recorded_in_the_residual() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    let plain = genesis(3);
+    std::fs::write(&target, &plain).expect("write target");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    for bits in ["1", "4", "8"] {
+        let bf = [
+            "--map",
+            "bitfield",
+            "--mode",
+            "rgbpair",
+            "--bits-per-emission",
+            bits,
+        ];
+        let (tm, res, out) = (p("out.tm"), p("out.bf"), p("back.txt"));
+        let mut fit = vec![
+            "timemap",
+            "fit-xor-chunked",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--search-emissions",
+            "6000",
+            "--max-ticks",
+            "200000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "500",
+            "--bit-mapping",
+            "lab-geom",
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            &res,
+        ];
+        fit.extend(bf);
+        ok(&fit);
+
+        let mut recon = vec![
+            "timemap",
+            "reconstruct",
+            "--recipe",
+            &recipe,
+            "--timemap",
+            &tm,
+            "--residual",
+            &res,
+            "--out",
+            &out,
+            "--bit-mapping",
+            "lab-geom",
+        ];
+        recon.extend(bf);
+        let o = ok(&recon);
+        assert!(String::from_utf8_lossy(&o.stderr).contains("bit_mapping=LabGeom"));
+        assert_eq!(std::fs::read(&out).unwrap(), plain, "bits={bits}");
+
+        // The residual header remembers the mapping.
+        recon.truncate(recon.len() - bf.len() - 2);
+        recon.extend(bf);
+        let o = run(&recon);
+        assert!(!o.status.success());
+        assert!(String::from_utf8_lossy(&o.stderr).contains("file=LabGeom cli=Geom"));
+    }
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{genesis, ok, run};
+
+#[test]
+fn parallel_scan_picks_the_same_windows_as_the_sequential_scan() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    let plain = genesis(3);
+    std::fs::write(&target, &plain).expect("write target");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    // 1 bit/emission takes the packed-word path; the rest score by zstd or matches.
+    let configs: [&[&str]; 4] = [
+        &["--bits-per-emission", "1", "--chunk-xform", "addk"],
+        &["--bits-per-emission", "2"],
+        &["--bits-per-emission", "4", "--chunk-xform", "addk"],
+        &["--bits-per-emission", "8", "--objective", "matches"],
+    ];
+    for cfg in configs {
+        let fit = |tag: &str, extra: &[&str]| {
+            let (tm, res) = (p(&format!("{tag}.tm")), p(&format!("{tag}.bf")));
+            let mut args = vec![
+                "timemap",
+                "fit-xor-chunked",
+                "--map",
+                "bitfield",
+                "--mode",
+                "rgbpair",
+                "--recipe",
+                &recipe,
+                "--target",
+                &target,
+                "--search-emissions",
+                "6000",
+                "--max-ticks",
+                "200000000",
+                "--chunk-size",
+                "32",
+                "--lookahead",
+                "500",
+                "--out-timemap",
+                &tm,
+                "--out-residual",
+                &res,
+            ];
+            args.extend_from_slice(cfg);
+            args.extend_from_slice(extra);
+            ok(&args);
+            (std::fs::read(&tm).unwrap(), std::fs::read(&res).unwrap())
+        };
+        assert_eq!(fit("seq", &[]), fit("par", &["--parallel-scan"]), "{cfg:?}");
+    }
+
+    let o = run(&[
+        "timemap",
+        "fit-xor-chunked",
+        "--recipe",
+        &recipe,
+        "--target",
+        &target,
+        "--out-timemap",
+        &p("x.tm"),
+        "--out-residual",
+        &p("x.bin"),
+        "--parallel-scan",
+    ]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("--parallel-scan requires --map bitfield"));
+}
@@ -0,0 +1,131 @@
+// crates/k8dnz-cli/tests/common/mod.rs
+//
+// Helpers shared by the CLI integration tests. Every test file is its own crate and
+// pulls in only what it needs, so most helpers are unused in any one of them.
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+/// Genesis 1:1, the text fixture most tests fit or encode.
+pub const GENESIS_LINE: &[u8] = b"In the beginning God created the heaven and the earth.\n";
+
+/// `GENESIS_LINE` repeated `n` times.
+pub fn genesis(n: usize) -> Vec<u8> {
+    GENESIS_LINE.repeat(n)
+}
+
+/// Runs the k8dnz-cli binary; the caller checks the exit status.
+pub fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(args)
+        .output()
+        .expect("run k8dnz-cli")
+}
+
+/// `run`, failing the test (with both output streams) unless the command succeeds.
+pub fn ok(args: &[&str]) -> Output {
+    let out = run(args);
+    assert!(
+        out.status.success(),
+        "k8dnz-cli {:?} failed: status={:?}\nstdout:\n{}\nstderr:\n{}",
+        args,
+        out.status.code(),
+        String::from_utf8_lossy(&out.stdout),
+        String::from_utf8_lossy(&out.stderr)
+    );
+    out
+}
+
+pub fn stderr(o: &Output) -> String {
+    String::from_utf8_lossy(&o.stderr).into_owned()
+}
+
+/// The `<key>=<value>` value from a run's stderr.
+pub fn printed(o: &Output, key: &str) -> String {
+    let prefix = format!("{key}=");
+    stderr(o)
+        .split_whitespace()
+        .find_map(|kv| kv.strip_prefix(prefix.as_str()).map(str::to_string))
+        .unwrap_or_else(|| panic!("{key}= in stderr: {}", stderr(o)))
+}
+
+/// The `<key> = <value>` line of a tune report.
+pub fn report_value<'a>(report: &'a str, key: &str) -> &'a str {
+    report
+        .lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix(" = "))
+        .unwrap_or_else(|| panic!("missing {key} in report:\n{report}"))
+}
+
+/// `start_pos=` of every `chunk ...` line a chunked fit logs.
+pub fn chunk_starts(stderr: &[u8]) -> Vec<u64> {
+    String::from_utf8_lossy(stderr)
+        .lines()
+        .filter(|l| l.starts_with("chunk "))
+        .filter_map(|l| {
+            l.split_whitespace()
+                .find_map(|kv| kv.strip_prefix("start_pos="))
+                .and_then(|v| v.parse().ok())
+        })
+        .collect()
+}
+
+/// Packed bytes of the default recipe's first 2000 emissions.
+pub fn default_stream() -> Vec<u8> {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    e.run_emissions(2_000, 400_000_000)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect()
+}
+
+/// `timemap fit-xor --objective matches --scan-step 64` of `target` against the default
+/// recipe's first 2000 emissions; returns stderr.
+pub fn fit_xor_matches(dir: &Path, target: &[u8], extra: &[&str]) -> String {
+    let p = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    let (recipe, tgt, tm, res) = (p("r.k8r"), p("t.bin"), p("o.tm"), p("o.bin"));
+    std::fs::write(
+        &recipe,
+        k8dnz_core::recipe::format::encode(&default_recipe()),
+    )
+    .unwrap();
+    std::fs::write(&tgt, target).unwrap();
+    let mut args = vec![
+        "timemap",
+        "fit-xor",
+        "--recipe",
+        &recipe,
+        "--target",
+        &tgt,
+        "--out-timemap",
+        &tm,
+        "--out-residual",
+        &res,
+        "--search-emissions",
+        "2000",
+        "--max-ticks",
+        "400000000",
+        "--objective",
+        "matches",
+        "--scan-step",
+        "64",
+    ];
+    args.extend_from_slice(extra);
+    stderr(&ok(&args))
+}
+
+/// `key` value from the final `timemap fit-xor ok:` line.
+pub fn fit_xor_field<'a>(stderr: &'a str, key: &str) -> &'a str {
+    stderr
+        .lines()
+        .find(|l| l.starts_with("timemap fit-xor ok:"))
+        .unwrap_or_else(|| panic!("no summary line in:\n{stderr}"))
+        .split(key)
+        .nth(1)
+        .and_then(|v| v.split([' ', '/']).next())
+        .unwrap_or_else(|| panic!("missing {key} in:\n{stderr}"))
+}
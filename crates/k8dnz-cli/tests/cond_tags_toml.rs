@@ -0,0 +1,103 @@
+mod common;
+
+use common::{run, GENESIS_LINE};
+
+#[test]
+fn csv_and_toml_tags_condition_identically() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    std::fs::write(&target, GENESIS_LINE).expect("write target");
+    let sim = run(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    assert!(sim.status.success());
+
+    // 55 bytes / 16 per block = 4 blocks; the fifth entry is never applied.
+    let tags = [0x42u8, 0xFF, 0x00, 0x17, 0x99];
+    let mut csv = String::from("block,tag\n");
+    let mut toml = String::new();
+    for (i, t) in tags.iter().enumerate().rev() {
+        csv.push_str(&format!("{i},{t}\n"));
+        toml.push_str(&format!("[[tags]]\nblock = {i}\ntag = 0x{t:02x}\n"));
+    }
+    std::fs::write(p("tags.csv"), csv).unwrap();
+    std::fs::write(p("tags.toml"), toml).unwrap();
+
+    let tm = p("out.tm");
+    let fit = |tags: &str, resid: &str, extra: &[&str]| {
+        let mut args = vec![
+            "timemap",
+            "fit-xor",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            resid,
+            "--search-emissions",
+            "2000",
+            "--cond-tags",
+            tags,
+            "--cond-seed",
+            "7",
+        ];
+        args.extend_from_slice(extra);
+        run(&args)
+    };
+
+    let (resid_csv, resid_toml, dumped) = (p("csv.bin"), p("toml.bin"), p("dump.toml"));
+    let out = fit(&p("tags.csv"), &resid_csv, &["--dump-cond-tags", &dumped]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let out = fit(&p("tags.toml"), &resid_toml, &[]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(
+        std::fs::read(&resid_csv).unwrap(),
+        std::fs::read(&resid_toml).unwrap()
+    );
+
+    let dump = std::fs::read_to_string(&dumped).unwrap();
+    assert_eq!(dump.matches("[[tags]]").count(), 4);
+    assert!(dump.contains("block = 0\ntag = 0x42"));
+
+    let resid_dump = p("dump.bin");
+    let out = fit(&dumped, &resid_dump, &[]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(
+        std::fs::read(&resid_csv).unwrap(),
+        std::fs::read(&resid_dump).unwrap()
+    );
+
+    // An explicit --cond-tag-format wins over the extension: the CSV text is read
+    // as raw byte tags, so the conditioning differs.
+    let resid_raw = p("raw.bin");
+    let out = fit(&p("tags.csv"), &resid_raw, &["--cond-tag-format", "byte"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_ne!(
+        std::fs::read(&resid_csv).unwrap(),
+        std::fs::read(&resid_raw).unwrap()
+    );
+
+    std::fs::write(p("bad.toml"), "[[tags]]\nblock = 0\ntag = 256\n").unwrap();
+    let out = fit(&p("bad.toml"), &p("bad.bin"), &[]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("0..=255"));
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use sha2::{Digest, Sha256};
+
+use common::{genesis, printed, run, stderr};
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[test]
+fn dry_run_matches_the_real_encode_and_decode_without_writing() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (plain, ark, out) = (p("in.txt"), p("in.ark"), p("out.txt"));
+    let text = genesis(20);
+    std::fs::write(&plain, &text).unwrap();
+
+    let o = run(&["encode", "--in", &plain, "--dry-run", "--dry-run-hash"]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert!(stderr(&o).contains("encode dry-run ok:"));
+    let (size, hash, ticks) = (
+        printed(&o, "would_write_bytes"),
+        printed(&o, "sha256"),
+        printed(&o, "ticks"),
+    );
+
+    // --out is accepted but left alone.
+    let o = run(&["encode", "--in", &plain, "--out", &ark, "--dry-run"]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert!(!std::path::Path::new(&ark).exists());
+    assert!(!stderr(&o).contains("sha256="));
+
+    let o = run(&["encode", "--in", &plain, "--out", &ark]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    let ark_bytes = std::fs::read(&ark).unwrap();
+    assert_eq!(size, ark_bytes.len().to_string());
+    assert_eq!(hash, sha256_hex(&ark_bytes));
+    assert_eq!(ticks, printed(&o, "ticks"));
+
+    let o = run(&[
+        "decode",
+        "--in",
+        &ark,
+        "--out",
+        &out,
+        "--dry-run",
+        "--dry-run-hash",
+    ]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert!(!std::path::Path::new(&out).exists());
+    assert_eq!(printed(&o, "would_write_bytes"), text.len().to_string());
+    assert_eq!(printed(&o, "sha256"), sha256_hex(&text));
+
+    // A tick budget too small for the stream fails and says so.
+    let o = run(&["decode", "--in", &ark, "--dry-run", "--max-ticks", "1000"]);
+    assert!(!o.status.success());
+    assert!(
+        stderr(&o).contains("decode dry-run: would fail"),
+        "{}",
+        stderr(&o)
+    );
+
+    let o = run(&["encode", "--in", &plain]);
+    assert!(!o.status.success());
+    let o = run(&["encode", "--in", &plain, "--out", &ark, "--dry-run-hash"]);
+    assert!(!o.status.success());
+}
+
+#[test]
+fn failed_decode_leaves_an_existing_out_file_alone() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (plain, ark, out) = (p("in.txt"), p("in.ark"), p("out.txt"));
+    std::fs::write(&plain, genesis(20)).unwrap();
+
+    let o = run(&["encode", "--in", &plain, "--out", &ark]);
+    assert!(o.status.success(), "{}", stderr(&o));
+
+    std::fs::write(&out, b"keep me").unwrap();
+    let o = run(&["decode", "--in", &ark, "--out", &out, "--max-ticks", "1000"]);
+    assert!(!o.status.success());
+    assert_eq!(std::fs::read(&out).unwrap(), b"keep me");
+    assert!(!std::path::Path::new(&format!("{out}.tmp")).exists());
+
+    let o = run(&["decode", "--in", &ark, "--out", &out]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert_eq!(std::fs::read(&out).unwrap(), std::fs::read(&plain).unwrap());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{genesis, run, stderr};
+
+// The 1100-byte fixture needs ~4.3M ticks; at 4.6M the pessimistic estimate says 900.
+const TIGHT_TICKS: &str = "4600000";
+
+#[test]
+fn short_estimate_fails_before_encoding() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (plain, ark) = (p("in.txt"), p("in.ark"));
+    let text = genesis(20);
+    std::fs::write(&plain, &text).unwrap();
+
+    let o = run(&[
+        "encode",
+        "--in",
+        &plain,
+        "--out",
+        &ark,
+        "--max-ticks",
+        TIGHT_TICKS,
+    ]);
+    assert!(!o.status.success());
+    let err = stderr(&o);
+    assert!(err.contains("estimated_emissions=900 (need 1100)"), "{err}");
+    assert!(err.contains("looks too low"), "{err}");
+    assert!(!std::path::Path::new(&ark).exists());
+    assert!(!std::path::Path::new(&format!("{ark}.tmp")).exists());
+}
+
+#[test]
+fn skip_budget_check_warns_and_encodes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (plain, ark, out) = (p("in.txt"), p("in.ark"), p("out.txt"));
+    let text = genesis(20);
+    std::fs::write(&plain, &text).unwrap();
+
+    let o = run(&[
+        "encode",
+        "--in",
+        &plain,
+        "--out",
+        &ark,
+        "--max-ticks",
+        TIGHT_TICKS,
+        "--skip-budget-check",
+    ]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert!(stderr(&o).contains("WARN: max_ticks=4600000 may be too low"));
+
+    let o = run(&["decode", "--in", &ark, "--out", &out]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert_eq!(std::fs::read(&out).unwrap(), text);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use k8dnz_core::TimingMap;
+
+use common::{chunk_starts, genesis, run, GENESIS_LINE};
+
+#[test]
+fn anchor_pins_first_chunk_and_is_range_checked() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    std::fs::write(&target, genesis(2)).expect("write target");
+    let sim = run(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    assert!(sim.status.success());
+
+    let fit = |anchor: &str| {
+        run(&[
+            "timemap",
+            "fit-xor-chunked",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &p("out.tm"),
+            "--out-residual",
+            &p("out.bin"),
+            "--search-emissions",
+            "6000",
+            "--max-ticks",
+            "200000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "500",
+            "--anchor-first-chunk-at",
+            anchor,
+        ])
+    };
+
+    let out = fit("4000");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let starts = chunk_starts(&out.stderr);
+    assert!(!starts.is_empty());
+    assert!(
+        (4000..=4500).contains(&starts[0]),
+        "first start {}",
+        starts[0]
+    );
+    assert!(starts.windows(2).all(|w| w[1] > w[0]));
+
+    let out = fit("6001");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--anchor-first-chunk-at"));
+}
+
+#[test]
+fn anchor_respects_start_emission_offset() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    std::fs::write(&target, GENESIS_LINE).expect("write target");
+    assert!(run(&["sim", "--emissions", "1", "--save-recipe", &recipe])
+        .status
+        .success());
+
+    let fit = |anchor: &str| {
+        run(&[
+            "timemap",
+            "fit-xor-chunked",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &p("out.tm"),
+            "--out-residual",
+            &p("out.bin"),
+            "--start-emission",
+            "1000",
+            "--search-emissions",
+            "6000",
+            "--max-ticks",
+            "200000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "200",
+            "--anchor-first-chunk-at",
+            anchor,
+        ])
+    };
+
+    let out = fit("5500");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!((5500..=5700).contains(&chunk_starts(&out.stderr)[0]));
+    for anchor in ["999", "6001"] {
+        let out = fit(anchor);
+        assert!(!out.status.success(), "anchor {anchor} accepted");
+        assert!(String::from_utf8_lossy(&out.stderr).contains("[1000, 6000]"));
+    }
+}
+
+#[test]
+fn bitfield_anchor_pins_first_emission() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    let tm = p("out.tm");
+    std::fs::write(&target, GENESIS_LINE).expect("write target");
+    assert!(run(&["sim", "--emissions", "1", "--save-recipe", &recipe])
+        .status
+        .success());
+
+    let fit = |anchor: &str| {
+        run(&[
+            "timemap",
+            "fit-xor-chunked",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            &p("out.bf"),
+            "--map",
+            "bitfield",
+            "--mode",
+            "rgbpair",
+            "--bits-per-emission",
+            "2",
+            "--search-emissions",
+            "6000",
+            "--max-ticks",
+            "200000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "300",
+            "--anchor-first-chunk-at",
+            anchor,
+        ])
+    };
+
+    let out = fit("3000");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(String::from_utf8_lossy(&out.stderr).contains("anchor_first_chunk_at=3000"));
+    let first = TimingMap::decode_auto(&std::fs::read(&tm).unwrap())
+        .unwrap()
+        .indices[0];
+    assert!((3000..=3300).contains(&first), "first emission {first}");
+
+    let out = fit("6001");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--anchor-first-chunk-at"));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{chunk_starts, genesis, ok};
+
+/// Mean distance from the end of one chunk to the start of the next.
+fn mean_gap(starts: &[u64], chunk: u64) -> f64 {
+    let gaps: Vec<u64> = starts.windows(2).map(|w| w[1] - (w[0] + chunk)).collect();
+    gaps.iter().sum::<u64>() as f64 / gaps.len() as f64
+}
+
+#[test]
+fn diversity_penalty_spreads_chunk_starts() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    std::fs::write(&target, genesis(6)).expect("write target");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let fit = |weight: &str| {
+        let out = ok(&[
+            "timemap",
+            "fit-xor-chunked",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &p("out.tm"),
+            "--out-residual",
+            &p("out.bin"),
+            "--search-emissions",
+            "30000",
+            "--max-ticks",
+            "200000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "4000",
+            "--diversity-window",
+            "2000",
+            "--diversity-penalty",
+            weight,
+        ]);
+        chunk_starts(&out.stderr)
+    };
+
+    let plain = fit("0");
+    let spread = fit("200");
+    assert_eq!(plain.len(), spread.len());
+    assert!(plain.len() >= 8, "too few chunks: {}", plain.len());
+
+    let (g0, g1) = (mean_gap(&plain, 32), mean_gap(&spread, 32));
+    assert!(g1 > g0 * 1.5, "mean gap without={} with={}", g0, g1);
+    assert!(spread.last() > plain.last());
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use k8dnz_cli::io::timemap::read_timemap;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+use common::{ok, run};
+
+const MAX_TICKS: u64 = 80_000_000;
+
+/// Every 7th byte of the recipe's own stream, so the greedy fit always succeeds.
+fn target_bytes() -> Vec<u8> {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    e.run_emissions(120, MAX_TICKS)
+        .iter()
+        .step_by(7)
+        .map(|t| t.pack_byte())
+        .collect()
+}
+
+#[test]
+fn run_until_byte_matches_timemap_fit() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, target, tm) = (p("r.k8r"), p("target.txt"), p("fit.tm"));
+
+    std::fs::write(
+        &recipe,
+        k8dnz_core::recipe::format::encode(&default_recipe()),
+    )
+    .expect("write recipe");
+    let bytes = target_bytes();
+    std::fs::write(&target, &bytes).expect("write target");
+
+    ok(&[
+        "timemap", "fit", "--recipe", &recipe, "--target", &target, "--out", &tm,
+    ]);
+    let fitted = read_timemap(&tm).expect("read timemap").indices;
+
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let by_byte: Vec<u64> = bytes
+        .iter()
+        .map(|&b| {
+            let (idx, tok) = e.run_until_byte(b, MAX_TICKS).expect("byte found");
+            assert_eq!(tok.pack_byte(), b);
+            idx
+        })
+        .collect();
+    assert_eq!(by_byte, fitted);
+
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let seq = e
+        .run_until_sequence(&bytes, MAX_TICKS)
+        .expect("sequence found");
+    let seq_idx: Vec<u64> = seq.iter().map(|(i, _)| *i).collect();
+    assert_eq!(seq_idx, fitted);
+}
+
+#[test]
+fn run_until_byte_stops_at_max_ticks() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    assert!(e.run_until_sequence(&target_bytes(), 1_000).is_none());
+    assert!(e.stats.ticks >= 1_000);
+}
+
+#[test]
+fn run_until_byte_within_stops_at_the_emission_bound() {
+    let stream: Vec<u8> = Engine::new(default_recipe())
+        .unwrap()
+        .run_emissions(40, MAX_TICKS)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    let missing = (0..=255u8)
+        .find(|b| !stream.contains(b))
+        .expect("a byte absent from 40 emissions");
+
+    let mut e = Engine::new(default_recipe()).unwrap();
+    assert!(e.run_until_byte_within(missing, 40, MAX_TICKS).is_none());
+    assert_eq!(e.stats.emissions, 40);
+
+    let mut e = Engine::new(default_recipe()).unwrap();
+    assert_eq!(
+        e.run_until_byte_within(stream[39], 40, MAX_TICKS)
+            .map(|(i, _)| i),
+        stream
+            .iter()
+            .position(|&b| b == stream[39])
+            .map(|i| i as u64)
+    );
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, target) = (p("r.k8r"), p("target.bin"));
+    std::fs::write(
+        &recipe,
+        k8dnz_core::recipe::format::encode(&default_recipe()),
+    )
+    .unwrap();
+    std::fs::write(&target, [stream[0], missing]).unwrap();
+    let o = run(&[
+        "timemap",
+        "fit",
+        "--recipe",
+        &recipe,
+        "--target",
+        &target,
+        "--out",
+        &p("o.tm"),
+        "--search-emissions",
+        "40",
+    ]);
+    assert!(!o.status.success());
+    let err = String::from_utf8_lossy(&o.stderr);
+    assert!(err.contains("matched 1/2 bytes"), "{err}");
+    assert!(err.contains("searched_emissions=40 "), "{err}");
+    let seen = stream.iter().filter(|&&b| b == stream[0]).count();
+    assert!(err.contains(&format!("first_seen={seen} ")), "{err}");
+}
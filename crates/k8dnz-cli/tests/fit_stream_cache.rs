@@ -0,0 +1,153 @@
+mod common;
+
+use common::{genesis, run};
+
+fn field(stderr: &str, key: &str) -> Option<u64> {
+    stderr
+        .split_whitespace()
+        .find_map(|kv| kv.strip_prefix(key))
+        .and_then(|v| v.parse().ok())
+}
+
+#[test]
+fn capped_stream_cache_evicts_and_matches_uncapped_fit() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    std::fs::write(&target, genesis(3)).expect("write target");
+    let sim = run(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    assert!(sim.status.success());
+
+    let fit = |tag: &str, cache: Option<&str>| {
+        let (tm, res) = (p(&format!("{tag}.tm")), p(&format!("{tag}.bin")));
+        let mut args = vec![
+            "timemap",
+            "fit-xor-chunked",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            &res,
+            "--search-emissions",
+            "6000",
+            "--max-ticks",
+            "200000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "300",
+        ];
+        if let Some(mb) = cache {
+            args.extend(["--stream-cache-size", mb]);
+        }
+        let out = run(&args);
+        let stderr = String::from_utf8_lossy(&out.stderr).into_owned();
+        assert!(out.status.success(), "{stderr}");
+        (
+            std::fs::read(tm).unwrap(),
+            std::fs::read(res).unwrap(),
+            stderr,
+        )
+    };
+
+    let (tm_full, res_full, _) = fit("full", None);
+    let (tm_cap, res_cap, stderr) = fit("cap", Some("0"));
+    assert_eq!(tm_cap, tm_full);
+    assert_eq!(res_cap, res_full);
+
+    let line = stderr
+        .lines()
+        .find(|l| l.starts_with("stream_cache:"))
+        .expect("stream_cache line");
+    let evicted = field(line, "evicted_bytes=").unwrap();
+    let ram = field(line, "ram_bytes=").unwrap();
+    assert!(evicted > 0, "{line}");
+    assert!(ram < field(line, "stream_bytes=").unwrap(), "{line}");
+}
+
+#[test]
+fn ring_buffer_fit_matches_full_stream_fit() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    std::fs::write(&target, genesis(3)).expect("write target");
+    let sim = run(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    assert!(sim.status.success());
+
+    let fit = |tag: &str, mode: &str, ring: bool| {
+        let (tm, res) = (p(&format!("{tag}.tm")), p(&format!("{tag}.bin")));
+        let mut args = vec![
+            "timemap",
+            "fit-xor-chunked",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            &res,
+            "--mode",
+            mode,
+            "--search-emissions",
+            "20000",
+            "--max-ticks",
+            "2000000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "300",
+        ];
+        if ring {
+            args.push("--use-ring-buffer");
+        }
+        let out = run(&args);
+        let stderr = String::from_utf8_lossy(&out.stderr).into_owned();
+        assert!(out.status.success(), "{stderr}");
+        (
+            std::fs::read(tm).unwrap(),
+            std::fs::read(res).unwrap(),
+            stderr,
+        )
+    };
+
+    for mode in ["pair", "rgbpair"] {
+        let (tm_full, res_full, _) = fit(&format!("{mode}_full"), mode, false);
+        let (tm_ring, res_ring, stderr) = fit(&format!("{mode}_ring"), mode, true);
+        assert_eq!(tm_ring, tm_full, "{mode}");
+        assert_eq!(res_ring, res_full, "{mode}");
+
+        let line = stderr
+            .lines()
+            .find(|l| l.starts_with("ring_buffer:"))
+            .expect("ring_buffer line");
+        let size = field(line, "size_bytes=").unwrap();
+        assert!(size <= 300 + 32 + 6, "{line}");
+        // Only the windows the chunks reached were generated, not the whole search stream.
+        assert!(field(line, "stream_bytes=").unwrap() < 20_000, "{line}");
+    }
+
+    let out = run(&[
+        "timemap",
+        "fit-xor-chunked",
+        "--recipe",
+        &recipe,
+        "--target",
+        &target,
+        "--out-timemap",
+        &p("x.tm"),
+        "--out-residual",
+        &p("x.bin"),
+        "--use-ring-buffer",
+        "--stream-cache-size",
+        "1",
+    ]);
+    assert!(!out.status.success());
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::{genesis, ok, run};
+
+#[test]
+fn trans_penalty_calibrate_runs_on_bitfield_and_is_rejected_elsewhere() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    std::fs::write(&target, genesis(3)).expect("write target");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let fit = |map: &str| {
+        run(&[
+            "timemap",
+            "fit-xor-chunked",
+            "--map",
+            map,
+            "--mode",
+            "rgbpair",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--search-emissions",
+            "6000",
+            "--max-ticks",
+            "200000000",
+            "--chunk-size",
+            "32",
+            "--lookahead",
+            "500",
+            "--out-timemap",
+            &p("out.tm"),
+            "--out-residual",
+            &p("out.bin"),
+            "--trans-penalty-calibrate",
+            "--calibration-chunks",
+            "2",
+        ])
+    };
+
+    let o = fit("bitfield");
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+    assert!(String::from_utf8_lossy(&o.stderr).contains("trans_penalty_calibrated = "));
+
+    let o = fit("none");
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr)
+        .contains("--trans-penalty-calibrate requires --map bitfield"));
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{default_stream, fit_xor_field, fit_xor_matches};
+
+#[test]
+fn multi_start_emission_keeps_best_start() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let s = default_stream();
+    // Only a scan starting at 1500 lands on the exact window with --scan-step 64.
+    let out = fit_xor_matches(
+        dir.path(),
+        &s[1500..1756],
+        &["--multi-start-emission", "0,700,1500"],
+    );
+    assert!(out.contains("--- multi-start scoreboard ---"), "{out}");
+    for start in ["0", "700", "1500"] {
+        let row = format!("start_emission={start} window_start_pos=");
+        assert_eq!(out.matches(row.as_str()).count(), 1, "{out}");
+    }
+    assert!(out.contains("best_start_emission = 1500"), "{out}");
+    assert_eq!(fit_xor_field(&out, "window_start_pos="), "1500");
+    let resid = std::fs::read(dir.path().join("o.bin")).unwrap();
+    assert!(resid.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn start_emission_sequence_scans_each_start() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let s = default_stream();
+    let out = fit_xor_matches(
+        dir.path(),
+        &s[1500..1756],
+        &[
+            "--start-emission",
+            "1300",
+            "--start-emission-step",
+            "100",
+            "--start-emission-count",
+            "3",
+        ],
+    );
+    assert_eq!(
+        out.matches("effective_bytes_no_recipe=").count(),
+        3,
+        "{out}"
+    );
+    assert!(
+        out.contains("start_emission=1400 window_start_pos="),
+        "{out}"
+    );
+    assert!(out.contains("best_start_emission = 1500"), "{out}");
+    assert_eq!(fit_xor_field(&out, "window_start_pos="), "1500");
+}
+
+#[test]
+fn single_start_prints_no_multi_scoreboard() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let s = default_stream();
+    let out = fit_xor_matches(dir.path(), &s[1024..1280], &[]);
+    assert!(!out.contains("multi-start"), "{out}");
+    assert_eq!(fit_xor_field(&out, "window_start_pos="), "1024");
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::{default_stream, fit_xor_field, fit_xor_matches};
+
+#[test]
+fn progressive_refinement_finds_off_grid_window() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let s = default_stream();
+    // Head aligned to the coarse grid (1024), tail to an off-grid offset (1026).
+    let target: Vec<u8> = (0..260)
+        .map(|i| if i < 60 { s[1024 + i] } else { s[1026 + i] })
+        .collect();
+
+    let coarse = fit_xor_matches(dir.path(), &target, &[]);
+    assert_eq!(fit_xor_field(&coarse, "window_start_pos="), "1024");
+
+    let fine = fit_xor_matches(dir.path(), &target, &["--progressive-refinement"]);
+    assert_eq!(fine.matches("progressive level=").count(), 4, "{fine}");
+    assert!(fine.contains("progressive level=3 step=1 "), "{fine}");
+    assert_eq!(fit_xor_field(&fine, "window_start_pos="), "1026");
+    let m: u64 = fit_xor_field(&fine, " matches=").parse().unwrap();
+    assert!(m >= 200, "{fine}");
+}
+
+#[test]
+fn progressive_refinement_short_circuits_on_exact_match() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let s = default_stream();
+    let out = fit_xor_matches(dir.path(), &s[1024..1280], &["--progressive-refinement"]);
+    assert!(out.contains("refinement skipped"), "{out}");
+    assert!(!out.contains("progressive level=1"), "{out}");
+    assert_eq!(fit_xor_field(&out, "window_start_pos="), "1024");
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{ok, run};
+
+#[test]
+fn fibonacci_law_reconstructs_and_spaces_late_chunks() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, target, tm, resid) = (p("r.k8r"), p("t.txt"), p("f.tm"), p("f.bin"));
+
+    // 40 bytes * 8 bits = 320 symbols = 20 chunks of 16.
+    std::fs::write(&target, b"In the beginning God created the heaven.").unwrap();
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let gen = |scale: &str, search: &str| {
+        run(&[
+            "timemap",
+            "gen-law",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            &resid,
+            "--gen-mode",
+            "fibonacci",
+            "--chunk-size",
+            "16",
+            "--fibonacci-scale",
+            scale,
+            "--search-emissions",
+            search,
+            "--max-ticks",
+            "500000000",
+        ])
+    };
+    let out = gen("1", "12000");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    ok(&[
+        "timemap",
+        "reconstruct",
+        "--recipe",
+        &recipe,
+        "--timemap",
+        &tm,
+        "--residual",
+        &resid,
+        "--out",
+        &p("out.txt"),
+        "--mode",
+        "rgbpair",
+        "--map",
+        "bitfield",
+        "--bits-per-emission",
+        "1",
+        "--max-ticks",
+        "500000000",
+    ]);
+    assert_eq!(
+        std::fs::read(p("out.txt")).unwrap(),
+        std::fs::read(&target).unwrap()
+    );
+
+    ok(&["timemap", "export-text", "--in", &tm, "--out", &p("tm.txt")]);
+    let idx: Vec<u64> = std::fs::read_to_string(p("tm.txt"))
+        .unwrap()
+        .lines()
+        .map(|l| l.parse().unwrap())
+        .collect();
+    assert_eq!(idx.len(), 320);
+    // Early chunks are pushed up against each other; later ones sit on F(k).
+    assert_eq!(idx[0], 1);
+    assert_eq!(idx[18 * 16], 6765);
+    assert_eq!(idx[19 * 16], 10946);
+
+    // Scale 2 puts the last chunk past the emission budget.
+    let out = gen("2", "12000");
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("fibonacci: chunk"));
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::ok;
+
+#[test]
+fn poisson_law_reconstructs_and_replays_from_its_seed() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, target, resid) = (p("r.k8r"), p("t.txt"), p("f.bin"));
+
+    // 40 bytes * 8 bits = 320 symbols = 20 chunks of 16.
+    std::fs::write(&target, b"In the beginning God created the heaven.").unwrap();
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let indices = |seed: &str, scale: &str| -> Vec<u64> {
+        let tm = p(&format!("s{seed}_{scale}.tm"));
+        ok(&[
+            "timemap",
+            "gen-law",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            &resid,
+            "--gen-mode",
+            "poisson",
+            "--chunk-size",
+            "16",
+            "--rate",
+            "200",
+            "--seed",
+            seed,
+            "--poisson-scale",
+            scale,
+            "--search-emissions",
+            "20000",
+            "--max-ticks",
+            "500000000",
+        ]);
+        let txt = p("tm.txt");
+        ok(&["timemap", "export-text", "--in", &tm, "--out", &txt]);
+        std::fs::read_to_string(txt)
+            .unwrap()
+            .lines()
+            .map(|l| l.parse().unwrap())
+            .collect()
+    };
+
+    let idx = indices("12345", "1");
+    assert_eq!(idx.len(), 320);
+    ok(&[
+        "timemap",
+        "reconstruct",
+        "--recipe",
+        &recipe,
+        "--timemap",
+        &p("s12345_1.tm"),
+        "--residual",
+        &resid,
+        "--out",
+        &p("out.txt"),
+        "--mode",
+        "rgbpair",
+        "--map",
+        "bitfield",
+        "--bits-per-emission",
+        "1",
+        "--max-ticks",
+        "500000000",
+    ]);
+    assert_eq!(
+        std::fs::read(p("out.txt")).unwrap(),
+        std::fs::read(&target).unwrap()
+    );
+
+    // Starts are irregular, strictly increasing and fixed by the seed.
+    let starts: Vec<u64> = idx.iter().step_by(16).copied().collect();
+    assert_eq!(starts[0], 0);
+    let gaps: Vec<u64> = starts.windows(2).map(|w| w[1] - w[0]).collect();
+    assert!(gaps.iter().all(|&g| g >= 16));
+    assert!(gaps.windows(2).any(|w| w[0] != w[1]));
+    assert_eq!(indices("12345", "1"), idx);
+    assert_ne!(indices("54321", "1"), idx);
+
+    // Scaling the arrivals by 2 roughly doubles the span.
+    let wide = indices("12345", "2");
+    let (span, wide_span) = (starts[19], wide[19 * 16]);
+    assert!(wide_span > span * 3 / 2, "span {span} -> {wide_span}");
+}
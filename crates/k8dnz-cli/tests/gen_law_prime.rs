@@ -0,0 +1,88 @@
+mod common;
+
+use common::{ok, run};
+
+#[test]
+fn prime_laws_place_chunks_on_scaled_primes_and_reconstruct() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, target, tm, resid) = (p("r.k8r"), p("t.txt"), p("p.tm"), p("p.bin"));
+
+    // 40 bytes * 8 bits = 320 symbols = 20 chunks of 16.
+    std::fs::write(&target, b"In the beginning God created the heaven.").unwrap();
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let indices = |mode: &str, scale: &str, search: &str| -> Result<Vec<u64>, String> {
+        let out = run(&[
+            "timemap",
+            "gen-law",
+            "--recipe",
+            &recipe,
+            "--target",
+            &target,
+            "--out-timemap",
+            &tm,
+            "--out-residual",
+            &resid,
+            "--gen-mode",
+            mode,
+            "--chunk-size",
+            "16",
+            "--twin-prime-scale",
+            scale,
+            "--search-emissions",
+            search,
+            "--max-ticks",
+            "500000000",
+        ]);
+        if !out.status.success() {
+            return Err(String::from_utf8_lossy(&out.stderr).into_owned());
+        }
+        ok(&[
+            "timemap",
+            "reconstruct",
+            "--recipe",
+            &recipe,
+            "--timemap",
+            &tm,
+            "--residual",
+            &resid,
+            "--out",
+            &p("out.txt"),
+            "--mode",
+            "rgbpair",
+            "--map",
+            "bitfield",
+            "--bits-per-emission",
+            "1",
+            "--max-ticks",
+            "500000000",
+        ]);
+        assert_eq!(
+            std::fs::read(p("out.txt")).unwrap(),
+            std::fs::read(&target).unwrap()
+        );
+        ok(&["timemap", "export-text", "--in", &tm, "--out", &p("tm.txt")]);
+        Ok(std::fs::read_to_string(p("tm.txt"))
+            .unwrap()
+            .lines()
+            .map(|l| l.parse().unwrap())
+            .collect())
+    };
+
+    // Lower twin primes 3, 5, 11, ..., 311 (the 20th), scaled by 10.
+    let idx = indices("twin-prime", "10", "4000").unwrap();
+    assert_eq!(idx.len(), 320);
+    assert_eq!(idx[0], 30);
+    assert_eq!(idx[16], 50);
+    assert_eq!(idx[19 * 16], 3110);
+
+    // Primes 2, 3, ...: chunk 1 (30) lands inside chunk 0 (20..36) and is pushed up.
+    let idx = indices("prime", "10", "4000").unwrap();
+    assert_eq!(idx[0], 20);
+    assert_eq!(idx[16], 36);
+    assert_eq!(idx[19 * 16], 710);
+
+    let err = indices("twin-prime", "10", "3000").unwrap_err();
+    assert!(err.contains("twin-prime: chunk"), "{err}");
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use k8dnz_cli::cmd::timemap::args::{MapMode, ResidualMode};
+use k8dnz_cli::cmd::timemap::mapping::map_byte;
+use k8dnz_cli::cmd::timemap::residual::{apply_mapped_residual_byte, make_mapped_residual_byte};
+use k8dnz_core::signal::fit::{feistel_permute, feistel_unpermute};
+
+use common::{run, GENESIS_LINE};
+
+#[test]
+fn feistel_is_a_position_keyed_permutation() {
+    for (seed, pos, rounds) in [
+        (0u64, 0u64, 4u8),
+        (0xDEAD_BEEF, 17, 4),
+        (7, 1 << 40, 1),
+        (7, 3, 9),
+    ] {
+        let mut seen = [false; 256];
+        for raw in 0..=255u8 {
+            let m = map_byte(MapMode::Feistel, seed, pos, raw, rounds);
+            assert_eq!(m, feistel_permute(seed, pos, raw, rounds));
+            assert!(
+                !seen[m as usize],
+                "collision seed={seed} pos={pos} raw={raw}"
+            );
+            seen[m as usize] = true;
+            assert_eq!(feistel_unpermute(seed, pos, m, rounds), raw);
+
+            for plain in 0..=255u8 {
+                for mode in [ResidualMode::Xor, ResidualMode::Sub] {
+                    let resid = make_mapped_residual_byte(
+                        mode,
+                        MapMode::Feistel,
+                        seed,
+                        pos,
+                        rounds,
+                        m,
+                        plain,
+                    );
+                    let back = apply_mapped_residual_byte(
+                        mode,
+                        MapMode::Feistel,
+                        seed,
+                        pos,
+                        rounds,
+                        m,
+                        resid,
+                    );
+                    assert_eq!(back, plain);
+                }
+            }
+        }
+    }
+
+    let at = |pos| {
+        (0..=255u8)
+            .map(|b| feistel_permute(1, pos, b, 4))
+            .collect::<Vec<_>>()
+    };
+    assert_ne!(at(0), at(1));
+}
+
+#[test]
+fn fit_xor_feistel_reconstructs() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, target, tm, resid, out) =
+        (p("r.k8r"), p("t.txt"), p("t.tm"), p("t.res"), p("out.txt"));
+
+    let plain = GENESIS_LINE;
+    std::fs::write(&target, plain).unwrap();
+    assert!(run(&["sim", "--emissions", "1", "--save-recipe", &recipe])
+        .status
+        .success());
+
+    let map = [
+        "--map",
+        "feistel",
+        "--map-seed",
+        "99",
+        "--feistel-rounds",
+        "6",
+    ];
+    let mut fit = vec![
+        "timemap",
+        "fit-xor",
+        "--recipe",
+        &recipe,
+        "--target",
+        &target,
+        "--out-timemap",
+        &tm,
+        "--out-residual",
+        &resid,
+        "--search-emissions",
+        "2000",
+    ];
+    fit.extend_from_slice(&map);
+    let o = run(&fit);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    let mut recon = vec![
+        "timemap",
+        "reconstruct",
+        "--recipe",
+        &recipe,
+        "--timemap",
+        &tm,
+        "--residual",
+        &resid,
+        "--out",
+        &out,
+    ];
+    recon.extend_from_slice(&map);
+    let o = run(&recon);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+    assert_eq!(std::fs::read(&out).unwrap(), plain);
+}
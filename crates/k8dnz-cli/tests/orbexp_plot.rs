@@ -0,0 +1,64 @@
+use std::process::Command;
+
+#[test]
+fn plot_marks_bodies_and_meets() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let txt = dir.path().join("orbit.txt");
+
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args([
+            "orbexp", "plot", "--modn", "100", "--step-a", "3", "--step-c", "7", "--ticks", "60",
+            "--width", "100", "--height", "5", "--output",
+        ])
+        .arg(&txt)
+        .output()
+        .expect("run k8dnz-cli");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("period = mod / gcd(mod, d) = 100 / 4 = 25"));
+    assert!(stdout.contains("... 56 more ticks"));
+    assert!(!stdout.contains("---MEET---"));
+
+    let text = std::fs::read_to_string(&txt).unwrap();
+    let rows: Vec<(u64, &str)> = text
+        .lines()
+        .filter_map(|l| {
+            let (t, row) = l.trim_start().split_once(' ')?;
+            Some((t.parse().ok()?, row))
+        })
+        .collect();
+    assert_eq!(rows.len(), 61);
+
+    // width == modn: one cell per position, so the row is the exact phases.
+    for &(t, row) in &rows {
+        assert_eq!(row.len(), 100);
+        let (pa, pc) = ((3 * t % 100) as usize, (7 * t % 100) as usize);
+        let bytes = row.as_bytes();
+        if pa == pc {
+            assert_eq!(bytes[pa], b'@', "t={t}");
+        } else {
+            assert_eq!((bytes[pa], bytes[pc]), (b'A', b'C'), "t={t}");
+        }
+        assert_eq!(
+            bytes.iter().filter(|&&b| b != b'.').count(),
+            1 + (pa != pc) as usize
+        );
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    let meets: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with("---MEET---"))
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(meets.len(), 2);
+    assert!(lines[meets[0] - 1].trim_start().starts_with("25 "));
+    assert_eq!(lines[meets[1]], "---MEET--- t=50");
+    assert!(text.ends_with("meets=2 at t=[25, 50] (closed-form period 25)\n"));
+}
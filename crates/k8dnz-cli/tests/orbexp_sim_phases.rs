@@ -0,0 +1,42 @@
+use std::process::Command;
+
+#[test]
+fn orbexp_sim_writes_phase_trajectory() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let csv = dir.path().join("phases.csv").to_string_lossy().into_owned();
+
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args([
+            "orbexp",
+            "sim",
+            "--modn",
+            "97",
+            "--step-a",
+            "5",
+            "--step-c",
+            "12",
+            "--steps",
+            "200",
+            "--output-phases",
+            &csv,
+        ])
+        .output()
+        .expect("run k8dnz-cli");
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("t_sim       = 97"), "{stdout}");
+    assert!(stdout.contains("t_closed    = 97"), "{stdout}");
+    assert!(stdout.contains("meets       = 2"), "{stdout}");
+
+    let text = std::fs::read_to_string(&csv).unwrap();
+    let rows: Vec<&str> = text.lines().collect();
+    assert_eq!(rows[0], "t,phase_a,phase_c,meet");
+    assert_eq!(rows.len(), 202);
+    assert_eq!(rows[1], "0,0,0,1");
+    assert_eq!(rows[2], "1,5,12,0");
+    assert_eq!(rows[98], "97,0,0,1");
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use k8dnz_cli::io::timemap::write_tm1;
+use k8dnz_core::signal::timing_map::TimingMap;
+
+use common::run;
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+#[test]
+fn write_gaps_places_bytes_at_timemap_positions() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, tm, resid) = (p("r.k8r"), p("t.tm"), p("t.res"));
+
+    assert!(run(&["sim", "--emissions", "1", "--save-recipe", &recipe])
+        .status
+        .success());
+
+    // Subset timemap: positions 100..200 step 5 plus a few stragglers.
+    let indices: Vec<u64> = [3u64, 40]
+        .into_iter()
+        .chain((100..200).step_by(5))
+        .chain([499])
+        .collect();
+    write_tm1(&tm, &TimingMap::new(indices.clone()).unwrap()).unwrap();
+    std::fs::write(
+        &resid,
+        (0..indices.len()).map(|i| i as u8).collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    let reconstruct = |out: &str, extra: &[&str]| {
+        let mut args = vec![
+            "timemap",
+            "reconstruct",
+            "--recipe",
+            &recipe,
+            "--timemap",
+            &tm,
+            "--residual",
+            &resid,
+            "--out",
+            out,
+        ];
+        args.extend_from_slice(extra);
+        let o = run(&args);
+        assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+        std::fs::read(out).unwrap()
+    };
+
+    let dense = reconstruct(&p("dense.bin"), &[]);
+    assert_eq!(dense.len(), indices.len());
+
+    let check = |got: &[u8], gap: &dyn Fn(usize) -> u8| {
+        assert_eq!(got.len(), 500);
+        for (pos, &b) in got.iter().enumerate() {
+            match indices.iter().position(|&i| i as usize == pos) {
+                Some(k) => assert_eq!(b, dense[k], "pos {pos}"),
+                None => assert_eq!(b, gap(pos), "gap pos {pos}"),
+            }
+        }
+    };
+
+    check(&reconstruct(&p("z.bin"), &["--write-gaps"]), &|_| 0);
+    check(
+        &reconstruct(&p("dot.bin"), &["--write-gaps", "0x2e"]),
+        &|_| b'.',
+    );
+    check(&reconstruct(&p("d.bin"), &["--write-gaps", "200"]), &|_| {
+        200
+    });
+    check(
+        &reconstruct(&p("ff.bin"), &["--gap-fill-mode", "0xff"]),
+        &|_| 0xff,
+    );
+    let rnd = reconstruct(&p("rnd.bin"), &["--gap-fill-mode", "random"]);
+    check(&rnd, &|pos| splitmix64(pos as u64) as u8);
+    assert_eq!(
+        reconstruct(&p("rnd2.bin"), &["--gap-fill-mode", "random"]),
+        rnd
+    );
+
+    for bad in [
+        &["--write-gaps", "1", "--gap-fill-mode", "zero"][..],
+        &["--write-gaps", "256"],
+        &["--write-gaps", "0x100"],
+    ] {
+        let out = p("bad.bin");
+        let mut args = vec![
+            "timemap",
+            "reconstruct",
+            "--recipe",
+            &recipe,
+            "--timemap",
+            &tm,
+            "--residual",
+            &resid,
+            "--out",
+            &out,
+        ];
+        args.extend_from_slice(bad);
+        assert!(!run(&args).status.success(), "{bad:?} should be rejected");
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::run;
+
+fn golden(name: &str) -> String {
+    format!(
+        "{}/../../fixtures/golden/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+fn default_recipe(dir: &tempfile::TempDir) -> String {
+    let recipe = dir
+        .path()
+        .join("default.k8r")
+        .to_string_lossy()
+        .into_owned();
+    let out = run(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    assert!(out.status.success(), "sim --save-recipe failed");
+    recipe
+}
+
+#[test]
+fn regen_matches_checked_in_golden_stream() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let recipe = default_recipe(&dir);
+    let reference = golden("default_256.bytes");
+
+    let out = run(&[
+        "regen",
+        "--recipe",
+        &recipe,
+        "--emissions",
+        "256",
+        "--verify",
+        &reference,
+    ]);
+    assert!(
+        out.status.success(),
+        "non-determinism regression against {}:\n{}",
+        reference,
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn regen_verify_reports_first_mismatches() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let recipe = default_recipe(&dir);
+
+    let mut bytes = std::fs::read(golden("default_256.bytes")).expect("read golden");
+    bytes[10] ^= 0xFF;
+    bytes[20] ^= 0xFF;
+    bytes[30] ^= 0xFF;
+    let reference = dir.path().join("bad.bytes").to_string_lossy().into_owned();
+    std::fs::write(&reference, &bytes).expect("write reference");
+
+    let out = run(&[
+        "regen",
+        "--recipe",
+        &recipe,
+        "--emissions",
+        "256",
+        "--verify",
+        &reference,
+        "--max-mismatches",
+        "2",
+    ]);
+    assert!(!out.status.success());
+
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("mismatch pos=10 "), "{stderr}");
+    assert!(stderr.contains("mismatch pos=20 "), "{stderr}");
+    assert!(!stderr.contains("mismatch pos=30 "), "{stderr}");
+    assert!(stderr.contains("mismatches=3 reported=2"), "{stderr}");
+}
@@ -0,0 +1,52 @@
+// crates/k8dnz-cli/tests/residual_ternary.rs
+
+use k8dnz_cli::cmd::timemap::args::ResidualMode;
+use k8dnz_cli::cmd::timemap::residual::{
+    apply_residual_symbol, make_residual_symbol, residual_symbol_bits, sym_mask, TERNARY_BITS,
+};
+
+#[test]
+fn ternary_codes_are_two_bit_and_circular() {
+    let t = ResidualMode::Ternary;
+    let mask = sym_mask(2);
+
+    assert_eq!(make_residual_symbol(t, 1, 1, mask), 0);
+    assert_eq!(make_residual_symbol(t, 1, 2, mask), 1);
+    assert_eq!(make_residual_symbol(t, 1, 0, mask), 3);
+    // Wraps: 3 -> 0 is one step up, 0 -> 3 one step down.
+    assert_eq!(make_residual_symbol(t, 3, 0, mask), 1);
+    assert_eq!(make_residual_symbol(t, 0, 3, mask), 3);
+    assert_eq!(apply_residual_symbol(t, 3, 1, mask), 0);
+    assert_eq!(apply_residual_symbol(t, 0, 3, mask), 3);
+
+    assert_eq!(residual_symbol_bits(t, 1), TERNARY_BITS);
+    assert_eq!(residual_symbol_bits(t, 6), TERNARY_BITS);
+    assert_eq!(residual_symbol_bits(ResidualMode::Xor, 6), 6);
+}
+
+#[test]
+fn ternary_roundtrips_within_one_step_and_clamps_beyond() {
+    let t = ResidualMode::Ternary;
+    for bits in 1..=8u8 {
+        let mask = sym_mask(bits);
+        let alphabet = mask as u16 + 1;
+        for model in 0..alphabet {
+            for plain in 0..alphabet {
+                let (m, p) = (model as u8, plain as u8);
+                let r = make_residual_symbol(t, m, p, mask);
+                assert!(matches!(r, 0 | 1 | 3), "bits={} code={}", bits, r);
+
+                let up = (plain + alphabet - model) % alphabet;
+                let dist = up.min(alphabet - up);
+                let back = apply_residual_symbol(t, m, r, mask);
+                if dist <= 1 {
+                    assert_eq!(back, p, "bits={} model={} plain={}", bits, m, p);
+                } else {
+                    // Clamped toward the target by exactly one step.
+                    let moved = (back as u16 + alphabet - model) % alphabet;
+                    assert!(moved == 1 || moved == alphabet - 1);
+                }
+            }
+        }
+    }
+}
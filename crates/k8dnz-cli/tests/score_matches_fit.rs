@@ -0,0 +1,59 @@
+mod common;
+
+use common::{genesis, ok};
+
+fn scoreboard_lines(s: &str) -> Vec<String> {
+    s.lines()
+        .skip_while(|l| !l.starts_with("--- scoreboard ---"))
+        .map(|l| l.to_string())
+        .collect()
+}
+
+#[test]
+fn score_reproduces_fit_xor_chunked_scoreboard() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let target = p("target.txt");
+    let tm = p("out.tm");
+    let resid = p("out.bin");
+
+    std::fs::write(&target, genesis(4)).expect("write target");
+
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+
+    let fit = ok(&[
+        "timemap",
+        "fit-xor-chunked",
+        "--recipe",
+        &recipe,
+        "--target",
+        &target,
+        "--out-timemap",
+        &tm,
+        "--out-residual",
+        &resid,
+        "--search-emissions",
+        "5000",
+        "--max-ticks",
+        "20000000",
+    ]);
+
+    let score = ok(&[
+        "score",
+        "--recipe",
+        &recipe,
+        "--timemap",
+        &tm,
+        "--residual",
+        &resid,
+        "--plain",
+        &target,
+    ]);
+
+    let want = scoreboard_lines(&String::from_utf8_lossy(&fit.stderr));
+    let got = scoreboard_lines(&String::from_utf8_lossy(&score.stdout));
+    assert!(!want.is_empty(), "fit printed no scoreboard");
+    assert_eq!(got, want);
+}
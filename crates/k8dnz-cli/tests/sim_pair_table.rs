@@ -0,0 +1,49 @@
+mod common;
+
+use common::ok;
+
+#[test]
+fn emit_pairs_table_matches_bin_and_highlights() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let bin_path = dir.path().join("pairs.bin").to_string_lossy().into_owned();
+    let table_path = dir.path().join("table.txt").to_string_lossy().into_owned();
+
+    ok(&[
+        "sim",
+        "--emissions",
+        "12",
+        "--fmt",
+        "bin",
+        "--out",
+        &bin_path,
+    ]);
+    let bytes = std::fs::read(&bin_path).expect("read bin");
+    assert_eq!(bytes.len(), 12);
+
+    let hl = bytes[3].to_string();
+    ok(&[
+        "sim",
+        "--emissions",
+        "12",
+        "--emit-pairs",
+        "--out-table",
+        &table_path,
+        "--highlight",
+        &hl,
+    ]);
+    let table = std::fs::read_to_string(&table_path).expect("read table");
+    let rows: Vec<&str> = table.lines().collect();
+    assert_eq!(rows.len(), 12);
+
+    for (i, (row, &b)) in rows.iter().zip(bytes.iter()).enumerate() {
+        let expect = format!(
+            "{:>2}: A=0x{:x} B=0x{:x} byte=0x{:02x} char=",
+            i,
+            b >> 4,
+            b & 0x0f,
+            b
+        );
+        assert!(row.starts_with(&expect), "row {i}: {row:?} vs {expect:?}");
+        assert_eq!(row.ends_with(" *"), b == bytes[3], "row {i}: {row:?}");
+    }
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::ok;
+
+#[test]
+fn plot_grid_marks_exactly_the_emitted_bytes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let bin_path = dir.path().join("s.bin").to_string_lossy().into_owned();
+    let plot_path = dir.path().join("plot.txt").to_string_lossy().into_owned();
+    let common = ["sim", "--emissions", "300", "--max-ticks", "100000000"];
+
+    let mut a = common.to_vec();
+    a.extend(["--fmt", "bin", "--out", &bin_path]);
+    ok(&a);
+    let bytes = std::fs::read(&bin_path).unwrap();
+    let mut hist = [0u64; 256];
+    for &b in &bytes {
+        hist[b as usize] += 1;
+    }
+    let max = *hist.iter().max().unwrap();
+
+    let mut a = common.to_vec();
+    a.extend(["--plot-distribution", "--out", &plot_path]);
+    ok(&a);
+    let plot = std::fs::read_to_string(&plot_path).unwrap();
+    let rows: Vec<&str> = plot.lines().collect();
+    assert_eq!(rows.len(), 18, "{plot}");
+    assert_eq!(rows[0], "A\\B  0123456789abcdef");
+
+    for hi in 0..16usize {
+        let cells: Vec<char> = rows[1 + hi].chars().collect();
+        assert_eq!(cells.len(), 22, "{:?}", rows[1 + hi]);
+        for lo in 0..16usize {
+            let c = hist[(hi << 4) | lo];
+            let ch = cells[5 + lo];
+            assert_eq!(ch == ' ', c == 0, "cell {hi:x}{lo:x} count={c} ch={ch:?}");
+            if c == max {
+                assert_eq!(ch, '@');
+            }
+        }
+    }
+    assert!(rows[17].starts_with(&format!("total={} ", bytes.len())));
+}
+
+#[test]
+fn plot_bar_uses_custom_palette() {
+    let out = ok(&[
+        "sim",
+        "--emissions",
+        "100",
+        "--max-ticks",
+        "100000000",
+        "--plot-distribution",
+        "--plot-mode",
+        "bar",
+        "--plot-palette",
+        "▁▂▃▄▅▆▇█",
+    ]);
+    let text = String::from_utf8_lossy(&out.stdout);
+    let rows: Vec<&str> = text.lines().collect();
+    assert_eq!(rows.len(), 257);
+    assert!(rows[0].starts_with("0x00 |"));
+    assert!(text.contains('█'));
+    let total: u64 = rows[..256]
+        .iter()
+        .map(|r| r.rsplit(' ').next().unwrap().parse::<u64>().unwrap())
+        .sum();
+    assert_eq!(total, 100);
+}
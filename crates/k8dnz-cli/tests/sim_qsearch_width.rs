@@ -0,0 +1,61 @@
+use std::process::Command;
+
+use k8dnz_cli::io::recipe_file::load_k8r;
+use k8dnz_core::recipe::defaults::default_recipe;
+
+#[test]
+fn qsearch_width_range_saves_best_width_and_shift() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let saved = dir.path().join("best.k8r");
+    let widths = [100_000_000i64, 200_000_000, 300_000_000];
+
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args([
+            "sim",
+            "--qsearch",
+            "--qsearch-candidates",
+            "3",
+            "--qsearch-emissions",
+            "200",
+            "--qsearch-width-range",
+            "100000000:300000000:100000000",
+            "--save-recipe",
+        ])
+        .arg(&saved)
+        .output()
+        .expect("run k8dnz-cli");
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{err}");
+
+    assert!(err
+        .contains("width search: range=100000000:300000000:100000000 widths=3 total_candidates=9"));
+    assert_eq!(err.lines().filter(|l| l.starts_with("cand ")).count(), 9);
+    let board: Vec<&str> = err
+        .lines()
+        .skip_while(|l| !l.starts_with("--- qsearch 2d scoreboard"))
+        .skip(1)
+        .take_while(|l| l.starts_with('#'))
+        .collect();
+    assert_eq!(board.len(), 3, "{err}");
+
+    let base = default_recipe();
+    let best = load_k8r(saved.to_str().unwrap()).unwrap();
+    let width = best.quant.max - best.quant.min;
+    assert!(widths.contains(&width), "width={width}");
+    assert!(board[0].contains(&format!("width={width} ")));
+    assert!(board[0].contains(&format!("shift={} ", best.quant.shift)));
+
+    // The range stays centered on the base range.
+    let center = |min: i64, max: i64| (min as i128 + max as i128) / 2;
+    let drift = center(best.quant.min, best.quant.max) - center(base.quant.min, base.quant.max);
+    assert!(drift.abs() <= 1, "drift={drift}");
+}
+
+#[test]
+fn qsearch_width_range_requires_qsearch() {
+    let out = Command::new(env!("CARGO_BIN_EXE_k8dnz-cli"))
+        .args(["sim", "--qsearch-width-range", "1:2:1"])
+        .output()
+        .expect("run k8dnz-cli");
+    assert!(!out.status.success());
+}
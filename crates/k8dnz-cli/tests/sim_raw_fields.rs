@@ -0,0 +1,59 @@
+mod common;
+
+use common::ok;
+
+/// Pulls `"key":<int>` values in order of appearance.
+fn ints_after(line: &str, key: &str) -> Vec<i64> {
+    line.match_indices(key)
+        .map(|(i, _)| {
+            let rest = &line[i + key.len()..];
+            let end = rest
+                .find(|c: char| c != '-' && !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            rest[..end].parse().expect("int")
+        })
+        .collect()
+}
+
+#[test]
+fn raw_fields_jsonl_and_bin_agree() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let bin_path = dir.path().join("fields.bin").to_string_lossy().into_owned();
+
+    let out = ok(&["sim", "--emissions", "20", "--output-raw-fields"]);
+    let lines: Vec<String> = String::from_utf8(out.stdout)
+        .expect("utf8")
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(lines.len(), 20);
+
+    ok(&[
+        "sim",
+        "--emissions",
+        "20",
+        "--output-raw-fields",
+        "--fmt",
+        "bin",
+        "--out",
+        &bin_path,
+    ]);
+    let bytes = std::fs::read(&bin_path).expect("read bin");
+    assert_eq!(bytes.len(), 20 * 32);
+
+    for (n, (line, rec)) in lines.iter().zip(bytes.chunks(32)).enumerate() {
+        assert_eq!(ints_after(line, "\"emission\":"), vec![n as i64]);
+
+        let json: Vec<i64> = [
+            ints_after(line, "\"raw\":"),
+            ints_after(line, "\"clamped\":"),
+        ]
+        .concat();
+        // json order: raw_a, raw_c, clamped_a, clamped_c; bin order: raw_a, clamped_a, raw_c, clamped_c
+        let bin: Vec<i64> = rec
+            .chunks(8)
+            .map(|b| i64::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(json, vec![bin[0], bin[2], bin[1], bin[3]], "emission {n}");
+    }
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use common::run;
+
+#[test]
+fn sample_every_k_keeps_every_kth_emission() {
+    let full = run(&["sim", "--emissions", "40"]);
+    assert!(full.status.success());
+    let full = String::from_utf8(full.stdout).unwrap();
+
+    let o = run(&["sim", "--emissions", "8", "--sample-every-k", "5"]);
+    let stderr = String::from_utf8_lossy(&o.stderr);
+    assert!(o.status.success(), "{stderr}");
+    assert!(
+        stderr.contains("sample every k: k=5 kept=8 first_index=Some(0) last_index=Some(35)"),
+        "{stderr}"
+    );
+
+    let want: Vec<&str> = full.lines().step_by(5).collect();
+    let got = String::from_utf8(o.stdout).unwrap();
+    assert_eq!(got.lines().collect::<Vec<_>>(), want);
+
+    assert!(!run(&["sim", "--sample-every-k", "0"]).status.success());
+    assert!(
+        !run(&["sim", "--sample-every-k", "2", "--output-raw-fields"])
+            .status
+            .success()
+    );
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::run;
+
+fn sim(args: &[&str]) {
+    let mut full = vec!["sim", "--fmt", "bin"];
+    full.extend_from_slice(args);
+    let o = run(&full);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+}
+
+#[test]
+fn export_then_import_continues_the_stream() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (a, b, full, state) = (p("a.bin"), p("b.bin"), p("full.bin"), p("s.k8st"));
+
+    sim(&["--emissions", "30", "--out", &a, "--export-state", &state]);
+    sim(&["--emissions", "30", "--out", &b, "--import-state", &state]);
+    sim(&["--emissions", "60", "--out", &full]);
+
+    let mut joined = std::fs::read(&a).unwrap();
+    joined.extend(std::fs::read(&b).unwrap());
+    assert_eq!(joined.len(), 60);
+    assert_eq!(joined, std::fs::read(&full).unwrap());
+}
+
+#[test]
+fn import_rejects_other_recipe() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (out, state) = (p("a.bin"), p("s.k8st"));
+
+    sim(&["--emissions", "2", "--out", &out, "--export-state", &state]);
+    let o = run(&[
+        "sim",
+        "--emissions",
+        "2",
+        "--qshift",
+        "1",
+        "--fmt",
+        "bin",
+        "--out",
+        &out,
+        "--import-state",
+        &state,
+    ]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("recipe_id="));
+}
+
+#[test]
+fn state_flags_are_rejected_by_analysis_modes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (out, state) = (p("a.bin"), p("s.k8st"));
+
+    sim(&["--emissions", "2", "--out", &out, "--export-state", &state]);
+    let o = run(&[
+        "sim",
+        "--emissions",
+        "200",
+        "--period-detect",
+        "--import-state",
+        &state,
+    ]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("not --period-detect"));
+}
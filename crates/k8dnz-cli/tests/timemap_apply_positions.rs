@@ -0,0 +1,76 @@
+mod common;
+
+use k8dnz_cli::io::timemap::read_timemap;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+use common::ok;
+
+const MAX_TICKS: u64 = 80_000_000;
+
+#[test]
+fn apply_writes_one_position_per_output_byte() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, target, tm, out) = (p("r.k8r"), p("target.bin"), p("fit.tm"), p("out.bin"));
+
+    std::fs::write(
+        &recipe,
+        k8dnz_core::recipe::format::encode(&default_recipe()),
+    )
+    .expect("write recipe");
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let stream: Vec<u8> = e
+        .run_emissions(200, MAX_TICKS)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    let bytes: Vec<u8> = stream.iter().step_by(5).copied().collect();
+    std::fs::write(&target, &bytes).expect("write target");
+
+    ok(&[
+        "timemap", "fit", "--recipe", &recipe, "--target", &target, "--out", &tm,
+    ]);
+    let indices = read_timemap(&tm).expect("read timemap").indices;
+
+    let (txt, bin) = (p("pos.txt"), p("pos.bin"));
+    for (path, fmt) in [(&txt, "text"), (&bin, "binary")] {
+        ok(&[
+            "timemap",
+            "apply",
+            "--recipe",
+            &recipe,
+            "--timemap",
+            &tm,
+            "--out",
+            &out,
+            "--output-positions",
+            path,
+            "--output-positions-fmt",
+            fmt,
+        ]);
+    }
+
+    let from_text: Vec<u64> = std::fs::read_to_string(&txt)
+        .unwrap()
+        .lines()
+        .map(|l| l.parse().expect("decimal position"))
+        .collect();
+    let from_bin: Vec<u64> = std::fs::read(&bin)
+        .unwrap()
+        .chunks_exact(8)
+        .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+        .collect();
+    assert_eq!(from_text, indices);
+    assert_eq!(from_bin, indices);
+
+    // Each listed position is where the matching output byte sits in the stream.
+    let applied = std::fs::read(&out).unwrap();
+    assert_eq!(applied, bytes);
+    for (i, &pos) in from_text.iter().enumerate() {
+        assert_eq!(
+            stream[pos as usize], applied[i],
+            "byte {i} at position {pos}"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::{ok, run};
+
+fn first_last(stderr: &str, k: usize) -> (u64, u64) {
+    let tag = format!("target[{}]=", k);
+    let line = stderr
+        .lines()
+        .find(|l| l.contains(&tag))
+        .expect("per-target line");
+    let num = |key: &str| -> u64 {
+        let rest = &line[line.find(key).expect(key) + key.len()..];
+        rest[..rest.find(')').unwrap()].parse().unwrap()
+    };
+    (num("first_idx=Some("), num("last_idx=Some("))
+}
+
+#[test]
+fn multi_target_fit_writes_one_timemap_per_target() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let stream = p("stream.bin");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    ok(&[
+        "regen",
+        "--recipe",
+        &recipe,
+        "--emissions",
+        "400",
+        "--out",
+        "bin",
+        "--output",
+        &stream,
+    ]);
+
+    // Targets are subsequences of the stream, so both modes must succeed.
+    let s = std::fs::read(&stream).expect("read stream");
+    let targets: Vec<Vec<u8>> = vec![
+        s[10..20].iter().step_by(3).copied().collect(),
+        s[5..40].iter().step_by(5).copied().collect(),
+        s[100..130].iter().step_by(2).copied().collect(),
+    ];
+    let paths: Vec<String> = (0..targets.len())
+        .map(|k| p(&format!("t{k}.bin")))
+        .collect();
+    for (path, t) in paths.iter().zip(&targets) {
+        std::fs::write(path, t).expect("write target");
+    }
+
+    for (ordered, pattern) in [(false, p("any_{}.tm")), (true, p("ord_{}.tm"))] {
+        let mut args = vec!["timemap", "fit", "--recipe", &recipe];
+        for path in &paths {
+            args.extend(["--multi-target", path.as_str()]);
+        }
+        args.extend(["--out-multi-timemap", pattern.as_str()]);
+        if !ordered {
+            args.push("--concurrent");
+        }
+        let fit = ok(&args);
+        let stderr = String::from_utf8_lossy(&fit.stderr).into_owned();
+
+        for (k, t) in targets.iter().enumerate() {
+            let tm = pattern.replace("{}", &k.to_string());
+            let out = p(&format!("out_{ordered}_{k}.bin"));
+            ok(&[
+                "timemap",
+                "apply",
+                "--recipe",
+                &recipe,
+                "--timemap",
+                &tm,
+                "--out",
+                &out,
+            ]);
+            assert_eq!(&std::fs::read(&out).expect("read apply"), t);
+        }
+
+        if ordered {
+            for k in 1..targets.len() {
+                assert!(first_last(&stderr, k).0 > first_last(&stderr, k - 1).1);
+            }
+        }
+    }
+}
+
+#[test]
+fn multi_target_fit_keeps_target_order_by_default() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let recipe = p("r.k8r");
+    let stream = p("stream.bin");
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    ok(&[
+        "regen",
+        "--recipe",
+        &recipe,
+        "--emissions",
+        "400",
+        "--out",
+        "bin",
+        "--output",
+        &stream,
+    ]);
+
+    // The second target sits earlier in the stream than the first.
+    let s = std::fs::read(&stream).expect("read stream");
+    let (t0, t1) = (p("t0.bin"), p("t1.bin"));
+    std::fs::write(&t0, &s[100..110]).expect("write target");
+    std::fs::write(&t1, &s[0..10]).expect("write target");
+
+    let fit = |pattern: &str, extra: &[&str]| {
+        let mut args = vec![
+            "timemap",
+            "fit",
+            "--recipe",
+            &recipe,
+            "--multi-target",
+            &t0,
+            "--multi-target",
+            &t1,
+        ];
+        args.extend(["--out-multi-timemap", pattern]);
+        args.extend_from_slice(extra);
+        String::from_utf8_lossy(&ok(&args).stderr).into_owned()
+    };
+
+    let ordered = fit(&p("ord_{}.tm"), &[]);
+    assert!(ordered.contains("ordered=true"), "{ordered}");
+    assert!(
+        first_last(&ordered, 1).0 > first_last(&ordered, 0).1,
+        "{ordered}"
+    );
+
+    let concurrent = fit(&p("any_{}.tm"), &["--concurrent"]);
+    assert!(concurrent.contains("ordered=false"), "{concurrent}");
+    assert!(
+        first_last(&concurrent, 1).0 < first_last(&concurrent, 0).0,
+        "{concurrent}"
+    );
+
+    let strict = fit(&p("strict_{}.tm"), &["--require-order"]);
+    assert!(strict.contains("ordered=true"), "{strict}");
+
+    let swapped = run(&[
+        "timemap",
+        "fit",
+        "--recipe",
+        &recipe,
+        "--multi-target",
+        &t0,
+        "--multi-target",
+        &t1,
+        "--out-multi-timemap",
+        &p("bad_{}.tm"),
+        "--concurrent",
+        "--require-order",
+    ]);
+    assert!(!swapped.status.success());
+    let stderr = String::from_utf8_lossy(&swapped.stderr);
+    assert!(stderr.contains("--require-order violated"), "{stderr}");
+    assert!(!std::path::Path::new(&p("bad_0.tm")).exists());
+}
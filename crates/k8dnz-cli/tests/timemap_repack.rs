@@ -0,0 +1,66 @@
+mod common;
+
+use k8dnz_cli::io::timemap::{read_timemap, write_tm1};
+use k8dnz_core::signal::timing_map::TimingMap;
+
+use common::run;
+
+#[test]
+fn repack_rewrites_tm1_in_the_smallest_format() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+
+    let cases: [(&str, Vec<u64>, &[u8; 4]); 3] = [
+        ("contig", (4_000..4_512).collect(), b"TM0\0"),
+        (
+            "runs",
+            (0..8u64).flat_map(|r| r * 900..r * 900 + 40).collect(),
+            b"TM2\0",
+        ),
+        (
+            "sparse",
+            (0..64u64).map(|i| i * i * 3 + i).collect(),
+            b"TM1\0",
+        ),
+    ];
+    for (name, indices, magic) in cases {
+        let src = p(&format!("{name}.tm1"));
+        let dst = p(&format!("{name}.tm"));
+        let tm = TimingMap::new(indices).unwrap();
+        write_tm1(&src, &tm).unwrap();
+
+        let o = run(&["timemap", "repack", "--in", &src, "--out", &dst]);
+        let stderr = String::from_utf8_lossy(&o.stderr);
+        assert!(o.status.success(), "{stderr}");
+
+        let before = std::fs::metadata(&src).unwrap().len();
+        let after = std::fs::read(&dst).unwrap();
+        assert_eq!(&after[..4], magic, "{name}");
+        assert!(after.len() as u64 <= before, "{name}");
+        assert!(
+            stderr.contains(&format!("before={before} (TM1) after={}", after.len())),
+            "{stderr}"
+        );
+        assert_eq!(read_timemap(&dst).unwrap(), tm);
+
+        let o = run(&["timemap", "inspect", "--in", &src, "--suggest-format"]);
+        let stderr = String::from_utf8_lossy(&o.stderr);
+        assert!(o.status.success(), "{stderr}");
+        assert!(
+            stderr.contains(&format!("current=TM1 bytes={before}")),
+            "{stderr}"
+        );
+        let fmt = std::str::from_utf8(&magic[..3]).unwrap();
+        assert!(
+            stderr.contains(&format!(
+                "timemap suggest: format={fmt} bytes={}",
+                after.len()
+            )),
+            "{stderr}"
+        );
+    }
+
+    // Without the flag, inspect stays quiet about formats.
+    let o = run(&["timemap", "inspect", "--in", &p("contig.tm1")]);
+    assert!(!String::from_utf8_lossy(&o.stderr).contains("timemap suggest"));
+}
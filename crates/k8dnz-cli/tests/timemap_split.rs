@@ -0,0 +1,42 @@
+mod common;
+
+use common::{ok, run};
+
+#[test]
+fn split_halves_apply_to_the_full_output() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, full, a, b) = (p("r.k8r"), p("full.tm"), p("a.tm"), p("b.tm"));
+
+    ok(&["sim", "--emissions", "1", "--save-recipe", &recipe]);
+    ok(&[
+        "timemap", "make", "--out", &full, "--len", "300", "--start", "5",
+    ]);
+    ok(&[
+        "timemap", "split", "--in", &full, "--at", "120", "--out-a", &a, "--out-b", &b,
+    ]);
+
+    let apply = |tm: &str, out: &str| {
+        ok(&[
+            "timemap",
+            "apply",
+            "--recipe",
+            &recipe,
+            "--timemap",
+            tm,
+            "--out",
+            out,
+        ]);
+        std::fs::read(out).unwrap()
+    };
+    let whole = apply(&full, &p("full.bin"));
+    let mut joined = apply(&a, &p("a.bin"));
+    assert_eq!(joined.len(), 120);
+    joined.extend(apply(&b, &p("b.bin")));
+    assert_eq!(joined, whole);
+
+    let out = run(&[
+        "timemap", "split", "--in", &full, "--at", "301", "--out-a", &a, "--out-b", &b,
+    ]);
+    assert!(!out.status.success());
+}
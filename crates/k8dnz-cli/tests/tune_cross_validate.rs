@@ -0,0 +1,87 @@
+mod common;
+
+use common::{report_value, run};
+
+#[test]
+fn cross_validate_reports_folds_and_keeps_cv_winner() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (fit, out, report) = (p("fit.txt"), p("cv.k8r"), p("cv.txt"));
+    let words = [
+        "orbit", "lock", "field", "quant", "shift", "emission", "tick",
+    ];
+    let text: String = (0..900usize)
+        .map(|i| words[(i * 7 + i / 5) % words.len()])
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert!(text.len() >= 5 * 1024);
+    std::fs::write(&fit, &text).unwrap();
+
+    let o = run(&[
+        "tune",
+        "--cross-validate",
+        "--cv-splits",
+        "3",
+        "--candidates",
+        "3",
+        "--per-max-ticks",
+        "80000000",
+        "--fit-in",
+        &fit,
+        "--out-recipe",
+        &out,
+        "--report",
+        &report,
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    let report = std::fs::read_to_string(&report).unwrap();
+    assert!(report.contains("--- cross_validation ---"));
+    assert_eq!(report_value(&report, "cv_splits"), "3");
+    let folds: Vec<&str> = report.lines().filter(|l| l.starts_with("fold ")).collect();
+    assert_eq!(folds.len(), 3, "{report}");
+
+    let cv_best = report_value(&report, "cv_best_shift");
+    assert_eq!(report_value(&report, "best_shift"), cv_best);
+    // The winner is only scored on the folds held out from the runs that picked it.
+    let own: Vec<&str> = folds
+        .iter()
+        .filter(|l| l.contains(&format!("best_shift={cv_best} ")))
+        .map(|l| l.rsplit('=').next().unwrap())
+        .collect();
+    assert!(!own.is_empty());
+    let per_fold = report
+        .lines()
+        .find(|l| l.starts_with(&format!("candidate shift={cv_best} ")))
+        .and_then(|l| l.split("per_fold=").nth(1))
+        .unwrap_or_else(|| panic!("candidate line for {cv_best}:\n{report}"));
+    assert_eq!(per_fold, format!("[{}]", own.join(", ")));
+    report_value(&report, "cv_kfold_std_held_out_effective_bytes");
+    let std: f64 = report_value(&report, "cv_std_held_out_effective_bytes")
+        .parse()
+        .unwrap();
+    assert!(std >= 0.0);
+}
+
+#[test]
+fn cross_validate_falls_back_on_small_input() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (fit, out) = (p("fit.txt"), p("r.k8r"));
+    std::fs::write(&fit, "small fit input. ".repeat(20)).unwrap();
+
+    let o = run(&[
+        "tune",
+        "--cross-validate",
+        "--candidates",
+        "3",
+        "--fit-in",
+        &fit,
+        "--out-recipe",
+        &out,
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+    let stderr = String::from_utf8_lossy(&o.stderr);
+    assert!(stderr.contains("falling back to normal tuning"), "{stderr}");
+    assert!(!stderr.contains("cv fold"));
+}
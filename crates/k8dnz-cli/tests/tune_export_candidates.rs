@@ -0,0 +1,83 @@
+mod common;
+
+use common::run;
+
+fn tune(out: &str, export: &str, extra: &[&str]) {
+    let mut args = vec![
+        "tune",
+        "--recipe-preset",
+        "text-aligned",
+        "--passes",
+        "2",
+        "--candidates",
+        "5",
+        "--out-recipe",
+        out,
+        "--export-all-candidates",
+        export,
+    ];
+    args.extend_from_slice(extra);
+    let o = run(&args);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+}
+
+fn names(dir: &std::path::Path) -> Vec<String> {
+    let mut v: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    v.sort();
+    v
+}
+
+#[test]
+fn every_candidate_is_exported_as_a_loadable_recipe() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let out = dir.path().join("best.k8r").to_string_lossy().into_owned();
+    let all = dir.path().join("all");
+    tune(&out, &all.to_string_lossy(), &[]);
+
+    let files = names(&all);
+    assert_eq!(files.len(), 10, "{files:?}");
+    for pass in 1..=2 {
+        for rank in 1..=5 {
+            let prefix = format!("candidate_pass{pass}_rank{rank}_shift");
+            assert_eq!(
+                files.iter().filter(|f| f.starts_with(&prefix)).count(),
+                1,
+                "{prefix} in {files:?}"
+            );
+        }
+    }
+
+    // The last pass's winner is the saved recipe.
+    let best = files
+        .iter()
+        .find(|f| f.starts_with("candidate_pass2_rank1_"))
+        .unwrap();
+    assert_eq!(
+        std::fs::read(all.join(best)).unwrap(),
+        std::fs::read(&out).unwrap()
+    );
+
+    // Each file carries the shift in its name and loads like any recipe.
+    for f in &files {
+        let path = all.join(f).to_string_lossy().into_owned();
+        let o = run(&["regen", "--recipe", &path, "--emissions", "4"]);
+        assert!(
+            o.status.success(),
+            "{f}: {}",
+            String::from_utf8_lossy(&o.stderr)
+        );
+        let shift = f.trim_end_matches(".k8r").rsplit_once("_shift").unwrap().1;
+        assert!(shift.parse::<i64>().is_ok(), "{f}");
+    }
+
+    let top = dir.path().join("top");
+    tune(&out, &top.to_string_lossy(), &["--export-top-k", "2"]);
+    let files = names(&top);
+    assert_eq!(files.len(), 4, "{files:?}");
+    assert!(files
+        .iter()
+        .all(|f| f.contains("_rank1_") || f.contains("_rank2_")));
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::{genesis, report_value, run};
+
+#[test]
+fn tune_gamma_sweeps_the_curve_and_saves_the_winner() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let fit = p("fit.txt");
+    std::fs::write(&fit, genesis(4)).unwrap();
+
+    let (out, report) = (p("g.k8r"), p("g.txt"));
+    let o = run(&[
+        "tune",
+        "--tune-gamma",
+        "--candidates",
+        "3",
+        "--fit-in",
+        &fit,
+        "--out-recipe",
+        &out,
+        "--report",
+        &report,
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    let report = std::fs::read_to_string(&report).unwrap();
+    assert!(report.contains("--- quant_gamma ---"), "{report}");
+    let ranked = report.lines().filter(|l| l.contains(" gamma=")).count();
+    assert!((1..=5).contains(&ranked), "{report}");
+    let best: f64 = report_value(&report, "best_quant_gamma").parse().unwrap();
+    assert!([0.25, 0.5, 1.0, 2.0, 4.0].contains(&best));
+
+    let o = run(&["recipe", "inspect", "--recipe", &out]);
+    let shown = String::from_utf8_lossy(&o.stdout);
+    let want = if best == 1.0 {
+        "None".to_string()
+    } else {
+        format!("Some({best})")
+    };
+    assert!(shown.contains(&format!("quant_gamma  = {want}")), "{shown}");
+
+    let o = run(&["tune", "--tune-gamma", "--out-recipe", &p("x.k8r")]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("--tune-gamma requires --fit-in"));
+}
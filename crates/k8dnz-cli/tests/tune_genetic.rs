@@ -0,0 +1,66 @@
+mod common;
+
+use common::run;
+
+fn best_per_gen(report: &str) -> Vec<u64> {
+    report
+        .lines()
+        .filter(|l| l.starts_with("gen "))
+        .map(|l| {
+            let v = l.split("best_effective_bytes=").nth(1).unwrap();
+            v.split_whitespace().next().unwrap().parse().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn genetic_tune_is_elitist_and_reproducible() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let fit = p("fit.txt");
+    std::fs::write(
+        &fit,
+        "In the beginning God created the heaven and the earth. ".repeat(6),
+    )
+    .unwrap();
+
+    let tune = |out: &str, report: &str| {
+        let o = run(&[
+            "tune",
+            "--genetic",
+            "--fit-in",
+            &fit,
+            "--out-recipe",
+            out,
+            "--report",
+            report,
+            "--pop-size",
+            "6",
+            "--generations",
+            "3",
+        ]);
+        assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+    };
+    let (r1, rep1, r2, rep2) = (p("a.k8r"), p("a.txt"), p("b.k8r"), p("b.txt"));
+    tune(&r1, &rep1);
+    tune(&r2, &rep2);
+
+    let report = std::fs::read_to_string(&rep1).unwrap();
+    let best = best_per_gen(&report);
+    assert_eq!(best.len(), 3);
+    assert!(best.windows(2).all(|w| w[1] <= w[0]), "{best:?}");
+    assert!(report.contains(&format!("best_effective_bytes = {}", best[2])));
+
+    assert_eq!(std::fs::read(&r1).unwrap(), std::fs::read(&r2).unwrap());
+    let o = run(&["sim", "--recipe", &r1, "--emissions", "4"]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+}
+
+#[test]
+fn genetic_requires_fit_in() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let out = dir.path().join("r.k8r").to_string_lossy().into_owned();
+    let o = run(&["tune", "--genetic", "--out-recipe", &out]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("--genetic requires --fit-in"));
+}
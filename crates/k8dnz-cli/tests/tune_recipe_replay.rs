@@ -0,0 +1,109 @@
+mod common;
+
+use common::{genesis, run};
+
+#[test]
+fn emitted_tune_recipe_replays_to_the_same_best_shift() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (fit, base, tuned, tr) = (p("fit.txt"), p("base.k8r"), p("a.k8r"), p("a.toml"));
+    std::fs::write(&fit, genesis(4)).unwrap();
+    let o = run(&[
+        "tune",
+        "--recipe-preset",
+        "text-aligned",
+        "--candidates",
+        "1",
+        "--out-recipe",
+        &base,
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    let o = run(&[
+        "tune",
+        "--recipe",
+        &base,
+        "--fit-in",
+        &fit,
+        "--rank-by-effective-zstd",
+        "--passes",
+        "2",
+        "--candidates",
+        "5",
+        "--out-recipe",
+        &tuned,
+        "--emit-tune-recipe",
+        &tr,
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    let text = std::fs::read_to_string(&tr).unwrap();
+    let doc: toml::Table = text.parse().unwrap();
+    assert_eq!(doc["$schema_version"].as_integer(), Some(1));
+    assert_eq!(
+        doc["crate_version"].as_str(),
+        Some(env!("CARGO_PKG_VERSION"))
+    );
+    assert!(doc["created_unix"].as_integer().unwrap() > 0);
+    assert_eq!(doc["base_recipe"].as_str(), Some(base.as_str()));
+    // Rebuilt from the parsed arguments: search parameters only, not the outputs.
+    let args: Vec<&str> = doc["args"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a.as_str().unwrap())
+        .collect();
+    assert!(args.contains(&"--rank-by-effective-zstd"));
+    assert!(args.contains(&"--passes=2"));
+    assert!(args.contains(&format!("--fit-in={fit}").as_str()));
+    assert!(!args.iter().any(|a| a.starts_with("--emit-tune-recipe")));
+    let passes = doc["pass"].as_array().unwrap();
+    assert_eq!(passes.len(), 2);
+    for (i, pass) in passes.iter().enumerate() {
+        assert_eq!(pass["pass"].as_integer(), Some(i as i64 + 1));
+        assert_eq!(pass["ranking"].as_str(), Some("effective_zstd"));
+        assert_eq!(pass["candidate"].as_array().unwrap().len(), 5);
+    }
+    let best_shift = doc["best_shift"].as_integer().unwrap();
+    let last_best = &passes[1]["candidate"][0];
+    assert_eq!(last_best["rank"].as_integer(), Some(1));
+    assert_eq!(last_best["shift"].as_integer(), Some(best_shift));
+
+    // The base recipe is embedded, so the replay does not need the original file.
+    std::fs::remove_file(&base).unwrap();
+    let replayed = p("b.k8r");
+    let o = run(&["tune", "--from-tune-recipe", &tr, "--out-recipe", &replayed]);
+    let stderr = String::from_utf8_lossy(&o.stderr);
+    assert!(o.status.success(), "{stderr}");
+    assert!(stderr.contains(&format!("tune replay ok: best_shift={best_shift}")));
+    assert_eq!(
+        std::fs::read(&tuned).unwrap(),
+        std::fs::read(&replayed).unwrap()
+    );
+
+    // A record that no longer matches the search is reported.
+    let tampered = p("tampered.toml");
+    std::fs::write(
+        &tampered,
+        text.replace(
+            &format!("best_shift = {best_shift}"),
+            &format!("best_shift = {}", best_shift + 1),
+        ),
+    )
+    .unwrap();
+    let o = run(&[
+        "tune",
+        "--from-tune-recipe",
+        &tampered,
+        "--out-recipe",
+        &replayed,
+    ]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("tune replay mismatch"));
+
+    // So is a changed --fit-in.
+    std::fs::write(&fit, b"something else entirely\n").unwrap();
+    let o = run(&["tune", "--from-tune-recipe", &tr, "--out-recipe", &replayed]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("changed since the tune was recorded"));
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use k8dnz_core::recipe::format::{self, FORMAT_VERSION_RGB};
+
+use common::{genesis, report_value, run};
+
+#[test]
+fn tune_rgb_params_saves_a_v5_recipe_with_the_winner() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let fit = p("fit.txt");
+    std::fs::write(&fit, genesis(4)).unwrap();
+
+    let (out, report) = (p("rgb.k8r"), p("rgb.txt"));
+    let o = run(&[
+        "tune",
+        "--tune-rgb-params",
+        "--rgb-step-range",
+        "1:3:1",
+        "--rgb-scale-range",
+        "1:2:1",
+        "--candidates",
+        "3",
+        "--fit-in",
+        &fit,
+        "--out-recipe",
+        &out,
+        "--report",
+        &report,
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    let report = std::fs::read_to_string(&report).unwrap();
+    assert_eq!(report_value(&report, "rgb_combinations"), "6");
+    let g_step: i16 = report_value(&report, "best_rgb_g_step").parse().unwrap();
+    let p_scale: i16 = report_value(&report, "best_rgb_p_scale").parse().unwrap();
+
+    let bytes = std::fs::read(&out).unwrap();
+    let saved = format::decode(&bytes).unwrap();
+    assert_eq!(saved.version, FORMAT_VERSION_RGB);
+    assert_eq!((saved.rgb.g_step, saved.rgb.p_scale), (g_step, p_scale));
+
+    // The RGB block round-trips through the v5 layout unchanged.
+    let again = format::decode(&format::encode(&saved)).unwrap();
+    assert_eq!(format!("{:?}", again.rgb), format!("{:?}", saved.rgb));
+    assert_eq!(format::encode(&again), bytes);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::{genesis, run};
+
+#[test]
+fn sensitivity_report_sweeps_around_the_best_shift() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let fit = p("fit.txt");
+    std::fs::write(&fit, genesis(4)).unwrap();
+
+    let (out, report, csv) = (p("t.k8r"), p("t.txt"), p("sens.csv"));
+    let o = run(&[
+        "tune",
+        "--candidates",
+        "3",
+        "--fit-in",
+        &fit,
+        "--out-recipe",
+        &out,
+        "--report",
+        &report,
+        "--sensitivity-report",
+        &csv,
+        "--sensitivity-points",
+        "4",
+    ]);
+    assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+
+    // 4 points is forced odd: 5 rows centred on delta 0, evenly spaced.
+    let csv = std::fs::read_to_string(&csv).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("delta,effective_bytes"));
+    let deltas: Vec<i64> = lines
+        .map(|l| {
+            let (d, e) = l.split_once(',').expect("delta,effective_bytes row");
+            assert!(e == "dead" || e.parse::<usize>().is_ok(), "{l}");
+            d.parse().unwrap()
+        })
+        .collect();
+    assert_eq!(deltas.len(), 5, "{csv}");
+    let step = deltas[3] - deltas[2];
+    assert!(step > 0);
+    assert_eq!(deltas, [-2 * step, -step, 0, step, 2 * step]);
+
+    let report = std::fs::read_to_string(&report).unwrap();
+    assert!(report.contains("--- sensitivity ---"), "{report}");
+    let class = report
+        .lines()
+        .find_map(|l| l.strip_prefix("minimum = "))
+        .unwrap_or_else(|| panic!("missing minimum in report:\n{report}"));
+    assert!(class.starts_with("steep") || class == "shallow", "{class}");
+
+    let o = run(&[
+        "tune",
+        "--sensitivity-report",
+        &p("x.csv"),
+        "--out-recipe",
+        &p("x.k8r"),
+    ]);
+    assert!(!o.status.success());
+    assert!(String::from_utf8_lossy(&o.stderr).contains("--sensitivity-report requires --fit-in"));
+}
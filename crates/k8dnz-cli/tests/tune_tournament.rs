@@ -0,0 +1,101 @@
+mod common;
+
+use common::{genesis, report_value, run};
+
+#[test]
+fn tournament_tunes_every_recipe_and_keeps_the_global_winner() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let fit = p("fit.txt");
+    std::fs::write(&fit, genesis(4)).unwrap();
+
+    let entrants = [p("default.k8r"), p("flat.k8r"), p("text.k8r")];
+    for (path, preset) in entrants.iter().zip(["default", "flat", "text-aligned"]) {
+        let o = run(&[
+            "tune",
+            "--recipe-preset",
+            preset,
+            "--candidates",
+            "1",
+            "--out-recipe",
+            path,
+        ]);
+        assert!(o.status.success(), "{}", String::from_utf8_lossy(&o.stderr));
+    }
+
+    let out_dir = p("out");
+    let mut args = vec!["tune", "--multi-recipe-tournament"];
+    args.extend(entrants.iter().map(String::as_str));
+    let winner = p("winner.k8r");
+    let report = p("tournament.txt");
+    args.extend([
+        "--fit-in",
+        &fit,
+        "--candidates",
+        "3",
+        "--out-recipe",
+        &winner,
+        "--out-dir",
+        &out_dir,
+        "--report",
+        &report,
+    ]);
+    let o = run(&args);
+    let stderr = String::from_utf8_lossy(&o.stderr);
+    assert!(o.status.success(), "{stderr}");
+
+    for n in 1..=3 {
+        let r = std::fs::read_to_string(format!("{out_dir}/recipe_{n}_report.txt")).unwrap();
+        assert!(r.starts_with("--- k8dnz tune report ---"));
+        assert!(r.contains("best_shift = "));
+    }
+
+    let report = std::fs::read_to_string(&report).unwrap();
+    let rows: Vec<&str> = report
+        .lines()
+        .filter(|l| entrants.iter().any(|e| l.contains(e.as_str())))
+        .filter(|l| !l.starts_with("winner_base_recipe"))
+        .collect();
+    assert_eq!(rows.len(), 3, "{report}");
+    let effective: Vec<usize> = rows
+        .iter()
+        .map(|l| {
+            let cols: Vec<&str> = l.split_whitespace().collect();
+            cols[4].parse().unwrap()
+        })
+        .collect();
+
+    let best: usize = report_value(&report, "best_effective_bytes")
+        .parse()
+        .unwrap();
+    assert_eq!(best, *effective.iter().min().unwrap());
+    let n: usize = report_value(&report, "winner").parse().unwrap();
+    assert_eq!(effective[n - 1], best);
+    assert!(rows[n - 1].ends_with('*'));
+    assert_eq!(
+        std::fs::read(&winner).unwrap(),
+        std::fs::read(format!("{out_dir}/recipe_{n}.k8r")).unwrap()
+    );
+
+    // Entrants come from the positional list, not --recipe; --fit-in is needed to score them.
+    let o = run(&[
+        "tune",
+        "--multi-recipe-tournament",
+        &entrants[0],
+        "--recipe",
+        &entrants[1],
+        "--fit-in",
+        &fit,
+        "--out-recipe",
+        &winner,
+    ]);
+    assert!(!o.status.success());
+    let o = run(&[
+        "tune",
+        "--multi-recipe-tournament",
+        &entrants[0],
+        "--out-recipe",
+        &winner,
+    ]);
+    assert!(!o.status.success());
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::{printed, run, stderr, GENESIS_LINE};
+
+#[test]
+fn regen_encode_decode_check_the_recipe_id() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let p = |name: &str| dir.path().join(name).to_string_lossy().into_owned();
+    let (recipe, stale) = (p("r.k8r"), p("stale.k8r"));
+    assert!(run(&["sim", "--emissions", "1", "--save-recipe", &recipe])
+        .status
+        .success());
+    assert!(run(&[
+        "tune",
+        "--recipe-preset",
+        "flat",
+        "--candidates",
+        "1",
+        "--out-recipe",
+        &stale,
+    ])
+    .status
+    .success());
+
+    // The id is printed even without validation, ready to pin.
+    let o = run(&["regen", "--recipe", &recipe, "--emissions", "4"]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    let rid = printed(&o, "recipe_id");
+    assert_eq!(rid.len(), 32);
+
+    let upper = rid.to_uppercase();
+    let o = run(&[
+        "regen",
+        "--recipe",
+        &recipe,
+        "--emissions",
+        "4",
+        "--validate-recipe-id",
+        &upper,
+    ]);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert!(stderr(&o).contains(&format!("recipe_id ok: {rid}")));
+
+    let o = run(&[
+        "regen",
+        "--recipe",
+        &stale,
+        "--emissions",
+        "4",
+        "--validate-recipe-id",
+        &rid,
+    ]);
+    assert!(!o.status.success());
+    assert!(stderr(&o).contains("recipe id mismatch"), "{}", stderr(&o));
+    assert!(o.stdout.is_empty(), "no stream on mismatch");
+
+    // encode checks the id it embeds; decode checks the one in the .ark.
+    let (plain, ark, back) = (p("plain.txt"), p("plain.ark"), p("back.txt"));
+    std::fs::write(&plain, GENESIS_LINE).unwrap();
+    let encode = |id: &str| {
+        run(&[
+            "encode",
+            "--in",
+            &plain,
+            "--out",
+            &ark,
+            "--recipe",
+            &recipe,
+            "--validate-recipe-id",
+            id,
+        ])
+    };
+    let o = encode("00000000000000000000000000000000");
+    assert!(!o.status.success());
+    assert!(stderr(&o).contains("recipe id mismatch"));
+    assert!(!std::path::Path::new(&ark).exists());
+
+    let o = encode(&rid);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert_eq!(printed(&o, "recipe_id"), rid);
+
+    let decode = |id: &str| {
+        run(&[
+            "decode",
+            "--in",
+            &ark,
+            "--out",
+            &back,
+            "--validate-recipe-id",
+            id,
+        ])
+    };
+    let o = decode(&rid);
+    assert!(o.status.success(), "{}", stderr(&o));
+    assert_eq!(
+        std::fs::read(&back).unwrap(),
+        std::fs::read(&plain).unwrap()
+    );
+
+    std::fs::remove_file(&back).unwrap();
+    let o = decode("00000000000000000000000000000000");
+    assert!(!o.status.success());
+    assert!(stderr(&o).contains("recipe id mismatch"));
+    assert!(!std::path::Path::new(&back).exists());
+}
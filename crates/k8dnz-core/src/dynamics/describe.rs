@@ -0,0 +1,200 @@
+// crates/k8dnz-core/src/dynamics/describe.rs
+//
+// Structured, human-oriented summary of an Engine's recipe. Pure function of the
+// recipe (no stepping), so it is valid before the first tick.
+
+use crate::dynamics::engine::Engine;
+use crate::recipe::format::recipe_id_hex;
+use crate::recipe::recipe::{KeystreamMix, PayloadKind};
+use crate::signal::quantize;
+
+const TURN: f64 = 4_294_967_296.0; // 2^32 (one Turn32 / full Unit32 span)
+
+#[derive(Clone, Debug)]
+pub struct EngineDescription {
+    pub recipe_id: String,
+    pub version: u16,
+    pub seed: u64,
+    pub quant_range_width: i64,
+    /// quant.shift / quant_range_width.
+    pub quant_shift_fraction: f64,
+    pub field_clamp_width: i64,
+    /// Rough ticks per emission from the orbit params (see `estimate_period`).
+    pub expected_emission_period_estimate: f64,
+    pub keystream_mix: KeystreamMix,
+    pub payload_kind: PayloadKind,
+    pub notes: Vec<String>,
+}
+
+impl Engine {
+    pub fn describe(&self) -> EngineDescription {
+        let r = &self.recipe;
+        let quant_range_width = r.quant.max.saturating_sub(r.quant.min);
+        let field_clamp_width = r.field_clamp.max.saturating_sub(r.field_clamp.min);
+        let quant_shift_fraction = if quant_range_width == 0 {
+            0.0
+        } else {
+            r.quant.shift as f64 / quant_range_width as f64
+        };
+
+        let mut notes: Vec<String> = Vec::new();
+        if quant_range_width.saturating_mul(4) < field_clamp_width {
+            notes.push(
+                "quant range is narrow vs field clamp: may produce low-entropy output".to_string(),
+            );
+        }
+        if quant_shift_fraction.abs() > 0.4 {
+            notes.push(format!(
+                "shift is near or past the boundary of the range ({:.3} of width)",
+                quant_shift_fraction
+            ));
+        }
+        let rel = relative_step(self);
+        if rel > 2.0 * r.free.epsilon.0 as f64 {
+            notes.push(
+                "relative orbit step exceeds the alignment window: cadence is stroboscopic (irregular)"
+                    .to_string(),
+            );
+        }
+        for w in r.validate_deep() {
+            notes.push(w.to_string());
+        }
+
+        EngineDescription {
+            recipe_id: recipe_id_hex(r),
+            version: r.version,
+            seed: r.seed,
+            quant_range_width,
+            quant_shift_fraction,
+            field_clamp_width,
+            expected_emission_period_estimate: estimate_period(self),
+            keystream_mix: r.keystream_mix,
+            payload_kind: r.payload_kind,
+            notes,
+        }
+    }
+}
+
+/// Slack applied around `estimate_period` by `expected_emission_period_range`.
+const PERIOD_SAFETY: f64 = 4.0;
+
+impl Engine {
+    /// Rough (lower, upper) bound on average ticks per emission: `estimate_period`
+    /// divided/multiplied by a 4x safety factor. The lower bound never drops below the
+    /// lockstep climb; a recipe that never aligns yields `(lo, u64::MAX)`.
+    pub fn expected_emission_period_range(&self) -> (u64, u64) {
+        let est = estimate_period(self);
+        let lock = lock_ticks(self);
+        if !est.is_finite() {
+            return (lock.ceil() as u64, u64::MAX);
+        }
+        let lo = (est / PERIOD_SAFETY).max(lock + 1.0);
+        let hi = (est * PERIOD_SAFETY).max(lo);
+        (lo.floor() as u64, hi.ceil().min(u64::MAX as f64) as u64)
+    }
+
+    /// Quant bins per channel the clamped field can actually reach: the overlap of the
+    /// field clamp range with the (shifted) quant range, in quant steps (width / 16).
+    pub fn reachable_quant_bins(&self) -> u8 {
+        let r = &self.recipe;
+        let (qmin, qmax) = quantize::shifted_bounds(r.quant.min, r.quant.max, r.quant.shift);
+        let step = (qmax.saturating_sub(qmin) as f64 / 16.0).max(1.0);
+        let (lo, hi) = (r.field_clamp.min.max(qmin), r.field_clamp.max.min(qmax));
+        let overlap = hi.saturating_sub(lo);
+        if overlap <= 0 {
+            return 1;
+        }
+        (overlap as f64 / step).ceil().clamp(1.0, 16.0) as u8
+    }
+
+    /// Emissions needed to see one given (reachable) byte with 99% probability, treating
+    /// each emission as a uniform draw over `reachable_quant_bins()^2` pair values.
+    pub fn recommended_search_emissions(&self) -> u64 {
+        let bins = self.reachable_quant_bins() as f64;
+        let p = 1.0 / (bins * bins);
+        if p >= 1.0 {
+            return 1;
+        }
+        ((0.01f64).ln() / (1.0 - p).ln()).ceil() as u64
+    }
+}
+
+/// A and C counter-rotate, so their separation closes by v_a + v_c per tick (Turn32 units).
+fn relative_step(e: &Engine) -> f64 {
+    e.recipe.free.v_a.0 as f64 + e.recipe.free.v_c.0 as f64
+}
+
+/// Ticks of the lockstep climb from t = 0 to the top rim.
+fn lock_ticks(e: &Engine) -> f64 {
+    TURN / (e.recipe.lock.t_step.max(1) as f64)
+}
+
+/// Ticks per emission, roughly: one relative revolution per encounter, divided by the
+/// chance an encounter lands inside the +/-epsilon window, plus the lockstep climb.
+/// Ignores reset geometry, so treat it as an order-of-magnitude figure.
+fn estimate_period(e: &Engine) -> f64 {
+    let rel = relative_step(e);
+    let lock_ticks = lock_ticks(e);
+    if rel == 0.0 {
+        return f64::INFINITY;
+    }
+    let encounter_ticks = TURN / rel;
+    let hit = (2.0 * e.recipe.free.epsilon.0 as f64 / rel).clamp(f64::MIN_POSITIVE, 1.0);
+    encounter_ticks / hit + lock_ticks
+}
+
+impl EngineDescription {
+    /// Single-line JSON: notes are string-escaped, enums use their Debug names, and an
+    /// unbounded period estimate is written as null.
+    pub fn to_json(&self) -> String {
+        let notes: Vec<String> = self
+            .notes
+            .iter()
+            .map(|n| format!("\"{}\"", n.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+        format!(
+            "{{\"recipe_id\":\"{}\",\"version\":{},\"seed\":{},\"quant_range_width\":{},\"quant_shift_fraction\":{},\"field_clamp_width\":{},\"expected_emission_period_estimate\":{},\"keystream_mix\":\"{:?}\",\"payload_kind\":\"{:?}\",\"notes\":[{}]}}",
+            self.recipe_id,
+            self.version,
+            self.seed,
+            self.quant_range_width,
+            json_f64(self.quant_shift_fraction),
+            self.field_clamp_width,
+            json_f64(self.expected_emission_period_estimate),
+            self.keystream_mix,
+            self.payload_kind,
+            notes.join(",")
+        )
+    }
+}
+
+/// JSON has no inf/NaN; a non-aligning orbit's infinite period is written as null.
+fn json_f64(x: f64) -> String {
+    if x.is_finite() {
+        x.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+impl std::fmt::Display for EngineDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "recipe_id                 = {}", self.recipe_id)?;
+        writeln!(f, "version                   = {}", self.version)?;
+        writeln!(f, "seed                      = {}", self.seed)?;
+        writeln!(f, "quant_range_width         = {}", self.quant_range_width)?;
+        writeln!(f, "quant_shift_fraction      = {:.6}", self.quant_shift_fraction)?;
+        writeln!(f, "field_clamp_width         = {}", self.field_clamp_width)?;
+        writeln!(
+            f,
+            "emission_period_estimate  = {:.1} ticks",
+            self.expected_emission_period_estimate
+        )?;
+        writeln!(f, "keystream_mix             = {:?}", self.keystream_mix)?;
+        writeln!(f, "payload_kind              = {:?}", self.payload_kind)?;
+        for n in &self.notes {
+            writeln!(f, "note: {}", n)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+// crates/k8dnz-core/src/dynamics/emission_buffer.rs
+//
+// Fixed-size ring of the most recent stream bytes, addressed by absolute position.
+//
+// A fit that only ever looks `size` bytes behind its generation frontier can keep this
+// instead of the whole stream: memory is O(size) rather than O(search_emissions).
+
+use crate::dynamics::engine::Engine;
+
+#[derive(Clone, Debug)]
+pub struct EmissionBuffer {
+    buf: Vec<u8>,
+    /// Absolute position of the first byte ever pushed.
+    origin: u64,
+    /// Total bytes pushed.
+    pushed: u64,
+}
+
+impl EmissionBuffer {
+    /// Empty ring holding the last `size` bytes (at least 1); the first push lands at
+    /// absolute position `origin`.
+    pub fn with_origin(size: usize, origin: u64) -> Self {
+        EmissionBuffer {
+            buf: vec![0u8; size.max(1)],
+            origin,
+            pushed: 0,
+        }
+    }
+
+    pub fn push(&mut self, b: u8) {
+        let slot = (self.pushed % self.buf.len() as u64) as usize;
+        self.buf[slot] = b;
+        self.pushed += 1;
+    }
+
+    /// Byte at `absolute_pos`, or `None` if it has not been pushed yet or was overwritten.
+    pub fn get(&self, absolute_pos: u64) -> Option<u8> {
+        let idx = absolute_pos.checked_sub(self.origin)?;
+        if idx >= self.pushed || self.pushed - idx > self.buf.len() as u64 {
+            return None;
+        }
+        Some(self.buf[(idx % self.buf.len() as u64) as usize])
+    }
+
+    /// Total bytes ever pushed (not just the ones still held).
+    pub fn len(&self) -> u64 {
+        self.pushed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pushed == 0
+    }
+
+    /// Ring size in bytes.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn origin(&self) -> u64 {
+        self.origin
+    }
+
+    /// Oldest absolute position still held.
+    pub fn first_held(&self) -> u64 {
+        self.origin + self.pushed.saturating_sub(self.buf.len() as u64)
+    }
+}
+
+impl Engine {
+    /// Ring buffer of `size` bytes whose positions continue from this engine's emission
+    /// count (one byte per emission, e.g. `PairToken::pack_byte`).
+    pub fn emission_buffer(&self, size: usize) -> EmissionBuffer {
+        EmissionBuffer::with_origin(size, self.stats.emissions)
+    }
+}
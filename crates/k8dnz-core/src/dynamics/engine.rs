@@ -1,6 +1,6 @@
 // crates/k8dnz-core/src/dynamics/engine.rs
 
-use crate::error::Result;
+use crate::error::{K8Error, Result};
 use crate::validate::validate_recipe;
 
 use crate::dynamics::{
@@ -8,10 +8,17 @@ use crate::dynamics::{
     state::{FreeOrbitState, Mode},
 };
 use crate::field::{params::FieldModel, tri_wave};
+use crate::fixed::turn32::Turn32;
 use crate::fixed::unit32::Unit32;
 use crate::recipe::recipe::{Alphabet, Recipe, ResetMode};
-use crate::signal::{quantize, sample::FieldSample, token::PairToken};
+use crate::signal::{
+    quantize::{self, GammaCurve, QuantStats},
+    sample::FieldSample,
+    token::PairToken,
+};
 use crate::stats::counters::Counters;
+use crate::stats::randomness::{stat_test_bytes, StatTestReport, STAT_TEST_ALPHA};
+use crate::stats::tpe::TpeStats;
 
 #[derive(Clone, Copy, Debug, Default)]
 pub struct FieldRangeStats {
@@ -50,12 +57,18 @@ pub struct EmissionField {
     pub clamped_c: i64,
 }
 
+/// Tick / alignment / emission counters kept in `Engine::stats`.
+pub type EngineStats = Counters;
+
+#[derive(Clone)]
 pub struct Engine {
     pub recipe: Recipe,
     pub mode: Mode,
-    pub stats: Counters,
+    pub stats: EngineStats,
     pub field: FieldModel,
     pub time: u64,
+    /// `recipe.quant_gamma` as a fixed-point curve, built once like `field`.
+    pub gamma_curve: Option<GammaCurve>,
 }
 
 impl Engine {
@@ -64,6 +77,7 @@ impl Engine {
 
         // Field clamp is now driven by recipe (v3+).
         let field = FieldModel::new(recipe.field.clone(), recipe.field_clamp.into());
+        let gamma_curve = recipe.quant_gamma.map(GammaCurve::new);
 
         let start = FreeOrbitState {
             phi_a: recipe.free.phi_a0,
@@ -75,6 +89,7 @@ impl Engine {
             stats: Counters::default(),
             field,
             time: 0,
+            gamma_curve,
         })
     }
 
@@ -87,6 +102,14 @@ impl Engine {
     ///
     /// IMPORTANT: cadence dynamics unchanged; this only exposes emission-time samples.
     pub fn step_with_fields(&mut self) -> Option<(PairToken, EmissionField)> {
+        let phi_l = self.advance()?;
+        Some(self.sample_emission(phi_l))
+    }
+
+    /// One tick of the cadence dynamics. On emission, counts it and returns the lockstep
+    /// phase the emission is sampled at; the field evaluation is left to
+    /// `sample_emission`, so callers that skip emissions never pay for it.
+    fn advance(&mut self) -> Option<Turn32> {
         self.stats.ticks += 1;
         self.time = self.time.wrapping_add(1);
 
@@ -117,36 +140,6 @@ impl Engine {
                 let lock_next = lockstep::tick(lock, &self.recipe.lock);
 
                 if lockstep::done(&lock_next) {
-                    // Emit at top rim (t == MAX)
-                    let phi1 = lock_next.phi_l;
-                    let phi2 = lock_next.phi_l.wrapping_add(self.recipe.lock.delta);
-                    let t_top = Unit32::MAX;
-
-                    // raw + clamped (clamp comes from recipe-driven model.cfg)
-                    let s1_raw = tri_wave::eval_raw(&self.field, phi1, t_top, self.time);
-                    let s2_raw = tri_wave::eval_raw(&self.field, phi2, t_top, self.time);
-
-                    let s1 = s1_raw.clamp(self.field.cfg.clamp_min, self.field.cfg.clamp_max);
-                    let s2 = s2_raw.clamp(self.field.cfg.clamp_min, self.field.cfg.clamp_max);
-
-                    // quantize to N=16 bins using recipe quant range (+ optional shift)
-                    let n = match self.recipe.alphabet {
-                        Alphabet::N16 => 16u8,
-                    };
-
-                    // Apply the deterministic "bin boundary shift" knob:
-                    // eff_min = min + shift
-                    // eff_max = max + shift
-                    let (qmin, qmax) = quantize::shifted_bounds(
-                        self.recipe.quant.min,
-                        self.recipe.quant.max,
-                        self.recipe.quant.shift,
-                    );
-
-                    let p0 = quantize::quantize(FieldSample(s1), qmin, qmax, n);
-                    let p1 = quantize::quantize(FieldSample(s2), qmin, qmax, n);
-
-                    let tok = PairToken { a: p0, b: p1 };
                     self.stats.emissions += 1;
 
                     // Reset behavior
@@ -158,16 +151,7 @@ impl Engine {
                     };
 
                     self.mode = Mode::FreeOrbit(next_free);
-
-                    Some((
-                        tok,
-                        EmissionField {
-                            raw_a: s1_raw,
-                            raw_c: s2_raw,
-                            clamped_a: s1,
-                            clamped_c: s2,
-                        },
-                    ))
+                    Some(lock_next.phi_l)
                 } else {
                     self.mode = Mode::Lockstep {
                         pre_lock,
@@ -179,6 +163,60 @@ impl Engine {
         }
     }
 
+    /// Token and fields of the emission `advance` just reported at `phi_l`.
+    fn sample_emission(&self, phi_l: Turn32) -> (PairToken, EmissionField) {
+        // Emit at top rim (t == MAX)
+        let phi1 = phi_l;
+        let phi2 = phi_l.wrapping_add(self.recipe.lock.delta);
+        let t_top = Unit32::MAX;
+
+        // raw + clamped (clamp comes from recipe-driven model.cfg)
+        let s1_raw = tri_wave::eval_raw(&self.field, phi1, t_top, self.time);
+        let s2_raw = tri_wave::eval_raw(&self.field, phi2, t_top, self.time);
+
+        let s1 = self.field.cfg.apply(s1_raw);
+        let s2 = self.field.cfg.apply(s2_raw);
+
+        // quantize to N=16 bins using recipe quant range (+ optional shift)
+        let n = match self.recipe.alphabet {
+            Alphabet::N16 => 16u8,
+        };
+
+        // Apply the deterministic "bin boundary shift" knob:
+        // eff_min = min + shift
+        // eff_max = max + shift
+        let (qmin, qmax) = quantize::shifted_bounds(
+            self.recipe.quant.min,
+            self.recipe.quant.max,
+            self.recipe.quant.shift,
+        );
+
+        let p0 = quantize::quantize_with_gamma(
+            FieldSample(s1),
+            qmin,
+            qmax,
+            n,
+            self.gamma_curve.as_ref(),
+        );
+        let p1 = quantize::quantize_with_gamma(
+            FieldSample(s2),
+            qmin,
+            qmax,
+            n,
+            self.gamma_curve.as_ref(),
+        );
+
+        (
+            PairToken { a: p0, b: p1 },
+            EmissionField {
+                raw_a: s1_raw,
+                raw_c: s2_raw,
+                clamped_a: s1,
+                clamped_c: s2,
+            },
+        )
+    }
+
     /// Run until we collect `k` emissions (or until `max_ticks`).
     pub fn run_emissions(&mut self, k: u64, max_ticks: u64) -> Vec<PairToken> {
         let mut out = Vec::with_capacity(k as usize);
@@ -190,6 +228,250 @@ impl Engine {
         out
     }
 
+    /// Streaming run_emissions: hands each of up to `n` tokens to `callback` together
+    /// with its absolute emission index instead of collecting them, so nothing is
+    /// allocated. Stops early once `stats.ticks` reaches `max_ticks`; returns how many
+    /// tokens were delivered.
+    pub fn run_with_emission_callback<F: FnMut(u64, PairToken)>(
+        &mut self,
+        n: u64,
+        max_ticks: u64,
+        callback: &mut F,
+    ) -> u64 {
+        let mut delivered = 0u64;
+        while delivered < n && self.stats.ticks < max_ticks {
+            if let Some(tok) = self.step() {
+                callback(self.stats.emissions - 1, tok);
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+
+    /// Like run_emissions, but pairs each token with its absolute emission index
+    /// (`stats.emissions - 1` right after the emitting step). Indices are strictly
+    /// increasing and continue from wherever the engine currently is.
+    pub fn run_emissions_with_positions(
+        &mut self,
+        k: u64,
+        max_ticks: u64,
+    ) -> Vec<(u64, PairToken)> {
+        let mut out = Vec::with_capacity(k as usize);
+        while out.len() < k as usize && self.stats.ticks < max_ticks {
+            if let Some(tok) = self.step() {
+                out.push((self.stats.emissions - 1, tok));
+            }
+        }
+        out
+    }
+
+    /// Decimated run_emissions: keeps the next emission and then every `k`-th one after
+    /// it (skipping `k - 1` between kept tokens) until `n` are collected or `max_ticks`
+    /// is reached. Skipped emissions advance the dynamics without evaluating the field.
+    /// `k = 0` is treated as 1.
+    pub fn run_every_k(&mut self, k: u64, n: u64, max_ticks: u64) -> Vec<PairToken> {
+        self.run_every_k_with_positions(k, n, max_ticks)
+            .into_iter()
+            .map(|(_, tok)| tok)
+            .collect()
+    }
+
+    /// run_every_k with each kept token's absolute emission index; consecutive
+    /// indices differ by exactly `k`.
+    pub fn run_every_k_with_positions(
+        &mut self,
+        k: u64,
+        n: u64,
+        max_ticks: u64,
+    ) -> Vec<(u64, PairToken)> {
+        let k = k.max(1);
+        let mut out = Vec::with_capacity(n as usize);
+        let mut skip = 0u64;
+        while out.len() < n as usize && self.stats.ticks < max_ticks {
+            let Some(phi_l) = self.advance() else {
+                continue;
+            };
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let (tok, _) = self.sample_emission(phi_l);
+            out.push((self.stats.emissions - 1, tok));
+            skip = k - 1;
+        }
+        out
+    }
+
+    /// Step until an emission packs to `target`; returns its absolute emission
+    /// index and token, or None once `stats.ticks` reaches `max_ticks`.
+    pub fn run_until_byte(&mut self, target: u8, max_ticks: u64) -> Option<(u64, PairToken)> {
+        self.run_until_byte_within(target, u64::MAX, max_ticks)
+    }
+
+    /// `run_until_byte` that also gives up once `stats.emissions` reaches
+    /// `max_emissions`, so only emission indices below it can match.
+    pub fn run_until_byte_within(
+        &mut self,
+        target: u8,
+        max_emissions: u64,
+        max_ticks: u64,
+    ) -> Option<(u64, PairToken)> {
+        while self.stats.emissions < max_emissions {
+            let tok = self.next_emission(max_ticks)?;
+            if tok.pack_byte() == target {
+                return Some((self.stats.emissions - 1, tok));
+            }
+        }
+        None
+    }
+
+    /// Greedy in-order match of `target` (each byte via `run_until_byte`), i.e. the
+    /// search `timemap fit` performs. None if the tick budget runs out first.
+    pub fn run_until_sequence(
+        &mut self,
+        target: &[u8],
+        max_ticks: u64,
+    ) -> Option<Vec<(u64, PairToken)>> {
+        target
+            .iter()
+            .map(|&b| self.run_until_byte(b, max_ticks))
+            .collect()
+    }
+
+    /// Like run_emissions, but also accumulates `QuantStats` over the packed bytes.
+    pub fn run_emissions_with_stats(
+        &mut self,
+        k: u64,
+        max_ticks: u64,
+    ) -> (Vec<PairToken>, QuantStats) {
+        let mut out = Vec::with_capacity(k as usize);
+        let mut qs = QuantStats::new();
+        while qs.count() < k && self.stats.ticks < max_ticks {
+            if let Some(tok) = self.step() {
+                qs.update(tok.pack_byte());
+                out.push(tok);
+            }
+        }
+        (out, qs)
+    }
+
+    /// Like run_emissions, but also tracks the ticks between emissions. The first
+    /// gap is measured from the engine's tick count when the call starts.
+    pub fn run_emissions_with_tpe_stats(
+        &mut self,
+        k: u64,
+        max_ticks: u64,
+    ) -> (Vec<PairToken>, TpeStats) {
+        let mut out = Vec::with_capacity(k as usize);
+        let mut tpe = TpeStats::new();
+        let mut last = self.stats.ticks;
+        while out.len() < k as usize && self.stats.ticks < max_ticks {
+            if let Some(tok) = self.step() {
+                tpe.update(self.stats.ticks - last);
+                last = self.stats.ticks;
+                out.push(tok);
+            }
+        }
+        (out, tpe)
+    }
+
+    /// (mean, stddev, min, max) ticks per emission over the next `n` emissions. The
+    /// tick budget is `n` times the upper bound of `expected_emission_period_range`;
+    /// a recipe that never aligns returns all zeros without stepping.
+    pub fn ticks_per_emission_stats(&mut self, n: u64) -> (f64, f64, u64, u64) {
+        let (_, hi) = self.expected_emission_period_range();
+        if hi == u64::MAX {
+            return TpeStats::new().summary();
+        }
+        let max_ticks = self.stats.ticks.saturating_add(n.saturating_mul(hi));
+        self.run_emissions_with_tpe_stats(n, max_ticks).1.summary()
+    }
+
+    /// Stats-only run: same cadence as run_emissions, O(1) memory.
+    pub fn run_quant_stats(&mut self, k: u64, max_ticks: u64) -> QuantStats {
+        let mut qs = QuantStats::new();
+        while qs.count() < k && self.stats.ticks < max_ticks {
+            if let Some(tok) = self.step() {
+                qs.update(tok.pack_byte());
+            }
+        }
+        qs
+    }
+
+    /// Runs fresh engines for `recipe_a` and `recipe_b` side by side for `n`
+    /// emissions and returns `(emission_index, byte_a, byte_b)` wherever the packed
+    /// bytes differ. Memory is O(differences). Fails if either recipe is invalid or
+    /// cannot produce `n` emissions within `max_ticks`.
+    pub fn diff_outputs(
+        recipe_a: &Recipe,
+        recipe_b: &Recipe,
+        n: u64,
+        max_ticks: u64,
+    ) -> Result<Vec<(u64, u8, u8)>> {
+        let mut ea = Engine::new(recipe_a.clone())?;
+        let mut eb = Engine::new(recipe_b.clone())?;
+        let mut out = Vec::new();
+        for i in 0..n {
+            let (Some(ta), Some(tb)) = (ea.next_emission(max_ticks), eb.next_emission(max_ticks))
+            else {
+                return Err(K8Error::InsufficientEmissions {
+                    context: "diff_outputs",
+                    needed: n,
+                    produced: i,
+                    max_ticks,
+                    ticks: ea.stats.ticks.max(eb.stats.ticks),
+                });
+            };
+            let (a, b) = (ta.pack_byte(), tb.pack_byte());
+            if a != b {
+                out.push((i, a, b));
+            }
+        }
+        Ok(out)
+    }
+
+    fn next_emission(&mut self, max_ticks: u64) -> Option<PairToken> {
+        while self.stats.ticks < max_ticks {
+            if let Some(tok) = self.step() {
+                return Some(tok);
+            }
+        }
+        None
+    }
+
+    /// Pre-flight guess at how many emissions are left before `stats.ticks` reaches
+    /// `max_ticks`. A clone runs `max_ticks / 1000` ticks (at least 1) from the current
+    /// state and its emission rate is extrapolated linearly over the remaining budget,
+    /// then scaled by 0.9 to stay pessimistic about warm-up. `self` is not advanced.
+    pub fn max_emissions_estimate(&self, max_ticks: u64) -> u64 {
+        let remaining = max_ticks.saturating_sub(self.stats.ticks);
+        if remaining == 0 {
+            return 0;
+        }
+        let calibration_ticks = (max_ticks / 1000).clamp(1, remaining);
+
+        let mut probe = self.clone();
+        let (t0, e0) = (probe.stats.ticks, probe.stats.emissions);
+        while probe.stats.ticks - t0 < calibration_ticks {
+            probe.step();
+        }
+        let seen = probe.stats.emissions - e0;
+
+        let est = seen as u128 * remaining as u128 * 9 / (calibration_ticks as u128 * 10);
+        est.min(u64::MAX as u128) as u64
+    }
+
+    /// Runs `n` emissions and applies the built-in randomness battery to the packed
+    /// bytes (alpha = STAT_TEST_ALPHA). Fewer bytes are tested if max_ticks runs out.
+    pub fn statistical_test_run(&mut self, n: u64, max_ticks: u64) -> StatTestReport {
+        let bytes: Vec<u8> = self
+            .run_emissions(n, max_ticks)
+            .iter()
+            .map(|t| t.pack_byte())
+            .collect();
+        stat_test_bytes(&bytes, STAT_TEST_ALPHA)
+    }
+
     /// Like run_emissions, but also returns field-range stats measured at emission time.
     pub fn run_emissions_with_field_stats(
         &mut self,
@@ -237,8 +519,8 @@ impl Engine {
                         let s1_raw = tri_wave::eval_raw(&self.field, phi1, t_top, self.time);
                         let s2_raw = tri_wave::eval_raw(&self.field, phi2, t_top, self.time);
 
-                        let s1 = s1_raw.clamp(self.field.cfg.clamp_min, self.field.cfg.clamp_max);
-                        let s2 = s2_raw.clamp(self.field.cfg.clamp_min, self.field.cfg.clamp_max);
+                        let s1 = self.field.cfg.apply(s1_raw);
+                        let s2 = self.field.cfg.apply(s2_raw);
 
                         fr.observe(s1_raw, s1);
                         fr.observe(s2_raw, s2);
@@ -254,8 +536,20 @@ impl Engine {
                             self.recipe.quant.shift,
                         );
 
-                        let p0 = quantize::quantize(FieldSample(s1), qmin, qmax, n);
-                        let p1 = quantize::quantize(FieldSample(s2), qmin, qmax, n);
+                        let p0 = quantize::quantize_with_gamma(
+                            FieldSample(s1),
+                            qmin,
+                            qmax,
+                            n,
+                            self.gamma_curve.as_ref(),
+                        );
+                        let p1 = quantize::quantize_with_gamma(
+                            FieldSample(s2),
+                            qmin,
+                            qmax,
+                            n,
+                            self.gamma_curve.as_ref(),
+                        );
 
                         let tok = PairToken { a: p0, b: p1 };
                         self.stats.emissions += 1;
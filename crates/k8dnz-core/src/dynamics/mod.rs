@@ -1,5 +1,8 @@
+pub mod describe;
+pub mod emission_buffer;
 pub mod engine;
 pub mod free_orbit;
 pub mod lockstep;
 pub mod reset;
+pub mod snapshot;
 pub mod state;
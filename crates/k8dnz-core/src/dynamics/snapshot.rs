@@ -0,0 +1,144 @@
+// crates/k8dnz-core/src/dynamics/snapshot.rs
+//
+// Engine state snapshots: serialize a stepping engine mid-stream and resume it later.
+//
+// Layout (v1, all integers LE):
+//   magic "K8ST" | version:u16 | recipe_len:u32 | recipe (.k8r bytes, ends in recipe id)
+//   mode:u8 (0 = free orbit, 1 = lockstep) | phi_a:u32 | phi_c:u32 | [phi_l:u32 | t:u32]
+//   ticks:u64 | alignments:u64 | emissions:u64 | time:u64 | crc32 over everything before
+//
+// The field model is a pure function of the recipe, so it is rebuilt, not stored.
+
+use crate::dynamics::engine::Engine;
+use crate::dynamics::state::{FreeOrbitState, LockstepState, Mode};
+use crate::error::{K8Error, Result};
+use crate::fixed::{turn32::Turn32, unit32::Unit32};
+use crate::recipe::checksum::crc32;
+use crate::recipe::format;
+use crate::stats::counters::Counters;
+
+const STATE_MAGIC: &[u8; 4] = b"K8ST";
+pub const STATE_VERSION: u16 = 1;
+
+const MODE_FREE_ORBIT: u8 = 0;
+const MODE_LOCKSTEP: u8 = 1;
+
+impl Engine {
+    /// Snapshot the full stepping state (embedded recipe, orbit phases, counters).
+    pub fn serialize_state(&self) -> Vec<u8> {
+        let recipe = format::encode(&self.recipe);
+
+        let mut b = Vec::with_capacity(recipe.len() + 64);
+        b.extend_from_slice(STATE_MAGIC);
+        b.extend_from_slice(&STATE_VERSION.to_le_bytes());
+        b.extend_from_slice(&(recipe.len() as u32).to_le_bytes());
+        b.extend_from_slice(&recipe);
+
+        let push_free = |b: &mut Vec<u8>, s: &FreeOrbitState| {
+            b.extend_from_slice(&s.phi_a.0.to_le_bytes());
+            b.extend_from_slice(&s.phi_c.0.to_le_bytes());
+        };
+        match &self.mode {
+            Mode::FreeOrbit(s) => {
+                b.push(MODE_FREE_ORBIT);
+                push_free(&mut b, s);
+            }
+            Mode::Lockstep { pre_lock, lock } => {
+                b.push(MODE_LOCKSTEP);
+                push_free(&mut b, pre_lock);
+                b.extend_from_slice(&lock.phi_l.0.to_le_bytes());
+                b.extend_from_slice(&lock.t.0.to_le_bytes());
+            }
+        }
+
+        for v in [
+            self.stats.ticks,
+            self.stats.alignments,
+            self.stats.emissions,
+            self.time,
+        ] {
+            b.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let c = crc32(&b);
+        b.extend_from_slice(&c.to_le_bytes());
+        b
+    }
+
+    /// Rebuild an engine from `serialize_state` bytes. The next `step` continues
+    /// exactly where the snapshotted engine stopped.
+    pub fn deserialize_state(bytes: &[u8]) -> Result<Engine> {
+        if bytes.len() < 8 {
+            return Err(K8Error::TruncatedInput { context: "engine state", expected: 8, got: bytes.len() });
+        }
+        let magic: [u8; 4] = bytes[0..4].try_into().unwrap();
+        if &magic != STATE_MAGIC {
+            return Err(K8Error::BadMagic { context: "engine state", expected: *STATE_MAGIC, got: magic });
+        }
+        let (body, tail) = bytes.split_at(bytes.len() - 4);
+        let want = u32::from_le_bytes(tail.try_into().unwrap());
+        if crc32(body) != want {
+            return Err(bad("crc mismatch"));
+        }
+
+        let mut i = 4usize;
+        let version = u16::from_le_bytes(take(body, &mut i, 2)?.try_into().unwrap());
+        if version != STATE_VERSION {
+            return Err(K8Error::BadVersion { expected: STATE_VERSION, got: version });
+        }
+
+        let recipe_len = read_u32(body, &mut i)? as usize;
+        let recipe = format::decode(take(body, &mut i, recipe_len)?)?;
+        let mut engine = Engine::new(recipe)?;
+
+        let tag = take(body, &mut i, 1)?[0];
+        let pre = FreeOrbitState {
+            phi_a: Turn32(read_u32(body, &mut i)?),
+            phi_c: Turn32(read_u32(body, &mut i)?),
+        };
+        engine.mode = match tag {
+            MODE_FREE_ORBIT => Mode::FreeOrbit(pre),
+            MODE_LOCKSTEP => Mode::Lockstep {
+                pre_lock: pre,
+                lock: LockstepState {
+                    phi_l: Turn32(read_u32(body, &mut i)?),
+                    t: Unit32(read_u32(body, &mut i)?),
+                },
+            },
+            other => return Err(bad(&format!("unknown mode tag {other}"))),
+        };
+
+        engine.stats = Counters {
+            ticks: read_u64(body, &mut i)?,
+            alignments: read_u64(body, &mut i)?,
+            emissions: read_u64(body, &mut i)?,
+        };
+        engine.time = read_u64(body, &mut i)?;
+
+        if i != body.len() {
+            return Err(bad("trailing bytes"));
+        }
+        Ok(engine)
+    }
+}
+
+fn bad(msg: &str) -> K8Error {
+    K8Error::Validation(format!("engine state: {msg}"))
+}
+
+fn take<'a>(bytes: &'a [u8], i: &mut usize, n: usize) -> Result<&'a [u8]> {
+    if bytes.len() < *i + n {
+        return Err(K8Error::TruncatedInput { context: "engine state", expected: *i + n, got: bytes.len() });
+    }
+    let s = &bytes[*i..*i + n];
+    *i += n;
+    Ok(s)
+}
+
+fn read_u32(bytes: &[u8], i: &mut usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(take(bytes, i, 4)?.try_into().unwrap()))
+}
+
+fn read_u64(bytes: &[u8], i: &mut usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(take(bytes, i, 8)?.try_into().unwrap()))
+}
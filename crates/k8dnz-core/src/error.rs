@@ -10,6 +10,37 @@ pub enum K8Error {
     #[error("recipe format error: {0}")]
     RecipeFormat(String),
 
+    /// A prediction missed more symbols than a patch is worth (see `PatchList::from_symbol_streams`).
+    #[error("too many mismatches: {count} exceeds limit {limit}")]
+    TooManyMismatches { count: usize, limit: usize },
+
+    /// Input ended before a fixed-size header or field could be read.
+    /// `context` names the reader, e.g. "ark1s" or "varint".
+    #[error("{context}: truncated input: expected {expected} bytes, got {got}")]
+    TruncatedInput { context: &'static str, expected: usize, got: usize },
+
+    /// `context` names the format, e.g. "timemap" or "recipe".
+    #[error("{context}: bad magic: expected \"{}\", got \"{}\"", .expected.escape_ascii(), .got.escape_ascii())]
+    BadMagic { context: &'static str, expected: [u8; 4], got: [u8; 4] },
+
+    /// `expected` is the newest version this build can read.
+    #[error("bad version: expected {expected}, got {got}")]
+    BadVersion { expected: u16, got: u16 },
+
+    /// A length-prefixed field (or lane read) `offset..offset + len` runs past the end of its buffer.
+    #[error("{name} out of bounds (offset {offset}, len {len})")]
+    OutOfBounds { name: &'static str, offset: usize, len: usize },
+
+    /// The engine did not produce enough emissions within its tick budget.
+    /// `ticks` is how far the engine ran before giving up.
+    #[error("{context}: insufficient emissions (need {needed}, got {produced}) within max_ticks={max_ticks} (ticks={ticks})")]
+    InsufficientEmissions { context: &'static str, needed: u64, produced: u64, max_ticks: u64, ticks: u64 },
+
+    /// A .k8rs share header no split can produce: index outside 1..=shares or
+    /// threshold outside 2..=shares.
+    #[error("share: invalid header (index {index}, threshold {threshold}, shares {shares})")]
+    InvalidShare { index: u8, threshold: u8, shares: u8 },
+
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -0,0 +1,38 @@
+/// Hard clip into the inclusive range `[min, max]`.
+#[inline]
+pub fn hard_clamp(x: i64, min: i64, max: i64) -> i64 {
+    x.clamp(min, max)
+}
+
+/// Smooth saturation into `[min, max]`.
+///
+/// Values at least `knee` away from both bounds pass through unchanged. Inside
+/// the knee band the overshoot `d` past `max - knee` (or below `min + knee`) is
+/// compressed to `knee * d / (d + knee)`: an integer stand-in for
+/// `knee * tanh(d / knee)` with the same unit slope at the band edge and the
+/// same asymptote, so outputs never reach the bounds themselves and far-out
+/// samples no longer pile up on a single boundary value.
+///
+/// `knee` is capped at half the range width; `knee == 0` is a hard clamp.
+pub fn soft_clamp(x: i64, min: i64, max: i64, knee: u32) -> i64 {
+    if min >= max {
+        return hard_clamp(x, min, max);
+    }
+    let width = (max as i128) - (min as i128);
+    let k = (knee as i128).min(width / 2);
+    if k == 0 {
+        return hard_clamp(x, min, max);
+    }
+
+    let (x, lo, hi) = (x as i128, min as i128 + k, max as i128 - k);
+    let y = if x > hi {
+        let d = x - hi;
+        hi + k * d / (d + k)
+    } else if x < lo {
+        let d = lo - x;
+        lo - k * d / (d + k)
+    } else {
+        x
+    };
+    y as i64
+}
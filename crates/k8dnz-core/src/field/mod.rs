@@ -1,2 +1,3 @@
+pub mod clamp;
 pub mod params;
 pub mod tri_wave;
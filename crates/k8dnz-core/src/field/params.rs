@@ -1,3 +1,4 @@
+use crate::field::clamp::{hard_clamp, soft_clamp};
 use crate::recipe::recipe::{FieldClampParams, FieldParams};
 
 #[derive(Clone, Copy, Debug)]
@@ -6,6 +7,8 @@ pub struct FieldEvalCfg {
     /// Must be wide enough that typical wave sums don't saturate.
     pub clamp_min: i64,
     pub clamp_max: i64,
+    /// Soft saturation band width (see `clamp::soft_clamp`); None = hard clip.
+    pub soft_knee: Option<u32>,
 }
 
 impl Default for FieldEvalCfg {
@@ -14,6 +17,7 @@ impl Default for FieldEvalCfg {
         Self {
             clamp_min: -100_000_000,
             clamp_max: 100_000_000,
+            soft_knee: None,
         }
     }
 }
@@ -23,6 +27,18 @@ impl From<FieldClampParams> for FieldEvalCfg {
         Self {
             clamp_min: c.min,
             clamp_max: c.max,
+            soft_knee: c.soft_knee,
+        }
+    }
+}
+
+impl FieldEvalCfg {
+    /// Clamp a raw field sample, soft or hard depending on `soft_knee`.
+    #[inline]
+    pub fn apply(&self, raw: i64) -> i64 {
+        match self.soft_knee {
+            Some(knee) => soft_clamp(raw, self.clamp_min, self.clamp_max, knee),
+            None => hard_clamp(raw, self.clamp_min, self.clamp_max),
         }
     }
 }
@@ -25,7 +25,7 @@ pub fn eval_raw(model: &FieldModel, phi: Turn32, t: Unit32, time: u64) -> i64 {
 
 /// Evaluate field at (phi, t, time) and clamp to model cfg.
 pub fn eval(model: &FieldModel, phi: Turn32, t: Unit32, time: u64) -> i64 {
-    eval_raw(model, phi, t, time).clamp(model.cfg.clamp_min, model.cfg.clamp_max)
+    model.cfg.apply(eval_raw(model, phi, t, time))
 }
 
 #[inline]
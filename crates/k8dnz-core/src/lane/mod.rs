@@ -7,8 +7,16 @@
 //     kind_lane: {LETTER, DIGIT, PUNCT, RAW} length = other_count
 //     case_lane: {LOWER, UPPER} length = n_letters
 //     letter_lane: 0..25 for a..z length = n_letters
+//       (UTF-8-aware mode, v5: 0..=120 = a..z then LATIN_EXT_LOWER, U+00C0..U+017E letters)
 //     digit_lane: 0..9 length = n_digits
-//     punct_lane: 0..(PUNCT_ALPH.len-1) length = n_punct
+//       OR (numeric mode) one entry per maximal digit run (chunked at NUMERIC_MAX_RUN):
+//       numeric_run_lane: run length 1..=19 length = n_runs
+//       numeric_lane: integer value of the run (u64) length = n_runs
+//       OR (DECIMAL_RUN mode, v6) runs of 3+ digits keep their leading digits in digit_lane:
+//       decrun_val_lane: last three digits of the run (value mod 1000) length = n_decruns
+//       (run lengths follow from class/kind, so they are not stored; v6 exists because
+//       digit_lane no longer holds every digit and older decoders would misread it)
+//     punct_lane: 0..(alphabet.len-1) length = n_punct  (PUNCT_ALPH unless the artifact carries its own)
 //     raw_lane: raw bytes length = n_raw
 //
 // Prediction:
@@ -46,21 +54,61 @@
 //     class_patch_len: varint, class_patch_bytes
 //     other_patch_len: varint, other_patch_bytes
 //
+//   v4 layout (v3 + user punctuation alphabet; only emitted for a non-default alphabet):
+//     total_len: varint
+//     other_len: varint
+//     max_ticks: varint
+//     recipe_len: varint, recipe bytes
+//     omega_len: varint, omega bytes   (OmegaProgram, same encoding as v3)
+//     punct_len: varint, punct alphabet bytes
+//     class_patch_len: varint, class_patch_bytes
+//     other_patch_len: varint, other_patch_bytes
+//
+//   v5 layout (v4 + text flags; only emitted for UTF-8-aware letters):
+//     total_len: varint                (lane symbols: a 2-byte accented letter counts once)
+//     other_len: varint
+//     max_ticks: varint
+//     recipe_len: varint, recipe bytes
+//     omega_len: varint, omega bytes   (OmegaProgram, same encoding as v3)
+//     punct_len: varint, punct alphabet bytes (0 = PUNCT_ALPH)
+//     text_flags: u8                   (bit 0: UTF-8-aware letter lane)
+//     class_patch_len: varint, class_patch_bytes
+//     other_patch_len: varint, other_patch_bytes
+//
+//   v6 layout (v5 header; only emitted when DECIMAL_RUN digits won):
+//     same fields as v5; other_patch_bytes carries DECRUN_LEN/DECRUN_VAL
+//
+//   v8 layout (v5 header; only emitted when numeric digits won):
+//     same fields as v5; other_patch_bytes carries NUMERIC in place of DIGIT
+//
 // IMPORTANT: other_patch_bytes is a mux container holding multiple PatchList blobs:
 //   varint n
 //   repeated n times: varint id, varint len, len bytes
 // ids must match k8dnz-cli demux_other_patches() constants.
+// Numeric mode emits NUMERIC in place of DIGIT (auto-selected when smaller; v8 artifacts
+// only, so older decoders fail on the version instead of misreading digits). Run lengths
+// follow from class/kind and are not stored.
+// DECIMAL_RUN mode adds DECRUN_LEN/DECRUN_VAL next to DIGIT (v6 artifacts only).
+// v7 = v6 layout whose class_patch_bytes and non-digit mux blobs may be PZST zstd
+// envelopes (see symbol::patch); only v7 blobs go through PatchList::decode_auto,
+// older versions are plain PatchList::decode. DECIMAL_RUN ids may appear in v7 too.
+// v8 blobs may be PZST envelopes as in v7.
 //
 // Public API contract (matches k8dnz-cli expectations):
-//   encode_k8l1(input, recipe_bytes, max_ticks) -> (artifact_bytes, stats)
+//   encode_k8l1(input, recipe_bytes, max_ticks, punct_alphabet) -> (artifact_bytes, stats)
+//   encode_k8l1_with_punct(input, recipe_bytes, max_ticks, omega_prog, punct_alphabet) -> (artifact_bytes, stats)
+//   encode_k8l1_with_config(input, recipe_bytes, max_ticks, omega_prog, punct_alphabet, cfg) -> (artifact_bytes, stats)
 //   encode_k8l1_with_omega(input, recipe_bytes, max_ticks, omega) -> (artifact_bytes, stats)
 //   encode_k8l1_with_omega_prog(input, recipe_bytes, max_ticks, omega_prog) -> (artifact_bytes, stats)
+//   write_k8l1_with_omega_prog(w, input, recipe_bytes, max_ticks, omega_prog) -> stats  (streams to w)
 //   decode_k8l1(bytes) -> decoded bytes
 
+use std::io::Write;
+
 use crate::error::{K8Error, Result};
 use crate::recipe::format as recipe_format;
 use crate::repr::text_norm;
-use crate::symbol::patch::PatchList;
+use crate::symbol::patch::{self, PatchList};
 use crate::symbol::varint;
 use crate::{Engine, Recipe};
 
@@ -68,13 +116,129 @@ pub const MAGIC_K8L1: [u8; 4] = *b"K8L1";
 pub const K8L1_VERSION_V1: u8 = 1;
 pub const K8L1_VERSION_V2: u8 = 2;
 pub const K8L1_VERSION_V3: u8 = 3;
+pub const K8L1_VERSION_V4: u8 = 4;
+pub const K8L1_VERSION_V5: u8 = 5;
+pub const K8L1_VERSION_V6: u8 = 6;
+pub const K8L1_VERSION_V7: u8 = 7;
+pub const K8L1_VERSION_V8: u8 = 8;
 
 // Default version we emit going forward (v2 unless segmented Ω requires v3).
 pub const K8L1_VERSION: u8 = K8L1_VERSION_V2;
 
-// -------------------- punctuation alphabet (fixed, corpus-free) --------------------
+// -------------------- punctuation alphabet (default is corpus-free) --------------------
+
+/// Default punctuation alphabet. Artifacts built with another alphabet embed it (v4).
+pub const PUNCT_ALPH: &[u8] = b".,;:?!'\"()-";
 
-const PUNCT_ALPH: &[u8] = b".,;:?!'\"()-";
+/// A custom alphabet must be non-empty, duplicate-free and only hold bytes that
+/// would otherwise reach the OTHER/RAW path (letters, digits, ' ' and '\n' never do).
+pub fn validate_punct_alphabet(alph: &[u8]) -> Result<()> {
+    if alph.is_empty() {
+        return Err(K8Error::Validation("punct alphabet: empty".to_string()));
+    }
+    if alph.len() > u8::MAX as usize {
+        return Err(K8Error::Validation(format!("punct alphabet: {} symbols (max 255)", alph.len())));
+    }
+    let mut seen = [false; 256];
+    for &b in alph {
+        if b.is_ascii_alphanumeric() || b == b' ' || b == b'\n' || b == b'\r' {
+            return Err(K8Error::Validation(format!(
+                "punct alphabet: byte 0x{b:02x} is handled by another lane"
+            )));
+        }
+        if std::mem::replace(&mut seen[b as usize], true) {
+            return Err(K8Error::Validation(format!("punct alphabet: duplicate byte 0x{b:02x}")));
+        }
+    }
+    Ok(())
+}
+
+// Longest digit run folded into one numeric symbol (10^19 - 1 still fits in u64).
+const NUMERIC_MAX_RUN: u8 = 19;
+
+// Shortest digit run (after NUMERIC_MAX_RUN chunking) coded as a DECIMAL_RUN; its last
+// DECRUN_TAIL digits become one value in 0..1000.
+const DECRUN_MIN_RUN: u8 = 3;
+const DECRUN_TAIL: u8 = 3;
+
+// -------------------- UTF-8-aware letters (v5) --------------------
+
+/// Text lane options. The default keeps the ASCII-only letter lane (v1..v4 artifacts).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextLanesV2Config {
+    /// For valid UTF-8 input, code Latin-1 Supplement / Latin Extended-A letters
+    /// (U+00C0..U+017E) as letters with case instead of two raw bytes each.
+    pub utf8_aware: bool,
+    /// Also try DECIMAL_RUN digits (runs of 3+ digits as length + value mod 1000); the
+    /// artifact is v6 when that beats the per-digit and numeric encodings.
+    pub decimal_runs: bool,
+}
+
+/// `text_flags` bit: the letter lane is UTF-8-aware (see `k8l1_text_flags`).
+pub const TEXT_FLAG_UTF8: u8 = 0x01;
+
+const LETTERS_ASCII: u8 = 26;
+const LETTERS_UTF8: u8 = LETTERS_ASCII + LATIN_EXT_LOWER.len() as u8;
+
+// Lowercase letters in U+00C0..U+017E; letter index = 26 + position. U+0131 (dotless i)
+// is left out because it uppercases to ASCII 'I'.
+const LATIN_EXT_LOWER: [u16; 95] = [
+    0x00DF, 0x00E0, 0x00E1, 0x00E2, 0x00E3, 0x00E4, 0x00E5, 0x00E6, 0x00E7, 0x00E8,
+    0x00E9, 0x00EA, 0x00EB, 0x00EC, 0x00ED, 0x00EE, 0x00EF, 0x00F0, 0x00F1, 0x00F2,
+    0x00F3, 0x00F4, 0x00F5, 0x00F6, 0x00F8, 0x00F9, 0x00FA, 0x00FB, 0x00FC, 0x00FD,
+    0x00FE, 0x00FF, 0x0101, 0x0103, 0x0105, 0x0107, 0x0109, 0x010B, 0x010D, 0x010F,
+    0x0111, 0x0113, 0x0115, 0x0117, 0x0119, 0x011B, 0x011D, 0x011F, 0x0121, 0x0123,
+    0x0125, 0x0127, 0x0129, 0x012B, 0x012D, 0x012F, 0x0133, 0x0135, 0x0137, 0x0138,
+    0x013A, 0x013C, 0x013E, 0x0140, 0x0142, 0x0144, 0x0146, 0x0148, 0x0149, 0x014B,
+    0x014D, 0x014F, 0x0151, 0x0153, 0x0155, 0x0157, 0x0159, 0x015B, 0x015D, 0x015F,
+    0x0161, 0x0163, 0x0165, 0x0167, 0x0169, 0x016B, 0x016D, 0x016F, 0x0171, 0x0173,
+    0x0175, 0x0177, 0x017A, 0x017C, 0x017E,
+];
+
+// Uppercase partner of LATIN_EXT_LOWER[i] (0 = none: U+00DF, U+0138, U+0149).
+const LATIN_EXT_UPPER: [u16; 95] = [
+    0x0000, 0x00C0, 0x00C1, 0x00C2, 0x00C3, 0x00C4, 0x00C5, 0x00C6, 0x00C7, 0x00C8,
+    0x00C9, 0x00CA, 0x00CB, 0x00CC, 0x00CD, 0x00CE, 0x00CF, 0x00D0, 0x00D1, 0x00D2,
+    0x00D3, 0x00D4, 0x00D5, 0x00D6, 0x00D8, 0x00D9, 0x00DA, 0x00DB, 0x00DC, 0x00DD,
+    0x00DE, 0x0178, 0x0100, 0x0102, 0x0104, 0x0106, 0x0108, 0x010A, 0x010C, 0x010E,
+    0x0110, 0x0112, 0x0114, 0x0116, 0x0118, 0x011A, 0x011C, 0x011E, 0x0120, 0x0122,
+    0x0124, 0x0126, 0x0128, 0x012A, 0x012C, 0x012E, 0x0132, 0x0134, 0x0136, 0x0000,
+    0x0139, 0x013B, 0x013D, 0x013F, 0x0141, 0x0143, 0x0145, 0x0147, 0x0000, 0x014A,
+    0x014C, 0x014E, 0x0150, 0x0152, 0x0154, 0x0156, 0x0158, 0x015A, 0x015C, 0x015E,
+    0x0160, 0x0162, 0x0164, 0x0166, 0x0168, 0x016A, 0x016C, 0x016E, 0x0170, 0x0172,
+    0x0174, 0x0176, 0x0179, 0x017B, 0x017D,
+];
+
+/// Accented letter at the start of `s`: (letter index, upper, byte length).
+/// Every letter in the tables is a 2-byte UTF-8 sequence with lead byte 0xC3..=0xC5.
+fn latin_ext_letter_at(s: &[u8]) -> Option<(u8, bool, usize)> {
+    let (&b0, &b1) = (s.first()?, s.get(1)?);
+    if !(0xC3..=0xC5).contains(&b0) || b1 & 0xC0 != 0x80 {
+        return None;
+    }
+    let cp = (((b0 & 0x1F) as u16) << 6) | (b1 & 0x3F) as u16;
+    if let Some(ix) = LATIN_EXT_LOWER.iter().position(|&c| c == cp) {
+        return Some((LETTERS_ASCII + ix as u8, false, 2));
+    }
+    LATIN_EXT_UPPER
+        .iter()
+        .position(|&c| c != 0 && c == cp)
+        .map(|ix| (LETTERS_ASCII + ix as u8, true, 2))
+}
+
+fn push_latin_ext_letter(out: &mut Vec<u8>, letter: u8, upper: bool) -> Result<()> {
+    let ix = (letter - LETTERS_ASCII) as usize;
+    let lower = *LATIN_EXT_LOWER
+        .get(ix)
+        .ok_or(K8Error::OutOfBounds { name: "unsplit latin letter", offset: ix, len: 1 })?;
+    let cp = if upper { LATIN_EXT_UPPER[ix] } else { lower };
+    if cp == 0 {
+        return Err(K8Error::Validation(format!("unsplit: letter U+{lower:04X} has no uppercase")));
+    }
+    out.push(0xC0 | (cp >> 6) as u8);
+    out.push(0x80 | (cp & 0x3F) as u8);
+    Ok(())
+}
 
 // -------------------- Ω schedule (v2) --------------------
 
@@ -335,7 +499,9 @@ impl OmegaProgram {
         let mut i = 0usize;
         let ver = varint::get_u64(bytes, &mut i)?;
         if ver != 2 {
-            return Err(K8Error::Validation(format!("omega_prog: bad ver {ver}")));
+            let got = u16::try_from(ver)
+                .map_err(|_| K8Error::Validation(format!("omega_prog: version {ver} out of range")))?;
+            return Err(K8Error::BadVersion { expected: 2, got });
         }
 
         fn get_lane(bytes: &[u8], i: &mut usize) -> Result<LaneOmegaProg> {
@@ -388,6 +554,12 @@ fn bucket_u8(b: u8, k: u8) -> u8 {
     ((b as u16 * k as u16) >> 8) as u8
 }
 
+// Numeric analogue of bucket_u8: map a pack_byte onto 0..10^run_len.
+#[inline]
+fn bucket_numeric(b: u8, run_len: u8) -> u64 {
+    ((b as u128 * 10u128.pow(run_len as u32)) >> 8) as u64
+}
+
 // -------------------- V2 lane model (internal only) --------------------
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -396,10 +568,12 @@ struct TextLanesV2 {
     class_lane: Vec<u8>,   // 0..=2
     kind_lane: Vec<u8>,    // 0..=3, only for OTHER positions
     case_lane: Vec<u8>,    // 0..=1, only for letters
-    letter_lane: Vec<u8>,  // 0..=25, only for letters
+    letter_lane: Vec<u8>,  // 0..=25 (0..=120 when UTF-8-aware), only for letters
     digit_lane: Vec<u8>,   // 0..=9, only for digits
-    punct_lane: Vec<u8>,   // 0..=PUNCT_ALPH.len-1, only for punct
+    punct_lane: Vec<u8>,   // 0..=alphabet.len-1, only for punct
     raw_lane: Vec<u8>,     // raw bytes, only for kind=RAW
+    numeric_run_lane: Vec<u8>, // 1..=NUMERIC_MAX_RUN, one per digit run
+    numeric_lane: Vec<u64>,    // run value; leading zeros are implied by run length
 }
 
 impl TextLanesV2 {
@@ -415,7 +589,8 @@ impl TextLanesV2 {
     const CASE_LOWER: u8 = 0;
     const CASE_UPPER: u8 = 1;
 
-    fn split(norm: &[u8]) -> Result<Self> {
+    /// `utf8_letters`: also take 2-byte accented letters (see `latin_ext_letter_at`).
+    fn split(norm: &[u8], punct_alph: &[u8], utf8_letters: bool) -> Result<Self> {
         let mut class_lane = Vec::with_capacity(norm.len());
         let mut kind_lane = Vec::new();
         let mut case_lane = Vec::new();
@@ -423,8 +598,28 @@ impl TextLanesV2 {
         let mut digit_lane = Vec::new();
         let mut punct_lane = Vec::new();
         let mut raw_lane = Vec::new();
+        let mut numeric_run_lane: Vec<u8> = Vec::new();
+        let mut numeric_lane: Vec<u64> = Vec::new();
+
+        // Digit runs are tracked alongside the per-digit lane; the encoder picks one.
+        let mut prev_digit = false;
+
+        let mut i = 0usize;
+        while i < norm.len() {
+            let b = norm[i];
+            if utf8_letters && b >= 0x80 {
+                if let Some((letter, upper, len)) = latin_ext_letter_at(&norm[i..]) {
+                    class_lane.push(Self::CLASS_OTHER);
+                    kind_lane.push(Self::KIND_LETTER);
+                    case_lane.push(if upper { Self::CASE_UPPER } else { Self::CASE_LOWER });
+                    letter_lane.push(letter);
+                    prev_digit = false;
+                    i += len;
+                    continue;
+                }
+            }
+            i += 1;
 
-        for &b in norm {
             match b {
                 b' ' => class_lane.push(Self::CLASS_SPACE),
                 b'\n' => class_lane.push(Self::CLASS_NL),
@@ -442,8 +637,20 @@ impl TextLanesV2 {
                         }
                     } else if b.is_ascii_digit() {
                         kind_lane.push(Self::KIND_DIGIT);
-                        digit_lane.push((b - b'0') as u8);
-                    } else if let Some(ix) = PUNCT_ALPH.iter().position(|&p| p == b) {
+                        let d = b - b'0';
+                        digit_lane.push(d);
+
+                        match (prev_digit, numeric_run_lane.last_mut(), numeric_lane.last_mut()) {
+                            (true, Some(run), Some(v)) if *run < NUMERIC_MAX_RUN => {
+                                *run += 1;
+                                *v = *v * 10 + d as u64;
+                            }
+                            _ => {
+                                numeric_run_lane.push(1);
+                                numeric_lane.push(d as u64);
+                            }
+                        }
+                    } else if let Some(ix) = punct_alph.iter().position(|&p| p == b) {
                         kind_lane.push(Self::KIND_PUNCT);
                         punct_lane.push(ix as u8);
                     } else {
@@ -452,10 +659,11 @@ impl TextLanesV2 {
                     }
                 }
             }
+            prev_digit = b.is_ascii_digit();
         }
 
         Ok(Self {
-            total_len: norm.len(),
+            total_len: class_lane.len(),
             class_lane,
             kind_lane,
             case_lane,
@@ -463,12 +671,131 @@ impl TextLanesV2 {
             digit_lane,
             punct_lane,
             raw_lane,
+            numeric_run_lane,
+            numeric_lane,
         })
     }
 
-    fn unsplit(self) -> Result<Vec<u8>> {
+    /// Digit run lengths implied by the class/kind lanes (same chunking as `split`).
+    fn derive_numeric_runs(class_lane: &[u8], kind_lane: &[u8]) -> Result<Vec<u8>> {
+        let mut runs: Vec<u8> = Vec::new();
+        let mut k_ix = 0usize;
+        let mut prev_digit = false;
+
+        for &cl in class_lane {
+            let is_digit = if cl == Self::CLASS_OTHER {
+                let k = *kind_lane
+                    .get(k_ix)
+                    .ok_or(K8Error::OutOfBounds { name: "numeric runs kind_lane", offset: k_ix, len: 1 })?;
+                k_ix += 1;
+                k == Self::KIND_DIGIT
+            } else {
+                false
+            };
+
+            if is_digit {
+                match runs.last_mut() {
+                    Some(run) if prev_digit && *run < NUMERIC_MAX_RUN => *run += 1,
+                    _ => runs.push(1),
+                }
+            }
+            prev_digit = is_digit;
+        }
+
+        Ok(runs)
+    }
+
+    /// Expand numeric runs back into per-digit symbols (zero-padded to run length).
+    fn expand_numeric_runs(runs: &[u8], values: &[u64]) -> Result<Vec<u8>> {
+        if runs.len() != values.len() {
+            return Err(K8Error::Validation("unsplit: numeric lanes length mismatch".to_string()));
+        }
+
+        let mut digits = Vec::new();
+        for (&run, &v) in runs.iter().zip(values.iter()) {
+            if run == 0 || run > NUMERIC_MAX_RUN {
+                return Err(K8Error::Validation(format!("unsplit: bad numeric run length {run}")));
+            }
+            if (v as u128) >= 10u128.pow(run as u32) {
+                return Err(K8Error::Validation("unsplit: numeric value exceeds run length".to_string()));
+            }
+            let start = digits.len();
+            let mut x = v;
+            for _ in 0..run {
+                digits.push((x % 10) as u8);
+                x /= 10;
+            }
+            digits[start..].reverse();
+        }
+        Ok(digits)
+    }
+
+    /// DECIMAL_RUN split of the per-digit lane along `runs` (see `derive_numeric_runs`):
+    /// (head digits, decrun values). A run of DECRUN_MIN_RUN+ digits keeps all but its
+    /// last DECRUN_TAIL digits in the head lane; shorter runs stay there whole.
+    fn split_decimal_runs(runs: &[u8], digits: &[u8]) -> Result<(Vec<u8>, Vec<u64>)> {
+        let mut head = Vec::with_capacity(digits.len());
+        let mut vals = Vec::new();
+        let mut d_ix = 0usize;
+
+        for &run in runs {
+            let run_digits = digits
+                .get(d_ix..d_ix + run as usize)
+                .ok_or(K8Error::OutOfBounds { name: "decimal runs digit_lane", offset: d_ix, len: run as usize })?;
+            d_ix += run as usize;
+            if run < DECRUN_MIN_RUN {
+                head.extend_from_slice(run_digits);
+                continue;
+            }
+            let (lead, tail) = run_digits.split_at((run - DECRUN_TAIL) as usize);
+            head.extend_from_slice(lead);
+            vals.push(tail.iter().fold(0u64, |v, &d| v * 10 + d as u64));
+        }
+        if d_ix != digits.len() {
+            return Err(K8Error::Validation("decimal runs: digit_lane too long".to_string()));
+        }
+
+        Ok((head, vals))
+    }
+
+    /// Inverse of `split_decimal_runs`; `vals` holds one entry per DECRUN_MIN_RUN+ run.
+    fn join_decimal_runs(runs: &[u8], head: &[u8], vals: &[u64]) -> Result<Vec<u8>> {
+        if runs.iter().filter(|&&r| r >= DECRUN_MIN_RUN).count() != vals.len() {
+            return Err(K8Error::Validation("unsplit: decimal run lanes disagree with kind lane".to_string()));
+        }
+
+        let mut digits = Vec::with_capacity(head.len() + vals.len() * DECRUN_TAIL as usize);
+        let (mut h_ix, mut v_ix) = (0usize, 0usize);
+        for &run in runs {
+            let n_head = if run < DECRUN_MIN_RUN { run } else { run - DECRUN_TAIL } as usize;
+            let lead = head
+                .get(h_ix..h_ix + n_head)
+                .ok_or(K8Error::OutOfBounds { name: "unsplit digit_lane", offset: h_ix, len: n_head })?;
+            digits.extend_from_slice(lead);
+            h_ix += n_head;
+            if run >= DECRUN_MIN_RUN {
+                let v = vals[v_ix];
+                v_ix += 1;
+                if v >= 1000 {
+                    return Err(K8Error::Validation(format!("unsplit: decimal run value {v} >= 1000")));
+                }
+                digits.extend_from_slice(&[(v / 100) as u8, (v / 10 % 10) as u8, (v % 10) as u8]);
+            }
+        }
+        if h_ix != head.len() {
+            return Err(K8Error::Validation("unsplit: digit_lane too long".to_string()));
+        }
+
+        Ok(digits)
+    }
+
+    fn unsplit(mut self, punct_alph: &[u8]) -> Result<Vec<u8>> {
         let mut out = Vec::with_capacity(self.total_len);
 
+        if self.digit_lane.is_empty() && !self.numeric_run_lane.is_empty() {
+            self.digit_lane = Self::expand_numeric_runs(&self.numeric_run_lane, &self.numeric_lane)?;
+        }
+
         let mut k_ix = 0usize;
         let mut l_ix = 0usize;
         let mut d_ix = 0usize;
@@ -481,7 +808,7 @@ impl TextLanesV2 {
                 Self::CLASS_NL => out.push(b'\n'),
                 Self::CLASS_OTHER => {
                     if k_ix >= self.kind_lane.len() {
-                        return Err(K8Error::Validation("unsplit: kind_lane too short".to_string()));
+                        return Err(K8Error::OutOfBounds { name: "unsplit kind_lane", offset: k_ix, len: 1 });
                     }
                     let k = self.kind_lane[k_ix];
                     k_ix += 1;
@@ -489,23 +816,29 @@ impl TextLanesV2 {
                     match k {
                         Self::KIND_LETTER => {
                             if l_ix >= self.letter_lane.len() || l_ix >= self.case_lane.len() {
-                                return Err(K8Error::Validation(
-                                    "unsplit: letter/case lanes too short".to_string(),
-                                ));
+                                return Err(K8Error::OutOfBounds {
+                                    name: "unsplit letter/case lanes",
+                                    offset: l_ix,
+                                    len: 1,
+                                });
                             }
                             let base = self.letter_lane[l_ix];
                             let case = self.case_lane[l_ix];
                             l_ix += 1;
 
-                            let mut b = b'a' + base;
-                            if case == Self::CASE_UPPER {
-                                b = b.to_ascii_uppercase();
+                            if base >= LETTERS_ASCII {
+                                push_latin_ext_letter(&mut out, base, case == Self::CASE_UPPER)?;
+                            } else {
+                                let mut b = b'a' + base;
+                                if case == Self::CASE_UPPER {
+                                    b = b.to_ascii_uppercase();
+                                }
+                                out.push(b);
                             }
-                            out.push(b);
                         }
                         Self::KIND_DIGIT => {
                             if d_ix >= self.digit_lane.len() {
-                                return Err(K8Error::Validation("unsplit: digit_lane too short".to_string()));
+                                return Err(K8Error::OutOfBounds { name: "unsplit digit_lane", offset: d_ix, len: 1 });
                             }
                             let v = self.digit_lane[d_ix];
                             d_ix += 1;
@@ -513,18 +846,18 @@ impl TextLanesV2 {
                         }
                         Self::KIND_PUNCT => {
                             if p_ix >= self.punct_lane.len() {
-                                return Err(K8Error::Validation("unsplit: punct_lane too short".to_string()));
+                                return Err(K8Error::OutOfBounds { name: "unsplit punct_lane", offset: p_ix, len: 1 });
                             }
                             let ix = self.punct_lane[p_ix] as usize;
                             p_ix += 1;
-                            let b = *PUNCT_ALPH
+                            let b = *punct_alph
                                 .get(ix)
-                                .ok_or_else(|| K8Error::Validation("unsplit: punct index OOB".to_string()))?;
+                                .ok_or(K8Error::OutOfBounds { name: "unsplit punct index", offset: ix, len: 1 })?;
                             out.push(b);
                         }
                         Self::KIND_RAW => {
                             if r_ix >= self.raw_lane.len() {
-                                return Err(K8Error::Validation("unsplit: raw_lane too short".to_string()));
+                                return Err(K8Error::OutOfBounds { name: "unsplit raw_lane", offset: r_ix, len: 1 });
                             }
                             let b = self.raw_lane[r_ix];
                             r_ix += 1;
@@ -550,61 +883,70 @@ const PATCH_LETTER: u64 = 3;
 const PATCH_DIGIT: u64 = 4;
 const PATCH_PUNCT: u64 = 5;
 const PATCH_RAW: u64 = 6;
+const PATCH_NUMERIC: u64 = 7;
+// 8 is reserved: numeric run lengths follow from class/kind, so no patch is stored.
+const PATCH_NUMERIC_RUN: u64 = 8;
+// 9 is reserved; decoders skip it like any unknown id.
+const PATCH_DECRUN_VAL: u64 = 10;
+
+/// Appends the muxed blobs to `out`.
+fn mux_other_patches(out: &mut Vec<u8>, parts: &[(u64, &[u8])]) {
+    write_other_patches(out, parts).expect("write to Vec cannot fail");
+}
 
-fn mux_other_patches(
-    kind: &[u8],
-    caseb: &[u8],
-    letter: &[u8],
-    digit: &[u8],
-    punct: &[u8],
-    raw: &[u8],
-) -> Vec<u8> {
-    // Always emit all 6 in fixed order (simple + deterministic).
-    let mut out = Vec::new();
-    varint::put_u64(6, &mut out);
-
-    fn put(out: &mut Vec<u8>, id: u64, bytes: &[u8]) {
-        varint::put_u64(id, out);
-        varint::put_u64(bytes.len() as u64, out);
-        out.extend_from_slice(bytes);
+fn write_other_patches<W: Write>(w: &mut W, parts: &[(u64, &[u8])]) -> std::io::Result<()> {
+    // Caller supplies parts in fixed order (simple + deterministic).
+    varint::write_u64(w, parts.len() as u64)?;
+
+    for &(id, bytes) in parts {
+        varint::write_u64(w, id)?;
+        varint::write_u64(w, bytes.len() as u64)?;
+        w.write_all(bytes)?;
     }
+    Ok(())
+}
 
-    put(&mut out, PATCH_KIND, kind);
-    put(&mut out, PATCH_CASE, caseb);
-    put(&mut out, PATCH_LETTER, letter);
-    put(&mut out, PATCH_DIGIT, digit);
-    put(&mut out, PATCH_PUNCT, punct);
-    put(&mut out, PATCH_RAW, raw);
-    out
+#[derive(Default)]
+struct OtherPatchBlobs {
+    kind: Vec<u8>,
+    caseb: Vec<u8>,
+    letter: Vec<u8>,
+    digit: Vec<u8>,
+    punct: Vec<u8>,
+    raw: Vec<u8>,
+    // Present only for numeric-mode (v8) artifacts.
+    numeric: Option<Vec<u8>>,
+    // Present only for DECIMAL_RUN (v6) artifacts.
+    decrun_val: Option<Vec<u8>>,
 }
 
-fn demux_other_patches(bytes: &[u8]) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>, Vec<u8>)> {
+fn demux_other_patches(ver: u8, bytes: &[u8]) -> Result<OtherPatchBlobs> {
     let mut i = 0usize;
     let n = varint::get_u64(bytes, &mut i)? as usize;
 
-    let mut kind = Vec::new();
-    let mut caseb = Vec::new();
-    let mut letter = Vec::new();
-    let mut digit = Vec::new();
-    let mut punct = Vec::new();
-    let mut raw = Vec::new();
+    let mut blobs = OtherPatchBlobs::default();
 
     for _ in 0..n {
         let id = varint::get_u64(bytes, &mut i)?;
         let len = varint::get_u64(bytes, &mut i)? as usize;
         if i + len > bytes.len() {
-            return Err(K8Error::Validation("k8l1: other_patch mux len oob".to_string()));
+            return Err(K8Error::OutOfBounds { name: "k8l1 other_patch mux", offset: i, len });
         }
         let chunk = bytes[i..i + len].to_vec();
         i += len;
 
         match id {
-            PATCH_KIND => kind = chunk,
-            PATCH_CASE => caseb = chunk,
-            PATCH_LETTER => letter = chunk,
-            PATCH_DIGIT => digit = chunk,
-            PATCH_PUNCT => punct = chunk,
-            PATCH_RAW => raw = chunk,
+            PATCH_KIND => blobs.kind = chunk,
+            PATCH_CASE => blobs.caseb = chunk,
+            PATCH_LETTER => blobs.letter = chunk,
+            PATCH_DIGIT => blobs.digit = chunk,
+            PATCH_PUNCT => blobs.punct = chunk,
+            PATCH_RAW => blobs.raw = chunk,
+            PATCH_NUMERIC | PATCH_NUMERIC_RUN if ver < K8L1_VERSION_V8 => {
+                return Err(K8Error::Validation(format!("K8L1 v{ver}: numeric lanes need v8")));
+            }
+            PATCH_NUMERIC => blobs.numeric = Some(chunk),
+            PATCH_DECRUN_VAL => blobs.decrun_val = Some(chunk),
             _ => {}
         }
     }
@@ -613,7 +955,25 @@ fn demux_other_patches(bytes: &[u8]) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>, Vec<u
         return Err(K8Error::Validation("k8l1: other_patch mux trailing bytes".to_string()));
     }
 
-    Ok((kind, caseb, letter, digit, punct, raw))
+    Ok(blobs)
+}
+
+/// Decodes one K8L1 patch blob; PZST envelopes are only legal from v7 on and may not
+/// inflate past what a patch over `total_len` symbols can take.
+pub fn decode_patch_blob(ver: u8, b: &[u8], total_len: usize) -> Result<PatchList> {
+    if ver >= K8L1_VERSION_V7 {
+        PatchList::decode_auto(b, patch::max_raw_len(total_len))
+    } else {
+        PatchList::decode(b)
+    }
+}
+
+fn decode_patch_or_empty(art: &K8L1Artifact, b: &[u8]) -> Result<PatchList> {
+    if b.is_empty() {
+        Ok(PatchList::new())
+    } else {
+        decode_patch_blob(art.ver, b, art.total_len)
+    }
 }
 
 // -------------------- predictor stream (Engine emissions) --------------------
@@ -624,10 +984,13 @@ fn burn_emissions(eng: &mut Engine, k: u64, max_ticks: u64) -> Result<()> {
     }
     let toks = eng.run_emissions(k, max_ticks);
     if toks.len() != k as usize {
-        return Err(K8Error::Validation(format!(
-            "engine: insufficient emissions (need {k}, got {}) within max_ticks={max_ticks}",
-            toks.len()
-        )));
+        return Err(K8Error::InsufficientEmissions {
+            context: "k8l1 burn",
+            needed: k,
+            produced: toks.len() as u64,
+            max_ticks,
+            ticks: eng.stats.ticks,
+        });
     }
     Ok(())
 }
@@ -641,10 +1004,13 @@ fn gen_pred_stream_with_omega(eng: &mut Engine, symbols: u64, max_ticks: u64, om
     for ix in 0..symbols {
         let toks = eng.run_emissions(1, max_ticks);
         if toks.len() != 1 {
-            return Err(K8Error::Validation(format!(
-                "engine: insufficient emissions (need 1, got {}) within max_ticks={max_ticks}",
-                toks.len()
-            )));
+            return Err(K8Error::InsufficientEmissions {
+                context: "k8l1 predictor",
+                needed: 1,
+                produced: toks.len() as u64,
+                max_ticks,
+                ticks: eng.stats.ticks,
+            });
         }
         out.push(toks[0].pack_byte());
 
@@ -681,10 +1047,13 @@ fn gen_pred_stream_with_prog(eng: &mut Engine, symbols: u64, max_ticks: u64, pro
 
         let toks = eng.run_emissions(1, max_ticks);
         if toks.len() != 1 {
-            return Err(K8Error::Validation(format!(
-                "engine: insufficient emissions (need 1, got {}) within max_ticks={max_ticks}",
-                toks.len()
-            )));
+            return Err(K8Error::InsufficientEmissions {
+                context: "k8l1 predictor",
+                needed: 1,
+                produced: toks.len() as u64,
+                max_ticks,
+                ticks: eng.stats.ticks,
+            });
         }
         out.push(toks[0].pack_byte());
 
@@ -705,7 +1074,9 @@ struct K8L1Artifact {
     other_len: usize,
     max_ticks: u64,
     recipe_bytes: Vec<u8>,
-    omega_bytes: Vec<u8>, // v2/v3 only; empty means default Ω
+    omega_bytes: Vec<u8>, // v2..v6 only; empty means default Ω
+    punct_alph: Vec<u8>,  // v4..v6 only; empty means PUNCT_ALPH
+    text_flags: u8,       // v5/v6 only (TEXT_FLAG_*)
     class_patch_bytes: Vec<u8>,
     other_patch_bytes: Vec<u8>,
 }
@@ -713,36 +1084,51 @@ struct K8L1Artifact {
 impl K8L1Artifact {
     fn to_bytes(&self) -> Vec<u8> {
         let mut out = Vec::new();
-        out.extend_from_slice(&MAGIC_K8L1);
-        out.push(self.ver);
+        self.write_to(&mut out).expect("write to Vec cannot fail");
+        out
+    }
+
+    fn write_to<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        w.write_all(&MAGIC_K8L1)?;
+        w.write_all(&[self.ver])?;
+
+        varint::write_u64(w, self.total_len as u64)?;
+        varint::write_u64(w, self.other_len as u64)?;
+        varint::write_u64(w, self.max_ticks)?;
+
+        varint::write_u64(w, self.recipe_bytes.len() as u64)?;
+        w.write_all(&self.recipe_bytes)?;
 
-        varint::put_u64(self.total_len as u64, &mut out);
-        varint::put_u64(self.other_len as u64, &mut out);
-        varint::put_u64(self.max_ticks, &mut out);
+        if (K8L1_VERSION_V2..=K8L1_VERSION_V8).contains(&self.ver) {
+            varint::write_u64(w, self.omega_bytes.len() as u64)?;
+            w.write_all(&self.omega_bytes)?;
+        }
 
-        varint::put_u64(self.recipe_bytes.len() as u64, &mut out);
-        out.extend_from_slice(&self.recipe_bytes);
+        if self.ver >= K8L1_VERSION_V4 {
+            varint::write_u64(w, self.punct_alph.len() as u64)?;
+            w.write_all(&self.punct_alph)?;
+        }
 
-        if self.ver == K8L1_VERSION_V2 || self.ver == K8L1_VERSION_V3 {
-            varint::put_u64(self.omega_bytes.len() as u64, &mut out);
-            out.extend_from_slice(&self.omega_bytes);
+        if self.ver >= K8L1_VERSION_V5 {
+            w.write_all(&[self.text_flags])?;
         }
 
-        varint::put_u64(self.class_patch_bytes.len() as u64, &mut out);
-        out.extend_from_slice(&self.class_patch_bytes);
+        varint::write_u64(w, self.class_patch_bytes.len() as u64)?;
+        w.write_all(&self.class_patch_bytes)?;
 
-        varint::put_u64(self.other_patch_bytes.len() as u64, &mut out);
-        out.extend_from_slice(&self.other_patch_bytes);
+        varint::write_u64(w, self.other_patch_bytes.len() as u64)?;
+        w.write_all(&self.other_patch_bytes)?;
 
-        out
+        Ok(())
     }
 
     fn from_bytes(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < 5 {
-            return Err(K8Error::Validation("K8L1 too short".to_string()));
+            return Err(K8Error::TruncatedInput { context: "K8L1", expected: 5, got: bytes.len() });
         }
         if &bytes[..4] != &MAGIC_K8L1 {
-            return Err(K8Error::Validation("K8L1 bad magic".to_string()));
+            let got = [bytes[0], bytes[1], bytes[2], bytes[3]];
+            return Err(K8Error::BadMagic { context: "K8L1", expected: MAGIC_K8L1, got });
         }
         let ver = bytes[4];
 
@@ -754,15 +1140,15 @@ impl K8L1Artifact {
 
         let rlen = varint::get_u64(bytes, &mut i)? as usize;
         if bytes.len() < i + rlen {
-            return Err(K8Error::Validation("K8L1 recipe OOB".to_string()));
+            return Err(K8Error::OutOfBounds { name: "K8L1 recipe", offset: i, len: rlen });
         }
         let recipe_bytes = bytes[i..i + rlen].to_vec();
         i += rlen;
 
-        let omega_bytes = if ver == K8L1_VERSION_V2 || ver == K8L1_VERSION_V3 {
+        let omega_bytes = if (K8L1_VERSION_V2..=K8L1_VERSION_V8).contains(&ver) {
             let olen = varint::get_u64(bytes, &mut i)? as usize;
             if bytes.len() < i + olen {
-                return Err(K8Error::Validation("K8L1 omega OOB".to_string()));
+                return Err(K8Error::OutOfBounds { name: "K8L1 omega", offset: i, len: olen });
             }
             let ob = bytes[i..i + olen].to_vec();
             i += olen;
@@ -770,19 +1156,48 @@ impl K8L1Artifact {
         } else if ver == K8L1_VERSION_V1 {
             Vec::new()
         } else {
-            return Err(K8Error::Validation(format!("K8L1 bad version {ver}")));
+            return Err(K8Error::BadVersion { expected: K8L1_VERSION_V8.into(), got: ver.into() });
+        };
+
+        let punct_alph = if ver >= K8L1_VERSION_V4 {
+            let plen = varint::get_u64(bytes, &mut i)? as usize;
+            if bytes.len() < i + plen {
+                return Err(K8Error::OutOfBounds { name: "K8L1 punct alphabet", offset: i, len: plen });
+            }
+            let pb = bytes[i..i + plen].to_vec();
+            i += plen;
+            // v5/v6 spell the default alphabet as an empty block.
+            if !(ver >= K8L1_VERSION_V5 && pb.is_empty()) {
+                validate_punct_alphabet(&pb)?;
+            }
+            pb
+        } else {
+            Vec::new()
+        };
+
+        let text_flags = if ver >= K8L1_VERSION_V5 {
+            let f = *bytes
+                .get(i)
+                .ok_or(K8Error::OutOfBounds { name: "K8L1 text flags", offset: i, len: 1 })?;
+            i += 1;
+            if f & !TEXT_FLAG_UTF8 != 0 {
+                return Err(K8Error::Validation(format!("K8L1 unknown text flags 0x{f:02x}")));
+            }
+            f
+        } else {
+            0
         };
 
         let clen = varint::get_u64(bytes, &mut i)? as usize;
         if bytes.len() < i + clen {
-            return Err(K8Error::Validation("K8L1 class_patch OOB".to_string()));
+            return Err(K8Error::OutOfBounds { name: "K8L1 class_patch", offset: i, len: clen });
         }
         let class_patch_bytes = bytes[i..i + clen].to_vec();
         i += clen;
 
         let olen = varint::get_u64(bytes, &mut i)? as usize;
         if bytes.len() < i + olen {
-            return Err(K8Error::Validation("K8L1 other_patch OOB".to_string()));
+            return Err(K8Error::OutOfBounds { name: "K8L1 other_patch", offset: i, len: olen });
         }
         let other_patch_bytes = bytes[i..i + olen].to_vec();
         i += olen;
@@ -798,10 +1213,25 @@ impl K8L1Artifact {
             max_ticks,
             recipe_bytes,
             omega_bytes,
+            punct_alph,
+            text_flags,
             class_patch_bytes,
             other_patch_bytes,
         })
     }
+
+    fn utf8_letters(&self) -> bool {
+        self.text_flags & TEXT_FLAG_UTF8 != 0
+    }
+
+    /// Alphabet the punct lane indexes into.
+    fn punct_alphabet(&self) -> &[u8] {
+        if self.punct_alph.is_empty() {
+            PUNCT_ALPH
+        } else {
+            &self.punct_alph
+        }
+    }
 }
 
 // -------------------- public encode/decode --------------------
@@ -814,6 +1244,10 @@ pub struct LaneEncodeStats {
     pub n_digits: usize,
     pub n_punct: usize,
     pub n_raw: usize,
+    /// Digit runs coded as numeric symbols (0 when the per-digit lane was smaller).
+    pub n_numeric_runs: usize,
+    /// Digit runs coded as DECIMAL_RUN values (0 unless that encoding was selected).
+    pub n_decimal_runs: usize,
     pub emissions_needed: usize,
     pub class_mismatches: usize,
     pub other_mismatches: usize,
@@ -821,13 +1255,296 @@ pub struct LaneEncodeStats {
     pub case_mismatches: usize,
     pub letter_mismatches: usize,
     pub digit_mismatches: usize,
+    pub numeric_mismatches: usize,
+    pub decimal_run_mismatches: usize,
     pub punct_mismatches: usize,
     pub raw_mismatches: usize,
     pub artifact_bytes: usize,
+    /// n_punct / (n_punct + n_raw): how much non-letter/digit text the punct
+    /// alphabet caught instead of leaving it to the raw lane (0 when both are empty).
+    pub punct_coverage: f64,
+    /// Lanes (names from `LANES`, never `other`) whose patch blob was stored
+    /// zstd-compressed rather than raw. The digit-mode lanes are always raw.
+    pub compressed_patches: Vec<&'static str>,
 }
 
-pub fn encode_k8l1(input: &[u8], recipe_bytes: &[u8], max_ticks: u64) -> Result<(Vec<u8>, LaneEncodeStats)> {
-    encode_k8l1_with_omega(input, recipe_bytes, max_ticks, OmegaSchedule::default())
+impl LaneEncodeStats {
+    /// Lane names accepted by `per_lane_mismatch_rate`.
+    pub const LANES: [&'static str; 10] = [
+        "class",
+        "other",
+        "kind",
+        "case",
+        "letter",
+        "digit",
+        "numeric",
+        "decimal_run",
+        "punct",
+        "raw",
+    ];
+
+    /// artifact_bytes / total_len (0 for empty input).
+    pub fn compression_ratio(&self) -> f64 {
+        ratio(self.artifact_bytes, self.total_len)
+    }
+
+    /// Artifact bits per input byte (0 for empty input).
+    pub fn bits_per_symbol(&self) -> f64 {
+        self.compression_ratio() * 8.0
+    }
+
+    /// How far the artifact exceeds the input; 0 when the codec actually compressed.
+    pub fn overhead_bytes(&self) -> usize {
+        self.artifact_bytes.saturating_sub(self.total_len)
+    }
+
+    /// Ratio, bits per symbol, overhead and the class/other mismatch rates as one
+    /// `key=value` line; the quality line CLI commands print after a K8L1 encode.
+    pub fn quality_summary(&self) -> String {
+        format!(
+            "artifact_bytes={} ratio={:.4} bits_per_symbol={:.4} overhead_bytes={} class_mismatch_rate={:.4} other_mismatch_rate={:.4}",
+            self.artifact_bytes,
+            self.compression_ratio(),
+            self.bits_per_symbol(),
+            self.overhead_bytes(),
+            self.per_lane_mismatch_rate("class").unwrap_or(0.0),
+            self.per_lane_mismatch_rate("other").unwrap_or(0.0)
+        )
+    }
+
+    /// Mismatches / predicted symbols for one lane (see `LANES`; `other` covers every
+    /// lane after `class`). `None` for an unknown name; an empty lane has rate 0.
+    pub fn per_lane_mismatch_rate(&self, lane: &str) -> Option<f64> {
+        let (mismatches, len) = match lane {
+            "class" => (self.class_mismatches, self.total_len),
+            "other" => (
+                self.other_mismatches,
+                self.emissions_needed.saturating_sub(self.total_len),
+            ),
+            "kind" => (self.kind_mismatches, self.other_len),
+            "case" => (self.case_mismatches, self.n_letters),
+            "letter" => (self.letter_mismatches, self.n_letters),
+            "digit" => (self.digit_mismatches, self.n_digits),
+            "numeric" => (self.numeric_mismatches, self.n_numeric_runs),
+            "decimal_run" => (self.decimal_run_mismatches, self.n_decimal_runs),
+            "punct" => (self.punct_mismatches, self.n_punct),
+            "raw" => (self.raw_mismatches, self.n_raw),
+            _ => return None,
+        };
+        Some(ratio(mismatches, len))
+    }
+
+    /// Whether a lane's patch was stored compressed (see `LANES`; `other` is true when
+    /// any lane after `class` was). `None` for an unknown name.
+    pub fn patch_compressed(&self, lane: &str) -> Option<bool> {
+        match lane {
+            "other" => Some(self.compressed_patches.iter().any(|&l| l != "class")),
+            _ if Self::LANES.contains(&lane) => Some(self.compressed_patches.contains(&lane)),
+            _ => None,
+        }
+    }
+}
+
+fn ratio(num: usize, den: usize) -> f64 {
+    if den == 0 {
+        0.0
+    } else {
+        num as f64 / den as f64
+    }
+}
+
+// How the digit lane is coded; the encoder tries each and keeps the smallest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DigitMode {
+    Digit,
+    Numeric,
+    DecimalRun,
+}
+
+// Patch blobs for the lanes after `letter` (digit/numeric/decimal-run, punct, raw).
+struct TailPatches {
+    mode: DigitMode,
+    digit_parts: Vec<(u64, Vec<u8>)>,
+    punct_bytes: Vec<u8>,
+    raw_bytes: Vec<u8>,
+    /// Emissions spent on the digit lanes.
+    digit_emissions: u64,
+    n_decimal_runs: usize,
+    digit_mismatches: usize,
+    numeric_mismatches: usize,
+    decimal_run_mismatches: usize,
+    punct_mismatches: usize,
+    raw_mismatches: usize,
+}
+
+// zstd level for patch blobs that compress below their raw size (see `store_patch`).
+const PATCH_ZSTD_LEVEL: i32 = 19;
+
+/// A PZST envelope makes the whole artifact v7, which pre-v7 decoders reject, so a blob
+/// is only wrapped when that saves at least this many bytes.
+const PATCH_ZSTD_MIN_SAVING: usize = 16;
+
+/// Stores a patch blob PZST-compressed when that saves `PATCH_ZSTD_MIN_SAVING` bytes, else raw.
+fn store_patch(raw: Vec<u8>) -> Result<Vec<u8>> {
+    let z = patch::smaller_encoding(raw.clone(), PATCH_ZSTD_LEVEL)?;
+    // A raw blob that starts with the magic must stay wrapped (see `smaller_encoding`).
+    Ok(if PatchList::is_compressed(&raw) || z.len() + PATCH_ZSTD_MIN_SAVING <= raw.len() {
+        z
+    } else {
+        raw
+    })
+}
+
+/// Patch blob and mismatch count for one u8 lane. Once mismatches pass `lanes.total_len / 2`
+/// the patch buys nothing, so the lane is stored verbatim (bit-packed at its alphabet width)
+/// unless the patch still comes out smaller.
+fn encode_lane_patch(lanes: &TextLanesV2, pred: &[u8], actual: &[u8]) -> Result<(Vec<u8>, usize)> {
+    match PatchList::from_symbol_streams(pred, actual, lanes.total_len / 2) {
+        Ok(p) => Ok((p.encode(), p.entries.len())),
+        Err(K8Error::TooManyMismatches { count, .. }) => {
+            let verbatim = PatchList::encode_verbatim(actual);
+            let patch = PatchList::from_pred_actual(pred, actual)?.encode();
+            Ok((if verbatim.len() < patch.len() { verbatim } else { patch }, count))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn encode_tail(
+    eng: &mut Engine,
+    lanes: &TextLanesV2,
+    mode: DigitMode,
+    max_ticks: u64,
+    omega: &OmegaProgram,
+    punct_alph: &[u8],
+) -> Result<TailPatches> {
+    let mut digit_parts = Vec::new();
+    let mut digit_mismatches = 0usize;
+    let mut numeric_mismatches = 0usize;
+    let mut decimal_run_mismatches = 0usize;
+    let mut n_decimal_runs = 0usize;
+    let digit_emissions;
+
+    match mode {
+        DigitMode::Numeric => {
+            // run lengths are implied by class/kind, so the decoder re-derives them
+            let runs = TextLanesV2::derive_numeric_runs(&lanes.class_lane, &lanes.kind_lane)?;
+            if runs != lanes.numeric_run_lane {
+                return Err(K8Error::Validation("k8l1: numeric runs disagree with class/kind".to_string()));
+            }
+
+            let n_runs_u = lanes.numeric_run_lane.len() as u64;
+            let pred_num_raw = gen_pred_stream_with_prog(eng, n_runs_u, max_ticks, &omega.digit)?;
+            let pred_num: Vec<u64> = pred_num_raw
+                .iter()
+                .zip(lanes.numeric_run_lane.iter())
+                .map(|(&b, &run)| bucket_numeric(b, run))
+                .collect();
+            let num_patch = PatchList::from_pred_actual_u64(&pred_num, &lanes.numeric_lane)?;
+
+            numeric_mismatches = num_patch.entries.len();
+            digit_emissions = n_runs_u;
+            // values exceed u8, so the dense format is not an option here
+            digit_parts.push((PATCH_NUMERIC, num_patch.encode_sparse_legacy()));
+        }
+        DigitMode::DecimalRun => {
+            let runs = TextLanesV2::derive_numeric_runs(&lanes.class_lane, &lanes.kind_lane)?;
+            let (head, vals) = TextLanesV2::split_decimal_runs(&runs, &lanes.digit_lane)?;
+
+            // one stream: head digits first, then one emission per run value
+            let n_syms = (head.len() + vals.len()) as u64;
+            let pred_raw = gen_pred_stream_with_prog(eng, n_syms, max_ticks, &omega.digit)?;
+            let (pred_head_raw, pred_val_raw) = pred_raw.split_at(head.len());
+            let pred_head: Vec<u8> = pred_head_raw.iter().map(|&b| bucket_u8(b, 10)).collect();
+            let pred_val: Vec<u64> = pred_val_raw.iter().map(|&b| bucket_numeric(b, DECRUN_TAIL)).collect();
+            let head_patch = PatchList::from_pred_actual(&pred_head, &head)?;
+            let val_patch = PatchList::from_pred_actual_u64(&pred_val, &vals)?;
+
+            digit_mismatches = head_patch.entries.len();
+            decimal_run_mismatches = val_patch.entries.len();
+            n_decimal_runs = vals.len();
+            digit_emissions = n_syms;
+            digit_parts.push((PATCH_DIGIT, head_patch.encode()));
+            digit_parts.push((PATCH_DECRUN_VAL, val_patch.encode_sparse_legacy()));
+        }
+        DigitMode::Digit => {
+            let n_digits_u = lanes.digit_lane.len() as u64;
+            let pred_digit_raw = gen_pred_stream_with_prog(eng, n_digits_u, max_ticks, &omega.digit)?;
+            let pred_digit: Vec<u8> = pred_digit_raw.iter().map(|&b| bucket_u8(b, 10)).collect();
+            // no verbatim fallback here: numeric / decimal-run are this lane's alternatives
+            let digit_patch = PatchList::from_pred_actual(&pred_digit, &lanes.digit_lane)?;
+
+            digit_mismatches = digit_patch.entries.len();
+            digit_emissions = n_digits_u;
+            digit_parts.push((PATCH_DIGIT, digit_patch.encode()));
+        }
+    }
+
+    // punct
+    let n_punct_u = lanes.punct_lane.len() as u64;
+    let pred_punct_raw = gen_pred_stream_with_prog(eng, n_punct_u, max_ticks, &omega.punct)?;
+    let pred_punct: Vec<u8> = pred_punct_raw
+        .iter()
+        .map(|&b| bucket_u8(b, punct_alph.len() as u8))
+        .collect();
+    let (punct_bytes, punct_mismatches) = encode_lane_patch(lanes, &pred_punct, &lanes.punct_lane)?;
+
+    // raw
+    let n_raw_u = lanes.raw_lane.len() as u64;
+    let pred_raw = gen_pred_stream_with_prog(eng, n_raw_u, max_ticks, &omega.raw)?;
+    let (raw_bytes, raw_mismatches) = encode_lane_patch(lanes, &pred_raw, &lanes.raw_lane)?;
+
+    Ok(TailPatches {
+        mode,
+        digit_parts,
+        punct_bytes,
+        raw_bytes,
+        digit_emissions,
+        n_decimal_runs,
+        digit_mismatches,
+        numeric_mismatches,
+        decimal_run_mismatches,
+        punct_mismatches,
+        raw_mismatches,
+    })
+}
+
+/// `punct_alphabet: None` (or the default itself) keeps PUNCT_ALPH and the v2/v3 layout;
+/// anything else is validated and embedded in a v4 header.
+pub fn encode_k8l1(
+    input: &[u8],
+    recipe_bytes: &[u8],
+    max_ticks: u64,
+    punct_alphabet: Option<&[u8]>,
+) -> Result<(Vec<u8>, LaneEncodeStats)> {
+    encode_k8l1_with_punct(input, recipe_bytes, max_ticks, OmegaProgram::default(), punct_alphabet)
+}
+
+/// `encode_k8l1_with_omega_prog` with an optional punctuation alphabet (see `encode_k8l1`).
+pub fn encode_k8l1_with_punct(
+    input: &[u8],
+    recipe_bytes: &[u8],
+    max_ticks: u64,
+    omega: OmegaProgram,
+    punct_alphabet: Option<&[u8]>,
+) -> Result<(Vec<u8>, LaneEncodeStats)> {
+    encode_k8l1_with_config(input, recipe_bytes, max_ticks, omega, punct_alphabet, TextLanesV2Config::default())
+}
+
+/// `encode_k8l1_with_punct` plus text lane options. `cfg.utf8_aware` only takes effect
+/// when the (newline-normalized) input is valid UTF-8; the artifact is then v5.
+pub fn encode_k8l1_with_config(
+    input: &[u8],
+    recipe_bytes: &[u8],
+    max_ticks: u64,
+    omega: OmegaProgram,
+    punct_alphabet: Option<&[u8]>,
+    cfg: TextLanesV2Config,
+) -> Result<(Vec<u8>, LaneEncodeStats)> {
+    let (art, mut stats) = build_k8l1(input, recipe_bytes, max_ticks, omega, punct_alphabet, cfg)?;
+    let artifact_bytes = art.to_bytes();
+    stats.artifact_bytes = artifact_bytes.len();
+    Ok((artifact_bytes, stats))
 }
 
 pub fn encode_k8l1_with_omega(
@@ -854,15 +1571,83 @@ pub fn encode_k8l1_with_omega_prog(
     max_ticks: u64,
     omega: OmegaProgram,
 ) -> Result<(Vec<u8>, LaneEncodeStats)> {
+    encode_k8l1_with_punct(input, recipe_bytes, max_ticks, omega, None)
+}
+
+/// Same artifact as `encode_k8l1_with_omega_prog`, written straight to `w`
+/// (e.g. a `BufWriter<File>`) without assembling it in memory first.
+pub fn write_k8l1_with_omega_prog<W: Write>(
+    w: &mut W,
+    input: &[u8],
+    recipe_bytes: &[u8],
+    max_ticks: u64,
+    omega: OmegaProgram,
+) -> Result<LaneEncodeStats> {
+    write_k8l1_with_config(w, input, recipe_bytes, max_ticks, omega, None, TextLanesV2Config::default())
+}
+
+/// Writer form of `encode_k8l1_with_config`. Nothing reaches `w` unless the lanes
+/// build, so a failed attempt (e.g. insufficient emissions) leaves `w` untouched.
+pub fn write_k8l1_with_config<W: Write>(
+    w: &mut W,
+    input: &[u8],
+    recipe_bytes: &[u8],
+    max_ticks: u64,
+    omega: OmegaProgram,
+    punct_alphabet: Option<&[u8]>,
+    cfg: TextLanesV2Config,
+) -> Result<LaneEncodeStats> {
+    let (art, mut stats) = build_k8l1(input, recipe_bytes, max_ticks, omega, punct_alphabet, cfg)?;
+    let mut cw = CountingWriter { inner: w, n: 0 };
+    art.write_to(&mut cw)?;
+    stats.artifact_bytes = cw.n;
+    Ok(stats)
+}
+
+struct CountingWriter<'a, W: Write> {
+    inner: &'a mut W,
+    n: usize,
+}
+
+impl<W: Write> Write for CountingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let k = self.inner.write(buf)?;
+        self.n += k;
+        Ok(k)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Runs the lane model and assembles the container; `stats.artifact_bytes` is
+/// left for the caller to fill once the artifact is serialized.
+fn build_k8l1(
+    input: &[u8],
+    recipe_bytes: &[u8],
+    max_ticks: u64,
+    omega: OmegaProgram,
+    punct_alphabet: Option<&[u8]>,
+    cfg: TextLanesV2Config,
+) -> Result<(K8L1Artifact, LaneEncodeStats)> {
     omega.validate()?;
 
+    // The default alphabet is implied by v2/v3, so only a different one costs header bytes.
+    let custom_punct = punct_alphabet.filter(|a| *a != PUNCT_ALPH);
+    if let Some(a) = custom_punct {
+        validate_punct_alphabet(a)?;
+    }
+    let punct_alph = custom_punct.unwrap_or(PUNCT_ALPH);
+
     let norm = text_norm::normalize_newlines(input);
-    let lanes = TextLanesV2::split(&norm)?;
+    let utf8_letters = cfg.utf8_aware && std::str::from_utf8(&norm).is_ok();
+    let lanes = TextLanesV2::split(&norm, punct_alph, utf8_letters)?;
+    let n_letter_syms = if utf8_letters { LETTERS_UTF8 } else { LETTERS_ASCII };
 
     let total_len_u = lanes.total_len as u64;
     let other_len_u = lanes.kind_lane.len() as u64;
     let n_letters_u = lanes.letter_lane.len() as u64;
-    let n_digits_u = lanes.digit_lane.len() as u64;
     let n_punct_u = lanes.punct_lane.len() as u64;
     let n_raw_u = lanes.raw_lane.len() as u64;
 
@@ -872,53 +1657,94 @@ pub fn encode_k8l1_with_omega_prog(
     // class
     let pred_class_raw = gen_pred_stream_with_prog(&mut eng, total_len_u, max_ticks, &omega.class)?;
     let pred_class: Vec<u8> = pred_class_raw.iter().map(|&b| bucket_u8(b, 3)).collect();
-    let class_patch = PatchList::from_pred_actual(&pred_class, &lanes.class_lane)?;
-    let class_patch_bytes = class_patch.encode();
+    let (class_patch_bytes, class_mismatches) = encode_lane_patch(&lanes, &pred_class, &lanes.class_lane)?;
 
     // kind
     let pred_kind_raw = gen_pred_stream_with_prog(&mut eng, other_len_u, max_ticks, &omega.kind)?;
     let pred_kind: Vec<u8> = pred_kind_raw.iter().map(|&b| bucket_u8(b, 4)).collect();
-    let kind_patch = PatchList::from_pred_actual(&pred_kind, &lanes.kind_lane)?;
-    let kind_bytes = kind_patch.encode();
+    let (kind_bytes, kind_mismatches) = encode_lane_patch(&lanes, &pred_kind, &lanes.kind_lane)?;
 
     // case
     let pred_case_raw = gen_pred_stream_with_prog(&mut eng, n_letters_u, max_ticks, &omega.caseb)?;
     let pred_case: Vec<u8> = pred_case_raw.iter().map(|&b| bucket_u8(b, 2)).collect();
-    let case_patch = PatchList::from_pred_actual(&pred_case, &lanes.case_lane)?;
-    let case_bytes = case_patch.encode();
+    let (case_bytes, case_mismatches) = encode_lane_patch(&lanes, &pred_case, &lanes.case_lane)?;
 
     // letter
     let pred_letter_raw = gen_pred_stream_with_prog(&mut eng, n_letters_u, max_ticks, &omega.letter)?;
-    let pred_letter: Vec<u8> = pred_letter_raw.iter().map(|&b| bucket_u8(b, 26)).collect();
-    let letter_patch = PatchList::from_pred_actual(&pred_letter, &lanes.letter_lane)?;
-    let letter_bytes = letter_patch.encode();
-
-    // digit
-    let pred_digit_raw = gen_pred_stream_with_prog(&mut eng, n_digits_u, max_ticks, &omega.digit)?;
-    let pred_digit: Vec<u8> = pred_digit_raw.iter().map(|&b| bucket_u8(b, 10)).collect();
-    let digit_patch = PatchList::from_pred_actual(&pred_digit, &lanes.digit_lane)?;
-    let digit_bytes = digit_patch.encode();
+    let pred_letter: Vec<u8> = pred_letter_raw.iter().map(|&b| bucket_u8(b, n_letter_syms)).collect();
+    let (letter_bytes, letter_mismatches) = encode_lane_patch(&lanes, &pred_letter, &lanes.letter_lane)?;
+
+    // digit / punct / raw: the per-digit and numeric encodings share the emission
+    // cursor from here on, so run both from the same engine state and keep the smaller.
+    let digit_tail = encode_tail(&mut eng.clone(), &lanes, DigitMode::Digit, max_ticks, &omega, punct_alph)?;
+    let mut alt_tails = Vec::new();
+    if !lanes.numeric_run_lane.is_empty() {
+        alt_tails.push(encode_tail(&mut eng.clone(), &lanes, DigitMode::Numeric, max_ticks, &omega, punct_alph)?);
+    }
+    if cfg.decimal_runs && lanes.numeric_run_lane.iter().any(|&r| r >= DECRUN_MIN_RUN) {
+        alt_tails.push(encode_tail(&mut eng, &lanes, DigitMode::DecimalRun, max_ticks, &omega, punct_alph)?);
+    }
 
-    // punct
-    let pred_punct_raw = gen_pred_stream_with_prog(&mut eng, n_punct_u, max_ticks, &omega.punct)?;
-    let pred_punct: Vec<u8> = pred_punct_raw
-        .iter()
-        .map(|&b| bucket_u8(b, PUNCT_ALPH.len() as u8))
-        .collect();
-    let punct_patch = PatchList::from_pred_actual(&pred_punct, &lanes.punct_lane)?;
-    let punct_bytes = punct_patch.encode();
+    let mut scratch = Vec::new();
+    let mut mux_len = |tail: &TailPatches| {
+        let mut parts: Vec<(u64, &[u8])> = vec![(PATCH_KIND, &kind_bytes), (PATCH_CASE, &case_bytes), (PATCH_LETTER, &letter_bytes)];
+        parts.extend(tail.digit_parts.iter().map(|(id, b)| (*id, b.as_slice())));
+        parts.push((PATCH_PUNCT, &tail.punct_bytes));
+        parts.push((PATCH_RAW, &tail.raw_bytes));
+        scratch.clear();
+        mux_other_patches(&mut scratch, &parts);
+        scratch.len()
+    };
 
-    // raw
-    let pred_raw = gen_pred_stream_with_prog(&mut eng, n_raw_u, max_ticks, &omega.raw)?;
-    let raw_patch = PatchList::from_pred_actual(&pred_raw, &lanes.raw_lane)?;
-    let raw_bytes = raw_patch.encode();
+    let mut best_len = mux_len(&digit_tail);
+    let mut tail = digit_tail;
+    for alt in alt_tails {
+        let alt_len = mux_len(&alt);
+        if alt_len < best_len {
+            best_len = alt_len;
+            tail = alt;
+        }
+    }
 
-    let other_patch_bytes =
-        mux_other_patches(&kind_bytes, &case_bytes, &letter_bytes, &digit_bytes, &punct_bytes, &raw_bytes);
+    // Every other blob is stored zstd-compressed where that saves PATCH_ZSTD_MIN_SAVING
+    // bytes (which makes the artifact v7, the first version decoders accept PZST in). The digit
+    // parts stay raw: numeric / decimal-run are already that lane's alternatives, and the
+    // mode above was chosen on raw sizes.
+    let mut compressed_patches = Vec::new();
+    let mut store = |lane: &'static str, raw: &[u8]| {
+        let b = store_patch(raw.to_vec())?;
+        if PatchList::is_compressed(&b) {
+            compressed_patches.push(lane);
+        }
+        Ok::<_, K8Error>(b)
+    };
+    let class_patch_bytes = store("class", &class_patch_bytes)?;
+    let mut stored = vec![
+        (PATCH_KIND, store("kind", &kind_bytes)?),
+        (PATCH_CASE, store("case", &case_bytes)?),
+        (PATCH_LETTER, store("letter", &letter_bytes)?),
+    ];
+    stored.extend(tail.digit_parts.iter().cloned());
+    stored.push((PATCH_PUNCT, store("punct", &tail.punct_bytes)?));
+    stored.push((PATCH_RAW, store("raw", &tail.raw_bytes)?));
+    let parts: Vec<(u64, &[u8])> = stored.iter().map(|(id, b)| (*id, b.as_slice())).collect();
+    let mut other_patch_bytes = scratch;
+    other_patch_bytes.clear();
+    mux_other_patches(&mut other_patch_bytes, &parts);
 
     let recipe_bytes_owned = recipe_to_bytes(&recipe)?;
 
-    let (ver, omega_bytes_owned) = if let Some(sched) = omega.to_schedule_if_singleton() {
+    let (ver, omega_bytes_owned) = if tail.mode == DigitMode::Numeric {
+        (K8L1_VERSION_V8, omega.encode_bytes_v3())
+    } else if !compressed_patches.is_empty() {
+        (K8L1_VERSION_V7, omega.encode_bytes_v3())
+    } else if tail.mode == DigitMode::DecimalRun {
+        (K8L1_VERSION_V6, omega.encode_bytes_v3())
+    } else if utf8_letters {
+        (K8L1_VERSION_V5, omega.encode_bytes_v3())
+    } else if custom_punct.is_some() {
+        (K8L1_VERSION_V4, omega.encode_bytes_v3())
+    } else if let Some(sched) = omega.to_schedule_if_singleton() {
         (K8L1_VERSION_V2, sched.encode_bytes())
     } else {
         (K8L1_VERSION_V3, omega.encode_bytes_v3())
@@ -931,26 +1757,31 @@ pub fn encode_k8l1_with_omega_prog(
         max_ticks,
         recipe_bytes: recipe_bytes_owned,
         omega_bytes: omega_bytes_owned,
+        punct_alph: custom_punct.map(<[u8]>::to_vec).unwrap_or_default(),
+        text_flags: if utf8_letters { TEXT_FLAG_UTF8 } else { 0 },
         class_patch_bytes,
         other_patch_bytes,
     };
 
-    let artifact_bytes = art.to_bytes();
-    let artifact_len = artifact_bytes.len();
+    let digit_mismatches = tail.digit_mismatches;
+    let numeric_mismatches = tail.numeric_mismatches;
+    let decimal_run_mismatches = tail.decimal_run_mismatches;
+    let punct_mismatches = tail.punct_mismatches;
+    let raw_mismatches = tail.raw_mismatches;
 
-    let class_mismatches = class_patch.entries.len();
-    let kind_mismatches = kind_patch.entries.len();
-    let case_mismatches = case_patch.entries.len();
-    let letter_mismatches = letter_patch.entries.len();
-    let digit_mismatches = digit_patch.entries.len();
-    let punct_mismatches = punct_patch.entries.len();
-    let raw_mismatches = raw_patch.entries.len();
+    let other_mismatches = kind_mismatches
+        + case_mismatches
+        + letter_mismatches
+        + digit_mismatches
+        + numeric_mismatches
+        + decimal_run_mismatches
+        + punct_mismatches
+        + raw_mismatches;
 
-    let other_mismatches =
-        kind_mismatches + case_mismatches + letter_mismatches + digit_mismatches + punct_mismatches + raw_mismatches;
+    let n_numeric_runs = if tail.mode == DigitMode::Numeric { lanes.numeric_run_lane.len() } else { 0 };
 
     let emissions_needed =
-        (total_len_u + other_len_u + n_letters_u + n_letters_u + n_digits_u + n_punct_u + n_raw_u) as usize;
+        (total_len_u + other_len_u + n_letters_u + n_letters_u + tail.digit_emissions + n_punct_u + n_raw_u) as usize;
 
     let stats = LaneEncodeStats {
         total_len: lanes.total_len,
@@ -959,6 +1790,8 @@ pub fn encode_k8l1_with_omega_prog(
         n_digits: lanes.digit_lane.len(),
         n_punct: lanes.punct_lane.len(),
         n_raw: lanes.raw_lane.len(),
+        n_numeric_runs,
+        n_decimal_runs: tail.n_decimal_runs,
         emissions_needed,
         class_mismatches,
         other_mismatches,
@@ -966,12 +1799,28 @@ pub fn encode_k8l1_with_omega_prog(
         case_mismatches,
         letter_mismatches,
         digit_mismatches,
+        numeric_mismatches,
+        decimal_run_mismatches,
         punct_mismatches,
         raw_mismatches,
-        artifact_bytes: artifact_len,
+        artifact_bytes: 0,
+        punct_coverage: {
+            let (p, r) = (lanes.punct_lane.len(), lanes.raw_lane.len());
+            if p + r == 0 {
+                0.0
+            } else {
+                p as f64 / (p + r) as f64
+            }
+        },
+        compressed_patches,
     };
 
-    Ok((artifact_bytes, stats))
+    Ok((art, stats))
+}
+
+/// The header's `text_flags` (0 before v5), read without decoding the lanes.
+pub fn k8l1_text_flags(bytes: &[u8]) -> Result<u8> {
+    Ok(K8L1Artifact::from_bytes(bytes)?.text_flags)
 }
 
 pub fn decode_k8l1(bytes: &[u8]) -> Result<Vec<u8>> {
@@ -979,7 +1828,7 @@ pub fn decode_k8l1(bytes: &[u8]) -> Result<Vec<u8>> {
     let recipe = recipe_from_bytes(&art.recipe_bytes)?;
     let mut eng = Engine::new(recipe.clone())?;
 
-    let omega_prog = if art.ver == K8L1_VERSION_V3 {
+    let omega_prog = if (K8L1_VERSION_V3..=K8L1_VERSION_V8).contains(&art.ver) {
         OmegaProgram::decode_bytes_v3(&art.omega_bytes)?
     } else {
         let sched = OmegaSchedule::decode_bytes(&art.omega_bytes)?;
@@ -997,19 +1846,19 @@ pub fn decode_k8l1(bytes: &[u8]) -> Result<Vec<u8>> {
     let total_len_u = art.total_len as u64;
     let other_len_u = art.other_len as u64;
 
+    // other_patch mux -> patch blobs (first, so a version/id mismatch fails up front)
+    let blobs = demux_other_patches(art.ver, &art.other_patch_bytes)?;
+
     // class
     let pred_class_raw = gen_pred_stream_with_prog(&mut eng, total_len_u, art.max_ticks, &omega_prog.class)?;
     let mut pred_class: Vec<u8> = pred_class_raw.iter().map(|&b| bucket_u8(b, 3)).collect();
-    let class_patch = PatchList::decode(&art.class_patch_bytes)?;
+    let class_patch = decode_patch_blob(art.ver, &art.class_patch_bytes, art.total_len)?;
     class_patch.apply_to_pred(&mut pred_class)?;
 
-    // other_patch mux -> patch blobs
-    let (kind_b, case_b, letter_b, digit_b, punct_b, raw_b) = demux_other_patches(&art.other_patch_bytes)?;
-
     // kind (needed to derive downstream lane lengths)
     let pred_kind_raw = gen_pred_stream_with_prog(&mut eng, other_len_u, art.max_ticks, &omega_prog.kind)?;
     let mut pred_kind: Vec<u8> = pred_kind_raw.iter().map(|&b| bucket_u8(b, 4)).collect();
-    let kind_patch = if kind_b.is_empty() { PatchList::new() } else { PatchList::decode(&kind_b)? };
+    let kind_patch = decode_patch_or_empty(&art, &blobs.kind)?;
     kind_patch.apply_to_pred(&mut pred_kind)?;
 
     // Determine lane counts from patched kind lane
@@ -1031,34 +1880,76 @@ pub fn decode_k8l1(bytes: &[u8]) -> Result<Vec<u8>> {
     // case
     let pred_case_raw = gen_pred_stream_with_prog(&mut eng, n_letters as u64, art.max_ticks, &omega_prog.caseb)?;
     let mut pred_case: Vec<u8> = pred_case_raw.iter().map(|&b| bucket_u8(b, 2)).collect();
-    let case_patch = if case_b.is_empty() { PatchList::new() } else { PatchList::decode(&case_b)? };
-    case_patch.apply_to_pred(&mut pred_case)?;
+    decode_patch_or_empty(&art, &blobs.caseb)?.apply_to_pred(&mut pred_case)?;
 
     // letter
     let pred_letter_raw = gen_pred_stream_with_prog(&mut eng, n_letters as u64, art.max_ticks, &omega_prog.letter)?;
-    let mut pred_letter: Vec<u8> = pred_letter_raw.iter().map(|&b| bucket_u8(b, 26)).collect();
-    let letter_patch = if letter_b.is_empty() { PatchList::new() } else { PatchList::decode(&letter_b)? };
-    letter_patch.apply_to_pred(&mut pred_letter)?;
+    let n_letter_syms = if art.utf8_letters() { LETTERS_UTF8 } else { LETTERS_ASCII };
+    let mut pred_letter: Vec<u8> = pred_letter_raw.iter().map(|&b| bucket_u8(b, n_letter_syms)).collect();
+    decode_patch_or_empty(&art, &blobs.letter)?.apply_to_pred(&mut pred_letter)?;
 
-    // digit
-    let pred_digit_raw = gen_pred_stream_with_prog(&mut eng, n_digits as u64, art.max_ticks, &omega_prog.digit)?;
-    let mut pred_digit: Vec<u8> = pred_digit_raw.iter().map(|&b| bucket_u8(b, 10)).collect();
-    let digit_patch = if digit_b.is_empty() { PatchList::new() } else { PatchList::decode(&digit_b)? };
-    digit_patch.apply_to_pred(&mut pred_digit)?;
+    // digit (per-digit lane, or numeric / decimal runs when the artifact carries them)
+    let mut pred_digit: Vec<u8> = Vec::new();
+    let mut pred_runs: Vec<u8> = Vec::new();
+    let mut pred_num: Vec<u64> = Vec::new();
+
+    let decimal_runs = blobs.decrun_val.is_some();
+    if art.ver < K8L1_VERSION_V7 && decimal_runs != (art.ver == K8L1_VERSION_V6) {
+        return Err(K8Error::Validation(format!(
+            "K8L1 v{}: decimal run lanes {}",
+            art.ver,
+            if decimal_runs { "need v6" } else { "missing" }
+        )));
+    }
+    let numeric = blobs.numeric.is_some();
+    if art.ver == K8L1_VERSION_V8 && (!numeric || decimal_runs) {
+        return Err(K8Error::Validation("K8L1 v8: needs numeric lanes and no decimal runs".to_string()));
+    }
+
+    if decimal_runs {
+        let runs = TextLanesV2::derive_numeric_runs(&pred_class, &pred_kind)?;
+        let n_head: usize = runs
+            .iter()
+            .map(|&r| if r < DECRUN_MIN_RUN { r } else { r - DECRUN_TAIL } as usize)
+            .sum();
+        let n_runs = runs.iter().filter(|&&r| r >= DECRUN_MIN_RUN).count();
+        let n_syms = (n_head + n_runs) as u64;
+        let pred_raw = gen_pred_stream_with_prog(&mut eng, n_syms, art.max_ticks, &omega_prog.digit)?;
+        let (pred_head_raw, pred_val_raw) = pred_raw.split_at(n_head);
+        let mut head: Vec<u8> = pred_head_raw.iter().map(|&b| bucket_u8(b, 10)).collect();
+        decode_patch_or_empty(&art, &blobs.digit)?.apply_to_pred(&mut head)?;
+        let mut vals: Vec<u64> = pred_val_raw.iter().map(|&b| bucket_numeric(b, DECRUN_TAIL)).collect();
+        decode_patch_or_empty(&art, blobs.decrun_val.as_deref().unwrap_or_default())?.apply_to_pred_u64(&mut vals)?;
+
+        pred_digit = TextLanesV2::join_decimal_runs(&runs, &head, &vals)?;
+    } else if numeric {
+        pred_runs = TextLanesV2::derive_numeric_runs(&pred_class, &pred_kind)?;
+
+        let pred_num_raw = gen_pred_stream_with_prog(&mut eng, pred_runs.len() as u64, art.max_ticks, &omega_prog.digit)?;
+        pred_num = pred_num_raw
+            .iter()
+            .zip(pred_runs.iter())
+            .map(|(&b, &run)| bucket_numeric(b, run))
+            .collect();
+        decode_patch_or_empty(&art, blobs.numeric.as_deref().unwrap_or_default())?.apply_to_pred_u64(&mut pred_num)?;
+    } else {
+        let pred_digit_raw = gen_pred_stream_with_prog(&mut eng, n_digits as u64, art.max_ticks, &omega_prog.digit)?;
+        pred_digit = pred_digit_raw.iter().map(|&b| bucket_u8(b, 10)).collect();
+        decode_patch_or_empty(&art, &blobs.digit)?.apply_to_pred(&mut pred_digit)?;
+    }
 
     // punct
     let pred_punct_raw = gen_pred_stream_with_prog(&mut eng, n_punct as u64, art.max_ticks, &omega_prog.punct)?;
+    let punct_alph = art.punct_alphabet();
     let mut pred_punct: Vec<u8> = pred_punct_raw
         .iter()
-        .map(|&b| bucket_u8(b, PUNCT_ALPH.len() as u8))
+        .map(|&b| bucket_u8(b, punct_alph.len() as u8))
         .collect();
-    let punct_patch = if punct_b.is_empty() { PatchList::new() } else { PatchList::decode(&punct_b)? };
-    punct_patch.apply_to_pred(&mut pred_punct)?;
+    decode_patch_or_empty(&art, &blobs.punct)?.apply_to_pred(&mut pred_punct)?;
 
     // raw
     let mut pred_raw = gen_pred_stream_with_prog(&mut eng, n_raw as u64, art.max_ticks, &omega_prog.raw)?;
-    let raw_patch = if raw_b.is_empty() { PatchList::new() } else { PatchList::decode(&raw_b)? };
-    raw_patch.apply_to_pred(&mut pred_raw)?;
+    decode_patch_or_empty(&art, &blobs.raw)?.apply_to_pred(&mut pred_raw)?;
 
     let lanes = TextLanesV2 {
         total_len: art.total_len,
@@ -1069,9 +1960,11 @@ pub fn decode_k8l1(bytes: &[u8]) -> Result<Vec<u8>> {
         digit_lane: pred_digit,
         punct_lane: pred_punct,
         raw_lane: pred_raw,
+        numeric_run_lane: pred_runs,
+        numeric_lane: pred_num,
     };
 
-    Ok(lanes.unsplit()?)
+    lanes.unsplit(punct_alph)
 }
 
 // -------------------- recipe format helpers --------------------
@@ -18,6 +18,8 @@ pub mod repr;
 pub mod symbol;
 pub mod lane;
 
+pub use crate::dynamics::describe::EngineDescription;
 pub use crate::dynamics::engine::Engine;
 pub use crate::recipe::recipe::Recipe;
+pub use crate::signal::timing_map::TimingMap;
 pub use crate::signal::token::{PackedByte, PairToken};
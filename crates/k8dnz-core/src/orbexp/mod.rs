@@ -12,14 +12,29 @@
 //   Let d = (stepA - stepC) mod MOD.
 //   If d == 0: already in lockstep => first meet at t=0.
 //   Else: first meet period = MOD / gcd(MOD, d).
+//
+// OrbSimulator steps the same two-body system one tick at a time and keeps a bounded
+// phase history, so the trajectory behind a closed-form meet time can be inspected.
+//
+// N bodies (all starting at phase 0): meet when every phase is equal.
+//   T = lcm_{i<j}( MOD / gcd(MOD, step_i - step_j) ), and T = 0 when all steps agree mod MOD.
 
 use crate::error::{K8Error, Result};
+use std::collections::VecDeque;
 
+/// How a block's bytes are folded into the 64-bit `delta` that seeds step C.
+///
+/// `Int`, `Crc32` and `DecPairs` are cheap and fine for non-cryptographic
+/// experiments (bucket sweeps, closed-form checks). `Blake3` and `Sha256` take
+/// the first 8 bytes (LE) of a cryptographic digest; prefer them when deltas
+/// must not be steerable by chosen input. `Blake3` is the faster of the two.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DeriveMode {
     Int,
     Crc32,
     DecPairs,
+    Blake3,
+    Sha256,
 }
 
 impl DeriveMode {
@@ -28,6 +43,8 @@ impl DeriveMode {
             "int" | "integer" => Ok(DeriveMode::Int),
             "crc32" | "crc" => Ok(DeriveMode::Crc32),
             "decpairs" | "dec" | "bcd" => Ok(DeriveMode::DecPairs),
+            "blake3" | "b3" => Ok(DeriveMode::Blake3),
+            "sha256" | "sha2" => Ok(DeriveMode::Sha256),
             _ => Err(K8Error::Validation(format!("unknown derive mode: {s}"))),
         }
     }
@@ -53,7 +70,9 @@ pub fn compute_first_meet(params: OrbParams) -> Result<OrbResult> {
     }
     let modn = params.modn;
 
-    let d = params.step_a.wrapping_sub(params.step_c) % modn;
+    // Subtract mod MOD, not mod 2^64: wrapping_sub would add 2^64 mod MOD when step_a < step_c.
+    let (a, c) = (params.step_a % modn, params.step_c % modn);
+    let d = if a >= c { a - c } else { modn - (c - a) };
 
     if d == 0 {
         return Ok(OrbResult {
@@ -96,6 +115,161 @@ pub fn simulate_first_meet(params: OrbParams, max_ticks: u64) -> Result<Option<u
     Ok(None)
 }
 
+/// Phase pairs `OrbSimulator::new` keeps for `history`.
+pub const ORB_HISTORY_DEFAULT: usize = 4096;
+
+/// Two-body orbit stepped one tick at a time. Both phases start at 0 (tick 0);
+/// `tick` advances to tick t+1, so the first `Some(t)` equals `compute_first_meet`'s
+/// `t_first_meet` whenever the steps differ mod MOD.
+#[derive(Clone, Debug)]
+pub struct OrbSimulator {
+    modn: u64,
+    step_a: u64,
+    step_c: u64,
+    phase_a: u64,
+    phase_c: u64,
+    t: u64,
+    history: VecDeque<(u64, u64)>,
+    history_cap: usize,
+}
+
+impl OrbSimulator {
+    pub fn new(params: OrbParams) -> Result<Self> {
+        Self::with_history_capacity(params, ORB_HISTORY_DEFAULT)
+    }
+
+    /// Like `new`, but keep at most `cap` phase pairs (including the start state).
+    pub fn with_history_capacity(params: OrbParams, cap: usize) -> Result<Self> {
+        if params.modn == 0 {
+            return Err(K8Error::Validation("mod must be non-zero".to_string()));
+        }
+        let mut history = VecDeque::with_capacity(cap.min(ORB_HISTORY_DEFAULT));
+        if cap > 0 {
+            history.push_back((0, 0));
+        }
+        Ok(Self {
+            modn: params.modn,
+            step_a: params.step_a % params.modn,
+            step_c: params.step_c % params.modn,
+            phase_a: 0,
+            phase_c: 0,
+            t: 0,
+            history,
+            history_cap: cap,
+        })
+    }
+
+    /// Advance one tick; `Some(t)` when the phases are equal at the new tick `t`.
+    pub fn tick(&mut self) -> Option<u64> {
+        let m = self.modn as u128;
+        self.phase_a = ((self.phase_a as u128 + self.step_a as u128) % m) as u64;
+        self.phase_c = ((self.phase_c as u128 + self.step_c as u128) % m) as u64;
+        self.t += 1;
+
+        if self.history_cap > 0 {
+            if self.history.len() == self.history_cap {
+                self.history.pop_front();
+            }
+            self.history.push_back((self.phase_a, self.phase_c));
+        }
+
+        (self.phase_a == self.phase_c).then_some(self.t)
+    }
+
+    /// Current (phase A, phase C).
+    pub fn phases(&self) -> (u64, u64) {
+        (self.phase_a, self.phase_c)
+    }
+
+    /// Ticks taken so far.
+    pub fn ticks(&self) -> u64 {
+        self.t
+    }
+
+    /// The last `n` phase pairs, oldest first (fewer if less history is kept).
+    pub fn history(&self, n: usize) -> Vec<(u64, u64)> {
+        let skip = self.history.len().saturating_sub(n);
+        self.history.iter().skip(skip).copied().collect()
+    }
+}
+
+/// Closed-form first meet for N >= 2 bodies. `d` is the gcd of all pairwise step
+/// differences (mod MOD), `gcd` is gcd(MOD, d); `t_first_meet` is the pairwise lcm.
+/// Errors if the lcm does not fit in u64.
+pub fn compute_multi_meet(modn: u64, steps: &[u64]) -> Result<OrbResult> {
+    if modn == 0 {
+        return Err(K8Error::Validation("mod must be non-zero".to_string()));
+    }
+    if steps.len() < 2 {
+        return Err(K8Error::Validation(format!(
+            "multi meet needs at least 2 steps, got {}",
+            steps.len()
+        )));
+    }
+
+    let mut d_all = 0u64;
+    let mut t: u64 = 1;
+    for i in 0..steps.len() {
+        for j in (i + 1)..steps.len() {
+            let (a, b) = (steps[i] % modn, steps[j] % modn);
+            let d = if a >= b { a - b } else { modn - (b - a) };
+            if d == 0 {
+                continue;
+            }
+            d_all = gcd_u64(d_all, d);
+            let period = modn / gcd_u64(modn, d);
+            t = lcm_u64(t, period).ok_or_else(|| {
+                K8Error::Validation(format!("multi meet period overflows u64 (mod={modn})"))
+            })?;
+        }
+    }
+
+    if d_all == 0 {
+        return Ok(OrbResult {
+            d: 0,
+            gcd: modn,
+            t_first_meet: 0,
+        });
+    }
+
+    Ok(OrbResult {
+        d: d_all,
+        gcd: gcd_u64(modn, d_all),
+        t_first_meet: t,
+    })
+}
+
+/// Simulate N bodies from phase 0 and return the first tick t in 1..=max_ticks at
+/// which all phases are equal. Lockstep (all steps equal mod MOD) returns Some(0),
+/// matching `compute_multi_meet`.
+pub fn simulate_multi_meet(modn: u64, steps: &[u64], max_ticks: u64) -> Result<Option<u64>> {
+    if modn == 0 {
+        return Err(K8Error::Validation("mod must be non-zero".to_string()));
+    }
+    if steps.len() < 2 {
+        return Err(K8Error::Validation(format!(
+            "multi meet needs at least 2 steps, got {}",
+            steps.len()
+        )));
+    }
+
+    let steps: Vec<u64> = steps.iter().map(|s| s % modn).collect();
+    if steps.iter().all(|&s| s == steps[0]) {
+        return Ok(Some(0));
+    }
+
+    let mut phases = vec![0u64; steps.len()];
+    for t in 1..=max_ticks {
+        for (ph, &st) in phases.iter_mut().zip(steps.iter()) {
+            *ph = ((*ph as u128 + st as u128) % modn as u128) as u64;
+        }
+        if phases.iter().all(|&ph| ph == phases[0]) {
+            return Ok(Some(t));
+        }
+    }
+    Ok(None)
+}
+
 pub fn derive_steps(
     p: u64,
     block: &[u8],
@@ -124,6 +298,11 @@ pub fn derive_steps(
         DeriveMode::Int => derive_int_msb_first(&block[..need_bytes], block_bits)?,
         DeriveMode::Crc32 => crc32_ieee(&block[..need_bytes]) as u64,
         DeriveMode::DecPairs => derive_dec_pairs(&block[..need_bytes])?,
+        DeriveMode::Blake3 => u64_le_prefix(blake3::hash(&block[..need_bytes]).as_bytes()),
+        DeriveMode::Sha256 => {
+            use sha2::Digest;
+            u64_le_prefix(&sha2::Sha256::digest(&block[..need_bytes]))
+        }
     };
 
     let step_a = splitmix64(p) % modn;
@@ -191,6 +370,13 @@ fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
     a
 }
 
+fn lcm_u64(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd_u64(a, b)).checked_mul(b)
+}
+
 fn splitmix64(mut x: u64) -> u64 {
     x = x.wrapping_add(0x9e3779b97f4a7c15);
     let mut z = x;
@@ -199,6 +385,12 @@ fn splitmix64(mut x: u64) -> u64 {
     z ^ (z >> 31)
 }
 
+fn u64_le_prefix(digest: &[u8]) -> u64 {
+    let mut b = [0u8; 8];
+    b.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(b)
+}
+
 fn crc32_ieee(data: &[u8]) -> u32 {
     let mut crc: u32 = 0xFFFF_FFFF;
     for &b in data {
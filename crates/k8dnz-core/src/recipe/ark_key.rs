@@ -3,15 +3,30 @@
 use crate::error::{K8Error, Result};
 use crate::fixed::turn32::Turn32;
 use crate::recipe::checksum::crc32;
-use crate::recipe::recipe::{Alphabet, KeystreamMix, PayloadKind, Recipe, ResetMode};
+use crate::recipe::format::{
+    FORMAT_VERSION, FORMAT_VERSION_QUANT_GAMMA, FORMAT_VERSION_RGB, FORMAT_VERSION_SOFT_CLAMP,
+};
+use crate::recipe::recipe::{Alphabet, KeystreamMix, PayloadKind, Recipe, ResetMode, RgbRecipe};
 
 const PREFIX: &str = "ARK1S:";
 
+/// String-format 0: the v4 field set only.
+const ARK1S_FMT_V4: u8 = 0;
+/// String-format 1: v4 fields plus the `.k8r` tails gated on recipe.version
+/// (rgb for v5+, soft_knee for v6+, quant_gamma for v7+).
+const ARK1S_FMT_TAILS: u8 = 1;
+
 pub fn encode_ark1s(recipe: &Recipe) -> String {
     let mut b = Vec::with_capacity(160);
 
-    // string-format version (not recipe.version)
-    b.push(0u8);
+    // string-format version (not recipe.version); v4 recipes keep format 0 so
+    // their keys are unchanged
+    let fmt = if recipe.version > FORMAT_VERSION {
+        ARK1S_FMT_TAILS
+    } else {
+        ARK1S_FMT_V4
+    };
+    b.push(fmt);
 
     // recipe.version is u16
     b.extend_from_slice(&recipe.version.to_le_bytes());
@@ -72,6 +87,26 @@ pub fn encode_ark1s(recipe: &Recipe) -> String {
         b.extend_from_slice(&w.amp.to_le_bytes());
     }
 
+    // v5+ rgb params (same layout as the .k8r tail)
+    if fmt >= ARK1S_FMT_TAILS && recipe.version >= FORMAT_VERSION_RGB {
+        b.push(recipe.rgb.backend);
+        b.push(recipe.rgb.alt_mode);
+        b.extend_from_slice(&recipe.rgb.base_a);
+        b.extend_from_slice(&recipe.rgb.base_c);
+        b.extend_from_slice(&recipe.rgb.g_step.to_le_bytes());
+        b.extend_from_slice(&recipe.rgb.p_scale.to_le_bytes());
+    }
+
+    // v6+ soft clamp knee (0 = hard clamp)
+    if fmt >= ARK1S_FMT_TAILS && recipe.version >= FORMAT_VERSION_SOFT_CLAMP {
+        b.extend_from_slice(&recipe.field_clamp.soft_knee.unwrap_or(0).to_le_bytes());
+    }
+
+    // v7+ quant gamma (0.0 = linear quantizer)
+    if fmt >= ARK1S_FMT_TAILS && recipe.version >= FORMAT_VERSION_QUANT_GAMMA {
+        b.extend_from_slice(&recipe.quant_gamma.unwrap_or(0.0).to_bits().to_le_bytes());
+    }
+
     // crc32 over everything so far
     let c = crc32(&b);
     b.extend_from_slice(&c.to_le_bytes());
@@ -87,7 +122,11 @@ pub fn decode_ark1s(s: &str) -> Result<Recipe> {
 
     let bytes = crock32_decode(body)?;
     if bytes.len() < 1 + 2 + 4 {
-        return Err(K8Error::Validation("ark1s: too small".into()));
+        return Err(K8Error::TruncatedInput {
+            context: "ark1s",
+            expected: 1 + 2 + 4,
+            got: bytes.len(),
+        });
     }
 
     let crc_off = bytes.len() - 4;
@@ -96,59 +135,73 @@ pub fn decode_ark1s(s: &str) -> Result<Recipe> {
     if crc_expected != crc_actual {
         return Err(K8Error::Validation("ark1s: crc32 mismatch".into()));
     }
+    let bytes = &bytes[..crc_off];
 
     let mut i = 0usize;
 
-    let _fmt_ver = read_u8(&bytes, &mut i)?;
-    let recipe_ver = read_u16(&bytes, &mut i)?;
+    let fmt = read_u8(bytes, &mut i)?;
+    if fmt > ARK1S_FMT_TAILS {
+        return Err(K8Error::BadVersion {
+            expected: ARK1S_FMT_TAILS.into(),
+            got: fmt.into(),
+        });
+    }
+    let recipe_ver = read_u16(bytes, &mut i)?;
+    if fmt == ARK1S_FMT_V4 && recipe_ver > FORMAT_VERSION {
+        // Written before ARK1S carried the v5+ fields: they were dropped, so the
+        // decoded recipe would not regenerate the same keystream.
+        return Err(K8Error::Validation(format!(
+            "ark1s: string-format 0 cannot carry recipe v{recipe_ver}; re-export the key from the .k8r"
+        )));
+    }
 
-    let alphabet = match read_u8(&bytes, &mut i)? {
+    let alphabet = match read_u8(bytes, &mut i)? {
         0 => Alphabet::N16,
         _ => return Err(K8Error::Validation("ark1s: bad alphabet".into())),
     };
-    let reset_mode = match read_u8(&bytes, &mut i)? {
+    let reset_mode = match read_u8(bytes, &mut i)? {
         0 => ResetMode::HoldAandC,
         1 => ResetMode::FromLockstep,
         _ => return Err(K8Error::Validation("ark1s: bad reset_mode".into())),
     };
-    let keystream_mix = match read_u8(&bytes, &mut i)? {
+    let keystream_mix = match read_u8(bytes, &mut i)? {
         0 => KeystreamMix::None,
         1 => KeystreamMix::SplitMix64,
         _ => return Err(K8Error::Validation("ark1s: bad keystream_mix".into())),
     };
-    let payload_kind = match read_u8(&bytes, &mut i)? {
+    let payload_kind = match read_u8(bytes, &mut i)? {
         0 => PayloadKind::CipherXor,
         1 => PayloadKind::ResidualXor,
         _ => return Err(K8Error::Validation("ark1s: bad payload_kind".into())),
     };
 
-    let seed = read_u64(&bytes, &mut i)?;
+    let seed = read_u64(bytes, &mut i)?;
 
-    let phi_a0 = read_turn32(&bytes, &mut i)?;
-    let phi_c0 = read_turn32(&bytes, &mut i)?;
-    let v_a = read_turn32(&bytes, &mut i)?;
-    let v_c = read_turn32(&bytes, &mut i)?;
-    let epsilon = read_turn32(&bytes, &mut i)?;
+    let phi_a0 = read_turn32(bytes, &mut i)?;
+    let phi_c0 = read_turn32(bytes, &mut i)?;
+    let v_a = read_turn32(bytes, &mut i)?;
+    let v_c = read_turn32(bytes, &mut i)?;
+    let epsilon = read_turn32(bytes, &mut i)?;
 
-    let v_l = read_turn32(&bytes, &mut i)?;
-    let delta = read_turn32(&bytes, &mut i)?;
-    let t_step = read_u32(&bytes, &mut i)?;
+    let v_l = read_turn32(bytes, &mut i)?;
+    let delta = read_turn32(bytes, &mut i)?;
+    let t_step = read_u32(bytes, &mut i)?;
 
-    let field_clamp_min = read_i64(&bytes, &mut i)?;
-    let field_clamp_max = read_i64(&bytes, &mut i)?;
+    let field_clamp_min = read_i64(bytes, &mut i)?;
+    let field_clamp_max = read_i64(bytes, &mut i)?;
 
-    let quant_min = read_i64(&bytes, &mut i)?;
-    let quant_max = read_i64(&bytes, &mut i)?;
-    let quant_shift = read_i64(&bytes, &mut i)?;
+    let quant_min = read_i64(bytes, &mut i)?;
+    let quant_max = read_i64(bytes, &mut i)?;
+    let quant_shift = read_i64(bytes, &mut i)?;
 
-    let waves_len = read_u16(&bytes, &mut i)? as usize;
+    let waves_len = read_u16(bytes, &mut i)? as usize;
     let mut waves = Vec::with_capacity(waves_len);
     for _ in 0..waves_len {
-        let k_phi = read_u32(&bytes, &mut i)?;
-        let k_t = read_u32(&bytes, &mut i)?;
-        let k_time = read_u32(&bytes, &mut i)?;
-        let phase = read_u32(&bytes, &mut i)?;
-        let amp = read_i32(&bytes, &mut i)?;
+        let k_phi = read_u32(bytes, &mut i)?;
+        let k_t = read_u32(bytes, &mut i)?;
+        let k_time = read_u32(bytes, &mut i)?;
+        let phase = read_u32(bytes, &mut i)?;
+        let amp = read_i32(bytes, &mut i)?;
         waves.push(crate::recipe::recipe::FieldWave {
             k_phi,
             k_t,
@@ -158,6 +211,40 @@ pub fn decode_ark1s(s: &str) -> Result<Recipe> {
         });
     }
 
+    let mut rgb = RgbRecipe::default();
+    if fmt >= ARK1S_FMT_TAILS && recipe_ver >= FORMAT_VERSION_RGB {
+        rgb.backend = read_u8(bytes, &mut i)?;
+        rgb.alt_mode = read_u8(bytes, &mut i)?;
+        for c in rgb.base_a.iter_mut().chain(rgb.base_c.iter_mut()) {
+            *c = read_u8(bytes, &mut i)?;
+        }
+        rgb.g_step = read_u16(bytes, &mut i)? as i16;
+        rgb.p_scale = read_u16(bytes, &mut i)? as i16;
+    }
+
+    let mut soft_knee = None;
+    if fmt >= ARK1S_FMT_TAILS && recipe_ver >= FORMAT_VERSION_SOFT_CLAMP {
+        let knee = read_u32(bytes, &mut i)?;
+        soft_knee = (knee != 0).then_some(knee);
+    }
+
+    let mut quant_gamma = None;
+    if fmt >= ARK1S_FMT_TAILS && recipe_ver >= FORMAT_VERSION_QUANT_GAMMA {
+        let gamma = f64::from_bits(read_u64(bytes, &mut i)?);
+        if gamma != 0.0 {
+            if !(gamma.is_finite() && gamma > 0.0) {
+                return Err(K8Error::Validation(format!(
+                    "ark1s: bad quant_gamma {gamma}"
+                )));
+            }
+            quant_gamma = Some(gamma);
+        }
+    }
+
+    if i != bytes.len() {
+        return Err(K8Error::Validation("ark1s: trailing bytes".into()));
+    }
+
     Ok(Recipe {
         version: recipe_ver,
         seed,
@@ -177,13 +264,15 @@ pub fn decode_ark1s(s: &str) -> Result<Recipe> {
         field_clamp: crate::recipe::recipe::FieldClampParams {
             min: field_clamp_min,
             max: field_clamp_max,
+            soft_knee,
         },
         quant: crate::recipe::recipe::QuantParams {
             min: quant_min,
             max: quant_max,
             shift: quant_shift,
         },
-        rgb: Default::default(),
+        rgb,
+        quant_gamma,
     })
 }
 
@@ -197,7 +286,11 @@ fn read_turn32(bytes: &[u8], i: &mut usize) -> Result<Turn32> {
 
 fn read_u8(bytes: &[u8], i: &mut usize) -> Result<u8> {
     if *i + 1 > bytes.len() {
-        return Err(K8Error::Validation("ark1s: unexpected eof".into()));
+        return Err(K8Error::TruncatedInput {
+            context: "ark1s",
+            expected: *i + 1,
+            got: bytes.len(),
+        });
     }
     let v = bytes[*i];
     *i += 1;
@@ -206,7 +299,11 @@ fn read_u8(bytes: &[u8], i: &mut usize) -> Result<u8> {
 
 fn read_u16(bytes: &[u8], i: &mut usize) -> Result<u16> {
     if *i + 2 > bytes.len() {
-        return Err(K8Error::Validation("ark1s: unexpected eof".into()));
+        return Err(K8Error::TruncatedInput {
+            context: "ark1s",
+            expected: *i + 2,
+            got: bytes.len(),
+        });
     }
     let v = u16::from_le_bytes(bytes[*i..*i + 2].try_into().unwrap());
     *i += 2;
@@ -215,7 +312,11 @@ fn read_u16(bytes: &[u8], i: &mut usize) -> Result<u16> {
 
 fn read_u32(bytes: &[u8], i: &mut usize) -> Result<u32> {
     if *i + 4 > bytes.len() {
-        return Err(K8Error::Validation("ark1s: unexpected eof".into()));
+        return Err(K8Error::TruncatedInput {
+            context: "ark1s",
+            expected: *i + 4,
+            got: bytes.len(),
+        });
     }
     let v = u32::from_le_bytes(bytes[*i..*i + 4].try_into().unwrap());
     *i += 4;
@@ -224,7 +325,11 @@ fn read_u32(bytes: &[u8], i: &mut usize) -> Result<u32> {
 
 fn read_i32(bytes: &[u8], i: &mut usize) -> Result<i32> {
     if *i + 4 > bytes.len() {
-        return Err(K8Error::Validation("ark1s: unexpected eof".into()));
+        return Err(K8Error::TruncatedInput {
+            context: "ark1s",
+            expected: *i + 4,
+            got: bytes.len(),
+        });
     }
     let v = i32::from_le_bytes(bytes[*i..*i + 4].try_into().unwrap());
     *i += 4;
@@ -233,7 +338,11 @@ fn read_i32(bytes: &[u8], i: &mut usize) -> Result<i32> {
 
 fn read_u64(bytes: &[u8], i: &mut usize) -> Result<u64> {
     if *i + 8 > bytes.len() {
-        return Err(K8Error::Validation("ark1s: unexpected eof".into()));
+        return Err(K8Error::TruncatedInput {
+            context: "ark1s",
+            expected: *i + 8,
+            got: bytes.len(),
+        });
     }
     let v = u64::from_le_bytes(bytes[*i..*i + 8].try_into().unwrap());
     *i += 8;
@@ -242,7 +351,11 @@ fn read_u64(bytes: &[u8], i: &mut usize) -> Result<u64> {
 
 fn read_i64(bytes: &[u8], i: &mut usize) -> Result<i64> {
     if *i + 8 > bytes.len() {
-        return Err(K8Error::Validation("ark1s: unexpected eof".into()));
+        return Err(K8Error::TruncatedInput {
+            context: "ark1s",
+            expected: *i + 8,
+            got: bytes.len(),
+        });
     }
     let v = i64::from_le_bytes(bytes[*i..*i + 8].try_into().unwrap());
     *i += 8;
@@ -1,11 +1,83 @@
 // crates/k8dnz-core/src/recipe/defaults.rs
 
 use crate::fixed::turn32::Turn32;
+use crate::recipe::format::FORMAT_VERSION_RGB;
 use crate::recipe::recipe::{
     Alphabet, FieldClampParams, FieldParams, FieldWave, FreeOrbitParams, KeystreamMix,
     LockstepParams, PayloadKind, QuantParams, Recipe, ResetMode,
 };
 
+/// Named built-in recipes. All presets share `default_recipe()`'s orbit, lockstep
+/// and field parameters; they differ in the tuned knobs (quant shift, rgb params).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DefaultRecipes {
+    /// `default_recipe()` as-is (the "tuned" profile).
+    Default,
+    /// Default with quant.shift = 0 (the "baseline" profile).
+    Flat,
+    /// Shift tuned for packed-byte entropy.
+    HighEntropy,
+    /// Shift tuned for zstd size of the residual against English text.
+    TextAligned,
+    /// rgb.g_step / rgb.p_scale tuned for the rgbpair residual against English text.
+    RgbOptimized,
+}
+
+impl DefaultRecipes {
+    pub const ALL: [DefaultRecipes; 5] = [
+        DefaultRecipes::Default,
+        DefaultRecipes::Flat,
+        DefaultRecipes::HighEntropy,
+        DefaultRecipes::TextAligned,
+        DefaultRecipes::RgbOptimized,
+    ];
+
+    /// Kebab-case name, as accepted by `--recipe-preset`.
+    pub fn name(self) -> &'static str {
+        match self {
+            DefaultRecipes::Default => "default",
+            DefaultRecipes::Flat => "flat",
+            DefaultRecipes::HighEntropy => "high-entropy",
+            DefaultRecipes::TextAligned => "text-aligned",
+            DefaultRecipes::RgbOptimized => "rgb-optimized",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<DefaultRecipes> {
+        DefaultRecipes::ALL.into_iter().find(|p| p.name() == name)
+    }
+}
+
+pub fn get(variant: DefaultRecipes) -> Recipe {
+    let mut r = default_recipe();
+    match variant {
+        DefaultRecipes::Default => {}
+        DefaultRecipes::Flat => {
+            r.quant.shift = 0;
+        }
+        DefaultRecipes::HighEntropy => {
+            // `tune --passes 3 --per-emissions 2000` from default_recipe(), ranked by
+            // packed-byte entropy (the default objective). Winner: entropy_byte ~6.05.
+            r.quant.shift = 10_376_782;
+        }
+        DefaultRecipes::TextAligned => {
+            // `tune --fit-in text/Genesis1.txt --rank-by-effective-zstd --passes 2`
+            // from default_recipe(). per_emissions=2000 (default) is unused here: residual
+            // ranking always generates one emission per byte of the fit file (4201).
+            r.quant.shift = 32_134_556;
+        }
+        DefaultRecipes::RgbOptimized => {
+            // `tune --fit-in text/Genesis1.txt --tune-rgb-params` from default_recipe(),
+            // per_emissions=2000 for the shift pass (which kept the default shift). The
+            // rgb sweep (g_step -8..=8, p_scale 1..=8) picked g_step=0 p_scale=1.
+            r.rgb.g_step = 0;
+            r.rgb.p_scale = 1;
+            r.version = FORMAT_VERSION_RGB;
+        }
+    }
+    r
+}
+
 #[inline]
 fn frac_turn(num: u64, den: u64) -> Turn32 {
     // Turn32 is u32 where 1.0 turn == 2^32.
@@ -108,6 +180,7 @@ pub fn default_recipe() -> Recipe {
         field_clamp: FieldClampParams {
             min: -147_728_900,
             max: 80_783_500,
+            soft_knee: None,
         },
 
         // Quant range equals clamp range; shift moves bin boundaries (does NOT change width).
@@ -120,5 +193,6 @@ pub fn default_recipe() -> Recipe {
 
         // RGB emission parameters (DNA/coupled-adder defaults).
         rgb: Default::default(),
+        quant_gamma: None,
     }
 }
@@ -0,0 +1,83 @@
+// crates/k8dnz-core/src/recipe/diff.rs
+//
+// Field-by-field comparison of two recipes, for "what did this change actually
+// touch?" reports next to a stream diff. Values are rendered with Debug so Turn32
+// and enum fields print the same way `recipe inspect` shows them.
+
+use crate::recipe::recipe::Recipe;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecipeFieldDiff {
+    /// Dotted path, e.g. `quant.shift` or `field.wave[2]`.
+    pub field: String,
+    pub a: String,
+    pub b: String,
+}
+
+impl Recipe {
+    /// Every field whose value differs between `self` (a) and `other` (b), in
+    /// `recipe inspect` order. Missing waves render as `<none>`.
+    pub fn field_diff(&self, other: &Recipe) -> Vec<RecipeFieldDiff> {
+        let mut out = Vec::new();
+        let mut cmp = |field: &str, a: String, b: String| {
+            if a != b {
+                out.push(RecipeFieldDiff {
+                    field: field.to_string(),
+                    a,
+                    b,
+                });
+            }
+        };
+        macro_rules! field {
+            ($name:literal, $($path:tt)+) => {
+                cmp($name, format!("{:?}", self.$($path)+), format!("{:?}", other.$($path)+))
+            };
+        }
+
+        field!("version", version);
+        field!("seed", seed);
+        field!("alphabet", alphabet);
+        field!("reset_mode", reset_mode);
+        field!("keystream_mix", keystream_mix);
+        field!("payload_kind", payload_kind);
+
+        field!("free.phi_a0", free.phi_a0);
+        field!("free.phi_c0", free.phi_c0);
+        field!("free.v_a", free.v_a);
+        field!("free.v_c", free.v_c);
+        field!("free.epsilon", free.epsilon);
+
+        field!("lock.v_l", lock.v_l);
+        field!("lock.delta", lock.delta);
+        field!("lock.t_step", lock.t_step);
+
+        field!("field.waves", field.waves.len());
+        let waves = self.field.waves.len().max(other.field.waves.len());
+        for i in 0..waves {
+            let show = |r: &Recipe| {
+                r.field
+                    .waves
+                    .get(i)
+                    .map_or_else(|| "<none>".to_string(), |w| format!("{w:?}"))
+            };
+            cmp(&format!("field.wave[{i}]"), show(self), show(other));
+        }
+
+        field!("field_clamp.min", field_clamp.min);
+        field!("field_clamp.max", field_clamp.max);
+        field!("field_clamp.soft_knee", field_clamp.soft_knee);
+        field!("quant.min", quant.min);
+        field!("quant.max", quant.max);
+        field!("quant.shift", quant.shift);
+        field!("quant_gamma", quant_gamma);
+
+        field!("rgb.backend", rgb.backend);
+        field!("rgb.alt_mode", rgb.alt_mode);
+        field!("rgb.base_a", rgb.base_a);
+        field!("rgb.base_c", rgb.base_c);
+        field!("rgb.g_step", rgb.g_step);
+        field!("rgb.p_scale", rgb.p_scale);
+
+        out
+    }
+}
@@ -7,6 +7,21 @@ use crate::recipe::recipe::*;
 
 const MAGIC: &[u8; 4] = b"K8R1";
 
+/// Base layout version (see layout notes below): recipes that use none of the opt-in
+/// fields are written as v4. Setting one bumps the written version to v5, v6 or v7
+/// (`FORMAT_VERSION_RGB` .. `FORMAT_VERSION_QUANT_GAMMA`); v2..=v7 all decode.
+pub const FORMAT_VERSION: u16 = 4;
+
+/// v4 plus the RGB block. Only recipes that opt in (e.g. `tune --tune-rgb-params`)
+/// use it, so default recipe ids stay stable.
+pub const FORMAT_VERSION_RGB: u16 = 5;
+
+/// v5 plus the soft clamp knee. Written only when `field_clamp.soft_knee` is set.
+pub const FORMAT_VERSION_SOFT_CLAMP: u16 = 6;
+
+/// v6 plus the quantizer gamma. Written only when `quant_gamma` is set.
+pub const FORMAT_VERSION_QUANT_GAMMA: u16 = 7;
+
 /// Minimal binary-stable format (owned).
 /// Layout (little-endian):
 /// MAGIC[4]
@@ -18,13 +33,18 @@ const MAGIC: &[u8; 4] = b"K8R1";
 /// [v3+] field_clamp: fmin:i64 fmax:i64
 /// [v2+] quant: qmin:i64 qmax:i64
 /// [v4+] qshift:i64
+/// [v5+] rgb: backend:u8 alt_mode:u8 base_a:[3] base_c:[3] g_step:i16 p_scale:i16
+/// [v6+] soft_knee:u32 (0 = hard clamp)
+/// [v7+] quant_gamma:f64 (0.0 = linear quantizer)
 /// waves_len:u16
 /// waves: repeated { k_phi:u32 k_t:u32 k_time:u32 phase:u32 amp:i32 }
 /// crc32:u32          (over everything before crc32)
 /// blake3_16:[16]     (over everything before blake3)
 ///
-/// NOTE: RGB params are currently NOT encoded in this binary format.
-/// They are injected via defaults on decode() for back-compat.
+/// NOTE: RGB params are only encoded from v5 on; older versions get
+/// RgbRecipe::default() on decode() for back-compat. Likewise soft_knee (v6+),
+/// which decodes as None (hard clamp) for older versions, and quant_gamma (v7+),
+/// which decodes as None (linear) for older versions.
 pub fn encode(r: &Recipe) -> Vec<u8> {
     let mut b = Vec::with_capacity(256);
     b.extend_from_slice(MAGIC);
@@ -63,6 +83,26 @@ pub fn encode(r: &Recipe) -> Vec<u8> {
         b.extend_from_slice(&r.quant.shift.to_le_bytes());
     }
 
+    // v5+ rgb params
+    if r.version >= 5 {
+        b.push(r.rgb.backend);
+        b.push(r.rgb.alt_mode);
+        b.extend_from_slice(&r.rgb.base_a);
+        b.extend_from_slice(&r.rgb.base_c);
+        b.extend_from_slice(&r.rgb.g_step.to_le_bytes());
+        b.extend_from_slice(&r.rgb.p_scale.to_le_bytes());
+    }
+
+    // v6+ soft clamp knee
+    if r.version >= 6 {
+        b.extend_from_slice(&r.field_clamp.soft_knee.unwrap_or(0).to_le_bytes());
+    }
+
+    // v7+ quant gamma
+    if r.version >= 7 {
+        b.extend_from_slice(&r.quant_gamma.unwrap_or(0.0).to_bits().to_le_bytes());
+    }
+
     let waves_len: u16 = r.field.waves.len().min(u16::MAX as usize) as u16;
     b.extend_from_slice(&waves_len.to_le_bytes());
     for w in r.field.waves.iter().take(waves_len as usize) {
@@ -84,8 +124,10 @@ pub fn encode(r: &Recipe) -> Vec<u8> {
 
 pub fn decode(bytes: &[u8]) -> Result<Recipe> {
     let mut i = 0usize;
-    if bytes.len() < 4 || &bytes[0..4] != MAGIC {
-        return Err(K8Error::RecipeFormat("bad magic".into()));
+    need(bytes, 0, 4)?;
+    if &bytes[0..4] != MAGIC {
+        let got = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        return Err(K8Error::BadMagic { context: "recipe", expected: *MAGIC, got });
     }
     i += 4;
 
@@ -109,6 +151,7 @@ pub fn decode(bytes: &[u8]) -> Result<Recipe> {
     let mut field_clamp = FieldClampParams {
         min: -100_000_000,
         max: 100_000_000,
+        soft_knee: None,
     };
     let mut quant = QuantParams {
         min: -100_000_000,
@@ -117,35 +160,56 @@ pub fn decode(bytes: &[u8]) -> Result<Recipe> {
     };
 
     if version >= 3 {
-        if bytes.len() < i + 16 {
-            return Err(K8Error::RecipeFormat(
-                "unexpected eof reading field_clamp".into(),
-            ));
-        }
+        need_for("recipe field_clamp", bytes, i, 16)?;
         field_clamp.min = read_i64(bytes, &mut i)?;
         field_clamp.max = read_i64(bytes, &mut i)?;
     }
 
     if version >= 2 {
-        if bytes.len() < i + 16 {
-            return Err(K8Error::RecipeFormat("unexpected eof reading quant".into()));
-        }
+        need_for("recipe quant", bytes, i, 16)?;
         quant.min = read_i64(bytes, &mut i)?;
         quant.max = read_i64(bytes, &mut i)?;
     }
 
     // v4+ quant shift
     if version >= 4 {
-        if bytes.len() < i + 8 {
-            return Err(K8Error::RecipeFormat(
-                "unexpected eof reading qshift".into(),
-            ));
-        }
+        need_for("recipe qshift", bytes, i, 8)?;
         quant.shift = read_i64(bytes, &mut i)?;
     } else {
         quant.shift = 0;
     }
 
+    // v5+ rgb params
+    let mut rgb = RgbRecipe::default();
+    if version >= 5 {
+        need_for("recipe rgb", bytes, i, 12)?;
+        rgb.backend = bytes[i];
+        rgb.alt_mode = bytes[i + 1];
+        rgb.base_a.copy_from_slice(&bytes[i + 2..i + 5]);
+        rgb.base_c.copy_from_slice(&bytes[i + 5..i + 8]);
+        i += 8;
+        rgb.g_step = read_u16(bytes, &mut i)? as i16;
+        rgb.p_scale = read_u16(bytes, &mut i)? as i16;
+    }
+
+    // v6+ soft clamp knee
+    if version >= 6 {
+        let knee = read_u32(bytes, &mut i)?;
+        field_clamp.soft_knee = (knee != 0).then_some(knee);
+    }
+
+    // v7+ quant gamma
+    let mut quant_gamma = None;
+    if version >= 7 {
+        let gamma = f64::from_bits(read_u64(bytes, &mut i)?);
+        if gamma != 0.0 {
+            if !(gamma.is_finite() && gamma > 0.0) {
+                return Err(K8Error::RecipeFormat(format!("bad quant_gamma {gamma}")));
+            }
+            quant_gamma = Some(gamma);
+        }
+    }
+
     let waves_len = read_u16(bytes, &mut i)? as usize;
     let mut waves = Vec::with_capacity(waves_len);
     for _ in 0..waves_len {
@@ -199,7 +263,8 @@ pub fn decode(bytes: &[u8]) -> Result<Recipe> {
         field: FieldParams { waves },
         field_clamp,
         quant,
-        rgb: RgbRecipe::default(),
+        rgb,
+        quant_gamma,
     })
 }
 
@@ -214,6 +279,105 @@ pub fn recipe_id_hex(r: &Recipe) -> String {
     hex16(&id)
 }
 
+// Byte offsets of the fields `RecipeIdPatcher` rewrites, following `encode()` for v4+:
+// MAGIC[4] version:u16 flags:u16 | seed:u64 | free 5 x u32 | lock 3 x u32 | clamp | quant | qshift
+const OFF_SEED: usize = 4 + 2 + 2;
+const OFF_CLAMP: usize = OFF_SEED + 8 + 5 * 4 + 3 * 4;
+const OFF_QUANT: usize = OFF_CLAMP + 16;
+const OFF_QSHIFT: usize = OFF_QUANT + 16;
+
+/// Recipe ids for variants of one base recipe that differ only in seed, quant range/shift
+/// and field clamp range. The base encoding is kept in a scratch buffer that each call
+/// patches in place and re-checksums, so no `Recipe` is cloned or re-encoded.
+pub struct RecipeIdPatcher {
+    base: Recipe,
+    // `encode(base)` minus the trailing blake3_16 (the crc32 slot is rewritten per call).
+    buf: Vec<u8>,
+}
+
+impl RecipeIdPatcher {
+    pub fn new(base: &Recipe) -> Self {
+        let mut buf = encode(base);
+        buf.truncate(buf.len() - 16);
+        Self {
+            base: base.clone(),
+            buf,
+        }
+    }
+
+    /// `recipe_id_hex` of the base recipe with these fields replaced.
+    pub fn id_hex(
+        &mut self,
+        seed: u64,
+        quant_min: i64,
+        quant_max: i64,
+        quant_shift: i64,
+        clamp_min: i64,
+        clamp_max: i64,
+    ) -> String {
+        let slow = || {
+            let mut r = self.base.clone();
+            r.seed = seed;
+            r.quant.min = quant_min;
+            r.quant.max = quant_max;
+            r.quant.shift = quant_shift;
+            r.field_clamp.min = clamp_min;
+            r.field_clamp.max = clamp_max;
+            recipe_id_hex(&r)
+        };
+        // Pre-v4 layouts have no qshift (and v2 no clamp).
+        if self.base.version < FORMAT_VERSION {
+            return slow();
+        }
+
+        let b = &mut self.buf;
+        b[OFF_SEED..OFF_SEED + 8].copy_from_slice(&seed.to_le_bytes());
+        b[OFF_CLAMP..OFF_CLAMP + 8].copy_from_slice(&clamp_min.to_le_bytes());
+        b[OFF_CLAMP + 8..OFF_CLAMP + 16].copy_from_slice(&clamp_max.to_le_bytes());
+        b[OFF_QUANT..OFF_QUANT + 8].copy_from_slice(&quant_min.to_le_bytes());
+        b[OFF_QUANT + 8..OFF_QUANT + 16].copy_from_slice(&quant_max.to_le_bytes());
+        b[OFF_QSHIFT..OFF_QSHIFT + 8].copy_from_slice(&quant_shift.to_le_bytes());
+        let crc_at = b.len() - 4;
+        let c = crc32(&b[..crc_at]);
+        b[crc_at..].copy_from_slice(&c.to_le_bytes());
+        let id = hex16(&blake3_16(b));
+
+        debug_assert_eq!(
+            id,
+            slow(),
+            "RecipeIdPatcher offsets out of sync with encode()"
+        );
+        id
+    }
+}
+
+/// `recipe_id_hex` of `default_recipe()` with seed, quant range/shift and field clamp
+/// replaced, without building a `Recipe` (see `RecipeIdPatcher` for other bases).
+pub fn recipe_id_from_params(
+    seed: u64,
+    quant_min: i64,
+    quant_max: i64,
+    quant_shift: i64,
+    clamp_min: i64,
+    clamp_max: i64,
+) -> String {
+    thread_local! {
+        static DEFAULT: std::cell::RefCell<RecipeIdPatcher> =
+            std::cell::RefCell::new(RecipeIdPatcher::new(&crate::recipe::defaults::default_recipe()));
+    }
+    DEFAULT.with(|p| {
+        let mut p = p.borrow_mut();
+        p.id_hex(
+            seed,
+            quant_min,
+            quant_max,
+            quant_shift,
+            clamp_min,
+            clamp_max,
+        )
+    })
+}
+
 pub fn recipe_id_16_from_encoded(encoded: &[u8]) -> Result<[u8; 16]> {
     if encoded.len() < 16 {
         return Err(K8Error::RecipeFormat(
@@ -226,15 +390,123 @@ pub fn recipe_id_16_from_encoded(encoded: &[u8]) -> Result<[u8; 16]> {
 }
 
 fn hex16(id: &[u8; 16]) -> String {
+    hex_lower(id)
+}
+
+fn hex_lower(bytes: &[u8]) -> String {
     const HEX: &[u8; 16] = b"0123456789abcdef";
-    let mut s = String::with_capacity(32);
-    for &b in id {
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
         s.push(HEX[(b >> 4) as usize] as char);
         s.push(HEX[(b & 0x0F) as usize] as char);
     }
     s
 }
 
+// ---- text forms ----
+//
+// Base64 (URL-safe, no padding) and hex are plain re-spellings of the exact
+// `encode()` bytes, i.e. a .k8r file, so they carry the same crc32 + blake3_16
+// and the same recipe_id.
+//
+// ARK1S (recipe::ark_key) is NOT the same thing: it is its own field layout
+// (string-format version byte, no magic, crc32 only, no blake3) spelled in
+// Crockford base32 for typing by hand. Convert between them by decoding to a
+// Recipe and re-encoding; the recipe_id survives for every version because
+// string-format 1 carries the v5+ tails (rgb, soft_knee, quant_gamma). A
+// string-format 0 key for a v5+ recipe predates that and is rejected.
+
+const B64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+pub fn encode_base64(r: &Recipe) -> String {
+    base64url_encode(&encode(r))
+}
+
+/// Decodes and fully validates (magic, crc32, blake3). Trailing `=` is tolerated.
+pub fn decode_base64(s: &str) -> Result<Recipe> {
+    decode(&base64url_decode(s)?)
+}
+
+pub fn encode_hex(r: &Recipe) -> String {
+    hex_lower(&encode(r))
+}
+
+pub fn decode_hex(s: &str) -> Result<Recipe> {
+    decode(&hex_decode(s)?)
+}
+
+/// recipe_id of a base64 recipe without materializing a file.
+pub fn recipe_id_hex_from_base64(s: &str) -> Result<String> {
+    let bytes = base64url_decode(s)?;
+    decode(&bytes)?;
+    Ok(hex16(&recipe_id_16_from_encoded(&bytes)?))
+}
+
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 4).div_ceil(3));
+    for chunk in bytes.chunks(3) {
+        let mut v = 0u32;
+        for (k, &b) in chunk.iter().enumerate() {
+            v |= (b as u32) << (16 - 8 * k);
+        }
+        for k in 0..=chunk.len() {
+            out.push(B64URL[((v >> (18 - 6 * k)) & 0x3F) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64url_decode(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim().trim_end_matches('=');
+    if s.len() % 4 == 1 {
+        return Err(K8Error::RecipeFormat("base64: truncated input".into()));
+    }
+
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    let mut acc: u32 = 0;
+    let mut bits: u8 = 0;
+    for ch in s.bytes() {
+        let v = match ch {
+            b'A'..=b'Z' => ch - b'A',
+            b'a'..=b'z' => ch - b'a' + 26,
+            b'0'..=b'9' => ch - b'0' + 52,
+            b'-' => 62,
+            b'_' => 63,
+            _ => {
+                return Err(K8Error::RecipeFormat(format!(
+                    "base64: invalid char {:?}",
+                    ch as char
+                )))
+            }
+        };
+        acc = (acc << 6) | v as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1u32 << bits) - 1;
+        }
+    }
+    Ok(out)
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if !s.len().is_multiple_of(2) {
+        return Err(K8Error::RecipeFormat("hex: odd number of digits".into()));
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|p| {
+            std::str::from_utf8(p)
+                .ok()
+                .and_then(|d| u8::from_str_radix(d, 16).ok())
+                .ok_or_else(|| K8Error::RecipeFormat("hex: invalid digit".into()))
+        })
+        .collect()
+}
+
 // ---- flags packing ----
 //
 // Goal: add new knobs WITHOUT changing old recipe bytes when defaults are used.
@@ -306,8 +578,12 @@ fn unpack_flags(flags: u16) -> Result<(Alphabet, ResetMode, KeystreamMix, Payloa
 }
 
 fn need(bytes: &[u8], i: usize, n: usize) -> Result<()> {
+    need_for("recipe", bytes, i, n)
+}
+
+fn need_for(context: &'static str, bytes: &[u8], i: usize, n: usize) -> Result<()> {
     if bytes.len() < i + n {
-        return Err(K8Error::RecipeFormat("unexpected eof".into()));
+        return Err(K8Error::TruncatedInput { context, expected: i + n, got: bytes.len() });
     }
     Ok(())
 }
@@ -0,0 +1,69 @@
+// crates/k8dnz-core/src/recipe/keygen.rs
+//
+// "Make a recipe from a password": PBKDF2-HMAC-SHA256 -> 32 bytes -> recipe fields.
+// Orbit/lockstep/field-wave params stay at the defaults (they are validated cadence
+// choices); the key only moves seed, quant/clamp ranges and RGB params.
+//
+// Byte map (all LE):
+//   0..8    seed: u64
+//   8..12   quant.shift: i32 -> i64, folded into [-width/8, +width/8]
+//   12..16  quant range: lo:u16 hi:u16   (qmin = -(BASE + lo*STEP), qmax = BASE + hi*STEP)
+//   16..20  field clamp: lo:u16 hi:u16   (widens quant range outward by *CLAMP_STEP)
+//   20..32  rgb: backend, alt, base_a[3], base_c[3], g_step, p_scale, (2 reserved)
+//
+// The RGB params need the v5 layout (format::FORMAT_VERSION_RGB) to survive a save.
+
+use crate::recipe::defaults::default_recipe;
+use crate::recipe::format::FORMAT_VERSION_RGB;
+use crate::recipe::recipe::{Recipe, RgbRecipe};
+
+/// Salt used when the caller does not supply one.
+pub const DEFAULT_SALT: &[u8] = b"k8dnz/arkkey/generate/v1";
+pub const PBKDF2_ROUNDS: u32 = 10_000;
+
+const QUANT_BASE: i64 = 64_000_000;
+const QUANT_STEP: i64 = 2_048;
+const CLAMP_STEP: i64 = 1_024;
+
+/// PBKDF2-HMAC-SHA256(key, salt, PBKDF2_ROUNDS) -> 32 bytes.
+pub fn derive_key_bytes(key: &[u8], salt: &[u8]) -> [u8; 32] {
+    pbkdf2::pbkdf2_hmac_array::<sha2::Sha256, 32>(key, salt, PBKDF2_ROUNDS)
+}
+
+/// Deterministic recipe from a passphrase. Same (key, salt) => same recipe_id.
+pub fn recipe_from_key(key: &[u8], salt: &[u8]) -> Recipe {
+    recipe_from_key_bytes(&derive_key_bytes(key, salt))
+}
+
+/// Map 32 derived bytes onto recipe fields (see byte map above).
+pub fn recipe_from_key_bytes(k: &[u8; 32]) -> Recipe {
+    let u16_at = |i: usize| u16::from_le_bytes([k[i], k[i + 1]]) as i64;
+
+    let mut r = default_recipe();
+
+    r.seed = u64::from_le_bytes(k[0..8].try_into().unwrap());
+
+    r.quant.min = -(QUANT_BASE + u16_at(12) * QUANT_STEP);
+    r.quant.max = QUANT_BASE + u16_at(14) * QUANT_STEP;
+
+    // Keep the shift well inside the range so every bin can still fire.
+    let width = r.quant.max - r.quant.min;
+    let raw_shift = i32::from_le_bytes(k[8..12].try_into().unwrap()) as i64;
+    r.quant.shift = raw_shift % (width / 8 + 1);
+
+    // Clamp always contains the quant range.
+    r.field_clamp.min = r.quant.min - u16_at(16) * CLAMP_STEP;
+    r.field_clamp.max = r.quant.max + u16_at(18) * CLAMP_STEP;
+
+    r.rgb = RgbRecipe {
+        backend: k[20] & 1,
+        alt_mode: k[21] & 1,
+        base_a: [k[22], k[23], k[24]],
+        base_c: [k[25], k[26], k[27]],
+        g_step: 1 + (k[28] % 8) as i16,
+        p_scale: 1 + (k[29] % 8) as i16,
+    };
+    r.version = r.version.max(FORMAT_VERSION_RGB);
+
+    r
+}
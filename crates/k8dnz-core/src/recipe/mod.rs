@@ -1,5 +1,9 @@
 pub mod ark_key;
 pub mod checksum;
 pub mod defaults;
+pub mod diff;
 pub mod format;
+pub mod keygen;
+pub mod random;
 pub mod recipe;
+pub mod shamir;
@@ -0,0 +1,66 @@
+// crates/k8dnz-core/src/recipe/random.rs
+//
+// Reproducible "random but plausible" recipes for tests and benches. A splitmix64
+// stream seeded by the caller fills seed, quant and clamp; orbit/lockstep/field-wave
+// params stay at the defaults (like keygen). Integer-only, so a given seed gives the
+// same recipe_id on every platform.
+//
+//   quant.min   in [-100_000, -1_000]
+//   quant.max   in [1_000, 100_000]
+//   quant.shift in [quant.min, quant.max]
+//   field_clamp = quant range widened outward by 1..=10% of its width on each side
+
+use crate::recipe::defaults::default_recipe;
+use crate::recipe::recipe::Recipe;
+
+pub const RANDOM_QUANT_MIN: (i64, i64) = (-100_000, -1_000);
+pub const RANDOM_QUANT_MAX: (i64, i64) = (1_000, 100_000);
+
+/// Derived seeds tried before giving up on a clean `validate_deep`.
+pub const RANDOM_MAX_ATTEMPTS: u32 = 64;
+
+/// The first candidate (seed, then derived seeds) with no `validate_deep` warnings,
+/// and the number of attempts it took. After `RANDOM_MAX_ATTEMPTS` the last candidate
+/// is returned as is.
+pub fn random_recipe(seed: u64) -> (Recipe, u32) {
+    let mut s = seed;
+    let mut r = random_candidate(s);
+    for attempt in 1..RANDOM_MAX_ATTEMPTS {
+        if r.validate_deep().is_empty() {
+            return (r, attempt);
+        }
+        s = splitmix64(&mut s);
+        r = random_candidate(s);
+    }
+    (r, RANDOM_MAX_ATTEMPTS)
+}
+
+fn random_candidate(seed: u64) -> Recipe {
+    let mut state = seed;
+    let mut range = |lo: i64, hi: i64| -> i64 {
+        let span = (hi - lo) as u64 + 1;
+        lo + (splitmix64(&mut state) % span) as i64
+    };
+
+    let mut r = default_recipe();
+    r.quant.min = range(RANDOM_QUANT_MIN.0, RANDOM_QUANT_MIN.1);
+    r.quant.max = range(RANDOM_QUANT_MAX.0, RANDOM_QUANT_MAX.1);
+    r.quant.shift = range(r.quant.min, r.quant.max);
+
+    let width = r.quant.max - r.quant.min;
+    let pad_lo = width * range(1, 10) / 100;
+    let pad_hi = width * range(1, 10) / 100;
+    r.field_clamp.min = r.quant.min - pad_lo;
+    r.field_clamp.max = r.quant.max + pad_hi;
+
+    r.seed = splitmix64(&mut state);
+    r
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
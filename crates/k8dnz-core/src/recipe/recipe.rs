@@ -1,6 +1,8 @@
 // crates/k8dnz-core/src/recipe/recipe.rs
 
 use crate::fixed::turn32::Turn32;
+use crate::signal::quantize;
+use crate::signal::sample::FieldSample;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Alphabet {
@@ -8,6 +10,15 @@ pub enum Alphabet {
     N16,
 }
 
+impl Alphabet {
+    /// Quantization levels per channel.
+    pub const fn levels(self) -> u8 {
+        match self {
+            Alphabet::N16 => 16,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ResetMode {
     HoldAandC,
@@ -72,6 +83,9 @@ pub struct FieldClampParams {
     pub min: i64,
     /// Inclusive max for field clamp.
     pub max: i64,
+    /// If set, saturate smoothly within this many units of min/max instead of
+    /// hard clipping. Only encoded from format v6 on.
+    pub soft_knee: Option<u32>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -146,4 +160,100 @@ pub struct Recipe {
 
     /// RGB emission parameters (cone law / coupled-adder).
     pub rgb: RgbRecipe,
+
+    /// If set, quantize with `QuantizeGamma` instead of the linear quantizer.
+    /// Only encoded from format v7 on.
+    pub quant_gamma: Option<f64>,
+}
+
+impl Recipe {
+    /// Field units per quantization level: the quant range width divided by the
+    /// per-channel level count (16 for `N16`), rounded up so the levels cover the range.
+    pub fn quant_step_size(&self) -> i64 {
+        let width = self.quant.max.saturating_sub(self.quant.min);
+        let levels = self.alphabet.levels() as i64;
+        width.saturating_add(levels - 1) / levels
+    }
+
+    /// The per-channel level (`0..levels`) a clamped field value quantizes to, with the
+    /// engine's arithmetic: round-to-nearest over the shifted quant range (gamma-warped
+    /// when `quant_gamma` is set).
+    pub fn quant_level_for_field_value(&self, v: i64) -> u8 {
+        let (qmin, qmax) =
+            quantize::shifted_bounds(self.quant.min, self.quant.max, self.quant.shift);
+        quantize::quantize_with_gamma(
+            FieldSample(v),
+            qmin,
+            qmax,
+            self.alphabet.levels(),
+            self.quant_gamma.map(quantize::GammaCurve::new).as_ref(),
+        )
+    }
+
+    /// Non-fatal sanity warnings; see `validate::validate_deep`.
+    pub fn validate_deep(&self) -> Vec<crate::validate::ValidationWarning> {
+        crate::validate::validate_deep(self)
+    }
+
+    /// The `.k8r` bytes as URL-safe base64 without padding (see `format::encode_base64`).
+    pub fn to_base64(&self) -> String {
+        crate::recipe::format::encode_base64(self)
+    }
+
+    pub fn from_base64(s: &str) -> crate::error::Result<Recipe> {
+        crate::recipe::format::decode_base64(s)
+    }
+
+    /// The `.k8r` bytes as lowercase hex.
+    pub fn to_hex(&self) -> String {
+        crate::recipe::format::encode_hex(self)
+    }
+
+    pub fn from_hex(s: &str) -> crate::error::Result<Recipe> {
+        crate::recipe::format::decode_hex(s)
+    }
+
+    /// Linear blend of the numeric knobs (`quant.{min,max,shift}`,
+    /// `field_clamp.{min,max}`, `seed`); everything else is copied from `a`.
+    ///
+    /// `t=0.0` gives `a`, `t=1.0` gives `b`; integers round to nearest.
+    /// Fails if `t` is outside `[0,1]` or the blended quant range is not `min < max`.
+    pub fn interpolate(a: &Recipe, b: &Recipe, t: f64) -> crate::error::Result<Recipe> {
+        if !(0.0..=1.0).contains(&t) {
+            return Err(crate::error::K8Error::Validation(format!(
+                "interpolate: t must be in [0,1], got {t}"
+            )));
+        }
+
+        let mut r = a.clone();
+        r.seed = lerp_int(a.seed as i128, b.seed as i128, t) as u64;
+        r.quant.min = lerp_int(a.quant.min as i128, b.quant.min as i128, t) as i64;
+        r.quant.max = lerp_int(a.quant.max as i128, b.quant.max as i128, t) as i64;
+        r.quant.shift = lerp_int(a.quant.shift as i128, b.quant.shift as i128, t) as i64;
+        r.field_clamp.min =
+            lerp_int(a.field_clamp.min as i128, b.field_clamp.min as i128, t) as i64;
+        r.field_clamp.max =
+            lerp_int(a.field_clamp.max as i128, b.field_clamp.max as i128, t) as i64;
+
+        if r.quant.min >= r.quant.max {
+            return Err(crate::error::K8Error::Validation(format!(
+                "interpolate: quant.min >= quant.max after blend (min={}, max={})",
+                r.quant.min, r.quant.max
+            )));
+        }
+
+        Ok(r)
+    }
+}
+
+/// Round-to-nearest lerp in i128 so u64 seeds and i64 ranges never overflow.
+/// The endpoints are returned exactly (f64 can't hold every 64-bit delta).
+fn lerp_int(a: i128, b: i128, t: f64) -> i128 {
+    if t <= 0.0 {
+        a
+    } else if t >= 1.0 {
+        b
+    } else {
+        a + ((b - a) as f64 * t).round() as i128
+    }
 }
@@ -0,0 +1,213 @@
+// crates/k8dnz-core/src/recipe/shamir.rs
+//
+// Shamir secret sharing of recipe bytes, byte by byte over GF(2^8) (AES polynomial
+// x^8 + x^4 + x^3 + x + 1). Share x-coordinates are 1..=shares; any `threshold` of
+// them interpolate the secret at x = 0, fewer reveal nothing about it.
+//
+// .k8rs layout (all integers LE):
+//   "K8RS" | version:u8 | index:u8 | threshold:u8 | shares:u8
+//   split_id:[u8; 8] | len:u32 | share bytes | crc32:u32 (over everything before it)
+//
+// split_id is random per split so shares from different splits are never mixed.
+
+use crate::error::{K8Error, Result};
+use crate::recipe::checksum::crc32;
+
+const MAGIC: &[u8; 4] = b"K8RS";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Share {
+    /// x-coordinate, 1..=shares.
+    pub index: u8,
+    pub threshold: u8,
+    pub shares: u8,
+    pub split_id: [u8; 8],
+    /// One polynomial evaluation per secret byte.
+    pub bytes: Vec<u8>,
+}
+
+/// Splits `secret` into `shares` shares, any `threshold` of which recover it.
+/// `fill_random` must supply unpredictable bytes: it provides the split id and the
+/// `threshold - 1` random coefficients per secret byte.
+pub fn split_secret(
+    secret: &[u8],
+    shares: u8,
+    threshold: u8,
+    mut fill_random: impl FnMut(&mut [u8]),
+) -> Result<Vec<Share>> {
+    if threshold < 2 || threshold > shares {
+        return Err(K8Error::Validation(format!(
+            "shamir: need 2 <= threshold <= shares (threshold={threshold} shares={shares})"
+        )));
+    }
+
+    let mut split_id = [0u8; 8];
+    fill_random(&mut split_id);
+
+    let mut out: Vec<Share> = (1..=shares)
+        .map(|index| Share {
+            index,
+            threshold,
+            shares,
+            split_id,
+            bytes: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+
+    // coeffs[0] is the secret byte, the rest are random.
+    let mut coeffs = vec![0u8; threshold as usize];
+    for &s in secret {
+        coeffs[0] = s;
+        fill_random(&mut coeffs[1..]);
+        for share in out.iter_mut() {
+            share.bytes.push(eval_poly(&coeffs, share.index));
+        }
+    }
+    coeffs.fill(0);
+    Ok(out)
+}
+
+/// Recovers the secret from at least `threshold` shares of the same split.
+/// Extra shares beyond the threshold are ignored.
+pub fn combine_shares(shares: &[Share]) -> Result<Vec<u8>> {
+    let Some(first) = shares.first() else {
+        return Err(K8Error::Validation("shamir: no shares given".into()));
+    };
+    let k = first.threshold as usize;
+    if shares.len() < k {
+        return Err(K8Error::Validation(format!(
+            "shamir: need {k} shares, got {}",
+            shares.len()
+        )));
+    }
+
+    let used = &shares[..k];
+    for (i, s) in used.iter().enumerate() {
+        if s.split_id != first.split_id
+            || s.threshold != first.threshold
+            || s.bytes.len() != first.bytes.len()
+        {
+            return Err(K8Error::Validation(format!(
+                "shamir: share index {} does not belong to the same split as share index {}",
+                s.index, first.index
+            )));
+        }
+        if s.index == 0 || used[..i].iter().any(|o| o.index == s.index) {
+            return Err(K8Error::Validation(format!(
+                "shamir: invalid or duplicate share index {}",
+                s.index
+            )));
+        }
+    }
+
+    // Lagrange basis at x = 0: l_i = prod_{j != i} x_j / (x_j - x_i); minus is xor.
+    let basis: Vec<u8> = used
+        .iter()
+        .map(|si| {
+            used.iter()
+                .filter(|sj| sj.index != si.index)
+                .fold(1u8, |acc, sj| {
+                    gf_mul(acc, gf_div(sj.index, sj.index ^ si.index))
+                })
+        })
+        .collect();
+
+    Ok((0..first.bytes.len())
+        .map(|b| {
+            used.iter()
+                .zip(basis.iter())
+                .fold(0u8, |acc, (s, &l)| acc ^ gf_mul(s.bytes[b], l))
+        })
+        .collect())
+}
+
+pub fn encode_share(s: &Share) -> Vec<u8> {
+    let mut b = Vec::with_capacity(HEADER_LEN + s.bytes.len() + 4);
+    b.extend_from_slice(MAGIC);
+    b.push(VERSION);
+    b.push(s.index);
+    b.push(s.threshold);
+    b.push(s.shares);
+    b.extend_from_slice(&s.split_id);
+    b.extend_from_slice(&(s.bytes.len() as u32).to_le_bytes());
+    b.extend_from_slice(&s.bytes);
+    let c = crc32(&b);
+    b.extend_from_slice(&c.to_le_bytes());
+    b
+}
+
+/// Decodes a .k8rs share; a corrupted share fails the crc32 check here, before
+/// it can poison a reconstruction.
+pub fn decode_share(bytes: &[u8]) -> Result<Share> {
+    if bytes.len() < HEADER_LEN + 4 {
+        return Err(K8Error::TruncatedInput { context: "share", expected: HEADER_LEN + 4, got: bytes.len() });
+    }
+    if &bytes[0..4] != MAGIC {
+        let got = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        return Err(K8Error::BadMagic { context: "share", expected: *MAGIC, got });
+    }
+    if bytes[4] != VERSION {
+        return Err(K8Error::RecipeFormat(format!(
+            "share: unsupported version {}",
+            bytes[4]
+        )));
+    }
+    let len = u32::from_le_bytes(bytes[16..20].try_into().unwrap()) as usize;
+    if bytes.len() != HEADER_LEN + len + 4 {
+        return Err(K8Error::RecipeFormat(format!(
+            "share: length mismatch (header says {} bytes, file has {})",
+            len,
+            bytes.len().saturating_sub(HEADER_LEN + 4)
+        )));
+    }
+    let body = &bytes[..HEADER_LEN + len];
+    let crc_expected = u32::from_le_bytes(bytes[HEADER_LEN + len..].try_into().unwrap());
+    if crc32(body) != crc_expected {
+        return Err(K8Error::RecipeFormat("share: crc32 mismatch".into()));
+    }
+    let (index, threshold, shares) = (bytes[5], bytes[6], bytes[7]);
+    if index == 0 || index > shares || threshold < 2 || threshold > shares {
+        return Err(K8Error::InvalidShare { index, threshold, shares });
+    }
+    Ok(Share {
+        index,
+        threshold,
+        shares,
+        split_id: bytes[8..16].try_into().unwrap(),
+        bytes: bytes[HEADER_LEN..HEADER_LEN + len].to_vec(),
+    })
+}
+
+/// Horner evaluation of `coeffs[0] + coeffs[1] x + ...` at `x`.
+fn eval_poly(coeffs: &[u8], x: u8) -> u8 {
+    coeffs.iter().rev().fold(0u8, |acc, &c| gf_mul(acc, x) ^ c)
+}
+
+/// Carry-less multiply reduced by 0x11B; no tables, no data-dependent branches.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut p = 0u8;
+    for _ in 0..8 {
+        p ^= a & (b & 1).wrapping_neg();
+        let hi = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (hi & 0x1B);
+        b >>= 1;
+    }
+    p
+}
+
+/// a / b for b != 0, via b^254 = b^-1.
+fn gf_div(a: u8, b: u8) -> u8 {
+    let mut inv = 1u8;
+    let mut base = b;
+    let mut e = 254u8;
+    while e > 0 {
+        if e & 1 == 1 {
+            inv = gf_mul(inv, base);
+        }
+        base = gf_mul(base, base);
+        e >>= 1;
+    }
+    gf_mul(a, inv)
+}
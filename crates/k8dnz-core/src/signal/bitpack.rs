@@ -1,9 +1,14 @@
 // crates/k8dnz-core/src/signal/bitpack.rs
 
+use std::io::{self, Read, Write};
+
 use crate::error::{K8Error, Result};
 
 const MAX_BITS: u8 = 8;
 
+/// Internal buffer size of the streaming pack/unpack variants.
+const STREAM_CHUNK: usize = 4096;
+
 /// Pack `symbols` where each symbol occupies exactly `bits_per_symbol` bits.
 ///
 /// Bit order is MSB-first within the packed byte stream:
@@ -65,13 +70,11 @@ pub fn unpack_symbols(bits_per_symbol: u8, packed: &[u8], symbol_count: usize) -
     let need_bytes: usize = (total_bits + 7) / 8;
 
     if packed.len() < need_bytes {
-        return Err(K8Error::Validation(format!(
-            "unpack_symbols short: need {} bytes for {} symbols ({} bits/sym), got {}",
-            need_bytes,
-            symbol_count,
-            bits_per_symbol,
-            packed.len()
-        )));
+        return Err(K8Error::TruncatedInput {
+            context: "unpack_symbols",
+            expected: need_bytes,
+            got: packed.len(),
+        });
     }
 
     let mut out = Vec::with_capacity(symbol_count);
@@ -92,6 +95,95 @@ pub fn unpack_symbols(bits_per_symbol: u8, packed: &[u8], symbol_count: usize) -
     Ok(out)
 }
 
+/// Streaming `pack_symbols`: writes the same bytes to `w`, flushing in 4 KB chunks
+/// instead of building the whole packed buffer first.
+///
+/// Bad `bits_per_symbol` or an out-of-range symbol fail with `InvalidInput`; in the
+/// latter case the chunks before the offending symbol may already have been written.
+pub fn pack_symbols_to_writer<W: Write + ?Sized>(
+    bits_per_symbol: u8,
+    symbols: &[u8],
+    w: &mut W,
+) -> io::Result<()> {
+    validate_bits(bits_per_symbol).map_err(invalid_input)?;
+    let mask: u8 = ((1u16 << bits_per_symbol) - 1) as u8;
+
+    let mut buf = [0u8; STREAM_CHUNK];
+    let mut fill: usize = 0;
+    // Pending bits, right-aligned; never more than 7 + 8 of them.
+    let mut acc: u32 = 0;
+    let mut acc_bits: u32 = 0;
+
+    for &sym in symbols {
+        if sym & !mask != 0 {
+            return Err(invalid_input(K8Error::Validation(format!(
+                "symbol out of range: sym={} bits_per_symbol={} mask=0x{:02x}",
+                sym, bits_per_symbol, mask
+            ))));
+        }
+        acc = (acc << bits_per_symbol) | sym as u32;
+        acc_bits += bits_per_symbol as u32;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            buf[fill] = (acc >> acc_bits) as u8;
+            acc &= (1u32 << acc_bits) - 1;
+            fill += 1;
+            if fill == STREAM_CHUNK {
+                w.write_all(&buf)?;
+                fill = 0;
+            }
+        }
+    }
+
+    if acc_bits > 0 {
+        buf[fill] = (acc << (8 - acc_bits)) as u8;
+        fill += 1;
+    }
+    w.write_all(&buf[..fill])
+}
+
+/// Streaming `unpack_symbols`: reads exactly the packed bytes for `symbol_count`
+/// symbols from `r`, 4 KB at a time. A short stream fails with `UnexpectedEof`.
+pub fn unpack_symbols_from_reader<R: Read + ?Sized>(
+    bits_per_symbol: u8,
+    r: &mut R,
+    symbol_count: usize,
+) -> io::Result<Vec<u8>> {
+    validate_bits(bits_per_symbol).map_err(invalid_input)?;
+    let mask: u32 = (1u32 << bits_per_symbol) - 1;
+
+    let total_bits: usize = symbol_count
+        .checked_mul(bits_per_symbol as usize)
+        .ok_or_else(|| invalid_input(K8Error::Validation("unpack_symbols overflow".into())))?;
+    let mut remaining_bytes: usize = total_bits.div_ceil(8);
+
+    let mut out = Vec::with_capacity(symbol_count);
+    let mut buf = [0u8; STREAM_CHUNK];
+    let mut acc: u32 = 0;
+    let mut acc_bits: u32 = 0;
+
+    while remaining_bytes > 0 {
+        let n = remaining_bytes.min(STREAM_CHUNK);
+        r.read_exact(&mut buf[..n])?;
+        remaining_bytes -= n;
+        for &byte in &buf[..n] {
+            acc = (acc << 8) | byte as u32;
+            acc_bits += 8;
+            while acc_bits >= bits_per_symbol as u32 && out.len() < symbol_count {
+                acc_bits -= bits_per_symbol as u32;
+                out.push(((acc >> acc_bits) & mask) as u8);
+            }
+            acc &= (1u32 << acc_bits) - 1;
+        }
+    }
+
+    Ok(out)
+}
+
+fn invalid_input(e: K8Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, e.to_string())
+}
+
 #[inline]
 fn validate_bits(bits_per_symbol: u8) -> Result<()> {
     if bits_per_symbol == 0 || bits_per_symbol > MAX_BITS {
@@ -102,3 +194,99 @@ fn validate_bits(bits_per_symbol: u8) -> Result<()> {
     }
     Ok(())
 }
+
+const LO7: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+
+fn word(chunk: &[u8]) -> u64 {
+    u64::from_le_bytes(chunk.try_into().expect("8-byte chunk"))
+}
+
+/// `sum_i popcount(a[i] ^ b[i])` over the common prefix of `a` and `b`.
+///
+/// Works on 8-byte words; on x86_64 the word loop is compiled a second time with
+/// the `popcnt` feature and picked at run time when the CPU has it.
+pub fn hamming_distance_u8(a: &[u8], b: &[u8]) -> u64 {
+    let n = a.len().min(b.len());
+    let (a, b) = (&a[..n], &b[..n]);
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("popcnt") {
+            // SAFETY: the CPU supports popcnt (checked just above).
+            return unsafe { hamming_popcnt(a, b) };
+        }
+    }
+    hamming_words(a, b)
+}
+
+#[inline(always)]
+fn hamming_words(a: &[u8], b: &[u8]) -> u64 {
+    let mut acc = 0u64;
+    let (wa, wb) = (a.chunks_exact(8), b.chunks_exact(8));
+    let (ta, tb) = (wa.remainder(), wb.remainder());
+    for (x, y) in wa.zip(wb) {
+        acc += (word(x) ^ word(y)).count_ones() as u64;
+    }
+    for (x, y) in ta.iter().zip(tb) {
+        acc += (x ^ y).count_ones() as u64;
+    }
+    acc
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "popcnt")]
+unsafe fn hamming_popcnt(a: &[u8], b: &[u8]) -> u64 {
+    hamming_words(a, b)
+}
+
+/// Number of positions `i` with `a[i] == b[i]` over the common prefix.
+///
+/// x86_64 compares 16 bytes at a time with SSE2 (part of the x86_64 baseline);
+/// other targets use an exact zero-byte test on 8-byte words.
+pub fn symbol_match_count(a: &[u8], b: &[u8]) -> u64 {
+    let n = a.len().min(b.len());
+    let (a, b) = (&a[..n], &b[..n]);
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        let split = n - n % 16;
+        // SAFETY: SSE2 is always available on x86_64.
+        let head = unsafe { match_count_sse2(&a[..split], &b[..split]) };
+        head + match_count_words(&a[split..], &b[split..])
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        match_count_words(a, b)
+    }
+}
+
+fn match_count_words(a: &[u8], b: &[u8]) -> u64 {
+    let mut acc = 0u64;
+    let (wa, wb) = (a.chunks_exact(8), b.chunks_exact(8));
+    let (ta, tb) = (wa.remainder(), wb.remainder());
+    for (x, y) in wa.zip(wb) {
+        let d = word(x) ^ word(y);
+        // High bit of each byte set iff that byte of `d` is zero.
+        let zero = !(((d & LO7) + LO7) | d | LO7);
+        acc += zero.count_ones() as u64;
+    }
+    for (x, y) in ta.iter().zip(tb) {
+        acc += (x == y) as u64;
+    }
+    acc
+}
+
+/// `a.len() == b.len()`, a multiple of 16.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn match_count_sse2(a: &[u8], b: &[u8]) -> u64 {
+    use std::arch::x86_64::{__m128i, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+
+    let mut acc = 0u64;
+    for (x, y) in a.chunks_exact(16).zip(b.chunks_exact(16)) {
+        let vx = _mm_loadu_si128(x.as_ptr() as *const __m128i);
+        let vy = _mm_loadu_si128(y.as_ptr() as *const __m128i);
+        acc += (_mm_movemask_epi8(_mm_cmpeq_epi8(vx, vy)) as u32).count_ones() as u64;
+    }
+    acc
+}
@@ -0,0 +1,288 @@
+// crates/k8dnz-core/src/signal/fit.rs
+//
+// Library form of `timemap fit-xor` (pair mode): slide a `target.len()` window over the
+// engine's packed-byte stream, score every `scan_step`-th start and keep the best one
+// as a contiguous stride timemap plus its residual.
+//
+// Only the byte map modes that are pure functions of (seed, pos, raw) live here; the
+// text alphabets, conditioning tags and progressive refinement stay in the CLI, which
+// calls back into `map_byte` / `make_mapped_residual_byte` for the modes shared with it.
+
+use crate::dynamics::engine::Engine;
+use crate::error::{K8Error, Result};
+use crate::signal::timing_map::TimingMap;
+
+/// Default rounds for `MapMode::Feistel` (also the `--feistel-rounds` default).
+pub const FEISTEL_DEFAULT_ROUNDS: u8 = 4;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum MapMode {
+    #[default]
+    None,
+    Splitmix64,
+    Ascii7,
+    Ascii7Splitmix,
+    Feistel,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ResidualMode {
+    #[default]
+    Xor,
+    Sub,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum FitObjective {
+    /// Fewest non-zero residual bytes.
+    Matches,
+    /// Smallest zstd-compressed residual.
+    #[default]
+    Zstd,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FitOptions {
+    pub map_mode: MapMode,
+    pub map_seed: u64,
+    pub residual_mode: ResidualMode,
+    pub scan_step: usize,
+    pub objective: FitObjective,
+    pub zstd_level: i32,
+    /// Rounds for `MapMode::Feistel`; ignored by the other map modes.
+    pub feistel_rounds: u8,
+    /// Emissions to generate past the engine's current position; bounds the scan.
+    pub search_emissions: u64,
+}
+
+impl Default for FitOptions {
+    fn default() -> Self {
+        FitOptions {
+            map_mode: MapMode::None,
+            map_seed: 0,
+            residual_mode: ResidualMode::Xor,
+            scan_step: 1,
+            objective: FitObjective::Zstd,
+            zstd_level: 3,
+            feistel_rounds: FEISTEL_DEFAULT_ROUNDS,
+            search_emissions: 2_000_000,
+        }
+    }
+}
+
+impl TimingMap {
+    /// Finds the best contiguous window for `target` in the engine's packed-byte
+    /// stream and returns it as a stride-1 timemap with the matching residual.
+    ///
+    /// Positions are absolute emission indices: the scan starts wherever `engine`
+    /// currently is, and the engine is left after the last generated emission. Ties go
+    /// to the earliest window; a perfect fit under `FitObjective::Matches` ends the scan.
+    pub fn stride_from_fit(
+        target: &[u8],
+        engine: &mut Engine,
+        max_ticks: u64,
+        opts: &FitOptions,
+    ) -> Result<(TimingMap, Vec<u8>)> {
+        if target.is_empty() {
+            return Err(K8Error::Validation(
+                "stride_from_fit: target is empty".into(),
+            ));
+        }
+        if opts.scan_step == 0 {
+            return Err(K8Error::Validation(
+                "stride_from_fit: scan_step must be >= 1".into(),
+            ));
+        }
+
+        let base_pos = engine.stats.emissions;
+        let mut stream: Vec<u8> = Vec::with_capacity(opts.search_emissions.min(200_000) as usize);
+        let produced =
+            engine.run_with_emission_callback(opts.search_emissions, max_ticks, &mut |_, tok| {
+                stream.push(tok.pack_byte())
+            });
+
+        let n = target.len();
+        if produced < n as u64 {
+            return Err(K8Error::InsufficientEmissions {
+                context: "stride_from_fit",
+                needed: n as u64,
+                produced,
+                max_ticks,
+                ticks: engine.stats.ticks,
+            });
+        }
+
+        let mut scratch = vec![0u8; n];
+        let mut best: Option<(usize, usize)> = None;
+        for s in (0..=stream.len() - n).step_by(opts.scan_step) {
+            let score = score_window(
+                target,
+                &stream[s..s + n],
+                base_pos + s as u64,
+                opts,
+                &mut scratch,
+            );
+            if best.is_none_or(|(b, _)| score < b) {
+                best = Some((score, s));
+                if score == 0 {
+                    break;
+                }
+            }
+        }
+        let (_, best_start) = best.expect("at least one window scanned");
+
+        let start = base_pos + best_start as u64;
+        score_window(
+            target,
+            &stream[best_start..best_start + n],
+            start,
+            opts,
+            &mut scratch,
+        );
+        Ok((TimingMap::stride(n as u64, start, 1)?, scratch))
+    }
+}
+
+/// Fills `resid` for the window whose first byte sits at `start` and returns its score.
+fn score_window(
+    target: &[u8],
+    window: &[u8],
+    start: u64,
+    opts: &FitOptions,
+    resid: &mut [u8],
+) -> usize {
+    let mut mismatches = 0usize;
+    for (i, (&raw, &plain)) in window.iter().zip(target).enumerate() {
+        let pos = start + i as u64;
+        let model = map_byte(opts.map_mode, opts.map_seed, pos, raw, opts.feistel_rounds);
+        resid[i] = make_mapped_residual_byte(
+            opts.residual_mode,
+            opts.map_mode,
+            opts.map_seed,
+            pos,
+            opts.feistel_rounds,
+            model,
+            plain,
+        );
+        if resid[i] != 0 {
+            mismatches += 1;
+        }
+    }
+    match opts.objective {
+        FitObjective::Matches => mismatches,
+        FitObjective::Zstd => zstd::encode_all(&resid[..], opts.zstd_level)
+            .map(|v| v.len())
+            .unwrap_or(usize::MAX),
+    }
+}
+
+/// `feistel_rounds` is only read by `MapMode::Feistel`.
+pub fn map_byte(mode: MapMode, seed: u64, pos: u64, raw: u8, feistel_rounds: u8) -> u8 {
+    match mode {
+        MapMode::None => raw,
+        MapMode::Splitmix64 => raw ^ splitmix64(seed ^ pos) as u8,
+        MapMode::Ascii7 => ascii7(raw),
+        MapMode::Ascii7Splitmix => ascii7(raw ^ splitmix64(seed ^ pos) as u8),
+        MapMode::Feistel => feistel_permute(seed, pos, raw, feistel_rounds),
+    }
+}
+
+fn feistel_round_f(seed: u64, pos: u64, round: u8, x: u8) -> u8 {
+    let key = splitmix64(seed ^ pos ^ round as u64);
+    (splitmix64(key ^ x as u64) as u8) & 0x0F
+}
+
+/// Nibble Feistel network on one byte: each round maps (hi, lo) -> (lo, hi ^ f(lo))
+/// with round key splitmix64(seed ^ pos ^ round). A bijection on 0..=255 for every
+/// (seed, pos), so unlike the XOR-with-hash modes no raw byte is mapped to a fixed
+/// keystream value independent of its input.
+pub fn feistel_permute(seed: u64, pos: u64, raw: u8, rounds: u8) -> u8 {
+    let (mut hi, mut lo) = (raw >> 4, raw & 0x0F);
+    for r in 0..rounds {
+        (hi, lo) = (lo, hi ^ feistel_round_f(seed, pos, r, lo));
+    }
+    (hi << 4) | lo
+}
+
+/// Inverse of `feistel_permute` (same seed, pos and rounds).
+pub fn feistel_unpermute(seed: u64, pos: u64, mapped: u8, rounds: u8) -> u8 {
+    let (mut hi, mut lo) = (mapped >> 4, mapped & 0x0F);
+    for r in (0..rounds).rev() {
+        (hi, lo) = (lo ^ feistel_round_f(seed, pos, r, hi), hi);
+    }
+    (hi << 4) | lo
+}
+
+/// Residual under `map_mode`. Feistel mode takes it between the permuted model and
+/// the permuted plaintext (same seed/pos key), so a hit still leaves 0 but a miss no
+/// longer exposes `model ^ plain`. Other modes are plain `make_residual_byte`.
+pub fn make_mapped_residual_byte(
+    mode: ResidualMode,
+    map_mode: MapMode,
+    seed: u64,
+    pos: u64,
+    feistel_rounds: u8,
+    model: u8,
+    plain: u8,
+) -> u8 {
+    match map_mode {
+        MapMode::Feistel => make_residual_byte(
+            mode,
+            feistel_permute(seed, pos, model, feistel_rounds),
+            feistel_permute(seed, pos, plain, feistel_rounds),
+        ),
+        _ => make_residual_byte(mode, model, plain),
+    }
+}
+
+/// Inverse of `make_mapped_residual_byte`; Feistel mode finishes with `feistel_unpermute`.
+pub fn apply_mapped_residual_byte(
+    mode: ResidualMode,
+    map_mode: MapMode,
+    seed: u64,
+    pos: u64,
+    feistel_rounds: u8,
+    model: u8,
+    resid: u8,
+) -> u8 {
+    match map_mode {
+        MapMode::Feistel => {
+            let model = feistel_permute(seed, pos, model, feistel_rounds);
+            let mapped = apply_residual_byte(mode, model, resid);
+            feistel_unpermute(seed, pos, mapped, feistel_rounds)
+        }
+        _ => apply_residual_byte(mode, model, resid),
+    }
+}
+
+pub fn make_residual_byte(mode: ResidualMode, model: u8, plain: u8) -> u8 {
+    match mode {
+        ResidualMode::Xor => model ^ plain,
+        ResidualMode::Sub => plain.wrapping_sub(model),
+    }
+}
+
+pub fn apply_residual_byte(mode: ResidualMode, model: u8, resid: u8) -> u8 {
+    match mode {
+        ResidualMode::Xor => model ^ resid,
+        ResidualMode::Sub => model.wrapping_add(resid),
+    }
+}
+
+/// Folds a byte into printable ASCII (0x20..=0x7E).
+pub fn ascii7(b: u8) -> u8 {
+    let x = b & 0x7F;
+    if (0x20..=0x7E).contains(&x) {
+        x
+    } else {
+        0x20u8 + (x % 95)
+    }
+}
+
+pub fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
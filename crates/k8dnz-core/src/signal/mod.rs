@@ -7,3 +7,4 @@ pub mod timing_map;
 pub mod token;
 
 pub mod bitpack;
+pub mod fit;
@@ -8,7 +8,6 @@ use crate::signal::sample::FieldSample;
 /// - Uses integer rounding to reduce systematic floor bias
 pub fn quantize(sample: FieldSample, min: i64, max: i64, n: u8) -> u8 {
     debug_assert!(n >= 2);
-    let n_i = n as i64;
 
     // Be defensive: if caller ever passes inverted bounds, normalize.
     let (min, max) = if min <= max { (min, max) } else { (max, min) };
@@ -24,6 +23,13 @@ pub fn quantize(sample: FieldSample, min: i64, max: i64, n: u8) -> u8 {
     let range: i64 = max - min;
     let shifted: i64 = s - min; // 0..=range
 
+    linear_bin(shifted, range, n)
+}
+
+/// Bin for `shifted` in `0..=range` (range > 0), shared by `quantize` and `QuantizeGamma`.
+fn linear_bin(shifted: i64, range: i64, n: u8) -> u8 {
+    let n_i = n as i64;
+
     // Ensure inclusive top end maps to the final bin.
     if shifted >= range {
         return (n - 1) as u8;
@@ -45,6 +51,203 @@ pub fn quantize(sample: FieldSample, min: i64, max: i64, n: u8) -> u8 {
     bin as u8
 }
 
+/// Power-law quantization over `min..=max`: the sample's position in the range is
+/// raised to `gamma` before the linear binning of `quantize`, i.e.
+/// `bin = round(n * ((x - min) / (max - min))^gamma)`.
+///
+/// `gamma < 1` spends more bins near `min` (0.5 is a square-root curve), which suits
+/// power-law shaped fields where most samples are small; `gamma > 1` favors the top.
+/// The curve is a `GammaCurve` (integer-only), so the bins do not depend on the
+/// platform's libm; `gamma = 1` reproduces `quantize` exactly.
+#[derive(Clone, Debug)]
+pub struct QuantizeGamma {
+    pub curve: GammaCurve,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl QuantizeGamma {
+    pub fn new(gamma: f64, min: i64, max: i64) -> Self {
+        Self {
+            curve: GammaCurve::new(gamma),
+            min,
+            max,
+        }
+    }
+
+    pub fn quantize(&self, sample: FieldSample, n: u8) -> u8 {
+        quantize_curve(sample, self.min, self.max, n, &self.curve)
+    }
+}
+
+fn quantize_curve(sample: FieldSample, min: i64, max: i64, n: u8, curve: &GammaCurve) -> u8 {
+    debug_assert!(n >= 2);
+
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    if min == max {
+        return 0;
+    }
+
+    let s = sample.0.clamp(min, max);
+    let range: i64 = max - min;
+    let shifted: i64 = s - min;
+
+    // Warp in field units so the integer rounding below stays the linear one.
+    linear_bin(curve.warp(shifted, range), range, n)
+}
+
+/// log2 of the `GammaCurve` table size.
+const GAMMA_LUT_BITS: u32 = 12;
+/// Fractional bits of the table entries and of the log2/exp2 helpers.
+const GAMMA_Q: u32 = 32;
+/// Fractional bits of the internal log2/exp2 mantissas.
+const MANT_Q: u32 = 62;
+
+/// `t^gamma` on `[0, 1]` in fixed point, for `QuantizeGamma`.
+///
+/// `gamma` is rounded to Q16, and the table (`2^12 + 1` Q32 entries, linearly
+/// interpolated) is built with integer log2/exp2 only, so every target computes
+/// the same bins. A Q16 gamma of exactly 1 skips the table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GammaCurve {
+    gamma_q16: u32,
+    lut: Vec<u64>,
+}
+
+impl GammaCurve {
+    pub fn new(gamma: f64) -> Self {
+        debug_assert!(gamma.is_finite() && gamma > 0.0);
+
+        // Scaling by 2^16 is exact; the rounding is the only step that
+        // touches floating point.
+        let gamma_q16 = (gamma * 65536.0).round().clamp(1.0, u32::MAX as f64) as u32;
+        if gamma_q16 == 1 << 16 {
+            return Self {
+                gamma_q16,
+                lut: Vec::new(),
+            };
+        }
+
+        let size = 1u64 << GAMMA_LUT_BITS;
+        let mut lut = Vec::with_capacity(size as usize + 1);
+        lut.push(0);
+        for k in 1..=size {
+            // log2(k / size) <= 0, then scale by gamma (Q32 * Q16 -> Q32).
+            let l = log2_q32(k) - ((GAMMA_LUT_BITS as i64) << GAMMA_Q);
+            let y = ((l as i128 * gamma_q16 as i128) >> 16) as i64;
+            lut.push(exp2_q32(y));
+        }
+        Self { gamma_q16, lut }
+    }
+
+    /// The gamma actually applied (Q16).
+    pub fn gamma_q16(&self) -> u32 {
+        self.gamma_q16
+    }
+
+    /// `range * (shifted / range)^gamma`, rounded, for `shifted` in `0..=range` (range > 0).
+    pub fn warp(&self, shifted: i64, range: i64) -> i64 {
+        if self.lut.is_empty() {
+            return shifted;
+        }
+        let (x, r) = (shifted as u128, range as u128);
+
+        // Position in the table: index + remainder (in units of 1/range).
+        let pos = x << GAMMA_LUT_BITS;
+        let (k, rem) = ((pos / r) as usize, pos % r);
+        let lo = self.lut[k] as u128;
+        let y = if k + 1 < self.lut.len() {
+            let hi = self.lut[k + 1] as u128;
+            lo + (hi - lo) * rem / r
+        } else {
+            lo
+        };
+
+        let warped = (y * r + (1u128 << (GAMMA_Q - 1))) >> GAMMA_Q;
+        (warped as i64).clamp(0, range)
+    }
+}
+
+/// `2^(2^-j)` in Q62 for `j = 1..=32`, by repeated integer square roots of 2.
+fn exp2_frac_roots() -> &'static [u128; 32] {
+    static ROOTS: std::sync::OnceLock<[u128; 32]> = std::sync::OnceLock::new();
+    ROOTS.get_or_init(|| {
+        let mut roots = [0u128; 32];
+        let mut c: u128 = 2 << MANT_Q;
+        for r in roots.iter_mut() {
+            c = isqrt_u128(c << MANT_Q);
+            *r = c;
+        }
+        roots
+    })
+}
+
+fn isqrt_u128(v: u128) -> u128 {
+    if v < 2 {
+        return v;
+    }
+    // Newton from an over-estimate; decreases monotonically to floor(sqrt(v)).
+    let mut x = 1u128 << (128 - v.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + v / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// log2(k) in Q32 for k >= 1 (bit-by-bit squaring of the mantissa).
+fn log2_q32(k: u64) -> i64 {
+    let ip = 63 - k.leading_zeros();
+    let mut m = (k as u128) << (MANT_Q - ip);
+    let mut frac: i64 = 0;
+    for bit in (0..GAMMA_Q).rev() {
+        m = (m * m) >> MANT_Q;
+        if m >= 2 << MANT_Q {
+            m >>= 1;
+            frac |= 1 << bit;
+        }
+    }
+    ((ip as i64) << GAMMA_Q) | frac
+}
+
+/// 2^y in Q32 for y <= 0 (Q32), rounded.
+fn exp2_q32(y: i64) -> u64 {
+    debug_assert!(y <= 0);
+    let ip = y >> GAMMA_Q;
+    let frac = (y & ((1i64 << GAMMA_Q) - 1)) as u64;
+
+    let roots = exp2_frac_roots();
+    let mut m: u128 = 1 << MANT_Q;
+    for (j, root) in roots.iter().enumerate() {
+        if frac & (1 << (GAMMA_Q - 1 - j as u32)) != 0 {
+            m = (m * root) >> MANT_Q;
+        }
+    }
+
+    let shift = (MANT_Q - GAMMA_Q) as i64 - ip;
+    if shift >= 128 {
+        return 0;
+    }
+    ((m + (1u128 << (shift - 1))) >> shift) as u64
+}
+
+/// `quantize`, or the `GammaCurve` warp when one is given (see `QuantizeGamma`).
+#[inline]
+pub fn quantize_with_gamma(
+    sample: FieldSample,
+    min: i64,
+    max: i64,
+    n: u8,
+    gamma: Option<&GammaCurve>,
+) -> u8 {
+    match gamma {
+        Some(curve) => quantize_curve(sample, min, max, n, curve),
+        None => quantize(sample, min, max, n),
+    }
+}
+
 /// Apply a shift to both bounds (min/max) using saturating arithmetic.
 /// This preserves the range width and only moves bin boundaries.
 ///
@@ -56,3 +259,136 @@ pub fn quantize(sample: FieldSample, min: i64, max: i64, n: u8) -> u8 {
 pub fn shifted_bounds(min: i64, max: i64, shift: i64) -> (i64, i64) {
     (min.saturating_add(shift), max.saturating_add(shift))
 }
+
+/// Online statistics over packed emission bytes, updated one byte at a time
+/// so long runs never need to hold the token stream.
+///
+/// Mean/variance use Welford's update; the histogram is the exact byte count.
+#[derive(Clone, Debug)]
+pub struct QuantStats {
+    hist: [u64; 256],
+    count: u64,
+    min: u8,
+    max: u8,
+    mean: f64,
+    m2: f64,
+    distinct: u32,
+}
+
+impl Default for QuantStats {
+    fn default() -> Self {
+        Self {
+            hist: [0u64; 256],
+            count: 0,
+            min: u8::MAX,
+            max: 0,
+            mean: 0.0,
+            m2: 0.0,
+            distinct: 0,
+        }
+    }
+}
+
+impl QuantStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn update(&mut self, pack_byte: u8) {
+        let slot = &mut self.hist[pack_byte as usize];
+        if *slot == 0 {
+            self.distinct += 1;
+        }
+        *slot += 1;
+
+        self.count += 1;
+        self.min = self.min.min(pack_byte);
+        self.max = self.max.max(pack_byte);
+
+        let x = pack_byte as f64;
+        let d = x - self.mean;
+        self.mean += d / self.count as f64;
+        self.m2 += d * (x - self.mean);
+    }
+
+    pub fn histogram(&self) -> &[u64; 256] {
+        &self.hist
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// None until the first update.
+    pub fn min(&self) -> Option<u8> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// None until the first update.
+    pub fn max(&self) -> Option<u8> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Population variance (divides by n).
+    pub fn variance(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.m2 / self.count as f64
+        }
+    }
+
+    pub fn distinct(&self) -> usize {
+        self.distinct as usize
+    }
+
+    /// Shannon entropy of the byte histogram, bits/byte.
+    pub fn entropy_bits(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let n = self.count as f64;
+        self.hist
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let p = c as f64 / n;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Per-channel nibble histograms (`a` = high nibble, `b` = low nibble).
+    pub fn nibble_histograms(&self) -> ([u64; 16], [u64; 16]) {
+        let mut ha = [0u64; 16];
+        let mut hb = [0u64; 16];
+        for (byte, &c) in self.hist.iter().enumerate() {
+            ha[byte >> 4] += c;
+            hb[byte & 0x0F] += c;
+        }
+        (ha, hb)
+    }
+
+    /// The `quant_stats` line of the tune report: min/max are null before the first
+    /// update, and the histogram lists all 256 bins.
+    pub fn to_json(&self) -> String {
+        let hist: Vec<String> = self.hist.iter().map(|c| c.to_string()).collect();
+        let opt = |v: Option<u8>| v.map_or("null".to_string(), |x| x.to_string());
+        format!(
+            "{{\"count\":{},\"min\":{},\"max\":{},\"mean\":{},\"variance\":{},\"distinct\":{},\"entropy_bits\":{},\"histogram\":[{}]}}",
+            self.count,
+            opt(self.min()),
+            opt(self.max()),
+            self.mean,
+            self.variance(),
+            self.distinct,
+            self.entropy_bits(),
+            hist.join(",")
+        )
+    }
+}
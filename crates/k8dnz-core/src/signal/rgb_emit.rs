@@ -161,6 +161,77 @@ pub fn emit_rgbpair_from_fields(
     }
 }
 
+/// Integer HSV -> RGB. `hue` in degrees (taken mod 360), `sat`/`val` in 0..=255.
+pub fn hsv_to_rgb(hue: u16, sat: u8, val: u8) -> Rgb {
+    let h = (hue % 360) as u32;
+    let s = sat as u32;
+    let v = val as u32;
+    if s == 0 {
+        return Rgb::new(val, val, val);
+    }
+
+    let region = h / 60;
+    let rem = h % 60;
+
+    // p/q/t as in the usual sector formula, all scaled by 255*60.
+    let p = (v * (255 - s) + 127) / 255;
+    let q = (v * (255 * 60 - s * rem) + 255 * 30) / (255 * 60);
+    let t = (v * (255 * 60 - s * (60 - rem)) + 255 * 30) / (255 * 60);
+
+    let (r, g, b) = match region {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+    Rgb::new(r as u8, g as u8, b as u8)
+}
+
+/// Integer RGB -> HSV: (hue 0..360, sat 0..=255, val 0..=255).
+/// Inverse of `hsv_to_rgb` up to rounding.
+pub fn rgb_to_hsv(c: Rgb) -> (u16, u8, u8) {
+    let (r, g, b) = (c.r as i32, c.g as i32, c.b as i32);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+    if max == 0 || d == 0 {
+        return (0, 0, max as u8);
+    }
+
+    let sat = (255 * d + max / 2) / max;
+
+    // 60 degrees per sector; offset within sector rounded to nearest degree.
+    let h = if max == r {
+        (60 * (g - b) + d / 2).div_euclid(d)
+    } else if max == g {
+        120 + (60 * (b - r) + d / 2).div_euclid(d)
+    } else {
+        240 + (60 * (r - g) + d / 2).div_euclid(d)
+    };
+    (h.rem_euclid(360) as u16, sat as u8, max as u8)
+}
+
+/// HSV emission model: A and C are hue angles advancing by `step_a`/`step_c`
+/// degrees per emission, converted to RGB at emission time. Circular phase, so
+/// no clamping edges.
+pub fn emit_hsv_pair_from_params(
+    emission: u64,
+    step_a: u16,
+    step_c: u16,
+    sat: u8,
+    val: u8,
+) -> RgbPairToken {
+    let e = emission % 360;
+    let hue_a = ((e * (step_a as u64 % 360)) % 360) as u16;
+    let hue_c = ((e * (step_c as u64 % 360)) % 360) as u16;
+    RgbPairToken {
+        a: hsv_to_rgb(hue_a, sat, val),
+        c: hsv_to_rgb(hue_c, sat, val),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,4 +272,26 @@ mod tests {
             "expected Cone and DNA to differ for some emission_idx"
         );
     }
+
+    #[test]
+    fn hsv_roundtrip_within_rounding() {
+        for &(sat, val) in &[(200u8, 220u8), (255, 255), (128, 180)] {
+            for hue in 0..360u16 {
+                let (h2, s2, v2) = rgb_to_hsv(hsv_to_rgb(hue, sat, val));
+                let dh = (hue as i32 - h2 as i32).rem_euclid(360);
+                let dh = dh.min(360 - dh);
+                assert!(dh <= 2, "hue {} -> {} (sat={} val={})", hue, h2, sat, val);
+                assert!((sat as i32 - s2 as i32).abs() <= 2, "sat {} -> {}", sat, s2);
+                assert_eq!(v2, val);
+            }
+        }
+    }
+
+    #[test]
+    fn hsv_emission_is_periodic_in_hue() {
+        let a = emit_hsv_pair_from_params(5, 7, 3, 200, 220);
+        let b = emit_hsv_pair_from_params(365, 7, 3, 200, 220);
+        assert_eq!(a, b);
+        assert_eq!(emit_hsv_pair_from_params(0, 7, 3, 200, 220).a, hsv_to_rgb(0, 200, 220));
+    }
 }
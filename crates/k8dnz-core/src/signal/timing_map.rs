@@ -6,6 +6,43 @@ const MAGIC_TM1: &[u8; 4] = b"TM1\0";
 const MAGIC_TM0: &[u8; 4] = b"TM0\0";
 const MAGIC_TM2: &[u8; 4] = b"TM2\0"; // piecewise runs (stride=1 segments)
 
+/// On-disk timemap encodings, told apart by their magic.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimemapFormat {
+    /// Implicit stride program (arithmetic progressions only).
+    Tm0,
+    /// Delta-varint index list.
+    Tm1,
+    /// Piecewise stride=1 runs.
+    Tm2,
+}
+
+impl TimemapFormat {
+    pub const ALL: [TimemapFormat; 3] = [
+        TimemapFormat::Tm0,
+        TimemapFormat::Tm1,
+        TimemapFormat::Tm2,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TimemapFormat::Tm0 => "TM0",
+            TimemapFormat::Tm1 => "TM1",
+            TimemapFormat::Tm2 => "TM2",
+        }
+    }
+
+    /// Format of an encoded timemap, from its magic; None if unrecognized.
+    pub fn detect(bytes: &[u8]) -> Option<TimemapFormat> {
+        match bytes.get(0..4)? {
+            m if m == MAGIC_TM0 => Some(TimemapFormat::Tm0),
+            m if m == MAGIC_TM1 => Some(TimemapFormat::Tm1),
+            m if m == MAGIC_TM2 => Some(TimemapFormat::Tm2),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TimingMap {
     pub indices: Vec<u64>,
@@ -37,10 +74,118 @@ impl TimingMap {
         TimingMap::new(indices)
     }
 
+    /// Parse positions text: one decimal u64 per line (the `--output-positions text`
+    /// format). Blank lines and lines starting with `#` are skipped. With
+    /// `require_sorted`, indices must be strictly increasing; otherwise they are
+    /// returned in file order and the caller decides what to do.
+    pub fn from_positions_text(text: &str, require_sorted: bool) -> Result<Self> {
+        let mut indices = Vec::new();
+        for (lineno, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let v: u64 = line.parse().map_err(|_| {
+                K8Error::Validation(format!(
+                    "timemap: line {}: not a u64: {:?}",
+                    lineno + 1,
+                    line
+                ))
+            })?;
+            indices.push(v);
+        }
+
+        if require_sorted {
+            TimingMap::new(indices)
+        } else {
+            Ok(TimingMap { indices })
+        }
+    }
+
+    pub fn from_positions_file(path: &str, require_sorted: bool) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        TimingMap::from_positions_text(&text, require_sorted)
+    }
+
+    /// Inverse of `from_positions_text`: one index per line.
+    pub fn to_positions_text(&self) -> String {
+        let mut s = String::with_capacity(self.indices.len() * 8);
+        for &p in &self.indices {
+            s.push_str(&p.to_string());
+            s.push('\n');
+        }
+        s
+    }
+
+    pub fn to_positions_file(&self, path: &str) -> Result<()> {
+        std::fs::write(path, self.to_positions_text())?;
+        Ok(())
+    }
+
+    /// Checks the invariants every on-disk timemap must satisfy: non-empty, strictly
+    /// increasing (so no duplicates), and no `u64::MAX` sentinel index.
+    pub fn validate(&self) -> Result<()> {
+        if self.indices.is_empty() {
+            return Err(K8Error::Validation("timemap: empty".into()));
+        }
+        for (i, w) in self.indices.windows(2).enumerate() {
+            if w[1] == w[0] {
+                return Err(K8Error::Validation(format!(
+                    "timemap: duplicate index {} at position {}",
+                    w[1],
+                    i + 1
+                )));
+            }
+            if w[1] < w[0] {
+                return Err(K8Error::Validation(format!(
+                    "timemap: index {} at position {} is below previous {}",
+                    w[1],
+                    i + 1,
+                    w[0]
+                )));
+            }
+        }
+        if self.indices.last() == Some(&u64::MAX) {
+            return Err(K8Error::Validation(
+                "timemap: index u64::MAX is reserved as a sentinel".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// True if the indices are exactly `start, start+step, start+2*step, ...` for
+    /// some start, i.e. the map is representable as TM0. Empty and single-index maps
+    /// qualify for any `step > 0`.
+    pub fn is_contiguous_stride(&self, step: u64) -> bool {
+        if step == 0 {
+            return false;
+        }
+        self.indices
+            .windows(2)
+            .all(|w| w[1].checked_sub(w[0]) == Some(step))
+    }
+
     pub fn last_index(&self) -> Option<u64> {
         self.indices.last().copied()
     }
 
+    /// Indices `[start..end)` as their own map (absolute positions are kept, so the
+    /// slice still reconstructs the same output bytes). Bounds are clamped to the
+    /// map's length; `start >= end` gives an empty map.
+    pub fn slice(&self, start: usize, end: usize) -> TimingMap {
+        let end = end.min(self.indices.len());
+        let start = start.min(end);
+        TimingMap {
+            indices: self.indices[start..end].to_vec(),
+        }
+    }
+
+    /// `(indices[..n], indices[n..])`, with `n` clamped to the map's length.
+    pub fn split_at(&self, n: usize) -> (TimingMap, TimingMap) {
+        let len = self.indices.len();
+        (self.slice(0, n), self.slice(n, len))
+    }
+
     /// TM1 binary encoding:
     /// MAGIC[4] = "TM1\0"
     /// count: varint(u64)
@@ -63,9 +208,7 @@ impl TimingMap {
     }
 
     pub fn decode_tm1(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 4 || &bytes[0..4] != MAGIC_TM1 {
-            return Err(K8Error::Validation("timemap: bad magic".into()));
-        }
+        check_magic(bytes, MAGIC_TM1)?;
         let mut i = 4usize;
 
         let count = read_var_u64(bytes, &mut i)? as usize;
@@ -101,21 +244,9 @@ impl TimingMap {
             return Some((self.indices[0], 1, 1));
         }
         let start = self.indices[0];
-        let step = self.indices[1].checked_sub(self.indices[0])?;
-        if step == 0 {
-            return None;
-        }
-        let mut prev = self.indices[1];
-        for &x in self.indices.iter().skip(2) {
-            if x <= prev {
-                return None;
-            }
-            if x.checked_sub(prev)? != step {
-                return None;
-            }
-            prev = x;
-        }
-        Some((start, n as u64, step))
+        let step = self.indices[1].checked_sub(start)?;
+        self.is_contiguous_stride(step)
+            .then_some((start, n as u64, step))
     }
 
     /// TM0 binary encoding (implicit stride program):
@@ -133,9 +264,7 @@ impl TimingMap {
     }
 
     pub fn decode_tm0(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 4 || &bytes[0..4] != MAGIC_TM0 {
-            return Err(K8Error::Validation("timemap: bad magic".into()));
-        }
+        check_magic(bytes, MAGIC_TM0)?;
         let mut i = 4usize;
         let len = read_var_u64(bytes, &mut i)?;
         let start = read_var_u64(bytes, &mut i)?;
@@ -170,9 +299,7 @@ impl TimingMap {
     }
 
     pub fn decode_tm2(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 4 || &bytes[0..4] != MAGIC_TM2 {
-            return Err(K8Error::Validation("timemap: bad magic".into()));
-        }
+        check_magic(bytes, MAGIC_TM2)?;
         let mut i = 4usize;
 
         let seg_count = read_var_u64(bytes, &mut i)? as usize;
@@ -299,10 +426,41 @@ impl TimingMap {
         self.encode_tm1()
     }
 
+    /// Encoding in `fmt`; None for TM0 when the map is not an arithmetic progression.
+    pub fn encode_as(&self, fmt: TimemapFormat) -> Option<Vec<u8>> {
+        match fmt {
+            TimemapFormat::Tm0 => self
+                .as_arith_prog()
+                .map(|(start, len, step)| TimingMap::encode_tm0(len, start, step)),
+            TimemapFormat::Tm1 => Some(self.encode_tm1()),
+            TimemapFormat::Tm2 => Some(self.encode_tm2_runs()),
+        }
+    }
+
+    /// Encoded size in `fmt` without encoding; None where `encode_as` is None.
+    pub fn encoded_len(&self, fmt: TimemapFormat) -> Option<usize> {
+        match fmt {
+            TimemapFormat::Tm0 => self.as_arith_prog().map(|(start, len, step)| {
+                4 + Self::var_u64_len(len) + Self::var_u64_len(start) + Self::var_u64_len(step)
+            }),
+            TimemapFormat::Tm1 => Some(self.estimate_tm1_len_bytes()),
+            TimemapFormat::Tm2 => Some(self.estimate_tm2_len_bytes(&self.as_runs_step1())),
+        }
+    }
+
+    /// The most compact format and its size. Ties go to the earlier of TM0, TM1, TM2.
+    pub fn smallest_format(&self) -> (TimemapFormat, usize) {
+        TimemapFormat::ALL
+            .into_iter()
+            .filter_map(|f| self.encoded_len(f).map(|n| (f, n)))
+            .min_by_key(|&(_, n)| n)
+            .expect("TM1 always encodes")
+    }
+
     /// Auto-decoding: detect TM0/TM1/TM2 magic.
     pub fn decode_auto(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < 4 {
-            return Err(K8Error::Validation("timemap: too short".into()));
+            return Err(K8Error::TruncatedInput { context: "timemap", expected: 4, got: bytes.len() });
         }
         if &bytes[0..4] == MAGIC_TM0 {
             return TimingMap::decode_tm0(bytes);
@@ -313,8 +471,21 @@ impl TimingMap {
         if &bytes[0..4] == MAGIC_TM1 {
             return TimingMap::decode_tm1(bytes);
         }
-        Err(K8Error::Validation("timemap: unknown magic".into()))
+        // Not one of ours: report it against the default (TM1) magic.
+        let got = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Err(K8Error::BadMagic { context: "timemap", expected: *MAGIC_TM1, got })
+    }
+}
+
+fn check_magic(bytes: &[u8], magic: &[u8; 4]) -> Result<()> {
+    let got: [u8; 4] = bytes
+        .get(..4)
+        .and_then(|m| m.try_into().ok())
+        .ok_or(K8Error::TruncatedInput { context: "timemap", expected: 4, got: bytes.len() })?;
+    if &got != magic {
+        return Err(K8Error::BadMagic { context: "timemap", expected: *magic, got });
     }
+    Ok(())
 }
 
 // --- u64 varint (LEB128-like, 7-bit groups) ---
@@ -333,7 +504,7 @@ fn read_var_u64(bytes: &[u8], i: &mut usize) -> Result<u64> {
 
     loop {
         if *i >= bytes.len() {
-            return Err(K8Error::Validation("timemap: unexpected eof".into()));
+            return Err(K8Error::TruncatedInput { context: "timemap", expected: *i + 1, got: bytes.len() });
         }
         let b = bytes[*i];
         *i += 1;
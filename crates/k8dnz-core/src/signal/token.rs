@@ -1,8 +1,84 @@
 // crates/k8dnz-core/src/signal/token.rs
 
-/// Back-compat alias: packed (a<<4)|b byte from a PairToken.
-/// lib.rs re-exports this, so it must exist.
-pub type PackedByte = u8;
+use crate::error::{K8Error, Result};
+
+/// Packed (a<<4)|b byte from a PairToken, with nibble/histogram helpers for analysis.
+/// lib.rs re-exports this. `pack_byte` still returns a plain u8; wrap with
+/// `PackedByte::from` (or `PairToken::packed`) when you want the helpers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct PackedByte(pub u8);
+
+impl PackedByte {
+    /// High nibble (token `a`).
+    #[inline]
+    pub fn nibble_a(self) -> u8 {
+        self.0 >> 4
+    }
+
+    /// Low nibble (token `b`).
+    #[inline]
+    pub fn nibble_b(self) -> u8 {
+        self.0 & 0x0F
+    }
+
+    /// True when both nibbles agree (a == b).
+    #[inline]
+    pub fn is_palindrome(self) -> bool {
+        self.nibble_a() == self.nibble_b()
+    }
+
+    #[inline]
+    pub fn count_ones(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Byte histogram without collecting into a buffer first.
+    pub fn frequency_table(iter: impl Iterator<Item = PackedByte>) -> [u64; 256] {
+        let mut h = [0u64; 256];
+        for x in iter {
+            h[x.0 as usize] += 1;
+        }
+        h
+    }
+
+    /// Joint histogram `[a][b]` over the common prefix of two streams.
+    /// Boxed because the table is 512 KiB.
+    pub fn joint_frequency_table(
+        a: impl Iterator<Item = PackedByte>,
+        b: impl Iterator<Item = PackedByte>,
+    ) -> Box<[[u64; 256]; 256]> {
+        let mut h: Box<[[u64; 256]; 256]> = vec![[0u64; 256]; 256]
+            .into_boxed_slice()
+            .try_into()
+            .expect("256 rows");
+        for (x, y) in a.zip(b) {
+            h[x.0 as usize][y.0 as usize] += 1;
+        }
+        h
+    }
+}
+
+impl From<u8> for PackedByte {
+    #[inline]
+    fn from(x: u8) -> Self {
+        Self(x)
+    }
+}
+
+impl From<PackedByte> for u8 {
+    #[inline]
+    fn from(x: PackedByte) -> Self {
+        x.0
+    }
+}
+
+impl From<PairToken> for PackedByte {
+    #[inline]
+    fn from(t: PairToken) -> Self {
+        t.packed()
+    }
+}
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct PairToken {
@@ -13,24 +89,48 @@ pub struct PairToken {
 impl PairToken {
     /// Pack N=16 pair into one byte: (a<<4)|b
     #[inline]
-    pub fn pack_byte(self) -> PackedByte {
+    pub fn pack_byte(self) -> u8 {
         ((self.a & 0x0F) << 4) | (self.b & 0x0F)
     }
 
+    /// Same byte as `pack_byte`, wrapped for analysis helpers.
+    #[inline]
+    pub fn packed(self) -> PackedByte {
+        PackedByte(self.pack_byte())
+    }
+
     /// Back-compat name used in older code.
     #[inline]
-    pub fn pack_n16(self) -> PackedByte {
+    pub fn pack_n16(self) -> u8 {
         self.pack_byte()
     }
 
-    #[inline]
-    pub fn unpack_byte(x: PackedByte) -> Self {
+    /// Inverse of `pack_byte`: high nibble -> `a`, low nibble -> `b`.
+    #[inline(always)]
+    pub const fn from_byte(x: u8) -> Self {
         Self {
-            a: (x >> 4) & 0x0F,
+            a: x >> 4,
             b: x & 0x0F,
         }
     }
 
+    /// Build from two N=16 symbols; either one >= 16 is a `K8Error::Validation`.
+    #[inline(always)]
+    pub fn from_nibbles(a: u8, b: u8) -> Result<Self> {
+        if a >= 16 || b >= 16 {
+            return Err(K8Error::Validation(format!(
+                "pair token nibbles out of range: a={a} b={b} (need < 16)"
+            )));
+        }
+        Ok(Self { a, b })
+    }
+
+    /// Back-compat name for `from_byte`.
+    #[inline]
+    pub fn unpack_byte(x: u8) -> Self {
+        Self::from_byte(x)
+    }
+
     /// Deterministic “color pair” view of this token.
     /// MVP palette-based mapping; later we’ll swap in additive/coupled cone laws.
     #[inline]
@@ -53,6 +153,79 @@ impl Rgb {
     pub const fn new(r: u8, g: u8, b: u8) -> Self {
         Self { r, g, b }
     }
+
+    /// Rec. 601 luma, 0..=255.
+    #[inline]
+    pub fn luminance(self) -> u8 {
+        ((299 * self.r as u32 + 587 * self.g as u32 + 114 * self.b as u32 + 500) / 1000) as u8
+    }
+
+    /// HSV saturation scaled to 0..=255 (0 for gray/black).
+    #[inline]
+    pub fn saturation(self) -> u8 {
+        let max = self.r.max(self.g).max(self.b) as u32;
+        let min = self.r.min(self.g).min(self.b) as u32;
+        if max == 0 {
+            return 0;
+        }
+        ((max - min) * 255 / max) as u8
+    }
+
+    /// HSV hue in whole degrees 0..360, or None when the color is achromatic.
+    pub fn hue(self) -> Option<u16> {
+        let (r, g, b) = (self.r as f64, self.g as f64, self.b as f64);
+        let max = r.max(g).max(b);
+        let d = max - r.min(g).min(b);
+        if d == 0.0 {
+            return None;
+        }
+        let h = if max == r {
+            60.0 * ((g - b) / d)
+        } else if max == g {
+            60.0 * ((b - r) / d + 2.0)
+        } else {
+            60.0 * ((r - g) / d + 4.0)
+        };
+        Some((h.rem_euclid(360.0).round() as u16) % 360)
+    }
+
+    /// CIE L*a*b* (D65 white point) of this sRGB color: L* in 0..=100, a* (green..red)
+    /// and b* (blue..yellow) roughly -128..=127.
+    pub fn to_lab(self) -> (f64, f64, f64) {
+        srgb_to_lab(self.r as f64, self.g as f64, self.b as f64)
+    }
+}
+
+/// sRGB (0..=255 per channel, fractional allowed) -> linear RGB -> XYZ -> L*a*b*.
+fn srgb_to_lab(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    fn linear(c: f64) -> f64 {
+        let c = c / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    fn f(t: f64) -> f64 {
+        const D: f64 = 6.0 / 29.0;
+        if t > D * D * D {
+            t.cbrt()
+        } else {
+            t / (3.0 * D * D) + 4.0 / 29.0
+        }
+    }
+    // D65 reference white.
+    const XN: f64 = 0.95047;
+    const YN: f64 = 1.0;
+    const ZN: f64 = 1.08883;
+
+    let (r, g, b) = (linear(r), linear(g), linear(b));
+    let x = 0.4124564 * r + 0.3575761 * g + 0.1804375 * b;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = 0.0193339 * r + 0.1191920 * g + 0.9503041 * b;
+
+    let (fx, fy, fz) = (f(x / XN), f(y / YN), f(z / ZN));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -69,6 +242,45 @@ impl RgbPairToken {
     pub fn to_bytes(self) -> [u8; 6] {
         [self.a.r, self.a.g, self.a.b, self.c.r, self.c.g, self.c.b]
     }
+
+    /// `|luminance(A) - luminance(C)|`: 0 = same brightness, 255 = black vs white.
+    #[inline]
+    pub fn contrast(self) -> u8 {
+        self.a.luminance().abs_diff(self.c.luminance())
+    }
+
+    /// Angle between the A and C hues, 0..=180 degrees. Gray on either side counts as 0.
+    pub fn hue_difference(self) -> u16 {
+        match (self.a.hue(), self.c.hue()) {
+            (Some(ha), Some(hc)) => {
+                let d = ha.abs_diff(hc);
+                d.min(360 - d)
+            }
+            _ => 0,
+        }
+    }
+
+    /// HSV saturation of A, 0..=255.
+    #[inline]
+    pub fn saturation_a(self) -> u8 {
+        self.a.saturation()
+    }
+
+    /// HSV saturation of C, 0..=255.
+    #[inline]
+    pub fn saturation_c(self) -> u8 {
+        self.c.saturation()
+    }
+
+    /// L*a*b* of the channel-wise mean of A and C (see `Rgb::to_lab`).
+    pub fn to_lab(self) -> (f64, f64, f64) {
+        let mean = |x: u8, y: u8| (x as f64 + y as f64) / 2.0;
+        srgb_to_lab(
+            mean(self.a.r, self.c.r),
+            mean(self.a.g, self.c.g),
+            mean(self.a.b, self.c.b),
+        )
+    }
 }
 
 /// A compact, deterministic 16-color palette that “reads” like an orderly spectrum.
@@ -0,0 +1,45 @@
+// crates/k8dnz-core/src/stats/complexity.rs
+//
+// Lempel-Ziv (LZ78) complexity of a byte stream: the number of phrases in its LZ78
+// parsing, where each phrase is the shortest prefix of the remaining input not seen as
+// a phrase before. The phrase dictionary is a trie stored as a (node, byte) -> child
+// hash map, so parsing is one lookup per input byte.
+
+use std::collections::HashMap;
+
+/// C_LZ: LZ78 phrase count of `bytes`. A trailing phrase that repeats an earlier one
+/// (the input ran out mid-match) still counts.
+pub fn lz78_complexity(bytes: &[u8]) -> usize {
+    let mut trie: HashMap<(u32, u8), u32> = HashMap::new();
+    let mut next_id: u32 = 1;
+    let mut node: u32 = 0;
+    let mut phrases = 0usize;
+    for &b in bytes {
+        match trie.get(&(node, b)) {
+            Some(&child) => node = child,
+            None => {
+                trie.insert((node, b), next_id);
+                next_id += 1;
+                phrases += 1;
+                node = 0;
+            }
+        }
+    }
+    if node != 0 {
+        phrases += 1;
+    }
+    phrases
+}
+
+/// C_LZ / (n / log_256(n)): n / log_k(n) is the phrase count of a random string over a
+/// k-letter alphabet (n / log2(n) for bits), so this is close to 1.0 for random bytes and
+/// well below it for repetitive input. Values above 1.0 only occur for short inputs.
+/// 0.0 for fewer than 2 bytes.
+pub fn lz_complexity_normalized(bytes: &[u8]) -> f64 {
+    let n = bytes.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let n = n as f64;
+    lz78_complexity(bytes) as f64 * (n.log2() / 8.0) / n
+}
@@ -0,0 +1,100 @@
+// crates/k8dnz-core/src/stats/correlation.rs
+//
+// Linear (Pearson) and rank (Spearman) correlation between two equal-length byte
+// streams, e.g. engine output vs a target file. p-values test r = 0 via
+// t = r * sqrt((n - 2) / (1 - r^2)) with n - 2 degrees of freedom.
+
+use super::uniformity::erfc;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CorrelationTest {
+    /// Number of byte pairs compared.
+    pub n: u64,
+    pub pearson: f64,
+    pub pearson_p: f64,
+    pub spearman: f64,
+    pub spearman_p: f64,
+}
+
+/// Both coefficients over `a[..n]` and `b[..n]`, `n = min(a.len(), b.len())`.
+pub fn correlation_test(a: &[u8], b: &[u8]) -> CorrelationTest {
+    let n = a.len().min(b.len());
+    let (a, b) = (&a[..n], &b[..n]);
+
+    let xa: Vec<f64> = a.iter().map(|&v| v as f64).collect();
+    let xb: Vec<f64> = b.iter().map(|&v| v as f64).collect();
+    let pearson = pearson_f64(&xa, &xb);
+    let spearman = pearson_f64(&byte_ranks(a), &byte_ranks(b));
+
+    CorrelationTest {
+        n: n as u64,
+        pearson,
+        pearson_p: correlation_p_value(pearson, n as u64),
+        spearman,
+        spearman_p: correlation_p_value(spearman, n as u64),
+    }
+}
+
+/// Pearson r of two byte streams (0.0 if either is constant or shorter than 2).
+pub fn pearson(a: &[u8], b: &[u8]) -> f64 {
+    correlation_test(a, b).pearson
+}
+
+/// Spearman rho: Pearson r of the ranks, ties getting their average rank.
+pub fn spearman(a: &[u8], b: &[u8]) -> f64 {
+    correlation_test(a, b).spearman
+}
+
+/// Two-sided p-value for H0: r = 0 over `n` pairs. The Student t tail is taken
+/// from the normal approximation `z = t (1 - 1/(4 df)) / sqrt(1 + t^2 / (2 df))`,
+/// which is good to ~1e-3 once df is in the dozens.
+pub fn correlation_p_value(r: f64, n: u64) -> f64 {
+    if n < 3 {
+        return 1.0;
+    }
+    let df = (n - 2) as f64;
+    let r2 = r * r;
+    if r2 >= 1.0 {
+        return 0.0;
+    }
+    let t = r.abs() * (df / (1.0 - r2)).sqrt();
+    let z = t * (1.0 - 1.0 / (4.0 * df)) / (1.0 + t * t / (2.0 * df)).sqrt();
+    erfc(z / std::f64::consts::SQRT_2).clamp(0.0, 1.0)
+}
+
+fn pearson_f64(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let nf = n as f64;
+    let mx = x.iter().sum::<f64>() / nf;
+    let my = y.iter().sum::<f64>() / nf;
+    let (mut sxy, mut sxx, mut syy) = (0.0f64, 0.0f64, 0.0f64);
+    for (&a, &b) in x.iter().zip(y) {
+        let (da, db) = (a - mx, b - my);
+        sxy += da * db;
+        sxx += da * da;
+        syy += db * db;
+    }
+    if sxx <= 0.0 || syy <= 0.0 {
+        return 0.0;
+    }
+    sxy / (sxx * syy).sqrt()
+}
+
+/// 1-based average ranks; bytes only have 256 distinct values, so ranks come
+/// straight from the histogram.
+fn byte_ranks(bytes: &[u8]) -> Vec<f64> {
+    let mut hist = [0u64; 256];
+    for &b in bytes {
+        hist[b as usize] += 1;
+    }
+    let mut rank = [0.0f64; 256];
+    let mut below = 0u64;
+    for (v, &c) in hist.iter().enumerate() {
+        rank[v] = below as f64 + (c as f64 + 1.0) / 2.0;
+        below += c;
+    }
+    bytes.iter().map(|&b| rank[b as usize]).collect()
+}
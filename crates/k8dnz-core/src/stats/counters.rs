@@ -1,6 +1,44 @@
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Counters {
     pub ticks: u64,
     pub alignments: u64,
     pub emissions: u64,
 }
+
+impl Counters {
+    /// Emissions per 1e6 ticks (0 before the first tick); a quick capacity-planning
+    /// figure since ticks are the engine's unit of CPU work.
+    pub fn emission_density_per_million_ticks(&self) -> f64 {
+        if self.ticks == 0 {
+            return 0.0;
+        }
+        self.emissions as f64 * 1e6 / self.ticks as f64
+    }
+
+    /// Zeroes the counters (e.g. after warm-up). Only bookkeeping: the engine's
+    /// dynamics do not read these, so the token stream is unaffected.
+    pub fn reset(&mut self) {
+        *self = Counters::default();
+    }
+
+    /// Snapshot for a later `since`.
+    pub fn checkpoint(&self) -> Counters {
+        self.clone()
+    }
+
+    /// Counts accumulated after `checkpoint` was taken.
+    pub fn since(&self, checkpoint: &Counters) -> Counters {
+        Counters {
+            ticks: self.ticks.saturating_sub(checkpoint.ticks),
+            alignments: self.alignments.saturating_sub(checkpoint.alignments),
+            emissions: self.emissions.saturating_sub(checkpoint.emissions),
+        }
+    }
+
+    /// Adds another run's counts into this one (totals over several engines).
+    pub fn accumulate(&mut self, other: &Counters) {
+        self.ticks = self.ticks.saturating_add(other.ticks);
+        self.alignments = self.alignments.saturating_add(other.alignments);
+        self.emissions = self.emissions.saturating_add(other.emissions);
+    }
+}
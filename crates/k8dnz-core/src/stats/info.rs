@@ -0,0 +1,121 @@
+// crates/k8dnz-core/src/stats/info.rs
+//
+// Information measures over paired byte streams (plug-in estimates from histograms).
+// Streams of different lengths are compared over their common prefix.
+// cross_entropy / kl_divergence compare two byte distributions and need no pairing.
+
+use std::collections::HashMap;
+
+use crate::signal::token::PackedByte;
+
+/// Shannon entropy in bits of a histogram whose counts sum to `total`; 0 when empty.
+pub fn entropy_bits(counts: &[u64], total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let n = total as f64;
+    let mut h = 0.0;
+    for &c in counts {
+        if c == 0 {
+            continue;
+        }
+        let p = c as f64 / n;
+        h -= p * p.log2();
+    }
+    h
+}
+
+/// Returns (H(X), H(Y), H(X,Y)) in bits over the common prefix.
+fn marginal_and_joint_entropy(xs: &[u8], ys: &[u8]) -> (f64, f64, f64) {
+    let n = xs.len().min(ys.len());
+    let (xs, ys) = (&xs[..n], &ys[..n]);
+
+    let hx = PackedByte::frequency_table(xs.iter().map(|&x| PackedByte(x)));
+    let hy = PackedByte::frequency_table(ys.iter().map(|&y| PackedByte(y)));
+    let hxy = PackedByte::joint_frequency_table(
+        xs.iter().map(|&x| PackedByte(x)),
+        ys.iter().map(|&y| PackedByte(y)),
+    );
+
+    let total = n as u64;
+    (
+        entropy_bits(&hx, total),
+        entropy_bits(&hy, total),
+        entropy_bits(hxy.as_flattened(), total),
+    )
+}
+
+/// I(X;Y) = H(X) + H(Y) - H(X,Y), in bits.
+pub fn mutual_information(xs: &[u8], ys: &[u8]) -> f64 {
+    let (h_x, h_y, h_xy) = marginal_and_joint_entropy(xs, ys);
+    (h_x + h_y - h_xy).max(0.0)
+}
+
+/// H(Y|X) = H(X,Y) - H(X), in bits.
+pub fn conditional_entropy(xs: &[u8], ys: &[u8]) -> f64 {
+    let (h_x, _h_y, h_xy) = marginal_and_joint_entropy(xs, ys);
+    (h_xy - h_x).max(0.0)
+}
+
+/// Second-order entropy H(X_{i+1} | X_i) of one stream, from its 256x256 bigram table.
+pub fn bigram_entropy(bytes: &[u8]) -> f64 {
+    if bytes.len() < 2 {
+        return 0.0;
+    }
+    conditional_entropy(&bytes[..bytes.len() - 1], &bytes[1..])
+}
+
+/// Third-order entropy H(X_{i+2} | X_i, X_{i+1}). The 256^3 joint table is kept
+/// sparse (only observed trigrams); also returns the distinct trigram count.
+pub fn trigram_entropy(bytes: &[u8]) -> (f64, usize) {
+    if bytes.len() < 3 {
+        return (0.0, 0);
+    }
+    let mut pairs = vec![0u64; 1 << 16];
+    let mut triples: HashMap<u32, u64> = HashMap::new();
+    for w in bytes.windows(3) {
+        let ctx = (w[0] as usize) << 8 | w[1] as usize;
+        pairs[ctx] += 1;
+        *triples.entry((ctx as u32) << 8 | w[2] as u32).or_insert(0) += 1;
+    }
+    let total = (bytes.len() - 2) as u64;
+    let counts: Vec<u64> = triples.values().copied().collect();
+    let h = entropy_bits(&counts, total) - entropy_bits(&pairs, total);
+    (h.max(0.0), triples.len())
+}
+
+/// Add-one smoothed byte distribution of `bytes`; never zero, so it is usable as a
+/// coding model for any other stream.
+fn smoothed_model(bytes: &[u8]) -> [f64; 256] {
+    let h = PackedByte::frequency_table(bytes.iter().map(|&b| PackedByte(b)));
+    let total = (bytes.len() + 256) as f64;
+    let mut q = [0.0f64; 256];
+    for (qi, &c) in q.iter_mut().zip(h.iter()) {
+        *qi = (c + 1) as f64 / total;
+    }
+    q
+}
+
+/// Cross-entropy H(P, Q) = -sum p(x) log2 q(x), in bits per byte: the cost of coding
+/// `p_bytes` with the byte distribution of `q_bytes` (add-one smoothed) as the model.
+pub fn cross_entropy(p_bytes: &[u8], q_bytes: &[u8]) -> f64 {
+    if p_bytes.is_empty() {
+        return 0.0;
+    }
+    let hp = PackedByte::frequency_table(p_bytes.iter().map(|&b| PackedByte(b)));
+    let q = smoothed_model(q_bytes);
+    let n = p_bytes.len() as f64;
+    hp.iter()
+        .zip(q.iter())
+        .filter(|(&c, _)| c > 0)
+        .map(|(&c, &qx)| -(c as f64 / n) * qx.log2())
+        .sum()
+}
+
+/// D_KL(P || Q) = H(P, Q) - H(P), in bits per byte, with the same smoothed Q as
+/// `cross_entropy`.
+pub fn kl_divergence(p_bytes: &[u8], q_bytes: &[u8]) -> f64 {
+    let hp = PackedByte::frequency_table(p_bytes.iter().map(|&b| PackedByte(b)));
+    let h = entropy_bits(&hp, p_bytes.len() as u64);
+    (cross_entropy(p_bytes, q_bytes) - h).max(0.0)
+}
@@ -1 +1,17 @@
+pub mod complexity;
+pub mod correlation;
 pub mod counters;
+pub mod info;
+pub mod randomness;
+pub mod tpe;
+pub mod uniformity;
+
+pub use complexity::{lz78_complexity, lz_complexity_normalized};
+pub use correlation::{correlation_p_value, correlation_test, pearson, spearman, CorrelationTest};
+pub use info::{
+    bigram_entropy, conditional_entropy, cross_entropy, entropy_bits, kl_divergence,
+    mutual_information, trigram_entropy,
+};
+pub use randomness::{stat_test_bytes, StatTestReport, StatTestResult, STAT_TEST_ALPHA};
+pub use tpe::TpeStats;
+pub use uniformity::{chi_squared_uniform, ks_uniform, UniformityTest};
@@ -0,0 +1,205 @@
+// crates/k8dnz-core/src/stats/randomness.rs
+//
+// Small built-in randomness battery for engine byte streams (a quick stand-in for
+// ENT / NIST runs during development). Each test reports an approximate p-value
+// under the "independent uniform bytes" hypothesis and passes when p >= alpha.
+
+use super::uniformity::{chi2_sf, chi_squared_uniform, erfc};
+
+/// Significance level used by `Engine::statistical_test_run`.
+pub const STAT_TEST_ALPHA: f64 = 0.01;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatTestResult {
+    pub name: &'static str,
+    pub statistic: f64,
+    pub p_value: f64,
+    pub pass: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatTestReport {
+    pub bytes: u64,
+    pub alpha: f64,
+    pub tests: Vec<StatTestResult>,
+}
+
+impl StatTestReport {
+    pub fn all_pass(&self) -> bool {
+        self.tests.iter().all(|t| t.pass)
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &StatTestResult> {
+        self.tests.iter().filter(|t| !t.pass)
+    }
+}
+
+/// Runs the whole battery: byte chi-squared, runs about the median, lag-1 serial
+/// correlation and approximate entropy (m=2, over bits).
+pub fn stat_test_bytes(bytes: &[u8], alpha: f64) -> StatTestReport {
+    let chi = chi_squared_uniform(bytes);
+    let results = [
+        ("chi2_bytes", chi.statistic, chi.p_value),
+        runs_test(bytes),
+        serial_correlation(bytes),
+        approximate_entropy_bits(bytes, 2),
+    ];
+
+    StatTestReport {
+        bytes: bytes.len() as u64,
+        alpha,
+        tests: results
+            .into_iter()
+            .map(|(name, statistic, p_value)| StatTestResult {
+                name,
+                statistic,
+                p_value,
+                pass: p_value >= alpha,
+            })
+            .collect(),
+    }
+}
+
+/// Two-sided normal tail, P(|Z| >= |z|).
+fn normal_two_sided(z: f64) -> f64 {
+    erfc(z.abs() / std::f64::consts::SQRT_2).clamp(0.0, 1.0)
+}
+
+fn median_byte(bytes: &[u8]) -> f64 {
+    let mut hist = [0u64; 256];
+    for &b in bytes {
+        hist[b as usize] += 1;
+    }
+    let n = bytes.len() as u64;
+    let (lo_rank, hi_rank) = ((n - 1) / 2, n / 2);
+    let (mut lo, mut hi, mut cum) = (None, None, 0u64);
+    for (v, &c) in hist.iter().enumerate() {
+        cum += c;
+        if lo.is_none() && cum > lo_rank {
+            lo = Some(v);
+        }
+        if cum > hi_rank {
+            hi = Some(v);
+            break;
+        }
+    }
+    (lo.unwrap_or(0) + hi.unwrap_or(0)) as f64 / 2.0
+}
+
+/// Wald-Wolfowitz runs test on above/below-median signs (ties dropped).
+/// Statistic is the z-score of the run count; a stream with only one side
+/// (e.g. constant) has no runs structure and gets p = 0.
+fn runs_test(bytes: &[u8]) -> (&'static str, f64, f64) {
+    const NAME: &str = "runs_median";
+    if bytes.is_empty() {
+        return (NAME, 0.0, 1.0);
+    }
+    let med = median_byte(bytes);
+    let (mut n1, mut n2, mut runs) = (0u64, 0u64, 0u64);
+    let mut prev: Option<bool> = None;
+    for &b in bytes {
+        let x = b as f64;
+        if x == med {
+            continue;
+        }
+        let above = x > med;
+        if above {
+            n1 += 1;
+        } else {
+            n2 += 1;
+        }
+        if prev != Some(above) {
+            runs += 1;
+        }
+        prev = Some(above);
+    }
+    if n1 == 0 || n2 == 0 {
+        return (NAME, 0.0, 0.0);
+    }
+
+    let (a, b) = (n1 as f64, n2 as f64);
+    let n = a + b;
+    let mean = 2.0 * a * b / n + 1.0;
+    let var = 2.0 * a * b * (2.0 * a * b - n) / (n * n * (n - 1.0));
+    if var <= 0.0 {
+        return (NAME, 0.0, 0.0);
+    }
+    let z = (runs as f64 - mean) / var.sqrt();
+    (NAME, z, normal_two_sided(z))
+}
+
+/// ENT-style circular lag-1 serial correlation coefficient. Under independence
+/// it is ~N(0, 1/n). A constant stream is reported as fully correlated.
+fn serial_correlation(bytes: &[u8]) -> (&'static str, f64, f64) {
+    const NAME: &str = "serial_corr";
+    let n = bytes.len();
+    if n < 2 {
+        return (NAME, 0.0, 1.0);
+    }
+    let (mut s1, mut s2, mut sc) = (0.0f64, 0.0f64, 0.0f64);
+    for i in 0..n {
+        let x = bytes[i] as f64;
+        let y = bytes[(i + 1) % n] as f64;
+        s1 += x;
+        s2 += x * x;
+        sc += x * y;
+    }
+    let nf = n as f64;
+    let den = nf * s2 - s1 * s1;
+    if den <= 0.0 {
+        return (NAME, 1.0, 0.0);
+    }
+    let r = (nf * sc - s1 * s1) / den;
+    (NAME, r, normal_two_sided(r * nf.sqrt()))
+}
+
+/// NIST SP 800-22 approximate entropy over the MSB-first bit sequence with
+/// circular m-bit blocks. Statistic is ApEn(m); `2n(ln 2 - ApEn)` is
+/// chi-squared with 2^m dof.
+fn approximate_entropy_bits(bytes: &[u8], m: u32) -> (&'static str, f64, f64) {
+    const NAME: &str = "apen_m2";
+    let n = bytes.len() * 8;
+    if n == 0 {
+        return (NAME, 0.0, 1.0);
+    }
+    let bit = |i: usize| ((bytes[(i % n) / 8] >> (7 - (i % n) % 8)) & 1) as usize;
+
+    let phi = |len: u32| -> f64 {
+        let mut counts = vec![0u64; 1usize << len];
+        for i in 0..n {
+            let mut v = 0usize;
+            for k in 0..len as usize {
+                v = (v << 1) | bit(i + k);
+            }
+            counts[v] += 1;
+        }
+        counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let p = c as f64 / n as f64;
+                p * p.ln()
+            })
+            .sum()
+    };
+
+    let apen = phi(m) - phi(m + 1);
+    let chi2 = (2.0 * n as f64 * (std::f64::consts::LN_2 - apen)).max(0.0);
+    (NAME, apen, chi2_sf_exact_even(chi2, 1u64 << m))
+}
+
+/// Upper tail of chi-squared for even dof `k` (closed form); larger k falls back
+/// to the Wilson-Hilferty approximation.
+fn chi2_sf_exact_even(x: f64, k: u64) -> f64 {
+    if k > 64 {
+        return chi2_sf(x, k as f64);
+    }
+    let h = x / 2.0;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for j in 1..(k / 2) {
+        term *= h / j as f64;
+        sum += term;
+    }
+    ((-h).exp() * sum).clamp(0.0, 1.0)
+}
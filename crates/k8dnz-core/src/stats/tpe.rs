@@ -0,0 +1,79 @@
+// crates/k8dnz-core/src/stats/tpe.rs
+//
+// Ticks-per-emission (tpe) distribution: the gaps between consecutive emissions.
+// A high stddev means bursty emissions, which makes timemaps less compressible.
+
+/// Online mean/stddev/min/max of emission gaps (Welford's update, O(1) memory).
+#[derive(Clone, Copy, Debug)]
+pub struct TpeStats {
+    count: u64,
+    min: u64,
+    max: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Default for TpeStats {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            min: u64::MAX,
+            max: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+}
+
+impl TpeStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn update(&mut self, gap_ticks: u64) {
+        self.count += 1;
+        self.min = self.min.min(gap_ticks);
+        self.max = self.max.max(gap_ticks);
+
+        let x = gap_ticks as f64;
+        let d = x - self.mean;
+        self.mean += d / self.count as f64;
+        self.m2 += d * (x - self.mean);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Population standard deviation (divides by n).
+    pub fn stddev(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            (self.m2 / self.count as f64).sqrt()
+        }
+    }
+
+    /// 0 until the first update.
+    pub fn min(&self) -> u64 {
+        if self.count == 0 {
+            0
+        } else {
+            self.min
+        }
+    }
+
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// (mean, stddev, min, max).
+    pub fn summary(&self) -> (f64, f64, u64, u64) {
+        (self.mean(), self.stddev(), self.min(), self.max())
+    }
+}
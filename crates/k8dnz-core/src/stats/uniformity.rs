@@ -0,0 +1,131 @@
+// crates/k8dnz-core/src/stats/uniformity.rs
+//
+// Goodness-of-fit of a byte stream against the uniform distribution on 0..=255.
+// A good residual should pass both; p-values are approximations (see each fn).
+
+use crate::signal::token::PackedByte;
+
+/// Test statistic plus approximate p-value (probability of a result at least this
+/// extreme under the uniform hypothesis).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniformityTest {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+impl UniformityTest {
+    pub fn passes(&self, alpha: f64) -> bool {
+        self.p_value >= alpha
+    }
+}
+
+/// Pearson chi-squared against uniform, 255 degrees of freedom:
+/// `chi2 = sum_k (observed_k - n/256)^2 / (n/256)`.
+///
+/// p-value uses the Wilson-Hilferty cube-root normal approximation, which is
+/// accurate to ~1e-3 at this many degrees of freedom.
+pub fn chi_squared_uniform(bytes: &[u8]) -> UniformityTest {
+    let n = bytes.len();
+    if n == 0 {
+        return UniformityTest {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let counts = PackedByte::frequency_table(bytes.iter().map(|&b| PackedByte(b)));
+    let expected = n as f64 / 256.0;
+    let chi2: f64 = counts
+        .iter()
+        .map(|&c| {
+            let d = c as f64 - expected;
+            d * d / expected
+        })
+        .sum();
+
+    UniformityTest {
+        statistic: chi2,
+        p_value: chi2_sf(chi2, 255.0),
+    }
+}
+
+/// One-sample Kolmogorov-Smirnov against the discrete uniform CDF `(k+1)/256`.
+///
+/// p-value uses the asymptotic Kolmogorov distribution with Stephens' small-n
+/// correction. For a discrete reference this is conservative (p is too high).
+pub fn ks_uniform(bytes: &[u8]) -> UniformityTest {
+    let n = bytes.len();
+    if n == 0 {
+        return UniformityTest {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let counts = PackedByte::frequency_table(bytes.iter().map(|&b| PackedByte(b)));
+    let nf = n as f64;
+    let mut cum = 0u64;
+    let mut d = 0.0f64;
+    for (k, &c) in counts.iter().enumerate() {
+        cum += c;
+        let emp = cum as f64 / nf;
+        let model = (k as f64 + 1.0) / 256.0;
+        d = d.max((emp - model).abs());
+    }
+
+    let sn = nf.sqrt();
+    let lambda = (sn + 0.12 + 0.11 / sn) * d;
+    UniformityTest {
+        statistic: d,
+        p_value: kolmogorov_q(lambda),
+    }
+}
+
+/// Upper tail of chi-squared with `k` dof (Wilson-Hilferty).
+pub(super) fn chi2_sf(x: f64, k: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let v = 2.0 / (9.0 * k);
+    let z = ((x / k).cbrt() - (1.0 - v)) / v.sqrt();
+    0.5 * erfc(z / std::f64::consts::SQRT_2)
+}
+
+/// Q_KS(lambda) = 2 * sum_{j>=1} (-1)^(j-1) exp(-2 j^2 lambda^2).
+fn kolmogorov_q(lambda: f64) -> f64 {
+    if lambda < 1e-3 {
+        return 1.0;
+    }
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let jf = j as f64;
+        let term = (-2.0 * jf * jf * lambda * lambda).exp();
+        sum += sign * term;
+        if term < 1e-12 {
+            break;
+        }
+        sign = -sign;
+    }
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// Complementary error function (Chebyshev fit, fractional error < 1.2e-7).
+pub(super) fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let r = t
+        * (-z * z - 1.265_512_23
+            + t * (1.000_023_68
+                + t * (0.374_091_96
+                    + t * (0.096_784_18
+                        + t * (-0.186_288_06
+                            + t * (0.278_868_07
+                                + t * (-1.135_203_98
+                                    + t * (1.488_515_87
+                                        + t * (-0.822_152_23 + t * 0.170_872_77)))))))))
+            .exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
@@ -19,17 +19,38 @@
 //   values_count: varint (must equal popcount(bitmap))
 //   values[values_count] (u8 each; actual symbol values at mismatch positions, in increasing pos order)
 //
+// 3) VERBATIM (fallback when the prediction is mostly wrong):
+//   sentinel: varint = u64::MAX
+//   fmt: varint = 3
+//   len: varint          (stream length in symbols)
+//   bits: varint         (bits per symbol, 1..=8; smallest width holding max(actual))
+//   packed[ceil(len*bits/8)] (the actual stream, bitpack::pack_symbols layout)
+//   Decodes to one entry per position, so applying it overwrites the prediction.
+//
+// Any of the above may be wrapped in a zstd envelope (read by `decode_auto`):
+//   magic[4] = "PZST"
+//   level: u8            (zstd level used; informational)
+//   zstd frame of the raw blob
+// `smaller_encoding` only emits the envelope when it is smaller, and never leaves a raw
+// blob that happens to start with the magic, so detection is unambiguous.
+//
 // Notes:
 // - New decode can read legacy sparse and new dense.
-// - Old decode cannot read new dense (that’s fine; we only require forward-compat).
+// - Old decode cannot read new dense or verbatim (that’s fine; we only require forward-compat).
 // - In-memory representation remains a sparse list of (pos,value) to avoid changing callers.
 
 use crate::error::{K8Error, Result};
+use crate::signal::bitpack;
 use crate::symbol::varint;
 
 const SENTINEL_NEWFMT: u64 = u64::MAX;
 const FMT_SPARSE: u64 = 1;
 const FMT_DENSE: u64 = 2;
+const FMT_VERBATIM: u64 = 3;
+
+/// Magic of the zstd envelope around a patch blob.
+pub const MAGIC_PZST: [u8; 4] = *b"PZST";
+const PZST_HEADER_LEN: usize = 5;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PatchList {
@@ -64,17 +85,63 @@ impl PatchList {
         Ok(pl)
     }
 
+    /// `from_pred_actual` that gives up with `K8Error::TooManyMismatches` as soon as more
+    /// than `max_mismatches` positions differ, before building a patch that would cost
+    /// more than the data itself. Callers then store the stream with `encode_verbatim`.
+    pub fn from_symbol_streams(pred: &[u8], actual: &[u8], max_mismatches: usize) -> Result<Self> {
+        if pred.len() != actual.len() {
+            return Err(K8Error::Validation("patch: pred/actual len mismatch".into()));
+        }
+        let count = pred.len() - bitpack::symbol_match_count(pred, actual) as usize;
+        if count > max_mismatches {
+            return Err(K8Error::TooManyMismatches {
+                count,
+                limit: max_mismatches,
+            });
+        }
+        Self::from_pred_actual(pred, actual)
+    }
+
     pub fn apply_to_pred(&self, pred: &mut [u8]) -> Result<()> {
         for &(pos, value) in &self.entries {
             let idx = pos as usize;
             if idx >= pred.len() {
-                return Err(K8Error::Validation("patch: position out of range".into()));
+                return Err(K8Error::OutOfBounds { name: "patch position", offset: idx, len: 1 });
             }
             pred[idx] = (value & 0xFF) as u8;
         }
         Ok(())
     }
 
+    /// Wide-symbol variant (e.g. numeric lane values). Callers must encode with
+    /// `encode_sparse_legacy`, since the dense format stores u8 values only.
+    pub fn from_pred_actual_u64(pred: &[u64], actual: &[u64]) -> Result<Self> {
+        if pred.len() != actual.len() {
+            return Err(K8Error::Validation("patch: pred/actual len mismatch".into()));
+        }
+        let mut pl = PatchList {
+            entries: Vec::new(),
+            len: pred.len() as u64,
+        };
+        for (i, (&p, &a)) in pred.iter().zip(actual.iter()).enumerate() {
+            if p != a {
+                pl.entries.push((i as u64, a));
+            }
+        }
+        Ok(pl)
+    }
+
+    pub fn apply_to_pred_u64(&self, pred: &mut [u64]) -> Result<()> {
+        for &(pos, value) in &self.entries {
+            let idx = pos as usize;
+            if idx >= pred.len() {
+                return Err(K8Error::OutOfBounds { name: "patch position", offset: idx, len: 1 });
+            }
+            pred[idx] = value;
+        }
+        Ok(())
+    }
+
     /// Encodes using whichever format is smaller (legacy sparse vs new dense),
     /// when `self.len` is known (>0). If len is unknown, falls back to sparse.
     pub fn encode(&self) -> Vec<u8> {
@@ -152,6 +219,45 @@ impl PatchList {
         out
     }
 
+    /// Verbatim encoding of the whole `actual` stream (format 3), bit-packed at the
+    /// narrowest width that holds its largest symbol.
+    pub fn encode_verbatim(actual: &[u8]) -> Vec<u8> {
+        let max = actual.iter().copied().max().unwrap_or(0);
+        let bits = (8 - max.leading_zeros() as u8).max(1);
+
+        let mut out = Vec::new();
+        varint::put_u64(SENTINEL_NEWFMT, &mut out);
+        varint::put_u64(FMT_VERBATIM, &mut out);
+        varint::put_u64(actual.len() as u64, &mut out);
+        varint::put_u64(bits as u64, &mut out);
+        out.extend_from_slice(&bitpack::pack_symbols(bits, actual).expect("bits in 1..=8"));
+        out
+    }
+
+    /// `encode` wrapped in the PZST zstd envelope, whatever the size.
+    pub fn encode_compressed(&self, level: i32) -> Result<Vec<u8>> {
+        compress_blob(&self.encode(), level)
+    }
+
+    /// True when `bytes` carries the PZST envelope.
+    pub fn is_compressed(bytes: &[u8]) -> bool {
+        bytes.starts_with(&MAGIC_PZST)
+    }
+
+    /// Decodes a raw blob or a PZST-wrapped one. The envelope may inflate to at most
+    /// `max_raw_len` bytes (see `max_raw_len`), so untrusted input cannot be a zstd bomb.
+    pub fn decode_auto(bytes: &[u8], max_raw_len: usize) -> Result<Self> {
+        if !Self::is_compressed(bytes) {
+            return Self::decode(bytes);
+        }
+        if bytes.len() < PZST_HEADER_LEN {
+            return Err(K8Error::TruncatedInput { context: "patch zstd envelope", expected: PZST_HEADER_LEN, got: bytes.len() });
+        }
+        let raw = zstd::bulk::decompress(&bytes[PZST_HEADER_LEN..], max_raw_len)
+            .map_err(|e| K8Error::Validation(format!("patch: zstd decode: {e}")))?;
+        Self::decode(&raw)
+    }
+
     pub fn decode(bytes: &[u8]) -> Result<Self> {
         let mut i = 0usize;
 
@@ -173,14 +279,14 @@ impl PatchList {
                     let bitmap_len = varint::get_u64(bytes, &mut i)? as usize;
 
                     if i + bitmap_len > bytes.len() {
-                        return Err(K8Error::Validation("patch: dense bitmap OOB".into()));
+                        return Err(K8Error::OutOfBounds { name: "patch dense bitmap", offset: i, len: bitmap_len });
                     }
                     let bitmap = &bytes[i..i + bitmap_len];
                     i += bitmap_len;
 
                     let values_count = varint::get_u64(bytes, &mut i)? as usize;
                     if i + values_count > bytes.len() {
-                        return Err(K8Error::Validation("patch: dense values OOB".into()));
+                        return Err(K8Error::OutOfBounds { name: "patch dense values", offset: i, len: values_count });
                     }
                     let values = &bytes[i..i + values_count];
                     i += values_count;
@@ -192,7 +298,7 @@ impl PatchList {
                     // Validate bitmap length matches len (allow a larger bitmap only if extra bits are zero).
                     let need_bitmap_len = ((len as usize) + 7) / 8;
                     if bitmap_len < need_bitmap_len {
-                        return Err(K8Error::Validation("patch: dense bitmap too short".into()));
+                        return Err(K8Error::TruncatedInput { context: "patch dense bitmap", expected: need_bitmap_len, got: bitmap_len });
                     }
                     // Count bits only up to len.
                     let pop = popcount_bitmap_prefix(bitmap, len as usize);
@@ -220,6 +326,27 @@ impl PatchList {
 
                     return Ok(Self { entries, len });
                 }
+                FMT_VERBATIM => {
+                    let len = varint::get_u64(bytes, &mut i)?;
+                    let bits = varint::get_u64(bytes, &mut i)?;
+                    if !(1..=8).contains(&bits) {
+                        return Err(K8Error::Validation(format!("patch: verbatim bits={}", bits)));
+                    }
+                    let need = (len as usize)
+                        .checked_mul(bits as usize)
+                        .map(|b| b.div_ceil(8))
+                        .ok_or_else(|| K8Error::Validation("patch: verbatim len overflow".into()))?;
+                    if bytes.len() - i != need {
+                        return Err(K8Error::Validation("patch: verbatim payload size mismatch".into()));
+                    }
+                    let values = bitpack::unpack_symbols(bits as u8, &bytes[i..], len as usize)?;
+                    let entries = values
+                        .into_iter()
+                        .enumerate()
+                        .map(|(pos, v)| (pos as u64, v as u64))
+                        .collect();
+                    return Ok(Self { entries, len });
+                }
                 _ => {
                     return Err(K8Error::Validation(format!("patch: unknown fmt={}", fmt)));
                 }
@@ -251,6 +378,36 @@ impl PatchList {
     }
 }
 
+/// Upper bound on the raw encoding of any patch over a `len`-symbol stream: every
+/// position patched in the legacy sparse form, with 10-byte varints. Used to cap
+/// `decode_auto`.
+pub fn max_raw_len(len: usize) -> usize {
+    len.saturating_mul(20).saturating_add(16)
+}
+
+/// Wraps an encoded patch blob in the PZST envelope. `level` is clamped to 0..=22.
+pub fn compress_blob(raw: &[u8], level: i32) -> Result<Vec<u8>> {
+    let level = level.clamp(0, 22);
+    let z = zstd::encode_all(raw, level)
+        .map_err(|e| K8Error::Validation(format!("patch: zstd encode: {e}")))?;
+    let mut out = Vec::with_capacity(PZST_HEADER_LEN + z.len());
+    out.extend_from_slice(&MAGIC_PZST);
+    out.push(level as u8);
+    out.extend_from_slice(&z);
+    Ok(out)
+}
+
+/// `raw` unless its PZST envelope is smaller. A raw blob that starts with the magic is
+/// always wrapped, so `decode_auto` cannot mistake it for an envelope.
+pub fn smaller_encoding(raw: Vec<u8>, level: i32) -> Result<Vec<u8>> {
+    let z = compress_blob(&raw, level)?;
+    Ok(if z.len() < raw.len() || PatchList::is_compressed(&raw) {
+        z
+    } else {
+        raw
+    })
+}
+
 // Popcount only up to `n_bits` bits (ignore trailing bits in last byte).
 fn popcount_bitmap_prefix(bitmap: &[u8], n_bits: usize) -> usize {
     if n_bits == 0 {
@@ -2,6 +2,8 @@
 //
 // Minimal unsigned varint (LEB128-like) for compact patch encoding.
 
+use std::io::{self, Read, Write};
+
 use crate::error::{K8Error, Result};
 
 pub fn put_u64(mut v: u64, out: &mut Vec<u8>) {
@@ -18,7 +20,7 @@ pub fn get_u64(bytes: &[u8], i: &mut usize) -> Result<u64> {
 
     loop {
         if *i >= bytes.len() {
-            return Err(K8Error::Validation("varint: eof".into()));
+            return Err(K8Error::TruncatedInput { context: "varint", expected: *i + 1, got: bytes.len() });
         }
         let b = bytes[*i];
         *i += 1;
@@ -38,3 +40,43 @@ pub fn get_u64(bytes: &[u8], i: &mut usize) -> Result<u64> {
         }
     }
 }
+
+/// Streaming counterpart of `put_u64`: same bytes, written straight to `w`.
+pub fn write_u64<W: Write + ?Sized>(w: &mut W, mut v: u64) -> io::Result<()> {
+    let mut buf = [0u8; 10];
+    let mut n = 0usize;
+    while v >= 0x80 {
+        buf[n] = ((v as u8) & 0x7F) | 0x80;
+        v >>= 7;
+        n += 1;
+    }
+    buf[n] = v as u8;
+    w.write_all(&buf[..=n])
+}
+
+/// Streaming counterpart of `get_u64`. EOF mid-varint is `UnexpectedEof`;
+/// overflow is `InvalidData`.
+pub fn read_u64<R: Read + ?Sized>(r: &mut R) -> io::Result<u64> {
+    let mut acc: u64 = 0;
+    let mut shift: u32 = 0;
+
+    loop {
+        let mut b = [0u8; 1];
+        r.read_exact(&mut b)?;
+        let b = b[0];
+
+        let low = (b & 0x7F) as u64;
+        if shift >= 64 || ((low << shift) >> shift) != low {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "varint: overflow"));
+        }
+        acc |= low << shift;
+
+        if (b & 0x80) == 0 {
+            return Ok(acc);
+        }
+        shift += 7;
+        if shift > 63 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "varint: too long"));
+        }
+    }
+}
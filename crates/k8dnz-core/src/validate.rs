@@ -1,6 +1,9 @@
 use crate::error::{K8Error, Result};
 use crate::fixed::turn32::Turn32;
-use crate::recipe::recipe::{Alphabet, Recipe, ResetMode};
+use crate::recipe::format::{
+    FORMAT_VERSION, FORMAT_VERSION_QUANT_GAMMA, FORMAT_VERSION_RGB, FORMAT_VERSION_SOFT_CLAMP,
+};
+use crate::recipe::recipe::{Alphabet, KeystreamMix, PayloadKind, Recipe, ResetMode};
 
 pub fn validate_recipe(r: &Recipe) -> Result<()> {
     // FREE_ORBIT invariant: different speeds (magnitudes).
@@ -41,6 +44,107 @@ pub fn validate_recipe(r: &Recipe) -> Result<()> {
     if r.quant.min >= r.quant.max {
         return Err(K8Error::Validation("quant.min must be < quant.max".into()));
     }
+    if let Some(g) = r.quant_gamma {
+        if !(g.is_finite() && g > 0.0) {
+            return Err(K8Error::Validation(format!(
+                "quant_gamma must be finite and > 0 (got {g})"
+            )));
+        }
+    }
 
     Ok(())
 }
+
+/// Non-fatal findings from `validate_deep`. A recipe carrying these still runs,
+/// but is probably not what the author meant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// quant.max - quant.min < 16: fewer raw values than N16 bins.
+    NarrowQuantRange { width: i64 },
+    /// Field clamp spans less than the quant range, so the outer bins never fire.
+    ClampNarrowerThanQuant { clamp_width: i64, quant_width: i64 },
+    /// keystream_mix only shapes the model stream for residual payloads.
+    MixWithoutResidual { mix: KeystreamMix, payload: PayloadKind },
+    /// |quant.shift| exceeds the quant width, so every sample lands in an edge bin.
+    ShiftOutOfRange { shift: i64, width: i64 },
+    /// Recipe version is outside the v4..=`newest` range this build writes.
+    VersionMismatch { found: u16, newest: u16 },
+}
+
+impl std::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NarrowQuantRange { width } => {
+                write!(f, "quant range width={} is < 16 (fewer values than bins)", width)
+            }
+            Self::ClampNarrowerThanQuant {
+                clamp_width,
+                quant_width,
+            } => write!(
+                f,
+                "field_clamp width={} is narrower than quant width={}",
+                clamp_width, quant_width
+            ),
+            Self::MixWithoutResidual { mix, payload } => write!(
+                f,
+                "keystream_mix={:?} has no effect unless payload_kind=ResidualXor (got {:?})",
+                mix, payload
+            ),
+            Self::ShiftOutOfRange { shift, width } => write!(
+                f,
+                "quant.shift={} is outside [-{}, +{}]",
+                shift, width, width
+            ),
+            Self::VersionMismatch { found, newest } => write!(
+                f,
+                "recipe version={} (this build writes v{}..=v{})",
+                found, FORMAT_VERSION, newest
+            ),
+        }
+    }
+}
+
+/// Soft checks on top of `validate_recipe`. Never fails; returns every finding.
+pub fn validate_deep(r: &Recipe) -> Vec<ValidationWarning> {
+    let mut out = Vec::new();
+
+    let quant_width = r.quant.max.saturating_sub(r.quant.min);
+    let clamp_width = r.field_clamp.max.saturating_sub(r.field_clamp.min);
+
+    if quant_width < 16 {
+        out.push(ValidationWarning::NarrowQuantRange { width: quant_width });
+    }
+    if clamp_width < quant_width {
+        out.push(ValidationWarning::ClampNarrowerThanQuant {
+            clamp_width,
+            quant_width,
+        });
+    }
+    if r.keystream_mix != KeystreamMix::None && r.payload_kind != PayloadKind::ResidualXor {
+        out.push(ValidationWarning::MixWithoutResidual {
+            mix: r.keystream_mix,
+            payload: r.payload_kind,
+        });
+    }
+    if r.quant.shift.unsigned_abs() > quant_width.unsigned_abs() {
+        out.push(ValidationWarning::ShiftOutOfRange {
+            shift: r.quant.shift,
+            width: quant_width,
+        });
+    }
+    if ![
+        FORMAT_VERSION,
+        FORMAT_VERSION_RGB,
+        FORMAT_VERSION_SOFT_CLAMP,
+        FORMAT_VERSION_QUANT_GAMMA,
+    ]
+    .contains(&r.version)
+    {
+        out.push(ValidationWarning::VersionMismatch {
+            found: r.version,
+            newest: FORMAT_VERSION_QUANT_GAMMA,
+        });
+    }
+
+    out
+}
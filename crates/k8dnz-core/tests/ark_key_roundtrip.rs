@@ -40,3 +40,75 @@ fn ark1s_roundtrip_matches_recipe_bytes_semantics() {
         assert_eq!(a.amp, b.amp);
     }
 }
+
+#[test]
+fn ark1s_roundtrip_keeps_v5_rgb_params() {
+    use k8dnz_core::recipe::format::{recipe_id_hex, FORMAT_VERSION_RGB};
+
+    let mut r1 = default_recipe();
+    r1.version = FORMAT_VERSION_RGB;
+    r1.rgb.backend = 0;
+    r1.rgb.base_a = [1, 2, 3];
+    r1.rgb.g_step = -7;
+    r1.rgb.p_scale = 5;
+
+    let r2 = decode_ark1s(&encode_ark1s(&r1)).unwrap();
+    assert_eq!(r2.version, FORMAT_VERSION_RGB);
+    assert_eq!(r2.rgb.backend, 0);
+    assert_eq!(r2.rgb.base_a, [1, 2, 3]);
+    assert_eq!(r2.rgb.g_step, -7);
+    assert_eq!(r2.rgb.p_scale, 5);
+    assert_eq!(recipe_id_hex(&r1), recipe_id_hex(&r2));
+}
+
+#[test]
+fn ark1s_v4_keys_are_unchanged() {
+    let s = encode_ark1s(&default_recipe());
+    // string-format byte 0 spells as a leading "00" in base32
+    assert!(s.starts_with("ARK1S:00"));
+}
+
+#[test]
+fn ark1s_roundtrip_keeps_v6_soft_knee() {
+    use k8dnz_core::recipe::format::{recipe_id_hex, FORMAT_VERSION_SOFT_CLAMP};
+
+    let mut r1 = default_recipe();
+    r1.version = FORMAT_VERSION_SOFT_CLAMP;
+    r1.field_clamp.soft_knee = Some(1_234);
+    r1.rgb.g_step = 3;
+
+    let r2 = decode_ark1s(&encode_ark1s(&r1)).unwrap();
+    assert_eq!(r2.version, FORMAT_VERSION_SOFT_CLAMP);
+    assert_eq!(r2.field_clamp.soft_knee, Some(1_234));
+    assert_eq!(r2.rgb.g_step, 3);
+    assert_eq!(recipe_id_hex(&r1), recipe_id_hex(&r2));
+
+    // v6 with a hard clamp stays a hard clamp
+    r1.field_clamp.soft_knee = None;
+    let r2 = decode_ark1s(&encode_ark1s(&r1)).unwrap();
+    assert_eq!(r2.field_clamp.soft_knee, None);
+    assert_eq!(recipe_id_hex(&r1), recipe_id_hex(&r2));
+}
+
+#[test]
+fn ark1s_roundtrip_keeps_v7_quant_gamma() {
+    use k8dnz_core::recipe::format::{recipe_id_hex, FORMAT_VERSION_QUANT_GAMMA};
+
+    let mut r1 = default_recipe();
+    r1.version = FORMAT_VERSION_QUANT_GAMMA;
+    r1.quant_gamma = Some(0.5);
+    r1.field_clamp.soft_knee = Some(77);
+    r1.rgb.p_scale = -4;
+
+    let r2 = decode_ark1s(&encode_ark1s(&r1)).unwrap();
+    assert_eq!(r2.version, FORMAT_VERSION_QUANT_GAMMA);
+    assert_eq!(r2.quant_gamma, Some(0.5));
+    assert_eq!(r2.field_clamp.soft_knee, Some(77));
+    assert_eq!(r2.rgb.p_scale, -4);
+    assert_eq!(recipe_id_hex(&r1), recipe_id_hex(&r2));
+
+    r1.quant_gamma = None;
+    let r2 = decode_ark1s(&encode_ark1s(&r1)).unwrap();
+    assert_eq!(r2.quant_gamma, None);
+    assert_eq!(recipe_id_hex(&r1), recipe_id_hex(&r2));
+}
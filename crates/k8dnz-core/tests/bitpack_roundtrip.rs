@@ -1,6 +1,9 @@
 // crates/k8dnz-core/tests/bitpack_roundtrip.rs
 
-use k8dnz_core::signal::bitpack::{pack_symbols, unpack_symbols};
+use k8dnz_core::signal::bitpack::{
+    hamming_distance_u8, pack_symbols, pack_symbols_to_writer, symbol_match_count, unpack_symbols,
+    unpack_symbols_from_reader,
+};
 
 fn lcg_next(x: &mut u64) -> u64 {
     // deterministic, not crypto
@@ -31,6 +34,63 @@ fn bitpack_roundtrip_all_widths() {
     }
 }
 
+#[test]
+fn bitpack_stream_matches_batch() {
+    let mut seed: u64 = 0x0bad_5eed_0000_0001;
+
+    for bits in 1u8..=8u8 {
+        let mask: u8 = ((1u16 << bits) - 1) as u8;
+
+        // Sizes around the 4 KB internal chunk boundary.
+        for &n in &[0usize, 1, 7, 9, 4095, 4096, 4097, 32768, 32769, 40_001] {
+            let syms: Vec<u8> = (0..n)
+                .map(|_| (lcg_next(&mut seed) >> 56) as u8 & mask)
+                .collect();
+
+            let batch = pack_symbols(bits, &syms).expect("pack ok");
+            let mut streamed: Vec<u8> = Vec::new();
+            pack_symbols_to_writer(bits, &syms, &mut streamed).expect("stream pack ok");
+            assert_eq!(streamed, batch, "bits={} n={}", bits, n);
+
+            let mut reader: &[u8] = &streamed;
+            let out = unpack_symbols_from_reader(bits, &mut reader, n).expect("stream unpack ok");
+            assert_eq!(out, syms, "bits={} n={}", bits, n);
+            assert!(
+                reader.is_empty(),
+                "reader must consume exactly the packed bytes"
+            );
+            assert_eq!(out, unpack_symbols(bits, &batch, n).unwrap());
+        }
+    }
+}
+
+#[test]
+fn bitpack_stream_leaves_trailing_bytes_unread() {
+    let packed = pack_symbols(3, &[1, 2, 3, 4, 5]).unwrap();
+    let mut data = packed.clone();
+    data.extend_from_slice(b"tail");
+    let mut reader: &[u8] = &data;
+    assert_eq!(
+        unpack_symbols_from_reader(3, &mut reader, 5).unwrap(),
+        vec![1, 2, 3, 4, 5]
+    );
+    assert_eq!(reader, b"tail");
+}
+
+#[test]
+fn bitpack_stream_errors() {
+    let mut sink: Vec<u8> = Vec::new();
+    let err = pack_symbols_to_writer(2, &[0, 1, 4], &mut sink).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("out of range"));
+    assert!(pack_symbols_to_writer(9, &[0], &mut sink).is_err());
+
+    let mut short: &[u8] = &[0xFF];
+    let err = unpack_symbols_from_reader(4, &mut short, 3).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(unpack_symbols_from_reader(0, &mut &[0u8][..], 1).is_err());
+}
+
 #[test]
 fn bitpack_rejects_out_of_range_symbols() {
     let err = pack_symbols(2, &[0, 1, 2, 3, 4]).unwrap_err();
@@ -45,3 +105,39 @@ fn bitpack_rejects_bad_bitwidth() {
     assert!(unpack_symbols(0, &[0], 1).is_err());
     assert!(unpack_symbols(9, &[0], 1).is_err());
 }
+
+#[test]
+fn batch_compares_match_scalar() {
+    let mut seed: u64 = 0x0bad_5eed;
+    let a: Vec<u8> = (0..300)
+        .map(|_| (lcg_next(&mut seed) >> 56) as u8)
+        .collect();
+    // Mostly-equal copy so match counts are not trivially near zero.
+    let b: Vec<u8> = a
+        .iter()
+        .map(|&x| {
+            if lcg_next(&mut seed) >> 62 == 0 {
+                x ^ (lcg_next(&mut seed) >> 56) as u8
+            } else {
+                x
+            }
+        })
+        .collect();
+
+    for off in 0..9 {
+        for &n in &[0usize, 1, 7, 8, 9, 15, 16, 17, 31, 33, 64, 255, 291 - off] {
+            let (x, y) = (&a[off..off + n], &b[..n]);
+            let ham: u64 = x
+                .iter()
+                .zip(y)
+                .map(|(p, q)| (p ^ q).count_ones() as u64)
+                .sum();
+            let eq = x.iter().zip(y).filter(|(p, q)| p == q).count() as u64;
+            assert_eq!(hamming_distance_u8(x, y), ham, "off={off} n={n}");
+            assert_eq!(symbol_match_count(x, y), eq, "off={off} n={n}");
+        }
+    }
+
+    assert_eq!(symbol_match_count(&a, &a[..10]), 10);
+    assert_eq!(hamming_distance_u8(&[0xFF; 40], &[0x00; 33]), 33 * 8);
+}
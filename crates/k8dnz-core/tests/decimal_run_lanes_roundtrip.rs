@@ -0,0 +1,110 @@
+// crates/k8dnz-core/tests/decimal_run_lanes_roundtrip.rs
+
+use k8dnz_core::lane::{
+    self, LaneEncodeStats, OmegaProgram, TextLanesV2Config, K8L1_VERSION_V6, K8L1_VERSION_V7,
+};
+use k8dnz_core::recipe::{defaults::default_recipe, format};
+use k8dnz_core::repr::text_norm;
+
+const MAX_TICKS: u64 = 200_000_000;
+
+fn encode(input: &[u8], decimal_runs: bool) -> (Vec<u8>, lane::LaneEncodeStats) {
+    lane::encode_k8l1_with_config(
+        input,
+        &format::encode(&default_recipe()),
+        MAX_TICKS,
+        OmegaProgram::default(),
+        None,
+        TextLanesV2Config {
+            decimal_runs,
+            ..Default::default()
+        },
+    )
+    .expect("encode")
+}
+
+/// Market-wrap style prose: years, prices, volumes and percentages.
+fn financial_news(items: usize) -> Vec<u8> {
+    const NAMES: [&str; 6] = [
+        "Acme Corp",
+        "Globex",
+        "Initech",
+        "Umbrella",
+        "Hooli",
+        "Vandelay",
+    ];
+    let mut s = String::new();
+    let mut x: u64 = 0x2545_F491_4F6C_DD1D;
+    for i in 0..items {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        s.push_str(&format!(
+            "{} shares closed at ${}.99 on March {}, 2024, up {}.{}% on volume of {} after Q{} {} results.\n",
+            NAMES[i % NAMES.len()],
+            100 + x % 900,
+            1 + (x >> 12) % 28,
+            (x >> 20) % 10,
+            (x >> 24) % 100,
+            10_000 + (x >> 32) % 90_000,
+            1 + (x >> 50) % 4,
+            2023 + (x >> 60) % 2,
+        ));
+    }
+    s.into_bytes()
+}
+
+/// Decimal-run artifacts are v6, or v7 once any patch blob is PZST-compressed.
+fn decimal_run_version(stats: &LaneEncodeStats) -> u8 {
+    if stats.compressed_patches.is_empty() {
+        K8L1_VERSION_V6
+    } else {
+        K8L1_VERSION_V7
+    }
+}
+
+#[test]
+fn decimal_runs_shrink_financial_news() {
+    let input = financial_news(40);
+    let (plain, plain_stats) = encode(&input, false);
+    let (art, stats) = encode(&input, true);
+    eprintln!(
+        "financial news {} B: current codec {} B (v{}), decimal runs {} B (v{}, {} runs)",
+        input.len(),
+        plain.len(),
+        plain[4],
+        art.len(),
+        art[4],
+        stats.n_decimal_runs
+    );
+
+    assert_eq!(plain_stats.n_decimal_runs, 0);
+    assert_ne!(plain[4], K8L1_VERSION_V6);
+    assert_eq!(art[4], decimal_run_version(&stats));
+    assert!(stats.n_decimal_runs > 0);
+    assert!(art.len() < plain.len(), "{} vs {}", art.len(), plain.len());
+
+    let decoded = lane::decode_k8l1(&art).expect("decode");
+    assert_eq!(decoded, text_norm::normalize_newlines(&input));
+}
+
+#[test]
+fn decimal_run_edge_runs_roundtrip() {
+    // leading zeros, exactly three digits, a run split at the numeric chunk size
+    let mut input = financial_news(40);
+    input.extend_from_slice(b"000 x 12 345 0099 9999999999999999999123 a1b22c333 007.50 0001000\n");
+    let (art, stats) = encode(&input, true);
+    assert_eq!(art[4], decimal_run_version(&stats));
+    assert!(stats.n_decimal_runs > 0);
+    let decoded = lane::decode_k8l1(&art).expect("decode");
+    assert_eq!(decoded, input);
+}
+
+#[test]
+fn decimal_runs_need_a_long_enough_run() {
+    let input = b"a1 b22 c3 d45, e6.\n";
+    let (art, stats) = encode(input, true);
+    assert_eq!(stats.n_decimal_runs, 0);
+    assert_ne!(art[4], K8L1_VERSION_V6);
+    assert_eq!(lane::decode_k8l1(&art).expect("decode"), input.to_vec());
+}
@@ -0,0 +1,47 @@
+// crates/k8dnz-core/tests/emission_buffer.rs
+
+use k8dnz_core::dynamics::emission_buffer::EmissionBuffer;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+#[test]
+fn ring_keeps_the_last_size_bytes_by_absolute_position() {
+    let mut b = EmissionBuffer::with_origin(4, 100);
+    assert!(b.is_empty());
+    assert_eq!(b.get(100), None);
+
+    for x in 0..10u8 {
+        b.push(x);
+    }
+    assert_eq!(b.len(), 10);
+    assert_eq!(b.capacity(), 4);
+    assert_eq!(b.first_held(), 106);
+    assert_eq!(b.get(105), None, "overwritten");
+    for pos in 106..110 {
+        assert_eq!(b.get(pos), Some((pos - 100) as u8));
+    }
+    assert_eq!(b.get(110), None, "not pushed yet");
+    assert_eq!(b.get(99), None, "before origin");
+}
+
+#[test]
+fn engine_buffer_matches_the_packed_stream() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    e.run_emissions(50, 50_000_000);
+
+    let mut b = e.emission_buffer(64);
+    assert_eq!(b.origin(), 50);
+    e.run_with_emission_callback(200, 50_000_000, &mut |_, tok| b.push(tok.pack_byte()));
+    assert_eq!(b.len(), 200);
+
+    let full: Vec<u8> = Engine::new(default_recipe())
+        .unwrap()
+        .run_emissions(250, 50_000_000)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    for pos in b.first_held()..250 {
+        assert_eq!(b.get(pos), Some(full[pos as usize]), "pos {pos}");
+    }
+    assert_eq!(b.first_held(), 250 - 64);
+}
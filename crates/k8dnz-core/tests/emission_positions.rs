@@ -0,0 +1,30 @@
+// crates/k8dnz-core/tests/emission_positions.rs
+
+use k8dnz_core::{recipe::defaults::default_recipe, Engine};
+
+#[test]
+fn positions_are_monotonic_and_match_run_emissions() {
+    let r = default_recipe();
+    let mut e1 = Engine::new(r.clone()).unwrap();
+    let mut e2 = Engine::new(r).unwrap();
+
+    let toks = e1.run_emissions(256, 5_000_000);
+    let pos = e2.run_emissions_with_positions(256, 5_000_000);
+
+    assert_eq!(pos.len(), 256);
+    for (k, (idx, tok)) in pos.iter().enumerate() {
+        assert_eq!(*idx, k as u64);
+        assert_eq!(*tok, toks[k]);
+    }
+}
+
+#[test]
+fn positions_continue_from_current_engine_state() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let _ = e.run_emissions(10, 5_000_000);
+
+    let pos = e.run_emissions_with_positions(32, 5_000_000);
+    assert_eq!(pos.first().map(|p| p.0), Some(10));
+    assert!(pos.windows(2).all(|w| w[1].0 == w[0].0 + 1));
+    assert_eq!(e.stats.emissions, 42);
+}
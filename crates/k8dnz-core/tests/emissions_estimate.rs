@@ -0,0 +1,42 @@
+// crates/k8dnz-core/tests/emissions_estimate.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+const MAX_TICKS: u64 = 5_000_000;
+
+fn step_ticks(e: &mut Engine, ticks: u64) {
+    for _ in 0..ticks {
+        e.step();
+    }
+}
+
+#[test]
+fn estimate_extrapolates_calibration_window_without_advancing_engine() {
+    let e = Engine::new(default_recipe()).unwrap();
+    let est = e.max_emissions_estimate(MAX_TICKS);
+    assert_eq!(e.stats.ticks, 0);
+    assert_eq!(e.stats.emissions, 0);
+
+    let calibration_ticks = MAX_TICKS / 1000;
+    let mut probe = e.clone();
+    step_ticks(&mut probe, calibration_ticks);
+    let seen = probe.stats.emissions;
+    assert!(seen > 0);
+    assert_eq!(est, seen * MAX_TICKS * 9 / (calibration_ticks * 10));
+
+    // Mid-run, only the remaining budget is extrapolated.
+    let mut mid = e.clone();
+    step_ticks(&mut mid, MAX_TICKS / 2);
+    let mut probe = mid.clone();
+    step_ticks(&mut probe, calibration_ticks);
+    let seen = probe.stats.emissions - mid.stats.emissions;
+    assert_eq!(
+        mid.max_emissions_estimate(MAX_TICKS),
+        seen * (MAX_TICKS / 2) * 9 / (calibration_ticks * 10)
+    );
+
+    let mut done = e.clone();
+    step_ticks(&mut done, MAX_TICKS);
+    assert_eq!(done.max_emissions_estimate(MAX_TICKS), 0);
+}
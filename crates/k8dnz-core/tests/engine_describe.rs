@@ -0,0 +1,78 @@
+// crates/k8dnz-core/tests/engine_describe.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::format::recipe_id_hex;
+use k8dnz_core::Engine;
+
+#[test]
+fn describe_before_first_step_matches_recipe() {
+    let r = default_recipe();
+    let e = Engine::new(r.clone()).unwrap();
+    let d = e.describe();
+
+    assert_eq!(d.recipe_id, recipe_id_hex(&r));
+    assert_eq!(d.version, r.version);
+    assert_eq!(d.seed, r.seed);
+    assert_eq!(d.quant_range_width, r.quant.max - r.quant.min);
+    assert_eq!(d.field_clamp_width, r.field_clamp.max - r.field_clamp.min);
+    assert!((d.quant_shift_fraction - r.quant.shift as f64 / d.quant_range_width as f64).abs() < 1e-12);
+    assert!(!d.notes.iter().any(|n| n.contains("narrow")));
+
+    // Within an order of magnitude of the real cadence.
+    let mut run = e.clone();
+    run.run_emissions(200, 50_000_000);
+    let observed = run.stats.ticks as f64 / run.stats.emissions as f64;
+    let ratio = observed / d.expected_emission_period_estimate;
+    assert!(ratio > 0.1 && ratio < 10.0, "observed={} est={}", observed, d.expected_emission_period_estimate);
+
+    assert!(d.to_json().starts_with("{\"recipe_id\":\""));
+}
+
+#[test]
+fn describe_json_writes_non_finite_period_as_null() {
+    let mut d = Engine::new(default_recipe()).unwrap().describe();
+    d.expected_emission_period_estimate = f64::INFINITY;
+    let json = d.to_json();
+    assert!(json.contains("\"expected_emission_period_estimate\":null,"), "{json}");
+    assert!(!json.contains("inf"), "{json}");
+}
+
+#[test]
+fn describe_flags_narrow_range_and_edge_shift() {
+    let mut r = default_recipe();
+    r.quant.min = -1_000;
+    r.quant.max = 1_000;
+    r.quant.shift = 900;
+    let d = Engine::new(r).unwrap().describe();
+    assert!(d.notes.iter().any(|n| n.contains("quant range is narrow")));
+    assert!(d.notes.iter().any(|n| n.contains("boundary")));
+}
+
+#[test]
+fn period_range_brackets_observed_cadence() {
+    let e = Engine::new(default_recipe()).unwrap();
+    let (lo, hi) = e.expected_emission_period_range();
+    assert!(lo > 0 && lo <= hi);
+
+    let mut run = e.clone();
+    run.run_emissions(500, 50_000_000);
+    let observed = run.stats.ticks / run.stats.emissions;
+    assert!(lo <= observed && observed <= hi, "observed={} range={}..{}", observed, lo, hi);
+}
+
+#[test]
+fn search_budget_follows_reachable_bins() {
+    let mut r = default_recipe();
+    let e = Engine::new(r.clone()).unwrap();
+    assert_eq!(e.reachable_quant_bins(), 16);
+    assert_eq!(e.recommended_search_emissions(), 1177);
+
+    // Quant range 4x wider than the clamp: only a quarter of the bins are hit.
+    let w = r.field_clamp.max - r.field_clamp.min;
+    r.quant.min = r.field_clamp.min;
+    r.quant.max = r.field_clamp.min + 4 * w;
+    r.quant.shift = 0;
+    let narrow = Engine::new(r).unwrap();
+    assert_eq!(narrow.reachable_quant_bins(), 4);
+    assert!(narrow.recommended_search_emissions() < e.recommended_search_emissions());
+}
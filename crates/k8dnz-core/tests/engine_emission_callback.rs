@@ -0,0 +1,38 @@
+// crates/k8dnz-core/tests/engine_emission_callback.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+#[test]
+fn callback_sees_the_same_tokens_and_indices_as_collecting() {
+    let mut collected = Engine::new(default_recipe()).unwrap();
+    let want = collected.run_emissions_with_positions(500, 50_000_000);
+
+    let mut streamed = Engine::new(default_recipe()).unwrap();
+    let mut got = Vec::new();
+    let n = streamed.run_with_emission_callback(500, 50_000_000, &mut |i, t| got.push((i, t)));
+
+    assert_eq!(n, 500);
+    assert_eq!(got, want);
+    assert_eq!(streamed.stats, collected.stats);
+
+    // Indices continue from where the engine stopped.
+    let mut next = Vec::new();
+    streamed.run_with_emission_callback(3, 50_000_000, &mut |i, _| next.push(i));
+    assert_eq!(next, [500, 501, 502]);
+}
+
+#[test]
+fn callback_stops_at_max_ticks() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let mut calls = 0u64;
+    let n = e.run_with_emission_callback(u64::MAX, 100_000, &mut |_, _| calls += 1);
+    assert_eq!(n, calls);
+    assert_eq!(e.stats.ticks, 100_000);
+    assert_eq!(n, e.stats.emissions);
+
+    assert_eq!(
+        e.run_with_emission_callback(0, u64::MAX, &mut |_, _| panic!()),
+        0
+    );
+}
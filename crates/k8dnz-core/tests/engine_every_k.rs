@@ -0,0 +1,66 @@
+// crates/k8dnz-core/tests/engine_every_k.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+const MAX_TICKS: u64 = 50_000_000;
+
+#[test]
+fn every_k_matches_filtering_the_full_stream() {
+    let mut full = Engine::new(default_recipe()).unwrap();
+    let all = full.run_emissions_with_positions(300, MAX_TICKS);
+
+    for k in [1u64, 2, 3, 7, 50] {
+        let n = 300 / k;
+        let mut e = Engine::new(default_recipe()).unwrap();
+        let got = e.run_every_k_with_positions(k, n, MAX_TICKS);
+        let want: Vec<_> = all
+            .iter()
+            .copied()
+            .step_by(k as usize)
+            .take(n as usize)
+            .collect();
+        assert_eq!(got, want, "k={k}");
+        assert!(got.windows(2).all(|w| w[1].0 - w[0].0 == k));
+
+        let mut e = Engine::new(default_recipe()).unwrap();
+        let toks = e.run_every_k(k, n, MAX_TICKS);
+        let want_toks: Vec<_> = want.iter().map(|&(_, t)| t).collect();
+        assert_eq!(toks, want_toks, "k={k}");
+
+        // Stops right after the last kept emission.
+        assert_eq!(e.stats.emissions, (n - 1) * k + 1);
+    }
+}
+
+#[test]
+fn every_k_leaves_the_engine_where_stepping_would() {
+    let mut a = Engine::new(default_recipe()).unwrap();
+    a.run_every_k(4, 10, MAX_TICKS);
+
+    let mut b = Engine::new(default_recipe()).unwrap();
+    b.run_emissions(37, MAX_TICKS);
+    assert_eq!(a.stats, b.stats);
+
+    // Both continue with the same stream and indices.
+    assert_eq!(
+        a.run_emissions_with_positions(5, MAX_TICKS),
+        b.run_emissions_with_positions(5, MAX_TICKS)
+    );
+    assert_eq!(a.run_every_k(3, 2, MAX_TICKS).len(), 2);
+}
+
+#[test]
+fn every_k_zero_is_one_and_max_ticks_caps() {
+    let mut a = Engine::new(default_recipe()).unwrap();
+    let mut b = Engine::new(default_recipe()).unwrap();
+    assert_eq!(
+        a.run_every_k(0, 20, MAX_TICKS),
+        b.run_emissions(20, MAX_TICKS)
+    );
+
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let got = e.run_every_k(10, u64::MAX >> 40, 200_000);
+    assert!(e.stats.ticks <= 200_000);
+    assert_eq!(got.len() as u64, e.stats.emissions.div_ceil(10));
+}
@@ -0,0 +1,72 @@
+// crates/k8dnz-core/tests/engine_state.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+const MAX_TICKS: u64 = 50_000_000;
+
+#[test]
+fn resumed_engine_matches_uninterrupted_run() {
+    let mut full = Engine::new(default_recipe()).unwrap();
+    let want: Vec<u8> = full
+        .run_emissions(80, MAX_TICKS)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+
+    let mut first = Engine::new(default_recipe()).unwrap();
+    let mut got: Vec<u8> = first
+        .run_emissions(40, MAX_TICKS)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+
+    let mut resumed = Engine::deserialize_state(&first.serialize_state()).unwrap();
+    assert_eq!(resumed.stats.ticks, first.stats.ticks);
+    assert_eq!(resumed.stats.emissions, 40);
+    got.extend(
+        resumed
+            .run_emissions(40, MAX_TICKS)
+            .iter()
+            .map(|t| t.pack_byte()),
+    );
+
+    assert_eq!(got, want);
+    assert_eq!(resumed.stats.ticks, full.stats.ticks);
+    assert_eq!(resumed.stats.alignments, full.stats.alignments);
+    assert_eq!(resumed.time, full.time);
+}
+
+#[test]
+fn snapshot_between_emissions_roundtrips() {
+    // Stop on arbitrary ticks so both free-orbit and lockstep modes get snapshotted.
+    let mut a = Engine::new(default_recipe()).unwrap();
+    for stop in [1u64, 777, 3_001, 12_345] {
+        while a.stats.ticks < stop {
+            a.step();
+        }
+        let mut b = Engine::deserialize_state(&a.serialize_state()).unwrap();
+        assert_eq!(b.serialize_state(), a.serialize_state());
+        for _ in 0..5_000 {
+            assert_eq!(
+                a.step().map(|t| t.pack_byte()),
+                b.step().map(|t| t.pack_byte())
+            );
+        }
+    }
+}
+
+#[test]
+fn corrupt_state_is_rejected() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    e.run_emissions(3, MAX_TICKS);
+    let good = e.serialize_state();
+
+    let mut flipped = good.clone();
+    let mid = flipped.len() / 2;
+    flipped[mid] ^= 0x40;
+    assert!(Engine::deserialize_state(&flipped).is_err());
+
+    assert!(Engine::deserialize_state(&good[..good.len() - 1]).is_err());
+    assert!(Engine::deserialize_state(b"K8R1").is_err());
+}
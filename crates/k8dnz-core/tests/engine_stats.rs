@@ -0,0 +1,59 @@
+// crates/k8dnz-core/tests/engine_stats.rs
+
+use k8dnz_core::dynamics::engine::EngineStats;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+#[test]
+fn checkpoint_since_gives_per_phase_deltas() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    e.run_emissions(50, 50_000_000);
+    let cp = e.stats.checkpoint();
+    e.run_emissions(30, 50_000_000);
+
+    let d = e.stats.since(&cp);
+    assert_eq!(d.emissions, 30);
+    assert_eq!(d.ticks, e.stats.ticks - cp.ticks);
+    assert_eq!(d.alignments, e.stats.alignments - cp.alignments);
+
+    let mut sum = cp.clone();
+    sum.accumulate(&d);
+    assert_eq!(sum, e.stats);
+}
+
+#[test]
+fn reset_does_not_change_the_stream() {
+    let mut a = Engine::new(default_recipe()).unwrap();
+    let mut b = a.clone();
+    a.run_emissions(40, 50_000_000);
+    b.run_emissions(40, 50_000_000);
+
+    b.stats.reset();
+    assert_eq!(b.stats, EngineStats::default());
+    let ta: Vec<u8> = a
+        .run_emissions(20, 50_000_000)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    let tb: Vec<u8> = b
+        .run_emissions(20, 50_000_000)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    assert_eq!(ta, tb);
+    assert_eq!(b.stats.emissions, 20);
+}
+
+#[test]
+fn density_is_emissions_per_million_ticks() {
+    assert_eq!(
+        EngineStats::default().emission_density_per_million_ticks(),
+        0.0
+    );
+    let s = EngineStats {
+        ticks: 4_000_000,
+        alignments: 0,
+        emissions: 1_000,
+    };
+    assert_eq!(s.emission_density_per_million_ticks(), 250.0);
+}
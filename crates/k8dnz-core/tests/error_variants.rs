@@ -0,0 +1,205 @@
+// crates/k8dnz-core/tests/error_variants.rs
+
+use k8dnz_core::error::K8Error;
+use k8dnz_core::lane::{self, K8L1_VERSION_V8, MAGIC_K8L1};
+use k8dnz_core::recipe::{ark_key, checksum, defaults::default_recipe, format, shamir};
+use k8dnz_core::signal::fit::FitOptions;
+use k8dnz_core::symbol::varint;
+use k8dnz_core::{Engine, TimingMap};
+
+fn artifact() -> Vec<u8> {
+    let (bytes, _) =
+        lane::encode_k8l1(b"Hello, world.", &format::encode(&default_recipe()), 50_000_000, None).expect("encode");
+    bytes
+}
+
+#[test]
+fn k8l1_header_errors_are_structured() {
+    let good = artifact();
+
+    let err = lane::decode_k8l1(&good[..3]).unwrap_err();
+    assert!(
+        matches!(err, K8Error::TruncatedInput { context: "K8L1", expected: 5, got: 3 }),
+        "{err}"
+    );
+
+    let mut bad_magic = good.clone();
+    bad_magic[..4].copy_from_slice(b"NOPE");
+    let err = lane::decode_k8l1(&bad_magic).unwrap_err();
+    assert!(
+        matches!(err, K8Error::BadMagic { expected, got, .. } if expected == MAGIC_K8L1 && &got == b"NOPE"),
+        "{err}"
+    );
+    assert_eq!(err.to_string(), "K8L1: bad magic: expected \"K8L1\", got \"NOPE\"");
+
+    let mut bad_version = good.clone();
+    bad_version[4] = 99;
+    let err = lane::decode_k8l1(&bad_version).unwrap_err();
+    assert!(
+        matches!(err, K8Error::BadVersion { expected, got: 99 } if expected == u16::from(K8L1_VERSION_V8)),
+        "{err}"
+    );
+}
+
+#[test]
+fn engine_state_reports_versions_past_255() {
+    let mut state = Engine::new(default_recipe()).expect("engine").serialize_state();
+    state[4..6].copy_from_slice(&300u16.to_le_bytes());
+    let body_len = state.len() - 4;
+    let crc = checksum::crc32(&state[..body_len]);
+    state[body_len..].copy_from_slice(&crc.to_le_bytes());
+
+    let Err(err) = Engine::deserialize_state(&state) else { panic!("version 300 accepted") };
+    assert!(matches!(err, K8Error::BadVersion { expected: 1, got: 300 }), "{err}");
+}
+
+#[test]
+fn k8l1_truncated_body_is_out_of_bounds_or_truncated() {
+    let good = artifact();
+    for cut in 5..good.len() {
+        let err = lane::decode_k8l1(&good[..cut]).unwrap_err();
+        assert!(
+            matches!(err, K8Error::OutOfBounds { .. } | K8Error::TruncatedInput { .. }),
+            "cut={cut}: {err}"
+        );
+    }
+}
+
+#[test]
+fn low_tick_budget_reports_insufficient_emissions() {
+    let err = lane::encode_k8l1(b"Hello, world.", &format::encode(&default_recipe()), 1, None).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            K8Error::InsufficientEmissions { needed, produced, max_ticks: 1, .. } if produced < needed
+        ),
+        "{err}"
+    );
+    let msg = err.to_string();
+    assert!(msg.contains("insufficient emissions"), "{msg}");
+    assert!(msg.contains("within max_ticks=1"), "{msg}");
+}
+
+#[test]
+fn timemap_and_varint_errors_are_structured() {
+    let err = TimingMap::decode_tm1(b"TM0\0\x00").unwrap_err();
+    assert!(
+        matches!(err, K8Error::BadMagic { expected, got, .. } if &expected == b"TM1\0" && &got == b"TM0\0"),
+        "{err}"
+    );
+
+    let err = TimingMap::decode_auto(b"NOPE\x00").unwrap_err();
+    assert!(
+        matches!(err, K8Error::BadMagic { context: "timemap", got, .. } if &got == b"NOPE"),
+        "{err}"
+    );
+    assert!(err.to_string().starts_with("timemap: bad magic: "), "{err}");
+
+    let err = TimingMap::decode_auto(b"TM").unwrap_err();
+    assert!(
+        matches!(err, K8Error::TruncatedInput { context: "timemap", expected: 4, got: 2 }),
+        "{err}"
+    );
+
+    let mut i = 0usize;
+    let err = varint::get_u64(&[0x80, 0x80], &mut i).unwrap_err();
+    assert!(
+        matches!(err, K8Error::TruncatedInput { context: "varint", expected: 3, got: 2 }),
+        "{err}"
+    );
+    assert_eq!(err.to_string(), "varint: truncated input: expected 3 bytes, got 2");
+}
+
+/// Crockford base32 as ARK1S writes it (MSB-first, no padding).
+fn crock32(bytes: &[u8]) -> String {
+    const CROCK: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+    let (mut acc, mut bits, mut out) = (0u32, 0u8, String::new());
+    for &b in bytes {
+        acc = (acc << 8) | b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(CROCK[((acc >> bits) & 0x1F) as usize] as char);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if bits > 0 {
+        out.push(CROCK[((acc << (5 - bits)) & 0x1F) as usize] as char);
+    }
+    out
+}
+
+#[test]
+fn ark1s_and_diff_errors_keep_their_context() {
+    // Valid CRC, but the body stops after the recipe version.
+    let mut body = vec![0u8, 4, 0];
+    body.extend_from_slice(&checksum::crc32(&body).to_le_bytes());
+    let err = ark_key::decode_ark1s(&format!("ARK1S:{}", crock32(&body))).unwrap_err();
+    assert!(
+        matches!(err, K8Error::TruncatedInput { context: "ark1s", expected: 4, got: 3 }),
+        "{err}"
+    );
+
+    let r = default_recipe();
+    let err = Engine::diff_outputs(&r, &r, 1_000, 1).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            K8Error::InsufficientEmissions { context: "diff_outputs", needed: 1_000, max_ticks: 1, .. }
+        ),
+        "{err}"
+    );
+    assert!(err.to_string().starts_with("diff_outputs: "), "{err}");
+}
+
+#[test]
+fn recipe_and_share_headers_are_structured() {
+    let good = format::encode(&default_recipe());
+
+    let mut bad_magic = good.clone();
+    bad_magic[..4].copy_from_slice(b"NOPE");
+    let err = format::decode(&bad_magic).unwrap_err();
+    assert!(
+        matches!(err, K8Error::BadMagic { context: "recipe", expected, got } if &expected == b"K8R1" && &got == b"NOPE"),
+        "{err}"
+    );
+    assert_eq!(err.to_string(), "recipe: bad magic: expected \"K8R1\", got \"NOPE\"");
+
+    let err = format::decode(&good[..2]).unwrap_err();
+    assert!(matches!(err, K8Error::TruncatedInput { context: "recipe", expected: 4, got: 2 }), "{err}");
+
+    // v3+ header (48 bytes) and field_clamp (16) read fine; quant stops short.
+    let err = format::decode(&good[..68]).unwrap_err();
+    assert!(
+        matches!(err, K8Error::TruncatedInput { context: "recipe quant", expected: 80, got: 68 }),
+        "{err}"
+    );
+
+    let share = shamir::encode_share(&shamir::split_secret(b"secret", 3, 2, |b| b.fill(7)).unwrap()[0]);
+    let err = shamir::decode_share(&share[..10]).unwrap_err();
+    assert!(matches!(err, K8Error::TruncatedInput { context: "share", got: 10, .. }), "{err}");
+    let mut bad_magic = share.clone();
+    bad_magic[..4].copy_from_slice(b"NOPE");
+    let err = shamir::decode_share(&bad_magic).unwrap_err();
+    assert!(
+        matches!(err, K8Error::BadMagic { context: "share", expected, .. } if &expected == b"K8RS"),
+        "{err}"
+    );
+}
+
+#[test]
+fn stride_fit_reports_emissions_produced() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let opts = FitOptions {
+        search_emissions: 10,
+        ..FitOptions::default()
+    };
+    let err = TimingMap::stride_from_fit(&[0u8; 64], &mut e, 50_000_000, &opts).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            K8Error::InsufficientEmissions { context: "stride_from_fit", needed: 64, produced: 10, .. }
+        ),
+        "{err}"
+    );
+}
@@ -0,0 +1,84 @@
+// crates/k8dnz-core/tests/field_soft_clamp.rs
+
+use k8dnz_core::field::clamp::{hard_clamp, soft_clamp};
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::format::{decode, encode, FORMAT_VERSION_SOFT_CLAMP};
+use k8dnz_core::Engine;
+
+fn probes(min: i64, max: i64, knee: u32) -> Vec<i64> {
+    let k = knee as i64;
+    let mut xs = vec![i64::MIN, i64::MAX, 0, -1, 1];
+    for edge in [min, max, min.saturating_add(k), max.saturating_sub(k)] {
+        for d in [-3 * k, -k, -1, 0, 1, k, 3 * k, 1 << 40] {
+            xs.push(edge.saturating_add(d));
+        }
+    }
+    let step = (((max as i128 - min as i128) / 97) as i64).max(1);
+    xs.extend((0..=97).map(|i| min.saturating_add(step.saturating_mul(i))));
+    xs.sort_unstable();
+    xs.dedup();
+    xs
+}
+
+#[test]
+fn soft_clamp_stays_in_range_and_is_monotone() {
+    for (min, max) in [
+        (-100i64, 100i64),
+        (-147_728_900, 80_783_500),
+        (0, 1),
+        (i64::MIN, i64::MAX),
+    ] {
+        for knee in [0u32, 1, 7, 50, 1_000_000, u32::MAX] {
+            let mut prev = i64::MIN;
+            for x in probes(min, max, knee) {
+                let y = soft_clamp(x, min, max, knee);
+                assert!(
+                    (min..=max).contains(&y),
+                    "min={min} max={max} knee={knee} x={x} y={y}"
+                );
+                assert!(y >= prev, "not monotone at x={x} knee={knee}");
+                prev = y;
+            }
+        }
+    }
+}
+
+#[test]
+fn soft_clamp_is_identity_inside_knee_and_hard_at_zero() {
+    let (min, max, knee) = (-1000i64, 1000i64, 100u32);
+    for x in -900..=900 {
+        assert_eq!(soft_clamp(x, min, max, knee), x);
+    }
+    // Far overshoot approaches but never lands on the bound.
+    assert_eq!(soft_clamp(1_000_000, min, max, knee), 999);
+    assert_eq!(soft_clamp(-1_000_000, min, max, knee), -999);
+
+    for x in [-5000i64, -1000, 0, 999, 5000] {
+        assert_eq!(soft_clamp(x, min, max, 0), hard_clamp(x, min, max));
+    }
+}
+
+#[test]
+fn engine_dispatches_on_soft_knee() {
+    let hard = default_recipe();
+    let mut soft = hard.clone();
+    soft.version = FORMAT_VERSION_SOFT_CLAMP;
+    soft.field_clamp.soft_knee = Some(20_000_000);
+
+    let dec = decode(&encode(&soft)).unwrap();
+    assert_eq!(dec.field_clamp.soft_knee, Some(20_000_000));
+
+    let (cmin, cmax) = (soft.field_clamp.min, soft.field_clamp.max);
+    let mut e = Engine::new(dec).unwrap();
+    let fields = e.run_emissions_with_fields(200, 50_000_000);
+    assert!(!fields.is_empty());
+    for (_, f) in &fields {
+        assert_eq!(f.clamped_a, soft_clamp(f.raw_a, cmin, cmax, 20_000_000));
+        assert_eq!(f.clamped_c, soft_clamp(f.raw_c, cmin, cmax, 20_000_000));
+    }
+
+    let mut h = Engine::new(hard).unwrap();
+    for (_, f) in h.run_emissions_with_fields(200, 50_000_000) {
+        assert_eq!(f.clamped_a, hard_clamp(f.raw_a, cmin, cmax));
+    }
+}
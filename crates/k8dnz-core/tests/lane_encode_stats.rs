@@ -0,0 +1,90 @@
+// crates/k8dnz-core/tests/lane_encode_stats.rs
+
+use k8dnz_core::lane::{self, LaneEncodeStats};
+use k8dnz_core::recipe::{defaults::default_recipe, format};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+#[test]
+fn derived_metrics_from_hand_counts() {
+    let st = LaneEncodeStats {
+        total_len: 200,
+        other_len: 120,
+        n_letters: 100,
+        n_digits: 10,
+        n_punct: 8,
+        n_raw: 2,
+        emissions_needed: 200 + 120 + 100 + 100 + 10 + 8 + 2,
+        class_mismatches: 50,
+        kind_mismatches: 30,
+        case_mismatches: 10,
+        letter_mismatches: 90,
+        digit_mismatches: 5,
+        punct_mismatches: 2,
+        raw_mismatches: 1,
+        other_mismatches: 30 + 10 + 90 + 5 + 2 + 1,
+        artifact_bytes: 150,
+        ..Default::default()
+    };
+
+    assert!(close(st.compression_ratio(), 0.75));
+    assert!(close(st.bits_per_symbol(), 6.0));
+    assert_eq!(st.overhead_bytes(), 0);
+
+    assert_eq!(st.per_lane_mismatch_rate("class"), Some(0.25));
+    assert_eq!(st.per_lane_mismatch_rate("kind"), Some(0.25));
+    assert_eq!(st.per_lane_mismatch_rate("case"), Some(0.1));
+    assert_eq!(st.per_lane_mismatch_rate("letter"), Some(0.9));
+    assert_eq!(st.per_lane_mismatch_rate("digit"), Some(0.5));
+    assert_eq!(st.per_lane_mismatch_rate("punct"), Some(0.25));
+    assert_eq!(st.per_lane_mismatch_rate("raw"), Some(0.5));
+    assert_eq!(st.per_lane_mismatch_rate("numeric"), Some(0.0));
+    assert_eq!(st.per_lane_mismatch_rate("other"), Some(138.0 / 340.0));
+    assert_eq!(st.per_lane_mismatch_rate("bogus"), None);
+
+    let bloated = LaneEncodeStats {
+        total_len: 4,
+        artifact_bytes: 90,
+        ..Default::default()
+    };
+    assert_eq!(bloated.overhead_bytes(), 86);
+    assert!(close(bloated.bits_per_symbol(), 180.0));
+    assert_eq!(
+        bloated.quality_summary(),
+        "artifact_bytes=90 ratio=22.5000 bits_per_symbol=180.0000 overhead_bytes=86 class_mismatch_rate=0.0000 other_mismatch_rate=0.0000"
+    );
+
+    let empty = LaneEncodeStats::default();
+    assert_eq!(empty.compression_ratio(), 0.0);
+    for l in LaneEncodeStats::LANES {
+        assert_eq!(empty.per_lane_mismatch_rate(l), Some(0.0), "{l}");
+    }
+}
+
+#[test]
+fn metrics_match_encoded_artifact() {
+    let recipe_bytes = format::encode(&default_recipe());
+    let input = b"In the beginning God created the heaven and the earth. 1 2 3!\n";
+
+    let (artifact, st) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000, None).expect("encode");
+
+    assert_eq!(st.artifact_bytes, artifact.len());
+    assert_eq!(st.total_len, input.len());
+    let ratio = artifact.len() as f64 / input.len() as f64;
+    assert!(close(st.compression_ratio(), ratio));
+    assert!(close(st.bits_per_symbol(), ratio * 8.0));
+    assert_eq!(
+        st.overhead_bytes(),
+        artifact.len().saturating_sub(input.len())
+    );
+    assert!(close(
+        st.per_lane_mismatch_rate("class").unwrap(),
+        st.class_mismatches as f64 / input.len() as f64
+    ));
+    for l in LaneEncodeStats::LANES {
+        let r = st.per_lane_mismatch_rate(l).unwrap();
+        assert!((0.0..=1.0).contains(&r), "{l}={r}");
+    }
+}
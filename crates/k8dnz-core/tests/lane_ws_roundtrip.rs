@@ -23,7 +23,7 @@ fn k8l1_encode_decode_roundtrips_normalized() {
     let input = b"Hello\r\nworld\rtest\n\nA B";
     let recipe_bytes = recipe_bytes_default();
 
-    let (artifact, _stats) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000).expect("encode");
+    let (artifact, _stats) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000, None).expect("encode");
     let decoded = lane::decode_k8l1(&artifact).expect("decode");
 
     let norm = text_norm::normalize_newlines(input);
@@ -35,7 +35,7 @@ fn k8l1_is_deterministic() {
     let input = b"abc def\nghi jkl\n";
     let recipe_bytes = recipe_bytes_default();
 
-    let (a1, _s1) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000).expect("encode1");
-    let (a2, _s2) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000).expect("encode2");
+    let (a1, _s1) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000, None).expect("encode1");
+    let (a2, _s2) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000, None).expect("encode2");
     assert_eq!(a1, a2);
 }
\ No newline at end of file
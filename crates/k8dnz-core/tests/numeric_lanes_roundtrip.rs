@@ -0,0 +1,55 @@
+// crates/k8dnz-core/tests/numeric_lanes_roundtrip.rs
+
+use k8dnz_core::lane;
+use k8dnz_core::recipe::{defaults::default_recipe, format};
+use k8dnz_core::repr::text_norm;
+
+fn recipe_bytes_default() -> Vec<u8> {
+    let r = default_recipe();
+    format::encode(&r)
+}
+
+fn csv_like(rows: usize) -> Vec<u8> {
+    let mut s = String::from("id,amount,ts\n");
+    let mut x: u64 = 0x9E37_79B9_7F4A_7C15;
+    for i in 0..rows {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        s.push_str(&format!("{},{},{}\n", i, x % 1_000_000, 1_700_000_000 + (x >> 40)));
+    }
+    s.into_bytes()
+}
+
+#[test]
+fn k8l1_numeric_csv_roundtrips_and_uses_numeric_runs() {
+    let input = csv_like(64);
+    let recipe_bytes = recipe_bytes_default();
+
+    let (artifact, stats) = lane::encode_k8l1(&input, &recipe_bytes, 50_000_000, None).expect("encode");
+    assert!(stats.n_numeric_runs > 0, "numeric runs should win on integer-heavy input");
+
+    assert_eq!(artifact[4], lane::K8L1_VERSION_V8);
+
+    let decoded = lane::decode_k8l1(&artifact).expect("decode");
+    assert_eq!(decoded, text_norm::normalize_newlines(&input));
+
+    // An older version byte must not decode the numeric ids as something else.
+    for older in [lane::K8L1_VERSION_V5, lane::K8L1_VERSION_V7] {
+        let mut downgraded = artifact.clone();
+        downgraded[4] = older;
+        let err = lane::decode_k8l1(&downgraded).expect_err("numeric lanes below v8");
+        assert!(err.to_string().contains("numeric lanes need v8"), "{err}");
+    }
+}
+
+#[test]
+fn k8l1_numeric_edge_runs_roundtrip() {
+    // leading zeros, a 19-digit run, and a run longer than one numeric symbol
+    let input = b"007 x 0 9999999999999999999 12345678901234567890123 a1b2 00\n";
+    let recipe_bytes = recipe_bytes_default();
+
+    let (artifact, _stats) = lane::encode_k8l1(input, &recipe_bytes, 20_000_000, None).expect("encode");
+    let decoded = lane::decode_k8l1(&artifact).expect("decode");
+    assert_eq!(decoded, input.to_vec());
+}
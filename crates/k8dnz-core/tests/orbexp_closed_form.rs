@@ -58,3 +58,119 @@ fn orbexp_closed_form_matches_simulation_small() {
         }
     }
 }
+
+#[test]
+fn orbexp_multi_meet_three_bodies() {
+    use k8dnz_core::orbexp::{compute_multi_meet, simulate_multi_meet};
+
+    // mod 12, steps 1,4,7: pairwise periods 4,2,4 => lcm 4.
+    let r = compute_multi_meet(12, &[1, 4, 7]).unwrap();
+    assert_eq!(r.t_first_meet, 4);
+    assert_eq!(r.d, 3);
+    assert_eq!(r.gcd, 3);
+    assert_eq!(simulate_multi_meet(12, &[1, 4, 7], 100).unwrap(), Some(4));
+
+    // all steps congruent => lockstep
+    assert_eq!(compute_multi_meet(12, &[5, 17, 29]).unwrap().t_first_meet, 0);
+    assert_eq!(simulate_multi_meet(12, &[5, 17, 29], 100).unwrap(), Some(0));
+}
+
+#[test]
+fn orbexp_multi_meet_four_bodies() {
+    use k8dnz_core::orbexp::{compute_multi_meet, simulate_multi_meet};
+
+    // mod 1024, steps 3,7,13,19: pairwise periods 256,512,64,512,256,512 => lcm 512.
+    let r = compute_multi_meet(1024, &[3, 7, 13, 19]).unwrap();
+    assert_eq!(r.t_first_meet, 512);
+    assert_eq!(simulate_multi_meet(1024, &[3, 7, 13, 19], 1_000_000).unwrap(), Some(512));
+
+    // mod 30, steps 0,5,10,12: pairwise periods 6,3,5,6,30,15 => lcm 30.
+    let r = compute_multi_meet(30, &[0, 5, 10, 12]).unwrap();
+    assert_eq!(r.t_first_meet, 30);
+    assert_eq!(simulate_multi_meet(30, &[0, 5, 10, 12], 1_000).unwrap(), Some(30));
+
+    // two bodies agree with the pairwise engine
+    let two = compute_multi_meet(997, &[123, 456]).unwrap();
+    let pair = k8dnz_core::orbexp::compute_first_meet(k8dnz_core::orbexp::OrbParams {
+        modn: 997,
+        step_a: 123,
+        step_c: 456,
+    })
+    .unwrap();
+    assert_eq!(two.t_first_meet, pair.t_first_meet);
+}
+
+#[test]
+fn orbexp_closed_form_step_a_below_step_c_composite_mod() {
+    // d is taken mod MOD: (0 - 1) mod 10 = 9, period 10 (not 2^64 - 1 mod 10).
+    let cases = [(10u64, 0u64, 1u64, 9u64, 10u64), (100, 3, 7, 96, 25)];
+    for (modn, step_a, step_c, d, t) in cases {
+        let params = OrbParams {
+            modn,
+            step_a,
+            step_c,
+        };
+        let r = compute_first_meet(params).unwrap();
+        assert_eq!((r.d, r.t_first_meet), (d, t));
+        let mut sim = k8dnz_core::orbexp::OrbSimulator::new(params).unwrap();
+        assert_eq!((0..1000).find_map(|_| sim.tick()), Some(t));
+    }
+}
+
+#[test]
+fn orbexp_simulator_steps_match_closed_form() {
+    use k8dnz_core::orbexp::OrbSimulator;
+
+    let params = OrbParams {
+        modn: 997,
+        step_a: 123,
+        step_c: 456,
+    };
+    let t_closed = compute_first_meet(params).unwrap().t_first_meet;
+
+    let mut sim = OrbSimulator::new(params).unwrap();
+    assert_eq!(sim.phases(), (0, 0));
+    let mut first = None;
+    for _ in 0..t_closed + 5 {
+        if let Some(t) = sim.tick() {
+            first.get_or_insert(t);
+        }
+    }
+    assert_eq!(first, Some(t_closed));
+    assert_eq!(sim.ticks(), t_closed + 5);
+    assert_eq!(
+        sim.phases(),
+        ((123 * (t_closed + 5)) % 997, (456 * (t_closed + 5)) % 997)
+    );
+
+    let h = sim.history(3);
+    assert_eq!(h.len(), 3);
+    assert_eq!(*h.last().unwrap(), sim.phases());
+    assert_eq!(
+        h[1],
+        (123 * (t_closed + 4) % 997, 456 * (t_closed + 4) % 997)
+    );
+}
+
+#[test]
+fn orbexp_simulator_history_is_bounded() {
+    use k8dnz_core::orbexp::OrbSimulator;
+
+    let params = OrbParams {
+        modn: 16,
+        step_a: 1,
+        step_c: 3,
+    };
+    let mut sim = OrbSimulator::with_history_capacity(params, 4).unwrap();
+    assert_eq!(sim.history(10), vec![(0, 0)]);
+    for _ in 0..6 {
+        sim.tick();
+    }
+    assert_eq!(sim.history(10), vec![(3, 9), (4, 12), (5, 15), (6, 2)]);
+    assert!(OrbSimulator::new(OrbParams {
+        modn: 0,
+        step_a: 1,
+        step_c: 2
+    })
+    .is_err());
+}
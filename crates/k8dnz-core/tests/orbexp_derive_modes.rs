@@ -0,0 +1,51 @@
+// crates/k8dnz-core/tests/orbexp_derive_modes.rs
+
+use k8dnz_core::orbexp::{derive_steps, DeriveMode};
+
+const P: u64 = 0x243f_6a88_85a3_08d3;
+const MODN: u64 = 4_294_967_291;
+
+#[test]
+fn orbexp_derive_mode_parse_aliases() {
+    assert_eq!(DeriveMode::parse("blake3").unwrap(), DeriveMode::Blake3);
+    assert_eq!(DeriveMode::parse("B3").unwrap(), DeriveMode::Blake3);
+    assert_eq!(DeriveMode::parse("sha256").unwrap(), DeriveMode::Sha256);
+    assert_eq!(DeriveMode::parse("sha2").unwrap(), DeriveMode::Sha256);
+    assert!(DeriveMode::parse("md5").is_err());
+}
+
+#[test]
+fn orbexp_derive_modes_give_distinct_steps() {
+    let block = b"In the beginning God created the heaven and the earth.";
+    let modes = [
+        DeriveMode::Int,
+        DeriveMode::Crc32,
+        DeriveMode::DecPairs,
+        DeriveMode::Blake3,
+        DeriveMode::Sha256,
+    ];
+
+    let outs: Vec<(u64, u64, u64)> = modes
+        .iter()
+        .map(|&m| derive_steps(P, block, 64, m, MODN).unwrap())
+        .collect();
+
+    for i in 0..outs.len() {
+        for j in (i + 1)..outs.len() {
+            assert_ne!(outs[i], outs[j], "{:?} vs {:?}", modes[i], modes[j]);
+        }
+    }
+
+    // step_a depends only on P, so every mode shares it.
+    assert!(outs.iter().all(|o| o.1 == outs[0].1));
+}
+
+#[test]
+fn orbexp_blake3_delta_is_le_digest_prefix() {
+    let block = [0x5au8; 8];
+    let (delta, _, _) = derive_steps(P, &block, 64, DeriveMode::Blake3, MODN).unwrap();
+    let h = blake3::hash(&block);
+    let mut b = [0u8; 8];
+    b.copy_from_slice(&h.as_bytes()[..8]);
+    assert_eq!(delta, u64::from_le_bytes(b));
+}
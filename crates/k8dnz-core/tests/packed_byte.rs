@@ -0,0 +1,56 @@
+// crates/k8dnz-core/tests/packed_byte.rs
+
+use k8dnz_core::{PackedByte, PairToken};
+
+#[test]
+fn nibbles_match_pair_token() {
+    for x in 0u8..=255 {
+        let p = PackedByte(x);
+        let t = PairToken::from_byte(x);
+        assert_eq!(p.nibble_a(), t.a);
+        assert_eq!(p.nibble_b(), t.b);
+        assert_eq!(p.is_palindrome(), t.a == t.b);
+        assert_eq!(p.count_ones(), x.count_ones());
+        assert_eq!(PackedByte::from(t), p);
+        assert_eq!(u8::from(p), t.pack_byte());
+    }
+}
+
+#[test]
+fn frequency_tables_count_common_prefix() {
+    let a = [0x11u8, 0x11, 0x2F, 0x00];
+    let b = [0x11u8, 0x20, 0x2F];
+
+    let h = PackedByte::frequency_table(a.iter().map(|&x| PackedByte(x)));
+    assert_eq!(h[0x11], 2);
+    assert_eq!(h[0x2F], 1);
+    assert_eq!(h[0x00], 1);
+    assert_eq!(h.iter().sum::<u64>(), 4);
+
+    let j = PackedByte::joint_frequency_table(
+        a.iter().map(|&x| PackedByte(x)),
+        b.iter().map(|&x| PackedByte(x)),
+    );
+    assert_eq!(j[0x11][0x11], 1);
+    assert_eq!(j[0x11][0x20], 1);
+    assert_eq!(j[0x2F][0x2F], 1);
+    assert_eq!(j.iter().flatten().sum::<u64>(), 3);
+}
+
+#[test]
+fn from_byte_inverts_pack_byte() {
+    const T: PairToken = PairToken::from_byte(0xA7);
+    assert_eq!((T.a, T.b), (0xA, 0x7));
+    for x in 0u8..=255 {
+        let t = PairToken::from_byte(x);
+        assert_eq!(t.pack_byte(), x);
+        assert_eq!(PairToken::from_nibbles(t.a, t.b).unwrap(), t);
+    }
+}
+
+#[test]
+fn from_nibbles_rejects_wide_symbols() {
+    assert!(PairToken::from_nibbles(16, 0).is_err());
+    assert!(PairToken::from_nibbles(0, 16).is_err());
+    assert!(PairToken::from_nibbles(15, 15).is_ok());
+}
@@ -0,0 +1,33 @@
+// crates/k8dnz-core/tests/patch_verbatim.rs
+
+use k8dnz_core::error::K8Error;
+use k8dnz_core::symbol::patch::PatchList;
+
+#[test]
+fn from_symbol_streams_enforces_mismatch_limit() {
+    let pred = [0u8, 1, 2, 3, 4, 5, 6, 7];
+    let actual = [0u8, 1, 9, 9, 9, 5, 6, 7];
+
+    let ok = PatchList::from_symbol_streams(&pred, &actual, 3).expect("at limit");
+    assert_eq!(ok.entries.len(), 3);
+
+    match PatchList::from_symbol_streams(&pred, &actual, 2) {
+        Err(K8Error::TooManyMismatches { count, limit }) => {
+            assert_eq!((count, limit), (3, 2));
+        }
+        other => panic!("expected TooManyMismatches, got {other:?}"),
+    }
+}
+
+#[test]
+fn verbatim_patch_rebuilds_lane_from_any_prediction() {
+    let actual: Vec<u8> = (0..200u32).map(|i| ((i * 7 + i / 3) % 5) as u8).collect();
+    let blob = PatchList::encode_verbatim(&actual);
+    // 200 symbols at 3 bits each, plus a small header
+    assert!(blob.len() < 90, "verbatim blob too large: {}", blob.len());
+
+    let p = PatchList::decode(&blob).expect("decode");
+    let mut pred: Vec<u8> = (0..200u32).map(|i| (i % 3) as u8).collect();
+    p.apply_to_pred(&mut pred).expect("apply");
+    assert_eq!(pred, actual);
+}
@@ -0,0 +1,145 @@
+// crates/k8dnz-core/tests/patch_zstd.rs
+
+use k8dnz_core::error::K8Error;
+use k8dnz_core::lane;
+use k8dnz_core::recipe::{defaults::default_recipe, format};
+use k8dnz_core::symbol::patch::{self, PatchList, MAGIC_PZST};
+
+fn repetitive_patch() -> PatchList {
+    let pred = vec![0u8; 4000];
+    let actual: Vec<u8> = (0..4000u32)
+        .map(|i| if i % 3 == 0 { (i % 4) as u8 + 1 } else { 0 })
+        .collect();
+    PatchList::from_pred_actual(&pred, &actual).expect("patch")
+}
+
+#[test]
+fn compressed_envelope_roundtrips() {
+    let p = repetitive_patch();
+    let z = p.encode_compressed(7).expect("compress");
+    assert_eq!(&z[..4], &MAGIC_PZST);
+    assert_eq!(z[4], 7);
+    assert!(PatchList::is_compressed(&z));
+    assert_eq!(
+        PatchList::decode_auto(&z, patch::max_raw_len(4000)).expect("decode compressed"),
+        p
+    );
+
+    let raw = p.encode();
+    assert!(!PatchList::is_compressed(&raw));
+    assert_eq!(
+        PatchList::decode_auto(&raw, patch::max_raw_len(4000)).expect("decode raw"),
+        p
+    );
+    assert_eq!(
+        patch::smaller_encoding(raw.clone(), 19)
+            .expect("store")
+            .len(),
+        usize::min(
+            raw.len(),
+            patch::compress_blob(&raw, 19).expect("compress").len()
+        )
+    );
+}
+
+#[test]
+fn smaller_encoding_keeps_tiny_blobs_raw() {
+    let mut p = PatchList::new();
+    p.entries.push((3, 1));
+    let raw = p.encode();
+    assert_eq!(
+        patch::smaller_encoding(raw.clone(), 19).expect("store"),
+        raw
+    );
+    assert_eq!(
+        patch::smaller_encoding(Vec::new(), 19).expect("store"),
+        Vec::<u8>::new()
+    );
+}
+
+#[test]
+fn raw_blob_starting_with_magic_is_always_wrapped() {
+    // Legacy sparse: count=80 ('P'), first pos 90 ('Z'), value 83 ('S'), next delta 84 ('T').
+    let mut p = PatchList::new();
+    p.entries.push((90, 83));
+    for k in 1..80u64 {
+        p.entries.push((90 + 84 * k, 1));
+    }
+    let raw = p.encode_sparse_legacy();
+    assert_eq!(&raw[..4], b"PZST");
+
+    // Pre-v7 artifacts never carry envelopes, so the same bytes stay a legacy blob there.
+    assert_eq!(
+        lane::decode_patch_blob(lane::K8L1_VERSION_V6, &raw, 7000)
+            .expect("legacy")
+            .entries,
+        p.entries
+    );
+
+    let stored = patch::smaller_encoding(raw, 19).expect("store");
+    assert!(PatchList::is_compressed(&stored));
+    assert_eq!(
+        PatchList::decode_auto(&stored, patch::max_raw_len(7000))
+            .expect("decode")
+            .entries,
+        p.entries
+    );
+    assert_eq!(
+        lane::decode_patch_blob(lane::K8L1_VERSION_V7, &stored, 7000)
+            .expect("v7")
+            .entries,
+        p.entries
+    );
+}
+
+#[test]
+fn truncated_or_corrupt_envelope_is_an_error() {
+    let err = PatchList::decode_auto(b"PZST", 64).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            K8Error::TruncatedInput {
+                context: "patch zstd envelope",
+                expected: 5,
+                got: 4
+            }
+        ),
+        "{err}"
+    );
+
+    let err = PatchList::decode_auto(b"PZST\x03not zstd", 64).unwrap_err();
+    assert!(matches!(err, K8Error::Validation(_)), "{err}");
+}
+
+#[test]
+fn envelope_cannot_inflate_past_the_cap() {
+    // 1 MiB of zeros squeezes into a few dozen bytes.
+    let bomb = patch::compress_blob(&vec![0u8; 1 << 20], 19).expect("compress");
+    assert!(bomb.len() < 1024);
+    let err = PatchList::decode_auto(&bomb, patch::max_raw_len(100)).unwrap_err();
+    assert!(matches!(err, K8Error::Validation(_)), "{err}");
+}
+
+#[test]
+fn k8l1_compresses_a_mismatch_heavy_raw_lane() {
+    let mut input = b"Binary tail follows.\n".to_vec();
+    input.extend((0..3000u32).map(|i| 0x80 + (i % 4) as u8));
+    let recipe = format::encode(&default_recipe());
+
+    let (bytes, stats) = lane::encode_k8l1(&input, &recipe, 400_000_000, None).expect("encode");
+    assert_eq!(
+        stats.patch_compressed("raw"),
+        Some(true),
+        "{:?}",
+        stats.compressed_patches
+    );
+    assert_eq!(stats.patch_compressed("other"), Some(true));
+    assert_eq!(stats.patch_compressed("bogus"), None);
+    assert_eq!(bytes[4], lane::K8L1_VERSION_V7);
+    assert_eq!(lane::decode_k8l1(&bytes).expect("decode"), input);
+
+    // The same artifact relabelled as v6 must not accept the envelopes.
+    let mut v6 = bytes.clone();
+    v6[4] = lane::K8L1_VERSION_V6;
+    assert!(lane::decode_k8l1(&v6).is_err());
+}
@@ -0,0 +1,48 @@
+// crates/k8dnz-core/tests/punct_alphabet_roundtrip.rs
+
+use k8dnz_core::lane;
+use k8dnz_core::recipe::{defaults::default_recipe, format};
+use k8dnz_core::repr::text_norm;
+
+fn recipe_bytes_default() -> Vec<u8> {
+    format::encode(&default_recipe())
+}
+
+const XML: &[u8] = b"<doc id=\"a1\">\n  <p class=\"x\">Hi & bye</p>\n  <br/>\n</doc>\n";
+
+#[test]
+fn custom_alphabet_is_embedded_and_roundtrips() {
+    let recipe_bytes = recipe_bytes_default();
+    let alph: &[u8] = b"<>/=\"&";
+
+    let (artifact, stats) = lane::encode_k8l1(XML, &recipe_bytes, 20_000_000, Some(alph)).expect("encode");
+    assert_eq!(artifact[4], lane::K8L1_VERSION_V4);
+    assert_eq!(stats.n_raw, 0);
+    assert_eq!(stats.punct_coverage, 1.0);
+
+    // Self-describing: the decoder is not told the alphabet.
+    let decoded = lane::decode_k8l1(&artifact).expect("decode");
+    assert_eq!(decoded, text_norm::normalize_newlines(XML));
+
+    // The default alphabet leaves most XML syntax to the raw lane.
+    let (_a, dstats) = lane::encode_k8l1(XML, &recipe_bytes, 20_000_000, None).expect("encode default");
+    assert!(dstats.punct_coverage < stats.punct_coverage);
+}
+
+#[test]
+fn default_alphabet_keeps_legacy_layout() {
+    let recipe_bytes = recipe_bytes_default();
+    let (a_none, _) = lane::encode_k8l1(XML, &recipe_bytes, 20_000_000, None).expect("none");
+    let (a_dflt, _) = lane::encode_k8l1(XML, &recipe_bytes, 20_000_000, Some(lane::PUNCT_ALPH)).expect("default");
+    assert_eq!(a_none, a_dflt);
+    assert_eq!(a_none[4], lane::K8L1_VERSION_V2);
+}
+
+#[test]
+fn bad_alphabets_are_rejected() {
+    let recipe_bytes = recipe_bytes_default();
+    for bad in [&b""[..], b"..", b".a", b". ", b"9"] {
+        assert!(lane::validate_punct_alphabet(bad).is_err(), "{:?}", bad);
+        assert!(lane::encode_k8l1(XML, &recipe_bytes, 20_000_000, Some(bad)).is_err());
+    }
+}
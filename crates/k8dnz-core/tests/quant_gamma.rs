@@ -0,0 +1,150 @@
+// crates/k8dnz-core/tests/quant_gamma.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::format::{decode, encode, FORMAT_VERSION, FORMAT_VERSION_QUANT_GAMMA};
+use k8dnz_core::signal::quantize::{quantize, QuantizeGamma};
+use k8dnz_core::signal::sample::FieldSample;
+use k8dnz_core::validate::validate_recipe;
+use k8dnz_core::Engine;
+
+#[test]
+fn gamma_one_matches_the_linear_quantizer() {
+    for (min, max) in [
+        (-100i64, 100i64),
+        (-147_728_900, 80_783_500),
+        (0, 1),
+        (3, 10),
+        (-7, 8),
+    ] {
+        let q = QuantizeGamma::new(1.0, min, max);
+        let step = ((max - min) / 997).max(1);
+        let mut x = min - 3 * step;
+        while x <= max + 3 * step {
+            for n in [2u8, 16, 255] {
+                assert_eq!(
+                    q.quantize(FieldSample(x), n),
+                    quantize(FieldSample(x), min, max, n),
+                    "min={min} max={max} x={x} n={n}"
+                );
+            }
+            x += step;
+        }
+    }
+}
+
+#[test]
+fn small_gamma_spends_more_bins_near_min() {
+    let (min, max) = (0i64, 1_000_000i64);
+    let sqrt = QuantizeGamma::new(0.5, min, max);
+    let square = QuantizeGamma::new(2.0, min, max);
+
+    // The bottom 10% of the range covers ~5 of 16 bins at gamma 0.5, ~0 at gamma 2.
+    let low = FieldSample(100_000);
+    assert_eq!(quantize(low, min, max, 16), 2);
+    assert_eq!(sqrt.quantize(low, 16), 5);
+    assert_eq!(square.quantize(low, 16), 0);
+
+    for q in [sqrt, square] {
+        assert_eq!(q.quantize(FieldSample(min), 16), 0);
+        assert_eq!(q.quantize(FieldSample(max), 16), 15);
+        let mut prev = 0;
+        for x in (min..=max).step_by(997) {
+            let b = q.quantize(FieldSample(x), 16);
+            assert!(b >= prev, "not monotone at x={x}");
+            prev = b;
+        }
+    }
+}
+
+#[test]
+fn recipe_gamma_round_trips_and_drives_the_engine() {
+    let linear = default_recipe();
+    assert_eq!(linear.quant_gamma, None);
+    assert_eq!(decode(&encode(&linear)).unwrap().quant_gamma, None);
+
+    let mut curved = linear.clone();
+    curved.version = FORMAT_VERSION_QUANT_GAMMA;
+    curved.quant_gamma = Some(0.5);
+    let dec = decode(&encode(&curved)).unwrap();
+    assert_eq!(dec.quant_gamma, Some(0.5));
+    assert_eq!(encode(&linear).len() + 4 + 8 + 12, encode(&curved).len());
+
+    // Emissions and fields are the same; only the labels move.
+    let toks = |r| Engine::new(r).unwrap().run_emissions(500, 50_000_000);
+    let mut unit = linear.clone();
+    unit.version = FORMAT_VERSION_QUANT_GAMMA;
+    unit.quant_gamma = Some(1.0);
+    assert_eq!(toks(unit), toks(linear.clone()));
+    let (a, b) = (toks(dec), toks(linear.clone()));
+    assert_eq!(a.len(), b.len());
+    assert_ne!(a, b);
+
+    // Below v7 the gamma is not written, so it decodes as linear.
+    let mut old = curved.clone();
+    old.version = FORMAT_VERSION;
+    assert_eq!(decode(&encode(&old)).unwrap().quant_gamma, None);
+
+    for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let mut r = curved.clone();
+        r.quant_gamma = Some(bad);
+        assert!(validate_recipe(&r).is_err(), "gamma={bad}");
+    }
+}
+
+/// The gamma curve is integer-only; these bins must not move across targets or libm.
+#[test]
+fn gamma_bins_are_pinned() {
+    let (min, max) = (-147_728_900i64, 80_783_500i64);
+    let golden: [(f64, [u8; 17], i64); 4] = [
+        (
+            0.25,
+            [
+                0, 128, 152, 168, 180, 191, 200, 207, 214, 221, 227, 232, 237, 242, 247, 251, 254,
+            ],
+            333_326,
+        ),
+        (
+            0.5,
+            [
+                0, 64, 90, 110, 128, 143, 156, 169, 180, 191, 202, 211, 221, 230, 239, 247, 254,
+            ],
+            111_107,
+        ),
+        (
+            2.0,
+            [
+                0, 1, 4, 9, 16, 25, 36, 49, 64, 81, 100, 121, 143, 168, 195, 224, 254,
+            ],
+            152,
+        ),
+        (
+            4.0,
+            [
+                0, 0, 0, 0, 1, 2, 5, 9, 16, 26, 39, 57, 81, 111, 149, 197, 254,
+            ],
+            0,
+        ),
+    ];
+    for (gamma, bins, warped) in golden {
+        let q = QuantizeGamma::new(gamma, min, max);
+        let got: Vec<u8> = (0..=16)
+            .map(|i| q.quantize(FieldSample(min + (max - min) / 16 * i), 255))
+            .collect();
+        assert_eq!(got, bins, "gamma={gamma}");
+        assert_eq!(q.curve.warp(12_345, 1_000_000), warped, "gamma={gamma}");
+    }
+
+    let mut r = default_recipe();
+    r.version = FORMAT_VERSION_QUANT_GAMMA;
+    r.quant_gamma = Some(0.5);
+    let packed: Vec<u8> = Engine::new(r)
+        .unwrap()
+        .run_emissions(16, 50_000_000)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    assert_eq!(
+        packed,
+        [185, 121, 106, 154, 203, 165, 254, 122, 191, 170, 198, 185, 121, 106, 154, 187]
+    );
+}
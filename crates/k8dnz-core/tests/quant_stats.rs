@@ -0,0 +1,58 @@
+// crates/k8dnz-core/tests/quant_stats.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::signal::quantize::QuantStats;
+use k8dnz_core::Engine;
+
+#[test]
+fn welford_matches_two_pass() {
+    let bytes: Vec<u8> = (0u32..1000).map(|i| (i * 37 % 251) as u8).collect();
+    let mut qs = QuantStats::new();
+    for &b in &bytes {
+        qs.update(b);
+    }
+
+    let n = bytes.len() as f64;
+    let mean = bytes.iter().map(|&b| b as f64).sum::<f64>() / n;
+    let var = bytes.iter().map(|&b| (b as f64 - mean).powi(2)).sum::<f64>() / n;
+
+    assert_eq!(qs.count(), 1000);
+    assert!((qs.mean() - mean).abs() < 1e-9);
+    assert!((qs.variance() - var).abs() < 1e-6);
+    assert_eq!(qs.min(), bytes.iter().copied().min());
+    assert_eq!(qs.max(), bytes.iter().copied().max());
+    assert_eq!(qs.distinct(), 251);
+    assert_eq!(qs.histogram().iter().sum::<u64>(), 1000);
+}
+
+#[test]
+fn empty_stats_are_neutral() {
+    let qs = QuantStats::new();
+    assert_eq!(qs.min(), None);
+    assert_eq!(qs.max(), None);
+    assert_eq!(qs.variance(), 0.0);
+    assert_eq!(qs.entropy_bits(), 0.0);
+    assert!(qs.to_json().contains("\"min\":null"));
+}
+
+#[test]
+fn engine_stats_agree_with_token_stream() {
+    let mut e1 = Engine::new(default_recipe()).unwrap();
+    let (toks, qs) = e1.run_emissions_with_stats(300, 50_000_000);
+
+    let mut e2 = Engine::new(default_recipe()).unwrap();
+    let only = e2.run_quant_stats(300, 50_000_000);
+    assert_eq!(e1.stats.ticks, e2.stats.ticks);
+
+    let mut h = [0u64; 256];
+    for t in &toks {
+        h[t.pack_byte() as usize] += 1;
+    }
+    assert_eq!(qs.histogram(), &h);
+    assert_eq!(only.histogram(), &h);
+    assert_eq!(qs.count(), toks.len() as u64);
+
+    let (ha, hb) = qs.nibble_histograms();
+    assert_eq!(ha.iter().sum::<u64>(), qs.count());
+    assert_eq!(hb.iter().sum::<u64>(), qs.count());
+}
@@ -0,0 +1,43 @@
+// crates/k8dnz-core/tests/quant_step_size.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+#[test]
+fn quant_step_size_covers_range() {
+    let mut r = default_recipe();
+    r.quant.min = -100;
+    r.quant.max = 100;
+    assert_eq!(r.quant_step_size(), 13);
+    r.quant.max = 60;
+    assert_eq!(r.quant_step_size(), 10);
+    assert!(r.quant_step_size() * 16 >= r.quant.max - r.quant.min);
+}
+
+#[test]
+fn quant_level_bounds_and_shift() {
+    let mut r = default_recipe();
+    r.quant.min = 0;
+    r.quant.max = 160;
+    r.quant.shift = 0;
+    assert_eq!(r.quant_level_for_field_value(-5), 0);
+    assert_eq!(r.quant_level_for_field_value(0), 0);
+    assert_eq!(r.quant_level_for_field_value(80), 8);
+    assert_eq!(r.quant_level_for_field_value(160), 15);
+    assert_eq!(r.quant_level_for_field_value(1_000), 15);
+
+    r.quant.shift = 10;
+    assert_eq!(r.quant_level_for_field_value(90), 8);
+}
+
+#[test]
+fn quant_level_matches_engine_tokens() {
+    let r = default_recipe();
+    let mut e = Engine::new(r.clone()).unwrap();
+    let pairs = e.run_emissions_with_fields(64, 50_000_000);
+    assert!(!pairs.is_empty());
+    for (tok, field) in &pairs {
+        assert_eq!(tok.a, r.quant_level_for_field_value(field.clamped_a));
+        assert_eq!(tok.b, r.quant_level_for_field_value(field.clamped_c));
+    }
+}
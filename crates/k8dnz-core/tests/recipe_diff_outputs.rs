@@ -0,0 +1,76 @@
+// crates/k8dnz-core/tests/recipe_diff_outputs.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::Engine;
+
+const N: u64 = 1_000;
+const MAX_TICKS: u64 = 500_000_000;
+
+fn packed(r: &k8dnz_core::Recipe) -> Vec<u8> {
+    Engine::new(r.clone())
+        .unwrap()
+        .run_emissions(N, MAX_TICKS)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect()
+}
+
+#[test]
+fn identical_recipes_have_no_diffs() {
+    let r = default_recipe();
+    assert!(r.field_diff(&r).is_empty());
+    assert!(Engine::diff_outputs(&r, &r, N, MAX_TICKS)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn diff_outputs_matches_separate_runs() {
+    let a = default_recipe();
+    let mut b = a.clone();
+    b.quant.shift = 0;
+
+    let fields = a.field_diff(&b);
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].field, "quant.shift");
+    assert_eq!(fields[0].b, "0");
+
+    let diffs = Engine::diff_outputs(&a, &b, N, MAX_TICKS).unwrap();
+    let (sa, sb) = (packed(&a), packed(&b));
+    let expect: Vec<(u64, u8, u8)> = (0..N as usize)
+        .filter(|&i| sa[i] != sb[i])
+        .map(|i| (i as u64, sa[i], sb[i]))
+        .collect();
+    assert!(!expect.is_empty());
+    assert_eq!(diffs, expect);
+}
+
+#[test]
+fn field_diff_reports_wave_count_changes() {
+    let a = default_recipe();
+    let mut b = a.clone();
+    b.field.waves.pop();
+    let names: Vec<String> = a.field_diff(&b).into_iter().map(|d| d.field).collect();
+    let last = format!("field.wave[{}]", b.field.waves.len());
+    assert_eq!(names, vec!["field.waves".to_string(), last]);
+}
+
+#[test]
+fn diff_outputs_errors_when_tick_budget_runs_out() {
+    let r = default_recipe();
+    assert!(Engine::diff_outputs(&r, &r, N, 10).is_err());
+}
+
+#[test]
+fn field_diff_reports_soft_knee_changes() {
+    let mut a = default_recipe();
+    a.version = k8dnz_core::recipe::format::FORMAT_VERSION_SOFT_CLAMP;
+    let mut b = a.clone();
+    b.field_clamp.soft_knee = Some(1_000);
+
+    let fields = a.field_diff(&b);
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].field, "field_clamp.soft_knee");
+    assert_eq!(fields[0].a, "None");
+    assert_eq!(fields[0].b, "Some(1000)");
+}
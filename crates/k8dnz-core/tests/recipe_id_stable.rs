@@ -19,3 +19,57 @@ fn recipe_id_is_stable_for_same_recipe() {
     let b = recipe::format::recipe_id_16(&r);
     assert_eq!(a, b);
 }
+
+#[test]
+fn recipe_id_from_params_matches_recipe_id_hex() {
+    use k8dnz_core::recipe::format::{recipe_id_from_params, recipe_id_hex};
+
+    let r = recipe::defaults::default_recipe();
+    let (q, c) = (r.quant, r.field_clamp);
+    let id = recipe_id_from_params(r.seed, q.min, q.max, q.shift, c.min, c.max);
+    assert_eq!(id, recipe_id_hex(&r));
+
+    let mut t = r.clone();
+    t.seed = 42;
+    t.quant.shift = -12_345;
+    t.quant.max += 7;
+    t.field_clamp.min -= 3;
+    let (q, c) = (t.quant, t.field_clamp);
+    let id = recipe_id_from_params(t.seed, q.min, q.max, q.shift, c.min, c.max);
+    assert_eq!(id, recipe_id_hex(&t));
+    assert_ne!(id, recipe_id_hex(&r));
+}
+
+#[test]
+fn recipe_id_patcher_follows_any_base_layout() {
+    use k8dnz_core::recipe::format::{recipe_id_hex, RecipeIdPatcher, FORMAT_VERSION_QUANT_GAMMA};
+
+    let mut base = recipe::defaults::default_recipe();
+    base.field_clamp.soft_knee = Some(1_000);
+    base.quant_gamma = Some(2.0);
+    base.version = FORMAT_VERSION_QUANT_GAMMA;
+    let mut ids = RecipeIdPatcher::new(&base);
+
+    for shift in [-5_000i64, 0, 777] {
+        let mut r = base.clone();
+        r.seed = 9;
+        r.quant.shift = shift;
+        r.field_clamp.max += 11;
+        let (q, c) = (r.quant, r.field_clamp);
+        assert_eq!(
+            ids.id_hex(r.seed, q.min, q.max, q.shift, c.min, c.max),
+            recipe_id_hex(&r)
+        );
+    }
+
+    let mut old = recipe::defaults::default_recipe();
+    old.version = 3;
+    let mut ids = RecipeIdPatcher::new(&old);
+    let mut r = old.clone();
+    r.quant.min -= 1;
+    let (q, c) = (r.quant, r.field_clamp);
+    assert_eq!(
+        ids.id_hex(r.seed, q.min, q.max, q.shift, c.min, c.max),
+        recipe_id_hex(&r)
+    );
+}
@@ -0,0 +1,53 @@
+// crates/k8dnz-core/tests/recipe_interpolate.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::format::recipe_id_hex;
+use k8dnz_core::Recipe;
+
+fn endpoints() -> (Recipe, Recipe) {
+    let a = default_recipe();
+    let mut b = a.clone();
+    b.seed = u64::MAX;
+    b.quant.min = a.quant.min - 1000;
+    b.quant.max = a.quant.max + 3001;
+    b.quant.shift = a.quant.shift + 10;
+    b.field_clamp.min = a.field_clamp.min - 4;
+    b.field_clamp.max = a.field_clamp.max + 4;
+    (a, b)
+}
+
+#[test]
+fn endpoints_are_exact() {
+    let (a, b) = endpoints();
+    let r0 = Recipe::interpolate(&a, &b, 0.0).unwrap();
+    let r1 = Recipe::interpolate(&a, &b, 1.0).unwrap();
+    assert_eq!(recipe_id_hex(&r0), recipe_id_hex(&a));
+    assert_eq!(recipe_id_hex(&r1), recipe_id_hex(&b));
+}
+
+#[test]
+fn midpoint_rounds_to_nearest() {
+    let (a, b) = endpoints();
+    let m = Recipe::interpolate(&a, &b, 0.5).unwrap();
+    assert_eq!(m.quant.min, a.quant.min - 500);
+    // +3001 / 2 = 1500.5 -> 1501
+    assert_eq!(m.quant.max, a.quant.max + 1501);
+    assert_eq!(m.quant.shift, a.quant.shift + 5);
+    assert_eq!(m.field_clamp.min, a.field_clamp.min - 2);
+    assert_eq!(m.field_clamp.max, a.field_clamp.max + 2);
+    assert!(m.seed > a.seed && m.seed < b.seed);
+}
+
+#[test]
+fn rejects_bad_t_and_inverted_quant() {
+    let (a, b) = endpoints();
+    assert!(Recipe::interpolate(&a, &b, -0.1).is_err());
+    assert!(Recipe::interpolate(&a, &b, 1.5).is_err());
+    assert!(Recipe::interpolate(&a, &b, f64::NAN).is_err());
+
+    let mut c = a.clone();
+    c.quant.min = a.quant.max + 10;
+    c.quant.max = a.quant.min - 10;
+    // Crossing ranges collapse around the middle.
+    assert!(Recipe::interpolate(&a, &c, 0.5).is_err());
+}
@@ -0,0 +1,43 @@
+// crates/k8dnz-core/tests/recipe_presets.rs
+
+use k8dnz_core::recipe::defaults::{default_recipe, get, DefaultRecipes};
+use k8dnz_core::recipe::format::{decode, encode, recipe_id_hex};
+use k8dnz_core::Engine;
+
+#[test]
+fn names_roundtrip_and_are_unique() {
+    for p in DefaultRecipes::ALL {
+        assert_eq!(DefaultRecipes::from_name(p.name()), Some(p));
+    }
+    assert_eq!(DefaultRecipes::from_name("tuned"), None);
+}
+
+#[test]
+fn default_preset_is_default_recipe_and_flat_is_baseline() {
+    assert_eq!(
+        recipe_id_hex(&get(DefaultRecipes::Default)),
+        recipe_id_hex(&default_recipe())
+    );
+    assert_eq!(get(DefaultRecipes::Flat).quant.shift, 0);
+}
+
+#[test]
+fn presets_are_distinct_and_survive_k8r_roundtrip() {
+    let ids: Vec<String> = DefaultRecipes::ALL
+        .iter()
+        .map(|&p| recipe_id_hex(&get(p)))
+        .collect();
+    for (i, a) in ids.iter().enumerate() {
+        assert!(!ids[i + 1..].contains(a), "duplicate preset id {a}");
+    }
+
+    for p in DefaultRecipes::ALL {
+        let r = get(p);
+        let back = decode(&encode(&r)).unwrap();
+        assert_eq!(recipe_id_hex(&back), recipe_id_hex(&r), "{}", p.name());
+        assert_eq!(back.rgb.g_step, r.rgb.g_step);
+
+        let mut e = Engine::new(r).unwrap();
+        assert_eq!(e.run_emissions(64, 50_000_000).len(), 64, "{}", p.name());
+    }
+}
@@ -0,0 +1,26 @@
+// crates/k8dnz-core/tests/recipe_random.rs
+
+use k8dnz_core::recipe::format::{self, recipe_id_hex};
+use k8dnz_core::recipe::random::{random_recipe, RANDOM_QUANT_MAX, RANDOM_QUANT_MIN};
+
+#[test]
+fn random_recipes_are_deterministic_and_in_range() {
+    let (a, _) = random_recipe(12345);
+    let (b, _) = random_recipe(12345);
+    assert_eq!(recipe_id_hex(&a), recipe_id_hex(&b));
+
+    let (c, _) = random_recipe(12346);
+    assert_ne!(recipe_id_hex(&a), recipe_id_hex(&c));
+
+    for seed in 0..32u64 {
+        let (r, _) = random_recipe(seed);
+        assert!(r.validate_deep().is_empty(), "seed={} {:?}", seed, r.validate_deep());
+        assert!((RANDOM_QUANT_MIN.0..=RANDOM_QUANT_MIN.1).contains(&r.quant.min));
+        assert!((RANDOM_QUANT_MAX.0..=RANDOM_QUANT_MAX.1).contains(&r.quant.max));
+        assert!(r.quant.shift >= r.quant.min && r.quant.shift <= r.quant.max);
+        assert!(r.field_clamp.min < r.quant.min && r.field_clamp.max > r.quant.max);
+
+        let back = format::decode(&format::encode(&r)).unwrap();
+        assert_eq!(recipe_id_hex(&back), recipe_id_hex(&r));
+    }
+}
@@ -0,0 +1,31 @@
+// crates/k8dnz-core/tests/recipe_rgb_layout.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::format::{decode, encode, FORMAT_VERSION, FORMAT_VERSION_RGB};
+
+#[test]
+fn v5_layout_roundtrips_rgb_params() {
+    let mut r = default_recipe();
+    r.version = FORMAT_VERSION_RGB;
+    r.rgb.g_step = -7;
+    r.rgb.p_scale = 5;
+    r.rgb.base_a = [1, 2, 3];
+
+    let dec = decode(&encode(&r)).unwrap();
+    assert_eq!(dec.version, FORMAT_VERSION_RGB);
+    assert_eq!(dec.rgb.g_step, -7);
+    assert_eq!(dec.rgb.p_scale, 5);
+    assert_eq!(dec.rgb.base_a, [1, 2, 3]);
+    assert!(dec.validate_deep().is_empty());
+}
+
+#[test]
+fn v4_layout_ignores_rgb_params() {
+    let r = default_recipe();
+    assert_eq!(r.version, FORMAT_VERSION);
+
+    let mut tuned = r.clone();
+    tuned.rgb.g_step = 3;
+    assert_eq!(encode(&r), encode(&tuned));
+    assert_eq!(decode(&encode(&tuned)).unwrap().rgb.g_step, r.rgb.g_step);
+}
@@ -0,0 +1,91 @@
+// crates/k8dnz-core/tests/recipe_shamir.rs
+
+use k8dnz_core::error::K8Error;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::format::{self, recipe_id_hex};
+use k8dnz_core::recipe::shamir::{combine_shares, decode_share, encode_share, split_secret, Share};
+
+/// Deterministic stand-in for the OS random source.
+fn test_rng(seed: u64) -> impl FnMut(&mut [u8]) {
+    let mut s = seed;
+    move |buf: &mut [u8]| {
+        for b in buf.iter_mut() {
+            s = s.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            *b = (s >> 56) as u8;
+        }
+    }
+}
+
+fn split_recipe(shares: u8, threshold: u8) -> (Vec<u8>, Vec<Share>) {
+    let secret = format::encode(&default_recipe());
+    let out = split_secret(&secret, shares, threshold, test_rng(7)).unwrap();
+    (secret, out)
+}
+
+#[test]
+fn any_threshold_shares_rebuild_the_recipe() {
+    let (secret, shares) = split_recipe(5, 3);
+    assert_eq!(shares.len(), 5);
+    assert!(shares.iter().all(|s| s.bytes != secret));
+
+    for combo in [[0, 1, 2], [0, 1, 3], [1, 3, 4], [4, 2, 0]] {
+        let picked: Vec<Share> = combo.iter().map(|&i| shares[i].clone()).collect();
+        let got = combine_shares(&picked).unwrap();
+        assert_eq!(got, secret, "combo {:?}", combo);
+        let r = format::decode(&got).unwrap();
+        assert_eq!(recipe_id_hex(&r), recipe_id_hex(&default_recipe()));
+    }
+}
+
+#[test]
+fn fewer_than_threshold_shares_do_not_rebuild() {
+    let (secret, shares) = split_recipe(5, 3);
+    assert!(combine_shares(&shares[..2]).is_err());
+
+    // Even interpolating the two shares as if they were enough gives garbage.
+    let mut two: Vec<Share> = shares[1..3].to_vec();
+    for s in two.iter_mut() {
+        s.threshold = 2;
+    }
+    let got = combine_shares(&two).unwrap();
+    assert_ne!(got, secret);
+    assert!(format::decode(&got).is_err());
+}
+
+#[test]
+fn share_files_roundtrip_and_detect_corruption() {
+    let (_, shares) = split_recipe(3, 2);
+    let bytes = encode_share(&shares[1]);
+    assert_eq!(decode_share(&bytes).unwrap(), shares[1]);
+
+    for at in [5, 20, bytes.len() - 1] {
+        let mut bad = bytes.clone();
+        bad[at] ^= 0x01;
+        assert!(decode_share(&bad).is_err(), "flip at {}", at);
+    }
+
+    // Shares from different splits are refused.
+    let secret = format::encode(&default_recipe());
+    let other = split_secret(&secret, 3, 2, test_rng(8)).unwrap();
+    assert!(combine_shares(&[shares[0].clone(), other[1].clone()]).is_err());
+
+    assert!(split_secret(&secret, 3, 1, test_rng(1)).is_err());
+    assert!(split_secret(&secret, 3, 4, test_rng(1)).is_err());
+}
+
+#[test]
+fn share_headers_outside_the_split_are_rejected() {
+    let (_, shares) = split_recipe(3, 2);
+    for (index, threshold) in [(0, 2), (4, 2), (1, 1), (1, 0), (1, 4)] {
+        let s = Share {
+            index,
+            threshold,
+            ..shares[0].clone()
+        };
+        let err = decode_share(&encode_share(&s)).unwrap_err();
+        assert!(
+            matches!(err, K8Error::InvalidShare { index: i, threshold: t, shares: 3 } if i == index && t == threshold),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// crates/k8dnz-core/tests/recipe_text_forms.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::format::{encode, recipe_id_hex, recipe_id_hex_from_base64};
+use k8dnz_core::Recipe;
+
+fn splitmix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Deterministic spread of recipes; dropping waves varies the encoded length mod 3.
+fn variants() -> Vec<Recipe> {
+    let base = default_recipe();
+    (0..48u64)
+        .map(|i| {
+            let mut r = base.clone();
+            let x = splitmix64(i);
+            r.seed = x;
+            r.quant.shift = (x >> 40) as i64 - (1 << 23);
+            r.quant.max = r.quant.min + 1 + (x & 0xFFFF_FFFF) as i64;
+            r.field
+                .waves
+                .truncate(i as usize % (base.field.waves.len() + 1));
+            r
+        })
+        .collect()
+}
+
+#[test]
+fn base64_roundtrip_preserves_bytes_and_id() {
+    for r in variants() {
+        let s = r.to_base64();
+        assert!(s
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'));
+        assert_eq!(s.len(), (encode(&r).len() * 4).div_ceil(3));
+
+        let back = Recipe::from_base64(&s).unwrap();
+        assert_eq!(encode(&back), encode(&r));
+        assert_eq!(recipe_id_hex_from_base64(&s).unwrap(), recipe_id_hex(&r));
+    }
+}
+
+#[test]
+fn hex_roundtrip_preserves_bytes() {
+    for r in variants() {
+        let s = r.to_hex();
+        assert_eq!(s.len(), encode(&r).len() * 2);
+        assert_eq!(encode(&Recipe::from_hex(&s).unwrap()), encode(&r));
+        assert_eq!(
+            encode(&Recipe::from_hex(&s.to_ascii_uppercase()).unwrap()),
+            encode(&r)
+        );
+    }
+}
+
+#[test]
+fn corrupted_text_forms_are_rejected() {
+    let r = default_recipe();
+
+    let mut s = r.to_base64().into_bytes();
+    let mid = s.len() / 2;
+    s[mid] = if s[mid] == b'A' { b'B' } else { b'A' };
+    let s = String::from_utf8(s).unwrap();
+    assert!(Recipe::from_base64(&s).is_err());
+    assert!(recipe_id_hex_from_base64(&s).is_err());
+
+    assert!(Recipe::from_base64("not base64!").is_err());
+    assert!(Recipe::from_hex(&r.to_hex()[1..]).is_err());
+}
+
+#[test]
+fn base64_and_ark1s_agree_for_every_format_version() {
+    use k8dnz_core::recipe::ark_key::{decode_ark1s, encode_ark1s};
+    use k8dnz_core::recipe::format::{
+        FORMAT_VERSION_QUANT_GAMMA, FORMAT_VERSION_RGB, FORMAT_VERSION_SOFT_CLAMP,
+    };
+
+    let mut v5 = default_recipe();
+    v5.version = FORMAT_VERSION_RGB;
+    v5.rgb.g_step = 9;
+    let mut v6 = v5.clone();
+    v6.version = FORMAT_VERSION_SOFT_CLAMP;
+    v6.field_clamp.soft_knee = Some(4_096);
+    let mut v7 = v6.clone();
+    v7.version = FORMAT_VERSION_QUANT_GAMMA;
+    v7.quant_gamma = Some(2.0);
+
+    for r in [default_recipe(), v5, v6, v7] {
+        let via_ark = decode_ark1s(&encode_ark1s(&r)).unwrap();
+        assert_eq!(via_ark.to_base64(), r.to_base64(), "v{}", r.version);
+        assert_eq!(recipe_id_hex(&via_ark), recipe_id_hex(&r));
+    }
+}
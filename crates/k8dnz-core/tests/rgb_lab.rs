@@ -0,0 +1,67 @@
+// crates/k8dnz-core/tests/rgb_lab.rs
+
+use k8dnz_core::signal::token::{Rgb, RgbPairToken};
+
+type Lab = (f64, f64, f64);
+
+fn assert_lab(got: Lab, want: Lab) {
+    let close = |x: f64, y: f64| (x - y).abs() < 1e-3;
+    assert!(
+        close(got.0, want.0) && close(got.1, want.1) && close(got.2, want.2),
+        "got {got:?}, want {want:?}"
+    );
+}
+
+// Reference values: sRGB, D65 white (as published by e.g. Bruce Lindbloom's calculator).
+const REFERENCE: [((u8, u8, u8), Lab); 8] = [
+    ((0, 0, 0), (0.0, 0.0, 0.0)),
+    ((255, 255, 255), (100.0, 0.0, 0.0)),
+    ((128, 128, 128), (53.5850, 0.0, 0.0)),
+    ((255, 0, 0), (53.2408, 80.0925, 67.2032)),
+    ((0, 255, 0), (87.7347, -86.1827, 83.1793)),
+    ((0, 0, 255), (32.2970, 79.1875, -107.8602)),
+    ((255, 255, 0), (97.1393, -21.5537, 94.4780)),
+    ((60, 120, 255), (53.6113, 27.1003, -72.6582)),
+];
+
+#[test]
+fn rgb_to_lab_matches_reference_values() {
+    for ((r, g, b), want) in REFERENCE {
+        assert_lab(Rgb::new(r, g, b).to_lab(), want);
+    }
+}
+
+#[test]
+fn pair_to_lab_uses_the_mean_color() {
+    let same = RgbPairToken {
+        a: Rgb::new(255, 0, 0),
+        c: Rgb::new(255, 0, 0),
+    };
+    assert_lab(same.to_lab(), Rgb::new(255, 0, 0).to_lab());
+
+    // Black + white averages to 127.5 gray, not to a mix of their Lab values.
+    let bw = RgbPairToken {
+        a: Rgb::new(0, 0, 0),
+        c: Rgb::new(255, 255, 255),
+    };
+    let (l, a, b) = bw.to_lab();
+    assert!((l - 53.39).abs() < 0.01, "{l}");
+    assert!(a.abs() < 1e-3 && b.abs() < 1e-3);
+
+    let swapped = RgbPairToken { a: bw.c, c: bw.a };
+    assert_eq!(swapped.to_lab(), bw.to_lab());
+}
+
+#[test]
+fn lab_axes_track_perceptual_opponents() {
+    for n in 0..=255u8 {
+        let tok = k8dnz_core::PairToken::unpack_byte(n).to_rgb_pair();
+        let (l, _, _) = tok.to_lab();
+        assert!((0.0..=100.0).contains(&l));
+    }
+    // Red is +a, green -a; yellow +b, blue -b.
+    assert!(Rgb::new(255, 0, 0).to_lab().1 > 0.0);
+    assert!(Rgb::new(0, 255, 0).to_lab().1 < 0.0);
+    assert!(Rgb::new(255, 255, 0).to_lab().2 > 0.0);
+    assert!(Rgb::new(0, 0, 255).to_lab().2 < 0.0);
+}
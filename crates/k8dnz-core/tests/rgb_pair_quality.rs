@@ -0,0 +1,52 @@
+// crates/k8dnz-core/tests/rgb_pair_quality.rs
+
+use k8dnz_core::signal::token::{PairToken, Rgb, RgbPairToken};
+
+fn pair(a: Rgb, c: Rgb) -> RgbPairToken {
+    RgbPairToken { a, c }
+}
+
+#[test]
+fn contrast_spans_black_to_white() {
+    let black = Rgb::new(0, 0, 0);
+    let white = Rgb::new(255, 255, 255);
+    assert_eq!(pair(black, white).contrast(), 255);
+    assert_eq!(pair(white, black).contrast(), 255);
+    assert_eq!(pair(white, white).contrast(), 0);
+}
+
+#[test]
+fn hue_difference_takes_the_short_way_round() {
+    let red = Rgb::new(255, 0, 0);
+    let green = Rgb::new(0, 255, 0);
+    let cyan = Rgb::new(0, 255, 255);
+    let magenta = Rgb::new(255, 0, 255);
+    assert_eq!(pair(red, green).hue_difference(), 120);
+    assert_eq!(pair(red, cyan).hue_difference(), 180);
+    assert_eq!(pair(red, magenta).hue_difference(), 60);
+    assert_eq!(pair(red, Rgb::new(128, 128, 128)).hue_difference(), 0);
+}
+
+#[test]
+fn saturation_is_hsv() {
+    let t = pair(Rgb::new(255, 0, 0), Rgb::new(200, 200, 200));
+    assert_eq!(t.saturation_a(), 255);
+    assert_eq!(t.saturation_c(), 0);
+    assert_eq!(
+        pair(Rgb::new(0, 0, 0), Rgb::new(255, 60, 60)).saturation_a(),
+        0
+    );
+    assert_eq!(
+        pair(Rgb::new(0, 0, 0), Rgb::new(255, 60, 60)).saturation_c(),
+        195
+    );
+}
+
+#[test]
+fn palette_pairs_stay_in_range() {
+    for x in 0..=255u8 {
+        let t = PairToken::from_byte(x).to_rgb_pair();
+        assert!(t.hue_difference() <= 180);
+        assert_eq!(t.contrast(), t.c.luminance().abs_diff(t.a.luminance()));
+    }
+}
@@ -0,0 +1,49 @@
+// crates/k8dnz-core/tests/stats_complexity.rs
+
+use k8dnz_core::stats::{lz78_complexity, lz_complexity_normalized};
+
+fn xorshift_bytes(n: usize, mut s: u64) -> Vec<u8> {
+    (0..n)
+        .map(|_| {
+            s ^= s << 13;
+            s ^= s >> 7;
+            s ^= s << 17;
+            (s >> 24) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn lz78_counts_phrases_of_known_parsings() {
+    assert_eq!(lz78_complexity(b""), 0);
+    assert_eq!(lz78_complexity(b"a"), 1);
+    // a | aa | b | ab | aba | ab (trailing repeat still counts)
+    assert_eq!(lz78_complexity(b"aaabababaab"), 6);
+    // a | b | ab | aba | bc | c
+    assert_eq!(lz78_complexity(b"ababababcc"), 6);
+    // Constant input: phrases grow by one byte each, so c*(c+1)/2 >= n.
+    let c = lz78_complexity(&[7u8; 5050]);
+    assert_eq!(c, 100);
+}
+
+#[test]
+fn normalized_is_near_one_for_random_and_low_for_repetition() {
+    let random = xorshift_bytes(200_000, 0x1234_5678_9ABC_DEF1);
+    let r = lz_complexity_normalized(&random);
+    assert!((0.8..1.0).contains(&r), "random: {r}");
+
+    let text = b"In the beginning God created the heaven and the earth. ".repeat(2_000);
+    let t = lz_complexity_normalized(&text);
+    assert!(t < 0.1, "repetitive: {t}");
+
+    assert_eq!(lz_complexity_normalized(b""), 0.0);
+    assert_eq!(lz_complexity_normalized(b"x"), 0.0);
+}
+
+#[test]
+fn normalized_can_exceed_one_for_short_inputs() {
+    let mut bytes: Vec<u8> = (0..=255u8).collect();
+    assert_eq!(lz_complexity_normalized(&bytes), 1.0);
+    bytes.push(0);
+    assert!(lz_complexity_normalized(&bytes) > 1.0);
+}
@@ -0,0 +1,56 @@
+// crates/k8dnz-core/tests/stats_correlation.rs
+
+use k8dnz_core::stats::{correlation_p_value, correlation_test, pearson, spearman};
+
+fn xorshift_bytes(n: usize, seed: u64) -> Vec<u8> {
+    let mut x = seed;
+    (0..n)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            (x >> 56) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn identical_and_inverted_streams_are_fully_correlated() {
+    let a = xorshift_bytes(4096, 0x9E37_79B9_7F4A_7C15);
+    let inv: Vec<u8> = a.iter().map(|&v| 255 - v).collect();
+
+    let c = correlation_test(&a, &a);
+    assert!((c.pearson - 1.0).abs() < 1e-9);
+    assert!((c.spearman - 1.0).abs() < 1e-9);
+    assert_eq!(c.pearson_p, 0.0);
+    assert!((pearson(&a, &inv) + 1.0).abs() < 1e-9);
+    assert!((spearman(&a, &inv) + 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn spearman_sees_monotone_nonlinear_relation() {
+    let a: Vec<u8> = (0..=255u8).collect();
+    let squashed: Vec<u8> = a.iter().map(|&v| ((v as u32 * v as u32) >> 8) as u8).collect();
+    assert!((spearman(&a, &squashed) - 1.0).abs() < 1e-3);
+    assert!(pearson(&a, &squashed) < 0.99);
+}
+
+#[test]
+fn independent_streams_are_uncorrelated() {
+    let a = xorshift_bytes(20_000, 1);
+    let b = xorshift_bytes(20_000, 0xDEAD_BEEF);
+    let c = correlation_test(&a, &b);
+    assert_eq!(c.n, 20_000);
+    assert!(c.pearson.abs() < 0.05, "r={}", c.pearson);
+    assert!(c.pearson_p > 0.001, "p={}", c.pearson_p);
+}
+
+#[test]
+fn p_value_matches_t_distribution() {
+    // r = 0.2, n = 100 => t = 2.0202 with 98 df; two-sided p ~= 0.0461.
+    let p = correlation_p_value(0.2, 100);
+    assert!((p - 0.0461).abs() < 2e-3, "p={p}");
+    assert_eq!(correlation_p_value(0.0, 100), 1.0);
+    assert_eq!(correlation_p_value(0.5, 2), 1.0);
+    assert_eq!(pearson(&[7, 7, 7], &[1, 2, 3]), 0.0);
+}
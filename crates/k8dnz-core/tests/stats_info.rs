@@ -0,0 +1,85 @@
+// crates/k8dnz-core/tests/stats_info.rs
+
+use k8dnz_core::stats::{
+    bigram_entropy, conditional_entropy, cross_entropy, kl_divergence, mutual_information,
+    trigram_entropy,
+};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn identical_streams_share_all_information() {
+    // uniform over 4 symbols => H(X) = 2 bits
+    let xs: Vec<u8> = (0..4000u32).map(|i| (i % 4) as u8).collect();
+    assert!(close(mutual_information(&xs, &xs), 2.0));
+    assert!(close(conditional_entropy(&xs, &xs), 0.0));
+}
+
+#[test]
+fn independent_streams_share_nothing() {
+    // x cycles every 2, y every 4 at half speed => all 8 (x,y) pairs equally likely
+    let xs: Vec<u8> = (0..8000u32).map(|i| (i % 2) as u8).collect();
+    let ys: Vec<u8> = (0..8000u32).map(|i| ((i / 2) % 4) as u8).collect();
+    assert!(close(mutual_information(&xs, &ys), 0.0));
+    assert!(close(conditional_entropy(&xs, &ys), 2.0));
+}
+
+#[test]
+fn deterministic_function_bounds() {
+    // y = x / 2 : knowing x fixes y, but not the other way round
+    let xs: Vec<u8> = (0..8000u32).map(|i| (i % 8) as u8).collect();
+    let ys: Vec<u8> = xs.iter().map(|&x| x / 2).collect();
+    assert!(close(mutual_information(&xs, &ys), 2.0));
+    assert!(close(conditional_entropy(&xs, &ys), 0.0));
+    assert!(close(conditional_entropy(&ys, &xs), 1.0));
+}
+
+#[test]
+fn empty_and_mismatched_lengths() {
+    assert!(close(mutual_information(&[], &[]), 0.0));
+    // compared over the common prefix
+    assert!(close(mutual_information(&[1, 2, 1, 2], &[1, 2]), 1.0));
+}
+
+#[test]
+fn bigram_and_trigram_entropy_of_patterns() {
+    // period 4 over 4 symbols: H1 = 2, but the previous byte fixes the next one
+    let cyc: Vec<u8> = (0..4000u32).map(|i| (i % 4) as u8).collect();
+    assert!(close(bigram_entropy(&cyc), 0.0));
+    assert_eq!(trigram_entropy(&cyc), (0.0, 4));
+
+    // 0,x,0,y,... with x,y a free bit: after 0 one bit of choice, after 1/2 none
+    let mut alt = Vec::new();
+    for i in 0..4000u32 {
+        alt.push(0u8);
+        alt.push(1 + ((i * 7) >> 2 & 1) as u8);
+    }
+    let h2 = bigram_entropy(&alt);
+    assert!((h2 - 0.5).abs() < 1e-3, "h2={h2}");
+    assert!(trigram_entropy(&alt).0 <= h2 + 1e-9);
+
+    assert!(close(bigram_entropy(&[7]), 0.0));
+    assert_eq!(trigram_entropy(&[1, 2]), (0.0, 0));
+}
+
+#[test]
+fn cross_entropy_and_kl_against_smoothed_model() {
+    // Every byte value 4 times: the smoothed model is exactly uniform.
+    let uniform: Vec<u8> = (0..1024u32).map(|i| i as u8).collect();
+    assert!(close(cross_entropy(&uniform, &uniform), 8.0));
+    assert!(close(kl_divergence(&uniform, &uniform), 0.0));
+
+    // P = {0, 1} each half; Q has only 0s: q(0) = 1001/1256, q(1) = 1/1256.
+    let p: Vec<u8> = (0..1000u32).map(|i| (i % 2) as u8).collect();
+    let q = vec![0u8; 1000];
+    let want = -0.5 * (1001.0f64 / 1256.0).log2() - 0.5 * (1.0f64 / 1256.0).log2();
+    assert!(close(cross_entropy(&p, &q), want));
+    assert!(close(kl_divergence(&p, &q), want - 1.0));
+
+    assert!(close(cross_entropy(&[], &q), 0.0));
+    assert!(close(kl_divergence(&[], &q), 0.0));
+    // an empty model is uniform after smoothing
+    assert!(close(cross_entropy(&p, &[]), 8.0));
+}
@@ -0,0 +1,65 @@
+// crates/k8dnz-core/tests/stats_randomness.rs
+
+use k8dnz_core::dynamics::engine::Engine;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::stats::{stat_test_bytes, STAT_TEST_ALPHA};
+
+fn xorshift_bytes(n: usize) -> Vec<u8> {
+    let mut x: u64 = 0x9E37_79B9_7F4A_7C15;
+    (0..n)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            (x >> 56) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn constant_stream_fails_every_test() {
+    let r = stat_test_bytes(&[128u8; 16 * 1024], STAT_TEST_ALPHA);
+    assert_eq!(r.bytes, 16 * 1024);
+    assert_eq!(r.tests.len(), 4);
+    for t in &r.tests {
+        assert!(!t.pass, "{} unexpectedly passed (p={})", t.name, t.p_value);
+    }
+    assert_eq!(r.failed().count(), 4);
+}
+
+#[test]
+fn pseudo_random_stream_passes_every_test() {
+    let r = stat_test_bytes(&xorshift_bytes(64 * 1024), STAT_TEST_ALPHA);
+    for t in &r.tests {
+        assert!(
+            t.pass,
+            "{} failed: stat={} p={}",
+            t.name, t.statistic, t.p_value
+        );
+    }
+    assert!(r.all_pass());
+}
+
+#[test]
+fn correlated_stream_fails_serial_and_runs() {
+    // Slow ramp: uniform histogram, but neighbours are nearly equal.
+    let bytes: Vec<u8> = (0..64 * 1024).map(|i| (i / 64 % 256) as u8).collect();
+    let r = stat_test_bytes(&bytes, STAT_TEST_ALPHA);
+    let by_name = |n: &str| r.tests.iter().find(|t| t.name == n).unwrap();
+    assert!(by_name("chi2_bytes").pass);
+    assert!(!by_name("serial_corr").pass);
+    assert!(!by_name("runs_median").pass);
+}
+
+#[test]
+fn engine_statistical_test_run_is_deterministic() {
+    let a = Engine::new(default_recipe())
+        .unwrap()
+        .statistical_test_run(2_000, 50_000_000);
+    let b = Engine::new(default_recipe())
+        .unwrap()
+        .statistical_test_run(2_000, 50_000_000);
+    assert_eq!(a, b);
+    assert_eq!(a.bytes, 2_000);
+    assert_eq!(a.alpha, STAT_TEST_ALPHA);
+}
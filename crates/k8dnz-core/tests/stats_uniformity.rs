@@ -0,0 +1,47 @@
+// crates/k8dnz-core/tests/stats_uniformity.rs
+
+use k8dnz_core::stats::{chi_squared_uniform, ks_uniform};
+
+fn xorshift_bytes(n: usize) -> Vec<u8> {
+    let mut x: u64 = 0x9E37_79B9_7F4A_7C15;
+    (0..n)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            (x >> 56) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn perfectly_flat_histogram_passes() {
+    let bytes: Vec<u8> = (0..256 * 16).map(|i| (i % 256) as u8).collect();
+    let chi = chi_squared_uniform(&bytes);
+    assert_eq!(chi.statistic, 0.0);
+    assert!(chi.p_value > 0.999);
+    assert!(ks_uniform(&bytes).statistic < 1e-12);
+}
+
+#[test]
+fn chi2_p_value_near_median_at_dof() {
+    // n = 256 (expected 1 per bin); half the bins hold 2, half hold 0 => chi2 = 256.
+    let bytes: Vec<u8> = (0..128u32).flat_map(|b| [b as u8, b as u8]).collect();
+    let chi = chi_squared_uniform(&bytes);
+    assert!((chi.statistic - 256.0).abs() < 1e-9);
+    // Chi-squared(255) median is ~254.3, so p just under 0.5.
+    assert!(chi.p_value > 0.40 && chi.p_value < 0.50, "p={}", chi.p_value);
+}
+
+#[test]
+fn pseudo_random_passes_and_text_fails() {
+    let rnd = xorshift_bytes(64 * 1024);
+    assert!(chi_squared_uniform(&rnd).passes(0.01));
+    assert!(ks_uniform(&rnd).passes(0.01));
+
+    let text = b"In the beginning God created the heaven and the earth.\n".repeat(200);
+    let chi = chi_squared_uniform(&text);
+    assert!(!chi.passes(0.05));
+    assert!(chi.p_value < 1e-6);
+    assert!(!ks_uniform(&text).passes(0.05));
+}
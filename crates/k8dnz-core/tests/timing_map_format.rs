@@ -0,0 +1,63 @@
+// crates/k8dnz-core/tests/timing_map_format.rs
+
+use k8dnz_core::signal::timing_map::TimemapFormat;
+use k8dnz_core::TimingMap;
+
+fn tm(indices: &[u64]) -> TimingMap {
+    TimingMap::new(indices.to_vec()).unwrap()
+}
+
+#[test]
+fn encoded_len_matches_the_real_encoding() {
+    let maps = [
+        tm(&[7]),
+        tm(&[10, 11, 12, 13]),
+        tm(&[0, 5, 10, 15, 20]),
+        tm(&[1, 2, 3, 100, 101, 102, 500]),
+        tm(&[3, 90, 91, 4_000_000, 4_000_003]),
+    ];
+    for m in &maps {
+        for f in TimemapFormat::ALL {
+            let enc = m.encode_as(f);
+            assert_eq!(enc.as_ref().map(Vec::len), m.encoded_len(f), "{f:?} {m:?}");
+            if let Some(bytes) = enc {
+                assert_eq!(TimemapFormat::detect(&bytes), Some(f));
+                assert_eq!(&TimingMap::decode_auto(&bytes).unwrap(), m);
+            }
+        }
+    }
+}
+
+#[test]
+fn tm0_only_for_arithmetic_progressions() {
+    assert!(tm(&[0, 5, 10]).encode_as(TimemapFormat::Tm0).is_some());
+    assert!(tm(&[0, 5, 11]).encode_as(TimemapFormat::Tm0).is_none());
+    assert_eq!(tm(&[0, 5, 11]).encoded_len(TimemapFormat::Tm0), None);
+}
+
+#[test]
+fn smallest_format_picks_the_most_compact_encoding() {
+    let contiguous: Vec<u64> = (1_000..5_000).collect();
+    assert_eq!(tm(&contiguous).smallest_format().0, TimemapFormat::Tm0);
+
+    let runs: Vec<u64> = (0..10u64)
+        .flat_map(|r| (r * 1_000..r * 1_000 + 50).collect::<Vec<_>>())
+        .collect();
+    assert_eq!(tm(&runs).smallest_format().0, TimemapFormat::Tm2);
+
+    let sparse: Vec<u64> = (0..100u64).map(|i| i * i + i).collect();
+    let m = tm(&sparse);
+    let (f, n) = m.smallest_format();
+    assert_eq!(f, TimemapFormat::Tm1);
+    assert_eq!(n, m.encode_tm1().len());
+    for g in TimemapFormat::ALL {
+        assert!(m.encoded_len(g).is_none_or(|len| len >= n));
+    }
+}
+
+#[test]
+fn detect_rejects_unknown_or_short_input() {
+    assert_eq!(TimemapFormat::detect(b"TM9\0\x01"), None);
+    assert_eq!(TimemapFormat::detect(b"TM"), None);
+    assert_eq!(TimemapFormat::Tm1.name(), "TM1");
+}
@@ -0,0 +1,47 @@
+// crates/k8dnz-core/tests/timing_map_split.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::signal::timing_map::TimingMap;
+use k8dnz_core::Engine;
+
+fn reconstruct(tm: &TimingMap, stream: &[u8]) -> Vec<u8> {
+    tm.indices.iter().map(|&i| stream[i as usize]).collect()
+}
+
+#[test]
+fn split_halves_reconstruct_the_full_map() {
+    let mut engine = Engine::new(default_recipe()).unwrap();
+    let stream: Vec<u8> = engine
+        .run_emissions(256, 50_000_000)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect();
+    assert_eq!(stream.len(), 256);
+
+    let full = TimingMap::stride(200, 17, 1).unwrap();
+    let whole = reconstruct(&full, &stream);
+
+    for n in [0usize, 1, 99, 199, 200] {
+        let (a, b) = full.split_at(n);
+        assert_eq!(a, full.slice(0, n));
+        assert_eq!(b, full.slice(n, full.indices.len()));
+        assert_eq!(a.indices.len(), n);
+
+        let mut joined = reconstruct(&a, &stream);
+        joined.extend(reconstruct(&b, &stream));
+        assert_eq!(joined, whole, "n={n}");
+    }
+}
+
+#[test]
+fn slice_clamps_out_of_range_bounds() {
+    let tm = TimingMap::new(vec![3, 5, 8, 13]).unwrap();
+    assert_eq!(tm.slice(1, 3).indices, vec![5, 8]);
+    assert_eq!(tm.slice(2, 99).indices, vec![8, 13]);
+    assert!(tm.slice(3, 1).indices.is_empty());
+    assert!(tm.slice(9, 12).indices.is_empty());
+
+    let (a, b) = tm.split_at(10);
+    assert_eq!(a, tm);
+    assert!(b.indices.is_empty());
+}
@@ -0,0 +1,215 @@
+// crates/k8dnz-core/tests/timing_map_stride_fit.rs
+
+use k8dnz_core::error::K8Error;
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::signal::fit::{
+    apply_mapped_residual_byte, feistel_permute, feistel_unpermute, make_mapped_residual_byte,
+    map_byte, FitObjective, FitOptions, MapMode, ResidualMode, FEISTEL_DEFAULT_ROUNDS,
+};
+use k8dnz_core::{Engine, TimingMap};
+
+const MAX_TICKS: u64 = 50_000_000;
+
+fn stream(n: u64) -> Vec<u8> {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    e.run_emissions(n, MAX_TICKS)
+        .iter()
+        .map(|t| t.pack_byte())
+        .collect()
+}
+
+fn opts(search_emissions: u64) -> FitOptions {
+    FitOptions {
+        search_emissions,
+        ..FitOptions::default()
+    }
+}
+
+/// Rebuilds the target from the timemap positions, the raw stream and the residual.
+fn reconstruct(tm: &TimingMap, resid: &[u8], o: &FitOptions) -> Vec<u8> {
+    let last = tm.last_index().unwrap();
+    let raw = stream(last + 1);
+    tm.indices
+        .iter()
+        .zip(resid)
+        .map(|(&pos, &r)| {
+            let model = map_byte(o.map_mode, o.map_seed, pos, raw[pos as usize], o.feistel_rounds);
+            apply_mapped_residual_byte(
+                o.residual_mode,
+                o.map_mode,
+                o.map_seed,
+                pos,
+                o.feistel_rounds,
+                model,
+                r,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn exact_slice_of_the_stream_fits_with_zero_residual() {
+    let raw = stream(2_000);
+    let target = raw[700..764].to_vec();
+    let o = FitOptions {
+        objective: FitObjective::Matches,
+        ..opts(2_000)
+    };
+
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let (tm, resid) = TimingMap::stride_from_fit(&target, &mut e, MAX_TICKS, &o).unwrap();
+
+    assert_eq!(resid, vec![0u8; target.len()]);
+    assert!(tm.is_contiguous_stride(1));
+    assert_eq!(tm.indices.len(), target.len());
+    let start = tm.indices[0] as usize;
+    assert!(start <= 700, "earliest perfect window wins, got {start}");
+    assert_eq!(&raw[start..start + target.len()], &target[..]);
+}
+
+#[test]
+fn residual_reconstructs_target_for_every_mode() {
+    let target: Vec<u8> = b"In the beginning God created the heaven and the earth.".to_vec();
+    for map_mode in [
+        MapMode::None,
+        MapMode::Splitmix64,
+        MapMode::Ascii7,
+        MapMode::Ascii7Splitmix,
+        MapMode::Feistel,
+    ] {
+        for residual_mode in [ResidualMode::Xor, ResidualMode::Sub] {
+            for objective in [FitObjective::Matches, FitObjective::Zstd] {
+                let o = FitOptions {
+                    map_mode,
+                    map_seed: 0xDEAD_BEEF,
+                    residual_mode,
+                    objective,
+                    ..opts(600)
+                };
+                let mut e = Engine::new(default_recipe()).unwrap();
+                let (tm, resid) =
+                    TimingMap::stride_from_fit(&target, &mut e, MAX_TICKS, &o).unwrap();
+                assert_eq!(resid.len(), target.len());
+                assert_eq!(
+                    reconstruct(&tm, &resid, &o),
+                    target,
+                    "{map_mode:?} {residual_mode:?} {objective:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn feistel_rounds_come_from_the_options() {
+    let target: Vec<u8> = b"In the beginning God created the heaven and the earth.".to_vec();
+    let fit = |feistel_rounds: u8| {
+        let o = FitOptions {
+            map_mode: MapMode::Feistel,
+            map_seed: 11,
+            feistel_rounds,
+            ..opts(600)
+        };
+        let mut e = Engine::new(default_recipe()).unwrap();
+        let (tm, resid) = TimingMap::stride_from_fit(&target, &mut e, MAX_TICKS, &o).unwrap();
+        assert_eq!(reconstruct(&tm, &resid, &o), target, "rounds={feistel_rounds}");
+        (tm, resid)
+    };
+    assert_eq!(FitOptions::default().feistel_rounds, FEISTEL_DEFAULT_ROUNDS);
+    assert_ne!(fit(2), fit(FEISTEL_DEFAULT_ROUNDS));
+
+    for rounds in [0u8, 1, 4, 9] {
+        let mut seen = [false; 256];
+        for raw in 0..=255u8 {
+            let m = feistel_permute(3, 1234, raw, rounds);
+            assert!(!seen[m as usize], "collision rounds={rounds} raw={raw}");
+            seen[m as usize] = true;
+            assert_eq!(feistel_unpermute(3, 1234, m, rounds), raw);
+        }
+    }
+}
+
+#[test]
+fn feistel_residual_round_trips_through_the_permutation() {
+    for mode in [ResidualMode::Xor, ResidualMode::Sub] {
+        for model in 0..=255u8 {
+            for plain in 0..=255u8 {
+                let r = make_mapped_residual_byte(mode, MapMode::Feistel, 9, 77, 4, model, plain);
+                assert_eq!(r == 0, model == plain, "hit must leave 0 ({mode:?})");
+                let back = apply_mapped_residual_byte(mode, MapMode::Feistel, 9, 77, 4, model, r);
+                assert_eq!(back, plain, "mode={mode:?} model={model} plain={plain}");
+            }
+        }
+    }
+
+    // A miss is no longer the bare `model ^ plain` difference.
+    let leaks = (0..=255u8)
+        .filter(|&p| make_mapped_residual_byte(ResidualMode::Xor, MapMode::Feistel, 9, 77, 4, b'a', p) == b'a' ^ p)
+        .count();
+    assert!(leaks < 256 / 4, "{leaks} residuals equal model ^ plain");
+}
+
+#[test]
+fn best_window_minimises_the_objective() {
+    let target: Vec<u8> = (0..48u8).map(|i| b'a' + i % 26).collect();
+    let o = FitOptions {
+        objective: FitObjective::Matches,
+        map_mode: MapMode::Splitmix64,
+        map_seed: 7,
+        ..opts(1_000)
+    };
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let (tm, resid) = TimingMap::stride_from_fit(&target, &mut e, MAX_TICKS, &o).unwrap();
+    let best = resid.iter().filter(|&&r| r != 0).count();
+
+    let raw = stream(1_000);
+    for s in 0..=raw.len() - target.len() {
+        let miss = (0..target.len())
+            .filter(|&i| {
+                let pos = (s + i) as u64;
+                map_byte(o.map_mode, o.map_seed, pos, raw[s + i], o.feistel_rounds) != target[i]
+            })
+            .count();
+        assert!(miss >= best, "window {s} beats the fit: {miss} < {best}");
+        if s < tm.indices[0] as usize {
+            assert!(miss > best, "earlier window {s} ties the fit");
+        }
+    }
+}
+
+#[test]
+fn positions_continue_from_the_engine_and_respect_scan_step() {
+    let target = stream(400)[300..332].to_vec();
+    let mut e = Engine::new(default_recipe()).unwrap();
+    e.run_emissions(100, MAX_TICKS);
+
+    let o = FitOptions {
+        scan_step: 7,
+        objective: FitObjective::Matches,
+        ..opts(300)
+    };
+    let (tm, resid) = TimingMap::stride_from_fit(&target, &mut e, MAX_TICKS, &o).unwrap();
+    let start = tm.indices[0];
+    assert!(start >= 100);
+    assert_eq!((start - 100) % 7, 0);
+    assert_eq!(reconstruct(&tm, &resid, &o), target);
+}
+
+#[test]
+fn rejects_bad_input_and_short_streams() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    assert!(TimingMap::stride_from_fit(&[], &mut e, MAX_TICKS, &opts(100)).is_err());
+
+    let zero_step = FitOptions {
+        scan_step: 0,
+        ..opts(100)
+    };
+    assert!(TimingMap::stride_from_fit(b"abc", &mut e, MAX_TICKS, &zero_step).is_err());
+
+    let err = TimingMap::stride_from_fit(&[0u8; 64], &mut e, MAX_TICKS, &opts(10)).unwrap_err();
+    assert!(matches!(err, K8Error::InsufficientEmissions { needed: 64, .. }), "{err}");
+
+    // max_ticks caps the stream as well.
+    let mut e = Engine::new(default_recipe()).unwrap();
+    assert!(TimingMap::stride_from_fit(&[0u8; 64], &mut e, 1, &opts(1_000)).is_err());
+}
@@ -0,0 +1,39 @@
+// crates/k8dnz-core/tests/timing_map_text.rs
+
+use k8dnz_core::TimingMap;
+
+#[test]
+fn positions_text_roundtrip_via_file() {
+    let path = std::env::temp_dir().join(format!("k8dnz_tm_text_{}.txt", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let tm = TimingMap::new(vec![0, 3, 4, 1_000_000, u64::MAX]).unwrap();
+    tm.to_positions_file(path).unwrap();
+
+    let back = TimingMap::from_positions_file(path, true);
+    let _ = std::fs::remove_file(path);
+    assert_eq!(back.unwrap(), tm);
+}
+
+#[test]
+fn positions_text_skips_comments_and_blank_lines() {
+    let text = "# exported by a script\n\n  7\n#8\n12  \n";
+    let tm = TimingMap::from_positions_text(text, true).unwrap();
+    assert_eq!(tm.indices, vec![7, 12]);
+}
+
+#[test]
+fn positions_text_sorted_check_is_opt_in() {
+    let text = "5\n3\n3\n";
+    assert!(TimingMap::from_positions_text(text, true).is_err());
+
+    let tm = TimingMap::from_positions_text(text, false).unwrap();
+    assert_eq!(tm.indices, vec![5, 3, 3]);
+}
+
+#[test]
+fn positions_text_rejects_non_u64() {
+    for bad in ["-1\n", "1.5\n", "18446744073709551616\n", "abc\n"] {
+        assert!(TimingMap::from_positions_text(bad, false).is_err(), "{bad:?}");
+    }
+}
@@ -0,0 +1,39 @@
+// crates/k8dnz-core/tests/timing_map_validate.rs
+
+use k8dnz_core::signal::timing_map::TimingMap;
+
+fn raw(indices: Vec<u64>) -> TimingMap {
+    TimingMap { indices }
+}
+
+#[test]
+fn validate_accepts_well_formed_maps() {
+    assert!(TimingMap::stride(100, 7, 3).unwrap().validate().is_ok());
+    assert!(raw(vec![0]).validate().is_ok());
+    assert!(raw(vec![1, 2, 9, u64::MAX - 1]).validate().is_ok());
+}
+
+#[test]
+fn validate_rejects_each_broken_invariant() {
+    let msg = |tm: TimingMap| tm.validate().unwrap_err().to_string();
+    assert!(msg(raw(vec![])).contains("empty"));
+    assert!(msg(raw(vec![1, 4, 4, 9])).contains("duplicate index 4"));
+    assert!(msg(raw(vec![1, 9, 4])).contains("below previous"));
+    assert!(msg(raw(vec![3, u64::MAX])).contains("sentinel"));
+}
+
+#[test]
+fn contiguous_stride_matches_tm0_selection() {
+    let tm = TimingMap::stride(50, 10, 4).unwrap();
+    assert!(tm.is_contiguous_stride(4));
+    assert!(!tm.is_contiguous_stride(1));
+    assert!(!tm.is_contiguous_stride(0));
+    assert_eq!(tm.as_arith_prog(), Some((10, 50, 4)));
+    assert_eq!(&tm.encode_auto()[..4], b"TM0\0");
+
+    let gappy = raw(vec![10, 14, 18, 23]);
+    assert!(!gappy.is_contiguous_stride(4));
+    assert_eq!(gappy.as_arith_prog(), None);
+
+    assert!(raw(vec![5]).is_contiguous_stride(9));
+}
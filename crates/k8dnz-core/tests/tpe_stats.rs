@@ -0,0 +1,41 @@
+// crates/k8dnz-core/tests/tpe_stats.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::stats::TpeStats;
+use k8dnz_core::Engine;
+
+const MAX_TICKS: u64 = 50_000_000;
+
+#[test]
+fn welford_matches_two_pass() {
+    let gaps = [3u64, 9, 4, 4, 17, 1, 8];
+    let mut s = TpeStats::new();
+    for &g in &gaps {
+        s.update(g);
+    }
+    let n = gaps.len() as f64;
+    let mean = gaps.iter().sum::<u64>() as f64 / n;
+    let var = gaps.iter().map(|&g| (g as f64 - mean).powi(2)).sum::<f64>() / n;
+
+    let (m, sd, lo, hi) = s.summary();
+    assert!((m - mean).abs() < 1e-12);
+    assert!((sd - var.sqrt()).abs() < 1e-12);
+    assert_eq!((lo, hi), (1, 17));
+    assert_eq!(TpeStats::new().summary(), (0.0, 0.0, 0, 0));
+}
+
+#[test]
+fn engine_gaps_add_up_to_ticks() {
+    let mut e = Engine::new(default_recipe()).unwrap();
+    let (toks, tpe) = e.run_emissions_with_tpe_stats(200, MAX_TICKS);
+    assert_eq!(toks.len(), 200);
+    assert_eq!(tpe.count(), 200);
+    // The run stops on the 200th emission, so the gaps cover every tick.
+    assert!((tpe.mean() * 200.0 - e.stats.ticks as f64).abs() < 1e-6);
+    assert!(tpe.min() as f64 <= tpe.mean() && tpe.mean() <= tpe.max() as f64);
+
+    let mut a = Engine::new(default_recipe()).unwrap();
+    let mut b = a.clone();
+    let got = a.ticks_per_emission_stats(200);
+    assert_eq!(got, b.run_emissions_with_tpe_stats(200, MAX_TICKS).1.summary());
+}
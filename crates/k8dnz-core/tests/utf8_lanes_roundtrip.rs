@@ -0,0 +1,100 @@
+// crates/k8dnz-core/tests/utf8_lanes_roundtrip.rs
+
+use k8dnz_core::lane::{self, OmegaProgram, TextLanesV2Config, K8L1_VERSION_V5, PUNCT_ALPH, TEXT_FLAG_UTF8};
+use k8dnz_core::recipe::{defaults::default_recipe, format};
+use k8dnz_core::repr::text_norm;
+
+const MAX_TICKS: u64 = 200_000_000;
+
+const FRENCH: &str = "À l'été, le garçon mangeait des crêpes près de la forêt.\nÇa coûte cher ! Où est l'hôtel ? Œuvre, Noël, maïs, Ÿ.\n";
+const SPANISH: &str = "¿Dónde está el niño? ¡Mañana será otro día!\nEl pingüino comió piñas en Año Nuevo; ÉL dijo: «sí».\n";
+const GERMAN: &str = "Größe, Übermaß und Äpfel: Die Straße führt über die Brücke.\r\nSCHÖN — ẞ bleibt roh, ß nicht.\n";
+
+fn encode(input: &[u8], utf8_aware: bool) -> (Vec<u8>, lane::LaneEncodeStats) {
+    lane::encode_k8l1_with_config(
+        input,
+        &format::encode(&default_recipe()),
+        MAX_TICKS,
+        OmegaProgram::default(),
+        None,
+        TextLanesV2Config {
+            utf8_aware,
+            ..Default::default()
+        },
+    )
+    .expect("encode")
+}
+
+#[test]
+fn utf8_aware_roundtrips_european_text() {
+    for text in [FRENCH, SPANISH, GERMAN] {
+        let input = text.as_bytes();
+        let (artifact, stats) = encode(input, true);
+        assert_eq!(artifact[4], K8L1_VERSION_V5, "compressed: {:?}", stats.compressed_patches);
+
+        let accented = text
+            .chars()
+            .filter(|c| ('\u{C0}'..='\u{17E}').contains(c) && c.is_alphabetic() && *c != '\u{131}')
+            .count();
+        let ascii_letters = text.bytes().filter(u8::is_ascii_alphabetic).count();
+        assert_eq!(stats.n_letters, ascii_letters + accented, "{text}");
+
+        let decoded = lane::decode_k8l1(&artifact).expect("decode");
+        assert_eq!(decoded, text_norm::normalize_newlines(input));
+    }
+}
+
+#[test]
+fn utf8_aware_moves_accents_out_of_raw_lane() {
+    let input = FRENCH.as_bytes();
+    let (_, ascii) = encode(input, false);
+    let (_, utf8) = encode(input, true);
+    assert!(utf8.n_raw < ascii.n_raw);
+    assert_eq!(
+        ascii.n_raw - utf8.n_raw,
+        2 * (utf8.n_letters - ascii.n_letters)
+    );
+    assert!(utf8.total_len < ascii.total_len);
+}
+
+#[test]
+fn utf8_aware_falls_back_for_ascii_only_layout() {
+    // Invalid UTF-8 keeps the byte-oriented lanes.
+    let mut input = GERMAN.as_bytes().to_vec();
+    input.push(0xFF);
+    let (artifact, _) = encode(&input, true);
+    assert_eq!(lane::k8l1_text_flags(&artifact).unwrap() & TEXT_FLAG_UTF8, 0);
+    assert_eq!(
+        lane::decode_k8l1(&artifact).unwrap(),
+        text_norm::normalize_newlines(&input)
+    );
+
+    let (default_artifact, _) = lane::encode_k8l1(
+        FRENCH.as_bytes(),
+        &format::encode(&default_recipe()),
+        MAX_TICKS,
+        Some(PUNCT_ALPH),
+    )
+    .expect("encode");
+    assert_eq!(default_artifact, encode(FRENCH.as_bytes(), false).0);
+}
+
+#[test]
+fn utf8_aware_keeps_custom_punct_alphabet() {
+    let input = SPANISH.as_bytes();
+    let (artifact, stats) = lane::encode_k8l1_with_config(
+        input,
+        &format::encode(&default_recipe()),
+        MAX_TICKS,
+        OmegaProgram::default(),
+        Some(b".,;:?!"),
+        TextLanesV2Config {
+            utf8_aware: true,
+            ..Default::default()
+        },
+    )
+    .expect("encode");
+    assert_eq!(artifact[4], K8L1_VERSION_V5);
+    assert!(stats.n_punct > 0);
+    assert_eq!(lane::decode_k8l1(&artifact).unwrap(), input.to_vec());
+}
@@ -0,0 +1,45 @@
+// crates/k8dnz-core/tests/validate_deep.rs
+
+use k8dnz_core::recipe::defaults::default_recipe;
+use k8dnz_core::recipe::recipe::{KeystreamMix, PayloadKind};
+use k8dnz_core::validate::ValidationWarning;
+
+#[test]
+fn default_recipe_has_no_deep_warnings() {
+    assert!(default_recipe().validate_deep().is_empty());
+}
+
+#[test]
+fn deep_warnings_cover_each_check() {
+    let mut r = default_recipe();
+    r.version = 3;
+    r.quant.min = 0;
+    r.quant.max = 10;
+    r.quant.shift = -11;
+    r.field_clamp.min = 0;
+    r.field_clamp.max = 5;
+    r.keystream_mix = KeystreamMix::SplitMix64;
+    r.payload_kind = PayloadKind::CipherXor;
+
+    let w = r.validate_deep();
+    assert!(w.contains(&ValidationWarning::NarrowQuantRange { width: 10 }));
+    assert!(w.contains(&ValidationWarning::ClampNarrowerThanQuant {
+        clamp_width: 5,
+        quant_width: 10
+    }));
+    assert!(w.contains(&ValidationWarning::MixWithoutResidual {
+        mix: KeystreamMix::SplitMix64,
+        payload: PayloadKind::CipherXor
+    }));
+    assert!(w.contains(&ValidationWarning::ShiftOutOfRange { shift: -11, width: 10 }));
+    assert!(w.contains(&ValidationWarning::VersionMismatch { found: 3, newest: 7 }));
+    assert!(w
+        .iter()
+        .any(|x| x.to_string() == "recipe version=3 (this build writes v4..=v7)"));
+    assert_eq!(w.len(), 5);
+
+    // Shift exactly at the edge and mix with a residual payload are fine.
+    r.quant.shift = 10;
+    r.payload_kind = PayloadKind::ResidualXor;
+    assert_eq!(r.validate_deep().len(), 3);
+}
@@ -0,0 +1,64 @@
+// crates/k8dnz-core/tests/varint_stream.rs
+
+use std::io::{Cursor, ErrorKind};
+
+use k8dnz_core::lane;
+use k8dnz_core::recipe::{defaults::default_recipe, format};
+use k8dnz_core::symbol::varint;
+
+const SAMPLES: &[u64] = &[0, 1, 127, 128, 300, 16_383, 16_384, u32::MAX as u64, u64::MAX - 1, u64::MAX];
+
+#[test]
+fn write_u64_matches_put_u64() {
+    let mut a = Vec::new();
+    let mut b = Vec::new();
+    for &v in SAMPLES {
+        varint::put_u64(v, &mut a);
+        varint::write_u64(&mut b, v).unwrap();
+    }
+    assert_eq!(a, b);
+
+    let mut r = Cursor::new(&b);
+    for &v in SAMPLES {
+        assert_eq!(varint::read_u64(&mut r).unwrap(), v);
+    }
+    assert_eq!(
+        varint::read_u64(&mut r).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn read_u64_rejects_overlong() {
+    let bytes = [0xFFu8; 11];
+    let err = varint::read_u64(&mut Cursor::new(&bytes[..])).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn k8l1_writer_matches_in_memory_encoder() {
+    let input = b"In the beginning God created the heaven and the earth. 1234 5678!\n".repeat(4);
+    let recipe_bytes = format::encode(&default_recipe());
+
+    let (artifact, stats) = lane::encode_k8l1_with_omega_prog(
+        &input,
+        &recipe_bytes,
+        20_000_000,
+        lane::OmegaProgram::default(),
+    )
+    .unwrap();
+
+    let mut streamed = Vec::new();
+    let wstats = lane::write_k8l1_with_omega_prog(
+        &mut streamed,
+        &input,
+        &recipe_bytes,
+        20_000_000,
+        lane::OmegaProgram::default(),
+    )
+    .unwrap();
+
+    assert_eq!(streamed, artifact);
+    assert_eq!(wstats.artifact_bytes, stats.artifact_bytes);
+    assert_eq!(lane::decode_k8l1(&streamed).unwrap(), input);
+}